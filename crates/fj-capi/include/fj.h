@@ -0,0 +1,112 @@
+/*
+ * Fornjot C API
+ *
+ * See the documentation of the `fj-capi` crate for details. Every pointer that
+ * a function returns must be passed to the matching `*_free` function once.
+ * Objects keep the core instance they were created in alive, so they can be
+ * freed in any order.
+ */
+
+#ifndef FJ_H
+#define FJ_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The result of a fallible function */
+typedef enum FjStatus {
+    FJ_STATUS_OK = 0,
+    FJ_STATUS_INVALID_ARGUMENT = 1,
+    FJ_STATUS_CORE_MISMATCH = 2,
+    FJ_STATUS_VALIDATION = 3,
+    FJ_STATUS_TRIANGULATION = 4,
+    FJ_STATUS_EXPORT = 5,
+    FJ_STATUS_PANIC = 6,
+} FjStatus;
+
+typedef struct FjCore FjCore;
+typedef struct FjSketch FjSketch;
+typedef struct FjSolid FjSolid;
+typedef struct FjMesh FjMesh;
+
+/*
+ * Message that describes the last error on the current thread, or null. Stays
+ * valid until the next function fails on the current thread.
+ */
+const char *fj_last_error(void);
+
+FjCore *fj_core_new(void);
+void fj_core_free(FjCore *core);
+FjStatus fj_core_validate(const FjCore *core);
+
+/* `points` contains `num_points` pairs of x and y coordinates. */
+FjStatus fj_sketch_polygon(
+    const FjCore *core,
+    const double *points,
+    size_t num_points,
+    FjSketch **out
+);
+FjStatus fj_sketch_circle(
+    const FjCore *core,
+    double x,
+    double y,
+    double radius,
+    FjSketch **out
+);
+FjStatus fj_sketch_merge(
+    const FjSketch *a,
+    const FjSketch *b,
+    FjSketch **out
+);
+FjStatus fj_sketch_sweep(
+    const FjSketch *sketch,
+    double x,
+    double y,
+    double z,
+    FjSolid **out
+);
+void fj_sketch_free(FjSketch *sketch);
+
+FjStatus fj_solid_translate(
+    const FjSolid *solid,
+    double x,
+    double y,
+    double z,
+    FjSolid **out
+);
+/* The length of the axis is the angle of rotation, in radians. */
+FjStatus fj_solid_rotate(
+    const FjSolid *solid,
+    double x,
+    double y,
+    double z,
+    FjSolid **out
+);
+FjStatus fj_solid_merge(const FjSolid *a, const FjSolid *b, FjSolid **out);
+FjStatus fj_solid_triangulate(
+    const FjSolid *solid,
+    double tolerance,
+    FjMesh **out
+);
+void fj_solid_free(FjSolid *solid);
+
+/* Vertices are triples of x, y, and z coordinates. */
+size_t fj_mesh_num_vertices(const FjMesh *mesh);
+const double *fj_mesh_vertices(const FjMesh *mesh);
+
+/* Triangles are triples of vertex indices. */
+size_t fj_mesh_num_triangles(const FjMesh *mesh);
+const uint32_t *fj_mesh_triangles(const FjMesh *mesh);
+
+FjStatus fj_mesh_export(const FjMesh *mesh, const char *path);
+void fj_mesh_free(FjMesh *mesh);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FJ_H */
@@ -0,0 +1,59 @@
+use fj_core::Core;
+
+use crate::{
+    error::FjStatus,
+    ffi::{arg, call, free, into_raw, lock, SharedCore},
+};
+
+/// An instance of the Fornjot core
+///
+/// All objects are created in a core instance. See [`fj_core_new`].
+pub struct FjCore {
+    pub(crate) core: SharedCore,
+}
+
+/// Create a core instance
+///
+/// The returned pointer must be freed with [`fj_core_free`].
+#[no_mangle]
+pub extern "C" fn fj_core_new() -> *mut FjCore {
+    into_raw(FjCore {
+        core: SharedCore::new(Core::new()),
+    })
+}
+
+/// Free a core instance
+///
+/// Objects that were created in the core instance keep it alive, until they
+/// are freed too. Does nothing, if `core` is null.
+///
+/// # Safety
+///
+/// `core` must be null, or have been returned by [`fj_core_new`] and not been
+/// freed already.
+#[no_mangle]
+pub unsafe extern "C" fn fj_core_free(core: *mut FjCore) {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { free(core) }
+}
+
+/// Check the objects that have been created in a core instance
+///
+/// Returns [`FjStatus::Validation`], if any object is not valid. The errors
+/// are reset afterwards, so calling this again only reports errors in objects
+/// that have been created since.
+///
+/// # Safety
+///
+/// `core` must be null, or point to a live core instance.
+#[no_mangle]
+pub unsafe extern "C" fn fj_core_validate(core: *const FjCore) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let core = unsafe { arg(core, "core") }?;
+
+        lock(&core.core)?.layers.validation.take_errors()?;
+
+        Ok(())
+    })
+}
@@ -0,0 +1,120 @@
+use std::{
+    cell::RefCell,
+    error::Error as _,
+    ffi::{c_char, CString},
+    ptr,
+};
+
+use fj_core::{
+    algorithms::{approx::InvalidTolerance, triangulate::TriangulateError},
+    validation::ValidationErrors,
+};
+
+/// The result of a fallible function
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FjStatus {
+    /// The function succeeded
+    Ok = 0,
+
+    /// An argument was null, or otherwise invalid
+    InvalidArgument = 1,
+
+    /// Objects from different core instances were combined
+    CoreMismatch = 2,
+
+    /// The objects in a core instance are not valid
+    Validation = 3,
+
+    /// A face failed to triangulate
+    Triangulation = 4,
+
+    /// A mesh failed to export
+    Export = 5,
+
+    /// The kernel panicked
+    ///
+    /// The core instance that was used can't be used anymore.
+    Panic = 6,
+}
+
+/// Access a message that describes the last error
+///
+/// Returns the message of the last function that failed on the current thread,
+/// or null, if none has. The message stays valid until the next function fails
+/// on the current thread.
+#[no_mangle]
+pub extern "C" fn fj_last_error() -> *const c_char {
+    LAST_ERROR.with_borrow(|message| {
+        message
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+pub fn set_last_error(message: String) {
+    // A message with a null byte in it would be cut short by C code anyway.
+    let message = message.replace('\0', "");
+    let message = CString::new(message).expect("Removed all null bytes");
+
+    LAST_ERROR.set(Some(message));
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("`{0}` must not be null")]
+    Null(&'static str),
+
+    #[error("{0}")]
+    InvalidArgument(String),
+
+    #[error(transparent)]
+    Tolerance(#[from] InvalidTolerance),
+
+    #[error("Objects belong to different core instances")]
+    CoreMismatch,
+
+    #[error("Core instance is unusable, after an earlier call panicked")]
+    Poisoned,
+
+    #[error(transparent)]
+    Validation(#[from] ValidationErrors),
+
+    #[error("Failed to triangulate face")]
+    Triangulation(#[from] TriangulateError),
+
+    #[error("Error exporting mesh")]
+    Export(#[from] fj_export::Error),
+}
+
+impl Error {
+    pub fn status(&self) -> FjStatus {
+        match self {
+            Self::Null(_) | Self::InvalidArgument(_) | Self::Tolerance(_) => {
+                FjStatus::InvalidArgument
+            }
+            Self::CoreMismatch => FjStatus::CoreMismatch,
+            Self::Poisoned => FjStatus::Panic,
+            Self::Validation(_) => FjStatus::Validation,
+            Self::Triangulation(_) => FjStatus::Triangulation,
+            Self::Export(_) => FjStatus::Export,
+        }
+    }
+
+    /// Describe the error, including all of its sources
+    pub fn report(&self) -> String {
+        let mut report = self.to_string();
+
+        let mut source = self.source();
+        while let Some(s) = source {
+            report.push_str(&format!(": {s}"));
+            source = s.source();
+        }
+
+        report
+    }
+}
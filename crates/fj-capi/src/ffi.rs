@@ -0,0 +1,159 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    slice,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use fj_core::Core;
+
+use crate::error::{set_last_error, Error, FjStatus};
+
+/// A core instance that is shared by all objects created in it
+///
+/// Validation errors that haven't been checked with [`fj_core_validate`], are
+/// discarded when the last object that shares the instance is freed. Dropping
+/// a `Core` with unhandled validation errors panics otherwise, and that panic
+/// would unwind into C code.
+///
+/// [`fj_core_validate`]: crate::fj_core_validate
+#[derive(Clone)]
+pub struct SharedCore(Arc<Mutex<Core>>);
+
+impl SharedCore {
+    pub fn new(core: Core) -> Self {
+        Self(Arc::new(Mutex::new(core)))
+    }
+}
+
+impl Drop for SharedCore {
+    fn drop(&mut self) {
+        // Nothing else can acquire a reference, while this is the last one.
+        if Arc::strong_count(&self.0) == 1 {
+            let mut core = match self.0.lock() {
+                Ok(core) => core,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let _ = core.layers.validation.take_errors();
+        }
+    }
+}
+
+/// Call a fallible function on behalf of C code
+///
+/// Unwinding into C code is undefined behavior, so panics are caught and
+/// reported like any other error.
+pub fn call(f: impl FnOnce() -> Result<(), Error>) -> FjStatus {
+    let (status, message) = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return FjStatus::Ok,
+        Ok(Err(err)) => (err.status(), err.report()),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Unknown panic".to_string());
+
+            (FjStatus::Panic, format!("Kernel panicked: {message}"))
+        }
+    };
+
+    set_last_error(message);
+    status
+}
+
+pub fn lock(core: &SharedCore) -> Result<MutexGuard<Core>, Error> {
+    core.0.lock().map_err(|_| Error::Poisoned)
+}
+
+pub fn same_core(a: &SharedCore, b: &SharedCore) -> Result<(), Error> {
+    if !Arc::ptr_eq(&a.0, &b.0) {
+        return Err(Error::CoreMismatch);
+    }
+
+    Ok(())
+}
+
+/// Make sure that numbers are finite, before they're passed to the kernel
+///
+/// The kernel panics on NaN, which would leave the core instance unusable.
+pub fn finite<const N: usize>(values: [f64; N]) -> Result<[f64; N], Error> {
+    if values.iter().any(|value| !value.is_finite()) {
+        return Err(Error::InvalidArgument(
+            "Numbers must be finite".to_string(),
+        ));
+    }
+
+    Ok(values)
+}
+
+/// Access an object that C code passed by pointer
+///
+/// # Safety
+///
+/// The pointer must be null, or point to a valid instance of `T`, which is not
+/// mutated while the returned reference exists.
+pub unsafe fn arg<'a, T>(
+    ptr: *const T,
+    name: &'static str,
+) -> Result<&'a T, Error> {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { ptr.as_ref() }.ok_or(Error::Null(name))
+}
+
+/// Access an array that C code passed by pointer
+///
+/// # Safety
+///
+/// The pointer must be null, or point to `len` valid instances of `T`, which
+/// are not mutated while the returned slice exists.
+pub unsafe fn array<'a, T>(
+    ptr: *const T,
+    len: usize,
+    name: &'static str,
+) -> Result<&'a [T], Error> {
+    if ptr.is_null() {
+        return Err(Error::Null(name));
+    }
+
+    // Sound, as per the requirements in this function's documentation.
+    Ok(unsafe { slice::from_raw_parts(ptr, len) })
+}
+
+/// Access a pointer that C code passed, to receive a result
+///
+/// # Safety
+///
+/// The pointer must be null, or be valid for writes.
+pub unsafe fn out_arg<'a, T>(
+    ptr: *mut *mut T,
+) -> Result<&'a mut *mut T, Error> {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { ptr.as_mut() }.ok_or(Error::Null("out"))
+}
+
+/// Transfer ownership of an object to C code
+pub fn into_raw<T>(object: T) -> *mut T {
+    Box::into_raw(Box::new(object))
+}
+
+/// Take back ownership of an object from C code, and drop it
+///
+/// Like [`call`], this catches panics, so they don't unwind into C code. There
+/// is no status to report them with, so they are only recorded as the last
+/// error.
+///
+/// # Safety
+///
+/// The pointer must be null, or have been returned by [`into_raw`] and not
+/// been freed already.
+pub unsafe fn free<T>(ptr: *mut T) {
+    if !ptr.is_null() {
+        // Sound, as per the requirements in this function's documentation.
+        let object = unsafe { Box::from_raw(ptr) };
+
+        let _ = call(|| {
+            drop(object);
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,54 @@
+//! # Fornjot C API
+//!
+//! [Fornjot] is an early-stage b-rep CAD kernel written in Rust. The kernel is
+//! split into multiple libraries that can be used semi-independently, and this
+//! is one of those.
+//!
+//! This library provides a C interface to model building, triangulation, and
+//! export, for embedding the kernel in applications that are not written in
+//! Rust. The declarations are in `include/fj.h`.
+//!
+//! ## Handles
+//!
+//! Objects are accessed through opaque pointers, like [`FjSketch`] and
+//! [`FjSolid`]. Like [`Handle`], which they wrap, they keep what they
+//! reference alive, including the core instance that they were created in.
+//! Every pointer that a function returns must be passed to the matching
+//! `*_free` function once, but they can be freed in any order.
+//!
+//! Objects from different core instances can't be combined. Functions detect
+//! that, and return an error.
+//!
+//! ## Errors
+//!
+//! Fallible functions return an [`FjStatus`], and write their result to an out
+//! pointer, if they succeed. If they fail, [`fj_last_error`] returns a message
+//! that describes the error. Panics are caught at the API boundary, and
+//! reported as an error too.
+//!
+//! [Fornjot]: https://www.fornjot.app/
+//! [`Handle`]: fj_core::storage::Handle
+
+mod core;
+mod error;
+mod ffi;
+mod mesh;
+mod sketch;
+mod solid;
+
+pub use self::{
+    core::{fj_core_free, fj_core_new, fj_core_validate, FjCore},
+    error::{fj_last_error, FjStatus},
+    mesh::{
+        fj_mesh_export, fj_mesh_free, fj_mesh_num_triangles,
+        fj_mesh_num_vertices, fj_mesh_triangles, fj_mesh_vertices, FjMesh,
+    },
+    sketch::{
+        fj_sketch_circle, fj_sketch_free, fj_sketch_merge, fj_sketch_polygon,
+        fj_sketch_sweep, FjSketch,
+    },
+    solid::{
+        fj_solid_free, fj_solid_merge, fj_solid_rotate, fj_solid_translate,
+        fj_solid_triangulate, FjSolid,
+    },
+};
@@ -0,0 +1,145 @@
+use std::{
+    ffi::{c_char, CStr},
+    path::Path,
+    ptr,
+};
+
+use fj_interop::Mesh;
+use fj_math::Point;
+
+use crate::{
+    error::{Error, FjStatus},
+    ffi::{arg, call, free},
+};
+
+/// A triangle mesh
+///
+/// See [`fj_solid_triangulate`].
+///
+/// [`fj_solid_triangulate`]: crate::fj_solid_triangulate
+pub struct FjMesh {
+    mesh: Mesh<Point<3>>,
+    vertices: Vec<f64>,
+    triangles: Vec<u32>,
+}
+
+impl FjMesh {
+    pub(crate) fn new(mesh: Mesh<Point<3>>) -> Self {
+        let vertices = mesh
+            .vertices()
+            .flat_map(|vertex| vertex.coords.components)
+            .map(|coord| coord.into_f64())
+            .collect();
+        let triangles = mesh.indices().collect();
+
+        Self {
+            mesh,
+            vertices,
+            triangles,
+        }
+    }
+}
+
+/// Access the number of vertices in a mesh
+///
+/// Returns zero, if `mesh` is null.
+///
+/// # Safety
+///
+/// `mesh` must be null, or point to a live mesh.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_num_vertices(mesh: *const FjMesh) -> usize {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { mesh.as_ref() }.map_or(0, |mesh| mesh.vertices.len() / 3)
+}
+
+/// Access the vertices of a mesh
+///
+/// Returns the x, y, and z coordinates of each vertex, one after the other.
+/// The returned pointer is valid for as long as the mesh is. Returns null, if
+/// `mesh` is null.
+///
+/// # Safety
+///
+/// `mesh` must be null, or point to a live mesh.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_vertices(mesh: *const FjMesh) -> *const f64 {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { mesh.as_ref() }.map_or(ptr::null(), |mesh| mesh.vertices.as_ptr())
+}
+
+/// Access the number of triangles in a mesh
+///
+/// Returns zero, if `mesh` is null.
+///
+/// # Safety
+///
+/// `mesh` must be null, or point to a live mesh.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_num_triangles(mesh: *const FjMesh) -> usize {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { mesh.as_ref() }.map_or(0, |mesh| mesh.triangles.len() / 3)
+}
+
+/// Access the triangles of a mesh
+///
+/// Returns the indices of the three vertices of each triangle, one after the
+/// other. The returned pointer is valid for as long as the mesh is. Returns
+/// null, if `mesh` is null.
+///
+/// # Safety
+///
+/// `mesh` must be null, or point to a live mesh.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_triangles(mesh: *const FjMesh) -> *const u32 {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { mesh.as_ref() }.map_or(ptr::null(), |mesh| mesh.triangles.as_ptr())
+}
+
+/// Export a mesh to a file
+///
+/// The format is selected by the extension of `path`. The mesh is assumed to
+/// be measured in millimeters.
+///
+/// # Safety
+///
+/// `mesh` must be null, or point to a live mesh. `path` must be null, or point
+/// to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_export(
+    mesh: *const FjMesh,
+    path: *const c_char,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let mesh = unsafe { arg(mesh, "mesh") }?;
+        if path.is_null() {
+            return Err(Error::Null("path"));
+        }
+        // Sound, as per the requirements in this function's documentation.
+        let path = unsafe { CStr::from_ptr(path) };
+
+        let path = path.to_str().map_err(|_| {
+            Error::InvalidArgument("`path` must be valid UTF-8".to_string())
+        })?;
+        fj_export::export(&mesh.mesh, Path::new(path))?;
+
+        Ok(())
+    })
+}
+
+/// Free a mesh
+///
+/// Does nothing, if `mesh` is null.
+///
+/// # Safety
+///
+/// `mesh` must be null, or have been returned by [`fj_solid_triangulate`],
+/// and not been freed already.
+///
+/// [`fj_solid_triangulate`]: crate::fj_solid_triangulate
+#[no_mangle]
+pub unsafe extern "C" fn fj_mesh_free(mesh: *mut FjMesh) {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { free(mesh) }
+}
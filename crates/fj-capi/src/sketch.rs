@@ -0,0 +1,208 @@
+use fj_core::{
+    operations::{
+        build::{BuildRegion, BuildSketch},
+        sweep::SweepSketch,
+        update::UpdateSketch,
+    },
+    topology::{Region, Sketch},
+    Core,
+};
+
+use crate::{
+    core::FjCore,
+    error::{Error, FjStatus},
+    ffi::{
+        arg, array, call, finite, free, into_raw, lock, out_arg, same_core,
+        SharedCore,
+    },
+    solid::FjSolid,
+};
+
+/// A sketch, made up of regions in 2D space
+///
+/// See [`fj_sketch_polygon`] and [`fj_sketch_circle`].
+pub struct FjSketch {
+    pub(crate) core: SharedCore,
+    pub(crate) sketch: Sketch,
+}
+
+/// Create a sketch that contains a polygon
+///
+/// `points` contains the x and y coordinates of each point, one after the
+/// other. A polygon needs at least 3 points.
+///
+/// # Safety
+///
+/// `core` must be null, or point to a live core instance. `points` must be
+/// null, or point to `num_points * 2` numbers. `out` must be null, or be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_sketch_polygon(
+    core: *const FjCore,
+    points: *const f64,
+    num_points: usize,
+    out: *mut *mut FjSketch,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let (core, points, out) = unsafe {
+            (
+                arg(core, "core")?,
+                array(points, num_points * 2, "points")?,
+                out_arg(out)?,
+            )
+        };
+
+        if num_points < 3 {
+            return Err(Error::InvalidArgument(
+                "A polygon needs at least 3 points".to_string(),
+            ));
+        }
+        let points = points
+            .chunks_exact(2)
+            .map(|point| finite([point[0], point[1]]))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let sketch = {
+            let core = &mut *lock(&core.core)?;
+            let surface = core.layers.topology.surfaces.space_2d();
+            let region = Region::polygon(points, surface, core);
+            sketch_with_region(region, core)
+        };
+
+        *out = into_raw(FjSketch {
+            core: core.core.clone(),
+            sketch,
+        });
+
+        Ok(())
+    })
+}
+
+/// Create a sketch that contains a circle
+///
+/// # Safety
+///
+/// `core` must be null, or point to a live core instance. `out` must be null,
+/// or be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_sketch_circle(
+    core: *const FjCore,
+    x: f64,
+    y: f64,
+    radius: f64,
+    out: *mut *mut FjSketch,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let (core, out) = unsafe { (arg(core, "core")?, out_arg(out)?) };
+
+        let [x, y, radius] = finite([x, y, radius])?;
+        if radius <= 0. {
+            return Err(Error::InvalidArgument(
+                "The radius of a circle must be positive".to_string(),
+            ));
+        }
+
+        let sketch = {
+            let core = &mut *lock(&core.core)?;
+            let surface = core.layers.topology.surfaces.space_2d();
+            let region = Region::circle([x, y], radius, surface, core);
+            sketch_with_region(region, core)
+        };
+
+        *out = into_raw(FjSketch {
+            core: core.core.clone(),
+            sketch,
+        });
+
+        Ok(())
+    })
+}
+
+/// Create a sketch that contains the regions of two sketches
+///
+/// The regions are not combined, so they should not overlap.
+///
+/// # Safety
+///
+/// `a` and `b` must be null, or point to live sketches. `out` must be null, or
+/// be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_sketch_merge(
+    a: *const FjSketch,
+    b: *const FjSketch,
+    out: *mut *mut FjSketch,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let (a, b, out) =
+            unsafe { (arg(a, "a")?, arg(b, "b")?, out_arg(out)?) };
+        same_core(&a.core, &b.core)?;
+
+        let sketch = a.sketch.add_regions(
+            b.sketch.regions().iter().cloned(),
+            &mut *lock(&a.core)?,
+        );
+
+        *out = into_raw(FjSketch {
+            core: a.core.clone(),
+            sketch,
+        });
+
+        Ok(())
+    })
+}
+
+/// Sweep a sketch along a path, starting from the xy-plane, to create a solid
+///
+/// # Safety
+///
+/// `sketch` must be null, or point to a live sketch. `out` must be null, or be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_sketch_sweep(
+    sketch: *const FjSketch,
+    x: f64,
+    y: f64,
+    z: f64,
+    out: *mut *mut FjSolid,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let (sketch, out) = unsafe { (arg(sketch, "sketch")?, out_arg(out)?) };
+
+        let path = finite([x, y, z])?;
+
+        let solid = {
+            let core = &mut *lock(&sketch.core)?;
+            let surface = core.layers.topology.surfaces.xy_plane();
+            sketch.sketch.sweep_sketch(surface, path, core)
+        };
+
+        *out = into_raw(FjSolid {
+            core: sketch.core.clone(),
+            solid,
+        });
+
+        Ok(())
+    })
+}
+
+/// Free a sketch
+///
+/// Does nothing, if `sketch` is null.
+///
+/// # Safety
+///
+/// `sketch` must be null, or have been returned by a function of this library,
+/// and not been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn fj_sketch_free(sketch: *mut FjSketch) {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { free(sketch) }
+}
+
+fn sketch_with_region(region: Region, core: &mut Core) -> Sketch {
+    Sketch::empty(&core.layers.topology).add_regions([region], core)
+}
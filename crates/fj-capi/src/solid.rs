@@ -0,0 +1,308 @@
+use fj_core::{
+    algorithms::{approx::Tolerance, triangulate::Triangulate},
+    operations::{merge::Merge, transform::TransformObject},
+    topology::Solid,
+};
+
+use crate::{
+    error::FjStatus,
+    ffi::{
+        arg, call, finite, free, into_raw, lock, out_arg, same_core, SharedCore,
+    },
+    mesh::FjMesh,
+};
+
+/// A solid, made up of shells in 3D space
+///
+/// See [`fj_sketch_sweep`].
+///
+/// [`fj_sketch_sweep`]: crate::fj_sketch_sweep
+pub struct FjSolid {
+    pub(crate) core: SharedCore,
+    pub(crate) solid: Solid,
+}
+
+/// Create a translated copy of a solid
+///
+/// # Safety
+///
+/// `solid` must be null, or point to a live solid. `out` must be null, or be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_solid_translate(
+    solid: *const FjSolid,
+    x: f64,
+    y: f64,
+    z: f64,
+    out: *mut *mut FjSolid,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let (solid, out) = unsafe { (arg(solid, "solid")?, out_arg(out)?) };
+
+        let offset = finite([x, y, z])?;
+        let translated =
+            solid.solid.translate(offset, &mut *lock(&solid.core)?);
+
+        *out = into_raw(FjSolid {
+            core: solid.core.clone(),
+            solid: translated,
+        });
+
+        Ok(())
+    })
+}
+
+/// Create a rotated copy of a solid
+///
+/// The solid is rotated around the origin. The rotation is defined by an axis,
+/// whose length is the angle of rotation, in radians.
+///
+/// # Safety
+///
+/// `solid` must be null, or point to a live solid. `out` must be null, or be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_solid_rotate(
+    solid: *const FjSolid,
+    x: f64,
+    y: f64,
+    z: f64,
+    out: *mut *mut FjSolid,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let (solid, out) = unsafe { (arg(solid, "solid")?, out_arg(out)?) };
+
+        let axis_angle = finite([x, y, z])?;
+        let rotated = solid.solid.rotate(axis_angle, &mut *lock(&solid.core)?);
+
+        *out = into_raw(FjSolid {
+            core: solid.core.clone(),
+            solid: rotated,
+        });
+
+        Ok(())
+    })
+}
+
+/// Create a solid that contains the shells of two solids
+///
+/// This does not compute the union of the solids, so they should not overlap.
+///
+/// # Safety
+///
+/// `a` and `b` must be null, or point to live solids. `out` must be null, or be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_solid_merge(
+    a: *const FjSolid,
+    b: *const FjSolid,
+    out: *mut *mut FjSolid,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let (a, b, out) =
+            unsafe { (arg(a, "a")?, arg(b, "b")?, out_arg(out)?) };
+        same_core(&a.core, &b.core)?;
+
+        let solid = a.solid.merge(&b.solid, &mut *lock(&a.core)?);
+
+        *out = into_raw(FjSolid {
+            core: a.core.clone(),
+            solid,
+        });
+
+        Ok(())
+    })
+}
+
+/// Triangulate a solid
+///
+/// `tolerance` defines how much the mesh can deviate from the solid, and must
+/// be positive. Fails, if any face fails to triangulate.
+///
+/// # Safety
+///
+/// `solid` must be null, or point to a live solid. `out` must be null, or be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fj_solid_triangulate(
+    solid: *const FjSolid,
+    tolerance: f64,
+    out: *mut *mut FjMesh,
+) -> FjStatus {
+    call(|| {
+        // Sound, as per the requirements in this function's documentation.
+        let (solid, out) = unsafe { (arg(solid, "solid")?, out_arg(out)?) };
+
+        let [tolerance] = finite([tolerance])?;
+        let tolerance = Tolerance::from_scalar(tolerance)?;
+
+        let triangulation = (&solid.solid, tolerance)
+            .triangulate_partial(&mut *lock(&solid.core)?);
+        if let Some(failed) = triangulation.failed_faces.into_iter().next() {
+            return Err(failed.error.into());
+        }
+
+        *out = into_raw(FjMesh::new(triangulation.mesh));
+
+        Ok(())
+    })
+}
+
+/// Free a solid
+///
+/// Does nothing, if `solid` is null.
+///
+/// # Safety
+///
+/// `solid` must be null, or have been returned by a function of this library,
+/// and not been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn fj_solid_free(solid: *mut FjSolid) {
+    // Sound, as per the requirements in this function's documentation.
+    unsafe { free(solid) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CStr, ptr};
+
+    use crate::{
+        fj_core_free, fj_core_new, fj_core_validate, fj_last_error,
+        fj_mesh_free, fj_mesh_num_triangles, fj_mesh_num_vertices,
+        fj_mesh_triangles, fj_mesh_vertices, fj_sketch_circle, fj_sketch_free,
+        fj_sketch_polygon, fj_sketch_sweep, fj_solid_free, fj_solid_merge,
+        fj_solid_translate, fj_solid_triangulate, FjStatus,
+    };
+
+    #[test]
+    fn build_and_triangulate_cuboid() {
+        unsafe {
+            let core = fj_core_new();
+
+            let points = [0., 0., 1., 0., 1., 1., 0., 1.];
+            let mut sketch = ptr::null_mut();
+            let status =
+                fj_sketch_polygon(core, points.as_ptr(), 4, &mut sketch);
+            assert_eq!(status, FjStatus::Ok);
+
+            let mut cuboid = ptr::null_mut();
+            let status = fj_sketch_sweep(sketch, 0., 0., 1., &mut cuboid);
+            assert_eq!(status, FjStatus::Ok);
+
+            let mut translated = ptr::null_mut();
+            let status =
+                fj_solid_translate(cuboid, 2., 0., 0., &mut translated);
+            assert_eq!(status, FjStatus::Ok);
+
+            let mut merged = ptr::null_mut();
+            let status = fj_solid_merge(cuboid, translated, &mut merged);
+            assert_eq!(status, FjStatus::Ok);
+
+            // Objects keep their core alive.
+            fj_core_free(core);
+            fj_sketch_free(sketch);
+            fj_solid_free(cuboid);
+            fj_solid_free(translated);
+
+            let mut mesh = ptr::null_mut();
+            let status = fj_solid_triangulate(merged, 0.01, &mut mesh);
+            assert_eq!(status, FjStatus::Ok);
+            fj_solid_free(merged);
+
+            assert_eq!(fj_mesh_num_triangles(mesh), 24);
+            assert_eq!(fj_mesh_num_vertices(mesh), 16);
+
+            let vertices = std::slice::from_raw_parts(
+                fj_mesh_vertices(mesh),
+                fj_mesh_num_vertices(mesh) * 3,
+            );
+            assert!(vertices.iter().all(|&v| (0. ..=3.).contains(&v)));
+
+            let triangles = std::slice::from_raw_parts(
+                fj_mesh_triangles(mesh),
+                fj_mesh_num_triangles(mesh) * 3,
+            );
+            assert!(triangles.iter().all(|&index| index < 16));
+
+            fj_mesh_free(mesh);
+        }
+    }
+
+    #[test]
+    fn report_errors() {
+        unsafe {
+            let core = fj_core_new();
+            let other_core = fj_core_new();
+
+            let mut sketch = ptr::null_mut();
+            let status = fj_sketch_circle(core, 0., 0., -1., &mut sketch);
+            assert_eq!(status, FjStatus::InvalidArgument);
+            assert!(sketch.is_null());
+
+            let status = fj_sketch_circle(core, 0., 0., f64::NAN, &mut sketch);
+            assert_eq!(status, FjStatus::InvalidArgument);
+
+            let status = fj_sketch_circle(core, 0., 0., 1., ptr::null_mut());
+            assert_eq!(status, FjStatus::InvalidArgument);
+            let message = CStr::from_ptr(fj_last_error());
+            assert_eq!(message.to_str(), Ok("`out` must not be null"));
+
+            let mut a = ptr::null_mut();
+            let mut b = ptr::null_mut();
+            let mut sketch_a = ptr::null_mut();
+            let mut sketch_b = ptr::null_mut();
+            fj_sketch_circle(core, 0., 0., 1., &mut sketch_a);
+            fj_sketch_circle(other_core, 0., 0., 1., &mut sketch_b);
+            fj_sketch_sweep(sketch_a, 0., 0., 1., &mut a);
+            fj_sketch_sweep(sketch_b, 0., 0., 1., &mut b);
+
+            let mut merged = ptr::null_mut();
+            let status = fj_solid_merge(a, b, &mut merged);
+            assert_eq!(status, FjStatus::CoreMismatch);
+
+            let mut mesh = ptr::null_mut();
+            let status = fj_solid_triangulate(a, 0., &mut mesh);
+            assert_eq!(status, FjStatus::InvalidArgument);
+
+            // The errors didn't leave anything broken behind.
+            assert_eq!(fj_core_validate(core), FjStatus::Ok);
+            assert_eq!(fj_core_validate(other_core), FjStatus::Ok);
+
+            fj_solid_free(a);
+            fj_solid_free(b);
+            fj_sketch_free(sketch_a);
+            fj_sketch_free(sketch_b);
+            fj_core_free(core);
+            fj_core_free(other_core);
+        }
+    }
+
+    #[test]
+    fn free_core_with_invalid_object() {
+        unsafe {
+            let core = fj_core_new();
+
+            // The first edge is much shorter than the default tolerance, which
+            // results in half-edges of zero length.
+            let points = [0., 0., 1e-9, 0., 0., 1.];
+            let mut sketch = ptr::null_mut();
+            let status =
+                fj_sketch_polygon(core, points.as_ptr(), 3, &mut sketch);
+            assert_eq!(status, FjStatus::Ok);
+
+            let mut solid = ptr::null_mut();
+            let status = fj_sketch_sweep(sketch, 0., 0., 1., &mut solid);
+            assert_eq!(status, FjStatus::Ok);
+
+            // The core is never validated, so its errors are not handled.
+            // Freeing the last object that refers to it must not panic.
+            fj_core_free(core);
+            fj_sketch_free(sketch);
+            fj_solid_free(solid);
+            assert!(fj_last_error().is_null());
+        }
+    }
+}
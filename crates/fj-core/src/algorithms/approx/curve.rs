@@ -1,19 +1,26 @@
 //! Curve approximation
+//!
+//! By default, curves are approximated with as few points as the tolerance
+//! allows. Alternatively, the points can be spaced uniformly by arc length. See
+//! [`CurveSampling`].
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, iter};
 
-use fj_math::Point;
+use fj_math::{Point, Scalar, Vector};
 
 use crate::{
     geometry::{
         CurveBoundary, Geometry, GlobalPath, HalfEdgeGeom, SurfaceGeom,
         SurfacePath,
     },
-    storage::Handle,
+    storage::{Handle, ObjectId},
     topology::{Curve, Surface},
 };
 
-use super::{Approx, ApproxPoint, Tolerance};
+use super::{
+    path::{approx_circle_on_surface, approx_line_on_sphere_or_torus},
+    Approx, ApproxPoint, Tolerance,
+};
 
 impl Approx for (&Handle<Curve>, &HalfEdgeGeom, &Handle<Surface>) {
     type Approximation = CurveApprox;
@@ -27,23 +34,124 @@ impl Approx for (&Handle<Curve>, &HalfEdgeGeom, &Handle<Surface>) {
     ) -> Self::Approximation {
         let (curve, half_edge, surface) = self;
 
-        match cache.get(curve, half_edge.boundary) {
+        (curve, half_edge, surface, CurveSampling::default())
+            .approx_with_cache(tolerance, cache, geometry)
+    }
+}
+
+impl Approx
+    for (
+        &Handle<Curve>,
+        &HalfEdgeGeom,
+        &Handle<Surface>,
+        CurveSampling,
+    )
+{
+    type Approximation = CurveApprox;
+    type Cache = CurveApproxCache;
+
+    fn approx_with_cache(
+        self,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut Self::Cache,
+        geometry: &Geometry,
+    ) -> Self::Approximation {
+        let (curve, half_edge, surface, sampling) = self;
+        let tolerance = tolerance.into();
+        let surface = geometry.of_surface(surface);
+
+        // Half-edges on a periodic curve can have boundaries that are a whole
+        // number of periods apart, for example when they are on different
+        // sides of a seam. Caching the approximation under the wrapped
+        // boundary makes sure that those share their approximation, and with
+        // that, their points.
+        //
+        // The approximation itself is still computed from the original
+        // boundary. Computing it from the wrapped one would result in global
+        // points that differ slightly from those computed elsewhere.
+        let boundary = match surface.period_of_path(&half_edge.path) {
+            Some(period) => half_edge.boundary.wrap(period),
+            None => half_edge.boundary,
+        };
+        let offset = half_edge.boundary.inner[0] - boundary.inner[0];
+
+        let approx = match cache.get(curve, boundary, tolerance, sampling) {
             Some(approx) => approx,
             None => {
                 let approx = approx_curve(
                     &half_edge.path,
-                    geometry.of_surface(surface),
+                    surface,
                     half_edge.boundary,
                     tolerance,
                     geometry,
                 );
-
-                cache.insert(curve.clone(), half_edge.boundary, approx)
+                let approx = match sampling {
+                    CurveSampling::Tolerance => approx,
+                    CurveSampling::Segments(_)
+                    | CurveSampling::MaxSpacing(_) => resample_uniformly(
+                        approx,
+                        &half_edge.path,
+                        surface,
+                        half_edge.boundary,
+                        sampling,
+                    ),
+                };
+
+                cache.insert(
+                    curve.clone(),
+                    boundary,
+                    tolerance,
+                    sampling,
+                    shift_approx(approx, -offset),
+                )
             }
-        }
+        };
+
+        shift_approx(approx, offset)
+    }
+}
+
+fn shift_approx(approx: CurveApprox, offset: Vector<1>) -> CurveApprox {
+    if offset == Vector::from([0.]) {
+        return approx;
+    }
+
+    CurveApprox {
+        points: approx
+            .points
+            .into_iter()
+            .map(|point| {
+                ApproxPoint::new(point.local_form + offset, point.global_form)
+            })
+            .collect(),
     }
 }
 
+/// How the points of a curve approximation are spaced
+///
+/// Regardless of the sampling, the approximation never includes the points at
+/// the boundary of the curve.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum CurveSampling {
+    /// Use as few points as the tolerance allows
+    ///
+    /// Where these points end up depends on the type of curve. Lines don't
+    /// need any points at all.
+    #[default]
+    Tolerance,
+
+    /// Divide the curve into this many segments of equal arc length
+    ///
+    /// Zero segments are treated like one, resulting in an approximation
+    /// without any points.
+    Segments(u32),
+
+    /// Divide the curve into segments of equal arc length, at most this long
+    ///
+    /// The spacing must be positive.
+    MaxSpacing(Scalar),
+}
+
 fn approx_curve(
     path: &SurfacePath,
     surface: &SurfaceGeom,
@@ -58,9 +166,12 @@ fn approx_curve(
     // `GlobalPath` grow APIs that are better suited to implementing this code
     // in a more abstract way.
     let points = match (path, surface.u) {
-        (SurfacePath::Circle(_), GlobalPath::Circle(_)) => {
-            todo!(
-                "Approximating a circle on a curved surface not supported yet."
+        (SurfacePath::Circle(circle), GlobalPath::Circle(_)) => {
+            approx_circle_on_surface(
+                circle,
+                surface,
+                boundary,
+                tolerance.into(),
             )
         }
         (SurfacePath::Circle(_), GlobalPath::Line(_)) => {
@@ -90,12 +201,51 @@ fn approx_curve(
                 })
                 .collect()
         }
+        (SurfacePath::Line(line), GlobalPath::Circle(_))
+            if surface.is_curved_along_v()
+                && line.direction().v != Scalar::ZERO =>
+        {
+            approx_line_on_sphere_or_torus(
+                line,
+                surface,
+                boundary,
+                tolerance.into(),
+            )
+        }
         (SurfacePath::Line(line), _) => {
             let range_u =
                 CurveBoundary::from(boundary.inner.map(|point_curve| {
                     [path.point_from_path_coords(point_curve).u]
                 }));
 
+            // On a cone or sphere, the u-axis is scaled along the v-axis, and
+            // so is the deviation of its approximation. For lines along the
+            // u-axis, this results in the same points as approximating the
+            // scaled circle.
+            let scale = boundary
+                .inner
+                .map(|point_curve| {
+                    surface
+                        .scale_at(path.point_from_path_coords(point_curve).v)
+                        .abs()
+                })
+                .into_iter()
+                .fold(Scalar::ZERO, Scalar::max);
+            if scale == Scalar::ZERO {
+                // This line is at the apex of a cone or the pole of a sphere,
+                // where all of its points are the same.
+                return CurveApprox { points: Vec::new() };
+            }
+            let tolerance = tolerance.into();
+            let tolerance = {
+                let scaled = Tolerance::from(tolerance.inner() / scale);
+                if tolerance.is_nested() {
+                    scaled.nested()
+                } else {
+                    scaled
+                }
+            };
+
             let approx_u = (surface.u, range_u).approx_with_cache(
                 tolerance,
                 &mut (),
@@ -105,10 +255,11 @@ fn approx_curve(
             let mut points = Vec::new();
             for (u, _) in approx_u {
                 let t = (u.t - line.origin().u) / line.direction().u;
-                let point_surface = path.point_from_path_coords([t]);
+                let point_curve = Point::from([t]);
+                let point_surface = path.point_from_path_coords(point_curve);
                 let point_global =
                     surface.point_from_surface_coords(point_surface);
-                points.push((u, point_global));
+                points.push((point_curve, point_global));
             }
 
             points
@@ -124,6 +275,79 @@ fn approx_curve(
     CurveApprox { points }
 }
 
+/// Space the points of an approximation uniformly by arc length
+///
+/// The arc length is measured along the provided approximation, so its
+/// accuracy depends on the tolerance that approximation was created with. The
+/// new points are then computed on the actual curve.
+fn resample_uniformly(
+    approx: CurveApprox,
+    path: &SurfacePath,
+    surface: &SurfaceGeom,
+    boundary: CurveBoundary<Point<1>>,
+    sampling: CurveSampling,
+) -> CurveApprox {
+    let point_global = |point_curve: Point<1>| {
+        surface
+            .point_from_surface_coords(path.point_from_path_coords(point_curve))
+    };
+
+    let [start, end] = boundary.inner;
+    let polyline = iter::once(start)
+        .chain(approx.points.iter().map(|point| point.local_form))
+        .chain(iter::once(end))
+        .map(|point_curve| (point_curve, point_global(point_curve)))
+        .collect::<Vec<_>>();
+
+    let mut total_length = Scalar::ZERO;
+    let mut lengths = vec![total_length];
+    for segment in polyline.windows(2) {
+        let [(_, a), (_, b)] = [segment[0], segment[1]];
+        total_length += (b - a).magnitude();
+        lengths.push(total_length);
+    }
+
+    let num_segments = match sampling {
+        CurveSampling::Tolerance => {
+            unreachable!("Not resampling tolerance-based approximation")
+        }
+        CurveSampling::Segments(num_segments) => u64::from(num_segments),
+        CurveSampling::MaxSpacing(spacing) => {
+            assert!(
+                spacing > Scalar::ZERO,
+                "Spacing of curve approximation must be positive"
+            );
+            (total_length / spacing).ceil().into_u64()
+        }
+    };
+    let num_segments = Scalar::from_u64(num_segments.max(1));
+
+    if total_length == Scalar::ZERO {
+        return CurveApprox { points: Vec::new() };
+    }
+
+    let mut points = Vec::new();
+    let mut i = 0;
+
+    for n in 1..num_segments.into_u64() {
+        let length = total_length * Scalar::from_u64(n) / num_segments;
+
+        // The lengths are increasing, and the one we're looking for is
+        // between the first and the last one. This finds the segment it's on.
+        while lengths[i + 1] < length {
+            i += 1;
+        }
+
+        let [(a, _), (b, _)] = [polyline[i], polyline[i + 1]];
+        let fraction = (length - lengths[i]) / (lengths[i + 1] - lengths[i]);
+        let point_curve = a + (b - a) * fraction;
+
+        points.push(ApproxPoint::new(point_curve, point_global(point_curve)));
+    }
+
+    CurveApprox { points }
+}
+
 /// Approximation of [`Curve`], within a specific boundary
 #[derive(Clone)]
 pub struct CurveApprox {
@@ -139,23 +363,41 @@ impl CurveApprox {
 }
 
 /// Cache for curve approximations
-#[derive(Default)]
+///
+/// Approximations are cached per tolerance and sampling, so the same cache can
+/// be used for different approximations of a curve, without mixing them up.
+#[derive(Clone, Default)]
 pub struct CurveApproxCache {
-    inner: BTreeMap<(Handle<Curve>, CurveBoundary<Point<1>>), CurveApprox>,
+    inner: BTreeMap<CurveApproxCacheKey, CurveApprox>,
 }
 
+type CurveApproxCacheKey = (
+    Handle<Curve>,
+    CurveBoundary<Point<1>>,
+    Tolerance,
+    CurveSampling,
+);
+
 impl CurveApproxCache {
     fn get(
         &self,
         handle: &Handle<Curve>,
         boundary: CurveBoundary<Point<1>>,
+        tolerance: Tolerance,
+        sampling: CurveSampling,
     ) -> Option<CurveApprox> {
-        if let Some(approx) = self.inner.get(&(handle.clone(), boundary)) {
-            return Some(approx.clone());
-        }
         if let Some(approx) =
-            self.inner.get(&(handle.clone(), boundary.reverse()))
+            self.inner
+                .get(&(handle.clone(), boundary, tolerance, sampling))
         {
+            return Some(approx.clone());
+        }
+        if let Some(approx) = self.inner.get(&(
+            handle.clone(),
+            boundary.reverse(),
+            tolerance,
+            sampling,
+        )) {
             return Some(approx.clone().reverse());
         }
 
@@ -166,12 +408,18 @@ impl CurveApproxCache {
         &mut self,
         handle: Handle<Curve>,
         boundary: CurveBoundary<Point<1>>,
+        tolerance: Tolerance,
+        sampling: CurveSampling,
         approx: CurveApprox,
     ) -> CurveApprox {
         self.inner
-            .insert((handle, boundary), approx.clone())
+            .insert((handle, boundary, tolerance, sampling), approx.clone())
             .unwrap_or(approx)
     }
+
+    pub(crate) fn retain_objects(&mut self, keep: impl Fn(ObjectId) -> bool) {
+        self.inner.retain(|(curve, ..), _| keep(curve.id()));
+    }
 }
 
 #[cfg(test)]
@@ -180,6 +428,8 @@ mod tests {
 
     use pretty_assertions::assert_eq;
 
+    use fj_math::{Circle, Point, Scalar};
+
     use crate::{
         algorithms::approx::{Approx, ApproxPoint},
         geometry::{CurveBoundary, GlobalPath, HalfEdgeGeom, SurfacePath},
@@ -188,6 +438,8 @@ mod tests {
         Core,
     };
 
+    use super::{CurveApproxCache, CurveSampling};
+
     #[test]
     fn approx_line_on_flat_surface() {
         let mut core = Core::new();
@@ -265,6 +517,47 @@ mod tests {
         assert_eq!(approx.points, expected_approx);
     }
 
+    #[test]
+    fn approx_line_on_cone_along_curve() {
+        let mut core = Core::new();
+
+        // At `v = 1`, this cone has a radius of 2.
+        let surface = Surface::cone(
+            Circle::from_center_and_radius([0., 0., 0.], 1.),
+            [0., 0., 1.],
+            2.,
+            &mut core,
+        );
+        let path = SurfacePath::line_from_points_with_coords([
+            ([0.], [0., 1.]),
+            ([TAU], [TAU, 1.]),
+        ]);
+        let curve =
+            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let boundary = CurveBoundary::from([[0.], [TAU]]);
+        let half_edge = HalfEdgeGeom { path, boundary };
+
+        let tolerance = 0.1;
+        let approx = (&curve, &half_edge, &surface)
+            .approx(tolerance, &core.layers.geometry);
+
+        // The points must match those of a circle on a plane, that has the
+        // same radius. Otherwise, a face on such a plane wouldn't fit.
+        let expected_approx = (GlobalPath::circle_from_radius(2.), boundary)
+            .approx(tolerance, &core.layers.geometry)
+            .into_iter()
+            .map(|(point_local, _)| point_local)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            approx
+                .points
+                .iter()
+                .map(|point| point.local_form)
+                .collect::<Vec<_>>(),
+            expected_approx,
+        );
+    }
+
     #[test]
     fn approx_circle_on_flat_surface() {
         let mut core = Core::new();
@@ -295,4 +588,181 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(approx.points, expected_approx);
     }
+
+    #[test]
+    fn approx_circle_on_curved_surface() {
+        let mut core = Core::new();
+
+        let surface = Surface::from_uv(
+            GlobalPath::circle_from_radius(1.),
+            [0., 0., 1.],
+            &mut core,
+        );
+        let path = SurfacePath::circle_from_center_and_radius([1., 1.], 0.5);
+        let curve =
+            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let boundary = CurveBoundary::from([[0.], [TAU]]);
+        let half_edge = HalfEdgeGeom { path, boundary };
+
+        let tolerance = 0.01;
+        let approx = (&curve, &half_edge, &surface)
+            .approx(tolerance, &core.layers.geometry);
+
+        let surface = core.layers.geometry.of_surface(&surface);
+        let point_global = |t: Scalar| {
+            surface.point_from_surface_coords(path.point_from_path_coords([t]))
+        };
+
+        // Check the deviation halfway between each pair of adjacent points.
+        // That's not necessarily the maximum, but close enough for a test.
+        let points = [Point::from([0.])]
+            .into_iter()
+            .chain(approx.points.iter().map(|point| point.local_form))
+            .chain([Point::from([TAU])])
+            .collect::<Vec<_>>();
+        assert!(points.len() > 3);
+        for segment in points.windows(2) {
+            let [a, b] = [segment[0].t, segment[1].t];
+
+            let chord_center =
+                point_global(a) + (point_global(b) - point_global(a)) / 2.;
+            let deviation =
+                (point_global((a + b) / 2.) - chord_center).magnitude();
+
+            assert!(deviation <= Scalar::from(tolerance));
+        }
+    }
+
+    #[test]
+    fn cache_approximations_per_tolerance() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xz_plane();
+        let path = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+        let curve =
+            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let boundary = CurveBoundary::from([[0.], [TAU]]);
+        let half_edge = HalfEdgeGeom { path, boundary };
+
+        let mut cache = CurveApproxCache::default();
+        let mut approx = |tolerance: f64| {
+            (&curve, &half_edge, &surface).approx_with_cache(
+                tolerance,
+                &mut cache,
+                &core.layers.geometry,
+            )
+        };
+
+        let coarse = approx(1.);
+        let fine = approx(0.01);
+
+        assert!(fine.points.len() > coarse.points.len());
+        assert_eq!(approx(1.).points, coarse.points);
+    }
+
+    #[test]
+    fn share_approximation_across_seam() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xz_plane();
+        let path = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+        let curve =
+            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+
+        let mut cache = CurveApproxCache::default();
+        let mut approx = |boundary: [[f64; 1]; 2]| {
+            let boundary = CurveBoundary::from(boundary);
+            let half_edge = HalfEdgeGeom { path, boundary };
+
+            (&curve, &half_edge, &surface).approx_with_cache(
+                0.01,
+                &mut cache,
+                &core.layers.geometry,
+            )
+        };
+
+        // The second boundary is the reverse of the first, one period later.
+        let a = approx([[0.], [TAU / 2.]]);
+        let b = approx([[TAU * 3. / 2.], [TAU]]);
+
+        let global = |approx: &super::CurveApprox| {
+            approx
+                .points
+                .iter()
+                .map(|point| point.global_form)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(global(&b), global(&a.clone().reverse()));
+        assert!(b
+            .points
+            .iter()
+            .all(|point| point.local_form.t > Scalar::TAU));
+    }
+
+    #[test]
+    fn approx_line_uniformly() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xz_plane();
+        let (path, boundary) =
+            SurfacePath::line_from_points([[1., 1.], [2., 1.]]);
+        let curve =
+            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let boundary = CurveBoundary::from(boundary);
+        let half_edge = HalfEdgeGeom { path, boundary };
+
+        let tolerance = 1.;
+        let sampling = CurveSampling::MaxSpacing(Scalar::from(0.3));
+        let approx = (&curve, &half_edge, &surface, sampling)
+            .approx(tolerance, &core.layers.geometry);
+
+        let expected_approx = [0.25, 0.5, 0.75]
+            .map(|t| {
+                let point_global = core
+                    .layers
+                    .geometry
+                    .of_surface(&surface)
+                    .point_from_surface_coords(
+                        path.point_from_path_coords([t]),
+                    );
+                ApproxPoint::new([t], point_global)
+            })
+            .to_vec();
+        assert_eq!(approx.points, expected_approx);
+    }
+
+    #[test]
+    fn approx_circle_uniformly() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xz_plane();
+        let path = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+        let curve =
+            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let boundary = CurveBoundary::from([[0.], [TAU]]);
+        let half_edge = HalfEdgeGeom { path, boundary };
+
+        let tolerance = 0.001;
+        let sampling = CurveSampling::Segments(5);
+        let approx = (&curve, &half_edge, &surface, sampling)
+            .approx(tolerance, &core.layers.geometry);
+
+        let start = core
+            .layers
+            .geometry
+            .of_surface(&surface)
+            .point_from_surface_coords(path.point_from_path_coords([0.]));
+        let points = [start]
+            .into_iter()
+            .chain(approx.points.iter().map(|point| point.global_form))
+            .chain([start])
+            .collect::<Vec<Point<3>>>();
+        assert_eq!(points.len(), 6);
+
+        let expected_distance = Scalar::from(2. * (TAU / 10.).sin());
+        for segment in points.windows(2) {
+            let distance = (segment[1] - segment[0]).magnitude();
+            assert!((distance - expected_distance).abs() < Scalar::from(0.001));
+        }
+    }
 }
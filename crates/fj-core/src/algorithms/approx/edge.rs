@@ -7,13 +7,13 @@
 
 use crate::{
     geometry::Geometry,
-    storage::Handle,
+    storage::{Handle, ObjectId},
     topology::{HalfEdge, Surface},
 };
 
 use super::{
-    curve::CurveApproxCache, vertex::VertexApproxCache, Approx, ApproxPoint,
-    Tolerance,
+    curve::CurveApproxCache, tolerance::ToleranceOverrides,
+    vertex::VertexApproxCache, Approx, ApproxPoint, Tolerance,
 };
 
 impl Approx for (&Handle<HalfEdge>, &Handle<Surface>) {
@@ -27,7 +27,9 @@ impl Approx for (&Handle<HalfEdge>, &Handle<Surface>) {
         geometry: &Geometry,
     ) -> Self::Approximation {
         let (half_edge, surface) = self;
-        let tolerance = tolerance.into();
+        let tolerance = cache
+            .tolerances
+            .for_curve(half_edge.curve(), tolerance.into());
 
         let start_position_surface =
             geometry.of_half_edge(half_edge).start_position();
@@ -77,8 +79,24 @@ pub struct HalfEdgeApprox {
 }
 
 /// Cache for half-edge approximations
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct HalfEdgeApproxCache {
+    /// Tolerances that override the one passed to the approximation
+    ///
+    /// They are stored here, as this cache is passed along to every half-edge
+    /// approximation. Cached approximations are keyed by tolerance, so
+    /// changing the overrides doesn't require clearing the cache.
+    pub tolerances: ToleranceOverrides,
+
     start_position: VertexApproxCache,
     curve: CurveApproxCache,
 }
+
+impl HalfEdgeApproxCache {
+    /// Remove everything that refers to objects that are not to be kept
+    pub(crate) fn retain_objects(&mut self, keep: impl Fn(ObjectId) -> bool) {
+        self.tolerances.retain_objects(&keep);
+        self.start_position.retain_objects(&keep);
+        self.curve.retain_objects(&keep);
+    }
+}
@@ -2,22 +2,31 @@
 //!
 //! See [`FaceApprox`].
 
-use std::{collections::BTreeSet, ops::Deref};
+use std::{
+    collections::BTreeSet,
+    ops::{Deref, RangeInclusive},
+};
+
+use fj_math::{Aabb, Circle, Point, Scalar, Tolerances};
 
 use crate::{
-    geometry::Geometry,
+    geometry::{Geometry, GlobalPath, SurfaceGeom},
     storage::Handle,
     topology::{Face, Handedness, ObjectSet},
-    validation::ValidationConfig,
 };
 
 use super::{
-    cycle::CycleApprox, edge::HalfEdgeApproxCache, Approx, ApproxPoint,
-    Tolerance,
+    cycle::CycleApprox, edge::HalfEdgeApproxCache, path::PathApproxParams,
+    Approx, ApproxPoint, Tolerance,
 };
 
 impl Approx for &ObjectSet<Face> {
-    type Approximation = BTreeSet<FaceApprox>;
+    /// The approximations of the faces, in the order of the set
+    ///
+    /// This is deliberately not a sorted collection. [`FaceApprox`] is ordered
+    /// by face handle first, and handles are ordered by the address of the
+    /// object they refer to, which differs between runs.
+    type Approximation = Vec<FaceApprox>;
     type Cache = HalfEdgeApproxCache;
 
     fn approx_with_cache(
@@ -27,42 +36,59 @@ impl Approx for &ObjectSet<Face> {
         geometry: &Geometry,
     ) -> Self::Approximation {
         let tolerance = tolerance.into();
+        let _span =
+            tracing::debug_span!("approximate", faces = self.len()).entered();
 
-        let approx = self
-            .into_iter()
+        self.into_iter()
             .map(|face| {
                 face.clone().approx_with_cache(tolerance, cache, geometry)
             })
-            .collect();
-
-        let min_distance = ValidationConfig::default().distinct_min_distance;
-        let mut all_points: BTreeSet<ApproxPoint<2>> = BTreeSet::new();
-
-        // Run some validation code on the approximation.
-        for approx in &approx {
-            let approx: &FaceApprox = approx;
-
-            for a in &approx.points() {
-                for b in &all_points {
-                    let distance = (b.global_form - a.global_form).magnitude();
-
-                    if b.global_form != a.global_form && distance < min_distance
-                    {
-                        panic!(
-                            "Invalid approximation: \
-                            Distinct points are too close \
-                            (a: {:?}, b: {:?}, distance: {distance})",
-                            a.global_form, b.global_form,
-                        );
-                    }
-                }
+            .collect()
+    }
+}
+
+/// Check that no distinct points of the approximations are too close
+///
+/// Points that are closer than [`Tolerances::linear`], but not the same, are
+/// the result of a bug in the approximation code, and would result in an
+/// invalid mesh.
+///
+/// # Panics
+///
+/// Panics, if any distinct points are too close.
+pub(crate) fn check_approximation(
+    approx: &[FaceApprox],
+    tolerances: &Tolerances,
+) {
+    let min_distance = tolerances.linear;
+    let mut all_points: BTreeSet<ApproxPoint<2>> = BTreeSet::new();
 
-                all_points.insert(*a);
+    // This compares all pairs of points, so it can take a while for big
+    // models.
+    let span = tracing::debug_span!(
+        "check_approximation",
+        points = tracing::field::Empty,
+    )
+    .entered();
+    for approx in approx {
+        for a in &approx.points() {
+            for b in &all_points {
+                let distance = (b.global_form - a.global_form).magnitude();
+
+                if b.global_form != a.global_form && distance < min_distance {
+                    panic!(
+                        "Invalid approximation: \
+                        Distinct points are too close \
+                        (a: {:?}, b: {:?}, distance: {distance})",
+                        a.global_form, b.global_form,
+                    );
+                }
             }
-        }
 
-        approx
+            all_points.insert(*a);
+        }
     }
+    span.record("points", all_points.len());
 }
 
 impl Approx for Handle<Face> {
@@ -76,19 +102,19 @@ impl Approx for Handle<Face> {
         geometry: &Geometry,
     ) -> Self::Approximation {
         let tolerance = tolerance.into();
+        let _span = tracing::trace_span!(
+            "approximate_face",
+            cycles = self.region().all_cycles().count(),
+        )
+        .entered();
 
-        // Curved faces whose curvature is not fully defined by their edges
-        // are not supported yet. For that reason, we can fully ignore `face`'s
-        // `surface` field and just pass the edges to `Self::for_edges`.
-        //
-        // An example of a curved face that is supported, is the cylinder. Its
-        // curvature is fully defined be the edges (circles) that border it. The
-        // circle approximations are sufficient to triangulate the surface.
+        // For most curved faces, the curvature is fully defined by their edges.
+        // An example of that is the cylinder, which is bordered by circles.
+        // The circle approximations are sufficient to triangulate the surface.
         //
-        // An example of a curved face that is currently not supported, and thus
-        // doesn't need to be handled here, is a sphere. A spherical face would
-        // would need to provide its own approximation, as the edges that bound
-        // it have nothing to do with its curvature.
+        // That is not the case for spheres and tori, as the edges that bound
+        // such a face have nothing to do with its curvature. Those need
+        // additional points within the face, which are added further down.
 
         let exterior = (self.region().exterior().deref(), self.surface())
             .approx_with_cache(tolerance, cache, geometry);
@@ -100,11 +126,23 @@ impl Approx for Handle<Face> {
             interiors.insert(cycle);
         }
 
+        let surface = geometry.of_surface(self.surface());
+        let surface_points = if surface.is_curved_along_v() {
+            approx_sphere_or_torus(
+                surface,
+                [&exterior].into_iter().chain(&interiors),
+                tolerance,
+            )
+        } else {
+            Vec::new()
+        };
+
         let coord_handedness = self.coord_handedness(geometry);
         FaceApprox {
             face: self,
             exterior,
             interiors,
+            surface_points,
             coord_handedness,
         }
     }
@@ -122,6 +160,12 @@ pub struct FaceApprox {
     /// Approximations of the interior cycles
     pub interiors: BTreeSet<CycleApprox>,
 
+    /// Points within the face, that approximate the curvature of its surface
+    ///
+    /// This is empty, unless the face's curvature is not defined by its edges,
+    /// as is the case for faces on a sphere or torus.
+    pub surface_points: Vec<ApproxPoint<2>>,
+
     /// The handedness of the approximated face's front-side coordinate system
     pub coord_handedness: Handedness,
 }
@@ -137,6 +181,139 @@ impl FaceApprox {
             points.extend(cycle_approx.points());
         }
 
+        points.extend(self.surface_points.iter().copied());
+
         points
     }
 }
+
+/// Approximate the inside of a face on a sphere or torus
+///
+/// The points are taken from a grid of parallels, which are spaced like the
+/// points of an approximated circle, whose radius is the length of the
+/// surface's v-axis. On a sphere, that is its equator. On a torus, that is the
+/// circle around its tube.
+///
+/// The points on each parallel are spaced the same way, so parallels that are
+/// shorter have fewer points. This keeps the points evenly spaced on the
+/// surface, instead of crowding them into slivers around the poles of a
+/// sphere, or on the inside of a torus.
+///
+/// Points that are too close to the face's boundary are skipped, as they would
+/// only result in tiny triangles.
+fn approx_sphere_or_torus<'r>(
+    surface: &SurfaceGeom,
+    cycles: impl IntoIterator<Item = &'r CycleApprox>,
+    tolerance: Tolerance,
+) -> Vec<ApproxPoint<2>> {
+    let GlobalPath::Circle(u) = surface.u else {
+        unreachable!("Sphere or torus must have a circle as its u-axis");
+    };
+    let radius = surface.v.magnitude();
+
+    let boundary = cycles
+        .into_iter()
+        .map(CycleApprox::points)
+        .collect::<Vec<_>>();
+    if boundary.iter().all(Vec::is_empty) {
+        return Vec::new();
+    }
+    let Aabb { min, max } = Aabb::<2>::from_points(
+        boundary.iter().flatten().map(|point| point.local_form),
+    );
+
+    // The edges of the triangles between the points of the grid, and between
+    // the grid and the boundary, are longer than the spacing of the grid. A
+    // quarter of the tolerance, which halves the spacing compared to the
+    // boundary, keeps them within the tolerance.
+    let increment = PathApproxParams::for_circle(
+        &Circle::from_center_and_radius([0., 0.], radius),
+        tolerance.inner() / 4.,
+    )
+    .increment();
+    let min_distance = radius * increment / 2.;
+
+    let mut points = Vec::new();
+
+    for row in index_range(min.v, max.v, increment) {
+        let v = increment * Scalar::from(row as f64);
+        if surface.is_sphere() && v.abs() >= Scalar::PI / 2. {
+            continue;
+        }
+
+        let num_points_on_parallel =
+            (Scalar::TAU * u.radius() * surface.scale_at(v)
+                / (radius * increment))
+                .ceil();
+        let increment_u = Scalar::TAU / num_points_on_parallel;
+
+        for column in index_range(min.u, max.u, increment_u) {
+            let point_surface =
+                Point::from([increment_u * Scalar::from(column as f64), v]);
+            let point_global = surface.point_from_surface_coords(point_surface);
+
+            if !contains(&boundary, point_surface) {
+                continue;
+            }
+            let is_close_to_boundary = boundary.iter().any(|cycle| {
+                cycle.windows(2).any(|segment| {
+                    let [a, b] =
+                        [segment[0], segment[1]].map(|point| point.global_form);
+                    let ab = b - a;
+
+                    let t = if ab.magnitude() == Scalar::ZERO {
+                        Scalar::ZERO
+                    } else {
+                        ((point_global - a).dot(&ab) / ab.dot(&ab))
+                            .max(Scalar::ZERO)
+                            .min(Scalar::ONE)
+                    };
+
+                    (point_global - (a + ab * t)).magnitude() < min_distance
+                })
+            });
+            if is_close_to_boundary {
+                continue;
+            }
+
+            points.push(ApproxPoint::new(point_surface, point_global));
+        }
+    }
+
+    points
+}
+
+/// Compute the indices of the multiples of `increment` between `min` and `max`
+///
+/// The range is exclusive on both ends.
+fn index_range(
+    min: Scalar,
+    max: Scalar,
+    increment: Scalar,
+) -> RangeInclusive<i64> {
+    let [min, max] = [min, max].map(|bound| (bound / increment).into_f64());
+    (min.floor() as i64 + 1)..=(max.ceil() as i64 - 1)
+}
+
+/// Determine whether a point is within the boundary of a face
+///
+/// Uses the even-odd rule, which works for the exterior and interior cycles
+/// alike, as long as they don't intersect each other.
+fn contains(boundary: &[Vec<ApproxPoint<2>>], point: Point<2>) -> bool {
+    let mut is_inside = false;
+
+    for cycle in boundary {
+        for segment in cycle.windows(2) {
+            let [a, b] = [segment[0], segment[1]].map(|point| point.local_form);
+
+            if (a.v > point.v) != (b.v > point.v) {
+                let u = a.u + (point.v - a.v) / (b.v - a.v) * (b.u - a.u);
+                if point.u < u {
+                    is_inside = !is_inside;
+                }
+            }
+        }
+    }
+
+    is_inside
+}
@@ -5,6 +5,7 @@ pub mod cycle;
 pub mod edge;
 pub mod face;
 pub mod path;
+pub mod polyline;
 pub mod shell;
 pub mod sketch;
 pub mod solid;
@@ -21,7 +22,10 @@ use fj_math::Point;
 
 use crate::geometry::Geometry;
 
-pub use self::tolerance::{InvalidTolerance, Tolerance};
+pub use self::{
+    polyline::{ApproxPolylines, FacePolylines, Polyline},
+    tolerance::{InvalidTolerance, Tolerance, ToleranceOverrides},
+};
 
 /// Approximate an object
 pub trait Approx: Sized {
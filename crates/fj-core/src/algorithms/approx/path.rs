@@ -27,12 +27,20 @@
 //! As a result, path approximation is guaranteed to generate points that can
 //! fit together in a valid mesh, no matter which ranges of a path are being
 //! approximated, and how many times.
+//!
+//! If the tolerance is [nested](Tolerance::nested), the number of points used
+//! to approximate a full circle is rounded up to a power of two. This means the
+//! set of points for one nested tolerance is a subset of the set of points for
+//! any smaller nested tolerance.
 
 use std::iter;
 
-use fj_math::{Circle, Point, Scalar, Sign};
+use fj_math::{Circle, Line, Point, Scalar, Sign};
 
-use crate::geometry::{CurveBoundary, Geometry, GlobalPath, SurfacePath};
+use crate::geometry::{
+    CurveBoundary, Geometry, GlobalPath, SurfaceGeom, SurfacePath,
+    SurfaceProfile,
+};
 
 use super::{Approx, Tolerance};
 
@@ -100,7 +108,63 @@ fn approx_circle<const D: usize>(
     points
 }
 
-struct PathApproxParams {
+/// Approximate a circle in the surface coordinates of a curved surface
+///
+/// The global form of such a circle is not a circle, so it can't be
+/// approximated like one. Instead, its points are spaced uniformly in circle
+/// coordinates, close enough that the approximation stays within the
+/// tolerance. Like for other paths, the spacing doesn't depend on the
+/// boundary, so the result is deterministic in the same way.
+pub(super) fn approx_circle_on_surface(
+    circle: &Circle<2>,
+    surface: &SurfaceGeom,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<3>)> {
+    let params =
+        PathApproxParams::for_circle_on_surface(circle, surface, tolerance);
+
+    approx_on_surface(&params, surface, boundary, |point_curve| {
+        circle.point_from_circle_coords(point_curve)
+    })
+}
+
+/// Approximate a line in the surface coordinates of a sphere or a torus
+///
+/// Lines that are not parallel to the u-axis are not straight on those
+/// surfaces, but meridians or spirals. They are approximated like
+/// [circles on curved surfaces](approx_circle_on_surface).
+pub(super) fn approx_line_on_sphere_or_torus(
+    line: &Line<2>,
+    surface: &SurfaceGeom,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<3>)> {
+    let params =
+        PathApproxParams::for_line_on_sphere_or_torus(line, surface, tolerance);
+
+    approx_on_surface(&params, surface, boundary, |point_curve| {
+        line.point_from_line_coords(point_curve)
+    })
+}
+
+fn approx_on_surface(
+    params: &PathApproxParams,
+    surface: &SurfaceGeom,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    point_surface: impl Fn(Point<1>) -> Point<2>,
+) -> Vec<(Point<1>, Point<3>)> {
+    params
+        .points(boundary)
+        .map(|point_curve| {
+            let point_global =
+                surface.point_from_surface_coords(point_surface(point_curve));
+            (point_curve, point_global)
+        })
+        .collect()
+}
+
+pub(super) struct PathApproxParams {
     increment: Scalar,
 }
 
@@ -109,14 +173,121 @@ impl PathApproxParams {
         circle: &Circle<D>,
         tolerance: impl Into<Tolerance>,
     ) -> Self {
+        let tolerance = tolerance.into();
         let radius = circle.a().magnitude();
 
-        let num_vertices_to_approx_full_circle = Scalar::max(
-            Scalar::PI
-                / (Scalar::ONE - (tolerance.into().inner() / radius)).acos(),
-            3.,
-        )
-        .ceil();
+        let num_vertices_to_approx_full_circle =
+            Scalar::PI / (Scalar::ONE - (tolerance.inner() / radius)).acos();
+
+        Self::for_num_vertices(num_vertices_to_approx_full_circle, tolerance)
+    }
+
+    pub fn for_circle_on_surface(
+        circle: &Circle<2>,
+        surface: &SurfaceGeom,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        let tolerance = tolerance.into();
+        let radius = circle.radius();
+
+        // The global form of the circle is
+        // `g(t) = u(s(t)) + r(s(t)) * k * v(t) + v(t) * V`, where `s(t)` and
+        // `v(t)` are the surface coordinates of the circle, `u` is the path of
+        // the surface, `r` its radial vector, `k` its taper, and `V` its
+        // v-vector. The first and second derivatives of `s` and `v` are at
+        // most `radius`, which bounds the second derivative of `g`. On a
+        // sphere, `g(t) = c + r(s(t)) * cos(v(t)) + sin(v(t)) * V` instead,
+        // where `c` is its center. On a torus, the radial vector is widened
+        // by `cos(v(t))` times the length of `V`, which adds terms of the same
+        // kind, scaled by the length of `V`.
+        let max_second_derivative = match (surface.u, surface.profile) {
+            (GlobalPath::Circle(u), SurfaceProfile::Spherical) => {
+                u.radius() * (radius * radius * 4. + radius * 2.)
+            }
+            (GlobalPath::Circle(u), SurfaceProfile::Toroidal) => {
+                (u.radius() + surface.v.magnitude() * 3.)
+                    * (radius * radius * 3. + radius)
+            }
+            (GlobalPath::Circle(u), SurfaceProfile::Linear { taper }) => {
+                let taper = taper.abs();
+                let max_scale =
+                    Scalar::ONE + taper * (circle.center().v.abs() + radius);
+
+                (u.radius() * radius * radius + u.radius() * radius) * max_scale
+                    + (u.radius() * radius * radius * 2. + u.radius() * radius)
+                        * taper
+                    + surface.v.magnitude() * radius
+            }
+            (GlobalPath::Line(u), _) => {
+                u.direction().magnitude() * radius
+                    + surface.v.magnitude() * radius
+            }
+        };
+
+        Self::for_max_second_derivative(max_second_derivative, tolerance)
+    }
+
+    pub fn for_line_on_sphere_or_torus(
+        line: &Line<2>,
+        surface: &SurfaceGeom,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        let tolerance = tolerance.into();
+        let [du, dv] = line.direction().components.map(|d| d.abs());
+
+        // With `s(t) = s + du * t` and `v(t) = v + dv * t`, the second
+        // derivative of `c + r(s(t)) * cos(v(t)) + sin(v(t)) * V` is bounded
+        // by the square of the direction's components, times the radius, which
+        // is the length of `V`. On a torus, `r(s(t))` is widened by
+        // `cos(v(t)) * |V|`, which adds the radius of its u-axis, and the
+        // length of `V` once more.
+        let radius = match surface.u {
+            GlobalPath::Circle(u) if surface.is_torus() => {
+                u.radius() + surface.v.magnitude() * 2.
+            }
+            _ => surface.v.magnitude(),
+        };
+        let max_second_derivative = radius * (du + dv) * (du + dv);
+
+        Self::for_max_second_derivative(max_second_derivative, tolerance)
+    }
+
+    /// Space the points such, that a curve with the provided bound on its
+    /// second derivative stays within the tolerance
+    ///
+    /// Points are still spaced such that they divide the range from zero to
+    /// `TAU` evenly, to keep nested tolerances working.
+    fn for_max_second_derivative(
+        max_second_derivative: Scalar,
+        tolerance: Tolerance,
+    ) -> Self {
+        // A segment of length `h` in path coordinates deviates from the curve
+        // by at most `max_second_derivative * h^2 / 8`.
+        let max_increment = Scalar::from_f64(
+            (tolerance.inner() * 8. / max_second_derivative)
+                .into_f64()
+                .sqrt(),
+        );
+        let num_vertices_to_approx_full_circle = Scalar::TAU / max_increment;
+
+        Self::for_num_vertices(num_vertices_to_approx_full_circle, tolerance)
+    }
+
+    fn for_num_vertices(
+        num_vertices_to_approx_full_circle: Scalar,
+        tolerance: Tolerance,
+    ) -> Self {
+        let num_vertices_to_approx_full_circle =
+            Scalar::max(num_vertices_to_approx_full_circle, 3.).ceil();
+        let num_vertices_to_approx_full_circle = if tolerance.is_nested() {
+            Scalar::from_u64(
+                num_vertices_to_approx_full_circle
+                    .into_u64()
+                    .next_power_of_two(),
+            )
+        } else {
+            num_vertices_to_approx_full_circle
+        };
 
         let increment = Scalar::TAU / num_vertices_to_approx_full_circle;
 
@@ -195,6 +366,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn increment_for_circle_with_nested_tolerance() {
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+
+        for (tolerance, expected_num_vertices) in
+            [(0.5, 4.), (0.1, 8.), (0.01, 32.)]
+        {
+            let tolerance = Tolerance::from(tolerance).nested();
+            let params = PathApproxParams::for_circle(&circle, tolerance);
+
+            let expected_increment = Scalar::TAU / expected_num_vertices;
+            assert_eq!(params.increment(), expected_increment);
+        }
+    }
+
+    #[test]
+    fn points_for_circle_with_nested_tolerance() {
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+        let boundary = CurveBoundary::from([[0.], [TAU]]);
+
+        let [coarse, fine] = [0.1, 0.01].map(|tolerance| {
+            let tolerance = Tolerance::from(tolerance).nested();
+            PathApproxParams::for_circle(&circle, tolerance)
+                .points(boundary)
+                .collect::<Vec<_>>()
+        });
+
+        for point in coarse {
+            assert!(fine.contains(&point));
+        }
+    }
+
+    #[test]
+    fn increment_for_circle_with_coarser_tolerance() {
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+        let tolerance = Tolerance::from(0.001);
+
+        for (levels, expected_num_vertices) in [(0, 128.), (1, 64.), (2, 32.)] {
+            let tolerance = tolerance.coarser(levels);
+            let params = PathApproxParams::for_circle(&circle, tolerance);
+
+            let expected_increment = Scalar::TAU / expected_num_vertices;
+            assert_eq!(params.increment(), expected_increment);
+        }
+    }
+
     #[test]
     fn points_for_circle() {
         // At the chosen values for radius and tolerance (see below), the
@@ -0,0 +1,167 @@
+//! # Polylines that approximate the cycles of a face
+//!
+//! See [`ApproxPolylines`].
+
+use std::ops::Deref;
+
+use fj_math::Point;
+
+use crate::{
+    geometry::Geometry,
+    storage::Handle,
+    topology::{Cycle, Face, Surface},
+};
+
+use super::{cycle::CycleApprox, edge::HalfEdgeApproxCache, Approx, Tolerance};
+
+/// Approximate the cycles of a face as polylines
+///
+/// This is a more direct way to get at the boundary of a face than the other
+/// approximation APIs, for use cases that don't need a triangulation. The
+/// polylines are always computed at the provided tolerance, like the
+/// approximation that [`Triangulate`] is based on.
+///
+/// [`Triangulate`]: crate::algorithms::triangulate::Triangulate
+pub trait ApproxPolylines {
+    /// Approximate the cycles of the face as polylines
+    fn approx_polylines(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> FacePolylines;
+}
+
+impl ApproxPolylines for Face {
+    fn approx_polylines(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> FacePolylines {
+        let tolerance = tolerance.into();
+        let mut cache = HalfEdgeApproxCache::default();
+
+        let mut approx = |cycle: &Handle<Cycle>, surface: &Handle<Surface>| {
+            let approx = (cycle.deref(), surface)
+                .approx_with_cache(tolerance, &mut cache, geometry);
+            Polyline::from(approx)
+        };
+
+        let exterior = approx(self.region().exterior(), self.surface());
+        let interiors = self
+            .region()
+            .interiors()
+            .iter()
+            .map(|cycle| approx(cycle, self.surface()))
+            .collect();
+
+        FacePolylines {
+            exterior,
+            interiors,
+        }
+    }
+}
+
+/// The polylines that approximate the cycles of a face
+///
+/// Returned by [`ApproxPolylines::approx_polylines`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FacePolylines {
+    /// The polyline that approximates the exterior cycle
+    pub exterior: Polyline,
+
+    /// The polylines that approximate the interior cycles
+    ///
+    /// They are in the same order as the interior cycles of the face's region.
+    pub interiors: Vec<Polyline>,
+}
+
+/// A closed polyline, in surface and in global coordinates
+///
+/// The points follow the half-edges of the approximated cycle, starting at the
+/// start vertex of its first half-edge. The last point repeats the first one,
+/// to close the polyline.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Polyline {
+    /// The points of the polyline, in the coordinates of the face's surface
+    pub points_surface: Vec<Point<2>>,
+
+    /// The points of the polyline, in global coordinates
+    ///
+    /// Each point corresponds to the point at the same index in
+    /// `points_surface`.
+    pub points_global: Vec<Point<3>>,
+}
+
+impl From<CycleApprox> for Polyline {
+    fn from(approx: CycleApprox) -> Self {
+        let (points_surface, points_global) = approx
+            .points()
+            .into_iter()
+            .map(|point| (point.local_form, point.global_form))
+            .unzip();
+
+        Self {
+            points_surface,
+            points_global,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::{
+        operations::{
+            build::{BuildCycle, BuildRegion},
+            insert::Insert,
+            update::UpdateRegion,
+        },
+        topology::{Cycle, Face, Region},
+        Core,
+    };
+
+    use super::ApproxPolylines;
+
+    #[test]
+    fn face_with_hole() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let exterior = [[0., 0.], [4., 0.], [4., 4.], [0., 4.]];
+        let interior = [[1., 1.], [1., 2.], [2., 2.], [2., 1.]];
+
+        let region = Region::polygon(exterior, surface.clone(), &mut core)
+            .add_interiors(
+                [Cycle::polygon(interior, surface.clone(), &mut core)],
+                &mut core,
+            )
+            .insert(&mut core);
+        let face = Face::new(surface, region);
+
+        let polylines = face.approx_polylines(1., &core.layers.geometry);
+
+        let closed = |points: [[f64; 2]; 4]| {
+            points.into_iter().chain([points[0]]).map(Point::from)
+        };
+        assert!(polylines
+            .exterior
+            .points_surface
+            .into_iter()
+            .eq(closed(exterior)));
+        assert!(polylines
+            .exterior
+            .points_global
+            .into_iter()
+            .eq(closed(exterior).map(|point| point.to_xyz())));
+
+        let [interior_polyline] = polylines.interiors.as_slice() else {
+            panic!("Expected exactly one interior polyline");
+        };
+        assert!(interior_polyline
+            .points_surface
+            .iter()
+            .copied()
+            .eq(closed(interior)));
+    }
+}
@@ -1,13 +1,11 @@
 //! Shell approximation
 
-use std::collections::BTreeSet;
-
 use crate::{geometry::Geometry, topology::Shell};
 
 use super::{edge::HalfEdgeApproxCache, face::FaceApprox, Approx, Tolerance};
 
 impl Approx for &Shell {
-    type Approximation = BTreeSet<FaceApprox>;
+    type Approximation = Vec<FaceApprox>;
     type Cache = HalfEdgeApproxCache;
 
     fn approx_with_cache(
@@ -1,13 +1,11 @@
 //! Sketch approximation
 
-use std::collections::BTreeSet;
-
 use crate::{geometry::Geometry, topology::Sketch};
 
 use super::{edge::HalfEdgeApproxCache, face::FaceApprox, Approx, Tolerance};
 
 impl Approx for &Sketch {
-    type Approximation = BTreeSet<FaceApprox>;
+    type Approximation = Vec<FaceApprox>;
     type Cache = HalfEdgeApproxCache;
 
     fn approx_with_cache(
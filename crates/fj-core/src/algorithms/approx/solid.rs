@@ -1,13 +1,11 @@
 //! Solid approximation
 
-use std::collections::BTreeSet;
-
 use crate::{geometry::Geometry, topology::Solid};
 
 use super::{edge::HalfEdgeApproxCache, face::FaceApprox, Approx, Tolerance};
 
 impl Approx for &Solid {
-    type Approximation = BTreeSet<FaceApprox>;
+    type Approximation = Vec<FaceApprox>;
     type Cache = HalfEdgeApproxCache;
 
     fn approx_with_cache(
@@ -2,8 +2,15 @@
 //!
 //! See [`Tolerance`].
 
+use std::collections::BTreeMap;
+
 use fj_math::Scalar;
 
+use crate::{
+    storage::{Handle, ObjectId},
+    topology::{Curve, Face, HalfEdge},
+};
+
 /// A tolerance value
 ///
 /// A tolerance value is used during approximation. It defines the maximum
@@ -12,6 +19,19 @@ use fj_math::Scalar;
 /// The `Tolerance` type enforces that the tolerance value is always larger than
 /// zero, which is an attribute that the approximation code relies on.
 ///
+/// # Nested approximations
+///
+/// By default, approximations generated with different tolerance values don't
+/// have anything in common. If meshes generated at multiple tolerances (for
+/// example, different levels of detail) are used together, this can lead to
+/// cracks between them.
+///
+/// Calling [`Tolerance::nested`] returns a tolerance that generates nested
+/// approximations instead: Any approximation generated with a nested
+/// tolerance, contains all points of any approximation generated with a
+/// larger, nested tolerance. This comes at the cost of generating more points
+/// than would be strictly required.
+///
 /// # Failing [`From`]/[`Into`] implementation
 ///
 /// The [`From`]/[`Into`] implementations of tolerance are fallible, which goes
@@ -22,7 +42,10 @@ use fj_math::Scalar;
 /// documentation doesn't provide any actual reasoning for this requirement, I'm
 /// feeling free to just ignore it.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct Tolerance(Scalar);
+pub struct Tolerance {
+    value: Scalar,
+    is_nested: bool,
+}
 
 impl Tolerance {
     /// Construct a `Tolerance` from a [`Scalar`]
@@ -37,12 +60,48 @@ impl Tolerance {
             return Err(InvalidTolerance(scalar));
         }
 
-        Ok(Self(scalar))
+        Ok(Self {
+            value: scalar,
+            is_nested: false,
+        })
     }
 
     /// Return the [`Scalar`] that defines the tolerance
     pub fn inner(&self) -> Scalar {
-        self.0
+        self.value
+    }
+
+    /// Return a tolerance that generates nested approximations
+    ///
+    /// See [type-level documentation](Tolerance) for more information.
+    #[must_use]
+    pub fn nested(self) -> Self {
+        Self {
+            is_nested: true,
+            ..self
+        }
+    }
+
+    /// Return a nested tolerance for a coarser level of detail
+    ///
+    /// The deviation of an approximation grows with the square of the distance
+    /// between its points. Each level multiplies the tolerance by four, which
+    /// halves the number of points that a nested approximation of a circle
+    /// has. Every point of the coarser approximation is still part of the finer
+    /// one, so meshes of different levels fit together.
+    #[must_use]
+    pub fn coarser(self, levels: u32) -> Self {
+        let factor = Scalar::from_u64(4u64.pow(levels));
+
+        Self {
+            value: self.value * factor,
+            is_nested: true,
+        }
+    }
+
+    /// Indicate whether this tolerance generates nested approximations
+    pub fn is_nested(&self) -> bool {
+        self.is_nested
     }
 }
 
@@ -56,7 +115,155 @@ where
     }
 }
 
+/// Tolerances that override the default tolerance for specific objects
+///
+/// Some parts of a shape need to be approximated more precisely than others.
+/// A bearing seat might need a fine approximation, while a cosmetic surface
+/// can do with a coarse one. Instead of using the finest tolerance for the
+/// whole shape, this allows setting it only where it is needed.
+///
+/// The overrides are stored per curve, as an edge's approximation is shared by
+/// both faces it bounds. Setting an override for a face sets it for all of its
+/// edges. If an edge has been given multiple overrides, directly or through
+/// its faces, the smallest one applies. That way, the approximations of
+/// neighboring faces still fit together.
+///
+/// Overrides are used by approximations that are done with the
+/// [`HalfEdgeApproxCache`] they are stored in.
+///
+/// [`HalfEdgeApproxCache`]: super::edge::HalfEdgeApproxCache
+#[derive(Clone, Debug, Default)]
+pub struct ToleranceOverrides {
+    curves: BTreeMap<Handle<Curve>, Tolerance>,
+}
+
+impl ToleranceOverrides {
+    /// Override the tolerance for all edges of a face
+    pub fn set_for_face(
+        &mut self,
+        face: &Handle<Face>,
+        tolerance: impl Into<Tolerance>,
+    ) {
+        let tolerance = tolerance.into();
+
+        for cycle in face.region().all_cycles() {
+            for half_edge in cycle.half_edges() {
+                self.set_for_half_edge(half_edge, tolerance);
+            }
+        }
+    }
+
+    /// Override the tolerance for a half-edge and its sibling
+    pub fn set_for_half_edge(
+        &mut self,
+        half_edge: &Handle<HalfEdge>,
+        tolerance: impl Into<Tolerance>,
+    ) {
+        self.set_for_curve(half_edge.curve(), tolerance);
+    }
+
+    /// Override the tolerance for a curve
+    ///
+    /// This affects all half-edges on the curve.
+    pub fn set_for_curve(
+        &mut self,
+        curve: &Handle<Curve>,
+        tolerance: impl Into<Tolerance>,
+    ) {
+        let tolerance = tolerance.into();
+
+        self.curves
+            .entry(curve.clone())
+            .and_modify(|existing| *existing = (*existing).min(tolerance))
+            .or_insert(tolerance);
+    }
+
+    /// Return the tolerance for a curve
+    ///
+    /// Returns the override for the curve, if one has been set, or `default`
+    /// otherwise.
+    pub fn for_curve(
+        &self,
+        curve: &Handle<Curve>,
+        default: Tolerance,
+    ) -> Tolerance {
+        self.curves.get(curve).copied().unwrap_or(default)
+    }
+
+    /// Iterate over all curves that have an override, and their tolerance
+    pub fn curves(
+        &self,
+    ) -> impl Iterator<Item = (&Handle<Curve>, Tolerance)> + '_ {
+        self.curves
+            .iter()
+            .map(|(curve, tolerance)| (curve, *tolerance))
+    }
+
+    /// Remove all overrides
+    pub fn clear(&mut self) {
+        self.curves.clear();
+    }
+
+    pub(crate) fn retain_objects(&mut self, keep: impl Fn(ObjectId) -> bool) {
+        self.curves.retain(|curve, _| keep(curve.id()));
+    }
+}
+
 /// Error converting scalar to tolerance
 #[derive(Debug, thiserror::Error)]
 #[error("Invalid tolerance ({0}); must be above zero")]
 pub struct InvalidTolerance(Scalar);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        algorithms::triangulate::Triangulate, geometry::GlobalPath,
+        operations::build::BuildSolid, topology::Solid, Core,
+    };
+
+    use super::Tolerance;
+
+    #[test]
+    fn override_for_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 1., &mut core);
+        let tolerance = Tolerance::from_scalar(0.1)?;
+
+        let default = (&cylinder, tolerance).triangulate(&mut core);
+
+        let end_face = cylinder
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .find(|face| {
+                matches!(
+                    core.layers.geometry.of_surface(face.surface()).u,
+                    GlobalPath::Line(_)
+                )
+            })
+            .expect("Cylinder has planar end faces")
+            .clone();
+        core.approx_cache
+            .tolerances
+            .set_for_face(&end_face, Tolerance::from_scalar(0.001)?);
+
+        let refined = (&cylinder, tolerance).triangulate(&mut core);
+        assert!(refined.num_triangles() > default.num_triangles());
+
+        // The side face uses the refined approximation of the shared edge, so
+        // every edge of the mesh is still shared by exactly two triangles.
+        let mut edges = BTreeMap::new();
+        for [a, b, c] in refined.triangle_indices() {
+            for [a, b] in [[a, b], [b, c], [c, a]] {
+                *edges.entry([a.min(b), a.max(b)]).or_insert(0) += 1;
+            }
+        }
+        assert!(edges.values().all(|&count| count == 2));
+
+        Ok(())
+    }
+}
@@ -4,10 +4,13 @@ use std::collections::BTreeMap;
 
 use fj_math::Point;
 
-use crate::{storage::Handle, topology::Vertex};
+use crate::{
+    storage::{Handle, ObjectId},
+    topology::Vertex,
+};
 
 /// Cache for vertex approximations
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct VertexApproxCache {
     inner: BTreeMap<Handle<Vertex>, Point<3>>,
 }
@@ -26,4 +29,8 @@ impl VertexApproxCache {
     ) -> Point<3> {
         self.inner.insert(handle, position).unwrap_or(position)
     }
+
+    pub(crate) fn retain_objects(&mut self, keep: impl Fn(ObjectId) -> bool) {
+        self.inner.retain(|vertex, _| keep(vertex.id()));
+    }
 }
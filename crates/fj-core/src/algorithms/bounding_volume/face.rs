@@ -23,10 +23,21 @@ impl super::BoundingVolume<3> for &Face {
 
                     aabb_bottom.merged(&aabb_top)
                 }
-                GlobalPath::Line(_) => Aabb {
-                    min: surface.point_from_surface_coords(aabb2.min),
-                    max: surface.point_from_surface_coords(aabb2.max),
-                },
+                GlobalPath::Line(_) => {
+                    // The surface axes are not necessarily aligned with the
+                    // global axes, so all corners of the 2D AABB are needed.
+                    let [min, max] = [aabb2.min, aabb2.max];
+                    let corners = [
+                        [min.u, min.v],
+                        [min.u, max.v],
+                        [max.u, min.v],
+                        [max.u, max.v],
+                    ];
+
+                    Aabb::<3>::from_points(corners.map(|corner| {
+                        surface.point_from_surface_coords(corner)
+                    }))
+                }
             }
         })
     }
@@ -0,0 +1,249 @@
+//! Convex hulls of points and shapes
+//!
+//! The convex hull of a set of points is the smallest convex solid that
+//! contains all of them. It is useful as simplified geometry, for bounding
+//! volumes, collision shapes, or enclosures.
+//!
+//! The hull is computed incrementally: Starting from a tetrahedron, each point
+//! that is outside of the current hull replaces the faces that it can see with
+//! new faces that connect it to the horizon of those faces. All decisions are
+//! made using [robust predicates](fj_math::predicates), so nearly coplanar
+//! points don't cause inconsistencies.
+
+use std::collections::BTreeSet;
+
+use fj_math::{predicates, Point, Scalar, Sign};
+
+use crate::{
+    operations::{
+        build::{BuildShell, BuildSolid},
+        insert::Insert,
+        update::UpdateSolid,
+    },
+    queries::AllFaces,
+    topology::{Shell, Solid},
+    Core,
+};
+
+use super::{approx::Tolerance, triangulate::TriangulateFiltered};
+
+/// Compute the convex hull of a set of points
+///
+/// The faces of the resulting solid are triangles. Faces of the hull that are
+/// not triangles, like those of a box, are made up of multiple triangles.
+/// Points that are inside of the hull, or on its faces, are not used.
+///
+/// Returns an error, if the points don't span a volume.
+pub fn convex_hull(
+    points: impl IntoIterator<Item = impl Into<Point<3>>>,
+    core: &mut Core,
+) -> Result<Solid, ConvexHullError> {
+    let points = points.into_iter().map(Into::into).collect::<Vec<_>>();
+    let triangles = hull_triangles(&points)?;
+
+    // Only build vertices for the points that are part of the hull.
+    let used = triangles
+        .iter()
+        .flatten()
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let vertices = used.iter().map(|&index| points[index]);
+    let indices = triangles.iter().map(|triangle| {
+        triangle.map(|index| {
+            used.binary_search(&index)
+                .expect("Index of triangle must be used")
+        })
+    });
+
+    let shell =
+        Shell::from_vertices_and_indices(vertices, indices, core).insert(core);
+    Ok(Solid::empty().add_shells([shell], core))
+}
+
+/// Compute the convex hull of a shape
+///
+/// The shape is triangulated with the provided tolerance, and the hull of the
+/// points of that triangulation is computed. For shapes with curved faces, the
+/// hull is thus an approximation.
+///
+/// See [`convex_hull`] for more information.
+pub fn convex_hull_of_shape(
+    shape: impl AllFaces,
+    tolerance: impl Into<Tolerance>,
+    core: &mut Core,
+) -> Result<Solid, ConvexHullError> {
+    let mesh = (shape, tolerance.into()).triangulate_filtered(|_| true, core);
+    let points = mesh.vertices().collect::<Vec<_>>();
+
+    convex_hull(points, core)
+}
+
+/// Error computing a convex hull
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ConvexHullError {
+    /// The points don't span a volume
+    ///
+    /// This is the case, if there are fewer than four distinct points, or if
+    /// all points are on a single plane.
+    #[error("Points don't span a volume; can't compute convex hull")]
+    Degenerate,
+}
+
+/// Compute the triangles of the convex hull
+///
+/// Returns the triangles as indices into `points`. The triangles are oriented
+/// such that their counter-clockwise side faces outwards.
+fn hull_triangles(
+    points: &[Point<3>],
+) -> Result<Vec<[usize; 3]>, ConvexHullError> {
+    let [a, b, c, d] = initial_tetrahedron(points)?;
+
+    // Orient each face, such that the opposite vertex of the tetrahedron is
+    // below it. That makes its counter-clockwise side face outwards.
+    let oriented = |[a, b, c]: [usize; 3], opposite: usize| {
+        let [pa, pb, pc, po] = [a, b, c, opposite].map(|i| points[i]);
+
+        match predicates::orient3d(pa, pb, pc, po) {
+            Sign::Positive => [a, b, c],
+            _ => [a, c, b],
+        }
+    };
+    let mut triangles = vec![
+        oriented([a, b, c], d),
+        oriented([a, b, d], c),
+        oriented([a, c, d], b),
+        oriented([b, c, d], a),
+    ];
+
+    for (point, &position) in points.iter().enumerate() {
+        if [a, b, c, d].contains(&point) {
+            continue;
+        }
+
+        let (visible, hidden): (Vec<_>, Vec<_>) =
+            triangles.into_iter().partition(|&[a, b, c]| {
+                predicates::orient3d(points[a], points[b], points[c], position)
+                    == Sign::Negative
+            });
+        triangles = hidden;
+
+        // The horizon consists of the edges of visible triangles, that are not
+        // shared with another visible triangle.
+        let edges = visible
+            .iter()
+            .flat_map(|&[a, b, c]| [[a, b], [b, c], [c, a]])
+            .collect::<BTreeSet<_>>();
+        let horizon = edges.iter().filter(|&&[a, b]| !edges.contains(&[b, a]));
+
+        triangles.extend(horizon.map(|&[a, b]| [a, b, point]));
+    }
+
+    Ok(triangles)
+}
+
+/// Find four points that form a tetrahedron with a non-zero volume
+fn initial_tetrahedron(
+    points: &[Point<3>],
+) -> Result<[usize; 4], ConvexHullError> {
+    let farthest_from = |measure: &dyn Fn(Point<3>) -> Scalar| {
+        points
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &point)| measure(point))
+            .map(|(index, _)| index)
+    };
+
+    let a = 0;
+    let b = farthest_from(&|point| point.distance_to(&points[a]))
+        .filter(|&b| points[b] != points[a])
+        .ok_or(ConvexHullError::Degenerate)?;
+
+    let direction = points[b] - points[a];
+    let c = farthest_from(&|point| {
+        (point - points[a]).cross(&direction).magnitude()
+    })
+    .filter(|&c| !predicates::are_collinear([a, b, c].map(|i| points[i])))
+    .ok_or(ConvexHullError::Degenerate)?;
+
+    let normal = direction.cross(&(points[c] - points[a]));
+    let d = farthest_from(&|point| (point - points[a]).dot(&normal).abs())
+        .filter(|&d| {
+            predicates::orient3d(points[a], points[b], points[c], points[d])
+                != Sign::Zero
+        })
+        .ok_or(ConvexHullError::Degenerate)?;
+
+    Ok([a, b, c, d])
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::{
+        algorithms::approx::Tolerance, operations::build::BuildSolid,
+        topology::Solid, Core,
+    };
+
+    use super::{
+        convex_hull, convex_hull_of_shape, hull_triangles, ConvexHullError,
+    };
+
+    #[test]
+    fn hull_of_cube_with_interior_points() {
+        let mut points = Vec::new();
+        for x in [0., 1.] {
+            for y in [0., 1.] {
+                for z in [0., 1.] {
+                    points.push(Point::from([x, y, z]));
+                }
+            }
+        }
+        points.extend(
+            [[0.5, 0.5, 0.5], [0.2, 0.7, 0.1], [0.5, 0.5, 1.]].map(Point::from),
+        );
+
+        let triangles = hull_triangles(&points).unwrap();
+
+        // Each face of the cube is made up of two triangles.
+        assert_eq!(triangles.len(), 12);
+        for triangle in &triangles {
+            assert!(triangle.iter().all(|&index| index < 8));
+        }
+    }
+
+    #[test]
+    fn hull_of_coplanar_points() {
+        let mut core = Core::new();
+
+        let points = [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [1., 1., 0.]];
+        let result = convex_hull(points, &mut core);
+
+        assert_eq!(result.err(), Some(ConvexHullError::Degenerate));
+    }
+
+    #[test]
+    fn hull_of_shape() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let tolerance = Tolerance::from_scalar(1.)?;
+
+        let hull =
+            convex_hull_of_shape(&tetrahedron.solid, tolerance, &mut core)?;
+
+        let num_faces = hull
+            .shells()
+            .iter()
+            .map(|shell| shell.faces().len())
+            .sum::<usize>();
+        assert_eq!(num_faces, 4);
+
+        Ok(())
+    }
+}
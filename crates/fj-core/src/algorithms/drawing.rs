@@ -0,0 +1,544 @@
+//! # Orthographic drawings of solids
+//!
+//! See [`Draw`].
+
+use std::collections::BTreeSet;
+
+use fj_interop::{Annotation, Drawing};
+use fj_math::{
+    intersect::SegmentSegmentIntersection, Point, Scalar, Segment, Tolerances,
+    Triangle, Vector,
+};
+
+use crate::{
+    annotations::ResolvedAnnotation, queries::AllHalfEdgesWithSurface,
+    topology::Solid, Core,
+};
+
+use super::{
+    approx::Tolerance,
+    silhouette::{silhouette_lines, View},
+    triangulate::Triangulate,
+};
+
+/// Create orthographic line drawings of a solid
+pub trait Draw {
+    /// Draw the solid, as seen when looking along `direction`
+    ///
+    /// The drawing contains all edges at which faces meet at an angle, as well
+    /// as the silhouette of the solid. It is projected into the same plane that
+    /// [`ComputeSilhouette`] uses.
+    ///
+    /// Lines that are hidden behind other parts of the solid are kept
+    /// separately. Where a hidden line coincides with a visible one, only the
+    /// visible one is kept.
+    ///
+    /// [Annotations] that refer to the solid are included. Dimensions are left
+    /// out, unless the view shows them at their true size.
+    ///
+    /// [`ComputeSilhouette`]: super::silhouette::ComputeSilhouette
+    /// [Annotations]: crate::annotations::Annotations
+    fn draw(
+        &self,
+        direction: impl Into<Vector<3>>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Drawing;
+
+    /// Draw the front, top, and right views, arranged into a single drawing
+    ///
+    /// The views are arranged using third-angle projection: The top view is
+    /// placed above the front view, the right view to the right of it. Each
+    /// annotation is shown in the first of these views that can show it.
+    fn draw_standard_views(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Drawing;
+}
+
+impl Draw for Solid {
+    fn draw(
+        &self,
+        direction: impl Into<Vector<3>>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Drawing {
+        let view = View::new(direction.into(), &core.tolerances());
+
+        let mut drawing = draw_lines(self, &view, tolerance.into(), core);
+        drawing.annotations =
+            project_annotations(self, &view, &mut BTreeSet::new(), core);
+
+        drawing
+    }
+
+    fn draw_standard_views(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Drawing {
+        let tolerance = tolerance.into();
+
+        let mut annotated = BTreeSet::new();
+
+        let [front, top, right] = StandardView::ALL.map(|view| {
+            let view = View::new(view.direction(), &core.tolerances());
+
+            let mut drawing = draw_lines(self, &view, tolerance, core);
+            drawing.annotations =
+                project_annotations(self, &view, &mut annotated, core);
+
+            drawing
+        });
+
+        let [Some(front_aabb), Some(top_aabb), Some(right_aabb)] =
+            [&front, &top, &right].map(|drawing| drawing.aabb())
+        else {
+            // If any view is empty, all of them are.
+            return Drawing::new();
+        };
+
+        // All views are drawn at the same scale, and the top and right views
+        // already line up with the front view. They only need to be moved
+        // out of its way.
+        let size = front_aabb.max - front_aabb.min;
+        let gap = size.u.max(size.v) / 4.;
+
+        let top = top
+            .translate([Scalar::ZERO, front_aabb.max.v + gap - top_aabb.min.v]);
+        let right = right.translate([
+            front_aabb.max.u + gap - right_aabb.min.u,
+            Scalar::ZERO,
+        ]);
+
+        front.merge(top).merge(right)
+    }
+}
+
+/// Draw the lines of a solid, as seen from the provided view
+fn draw_lines(
+    solid: &Solid,
+    view: &View,
+    tolerance: Tolerance,
+    core: &mut Core,
+) -> Drawing {
+    let tolerances = core.tolerances();
+
+    let lines =
+        silhouette_lines(solid, view, tolerance, true, &core.layers.geometry)
+            .into_iter()
+            .filter_map(|line| {
+                let [a, b] = line.map(|point| view.project(point));
+                if tolerances.are_coincident(a, b) {
+                    // Lines that are parallel to the view direction show up as
+                    // points, which are not part of the drawing.
+                    return None;
+                }
+
+                Some((line, Segment::from_points([a, b])))
+            })
+            .collect::<Vec<_>>();
+
+    let triangles = (solid, tolerance)
+        .triangulate(core)
+        .triangles()
+        .map(|triangle| triangle.inner)
+        .collect::<Vec<_>>();
+
+    let mut visible = Vec::new();
+    let mut hidden = Vec::new();
+
+    for (i, &(line, segment)) in lines.iter().enumerate() {
+        // The visibility of a line can only change where it passes behind
+        // another line of the drawing. Split it at all of those points.
+        let mut split_at = vec![Scalar::ZERO, Scalar::ONE];
+        for (j, (_, other)) in lines.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+
+            match SegmentSegmentIntersection::compute(
+                &segment,
+                other,
+                &tolerances,
+            ) {
+                Some(SegmentSegmentIntersection::Point {
+                    t: [t, _], ..
+                }) => {
+                    split_at.push(t);
+                }
+                Some(SegmentSegmentIntersection::Coincident { points }) => {
+                    split_at.extend(
+                        points.map(|point| parameter_of(&segment, point)),
+                    );
+                }
+                None => {}
+            }
+        }
+        split_at.sort();
+        split_at.dedup();
+
+        let [a, b] = line;
+        let mut pieces: Vec<(Scalar, Scalar, bool)> = Vec::new();
+
+        for t in split_at.windows(2) {
+            let [start, end] = [t[0], t[1]];
+
+            let middle = a + (b - a) * ((start + end) / 2.);
+            let is_hidden =
+                is_occluded(middle, view, &triangles, tolerance.inner());
+
+            match pieces.last_mut() {
+                Some((_, previous_end, previous_is_hidden))
+                    if *previous_is_hidden == is_hidden =>
+                {
+                    *previous_end = end;
+                }
+                _ => {
+                    pieces.push((start, end, is_hidden));
+                }
+            }
+        }
+
+        for (start, end, is_hidden) in pieces {
+            let [a, b] = segment.points();
+            let [start, end] = [start, end].map(|t| a + (b - a) * t);
+
+            if tolerances.are_coincident(start, end) {
+                continue;
+            }
+
+            let piece = Segment::from_points([start, end]);
+            if is_hidden {
+                hidden.push(piece);
+            } else {
+                visible.push(piece);
+            }
+        }
+    }
+
+    // Lines can overlap, for example if one edge is directly behind
+    // another. Since all lines have been split where they overlap, each
+    // piece is either covered by another, or not at all.
+    let mut drawing = Drawing::new();
+    for piece in visible {
+        if !is_covered(&piece, &drawing.visible, &tolerances) {
+            drawing.visible.push(piece);
+        }
+    }
+    for piece in hidden {
+        if !is_covered(&piece, &drawing.visible, &tolerances)
+            && !is_covered(&piece, &drawing.hidden, &tolerances)
+        {
+            drawing.hidden.push(piece);
+        }
+    }
+
+    drawing
+}
+
+/// Project the annotations of a solid into the provided view
+///
+/// Dimensions are only projected into views, in which they appear at their
+/// true size. Annotations whose index is in `annotated` are skipped. The
+/// indices of all projected annotations are added to it.
+fn project_annotations(
+    solid: &Solid,
+    view: &View,
+    annotated: &mut BTreeSet<usize>,
+    core: &Core,
+) -> Vec<Annotation> {
+    let annotations = &core.layers.annotations;
+    let tolerances = core.tolerances();
+
+    let half_edges = solid
+        .shells()
+        .iter()
+        .flat_map(|shell| shell.all_half_edges_with_surface())
+        .map(|(half_edge, _)| half_edge)
+        .collect::<BTreeSet<_>>();
+
+    let mut projected = Vec::new();
+
+    for (i, annotation) in annotations.annotations.iter().enumerate() {
+        if annotated.contains(&i) {
+            continue;
+        }
+
+        let is_part_of_solid = annotation.names().into_iter().all(|name| {
+            annotations
+                .names
+                .get(name)
+                .is_some_and(|named| half_edges.contains(named.half_edge()))
+        });
+        if !is_part_of_solid {
+            continue;
+        }
+
+        let annotation = match annotations
+            .resolve(annotation, &core.layers.geometry)
+        {
+            ResolvedAnnotation::Linear { from, to, offset } => {
+                if !tolerances.are_perpendicular(&(to - from), &view.direction)
+                {
+                    continue;
+                }
+
+                Annotation::Linear {
+                    from: view.project(from),
+                    to: view.project(to),
+                    offset,
+                    value: from.distance_to(&to),
+                }
+            }
+            ResolvedAnnotation::Radial {
+                center,
+                point,
+                normal,
+            } => {
+                if !tolerances.are_parallel(&normal, &view.direction) {
+                    continue;
+                }
+
+                Annotation::Radial {
+                    center: view.project(center),
+                    point: view.project(point),
+                    value: center.distance_to(&point),
+                }
+            }
+            ResolvedAnnotation::Note { position, text } => Annotation::Note {
+                position: view.project(position),
+                text,
+            },
+        };
+
+        annotated.insert(i);
+        projected.push(annotation);
+    }
+
+    projected
+}
+
+/// One of the standard views of an orthographic drawing
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum StandardView {
+    /// The view from the front, looking along the positive y-axis
+    Front,
+
+    /// The view from the top, looking along the negative z-axis
+    Top,
+
+    /// The view from the right, looking along the negative x-axis
+    Right,
+}
+
+impl StandardView {
+    /// All standard views
+    pub const ALL: [Self; 3] = [Self::Front, Self::Top, Self::Right];
+
+    /// The direction that the view looks along
+    pub fn direction(&self) -> Vector<3> {
+        match self {
+            Self::Front => Vector::from([0., 1., 0.]),
+            Self::Top => Vector::from([0., 0., -1.]),
+            Self::Right => Vector::from([-1., 0., 0.]),
+        }
+    }
+}
+
+/// Compute the parameter of a point on a segment
+///
+/// The parameter is `0` at the start of the segment and `1` at its end.
+fn parameter_of(segment: &Segment<2>, point: Point<2>) -> Scalar {
+    let [a, b] = segment.points();
+    let direction = b - a;
+
+    let t = (point - a).dot(&direction) / direction.dot(&direction);
+    t.max(Scalar::ZERO).min(Scalar::ONE)
+}
+
+/// Determine whether a point is hidden behind any of the triangles
+///
+/// The point is expected to be on the surface of the solid that the triangles
+/// approximate. Triangles that are closer to the point than the tolerance are
+/// ignored, as they could be part of that very surface.
+fn is_occluded(
+    point: Point<3>,
+    view: &View,
+    triangles: &[Triangle<3>],
+    tolerance: Scalar,
+) -> bool {
+    triangles.iter().any(|triangle| {
+        triangle
+            .cast_local_ray(point, -view.direction, f64::INFINITY, true)
+            .is_some_and(|distance| distance > tolerance)
+    })
+}
+
+/// Determine whether a segment lies on any of the other segments
+fn is_covered(
+    segment: &Segment<2>,
+    others: &[Segment<2>],
+    tolerances: &Tolerances,
+) -> bool {
+    let middle = segment.center();
+
+    others.iter().any(|other| {
+        let [a, b] = other.points();
+        let closest = a + (b - a) * parameter_of(other, middle);
+        tolerances.are_coincident(middle, closest)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::Annotation;
+    use fj_math::{Point, Scalar, Segment};
+
+    use crate::{
+        annotations,
+        geometry::{DatumPoint, GlobalPath, SurfacePath},
+        operations::build::BuildSolid,
+        queries::AllHalfEdgesWithSurface,
+        topology::Solid,
+        Core,
+    };
+
+    use super::Draw;
+
+    #[test]
+    fn cube() {
+        let mut core = Core::new();
+
+        let cube = Solid::rounded_box([1., 1., 1.], 0., &mut core);
+
+        // Each of the edges in the back is directly behind one in the front.
+        let drawing = cube.draw([0., 1., 0.], 0.01, &mut core);
+        assert_eq!(total_length(&drawing.visible), Scalar::from(4.));
+        assert!(drawing.hidden.is_empty());
+
+        let drawing = cube.draw_standard_views(0.01, &mut core);
+        assert_eq!(total_length(&drawing.visible), Scalar::from(12.));
+        assert!(drawing.hidden.is_empty());
+    }
+
+    #[test]
+    fn washer() {
+        let mut core = Core::new();
+
+        let washer = Solid::washer(2., 1., 0.25, &mut core);
+
+        // From the front, the outline of the washer is visible. The inner
+        // wall is hidden behind the outer one, and only its silhouette shows
+        // up as hidden lines. The rest of it coincides with visible lines.
+        let drawing = washer.draw([0., 1., 0.], 0.01, &mut core);
+
+        let visible = total_length(&drawing.visible);
+        assert!((visible - Scalar::from(8.5)).abs() < Scalar::from(0.01));
+
+        assert!(
+            (total_length(&drawing.hidden) - Scalar::from(0.5)).abs()
+                < Scalar::from(1e-9)
+        );
+        for segment in &drawing.hidden {
+            for point in segment.points() {
+                assert!(
+                    (point.u.abs() - Scalar::ONE).abs() < Scalar::from(1e-9)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn annotations() {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+        let geometry = &core.layers.geometry;
+
+        let half_edges = cylinder
+            .shells()
+            .only()
+            .all_half_edges_with_surface()
+            .collect::<Vec<_>>();
+        let start_of = |position: [f64; 3]| {
+            half_edges
+                .iter()
+                .find(|(half_edge, surface)| {
+                    DatumPoint::at_start_of_half_edge(
+                        half_edge, surface, geometry,
+                    )
+                    .position
+                        == Point::from(position)
+                })
+                .cloned()
+                .unwrap()
+        };
+        let (top_edge, top_surface) = half_edges
+            .iter()
+            .find(|(half_edge, surface)| {
+                matches!(
+                    geometry.of_half_edge(half_edge).path,
+                    SurfacePath::Circle(_)
+                ) && matches!(
+                    geometry.of_surface(surface).u,
+                    GlobalPath::Line(_)
+                ) && DatumPoint::at_start_of_half_edge(
+                    half_edge, surface, geometry,
+                )
+                .position
+                .z == Scalar::from(2.)
+            })
+            .cloned()
+            .unwrap();
+
+        let (bottom, bottom_surface) = start_of([1., 0., 0.]);
+        let (top, top_vertex_surface) = start_of([1., 0., 2.]);
+
+        let layer = &mut core.layers.annotations;
+        layer.name_vertex("bottom", bottom, bottom_surface).unwrap();
+        layer.name_vertex("top", top, top_vertex_surface).unwrap();
+        layer.name_edge("top edge", top_edge, top_surface).unwrap();
+        for annotation in [
+            annotations::Annotation::Linear {
+                from: "bottom".into(),
+                to: "top".into(),
+                offset: Scalar::ONE,
+            },
+            annotations::Annotation::Radial {
+                edge: "top edge".into(),
+            },
+            annotations::Annotation::Note {
+                at: "top edge".into(),
+                text: "Polished".into(),
+            },
+        ] {
+            layer.annotate(annotation, &core.layers.geometry).unwrap();
+        }
+
+        // Looking along the axis, the height doesn't show up at its true
+        // size, but the radius does.
+        let drawing = cylinder.draw([0., 0., -1.], 0.01, &mut core);
+        assert!(matches!(
+            drawing.annotations.as_slice(),
+            [Annotation::Radial { .. }, Annotation::Note { .. }]
+        ));
+
+        let drawing = cylinder.draw_standard_views(0.01, &mut core);
+        let [Annotation::Linear { value: height, .. }, Annotation::Note { .. }, Annotation::Radial { value: radius, .. }] =
+            drawing.annotations.as_slice()
+        else {
+            panic!("Unexpected annotations: {:?}", drawing.annotations);
+        };
+        assert_eq!(*height, Scalar::from(2.));
+        assert_eq!(*radius, Scalar::ONE);
+    }
+
+    fn total_length(segments: &[Segment<2>]) -> Scalar {
+        segments.iter().fold(Scalar::ZERO, |length, segment| {
+            let [a, b] = segment.points();
+            length + a.distance_to(&b)
+        })
+    }
+}
@@ -0,0 +1,833 @@
+//! # Fit analytic surfaces to triangle meshes
+//!
+//! Meshes, like those imported from STL files, only approximate the surfaces
+//! of a shape. This module recovers planes, cylinders, and spheres from regions
+//! of such meshes, and builds faces on them. This allows converting mesh-based
+//! parts into editable b-rep, one region at a time.
+//!
+//! See [`fit_surface`] and [`fit_face`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_interop::{Index, Mesh};
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeom, SurfaceProfile},
+    operations::{
+        build::{BuildCycle, BuildSurface},
+        insert::Insert,
+    },
+    storage::Handle,
+    topology::{Cycle, Face, Region, Surface},
+    Core,
+};
+
+use super::approx::Tolerance;
+
+/// Fit a surface to a region of a mesh
+///
+/// The region is defined by the indices of its triangles within `mesh`. A
+/// plane, a cylinder, and a sphere are tried, in that order. The first one
+/// that is within `tolerance` of all points of the region is returned.
+///
+/// # Panics
+///
+/// Panics, if any of the triangle indices is out of bounds.
+pub fn fit_surface(
+    mesh: &Mesh<Point<3>>,
+    region: impl IntoIterator<Item = usize>,
+    tolerance: impl Into<Tolerance>,
+) -> Result<SurfaceFit, FitSurfaceError> {
+    let triangles = region_triangles(mesh, region);
+    fit_triangles(mesh, &triangles, tolerance.into())
+}
+
+/// Fit a surface to a region of a mesh, and build a face on it
+///
+/// The surface is fitted using [`fit_surface`]. The boundary of the face is
+/// made up of line segments that connect the boundary points of the region.
+/// For the boundary to be recognized, the triangles of the region must share
+/// their vertices, as they do in meshes that are imported from STL files.
+///
+/// The region must have exactly one outer boundary, and may have any number of
+/// holes. Regions that go all the way around a cylinder or sphere, like a
+/// tube, are not supported.
+///
+/// # Panics
+///
+/// Panics, if any of the triangle indices is out of bounds.
+pub fn fit_face(
+    mesh: &Mesh<Point<3>>,
+    region: impl IntoIterator<Item = usize>,
+    tolerance: impl Into<Tolerance>,
+    core: &mut Core,
+) -> Result<Face, FitSurfaceError> {
+    let triangles = region_triangles(mesh, region);
+    let fit = fit_triangles(mesh, &triangles, tolerance.into())?;
+
+    let surface = fit.build_surface(core);
+    let surface_geom = *core.layers.geometry.of_surface(&surface);
+
+    let mut exterior = None;
+    let mut interiors = Vec::new();
+
+    for boundary in boundary_loops(&triangles)
+        .ok_or(FitSurfaceError::NonManifoldBoundary)?
+    {
+        let points = project_loop(
+            boundary.into_iter().map(|index| mesh.vertex(index)),
+            &surface_geom,
+        )?;
+
+        // The triangles are wound counter-clockwise, when viewed from the
+        // side their normals point to, and the fitted surface faces the same
+        // way. This makes the outer boundary counter-clockwise in surface
+        // coordinates, and holes clockwise.
+        let signed_area = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| a.u * b.v - b.u * a.v)
+            .fold(Scalar::ZERO, |sum, value| sum + value);
+
+        if signed_area > Scalar::ZERO {
+            if exterior.is_some() {
+                return Err(FitSurfaceError::NoSingleExterior);
+            }
+            exterior = Some(points);
+        } else {
+            interiors.push(points);
+        }
+    }
+
+    let exterior = exterior.ok_or(FitSurfaceError::NoSingleExterior)?;
+
+    let exterior = Cycle::polygon(exterior, surface.clone(), core).insert(core);
+    let interiors = interiors
+        .into_iter()
+        .map(|points| {
+            Cycle::polygon(points, surface.clone(), core).insert(core)
+        })
+        .collect::<Vec<_>>();
+    let region = Region::new(exterior, interiors).insert(core);
+
+    Ok(Face::new(surface, region))
+}
+
+/// A surface that has been fitted to a region of a mesh
+///
+/// Returned by [`fit_surface`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SurfaceFit {
+    /// The fitted surface
+    pub surface: FittedSurface,
+
+    /// The maximum distance of any point of the region from the surface
+    pub max_deviation: Scalar,
+
+    /// The centroid of the points of the region
+    ///
+    /// Used to place the seam of cylinders and spheres on the far side of the
+    /// region, when building the surface.
+    pub centroid: Point<3>,
+}
+
+impl SurfaceFit {
+    /// Build the fitted surface
+    ///
+    /// The surface faces the same way as the triangles of the region it was
+    /// fitted to. If it is a cylinder or sphere, its seam is placed opposite
+    /// of the region.
+    pub fn build_surface(&self, core: &mut Core) -> Handle<Surface> {
+        match self.surface {
+            FittedSurface::Plane { origin, normal } => {
+                let u = any_perpendicular(normal);
+                let v = normal.cross(&u);
+
+                Surface::from_uv(
+                    GlobalPath::Line(Line::from_origin_and_direction(
+                        origin, u,
+                    )),
+                    v,
+                    core,
+                )
+            }
+            FittedSurface::Cylinder {
+                axis,
+                radius,
+                inward,
+            } => {
+                let center = axis.point_from_line_coords(
+                    axis.point_to_line_coords(self.centroid),
+                );
+                let direction = axis.direction();
+
+                let circle =
+                    circle_with_seam(center, direction, self.centroid, radius);
+                let circle = if inward { circle.reverse() } else { circle };
+
+                Surface::from_uv(GlobalPath::Circle(circle), direction, core)
+            }
+            FittedSurface::Sphere {
+                center,
+                radius,
+                inward,
+            } => {
+                // The poles of the sphere are placed to the side of the
+                // region, so the region is centered on the equator.
+                let towards_region = self.centroid - center;
+                let axis = if towards_region == Vector::from([0., 0., 0.]) {
+                    Vector::unit_z()
+                } else {
+                    any_perpendicular(towards_region)
+                };
+
+                let circle =
+                    circle_with_seam(center, axis, self.centroid, radius);
+                let circle = if inward { circle.reverse() } else { circle };
+
+                let surface = Surface::new().insert(core);
+                core.layers.geometry.define_surface(
+                    surface.clone(),
+                    SurfaceGeom {
+                        u: GlobalPath::Circle(circle),
+                        v: axis * radius,
+                        profile: SurfaceProfile::Spherical,
+                    },
+                );
+
+                surface
+            }
+        }
+    }
+}
+
+/// A surface that has been fitted to a region of a mesh
+///
+/// See [`SurfaceFit`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FittedSurface {
+    /// A plane
+    Plane {
+        /// A point on the plane
+        origin: Point<3>,
+
+        /// The unit normal of the plane
+        ///
+        /// Points to the side that the triangles of the region face.
+        normal: Vector<3>,
+    },
+
+    /// A cylinder
+    Cylinder {
+        /// The axis of the cylinder, with a unit direction
+        axis: Line<3>,
+
+        /// The radius of the cylinder
+        radius: Scalar,
+
+        /// Whether the triangles of the region face towards the axis
+        ///
+        /// This is the case for the inside of a hole.
+        inward: bool,
+    },
+
+    /// A sphere
+    Sphere {
+        /// The center of the sphere
+        center: Point<3>,
+
+        /// The radius of the sphere
+        radius: Scalar,
+
+        /// Whether the triangles of the region face towards the center
+        inward: bool,
+    },
+}
+
+impl FittedSurface {
+    /// Compute the distance of a point from the surface
+    pub fn distance_to(&self, point: impl Into<Point<3>>) -> Scalar {
+        let point = point.into();
+
+        match *self {
+            Self::Plane { origin, normal } => (point - origin).dot(&normal),
+            Self::Cylinder { axis, radius, .. } => {
+                let offset = point - axis.origin();
+                let along_axis =
+                    axis.direction() * offset.dot(&axis.direction());
+                (offset - along_axis).magnitude() - radius
+            }
+            Self::Sphere { center, radius, .. } => {
+                (point - center).magnitude() - radius
+            }
+        }
+        .abs()
+    }
+}
+
+/// Error fitting a surface to a region of a mesh
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum FitSurfaceError {
+    /// The region contains no triangles
+    #[error("Region contains no triangles")]
+    EmptyRegion,
+
+    /// No plane, cylinder, or sphere is within tolerance of the region
+    #[error("No plane, cylinder, or sphere fits the region within tolerance")]
+    NoFit,
+
+    /// The boundary of the region is not made up of separate loops
+    ///
+    /// This is the case, if the region touches itself at a vertex, or if two
+    /// of its triangles overlap.
+    #[error("Boundary of region is not made up of separate loops")]
+    NonManifoldBoundary,
+
+    /// The region doesn't have exactly one outer boundary
+    #[error("Region doesn't have exactly one outer boundary")]
+    NoSingleExterior,
+
+    /// The boundary of the region goes around the seam of the fitted surface
+    #[error("Boundary of region goes around the seam of the fitted surface")]
+    WrapsAroundSeam,
+}
+
+fn region_triangles(
+    mesh: &Mesh<Point<3>>,
+    region: impl IntoIterator<Item = usize>,
+) -> Vec<[Index; 3]> {
+    let all = mesh.triangle_indices().collect::<Vec<_>>();
+    region.into_iter().map(|index| all[index]).collect()
+}
+
+fn fit_triangles(
+    mesh: &Mesh<Point<3>>,
+    triangles: &[[Index; 3]],
+    tolerance: Tolerance,
+) -> Result<SurfaceFit, FitSurfaceError> {
+    if triangles.is_empty() {
+        return Err(FitSurfaceError::EmptyRegion);
+    }
+
+    let points = triangles
+        .iter()
+        .flatten()
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|index| mesh.vertex(index))
+        .collect::<Vec<_>>();
+    let centroid = Point::origin()
+        + points
+            .iter()
+            .fold(Vector::from([0., 0., 0.]), |sum, point| sum + point.coords)
+            / points.len() as f64;
+
+    // The normals of the triangles, scaled by their area.
+    let normals = triangles
+        .iter()
+        .map(|&triangle| {
+            let [a, b, c] = mesh.triangle_vertices(triangle);
+            let normal = (b - a).cross(&(c - a)) / 2.;
+            (
+                Point::origin() + (a.coords + b.coords + c.coords) / 3.,
+                normal,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let candidates = [fit_plane, fit_cylinder, fit_sphere];
+    for fit in candidates {
+        let Some(surface) = fit(&points, &normals, centroid) else {
+            continue;
+        };
+
+        let max_deviation = points
+            .iter()
+            .map(|&point| surface.distance_to(point))
+            .fold(Scalar::ZERO, Scalar::max);
+
+        if max_deviation <= tolerance.inner() {
+            return Ok(SurfaceFit {
+                surface,
+                max_deviation,
+                centroid,
+            });
+        }
+    }
+
+    Err(FitSurfaceError::NoFit)
+}
+
+fn fit_plane(
+    _: &[Point<3>],
+    normals: &[(Point<3>, Vector<3>)],
+    centroid: Point<3>,
+) -> Option<FittedSurface> {
+    let normal = normals
+        .iter()
+        .fold(Vector::from([0., 0., 0.]), |sum, (_, normal)| sum + *normal);
+    if normal == Vector::from([0., 0., 0.]) {
+        return None;
+    }
+
+    Some(FittedSurface::Plane {
+        origin: centroid,
+        normal: normal.normalize(),
+    })
+}
+
+fn fit_cylinder(
+    points: &[Point<3>],
+    normals: &[(Point<3>, Vector<3>)],
+    centroid: Point<3>,
+) -> Option<FittedSurface> {
+    // The normals of a cylinder are all perpendicular to its axis. The axis is
+    // the direction that is closest to perpendicular to all normals, which is
+    // the eigenvector of the smallest eigenvalue of the following matrix.
+    let mut matrix = [[0.; 3]; 3];
+    for (_, normal) in normals {
+        let n = normal.components.map(Scalar::into_f64);
+        let weight = normal.magnitude().into_f64();
+        if weight == 0. {
+            continue;
+        }
+
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value += n[i] * n[j] / weight;
+            }
+        }
+    }
+
+    // The cross product of the two most different normals makes for a good
+    // initial guess.
+    let (_, first) = normals.first()?;
+    let guess = normals
+        .iter()
+        .map(|(_, normal)| first.cross(normal))
+        .max_by_key(|guess| guess.magnitude())?;
+    if guess == Vector::from([0., 0., 0.]) {
+        return None;
+    }
+    let direction = smallest_eigenvector(matrix, guess.normalize());
+
+    // Fit a circle to the points, as projected into a plane perpendicular to
+    // the axis.
+    let e1 = any_perpendicular(direction);
+    let e2 = direction.cross(&e1);
+    let rows = points.iter().map(|&point| {
+        let offset = point - centroid;
+        let [x, y] = [offset.dot(&e1), offset.dot(&e2)].map(Scalar::into_f64);
+        ([x, y, 1.], -(x * x + y * y))
+    });
+    let [d, e, f] = least_squares(rows)?;
+
+    let [cx, cy] = [-d / 2., -e / 2.];
+    let radius_squared = cx * cx + cy * cy - f;
+    if radius_squared <= 0. {
+        return None;
+    }
+
+    let axis = Line::from_origin_and_direction(
+        centroid + e1 * cx + e2 * cy,
+        direction,
+    );
+    let inward = normals
+        .iter()
+        .map(|&(point, normal)| {
+            let offset = point - axis.origin();
+            let radial = offset - direction * offset.dot(&direction);
+            radial.dot(&normal)
+        })
+        .fold(Scalar::ZERO, |sum, value| sum + value)
+        < Scalar::ZERO;
+
+    Some(FittedSurface::Cylinder {
+        axis,
+        radius: Scalar::from(radius_squared.sqrt()),
+        inward,
+    })
+}
+
+fn fit_sphere(
+    points: &[Point<3>],
+    normals: &[(Point<3>, Vector<3>)],
+    centroid: Point<3>,
+) -> Option<FittedSurface> {
+    let rows = points.iter().map(|&point| {
+        let [x, y, z] = (point - centroid).components.map(Scalar::into_f64);
+        ([x, y, z, 1.], -(x * x + y * y + z * z))
+    });
+    let [d, e, f, g] = least_squares(rows)?;
+
+    let center = [-d / 2., -e / 2., -f / 2.];
+    let radius_squared = center.iter().map(|c| c * c).sum::<f64>() - g;
+    if radius_squared <= 0. {
+        return None;
+    }
+
+    let center = centroid + Vector::from(center);
+    let inward = normals
+        .iter()
+        .map(|&(point, normal)| (point - center).dot(&normal))
+        .fold(Scalar::ZERO, |sum, value| sum + value)
+        < Scalar::ZERO;
+
+    Some(FittedSurface::Sphere {
+        center,
+        radius: Scalar::from(radius_squared.sqrt()),
+        inward,
+    })
+}
+
+/// Find the boundary loops of a region of triangles
+///
+/// Each loop is wound the same way as the triangles that it bounds. Returns
+/// `None`, if the boundary is not made up of separate loops.
+pub(crate) fn boundary_loops<I: Copy + Ord>(
+    triangles: &[[I; 3]],
+) -> Option<Vec<Vec<I>>> {
+    let mut edges = BTreeSet::new();
+    for &[a, b, c] in triangles {
+        for edge in [[a, b], [b, c], [c, a]] {
+            if !edges.insert(edge) {
+                return None;
+            }
+        }
+    }
+
+    // Edges that are shared by two triangles of the region are traversed in
+    // opposite directions. Those that aren't, are on the boundary.
+    let mut next = BTreeMap::new();
+    for &[a, b] in &edges {
+        if edges.contains(&[b, a]) {
+            continue;
+        }
+        if next.insert(a, b).is_some() {
+            return None;
+        }
+    }
+
+    let mut loops = Vec::new();
+    while let Some((start, mut current)) = next.pop_first() {
+        let mut boundary = vec![start];
+
+        while current != start {
+            boundary.push(current);
+            current = next.remove(&current)?;
+        }
+
+        loops.push(boundary);
+    }
+
+    Some(loops)
+}
+
+/// Project a boundary loop into surface coordinates
+///
+/// On cylinders and spheres, the u-coordinates are unwrapped, so the loop
+/// doesn't jump across the seam.
+fn project_loop(
+    points: impl IntoIterator<Item = Point<3>>,
+    surface: &SurfaceGeom,
+) -> Result<Vec<Point<2>>, FitSurfaceError> {
+    let mut points = points
+        .into_iter()
+        .map(|point| surface.project_global_point(point))
+        .collect::<Vec<_>>();
+
+    let Some(period) = surface.u_period() else {
+        return Ok(points);
+    };
+
+    let unwrap = |previous: Scalar, u: Scalar| {
+        u - ((u - previous) / period).round() * period
+    };
+
+    for i in 1..points.len() {
+        points[i].u = unwrap(points[i - 1].u, points[i].u);
+    }
+
+    // If the loop goes around the seam, coming back to the first point ends up
+    // in another period.
+    if let (Some(first), Some(last)) = (points.first(), points.last()) {
+        if unwrap(last.u, first.u) != first.u {
+            return Err(FitSurfaceError::WrapsAroundSeam);
+        }
+    }
+
+    Ok(points)
+}
+
+/// Build a circle whose seam is on the far side of `reference`
+fn circle_with_seam(
+    center: Point<3>,
+    normal: Vector<3>,
+    reference: Point<3>,
+    radius: Scalar,
+) -> Circle<3> {
+    let normal = normal.normalize();
+
+    let away = center - reference;
+    let away = away - normal * away.dot(&normal);
+    let direction = if away == Vector::from([0., 0., 0.]) {
+        any_perpendicular(normal)
+    } else {
+        away.normalize()
+    };
+
+    let a = direction * radius;
+    let mut b = normal.cross(&direction) * radius;
+
+    // `Circle` requires `a` and `b` to be of exactly the same length, which
+    // the cross product doesn't guarantee in floating point.
+    for _ in 0..4 {
+        if a.magnitude() == b.magnitude() {
+            break;
+        }
+        b = b * (a.magnitude() / b.magnitude());
+    }
+
+    Circle::new(center, a, b)
+}
+
+/// Compute a unit vector that is perpendicular to the provided one
+fn any_perpendicular(vector: Vector<3>) -> Vector<3> {
+    let [x, y, z] = vector.components.map(Scalar::abs);
+
+    let other = if x <= y && x <= z {
+        Vector::unit_x()
+    } else if y <= z {
+        Vector::unit_y()
+    } else {
+        Vector::unit_z()
+    };
+
+    vector.cross(&other).normalize()
+}
+
+/// Approximate the eigenvector of the smallest eigenvalue of a matrix
+///
+/// The matrix must be symmetric and positive semi-definite. This uses power
+/// iteration on a shifted matrix, whose largest eigenvalue corresponds to the
+/// smallest one of the original matrix.
+fn smallest_eigenvector(matrix: [[f64; 3]; 3], guess: Vector<3>) -> Vector<3> {
+    let trace = matrix[0][0] + matrix[1][1] + matrix[2][2];
+
+    let mut vector = guess.components.map(Scalar::into_f64);
+    for _ in 0..100 {
+        let mut next = [0.; 3];
+        for (i, value) in next.iter_mut().enumerate() {
+            *value = trace * vector[i]
+                - (0..3).map(|j| matrix[i][j] * vector[j]).sum::<f64>();
+        }
+
+        let length = next.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if length == 0. {
+            break;
+        }
+        vector = next.map(|v| v / length);
+    }
+
+    Vector::from(vector)
+}
+
+/// Solve a linear least squares problem, using the normal equations
+///
+/// Each row consists of the coefficients and the right-hand side of one
+/// equation. Returns `None`, if the solution is not unique.
+fn least_squares<const N: usize>(
+    rows: impl IntoIterator<Item = ([f64; N], f64)>,
+) -> Option<[f64; N]> {
+    let mut a = [[0.; N]; N];
+    let mut b = [0.; N];
+
+    for (row, rhs) in rows {
+        for i in 0..N {
+            for j in 0..N {
+                a[i][j] += row[i] * row[j];
+            }
+            b[i] += row[i] * rhs;
+        }
+    }
+
+    // Gaussian elimination with partial pivoting.
+    let scale = a.iter().flatten().fold(0., |max: f64, v| max.max(v.abs()));
+    for column in 0..N {
+        let pivot = (column..N).max_by(|&i, &j| {
+            a[i][column].abs().total_cmp(&a[j][column].abs())
+        })?;
+        if a[pivot][column].abs() <= scale * f64::EPSILON {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+
+        for row in column + 1..N {
+            let factor = a[row][column] / a[column][column];
+            for j in column..N {
+                a[row][j] -= factor * a[column][j];
+            }
+            b[row] -= factor * b[column];
+        }
+    }
+
+    let mut x = [0.; N];
+    for i in (0..N).rev() {
+        let sum = (i + 1..N).map(|j| a[i][j] * x[j]).sum::<f64>();
+        x[i] = (b[i] - sum) / a[i][i];
+    }
+
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_interop::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    use crate::{algorithms::approx::Tolerance, validate::Validate, Core};
+
+    use super::{fit_face, fit_surface, FitSurfaceError, FittedSurface};
+
+    /// Build a grid of triangles, whose vertices are computed from `[s, t]`
+    fn grid(
+        [ns, nt]: [usize; 2],
+        point: impl Fn(f64, f64) -> [f64; 3],
+    ) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+
+        let vertex = |i: usize, j: usize| {
+            Point::from(point(i as f64 / ns as f64, j as f64 / nt as f64))
+        };
+        for i in 0..ns {
+            for j in 0..nt {
+                let [a, b, c, d] = [
+                    vertex(i, j),
+                    vertex(i + 1, j),
+                    vertex(i + 1, j + 1),
+                    vertex(i, j + 1),
+                ];
+                mesh.push_triangle([a, b, c], Color::default());
+                mesh.push_triangle([a, c, d], Color::default());
+            }
+        }
+
+        mesh
+    }
+
+    #[test]
+    fn fit_plane_with_hole() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let mesh = grid([3, 3], |s, t| [s * 3., t * 3., 1.]);
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        // Leave out the center square, to create a hole.
+        let region = (0..mesh.num_triangles()).filter(|&i| i / 2 != 4);
+
+        let fit = fit_surface(&mesh, region.clone(), tolerance)?;
+        assert_eq!(
+            fit.surface,
+            FittedSurface::Plane {
+                origin: Point::from([1.5, 1.5, 1.]),
+                normal: [0., 0., 1.].into(),
+            }
+        );
+
+        let face = fit_face(&mesh, region, tolerance, &mut core)?;
+        assert_eq!(face.region().exterior().half_edges().len(), 12);
+        assert_eq!(face.region().interiors().len(), 1);
+        face.validate_and_return_first_error(&core.layers.geometry)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn fit_cylinder() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let radius = 2.;
+        let mesh = grid([12, 2], |s, t| {
+            let angle = s * PI;
+            [radius * angle.cos(), radius * angle.sin(), t]
+        });
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let fit = fit_surface(&mesh, 0..mesh.num_triangles(), tolerance)?;
+        let FittedSurface::Cylinder {
+            axis,
+            radius: fitted_radius,
+            inward,
+        } = fit.surface
+        else {
+            panic!("Expected cylinder, got {:?}", fit.surface);
+        };
+        assert!((fitted_radius - radius).abs() < Scalar::from(1e-9));
+        assert!(
+            axis.direction().cross(&[0., 0., 1.].into()).magnitude()
+                < Scalar::from(1e-9)
+        );
+        assert!(!inward);
+
+        let face =
+            fit_face(&mesh, 0..mesh.num_triangles(), tolerance, &mut core)?;
+        assert_eq!(face.region().exterior().half_edges().len(), 28);
+        assert!(face.region().interiors().is_empty());
+        face.validate_and_return_first_error(&core.layers.geometry)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn fit_sphere() -> anyhow::Result<()> {
+        let radius = 3.;
+        let mesh = grid([6, 6], |s, t| {
+            let [longitude, latitude] = [s - 0.5, t - 0.5];
+            [
+                radius * latitude.cos() * longitude.cos(),
+                radius * latitude.cos() * longitude.sin(),
+                radius * latitude.sin(),
+            ]
+        });
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let fit = fit_surface(&mesh, 0..mesh.num_triangles(), tolerance)?;
+        let FittedSurface::Sphere {
+            center,
+            radius: fitted_radius,
+            inward,
+        } = fit.surface
+        else {
+            panic!("Expected sphere, got {:?}", fit.surface);
+        };
+        assert!(center.coords.magnitude() < Scalar::from(1e-9));
+        assert!((fitted_radius - radius).abs() < Scalar::from(1e-9));
+        assert!(!inward);
+
+        Ok(())
+    }
+
+    #[test]
+    fn no_fit() -> anyhow::Result<()> {
+        // Two perpendicular planes don't fit any supported surface.
+        let mut mesh = grid([2, 2], |s, t| [s, t, 0.]);
+        mesh.push_triangle(
+            [[0., 0., 0.], [0., 0., 1.], [0., 1., 0.]],
+            Color::default(),
+        );
+
+        let result = fit_surface(
+            &mesh,
+            0..mesh.num_triangles(),
+            Tolerance::from_scalar(0.001)?,
+        );
+        assert_eq!(result, Err(FitSurfaceError::NoFit));
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use fj_math::{Aabb, Line, Point, Scalar, Segment, Vector};
+use fj_math::{Aabb, Line, Point, Segment, Tolerances, Vector};
 
 /// An intersection between a [`Line`] and a [`Segment`]
 #[derive(Debug, Eq, PartialEq)]
@@ -18,7 +18,11 @@ pub enum LineSegmentIntersection {
 
 impl LineSegmentIntersection {
     /// Determine the intersection between a [`Line`] and a [`Segment`]
-    pub fn compute(line: &Line<2>, segment: &Segment<2>) -> Option<Self> {
+    pub fn compute(
+        line: &Line<2>,
+        segment: &Segment<2>,
+        tolerances: &Tolerances,
+    ) -> Option<Self> {
         // Algorithm adapted from Real-Time Collision Detection by Christer
         // Ericson. See section 5.1.9.1, 2D Segment Intersection.
 
@@ -33,10 +37,10 @@ impl LineSegmentIntersection {
         let n_dot_origin = n.dot(&(b - line.origin()));
         let n_dot_direction = n.dot(&line.direction());
 
-        if n_dot_direction == Scalar::ZERO {
+        if tolerances.are_perpendicular(&n, &line.direction()) {
             // `line` and `segment` are parallel
 
-            if n_dot_origin == Scalar::ZERO {
+            if tolerances.is_zero_length(n_dot_origin / n.magnitude()) {
                 // `line` and `segment` are not just parallel, but coincident!
                 return Some(Self::Coincident {
                     points_on_line: segment
@@ -66,7 +70,7 @@ impl LineSegmentIntersection {
 
 #[cfg(test)]
 mod tests {
-    use fj_math::{Line, Point, Scalar, Segment, Vector};
+    use fj_math::{Line, Point, Scalar, Segment, Tolerances, Vector};
 
     use super::LineSegmentIntersection;
 
@@ -79,6 +83,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[1., -1.], [1., 1.]]),
+                &Tolerances::default(),
             ),
             Some(LineSegmentIntersection::Point {
                 point_on_line: Point::from([Scalar::ONE])
@@ -95,6 +100,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[1., 0.], [2., 0.]]),
+                &Tolerances::default(),
             ),
             Some(LineSegmentIntersection::Coincident {
                 points_on_line: [Point::from([1.]), Point::from([2.])],
@@ -111,6 +117,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[1., 1.], [1., 2.]]),
+                &Tolerances::default(),
             ),
             None,
         );
@@ -125,6 +132,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[1., -2.], [1., -1.]]),
+                &Tolerances::default(),
             ),
             None,
         );
@@ -139,6 +147,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[-1., 1.], [1., 1.]]),
+                &Tolerances::default(),
             ),
             None,
         );
@@ -1,6 +1,6 @@
 //! Intersection between a ray and a line segment in 2D
 
-use fj_math::Segment;
+use fj_math::{predicates, Segment, Sign};
 
 use super::{HorizontalRayToTheRight, Intersect};
 
@@ -43,22 +43,9 @@ impl Intersect for (&HorizontalRayToTheRight<2>, &Segment<2>) {
             return Some(RaySegmentIntersection::RayHitsSegmentAndAreParallel);
         }
 
-        let pa = robust::Coord {
-            x: lower.u,
-            y: lower.v,
-        };
-        let pb = robust::Coord {
-            x: upper.u,
-            y: upper.v,
-        };
-        let pc = robust::Coord {
-            x: ray.origin.u,
-            y: ray.origin.v,
-        };
-
-        let orient2d = robust::orient2d(pa, pb, pc);
-
-        if orient2d == 0. {
+        let orient2d = predicates::orient2d(lower, upper, ray.origin);
+
+        if orient2d == Sign::Zero {
             // ray starts on the line
 
             if ray.origin.v == a.v {
@@ -71,7 +58,7 @@ impl Intersect for (&HorizontalRayToTheRight<2>, &Segment<2>) {
             return Some(RaySegmentIntersection::RayStartsOnSegment);
         }
 
-        if orient2d > 0. {
+        if orient2d == Sign::Positive {
             // ray starts left of the line
 
             if ray.origin.v == upper.v {
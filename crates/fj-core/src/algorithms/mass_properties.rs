@@ -0,0 +1,148 @@
+//! # Mass properties of solids
+//!
+//! See [`ComputeMassProperties`].
+
+use fj_interop::Mesh;
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{topology::Solid, Core};
+
+use super::{approx::Tolerance, triangulate::Triangulate};
+
+/// Compute the mass properties of a [`Solid`]
+pub trait ComputeMassProperties {
+    /// Compute the mass properties
+    ///
+    /// The solid is triangulated with the provided tolerance, and its mass
+    /// properties are computed from the triangulation. For solids with curved
+    /// faces, the result is as accurate as that approximation.
+    fn mass_properties(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> MassProperties;
+}
+
+impl ComputeMassProperties for Solid {
+    fn mass_properties(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> MassProperties {
+        let mesh = (self, tolerance.into()).triangulate(core);
+        MassProperties::from_mesh(&mesh)
+    }
+}
+
+/// The mass properties of a solid
+///
+/// Returned by [`ComputeMassProperties::mass_properties`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MassProperties {
+    /// The volume enclosed by the solid
+    pub volume: Scalar,
+
+    /// The centroid of the volume
+    ///
+    /// This is the center of mass, if the solid has a uniform density.
+    pub centroid: Point<3>,
+
+    /// The mass of the solid
+    ///
+    /// The density is taken from the materials of the solid's faces. The mass
+    /// is only available, if all faces have a material with a known density.
+    /// It is exact, if all faces of each shell have the same density.
+    pub mass: Option<Scalar>,
+}
+
+impl MassProperties {
+    /// Compute the mass properties of the volume enclosed by a mesh
+    ///
+    /// The mesh must be closed, and its triangles must be wound
+    /// counter-clockwise, when viewed from outside. If the mesh encloses no
+    /// volume, the centroid is the origin.
+    pub fn from_mesh(mesh: &Mesh<Point<3>>) -> Self {
+        let mut volume = Scalar::ZERO;
+        let mut moment = Vector::from([0., 0., 0.]);
+        let mut mass = Some(Scalar::ZERO);
+
+        for triangle in mesh.triangles() {
+            // Each triangle forms a tetrahedron with the origin. Their signed
+            // volumes add up to the enclosed volume, as the parts outside of
+            // the mesh cancel each other out.
+            let [a, b, c] = triangle.inner.points().map(|point| point.coords);
+            let tetrahedron = a.dot(&b.cross(&c)) / 6.;
+
+            volume += tetrahedron;
+            moment = moment + (a + b + c) * tetrahedron / 4.;
+
+            let density = triangle
+                .material
+                .and_then(|material| mesh.material(material).density);
+            mass = mass.zip(density).map(|(mass, density)| {
+                mass + tetrahedron * Scalar::from(density)
+            });
+        }
+
+        let centroid = if volume == Scalar::ZERO {
+            Point::origin()
+        } else {
+            Point::origin() + moment / volume
+        };
+
+        Self {
+            volume,
+            centroid,
+            mass,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::Material;
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::approx::Tolerance,
+        operations::{build::BuildSolid, material::SetMaterial},
+        topology::Solid,
+        Core,
+    };
+
+    use super::ComputeMassProperties;
+
+    #[test]
+    fn tetrahedron() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [0., 3., 0.], [3., 0., 0.], [0., 0., 3.]],
+            &mut core,
+        );
+        let solid = tetrahedron.solid;
+        let tolerance = Tolerance::from_scalar(0.1)?;
+
+        let properties = solid.mass_properties(tolerance, &mut core);
+        assert_eq!(properties.volume, Scalar::from(4.5));
+        assert_eq!(properties.centroid, Point::from([0.75, 0.75, 0.75]));
+        assert_eq!(properties.mass, None);
+
+        let faces = solid.shells().only().faces().clone();
+        let steel = Material::new("steel").with_density(2.);
+        for face in &faces {
+            face.region().set_material(steel.clone(), &mut core);
+        }
+        let properties = solid.mass_properties(tolerance, &mut core);
+        assert_eq!(properties.mass, Some(Scalar::from(9.)));
+
+        // A single face without density makes the mass unknown.
+        let face = faces.first();
+        face.region()
+            .set_material(Material::new("paper"), &mut core);
+        let properties = solid.mass_properties(tolerance, &mut core);
+        assert_eq!(properties.mass, None);
+
+        Ok(())
+    }
+}
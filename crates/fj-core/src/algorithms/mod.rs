@@ -12,5 +12,12 @@
 
 pub mod approx;
 pub mod bounding_volume;
+pub mod convex_hull;
+pub mod drawing;
+pub mod fit_surface;
 pub mod intersect;
+pub mod mass_properties;
+pub mod polygonize;
+pub mod sdf;
+pub mod silhouette;
 pub mod triangulate;
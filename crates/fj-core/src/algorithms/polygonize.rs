@@ -0,0 +1,206 @@
+//! Conversion of implicit shapes into triangle meshes
+//!
+//! Implicit shapes are defined by a function, usually a signed distance field,
+//! that is negative inside of the shape and positive outside of it. This module
+//! extracts the surface where that function is zero, from values that were
+//! sampled on a [`VoxelGrid`].
+//!
+//! The surface is extracted using marching tetrahedra, a variant of marching
+//! cubes that splits each cell of the grid into six tetrahedra. Unlike marching
+//! cubes, it has no ambiguous cases, and the resulting mesh is closed wherever
+//! the shape is contained within the grid.
+
+use std::collections::BTreeMap;
+
+use fj_interop::{Color, Mesh};
+use fj_math::{Point, Scalar, Triangle};
+
+use super::sdf::VoxelGrid;
+
+/// Extract the surface of an implicit shape as a triangle mesh
+pub trait Polygonize {
+    /// Extract the surface where the sampled values are zero
+    ///
+    /// The triangles are oriented such that their counter-clockwise side faces
+    /// the positive values, meaning the outside of a signed distance field.
+    /// Triangles that would be degenerate are left out.
+    fn polygonize(&self) -> Mesh<Point<3>>;
+}
+
+impl Polygonize for VoxelGrid {
+    fn polygonize(&self) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+
+        // Points on the edges of the grid are computed once, and then reused
+        // by all tetrahedra that share the edge. That way, they are bitwise
+        // identical, and the mesh can share its vertices between triangles.
+        let mut points_on_edges = BTreeMap::new();
+
+        let [nx, ny, nz] = self.resolution;
+        for k in 0..nz - 1 {
+            for j in 0..ny - 1 {
+                for i in 0..nx - 1 {
+                    let corners =
+                        CUBE_CORNERS.map(|[x, y, z]| [i + x, j + y, k + z]);
+
+                    for tetrahedron in CUBE_TETRAHEDRA {
+                        let corners = tetrahedron.map(|index| corners[index]);
+                        polygonize_tetrahedron(
+                            self,
+                            corners,
+                            &mut points_on_edges,
+                            &mut mesh,
+                        );
+                    }
+                }
+            }
+        }
+
+        mesh
+    }
+}
+
+fn polygonize_tetrahedron(
+    grid: &VoxelGrid,
+    corners: [[usize; 3]; 4],
+    points_on_edges: &mut BTreeMap<[[usize; 3]; 2], Point<3>>,
+    mesh: &mut Mesh<Point<3>>,
+) {
+    let (inside, outside): (Vec<_>, Vec<_>) = corners
+        .into_iter()
+        .partition(|&corner| grid.value(corner) < Scalar::ZERO);
+
+    let mut point_on_edge = |a: [usize; 3], b: [usize; 3]| {
+        let mut edge = [a, b];
+        edge.sort();
+
+        *points_on_edges.entry(edge).or_insert_with(|| {
+            let [a, b] = edge;
+            let [value_a, value_b] = [a, b].map(|corner| grid.value(corner));
+            let [a, b] = [a, b].map(|corner| grid.point(corner));
+
+            let t = value_a / (value_a - value_b);
+            a + (b - a) * t
+        })
+    };
+
+    let triangles = match (inside.as_slice(), outside.as_slice()) {
+        ([a], [b, c, d]) | ([b, c, d], [a]) => {
+            vec![[
+                point_on_edge(*a, *b),
+                point_on_edge(*a, *c),
+                point_on_edge(*a, *d),
+            ]]
+        }
+        ([a, b], [c, d]) => {
+            let [ac, ad, bc, bd] = [(a, c), (a, d), (b, c), (b, d)]
+                .map(|(from, to)| point_on_edge(*from, *to));
+            vec![[ac, ad, bd], [ac, bd, bc]]
+        }
+        _ => return,
+    };
+
+    let center_of = |corners: &[[usize; 3]]| {
+        let sum = corners
+            .iter()
+            .map(|&corner| grid.point(corner).coords)
+            .fold(Point::origin(), |sum, coords| sum + coords);
+        Point {
+            coords: sum.coords / Scalar::from_u64(corners.len() as u64),
+        }
+    };
+    let inside_to_outside = center_of(&outside) - center_of(&inside);
+
+    for [a, b, c] in triangles {
+        let Ok(triangle) = Triangle::from_points([a, b, c]) else {
+            continue;
+        };
+
+        // Not using `Triangle::normal`, as that fails for triangles that are
+        // tiny, but not degenerate.
+        let normal = (b - a).cross(&(c - a));
+        let triangle = if normal.dot(&inside_to_outside) < Scalar::ZERO {
+            Triangle::from([a, c, b])
+        } else {
+            triangle
+        };
+
+        mesh.push_triangle(triangle, Color::default());
+    }
+}
+
+/// The corners of a cell of the grid, relative to its first corner
+const CUBE_CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [1, 1, 0],
+    [0, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [1, 1, 1],
+    [0, 1, 1],
+];
+
+/// The tetrahedra that a cell of the grid is split into
+///
+/// All of them share the diagonal from the first to the last corner. Each face
+/// of the cell is split along its diagonal from the corner closest to the
+/// grid's origin, so the tetrahedra of neighboring cells line up.
+const CUBE_TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 5, 1, 6],
+    [0, 1, 2, 6],
+    [0, 2, 3, 6],
+    [0, 3, 7, 6],
+    [0, 7, 4, 6],
+    [0, 4, 5, 6],
+];
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use fj_math::{Aabb, Point, Scalar};
+
+    use crate::algorithms::sdf::VoxelGrid;
+
+    use super::Polygonize;
+
+    #[test]
+    fn polygonize_sphere() {
+        let aabb = Aabb {
+            min: Point::from([-1.5, -1.5, -1.5]),
+            max: Point::from([1.5, 1.5, 1.5]),
+        };
+        let grid = VoxelGrid::from_fn(aabb, [16, 16, 16], |point| {
+            point.coords.magnitude() - Scalar::ONE
+        });
+
+        let mesh = grid.polygonize();
+        assert!(mesh.num_triangles() > 0);
+
+        // All vertices are on the sphere, up to the size of a grid cell.
+        for vertex in mesh.vertices() {
+            let distance = vertex.coords.magnitude() - Scalar::ONE;
+            assert!(distance.abs() < Scalar::from(0.2), "{vertex:?}");
+        }
+
+        // The mesh is closed, and all triangles are oriented the same way, if
+        // each edge is used once in each direction.
+        let mut edges = BTreeMap::new();
+        for [a, b, c] in mesh.triangle_indices() {
+            for edge in [[a, b], [b, c], [c, a]] {
+                *edges.entry(edge).or_insert(0) += 1;
+            }
+        }
+        for (&[a, b], &count) in &edges {
+            assert_eq!(count, 1);
+            assert_eq!(edges.get(&[b, a]), Some(&1));
+        }
+
+        // The triangles face outwards.
+        for triangle in mesh.triangles() {
+            let [a, ..] = triangle.inner.points();
+            assert!(triangle.inner.normal().dot(&a.coords) > Scalar::ZERO);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Signed distance fields of shapes
+//!
+//! A signed distance field maps each point in space to its distance from the
+//! surface of a shape. The distance is negative inside of the shape, and
+//! positive outside of it. This is the representation that SDF-based tools, and
+//! algorithms like lattice infill generation, work with.
+
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::{
+    geometry::{Geometry, GlobalPath},
+    queries::{AllFaces, ClosestPointOnFace},
+    storage::Handle,
+    topology::Face,
+    Core,
+};
+
+use super::{
+    approx::{Approx, Tolerance},
+    triangulate::Triangulate,
+};
+
+/// The signed distance field of a shape
+///
+/// Distances to planar faces are exact. Distances to faces on curved surfaces
+/// are computed from their triangulation, and are only as accurate as the
+/// tolerance that was used to create it.
+///
+/// Whether a point is inside of the shape is also determined using the
+/// triangulation, by computing its winding number. This works for shapes that
+/// are closed, like solids, regardless of the orientation of their faces.
+pub struct SignedDistanceField {
+    planar_faces: Vec<Handle<Face>>,
+    curved_triangles: Vec<Triangle<3>>,
+    triangles: Vec<Triangle<3>>,
+}
+
+impl SignedDistanceField {
+    /// Prepare the signed distance field of a shape
+    ///
+    /// Triangulates the faces of the shape, using the provided tolerance.
+    pub fn new(
+        shape: impl AllFaces,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Self {
+        let tolerance = tolerance.into();
+
+        let mut planar_faces = Vec::new();
+        let mut curved_triangles = Vec::new();
+        let mut triangles = Vec::new();
+
+        for face in shape.all_faces() {
+            let is_planar = matches!(
+                core.layers.geometry.of_surface(face.surface()).u,
+                GlobalPath::Line(_)
+            );
+
+            let mesh = face
+                .clone()
+                .approx_with_cache(
+                    tolerance,
+                    &mut core.approx_cache,
+                    &core.layers.geometry,
+                )
+                .triangulate(core);
+            let face_triangles =
+                mesh.triangles().map(|triangle| triangle.inner);
+
+            if is_planar {
+                planar_faces.push(face);
+                triangles.extend(face_triangles);
+            } else {
+                let start = triangles.len();
+                triangles.extend(face_triangles);
+                curved_triangles.extend_from_slice(&triangles[start..]);
+            }
+        }
+
+        Self {
+            planar_faces,
+            curved_triangles,
+            triangles,
+        }
+    }
+
+    /// Compute the signed distance of a point from the shape's surface
+    ///
+    /// Returns [`Scalar::MAX`], if the shape has no faces.
+    pub fn distance(
+        &self,
+        point: impl Into<Point<3>>,
+        geometry: &Geometry,
+    ) -> Scalar {
+        let point = point.into();
+
+        let to_planar_faces = self.planar_faces.iter().map(|face| {
+            face.closest_point_on_face(point, geometry)
+                .expect("Face is planar")
+                .distance_to(&point)
+        });
+        let to_curved_faces = self
+            .curved_triangles
+            .iter()
+            .map(|triangle| triangle.closest_point(point).distance_to(&point));
+
+        let distance = to_planar_faces
+            .chain(to_curved_faces)
+            .min()
+            .unwrap_or(Scalar::MAX);
+
+        if self.contains(point) {
+            -distance
+        } else {
+            distance
+        }
+    }
+
+    /// Determine whether a point is inside of the shape
+    pub fn contains(&self, point: impl Into<Point<3>>) -> bool {
+        self.winding_number(point.into()).abs() > Scalar::from(0.5)
+    }
+
+    /// Sample the signed distance field on a regular grid
+    ///
+    /// See [`VoxelGrid::from_fn`].
+    pub fn sample(
+        &self,
+        aabb: Aabb<3>,
+        resolution: [usize; 3],
+        geometry: &Geometry,
+    ) -> VoxelGrid {
+        VoxelGrid::from_fn(aabb, resolution, |point| {
+            self.distance(point, geometry)
+        })
+    }
+
+    /// Compute the generalized winding number of the triangles around a point
+    ///
+    /// This is the sum of the solid angles that the triangles span, as seen
+    /// from the point, divided by the solid angle of a full sphere. It is one
+    /// (or minus one) inside of a closed mesh, and zero outside of it.
+    fn winding_number(&self, point: Point<3>) -> Scalar {
+        let solid_angle = self.triangles.iter().fold(
+            Scalar::ZERO,
+            |solid_angle, triangle| {
+                // See Van Oosterom and Strackee, "The Solid Angle of a
+                // Plane Triangle", 1983.
+                let [a, b, c] = triangle.points().map(|vertex| vertex - point);
+                let [la, lb, lc] = [a, b, c].map(|v| v.magnitude());
+
+                let numerator = a.dot(&b.cross(&c));
+                let denominator = la * lb * lc
+                    + a.dot(&b) * lc
+                    + b.dot(&c) * la
+                    + c.dot(&a) * lb;
+
+                solid_angle + numerator.atan2(denominator) * 2.
+            },
+        );
+
+        solid_angle / (Scalar::TAU * 2.)
+    }
+}
+
+/// Values sampled on a regular grid of points
+///
+/// The grid spans an axis-aligned bounding box, with samples on its boundary.
+/// Created by [`SignedDistanceField::sample`] or [`VoxelGrid::from_fn`].
+#[derive(Clone, Debug)]
+pub struct VoxelGrid {
+    /// The bounding box that the grid spans
+    pub aabb: Aabb<3>,
+
+    /// The number of samples along each axis
+    pub resolution: [usize; 3],
+
+    /// The sampled values
+    ///
+    /// Ordered by x, then y, then z, meaning the x index varies fastest. Use
+    /// [`VoxelGrid::value`] to access a value by its indices.
+    pub values: Vec<Scalar>,
+}
+
+impl VoxelGrid {
+    /// Sample a function on a regular grid
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the resolution is smaller than 2 along any axis, as the grid
+    /// needs samples on both sides of the bounding box.
+    pub fn from_fn(
+        aabb: Aabb<3>,
+        resolution: [usize; 3],
+        f: impl Fn(Point<3>) -> Scalar,
+    ) -> Self {
+        assert!(
+            resolution.iter().all(|&n| n >= 2),
+            "Voxel grid needs at least 2 samples per axis"
+        );
+
+        let mut grid = Self {
+            aabb,
+            resolution,
+            values: Vec::new(),
+        };
+
+        let [nx, ny, nz] = resolution;
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    let value = f(grid.point([i, j, k]));
+                    grid.values.push(value);
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Access the point at the provided grid indices
+    pub fn point(&self, indices: [usize; 3]) -> Point<3> {
+        let size = self.aabb.size();
+
+        let offset = [0, 1, 2].map(|axis| {
+            let steps = Scalar::from_u64(self.resolution[axis] as u64 - 1);
+            let index = Scalar::from_u64(indices[axis] as u64);
+
+            size.components[axis] / steps * index
+        });
+
+        self.aabb.min + Vector::from(offset)
+    }
+
+    /// Access the value at the provided grid indices
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the indices are out of bounds.
+    pub fn value(&self, [i, j, k]: [usize; 3]) -> Scalar {
+        let [nx, ny, _] = self.resolution;
+        self.values[i + nx * (j + ny * k)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point, Scalar};
+
+    use crate::{
+        algorithms::approx::Tolerance, operations::build::BuildSolid,
+        topology::Solid, Core,
+    };
+
+    use super::{SignedDistanceField, VoxelGrid};
+
+    #[test]
+    fn signed_distance_to_rounded_box() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = Solid::rounded_box([2., 2., 2.], 0.5, &mut core);
+        let tolerance = Tolerance::from_scalar(0.001)?;
+        let sdf = SignedDistanceField::new(&solid, tolerance, &mut core);
+
+        let distance = |point: [f64; 3]| {
+            sdf.distance(point, &core.layers.geometry).into_f64()
+        };
+
+        // Distances to planar faces are exact.
+        assert_eq!(distance([0., 0., 1.]), -1.);
+        assert_eq!(distance([0., 0., 3.]), 1.);
+        assert_eq!(distance([0., 0., -0.5]), 0.5);
+
+        // The distance to a rounded edge is approximated.
+        let expected = 2_f64.sqrt() - 0.5;
+        assert!((distance([1.5, 1.5, 1.]) - expected).abs() < 0.01);
+
+        Ok(())
+    }
+
+    #[test]
+    fn voxel_grid() {
+        let aabb = Aabb {
+            min: Point::from([0., 0., 0.]),
+            max: Point::from([1., 2., 3.]),
+        };
+        let grid = VoxelGrid::from_fn(aabb, [2, 3, 4], |point| point.z);
+
+        assert_eq!(grid.values.len(), 24);
+        assert_eq!(grid.point([1, 2, 3]), aabb.max);
+        assert_eq!(grid.value([1, 1, 2]), Scalar::from(2.));
+    }
+}
@@ -0,0 +1,814 @@
+//! # Silhouettes of solids
+//!
+//! See [`ComputeSilhouette`].
+
+use std::f64::consts::TAU;
+
+use fj_math::{
+    Circle, Point, PointInPolygon, PolyChain, Polygon, Scalar, Tolerances,
+    Vector,
+};
+
+use crate::{
+    geometry::{Geometry, GlobalPath, SurfaceGeom, SurfaceProfile},
+    queries::SiblingOfHalfEdge,
+    storage::Handle,
+    topology::{Face, HalfEdge, Handedness, Shell, Solid},
+};
+
+use super::approx::{
+    edge::HalfEdgeApproxCache, polyline::ApproxPolylines, Approx, Tolerance,
+};
+
+/// Compute the silhouette of a solid, as seen from a given direction
+pub trait ComputeSilhouette {
+    /// Compute the silhouette, when looking along `direction`
+    ///
+    /// The silhouette consists of the edges at which a face that points
+    /// towards the viewer meets a face that points away, as well as the lines
+    /// along which a curved face turns away from the viewer. Faces that are
+    /// seen edge-on count as pointing away.
+    ///
+    /// All of these lines are projected into a plane that is perpendicular to
+    /// the view direction, and connected into cycles and open chains. In that
+    /// plane, the u-axis points to the right and the v-axis points up. "Up" is
+    /// the positive z-axis, or the positive y-axis, if looking along the
+    /// z-axis.
+    ///
+    /// Curved edges are approximated within the provided tolerance. The
+    /// silhouette lines of cylindrical faces are computed analytically. Those
+    /// of other curved faces, like cones, spheres, and tori, are traced
+    /// through a mesh that covers the face, whose spacing is derived from the
+    /// tolerance.
+    fn silhouette(
+        &self,
+        direction: impl Into<Vector<3>>,
+        tolerance: impl Into<Tolerance>,
+        tolerances: &Tolerances,
+        geometry: &Geometry,
+    ) -> Silhouette;
+}
+
+impl ComputeSilhouette for Solid {
+    fn silhouette(
+        &self,
+        direction: impl Into<Vector<3>>,
+        tolerance: impl Into<Tolerance>,
+        tolerances: &Tolerances,
+        geometry: &Geometry,
+    ) -> Silhouette {
+        let view = View::new(direction.into(), tolerances);
+        let tolerance = tolerance.into();
+
+        let segments =
+            silhouette_lines(self, &view, tolerance, false, geometry)
+                .into_iter()
+                .map(|segment| segment.map(|point| view.project(point)))
+                .collect();
+
+        Silhouette::from_segments(segments, tolerance.inner(), tolerances)
+    }
+}
+
+/// The silhouette of a solid
+///
+/// Returned by [`ComputeSilhouette::silhouette`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Silhouette {
+    /// The closed parts of the silhouette
+    pub cycles: Vec<Polygon<2>>,
+
+    /// The parts of the silhouette that could not be closed
+    ///
+    /// This is empty for a closed solid, unless silhouette lines overlap,
+    /// which can happen if a cylindrical face turns away from the viewer
+    /// exactly at one of its edges.
+    pub chains: Vec<PolyChain<2>>,
+}
+
+impl Silhouette {
+    fn from_segments(
+        mut segments: Vec<[Point<2>; 2]>,
+        tolerance: Scalar,
+        tolerances: &Tolerances,
+    ) -> Self {
+        let tolerance = tolerance.max(tolerances.linear);
+        let is_close =
+            |a: Point<2>, b: Point<2>| a.distance_to(&b) <= tolerance;
+
+        segments.retain(|&[a, b]| !is_close(a, b));
+
+        let mut silhouette = Self::default();
+
+        while let Some([start, end]) = segments.pop() {
+            let mut chain = vec![start, end];
+            let mut is_reversed = false;
+
+            let is_closed = loop {
+                let last = chain[chain.len() - 1];
+
+                if chain.len() > 2 && is_close(last, chain[0]) {
+                    chain.pop();
+                    break true;
+                }
+
+                let next = segments
+                    .iter()
+                    .position(|&[a, b]| is_close(a, last) || is_close(b, last));
+
+                match next {
+                    Some(i) => {
+                        let [a, b] = segments.swap_remove(i);
+                        chain.push(if is_close(a, last) { b } else { a });
+                    }
+                    None if !is_reversed => {
+                        // We might have started in the middle of an open
+                        // chain. Continue in the other direction.
+                        chain.reverse();
+                        is_reversed = true;
+                    }
+                    None => break false,
+                }
+            };
+
+            if is_closed {
+                silhouette.cycles.push(Polygon::from_points(chain));
+            } else {
+                chain.dedup();
+                silhouette.chains.push(PolyChain::from_points(chain));
+            }
+        }
+
+        silhouette
+    }
+}
+
+/// The direction that a solid is looked at from, and the plane it is projected
+/// into
+pub(super) struct View {
+    pub(super) direction: Vector<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+
+    /// How far a normal must point towards the viewer, for a face to count as
+    /// front-facing
+    threshold: Scalar,
+
+    /// The tolerances used to decide whether faces meet at an angle
+    tolerances: Tolerances,
+}
+
+impl View {
+    pub(super) fn new(direction: Vector<3>, tolerances: &Tolerances) -> Self {
+        let direction = direction.normalize();
+
+        let z = Vector::from([0., 0., 1.]);
+        let up = if tolerances.are_parallel(&direction, &z) {
+            Vector::from([0., 1., 0.])
+        } else {
+            z
+        };
+
+        let u = direction.cross(&up).normalize();
+        let v = u.cross(&direction);
+
+        Self {
+            direction,
+            u,
+            v,
+            threshold: tolerances.angular.sin_cos().0,
+            tolerances: *tolerances,
+        }
+    }
+
+    pub(super) fn project(&self, point: Point<3>) -> Point<2> {
+        Point::from([point.coords.dot(&self.u), point.coords.dot(&self.v)])
+    }
+
+    /// Negative, if a face with the given normal points towards the viewer
+    fn facing(&self, normal: Vector<3>) -> Scalar {
+        normal.dot(&self.direction) + self.threshold
+    }
+}
+
+/// A face, with the geometry required to compute its normal
+struct FaceGeom<'r> {
+    face: &'r Face,
+    surface: SurfaceGeom,
+    handedness: Handedness,
+}
+
+impl FaceGeom<'_> {
+    fn facing(&self, point: Point<3>, view: &View) -> Scalar {
+        view.facing(self.normal(point))
+    }
+
+    fn normal(&self, point: Point<3>) -> Vector<3> {
+        self.normal_at(self.surface.project_global_point(point))
+    }
+
+    fn normal_at(&self, point_surface: Point<2>) -> Vector<3> {
+        let normal = self.surface.normal_at(point_surface);
+        match self.handedness {
+            Handedness::RightHanded => normal,
+            Handedness::LeftHanded => -normal,
+        }
+    }
+}
+
+/// Collect the lines that make up the silhouette of a solid, in 3D
+///
+/// If `include_sharp_edges` is `true`, this includes all edges at which faces
+/// meet at an angle, regardless of whether they are part of the silhouette.
+pub(super) fn silhouette_lines(
+    solid: &Solid,
+    view: &View,
+    tolerance: Tolerance,
+    include_sharp_edges: bool,
+    geometry: &Geometry,
+) -> Vec<[Point<3>; 2]> {
+    let mut segments = Vec::new();
+
+    for shell in solid.shells() {
+        silhouette_of_shell(
+            shell,
+            view,
+            tolerance,
+            include_sharp_edges,
+            geometry,
+            &mut segments,
+        );
+    }
+
+    segments
+}
+
+fn silhouette_of_shell(
+    shell: &Shell,
+    view: &View,
+    tolerance: Tolerance,
+    include_sharp_edges: bool,
+    geometry: &Geometry,
+    segments: &mut Vec<[Point<3>; 2]>,
+) {
+    let faces = shell
+        .faces()
+        .iter()
+        .map(|face| FaceGeom {
+            face,
+            surface: *geometry.of_surface(face.surface()),
+            handedness: face.coord_handedness(geometry),
+        })
+        .collect::<Vec<_>>();
+
+    let half_edges = faces
+        .iter()
+        .flat_map(|face| {
+            face.face
+                .region()
+                .all_cycles()
+                .flat_map(|cycle| cycle.half_edges().iter())
+                .map(move |half_edge| (half_edge, face))
+        })
+        .collect::<Vec<_>>();
+
+    let mut cache = HalfEdgeApproxCache::default();
+    let mut visited: Vec<&Handle<HalfEdge>> = Vec::new();
+
+    for &(half_edge, face) in &half_edges {
+        if visited.contains(&half_edge) {
+            continue;
+        }
+
+        let sibling_face = half_edges
+            .iter()
+            .find(|(sibling, _)| {
+                shell.are_siblings(half_edge, sibling, geometry)
+            })
+            .map(|&(sibling, sibling_face)| {
+                visited.push(sibling);
+                sibling_face
+            });
+
+        let points = {
+            let half_edge_geom = geometry.of_half_edge(half_edge);
+            let end = face.surface.point_from_surface_coords(
+                half_edge_geom
+                    .path
+                    .point_from_path_coords(half_edge_geom.boundary.inner[1]),
+            );
+
+            let approx = (half_edge, face.face.surface())
+                .approx_with_cache(tolerance, &mut cache, geometry);
+
+            approx
+                .points
+                .into_iter()
+                .map(|point| point.global_form)
+                .chain([end])
+                .collect::<Vec<_>>()
+        };
+
+        let Some(sibling_face) = sibling_face else {
+            // Without a sibling, the edge is a boundary of the shell, which is
+            // always visible as part of the outline.
+            for points in points.windows(2) {
+                segments.push([points[0], points[1]]);
+            }
+            continue;
+        };
+
+        if include_sharp_edges {
+            let middle = points[points.len() / 2];
+            let [a, b] = [face, sibling_face].map(|face| face.normal(middle));
+
+            if !view.tolerances.are_parallel(&a, &b) {
+                for points in points.windows(2) {
+                    segments.push([points[0], points[1]]);
+                }
+                continue;
+            }
+        }
+
+        // The values are negative, where the respective face points towards
+        // the viewer. The edge is part of the silhouette, where exactly one
+        // of them is.
+        let facing =
+            |point| [face, sibling_face].map(|face| face.facing(point, view));
+        let is_silhouette =
+            |[a, b]: [Scalar; 2]| (a < Scalar::ZERO) != (b < Scalar::ZERO);
+
+        for points in points.windows(2) {
+            let [a, b] = [points[0], points[1]];
+            let [facing_a, facing_b] = [a, b].map(facing);
+
+            match [facing_a, facing_b].map(is_silhouette) {
+                [false, false] => {}
+                [true, true] => {
+                    segments.push([a, b]);
+                }
+                [is_silhouette_at_a, _] => {
+                    // One of the faces turns away from the viewer within this
+                    // segment. Find out where, assuming its facing changes
+                    // linearly along the segment.
+                    let i = if (facing_a[0] < Scalar::ZERO)
+                        != (facing_b[0] < Scalar::ZERO)
+                    {
+                        0
+                    } else {
+                        1
+                    };
+                    let t = facing_a[i] / (facing_a[i] - facing_b[i]);
+                    let transition = a + (b - a) * t;
+
+                    let segment = if is_silhouette_at_a {
+                        [a, transition]
+                    } else {
+                        [transition, b]
+                    };
+                    segments.push(segment);
+                }
+            }
+        }
+    }
+
+    for face in &faces {
+        match (face.surface.u, face.surface.profile) {
+            (GlobalPath::Circle(circle), SurfaceProfile::Linear { taper })
+                if taper == Scalar::ZERO =>
+            {
+                silhouette_of_cylinder(
+                    face, circle, view, tolerance, geometry, segments,
+                );
+            }
+            (GlobalPath::Circle(circle), _) => {
+                silhouette_of_mesh(
+                    face, circle, view, tolerance, geometry, segments,
+                );
+            }
+            (GlobalPath::Line(_), _) => {
+                // Planar faces don't turn away from the viewer.
+            }
+        }
+    }
+}
+
+/// Compute the lines along which a cylindrical face turns away from the viewer
+fn silhouette_of_cylinder(
+    face: &FaceGeom,
+    circle: Circle<3>,
+    view: &View,
+    tolerance: Tolerance,
+    geometry: &Geometry,
+    segments: &mut Vec<[Point<3>; 2]>,
+) {
+    // At the surface coordinate `u`, the normal of the face is proportional to
+    // `b cos u - a sin u` crossed with the v-axis. Its dot product with the
+    // view direction has the form `A cos u - B sin u`, which can be written as
+    // `R cos(u + phi)`.
+    let v = face.surface.v.normalize();
+    let sign = match face.handedness {
+        Handedness::RightHanded => 1.,
+        Handedness::LeftHanded => -1.,
+    };
+    let [a, b] = [circle.b(), circle.a()].map(|axis| {
+        (axis / circle.radius())
+            .cross(&v)
+            .dot(&view.direction)
+            .into_f64()
+            * sign
+    });
+
+    let r = a.hypot(b);
+    let threshold = view.threshold.into_f64();
+    if r <= threshold {
+        // The view direction is (almost) parallel to the cylinder's axis.
+        return;
+    }
+
+    let phi = b.atan2(a);
+    let angle = (-threshold / r).acos();
+
+    let polylines = face.face.approx_polylines(tolerance, geometry);
+    let polylines = [&polylines.exterior]
+        .into_iter()
+        .chain(&polylines.interiors)
+        .map(|polyline| &polyline.points_surface)
+        .collect::<Vec<_>>();
+
+    let [min_u, max_u] = polylines
+        .iter()
+        .flat_map(|points| points.iter())
+        .fold([f64::INFINITY, f64::NEG_INFINITY], |[min, max], point| {
+            let u = point.u.into_f64();
+            [min.min(u), max.max(u)]
+        });
+
+    for u in [angle - phi, -angle - phi] {
+        // The surface coordinates of the face could be anywhere on the circle,
+        // so consider every full turn that overlaps them.
+        let mut u = u - ((u - min_u) / TAU).floor() * TAU;
+
+        while u <= max_u {
+            let u_scalar = Scalar::from(u);
+
+            let mut crossings = Vec::new();
+            for points in &polylines {
+                for points in points.windows(2) {
+                    let [p, q] = [points[0], points[1]];
+
+                    // Count each crossing once, even if it happens at a
+                    // point of the polyline.
+                    if (p.u <= u_scalar) != (q.u <= u_scalar) {
+                        let t = (u_scalar - p.u) / (q.u - p.u);
+                        crossings.push(p.v + (q.v - p.v) * t);
+                    }
+                }
+            }
+            crossings.sort();
+
+            for pair in crossings.chunks_exact(2) {
+                segments.push([pair[0], pair[1]].map(|v| {
+                    face.surface.point_from_surface_coords([u_scalar, v])
+                }));
+            }
+
+            u += TAU;
+        }
+    }
+}
+
+/// Compute the lines along which a curved face turns away from the viewer
+///
+/// This works for any face whose u-axis is a circle, but it is only used for
+/// those that [`silhouette_of_cylinder`] doesn't cover. The face is covered
+/// with a mesh of triangles in surface coordinates, and the silhouette is
+/// traced through it, by interpolating where the face's facing changes sign
+/// along the edges of the triangles.
+fn silhouette_of_mesh(
+    face: &FaceGeom,
+    circle: Circle<3>,
+    view: &View,
+    tolerance: Tolerance,
+    geometry: &Geometry,
+    segments: &mut Vec<[Point<3>; 2]>,
+) {
+    let polylines = face.face.approx_polylines(tolerance, geometry);
+    let polygons = [&polylines.exterior]
+        .into_iter()
+        .chain(&polylines.interiors)
+        .map(|polyline| Polygon::from_points(polyline.points_surface.clone()))
+        .collect::<Vec<_>>();
+
+    let Some([min, max]) = polygons
+        .iter()
+        .flat_map(|polygon| polygon.points())
+        .fold(None, |range: Option<[Point<2>; 2]>, &point| {
+            let [min, max] = range.unwrap_or([point, point]);
+            Some([
+                Point::from([min.u.min(point.u), min.v.min(point.v)]),
+                Point::from([max.u.max(point.u), max.v.max(point.v)]),
+            ])
+        })
+    else {
+        return;
+    };
+
+    // The mesh is spaced like the approximation of the circle, along both
+    // axes. A point on the v-axis moves `length_v` per unit.
+    let radius = circle.radius();
+    let increment = Scalar::from(2.)
+        * (Scalar::ONE - (tolerance.inner() / radius).min(Scalar::ONE)).acos();
+    let length_v = match face.surface.profile {
+        SurfaceProfile::Linear { taper } => {
+            (face.surface.v + circle.a() * taper).magnitude()
+        }
+        SurfaceProfile::Spherical => radius,
+        SurfaceProfile::Toroidal => face.surface.v.magnitude(),
+    };
+    let steps = [
+        (max.u - min.u) / increment,
+        (max.v - min.v) * length_v / (radius * increment),
+    ]
+    .map(|steps| (steps.ceil().into_u64() as usize).max(1));
+
+    let point_at = |i: usize, j: usize| {
+        let [u, v] = [(min.u, max.u, i, steps[0]), (min.v, max.v, j, steps[1])]
+            .map(|(min, max, i, steps)| {
+                min + (max - min) * Scalar::from_u64(i as u64)
+                    / Scalar::from_u64(steps as u64)
+            });
+        Point::from([u, v])
+    };
+    let nodes = (0..=steps[1])
+        .map(|j| {
+            (0..=steps[0])
+                .map(|i| {
+                    let point = point_at(i, j);
+                    (point, view.facing(face.normal_at(point)))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for j in 0..steps[1] {
+        for i in 0..steps[0] {
+            let [a, b, c, d] = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)]
+                .map(|(i, j)| nodes[j][i]);
+
+            for triangle in [[a, b, c], [a, c, d]] {
+                let crossings = [[0, 1], [1, 2], [2, 0]]
+                    .into_iter()
+                    .filter_map(|[x, y]| {
+                        let [(p, facing_p), (q, facing_q)] =
+                            [triangle[x], triangle[y]];
+                        if (facing_p < Scalar::ZERO)
+                            == (facing_q < Scalar::ZERO)
+                        {
+                            return None;
+                        }
+
+                        // Always interpolate from the same end, so the
+                        // triangles on both sides of this edge agree on the
+                        // point.
+                        let [(p, facing_p), (q, facing_q)] =
+                            if facing_p < Scalar::ZERO {
+                                [(p, facing_p), (q, facing_q)]
+                            } else {
+                                [(q, facing_q), (p, facing_p)]
+                            };
+                        let t = facing_p / (facing_p - facing_q);
+                        Some(p + (q - p) * t)
+                    })
+                    .collect::<Vec<_>>();
+
+                let [p, q] = crossings[..] else {
+                    continue;
+                };
+                for [p, q] in clip_to_face(p, q, &polygons) {
+                    segments.push([p, q].map(|point| {
+                        face.surface.point_from_surface_coords(point)
+                    }));
+                }
+            }
+        }
+    }
+}
+
+/// Clip a line segment in surface coordinates to the inside of a face
+///
+/// The first polygon is the exterior of the face, all others are its holes.
+fn clip_to_face(
+    p: Point<2>,
+    q: Point<2>,
+    polygons: &[Polygon<2>],
+) -> Vec<[Point<2>; 2]> {
+    let pq = q - p;
+
+    let mut ts = vec![Scalar::ZERO, Scalar::ONE];
+    for [a, b] in polygons.iter().flat_map(|polygon| polygon.edges()) {
+        let ab = b - a;
+        let denominator = pq.cross2d(&ab);
+        if denominator == Scalar::ZERO {
+            continue;
+        }
+
+        let ap = a - p;
+        let t = ap.cross2d(&ab) / denominator;
+        let s = ap.cross2d(&pq) / denominator;
+        if t > Scalar::ZERO
+            && t < Scalar::ONE
+            && s >= Scalar::ZERO
+            && s <= Scalar::ONE
+        {
+            ts.push(t);
+        }
+    }
+    ts.sort();
+
+    ts.windows(2)
+        .filter_map(|ts| {
+            let [start, end] = [ts[0], ts[1]].map(|t| p + pq * t);
+
+            let middle = start + (end - start) / 2.;
+            let is_inside = polygons.iter().enumerate().all(|(i, polygon)| {
+                let is_exterior = i == 0;
+                match polygon.contains_point(middle) {
+                    PointInPolygon::Inside => is_exterior,
+                    PointInPolygon::Outside => !is_exterior,
+                    PointInPolygon::OnBoundary => true,
+                }
+            });
+
+            is_inside.then_some([start, end])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{operations::build::BuildSolid, topology::Solid, Core};
+
+    use super::ComputeSilhouette;
+
+    #[test]
+    fn cube() {
+        let mut core = Core::new();
+        let tolerances = core.tolerances();
+
+        let cube = Solid::rounded_box([1., 1., 1.], 0., &mut core);
+
+        let silhouette = cube.silhouette(
+            [0., 0., -1.],
+            0.01,
+            &tolerances,
+            &core.layers.geometry,
+        );
+        assert_eq!(silhouette.cycles.len(), 1);
+        assert!(silhouette.chains.is_empty());
+
+        let area = silhouette.cycles[0].signed_area().abs();
+        assert!((area - Scalar::ONE).abs() < Scalar::from(1e-12));
+
+        // Looking along a diagonal, the outline is a regular hexagon. Only
+        // the edges that don't touch the nearest or farthest vertex are part
+        // of it.
+        let silhouette = cube.silhouette(
+            [1., 1., 1.],
+            0.01,
+            &tolerances,
+            &core.layers.geometry,
+        );
+        assert_eq!(silhouette.cycles.len(), 1);
+        assert!(silhouette.chains.is_empty());
+
+        let hexagon = &silhouette.cycles[0];
+        assert_eq!(hexagon.points().len(), 6);
+        assert!(hexagon.is_convex());
+
+        let area = hexagon.signed_area().abs();
+        assert!((area - Scalar::from(3f64.sqrt())).abs() < Scalar::from(1e-12));
+    }
+
+    #[test]
+    fn cylinder() {
+        let mut core = Core::new();
+        let tolerances = core.tolerances();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+
+        // From the side, the outline is a rectangle made up of the two
+        // silhouette lines of the side face and two halves of the circular
+        // edges.
+        let silhouette = cylinder.silhouette(
+            [0., 1., 0.],
+            0.001,
+            &tolerances,
+            &core.layers.geometry,
+        );
+        assert_eq!(silhouette.cycles.len(), 1);
+        assert!(silhouette.chains.is_empty());
+
+        let area = silhouette.cycles[0].signed_area().abs();
+        assert!((area - Scalar::from(4.)).abs() < Scalar::from(0.01));
+
+        // From above, the outline is the top edge.
+        let tolerance = 0.001;
+        let silhouette = cylinder.silhouette(
+            [0., 0., -1.],
+            tolerance,
+            &tolerances,
+            &core.layers.geometry,
+        );
+        assert_eq!(silhouette.cycles.len(), 1);
+        assert!(silhouette.chains.is_empty());
+
+        let area = silhouette.cycles[0].signed_area().abs();
+        assert!(area <= Scalar::PI);
+        assert!(area > Scalar::PI * (1. - 2. * tolerance));
+    }
+
+    #[test]
+    fn cone() {
+        let mut core = Core::new();
+        let tolerances = core.tolerances();
+
+        let cone = Solid::cone(1., 0., 3., &mut core);
+
+        // From the side, the outline is a triangle, made up of two silhouette
+        // lines of the side face, which meet at the apex, and half of the
+        // bottom edge.
+        let tolerance = 0.001;
+        let silhouette = cone.silhouette(
+            [1., 0., 0.],
+            tolerance,
+            &tolerances,
+            &core.layers.geometry,
+        );
+        assert_eq!(silhouette.cycles.len(), 1, "{silhouette:?}");
+        assert!(silhouette.chains.is_empty());
+
+        let area = silhouette.cycles[0].signed_area().abs();
+        assert!((area - Scalar::from(3.)).abs() < Scalar::from(0.01));
+    }
+
+    #[test]
+    fn sphere() {
+        let mut core = Core::new();
+        let tolerances = core.tolerances();
+
+        let sphere = Solid::sphere(2., &mut core);
+
+        // The sphere has no edges, except for its seam. From any direction,
+        // the outline is a circle that runs through the face.
+        let tolerance = 0.001;
+        for direction in [[0., 0., -1.], [1., 0., 0.], [1., 1., 1.]] {
+            let silhouette = sphere.silhouette(
+                direction,
+                tolerance,
+                &tolerances,
+                &core.layers.geometry,
+            );
+            assert_eq!(silhouette.cycles.len(), 1, "{silhouette:?}");
+            assert!(silhouette.chains.is_empty());
+
+            let area = silhouette.cycles[0].signed_area().abs();
+            let circle = Scalar::PI * 4.;
+            assert!((area - circle).abs() < circle * 0.01, "{area}");
+        }
+    }
+
+    #[test]
+    fn washer() {
+        let mut core = Core::new();
+        let tolerances = core.tolerances();
+
+        let washer = Solid::washer(2., 1., 0.25, &mut core);
+
+        // From above, the hole is part of the outline.
+        let tolerance = 0.01;
+        let silhouette = washer.silhouette(
+            [0., 0., -1.],
+            tolerance,
+            &tolerances,
+            &core.layers.geometry,
+        );
+        assert_eq!(silhouette.cycles.len(), 2);
+        assert!(silhouette.chains.is_empty());
+
+        let mut areas = silhouette
+            .cycles
+            .iter()
+            .map(|cycle| cycle.signed_area().abs())
+            .collect::<Vec<_>>();
+        areas.sort();
+
+        for (area, radius) in areas.into_iter().zip([1., 2.]) {
+            let circle = Scalar::PI * radius * radius;
+            assert!(area <= circle);
+            assert!(area > circle * (1. - 2. * tolerance / radius));
+        }
+    }
+}
@@ -3,13 +3,24 @@ use std::collections::BTreeMap;
 use fj_math::{Point, Scalar, Triangle, Winding};
 use spade::HasPosition;
 
-use crate::{algorithms::approx::cycle::CycleApprox, topology::Handedness};
+use crate::{
+    algorithms::approx::{cycle::CycleApprox, ApproxPoint},
+    geometry::{GlobalPath, SurfaceGeom},
+    topology::Handedness,
+};
+
+use super::TriangulateError;
 
 /// Create a Delaunay triangulation of all points
-pub fn triangulate(
-    cycles: impl IntoIterator<Item = CycleApprox>,
+///
+/// The cycles constrain the triangulation, while `surface_points` are inserted
+/// without any constraints.
+pub fn triangulate<'r>(
+    cycles: impl IntoIterator<Item = &'r CycleApprox>,
+    surface_points: &[ApproxPoint<2>],
+    surface: &SurfaceGeom,
     coord_handedness: Handedness,
-) -> Vec<[TriangulationPoint; 3]> {
+) -> Result<Vec<[TriangulationPoint; 3]>, TriangulateError> {
     use spade::Triangulation as _;
 
     let mut triangulation = spade::ConstrainedDelaunayTriangulation::<_>::new();
@@ -24,11 +35,10 @@ pub fn triangulate(
                 Some(handle) => *handle,
                 None => {
                     let handle = triangulation
-                        .insert(TriangulationPoint {
-                            point_surface: point.local_form,
-                            point_global: point.global_form,
-                        })
-                        .expect("Inserted invalid point into triangulation");
+                        .insert(TriangulationPoint::new(point, surface))
+                        .map_err(|_| TriangulateError::InvalidPoint {
+                            point: point.local_form,
+                        })?;
 
                     points.insert(point, handle);
 
@@ -44,16 +54,21 @@ pub fn triangulate(
         }
     }
 
+    for point in surface_points {
+        triangulation
+            .insert(TriangulationPoint::new(*point, surface))
+            .map_err(|_| TriangulateError::InvalidPoint {
+                point: point.local_form,
+            })?;
+    }
+
     let mut triangles = Vec::new();
     for triangle in triangulation.inner_faces() {
         let [v0, v1, v2] = triangle.vertices().map(|vertex| *vertex.data());
-        let triangle_winding = Triangle::<2>::from_points([
-            v0.point_surface,
-            v1.point_surface,
-            v2.point_surface,
-        ])
-        .expect("invalid triangle")
-        .winding();
+        let points = [v0, v1, v2].map(|point| point.point_surface);
+        let triangle_winding = Triangle::<2>::from_points(points)
+            .map_err(|_| TriangulateError::DegenerateTriangle { points })?
+            .winding();
 
         let required_winding = match coord_handedness {
             Handedness::LeftHanded => Winding::Cw,
@@ -69,13 +84,77 @@ pub fn triangulate(
         triangles.push(triangle);
     }
 
-    triangles
+    Ok(triangles)
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct TriangulationPoint {
     pub point_surface: Point<2>,
     pub point_global: Point<3>,
+
+    /// The position of the point in the plane it is triangulated in
+    ///
+    /// This is the point in surface coordinates, except on spheres. Surface
+    /// coordinates stretch the sphere more and more towards its poles, and a
+    /// Delaunay triangulation in them results in long and thin triangles
+    /// there. Instead, points on a sphere are projected using the Mercator
+    /// projection, which keeps angles, and with that the shape of triangles,
+    /// intact.
+    ///
+    /// On tori, the circles along the u-axis are shorter on the inside. Points
+    /// on a torus are projected the same way, stretching the v-axis where they
+    /// are shorter, and shrinking it where they are longer.
+    position: Point<2>,
+}
+
+impl TriangulationPoint {
+    fn new(point: ApproxPoint<2>, surface: &SurfaceGeom) -> Self {
+        let point_surface = point.local_form;
+
+        let position = if surface.is_sphere() {
+            // The poles are infinitely far away in the Mercator projection.
+            // Moving them a tiny bit towards the equator still puts them
+            // beyond every other point.
+            let max_latitude = std::f64::consts::FRAC_PI_2 - 1e-9;
+            let latitude = point_surface
+                .v
+                .into_f64()
+                .clamp(-max_latitude, max_latitude);
+
+            let y = (std::f64::consts::FRAC_PI_4 + latitude / 2.).tan().ln();
+            Point::from([point_surface.u, Scalar::from_f64(y)])
+        } else if surface.is_torus() {
+            let GlobalPath::Circle(u) = surface.u else {
+                unreachable!("Torus must have a circle as its u-axis");
+            };
+
+            // `y` is the integral of `r / (R + r * cos(v))`, where `R` is the
+            // radius of the u-axis, and `r` the radius of the tube. Each full
+            // turn around the tube is handled separately, to keep `y`
+            // continuous.
+            let [major, minor] =
+                [u.radius(), surface.v.magnitude()].map(Scalar::into_f64);
+            let root = (major * major - minor * minor).sqrt();
+            let ratio = ((major - minor) / (major + minor)).sqrt();
+
+            let v = point_surface.v.into_f64();
+            let turns = (v / std::f64::consts::TAU).round();
+            let v = v - turns * std::f64::consts::TAU;
+
+            let y = minor / root
+                * ((ratio * (v / 2.).tan()).atan() * 2.
+                    + turns * std::f64::consts::TAU);
+            Point::from([point_surface.u, Scalar::from_f64(y)])
+        } else {
+            point_surface
+        };
+
+        Self {
+            point_surface,
+            point_global: point.global_form,
+            position,
+        }
+    }
 }
 
 // Enables the use of `LocalPoint` in the triangulation.
@@ -84,8 +163,8 @@ impl HasPosition for TriangulationPoint {
 
     fn position(&self) -> spade::Point2<Self::Scalar> {
         spade::Point2 {
-            x: self.point_surface.u,
-            y: self.point_surface.v,
+            x: self.position.u,
+            y: self.position.v,
         }
     }
 }
@@ -3,95 +3,453 @@
 mod delaunay;
 mod polygon;
 
-use fj_interop::Mesh;
-use fj_math::Point;
-
-use crate::{operations::presentation::GetColor, Core};
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+};
+
+use fj_interop::{FaceId, Mesh, Progress, Triangle};
+use fj_math::{Aabb, Point, Segment};
+
+use crate::{
+    algorithms::bounding_volume::BoundingVolume,
+    operations::{material::GetMaterial, presentation::GetColor},
+    queries::AllFaces,
+    storage::Handle,
+    topology::{Face, Topology},
+    Core,
+};
 
 use self::polygon::Polygon;
 
-use super::approx::{face::FaceApprox, Approx, Tolerance};
+use super::approx::{
+    edge::HalfEdgeApproxCache,
+    face::{check_approximation, FaceApprox},
+    Approx, Tolerance,
+};
 
 /// Triangulate a shape
+///
+/// # Determinism
+///
+/// Triangulating the same shape with the same tolerance results in the same
+/// mesh, down to the order of its vertices and triangles. This holds across
+/// runs and across platforms, which makes it possible to compare exported
+/// files directly.
+///
+/// Faces are triangulated in the order in which they appear in their shell,
+/// and shells in the order in which they appear in their solid. Nothing in
+/// the output depends on the addresses of objects in memory, with one
+/// exception: the [`FaceId`] of each triangle. Those identify faces within a
+/// single run only.
+///
+/// # Progress
+///
+/// Progress is reported to [`Core::progress`], once per face. If that
+/// cancels the triangulation, the returned mesh only contains the faces that
+/// have been triangulated up to that point.
+///
+/// # Failures
+///
+/// Each face is triangulated on its own, so a face that fails to triangulate
+/// doesn't affect the others. This includes faces whose triangulation panics.
+/// Use [`Triangulate::triangulate_partial`] to get the triangles of all other
+/// faces, along with the faces that failed.
+///
+/// Failures while approximating the shape are not isolated like that, as the
+/// shape is approximated as a whole.
 pub trait Triangulate: Sized {
     /// Triangulate the shape
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any face fails to triangulate.
     fn triangulate(self, core: &mut Core) -> Mesh<Point<3>> {
+        let triangulation = self.triangulate_partial(core);
+
+        if let Some(failed) = triangulation.failed_faces.first() {
+            panic!("Failed to triangulate face: {}", failed.error);
+        }
+
+        triangulation.mesh
+    }
+
+    /// Triangulate the shape, skipping any faces that fail to triangulate
+    fn triangulate_partial(self, core: &mut Core) -> Triangulation {
         let mut mesh = Mesh::new();
-        self.triangulate_into_mesh(&mut mesh, core);
-        mesh
+        let failed_faces = self.triangulate_into_mesh(&mut mesh, core);
+
+        Triangulation { mesh, failed_faces }
     }
 
     /// Triangulate a partial shape into the provided mesh
     ///
+    /// Returns the faces that failed to triangulate. Their triangles are not
+    /// added to the mesh.
+    ///
     /// This is a low-level method, intended for implementation of
     /// `Triangulate`. Most callers should prefer [`Triangulate::triangulate`].
-    fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>, core: &mut Core);
+    fn triangulate_into_mesh(
+        self,
+        mesh: &mut Mesh<Point<3>>,
+        core: &mut Core,
+    ) -> Vec<FailedFace>;
+}
+
+/// The result of [`Triangulate::triangulate_partial`]
+#[derive(Debug)]
+pub struct Triangulation {
+    /// The triangles of all faces that were triangulated successfully
+    pub mesh: Mesh<Point<3>>,
+
+    /// The faces that failed to triangulate
+    pub failed_faces: Vec<FailedFace>,
+}
+
+/// A face that failed to triangulate
+#[derive(Debug)]
+pub struct FailedFace {
+    /// The face
+    pub face: Handle<Face>,
+
+    /// The approximated boundary of the face
+    ///
+    /// The face has no triangles, so this can be used to show where it is.
+    pub boundary: Vec<Segment<3>>,
+
+    /// The reason why the face failed to triangulate
+    pub error: TriangulateError,
+}
+
+/// Error triangulating a face
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum TriangulateError {
+    /// A point of the face's approximation can't be triangulated
+    ///
+    /// This happens, if its coordinates are not finite, or too large.
+    #[error("Can't triangulate point `{point:?}`")]
+    InvalidPoint {
+        /// The point, in surface coordinates
+        point: Point<2>,
+    },
+
+    /// The triangulation contains a triangle that doesn't span any area
+    #[error("Triangulation contains degenerate triangle `{points:?}`")]
+    DegenerateTriangle {
+        /// The points of the triangle, in surface coordinates
+        points: [Point<2>; 3],
+    },
+
+    /// Triangulating the face panicked
+    #[error("Panic while triangulating face: {message}")]
+    Panic {
+        /// The message of the panic
+        message: String,
+    },
 }
 
 impl<T> Triangulate for (T, Tolerance)
 where
-    T: Approx,
+    T: Approx<Cache = HalfEdgeApproxCache>,
     T::Approximation: IntoIterator<Item = FaceApprox>,
 {
-    fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>, core: &mut Core) {
+    fn triangulate_into_mesh(
+        self,
+        mesh: &mut Mesh<Point<3>>,
+        core: &mut Core,
+    ) -> Vec<FailedFace> {
         let (approx, tolerance) = self;
+        let span = tracing::debug_span!(
+            "triangulate",
+            faces = tracing::field::Empty,
+            triangles = tracing::field::Empty,
+        )
+        .entered();
+        let num_triangles = mesh.num_triangles();
+        let mut failed_faces = Vec::new();
+
+        if core.progress.step("approximate", 0, 1).is_err() {
+            return failed_faces;
+        }
+        let approx = approx
+            .approx_with_cache(
+                tolerance,
+                &mut core.approx_cache,
+                &core.layers.geometry,
+            )
+            .into_iter()
+            .collect::<Vec<_>>();
+        check_approximation(&approx, &core.tolerances());
+        core.progress.report(Progress {
+            task: "approximate",
+            done: 1,
+            total: 1,
+        });
+
+        let num_faces = approx.len();
+        span.record("faces", num_faces);
+        for (i, approx) in approx.into_iter().enumerate() {
+            if core.progress.step("triangulate", i, num_faces).is_err() {
+                return failed_faces;
+            }
+            failed_faces.extend(approx.triangulate_into_mesh(mesh, core));
+        }
+        core.progress.report(Progress {
+            task: "triangulate",
+            done: num_faces,
+            total: num_faces,
+        });
+        span.record("triangles", mesh.num_triangles() - num_triangles);
 
-        let approx = approx.approx(tolerance, &core.layers.geometry);
+        failed_faces
+    }
+}
+
+/// Triangulate only those faces of a shape that are in a region of interest
+///
+/// This is useful for previewing parts of large models, where triangulating the
+/// whole model would take too long. Faces are selected by their bounding box,
+/// before they are approximated, so faces that are left out are cheap.
+pub trait TriangulateFiltered: Sized {
+    /// Triangulate the faces whose bounding box passes the filter
+    ///
+    /// This can be used with any spatial filter, like a camera frustum. Faces
+    /// whose bounding box can't be computed, are left out.
+    ///
+    /// Progress is reported like for [`Triangulate`], except that faces are
+    /// approximated one by one, so the progress covers that too.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any face fails to triangulate.
+    fn triangulate_filtered(
+        self,
+        filter: impl Fn(&Aabb<3>) -> bool,
+        core: &mut Core,
+    ) -> Mesh<Point<3>> {
+        let triangulation = self.triangulate_filtered_partial(filter, core);
 
-        for approx in approx {
-            approx.triangulate_into_mesh(mesh, core);
+        if let Some(failed) = triangulation.failed_faces.first() {
+            panic!("Failed to triangulate face: {}", failed.error);
         }
+
+        triangulation.mesh
+    }
+
+    /// Triangulate the faces whose bounding box passes the filter, skipping
+    /// any faces that fail to triangulate
+    ///
+    /// Like [`Triangulate::triangulate_partial`], failures are isolated to the
+    /// face they happen in.
+    fn triangulate_filtered_partial(
+        self,
+        filter: impl Fn(&Aabb<3>) -> bool,
+        core: &mut Core,
+    ) -> Triangulation;
+
+    /// Triangulate the faces whose bounding box intersects the provided one
+    ///
+    /// Convenience wrapper around [`TriangulateFiltered::triangulate_filtered`].
+    fn triangulate_within(
+        self,
+        region: &Aabb<3>,
+        core: &mut Core,
+    ) -> Mesh<Point<3>> {
+        self.triangulate_filtered(|aabb| aabb.intersects(region), core)
+    }
+}
+
+impl<T> TriangulateFiltered for (T, Tolerance)
+where
+    T: AllFaces,
+{
+    fn triangulate_filtered_partial(
+        self,
+        filter: impl Fn(&Aabb<3>) -> bool,
+        core: &mut Core,
+    ) -> Triangulation {
+        let (shape, tolerance) = self;
+        let mut mesh = Mesh::new();
+        let mut failed_faces = Vec::new();
+
+        let faces = shape.all_faces().collect::<Vec<_>>();
+        let span = tracing::debug_span!(
+            "triangulate_filtered",
+            faces = faces.len(),
+            triangles = tracing::field::Empty,
+        )
+        .entered();
+        for (i, face) in faces.iter().cloned().enumerate() {
+            if core.progress.step("triangulate", i, faces.len()).is_err() {
+                return Triangulation { mesh, failed_faces };
+            }
+
+            let Some(aabb) = face.aabb(&core.layers.geometry) else {
+                continue;
+            };
+            if !filter(&aabb) {
+                continue;
+            }
+
+            let approx = face.approx_with_cache(
+                tolerance,
+                &mut core.approx_cache,
+                &core.layers.geometry,
+            );
+            failed_faces.extend(approx.triangulate_into_mesh(&mut mesh, core));
+        }
+        core.progress.report(Progress {
+            task: "triangulate",
+            done: faces.len(),
+            total: faces.len(),
+        });
+        span.record("triangles", mesh.num_triangles());
+
+        Triangulation { mesh, failed_faces }
     }
 }
 
 impl Triangulate for FaceApprox {
-    fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>, core: &mut Core) {
-        let face_as_polygon = Polygon::new()
-            .with_exterior(
-                self.exterior
-                    .points()
-                    .into_iter()
-                    .map(|point| point.local_form),
-            )
-            .with_interiors(self.interiors.iter().map(|interior| {
-                interior.points().into_iter().map(|point| point.local_form)
-            }));
-
-        let cycles = [self.exterior].into_iter().chain(self.interiors);
-        let mut triangles =
-            delaunay::triangulate(cycles, self.coord_handedness);
-        triangles.retain(|triangle| {
-            face_as_polygon
-                .contains_triangle(triangle.map(|point| point.point_surface))
+    fn triangulate_into_mesh(
+        self,
+        mesh: &mut Mesh<Point<3>>,
+        core: &mut Core,
+    ) -> Vec<FailedFace> {
+        let span = tracing::trace_span!(
+            "triangulate_face",
+            triangles = tracing::field::Empty,
+        )
+        .entered();
+
+        let cycles = || [&self.exterior].into_iter().chain(&self.interiors);
+        let surface = core.layers.geometry.of_surface(self.face.surface());
+
+        // Nothing that this closure touches is used after a panic, except for
+        // the cycles, which it doesn't modify.
+        let triangles = panic::catch_unwind(AssertUnwindSafe(|| {
+            let face_as_polygon = Polygon::new()
+                .with_exterior(
+                    self.exterior
+                        .points()
+                        .into_iter()
+                        .map(|point| point.local_form),
+                )
+                .with_interiors(self.interiors.iter().map(|interior| {
+                    interior.points().into_iter().map(|point| point.local_form)
+                }));
+
+            let mut triangles = delaunay::triangulate(
+                cycles(),
+                &self.surface_points,
+                surface,
+                self.coord_handedness,
+            )?;
+            triangles.retain(|triangle| {
+                face_as_polygon.contains_triangle(
+                    triangle.map(|point| point.point_surface),
+                )
+            });
+
+            // Where a face touches the pole of a sphere, distinct points in
+            // surface coordinates can be the same point in global ones. The
+            // triangles between them have no area, and must not end up in the
+            // mesh.
+            triangles.retain(|[a, b, c]| {
+                a.point_global != b.point_global
+                    && b.point_global != c.point_global
+                    && c.point_global != a.point_global
+            });
+
+            Ok(triangles)
+        }))
+        .unwrap_or_else(|payload| {
+            Err(TriangulateError::Panic {
+                message: panic_message(payload),
+            })
         });
 
+        let triangles = match triangles {
+            Ok(triangles) => triangles,
+            Err(error) => {
+                return vec![FailedFace {
+                    boundary: cycles()
+                        .flat_map(|cycle| cycle.segments())
+                        .collect(),
+                    face: self.face,
+                    error,
+                }];
+            }
+        };
+
+        span.record("triangles", triangles.len());
+
         let color = self.face.region().get_color(core).unwrap_or_default();
+        let face = FaceId(self.face.id().0);
+        let material = self
+            .face
+            .region()
+            .get_material(core)
+            .map(|material| mesh.add_material(material));
+
+        mesh.extend(triangles.into_iter().map(|triangle| Triangle {
+            inner: triangle.map(|point| point.point_global).into(),
+            color,
+            face: Some(face),
+            material,
+        }));
+
+        Vec::new()
+    }
+}
 
-        for triangle in triangles {
-            let points = triangle.map(|point| point.point_global);
-            mesh.push_triangle(points, color);
-        }
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
     }
 }
 
+/// Find the face that a triangle of a mesh was created from
+///
+/// Triangles created by [`Triangulate`] carry a [`FaceId`]. This function can
+/// be used to find the face that such an ID refers to, for example after a
+/// triangle has been picked in a viewer.
+///
+/// Returns `None`, if no face with that ID exists in the provided stores.
+pub fn find_face(face: FaceId, topology: &Topology) -> Option<Handle<Face>> {
+    topology
+        .faces
+        .iter()
+        .find(|candidate| candidate.id().0 == face.0)
+}
+
 #[cfg(test)]
 mod tests {
-    use fj_interop::Mesh;
-    use fj_math::{Point, Scalar};
+    use std::sync::mpsc;
+
+    use fj_interop::{CancellationToken, Mesh};
+    use fj_math::{Aabb, Point, Scalar};
 
     use crate::{
         algorithms::approx::{Approx, Tolerance},
         operations::{
-            build::{BuildCycle, BuildFace},
+            build::{
+                BuildCycle, BuildFace, BuildShell, BuildSolid, BuildSurface,
+            },
             insert::Insert,
             update::{UpdateFace, UpdateRegion},
         },
         storage::Handle,
-        topology::{Cycle, Face},
+        topology::{Cycle, Face, ObjectSet, Shell, Solid, Surface},
         Core,
     };
 
-    use super::Triangulate;
+    use super::{
+        find_face, Triangulate, TriangulateError, TriangulateFiltered,
+    };
 
     #[test]
     fn simple() -> anyhow::Result<()> {
@@ -304,6 +662,251 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn find_face_of_triangle() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let face = Face::polygon(
+            core.layers.topology.surfaces.xy_plane(),
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        )
+        .insert(&mut core);
+
+        let triangles = triangulate(face.clone(), &mut core)?;
+
+        for triangle in triangles.triangles() {
+            let found = triangle
+                .face
+                .and_then(|id| find_face(id, &core.layers.topology));
+            assert_eq!(found.map(|found| found.id()), Some(face.id()));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn triangulate_within_region() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        )
+        .shell;
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        // Only the face that is opposite to this vertex doesn't touch it.
+        let around_vertex = Aabb {
+            min: Point::from([0.9, -0.1, -0.1]),
+            max: Point::from([1.1, 0.1, 0.1]),
+        };
+
+        let all = (&tetrahedron, tolerance).triangulate(&mut core);
+        let within = (&tetrahedron, tolerance)
+            .triangulate_within(&around_vertex, &mut core);
+
+        assert_eq!(all.triangles().count(), 4);
+        assert_eq!(within.triangles().count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_output() -> anyhow::Result<()> {
+        let tolerance = Tolerance::from_scalar(0.1)?;
+
+        let mut core = Core::new();
+        let solid = Solid::rounded_box([4., 3., 2.], 0.5, &mut core);
+        let a = (&solid, tolerance).triangulate(&mut core);
+
+        // Build some unrelated objects first, so the objects of the second
+        // solid end up at different addresses.
+        let mut core = Core::new();
+        let _ = Solid::cylinder(1., 1., &mut core);
+        let solid = Solid::rounded_box([4., 3., 2.], 0.5, &mut core);
+        let b = (&solid, tolerance).triangulate(&mut core);
+
+        assert!(a.vertices().eq(b.vertices()));
+        assert!(a.indices().eq(b.indices()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn golden_tetrahedron() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let mesh = (&tetrahedron.solid, tolerance).triangulate(&mut core);
+
+        // If this changes, files exported by earlier versions can no longer be
+        // compared to new ones. Make sure that's intended, before updating it.
+        let vertices = [[1., 0., 0.], [0., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+            .map(Point::from);
+        assert!(mesh.vertices().eq(vertices));
+        assert!(mesh.triangle_indices().eq([
+            [0, 1, 2],
+            [3, 2, 1],
+            [0, 3, 1],
+            [3, 0, 2]
+        ]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_progress() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let (sender, receiver) = mpsc::channel();
+        core.progress = Box::new(move |progress| {
+            sender.send(progress).unwrap();
+        });
+        let _ = (&tetrahedron.solid, tolerance).triangulate(&mut core);
+
+        let reports = receiver
+            .try_iter()
+            .filter(|progress| progress.task == "triangulate")
+            .map(|progress| (progress.done, progress.total))
+            .collect::<Vec<_>>();
+        assert_eq!(reports, [(0, 4), (1, 4), (2, 4), (3, 4), (4, 4)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let token = CancellationToken::new();
+        core.progress = Box::new(token.clone());
+
+        let mesh = (&tetrahedron.solid, tolerance).triangulate(&mut core);
+        assert_eq!(mesh.num_triangles(), 4);
+
+        token.cancel();
+        let mesh = (&tetrahedron.solid, tolerance).triangulate(&mut core);
+        assert_eq!(mesh.num_triangles(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn isolate_failed_faces() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let valid = Face::polygon(
+            surface.clone(),
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        )
+        .insert(&mut core);
+
+        // These coordinates are too large for the Delaunay triangulation.
+        let invalid = Face::polygon(
+            surface,
+            [[0., 0.], [1e70, 0.], [0., 1e70]],
+            &mut core,
+        )
+        .insert(&mut core);
+
+        let faces = [valid, invalid.clone()]
+            .into_iter()
+            .collect::<ObjectSet<_>>();
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+        let triangulation = (&faces, tolerance).triangulate_partial(&mut core);
+
+        assert_eq!(triangulation.mesh.num_triangles(), 2);
+        assert_eq!(triangulation.failed_faces.len(), 1);
+
+        let failed = &triangulation.failed_faces[0];
+        assert_eq!(failed.face.id(), invalid.id());
+        assert_eq!(failed.boundary.len(), 3);
+        assert!(matches!(
+            failed.error,
+            TriangulateError::InvalidPoint { .. }
+        ));
+
+        let shell = Shell::new(faces);
+        let filtered = (&shell, tolerance)
+            .triangulate_filtered_partial(|_| true, &mut core);
+        assert_eq!(filtered.mesh.num_triangles(), 2);
+        assert_eq!(filtered.failed_faces.len(), 1);
+        assert_eq!(filtered.failed_faces[0].face.id(), invalid.id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn hemisphere() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let radius = Scalar::from(2.);
+        let surface = Surface::sphere([0., 0., 0.], radius, &mut core);
+        let faces = [Face::polygon(
+            surface,
+            [
+                [Scalar::ZERO, Scalar::ZERO],
+                [Scalar::TAU, Scalar::ZERO],
+                [Scalar::TAU, Scalar::PI / 2.],
+                [Scalar::ZERO, Scalar::PI / 2.],
+            ],
+            &mut core,
+        )
+        .insert(&mut core)]
+        .into_iter()
+        .collect::<ObjectSet<_>>();
+
+        // Triangulating checks that no distinct points of the approximation
+        // are too close to each other, like the corners on either side of the
+        // seam.
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let mesh = (&faces, tolerance).triangulate(&mut core);
+
+        let mut area = Scalar::ZERO;
+        for triangle in mesh.triangles() {
+            let [a, b, c] = triangle.inner.points();
+
+            // There must not be any slivers around the pole.
+            let triangle_area = (b - a).cross(&(c - a)).magnitude() / 2.;
+            assert!(triangle_area > Scalar::ZERO);
+            area += triangle_area;
+
+            // The corners of the triangles are on the sphere, but their edges
+            // can only stray from it as far as the tolerance allows.
+            for [p, q] in [[a, b], [b, c], [c, a]] {
+                let midpoint = (p.coords + q.coords) / 2.;
+                assert!(radius - midpoint.magnitude() <= tolerance.inner());
+            }
+        }
+
+        // The mesh covers the hemisphere, as opposed to just the disk that its
+        // boundary encloses.
+        let expected_area = Scalar::TAU * radius * radius;
+        assert!((area - expected_area).abs() < expected_area * 0.01);
+
+        Ok(())
+    }
+
     fn triangulate(
         face: Handle<Face>,
         core: &mut Core,
@@ -1,14 +1,10 @@
 use fj_interop::ext::SliceExt;
-use fj_math::{Point, PolyChain, Segment, Triangle};
-
-use crate::algorithms::intersect::{
-    ray_segment::RaySegmentIntersection, HorizontalRayToTheRight, Intersect,
-};
+use fj_math::{Point, PointInPolygon, Segment, Triangle};
 
 #[derive(Default)]
 pub struct Polygon {
-    exterior: PolyChain<2>,
-    interiors: Vec<PolyChain<2>>,
+    exterior: fj_math::Polygon<2>,
+    interiors: Vec<fj_math::Polygon<2>>,
 }
 
 impl Polygon {
@@ -17,14 +13,17 @@ impl Polygon {
         Self::default()
     }
 
-    pub fn with_exterior(mut self, exterior: impl Into<PolyChain<2>>) -> Self {
+    pub fn with_exterior(
+        mut self,
+        exterior: impl Into<fj_math::Polygon<2>>,
+    ) -> Self {
         self.exterior = exterior.into();
         self
     }
 
     pub fn with_interiors(
         mut self,
-        interiors: impl IntoIterator<Item = impl Into<PolyChain<2>>>,
+        interiors: impl IntoIterator<Item = impl Into<fj_math::Polygon<2>>>,
     ) -> Self {
         self.interiors.extend(interiors.into_iter().map(Into::into));
         self
@@ -32,10 +31,13 @@ impl Polygon {
 
     #[cfg(test)]
     pub fn invert_winding(mut self) -> Self {
-        self.exterior = self.exterior.reverse();
+        let reverse = |polygon: &fj_math::Polygon<2>| {
+            fj_math::Polygon::from(polygon.points().iter().rev().copied())
+        };
 
+        self.exterior = reverse(&self.exterior);
         for interior in &mut self.interiors {
-            *interior = interior.clone().reverse();
+            *interior = reverse(interior);
         }
 
         self
@@ -91,121 +93,44 @@ impl Polygon {
     }
 
     fn contains_exterior_edge(&self, edge: Segment<2>) -> bool {
-        self.exterior.segments().contains(&edge)
-            || self.exterior.segments().contains(&edge.reverse())
+        has_edge(&self.exterior, edge)
     }
 
     fn contains_interior_edge(&self, edge: Segment<2>) -> bool {
-        let mut contains = false;
-
-        for chain in &self.interiors {
-            contains |= chain.segments().contains(&edge);
-            contains |= chain.segments().contains(&edge.reverse());
-        }
-
-        contains
+        self.interiors
+            .iter()
+            .any(|interior| has_edge(interior, edge))
     }
 
     /// Check whether the polygon contains a point
     ///
-    /// # Implementation Note
-    ///
-    /// This code is being duplicated by the `Contains<Point<2>>` implementation
-    /// for `Face`. It would be nice to be able to consolidate the duplication,
-    /// but this has turned out to be difficult.
+    /// Points on the boundary, including the boundary of a hole, count as
+    /// contained.
     fn contains_point(&self, point: impl Into<Point<2>>) -> bool {
-        let ray = HorizontalRayToTheRight {
-            origin: point.into(),
-        };
+        let point = point.into();
 
-        let mut num_hits = 0;
-
-        for chain in Some(&self.exterior).into_iter().chain(&self.interiors) {
-            let edges = chain.segments();
-
-            // We need to properly detect the ray passing the boundary at the
-            // "seam" of the polygon, i.e. the vertex between the last and the
-            // first segment. The logic in the loop properly takes care of that,
-            // as long as we initialize the `previous_hit` variable with the
-            // result of the last segment.
-            let mut previous_hit = edges
-                .last()
-                .copied()
-                .and_then(|edge| (&ray, &edge).intersect());
-
-            for edge in edges {
-                let hit = (&ray, &edge).intersect();
-
-                let count_hit = match (hit, previous_hit) {
-                    (
-                        Some(
-                            RaySegmentIntersection::RayStartsOnSegment
-                            | RaySegmentIntersection::RayStartsOnOnFirstVertex
-                            | RaySegmentIntersection::RayStartsOnSecondVertex,
-                        ),
-                        _,
-                    ) => {
-                        // If the ray starts on the boundary of the polygon,
-                        // there's nothing else to check. By the definition of
-                        // this intersection test, the polygon contains the
-                        // point.
-                        return true;
-                    }
-                    (Some(RaySegmentIntersection::RayHitsSegment), _) => {
-                        // We're hitting a segment right-on. Clear case.
-                        true
-                    }
-                    (
-                        Some(RaySegmentIntersection::RayHitsUpperVertex),
-                        Some(RaySegmentIntersection::RayHitsLowerVertex),
-                    )
-                    | (
-                        Some(RaySegmentIntersection::RayHitsLowerVertex),
-                        Some(RaySegmentIntersection::RayHitsUpperVertex),
-                    ) => {
-                        // If we're hitting a vertex, only count it if we've hit
-                        // the other kind of vertex right before.
-                        //
-                        // That means, we're passing through the polygon
-                        // boundary at where two edges touch. Depending on the
-                        // order in which edges are checked, we're seeing this
-                        // as a hit to one edge's lower/upper vertex, then the
-                        // other edge's opposite vertex.
-                        //
-                        // If we're seeing two of the same vertices in a row,
-                        // we're not actually passing through the polygon
-                        // boundary. Then we're just touching a vertex without
-                        // passing through anything.
-                        true
-                    }
-                    (Some(RaySegmentIntersection::RayHitsSegmentAndAreParallel), _) => {
-                        // A parallel edge must be completely ignored. Its
-                        // presence won't change anything, so we can treat it as
-                        // if it wasn't there, and its neighbors were connected
-                        // to each other.
-                        continue;
-                    }
-                    _ => {
-                        // Any other case is not a valid hit.
-                        false
-                    }
-                };
-
-                if count_hit {
-                    num_hits += 1;
-                }
-
-                previous_hit = hit;
-            }
+        match self.exterior.contains_point(point) {
+            PointInPolygon::Outside => return false,
+            PointInPolygon::OnBoundary => return true,
+            PointInPolygon::Inside => {}
         }
 
-        num_hits % 2 == 1
+        self.interiors.iter().all(|interior| {
+            interior.contains_point(point) != PointInPolygon::Inside
+        })
     }
 }
 
+fn has_edge(polygon: &fj_math::Polygon<2>, edge: Segment<2>) -> bool {
+    let [a, b] = edge.points();
+    polygon
+        .edges()
+        .any(|polygon_edge| polygon_edge == [a, b] || polygon_edge == [b, a])
+}
+
 #[cfg(test)]
 mod tests {
-    use fj_math::{Point, PolyChain};
+    use fj_math::Point;
 
     use super::Polygon;
 
@@ -220,8 +145,8 @@ mod tests {
         let f = [1., 2.];
 
         let polygon = Polygon::new()
-            .with_exterior(PolyChain::from([a, b, c]).close())
-            .with_interiors([PolyChain::from([d, e, f]).close()]);
+            .with_exterior([a, b, c])
+            .with_interiors([[d, e, f]]);
 
         assert!(!polygon.contains_triangle([d, e, f]));
     }
@@ -232,8 +157,7 @@ mod tests {
         let b = [2., 1.];
         let c = [0., 2.];
 
-        let polygon =
-            Polygon::new().with_exterior(PolyChain::from([a, b, c]).close());
+        let polygon = Polygon::new().with_exterior([a, b, c]);
 
         assert_contains_point(polygon, [1., 1.]);
     }
@@ -249,8 +173,8 @@ mod tests {
         let f = [1., 3.];
 
         let polygon = Polygon::new()
-            .with_exterior(PolyChain::from([a, b, c]).close())
-            .with_interiors([PolyChain::from([d, e, f]).close()]);
+            .with_exterior([a, b, c])
+            .with_interiors([[d, e, f]]);
 
         assert_contains_point(polygon, [1., 2.]);
     }
@@ -262,8 +186,7 @@ mod tests {
         let c = [3., 0.];
         let d = [3., 4.];
 
-        let polygon =
-            Polygon::new().with_exterior(PolyChain::from([a, b, c, d]).close());
+        let polygon = Polygon::new().with_exterior([a, b, c, d]);
 
         assert_contains_point(polygon, [1., 1.]);
     }
@@ -275,8 +198,7 @@ mod tests {
         let b = [2., 1.];
         let c = [3., 1.];
         let d = [0., 2.];
-        let polygon =
-            Polygon::new().with_exterior(PolyChain::from([a, b, c, d]).close());
+        let polygon = Polygon::new().with_exterior([a, b, c, d]);
         assert_contains_point(polygon, [1., 1.]);
 
         // Ray hits a vertex, but doesn't pass polygon boundary there.
@@ -285,8 +207,7 @@ mod tests {
         let c = [3., 1.];
         let d = [4., 0.];
         let e = [4., 5.];
-        let polygon = Polygon::new()
-            .with_exterior(PolyChain::from([a, b, c, d, e]).close());
+        let polygon = Polygon::new().with_exterior([a, b, c, d, e]);
         assert_contains_point(polygon, [1., 1.]);
     }
 
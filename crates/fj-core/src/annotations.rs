@@ -0,0 +1,388 @@
+//! Annotations of the object graph
+//!
+//! See [`Annotations`].
+
+use std::collections::BTreeMap;
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{Geometry, GlobalPath, SurfacePath},
+    storage::Handle,
+    topology::{HalfEdge, Surface},
+};
+
+/// Annotations of the object graph
+///
+/// Annotations, like dimensions and notes, are attached to geometry that has
+/// been given a name. They are not part of the shape itself, but are included,
+/// when [drawings] are created from it.
+///
+/// This data is made available through [`Layers`].
+///
+/// [drawings]: crate::algorithms::drawing
+/// [`Layers`]: crate::layers::Layers
+#[derive(Clone, Default)]
+pub struct Annotations {
+    /// Geometry that has been given a name, so annotations can refer to it
+    pub names: BTreeMap<String, NamedGeometry>,
+
+    /// The annotations, in the order in which they were added
+    pub annotations: Vec<Annotation>,
+}
+
+impl Annotations {
+    /// Check that an annotation refers to geometry that it can annotate
+    pub fn check(
+        &self,
+        annotation: &Annotation,
+        geometry: &Geometry,
+    ) -> Result<(), AnnotationError> {
+        match annotation {
+            Annotation::Linear { from, to, .. } => {
+                for name in [from, to] {
+                    let NamedGeometry::Vertex { .. } = self.get(name)? else {
+                        return Err(AnnotationError::NotAVertex(name.clone()));
+                    };
+                }
+            }
+            Annotation::Radial { edge } => {
+                let NamedGeometry::Edge { half_edge, surface } =
+                    self.get(edge)?
+                else {
+                    return Err(AnnotationError::NotAnEdge(edge.clone()));
+                };
+
+                if circle_of(half_edge, surface, geometry).is_none() {
+                    return Err(AnnotationError::NotCircular(edge.clone()));
+                }
+            }
+            Annotation::Note { at, .. } => {
+                self.get(at)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compute the positions that an annotation refers to
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the annotation doesn't pass [`Annotations::check`]. This is
+    /// always the case for annotations that have been added through the
+    /// layer.
+    pub fn resolve(
+        &self,
+        annotation: &Annotation,
+        geometry: &Geometry,
+    ) -> ResolvedAnnotation {
+        let named = |name: &String| {
+            self.names
+                .get(name)
+                .expect("Annotation should refer to named geometry")
+        };
+
+        match annotation {
+            Annotation::Linear { from, to, offset } => {
+                let [from, to] =
+                    [from, to].map(|name| named(name).position(geometry));
+
+                ResolvedAnnotation::Linear {
+                    from,
+                    to,
+                    offset: *offset,
+                }
+            }
+            Annotation::Radial { edge } => {
+                let (half_edge, surface) = named(edge).half_edge_and_surface();
+                let (center, point, normal) = circle_of(
+                    half_edge, surface, geometry,
+                )
+                .expect("Radial annotation should refer to circular edge");
+
+                ResolvedAnnotation::Radial {
+                    center,
+                    point,
+                    normal,
+                }
+            }
+            Annotation::Note { at, text } => ResolvedAnnotation::Note {
+                position: named(at).position(geometry),
+                text: text.clone(),
+            },
+        }
+    }
+
+    fn get(&self, name: &str) -> Result<&NamedGeometry, AnnotationError> {
+        self.names
+            .get(name)
+            .ok_or_else(|| AnnotationError::UnknownName(name.to_owned()))
+    }
+}
+
+/// Geometry that has been given a name
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum NamedGeometry {
+    /// The start vertex of a half-edge
+    Vertex {
+        /// The half-edge that starts at the vertex
+        half_edge: Handle<HalfEdge>,
+
+        /// The surface that the half-edge is defined on
+        surface: Handle<Surface>,
+    },
+
+    /// The edge that a half-edge belongs to
+    Edge {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+
+        /// The surface that the half-edge is defined on
+        surface: Handle<Surface>,
+    },
+}
+
+impl NamedGeometry {
+    /// Access the half-edge that identifies the named geometry
+    pub fn half_edge(&self) -> &Handle<HalfEdge> {
+        let (half_edge, _) = self.half_edge_and_surface();
+        half_edge
+    }
+
+    /// Compute the position of the named geometry
+    ///
+    /// For an edge, this is the point in the middle of it.
+    pub fn position(&self, geometry: &Geometry) -> Point<3> {
+        let (half_edge, surface) = self.half_edge_and_surface();
+        let half_edge_geom = geometry.of_half_edge(half_edge);
+
+        let position_surface = match self {
+            Self::Vertex { .. } => half_edge_geom.start_position(),
+            Self::Edge { .. } => {
+                let [start, end] = half_edge_geom.boundary.inner;
+                half_edge_geom
+                    .path
+                    .point_from_path_coords(start + (end - start) * 0.5)
+            }
+        };
+
+        geometry
+            .of_surface(surface)
+            .point_from_surface_coords(position_surface)
+    }
+
+    fn half_edge_and_surface(&self) -> (&Handle<HalfEdge>, &Handle<Surface>) {
+        match self {
+            Self::Vertex { half_edge, surface }
+            | Self::Edge { half_edge, surface } => (half_edge, surface),
+        }
+    }
+}
+
+/// An annotation of named geometry
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Annotation {
+    /// The distance between two named vertices
+    Linear {
+        /// The name of the vertex that the distance is measured from
+        from: String,
+
+        /// The name of the vertex that the distance is measured to
+        to: String,
+
+        /// The distance of the dimension line from the measured vertices
+        ///
+        /// See [`fj_interop::Annotation::Linear`].
+        offset: Scalar,
+    },
+
+    /// The radius of a named circular edge
+    Radial {
+        /// The name of the edge
+        edge: String,
+    },
+
+    /// A text note, attached to a named vertex or edge
+    Note {
+        /// The name of the vertex or edge
+        at: String,
+
+        /// The text of the note
+        text: String,
+    },
+}
+
+impl Annotation {
+    /// Access the names that the annotation refers to
+    pub fn names(&self) -> Vec<&String> {
+        match self {
+            Self::Linear { from, to, .. } => vec![from, to],
+            Self::Radial { edge } => vec![edge],
+            Self::Note { at, .. } => vec![at],
+        }
+    }
+}
+
+/// An annotation, with the names it refers to resolved into positions
+///
+/// Returned by [`Annotations::resolve`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum ResolvedAnnotation {
+    /// The distance between two points
+    Linear {
+        /// The point that the distance is measured from
+        from: Point<3>,
+
+        /// The point that the distance is measured to
+        to: Point<3>,
+
+        /// The distance of the dimension line from the measured points
+        offset: Scalar,
+    },
+
+    /// The radius of a circular edge
+    Radial {
+        /// The center of the circle
+        center: Point<3>,
+
+        /// The point in the middle of the edge
+        point: Point<3>,
+
+        /// The normal of the plane that the circle is in
+        normal: Vector<3>,
+    },
+
+    /// A text note
+    Note {
+        /// The point that the note is attached to
+        position: Point<3>,
+
+        /// The text of the note
+        text: String,
+    },
+}
+
+/// An error that can occur when annotating geometry
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum AnnotationError {
+    /// A name is already in use
+    #[error("Name `{0}` is already in use")]
+    DuplicateName(String),
+
+    /// A name doesn't refer to any geometry
+    #[error("No geometry is named `{0}`")]
+    UnknownName(String),
+
+    /// A name was expected to refer to a vertex, but doesn't
+    #[error("`{0}` does not name a vertex")]
+    NotAVertex(String),
+
+    /// A name was expected to refer to an edge, but doesn't
+    #[error("`{0}` does not name an edge")]
+    NotAnEdge(String),
+
+    /// An edge was expected to be a circle or arc within a plane, but isn't
+    #[error("Edge `{0}` is not a circle or arc within a plane")]
+    NotCircular(String),
+}
+
+/// Compute the center, a point, and the plane normal of a circular half-edge
+///
+/// Returns `None`, if the half-edge is not a circle or arc within a plane.
+fn circle_of(
+    half_edge: &Handle<HalfEdge>,
+    surface: &Handle<Surface>,
+    geometry: &Geometry,
+) -> Option<(Point<3>, Point<3>, Vector<3>)> {
+    let half_edge_geom = geometry.of_half_edge(half_edge);
+    let surface_geom = geometry.of_surface(surface);
+
+    let SurfacePath::Circle(circle) = half_edge_geom.path else {
+        return None;
+    };
+    let GlobalPath::Line(line) = surface_geom.u else {
+        return None;
+    };
+
+    let [start, end] = half_edge_geom.boundary.inner;
+    let [center, point] = [
+        circle.center(),
+        circle.point_from_circle_coords(start + (end - start) * 0.5),
+    ]
+    .map(|point| surface_geom.point_from_surface_coords(point));
+
+    let normal = line.direction().cross(&surface_geom.v).normalize();
+
+    Some((center, point, normal))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        operations::build::BuildSolid, queries::AllHalfEdgesWithSurface,
+        topology::Solid, Core,
+    };
+
+    use super::{Annotation, AnnotationError};
+
+    #[test]
+    fn check() {
+        let mut core = Core::new();
+
+        let cube = Solid::rounded_box([1., 1., 1.], 0., &mut core);
+        let (half_edge, surface) = cube
+            .shells()
+            .only()
+            .all_half_edges_with_surface()
+            .next()
+            .unwrap();
+
+        let layer = &mut core.layers.annotations;
+        layer
+            .name_vertex("vertex", half_edge.clone(), surface.clone())
+            .unwrap();
+        layer
+            .name_edge("edge", half_edge.clone(), surface.clone())
+            .unwrap();
+        assert_eq!(
+            layer.name_edge("edge", half_edge, surface),
+            Err(AnnotationError::DuplicateName("edge".into())),
+        );
+
+        let linear = |to: &str| Annotation::Linear {
+            from: "vertex".into(),
+            to: to.into(),
+            offset: Scalar::ZERO,
+        };
+        let radial = |edge: &str| Annotation::Radial { edge: edge.into() };
+
+        let geometry = &core.layers.geometry;
+        for (annotation, error) in [
+            (linear("edge"), AnnotationError::NotAVertex("edge".into())),
+            (
+                linear("nothing"),
+                AnnotationError::UnknownName("nothing".into()),
+            ),
+            (
+                radial("vertex"),
+                AnnotationError::NotAnEdge("vertex".into()),
+            ),
+            (radial("edge"), AnnotationError::NotCircular("edge".into())),
+        ] {
+            assert_eq!(
+                core.layers.annotations.annotate(annotation, geometry),
+                Err(error)
+            );
+        }
+        assert!(core.layers.annotations.annotations.is_empty());
+
+        core.layers
+            .annotations
+            .annotate(linear("vertex"), geometry)
+            .unwrap();
+        assert_eq!(core.layers.annotations.annotations.len(), 1);
+    }
+}
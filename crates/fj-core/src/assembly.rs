@@ -0,0 +1,574 @@
+//! # Positioning of parts in assemblies
+//!
+//! Placing parts relative to each other by composing transforms manually is
+//! tedious and error-prone. [`Assembly`] instead lets you describe how parts
+//! relate to each other, using [mates](Mate) between [datum features] of those
+//! parts, and then solves for the transforms that place each part accordingly.
+//!
+//! The datum features of each part are specified in that part's own coordinate
+//! system, meaning before its transform is applied. The solver first places
+//! each part by satisfying its mates one after the other, then refines all
+//! transforms simultaneously, until all mates are satisfied.
+//!
+//! [datum features]: crate::geometry::DatumPoint
+
+use fj_math::{Point, Rotation, Scalar, Transform, Vector};
+
+use crate::geometry::{DatumAxis, DatumPlane, DatumPoint};
+
+/// A set of parts, and the mates that position them relative to each other
+///
+/// See [module documentation](self) for more information.
+#[derive(Clone, Debug, Default)]
+pub struct Assembly {
+    parts: Vec<PartKind>,
+    mates: Vec<Mate>,
+}
+
+impl Assembly {
+    /// Create an empty assembly
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a part that stays in place
+    ///
+    /// The transform of a fixed part is always the identity transform. Every
+    /// assembly needs at least one fixed part, for the others to be positioned
+    /// relative to.
+    pub fn add_fixed_part(&mut self) -> PartId {
+        self.parts.push(PartKind::Fixed);
+        PartId(self.parts.len() - 1)
+    }
+
+    /// Add a part that is positioned by mates
+    pub fn add_part(&mut self) -> PartId {
+        self.parts.push(PartKind::Free);
+        PartId(self.parts.len() - 1)
+    }
+
+    /// Add a mate between two parts
+    pub fn add_mate(&mut self, mate: Mate) {
+        self.mates.push(mate);
+    }
+
+    /// Solve for the transforms of all parts
+    ///
+    /// Returns one transform per part, in the order in which the parts were
+    /// added. Returns an error, if the mates can't be satisfied.
+    pub fn solve(&self) -> Result<Vec<Transform>, SolveError> {
+        for mate in &self.mates {
+            for part in mate.parts() {
+                if part.0 >= self.parts.len() {
+                    return Err(SolveError::UnknownPart(part));
+                }
+            }
+        }
+
+        let initial = self.place_sequentially();
+
+        let free_parts = self
+            .parts
+            .iter()
+            .enumerate()
+            .filter(|(_, kind)| **kind == PartKind::Free)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        let transforms = |params: &[f64]| {
+            let mut transforms = initial.clone();
+
+            for (i, &part) in free_parts.iter().enumerate() {
+                let [rx, ry, rz, tx, ty, tz] = [0, 1, 2, 3, 4, 5]
+                    .map(|offset| params[i * PARAMS_PER_PART + offset]);
+
+                transforms[part] = Transform::translation([tx, ty, tz])
+                    * Transform::rotation([rx, ry, rz])
+                    * initial[part];
+            }
+
+            transforms
+        };
+        let residuals = |params: &[f64]| {
+            let transforms = transforms(params);
+            self.mates
+                .iter()
+                .flat_map(|mate| mate.residuals(&transforms))
+                .collect::<Vec<_>>()
+        };
+
+        let params = levenberg_marquardt(
+            vec![0.; free_parts.len() * PARAMS_PER_PART],
+            residuals,
+        )?;
+
+        Ok(transforms(&params))
+    }
+
+    /// Compute an initial placement for all parts
+    ///
+    /// Places parts by satisfying their mates one by one, in the order in which
+    /// they were added, as long as the other part of the mate has already been
+    /// placed. This doesn't satisfy all mates, if a part has more than one, but
+    /// gets the parts close enough for the numerical solver to take over.
+    fn place_sequentially(&self) -> Vec<Transform> {
+        let mut transforms = vec![Transform::identity(); self.parts.len()];
+        let mut is_placed = self
+            .parts
+            .iter()
+            .map(|kind| *kind == PartKind::Fixed)
+            .collect::<Vec<_>>();
+
+        loop {
+            let mut placed_any = false;
+
+            for mate in &self.mates {
+                let [a, b] = mate.parts();
+
+                let (placed, unplaced) = match (is_placed[a.0], is_placed[b.0])
+                {
+                    (true, false) => (a, b),
+                    (false, true) => (b, a),
+                    _ => continue,
+                };
+
+                transforms[unplaced.0] =
+                    mate.place(unplaced, &transforms[placed.0]);
+                is_placed[unplaced.0] = true;
+                placed_any = true;
+            }
+
+            if !placed_any {
+                break;
+            }
+        }
+
+        transforms
+    }
+}
+
+/// Identifies a part in an [`Assembly`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct PartId(pub usize);
+
+/// A relation between datum features of two parts
+#[derive(Clone, Copy, Debug)]
+pub enum Mate {
+    /// Two planes coincide, with their normals pointing at each other
+    ///
+    /// This is the relation between two faces that touch each other.
+    CoincidentPlanes {
+        /// The first part, and a plane on it
+        a: (PartId, DatumPlane),
+
+        /// The second part, and a plane on it
+        b: (PartId, DatumPlane),
+    },
+
+    /// Two axes are on the same line
+    ///
+    /// The axes might point in the same or opposite directions.
+    ConcentricAxes {
+        /// The first part, and an axis on it
+        a: (PartId, DatumAxis),
+
+        /// The second part, and an axis on it
+        b: (PartId, DatumAxis),
+    },
+
+    /// Two points are a specific distance apart
+    Distance {
+        /// The first part, and a point on it
+        a: (PartId, DatumPoint),
+
+        /// The second part, and a point on it
+        b: (PartId, DatumPoint),
+
+        /// The distance between the points
+        distance: Scalar,
+    },
+}
+
+impl Mate {
+    fn parts(&self) -> [PartId; 2] {
+        match self {
+            Self::CoincidentPlanes { a, b } => [a.0, b.0],
+            Self::ConcentricAxes { a, b } => [a.0, b.0],
+            Self::Distance { a, b, .. } => [a.0, b.0],
+        }
+    }
+
+    /// Compute how far the mate is from being satisfied
+    ///
+    /// All returned values are zero, if the mate is satisfied.
+    fn residuals(&self, transforms: &[Transform]) -> Vec<f64> {
+        let residuals = match *self {
+            Self::CoincidentPlanes { a, b } => {
+                let (origin_a, normal_a) = plane_in(a, transforms);
+                let (origin_b, normal_b) = plane_in(b, transforms);
+
+                let normals = normal_a + normal_b;
+                let offset = (origin_b - origin_a).dot(&normal_a);
+
+                [normals.components.as_slice(), &[offset]].concat()
+            }
+            Self::ConcentricAxes { a, b } => {
+                let (origin_a, direction_a) = axis_in(a, transforms);
+                let (origin_b, direction_b) = axis_in(b, transforms);
+
+                let directions = direction_a.cross(&direction_b);
+                let offset = origin_b - origin_a;
+                let offset = offset - direction_a * offset.dot(&direction_a);
+
+                [directions.components, offset.components].concat()
+            }
+            Self::Distance { a, b, distance } => {
+                let point_a = point_in(a, transforms);
+                let point_b = point_in(b, transforms);
+
+                vec![point_a.distance_to(&point_b) - distance]
+            }
+        };
+
+        residuals.into_iter().map(Scalar::into_f64).collect()
+    }
+
+    /// Compute a transform for one part that satisfies this mate
+    ///
+    /// Takes the transform of the other part into account, which is expected
+    /// to have been placed already.
+    fn place(&self, part: PartId, other_transform: &Transform) -> Transform {
+        match *self {
+            Self::CoincidentPlanes { a, b } => {
+                let (this, other) = if a.0 == part { (a, b) } else { (b, a) };
+                let (origin, normal) = plane_in_part(this.1);
+                let other_origin =
+                    other_transform.transform_point(&other.1.plane.origin());
+                let other_normal = other_transform
+                    .transform_vector(&other.1.plane.normal())
+                    .normalize();
+
+                let rotation = Transform::from_rotation(Rotation::between(
+                    normal,
+                    -other_normal,
+                ));
+                let origin = rotation.transform_point(&origin);
+                let offset =
+                    other_normal * (other_origin - origin).dot(&other_normal);
+
+                Transform::translation(offset) * rotation
+            }
+            Self::ConcentricAxes { a, b } => {
+                let (this, other) = if a.0 == part { (a, b) } else { (b, a) };
+                let other_origin =
+                    other_transform.transform_point(&other.1.origin());
+                let other_direction = other_transform
+                    .transform_vector(&other.1.direction())
+                    .normalize();
+
+                let rotation = Transform::from_rotation(Rotation::between(
+                    this.1.direction(),
+                    other_direction,
+                ));
+                let origin = rotation.transform_point(&this.1.origin());
+                let offset = other_origin - origin;
+                let offset =
+                    offset - other_direction * offset.dot(&other_direction);
+
+                Transform::translation(offset) * rotation
+            }
+            Self::Distance { a, b, distance } => {
+                let (this, other) = if a.0 == part { (a, b) } else { (b, a) };
+                let point = this.1.position;
+                let other_point =
+                    other_transform.transform_point(&other.1.position);
+
+                let direction = point - other_point;
+                let direction = if direction.magnitude() == Scalar::ZERO {
+                    Vector::unit_x()
+                } else {
+                    direction.normalize()
+                };
+
+                let target = other_point + direction * distance;
+                Transform::translation(target - point)
+            }
+        }
+    }
+}
+
+/// Error solving the mates of an [`Assembly`]
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum SolveError {
+    /// A mate refers to a part that is not part of the assembly
+    #[error("Mate refers to unknown part {0:?}")]
+    UnknownPart(PartId),
+
+    /// The solver did not find transforms that satisfy all mates
+    ///
+    /// This usually means that the mates contradict each other.
+    #[error("Could not satisfy mates (remaining error: {residual})")]
+    NotConverged {
+        /// The remaining error
+        residual: f64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PartKind {
+    Fixed,
+    Free,
+}
+
+fn plane_in_part(plane: DatumPlane) -> (Point<3>, Vector<3>) {
+    (plane.plane.origin(), plane.plane.normal().normalize())
+}
+
+fn plane_in(
+    (part, plane): (PartId, DatumPlane),
+    transforms: &[Transform],
+) -> (Point<3>, Vector<3>) {
+    let (origin, normal) = plane_in_part(plane);
+    let transform = &transforms[part.0];
+
+    (
+        transform.transform_point(&origin),
+        transform.transform_vector(&normal),
+    )
+}
+
+fn axis_in(
+    (part, axis): (PartId, DatumAxis),
+    transforms: &[Transform],
+) -> (Point<3>, Vector<3>) {
+    let transform = &transforms[part.0];
+
+    (
+        transform.transform_point(&axis.origin()),
+        transform.transform_vector(&axis.direction()),
+    )
+}
+
+fn point_in(
+    (part, point): (PartId, DatumPoint),
+    transforms: &[Transform],
+) -> Point<3> {
+    transforms[part.0].transform_point(&point.position)
+}
+
+/// Minimize the sum of squared residuals
+fn levenberg_marquardt(
+    mut params: Vec<f64>,
+    residuals: impl Fn(&[f64]) -> Vec<f64>,
+) -> Result<Vec<f64>, SolveError> {
+    let cost = |residuals: &[f64]| -> f64 {
+        residuals.iter().map(|residual| residual * residual).sum()
+    };
+
+    let mut current = residuals(&params);
+    let mut damping = 1e-3;
+
+    for _ in 0..MAX_ITERATIONS {
+        if cost(&current).sqrt() <= EPSILON {
+            return Ok(params);
+        }
+
+        // Approximate the Jacobian numerically. The residuals are cheap to
+        // compute, and there are only a few parameters per part.
+        let jacobian = (0..params.len())
+            .map(|j| {
+                let mut shifted = params.clone();
+                shifted[j] += JACOBIAN_STEP;
+
+                residuals(&shifted)
+                    .into_iter()
+                    .zip(&current)
+                    .map(|(shifted, current)| {
+                        (shifted - current) / JACOBIAN_STEP
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let n = params.len();
+        let mut matrix = vec![vec![0.; n]; n];
+        let mut gradient = vec![0.; n];
+        for i in 0..n {
+            for j in 0..n {
+                matrix[i][j] = dot(&jacobian[i], &jacobian[j]);
+            }
+            gradient[i] = dot(&jacobian[i], &current);
+        }
+
+        loop {
+            let mut damped = matrix.clone();
+            for (i, row) in damped.iter_mut().enumerate() {
+                row[i] += damping * (1. + matrix[i][i]);
+            }
+
+            let step = solve_linear_system(
+                damped,
+                gradient.iter().map(|value| -value).collect(),
+            );
+            let candidate = params
+                .iter()
+                .zip(&step)
+                .map(|(param, step)| param + step)
+                .collect::<Vec<_>>();
+            let candidate_residuals = residuals(&candidate);
+
+            if cost(&candidate_residuals) < cost(&current) {
+                params = candidate;
+                current = candidate_residuals;
+                damping = (damping / 10.).max(1e-12);
+                break;
+            }
+
+            damping *= 10.;
+            if damping > 1e12 {
+                return Err(SolveError::NotConverged {
+                    residual: cost(&current).sqrt(),
+                });
+            }
+        }
+    }
+
+    if cost(&current).sqrt() <= EPSILON {
+        Ok(params)
+    } else {
+        Err(SolveError::NotConverged {
+            residual: cost(&current).sqrt(),
+        })
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Solve a linear system using Gaussian elimination with partial pivoting
+///
+/// Expects the matrix to be regular, which the damping in the caller ensures.
+fn solve_linear_system(
+    mut matrix: Vec<Vec<f64>>,
+    mut rhs: Vec<f64>,
+) -> Vec<f64> {
+    let n = rhs.len();
+
+    for column in 0..n {
+        let pivot = (column..n)
+            .max_by(|&a, &b| {
+                matrix[a][column].abs().total_cmp(&matrix[b][column].abs())
+            })
+            .expect("Range is not empty");
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        for row in column + 1..n {
+            let factor = matrix[row][column] / matrix[column][column];
+            for k in column..n {
+                matrix[row][k] -= factor * matrix[column][k];
+            }
+            rhs[row] -= factor * rhs[column];
+        }
+    }
+
+    let mut solution = vec![0.; n];
+    for row in (0..n).rev() {
+        let sum = (row + 1..n)
+            .map(|k| matrix[row][k] * solution[k])
+            .sum::<f64>();
+        solution[row] = (rhs[row] - sum) / matrix[row][row];
+    }
+
+    solution
+}
+
+const PARAMS_PER_PART: usize = 6;
+const MAX_ITERATIONS: usize = 100;
+const JACOBIAN_STEP: f64 = 1e-7;
+const EPSILON: f64 = 1e-9;
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::geometry::{DatumAxis, DatumPlane, DatumPoint};
+
+    use super::{Assembly, Mate};
+
+    #[test]
+    fn place_part_on_plane_and_axis() -> anyhow::Result<()> {
+        let mut assembly = Assembly::new();
+
+        let base = assembly.add_fixed_part();
+        let pin = assembly.add_part();
+
+        // The top face of the base, and the bottom face of the pin.
+        let base_top = DatumPlane::through_points(
+            [[0., 0., 1.], [1., 0., 1.], [0., 1., 1.]]
+                .map(DatumPoint::from_position),
+        );
+        let pin_bottom = DatumPlane::through_points(
+            [[0., 0., 5.], [0., 1., 5.], [1., 0., 5.]]
+                .map(DatumPoint::from_position),
+        );
+
+        // The hole in the base, and the axis of the pin, which lies along the
+        // x-axis in the pin's coordinate system.
+        let hole = DatumAxis::through_points(
+            [[2., 3., 0.], [2., 3., 1.]].map(DatumPoint::from_position),
+        );
+        let pin_axis = DatumAxis::through_points(
+            [[0., 0., 5.], [0., 0., 6.]].map(DatumPoint::from_position),
+        );
+
+        assembly.add_mate(Mate::CoincidentPlanes {
+            a: (base, base_top),
+            b: (pin, pin_bottom),
+        });
+        assembly.add_mate(Mate::ConcentricAxes {
+            a: (base, hole),
+            b: (pin, pin_axis),
+        });
+
+        let transforms = assembly.solve()?;
+        let pin_transform = &transforms[pin.0];
+
+        let bottom_center =
+            pin_transform.transform_point(&Point::from([0., 0., 5.]));
+        assert_close(bottom_center, Point::from([2., 3., 1.]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn place_parts_at_distance() -> anyhow::Result<()> {
+        let mut assembly = Assembly::new();
+
+        let a = assembly.add_fixed_part();
+        let b = assembly.add_part();
+
+        let origin = DatumPoint::from_position([0., 0., 0.]);
+        let corner = DatumPoint::from_position([1., 1., 0.]);
+
+        assembly.add_mate(Mate::Distance {
+            a: (a, origin),
+            b: (b, corner),
+            distance: Scalar::from(3.),
+        });
+
+        let transforms = assembly.solve()?;
+
+        let corner = transforms[b.0].transform_point(&corner.position);
+        let distance = (corner - Point::origin()).magnitude();
+        assert!((distance - Scalar::from(3.)).abs() < Scalar::from(1e-9));
+
+        Ok(())
+    }
+
+    fn assert_close(a: Point<3>, b: Point<3>) {
+        let distance: Vector<3> = a - b;
+        assert!(distance.magnitude() < Scalar::from(1e-6), "{a:?} != {b:?}");
+    }
+}
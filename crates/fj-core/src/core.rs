@@ -2,15 +2,59 @@
 //!
 //! See [`Core`].
 
-use crate::{layers::Layers, validation::ValidationConfig};
+use fj_interop::{NoProgress, ProgressSink};
+use fj_math::Tolerances;
+
+use crate::{
+    algorithms::approx::edge::HalfEdgeApproxCache,
+    layers::Layers,
+    provenance::Operation,
+    validation::{CheckedBy, ValidationConfig},
+};
 
 /// An instance of the Fornjot core
 ///
 /// This is the main entry point to `fj-core`'s API.
-#[derive(Default)]
+///
+/// ## Sharing Across Threads
+///
+/// `Core` is `Send` and `Sync`. Anything that modifies it requires `&mut Core`,
+/// but read access through `&Core` can happen from any number of threads at
+/// once. This allows approximation (through the geometry layer), validation
+/// (through [`Core::validate`]), and similar read-only work to run on worker
+/// threads, for example using [`std::thread::scope`].
 pub struct Core {
     /// The layers of data that make up the state of a core instance
     pub layers: Layers,
+
+    /// Approximations that have been computed by previous triangulations
+    ///
+    /// Used by [`Triangulate`], so that triangulating the same objects at the
+    /// same tolerance again, doesn't need to approximate them again. The
+    /// cache grows with every object and tolerance that is triangulated. If
+    /// that becomes a problem, you can replace it with an empty one.
+    ///
+    /// It also holds the [`ToleranceOverrides`] for specific faces and edges,
+    /// which would need to be carried over to a replacement.
+    ///
+    /// [`Triangulate`]: crate::algorithms::triangulate::Triangulate
+    /// [`ToleranceOverrides`]: crate::algorithms::approx::ToleranceOverrides
+    pub approx_cache: HalfEdgeApproxCache,
+
+    /// Receives progress reports from long-running operations
+    ///
+    /// Triangulation and boolean operations report their progress here, and
+    /// stop early, if the sink cancels them. The results of a cancelled
+    /// operation are incomplete, and should be discarded by whoever cancelled
+    /// it. The default sink ignores all reports, and never cancels.
+    ///
+    /// This allows a host application to show a progress bar, and to cancel a
+    /// rebuild that is no longer needed. See [`CancellationToken`].
+    ///
+    /// The sink needs to be `Sync`, so `Core` can be shared across threads.
+    ///
+    /// [`CancellationToken`]: fj_interop::CancellationToken
+    pub progress: Box<dyn ProgressSink + Send + Sync>,
 }
 
 impl Core {
@@ -22,6 +66,209 @@ impl Core {
     /// Construct an instance of `Instance`, using the provided configuration
     pub fn with_validation_config(config: ValidationConfig) -> Self {
         let layers = Layers::with_validation_config(config);
-        Self { layers }
+        Self {
+            layers,
+            ..Self::default()
+        }
+    }
+
+    /// Create a fork of this instance
+    ///
+    /// The fork can be modified independently of this instance, for example
+    /// to try an operation that might fail. If the operation fails, drop the
+    /// fork. If it succeeds, replace this instance with the fork.
+    ///
+    /// Objects are immutable, so the memory of the stores is shared between
+    /// this instance and the fork, instead of being copied. Objects that
+    /// either instance inserts after the fork are only visible through that
+    /// instance, for example when serializing it, or when counting its
+    /// objects. See [`Store::share`]. [`Core::collect_garbage`] can remove
+    /// the objects of the fork, once it has been dropped. The data of all
+    /// other layers, and the approximation cache, are copied.
+    ///
+    /// [`Store::share`]: crate::storage::Store::share
+    ///
+    /// The fork starts out without any validation errors, and ignores
+    /// progress reports.
+    pub fn fork(&self) -> Self {
+        Self {
+            layers: self.layers.fork(),
+            approx_cache: self.approx_cache.clone(),
+            progress: Box::new(NoProgress),
+        }
+    }
+
+    /// Access the tolerances of this instance
+    ///
+    /// These are the tolerances from the [`ValidationConfig`]. Operations and
+    /// queries that need to decide whether positions or directions are the
+    /// same, use them too. That way, they agree with validation.
+    pub fn tolerances(&self) -> Tolerances {
+        self.layers.validation.config.tolerances
+    }
+
+    /// Run a specific validation check on an object
+    ///
+    /// Objects are validated with all applicable checks, when they are
+    /// inserted. This method runs a single check instead, on any object,
+    /// inserted or not, and returns the errors it finds, instead of storing
+    /// them in the validation layer. The check is specified explicitly, as in
+    /// `core.validate::<CurveGeometryMismatch>(&shell)`.
+    ///
+    /// Uses the validation configuration of this instance. See
+    /// [`Core::validate_with_config`] to provide a different one, and
+    /// [`ValidationChecks`] to run a selection of checks.
+    ///
+    /// [`ValidationChecks`]: crate::validation::ValidationChecks
+    pub fn validate<C>(&self, object: &impl CheckedBy<C>) -> Vec<C> {
+        self.validate_with_config(object, &self.layers.validation.config)
+    }
+
+    /// Run a specific validation check on an object, with a custom config
+    ///
+    /// See [`Core::validate`].
+    pub fn validate_with_config<C>(
+        &self,
+        object: &impl CheckedBy<C>,
+        config: &ValidationConfig,
+    ) -> Vec<C> {
+        object.check_with(&self.layers.geometry, config)
+    }
+
+    /// Run code as part of an operation
+    ///
+    /// All objects that are inserted or derived by `f`, are recorded as
+    /// originating from the operation. Operations can be nested. Use this with
+    /// a tagged operation, to find the objects it created later, using
+    /// [`Provenance::is_from_tag`].
+    ///
+    /// [`Provenance::is_from_tag`]: crate::provenance::Provenance::is_from_tag
+    pub fn record_operation<R>(
+        &mut self,
+        operation: Operation,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.layers.provenance.begin_operation(operation);
+        let result = f(self);
+        self.layers.provenance.end_operation();
+
+        result
+    }
+}
+
+impl Default for Core {
+    fn default() -> Self {
+        Self {
+            layers: Layers::default(),
+            approx_cache: HalfEdgeApproxCache::default(),
+            progress: Box::new(NoProgress),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::{
+        algorithms::approx::{Approx, Tolerance},
+        operations::build::BuildSolid,
+        serialization::SerializedCore,
+        storage::Handle,
+        topology::Solid,
+        validation::checks::CurveGeometryMismatch,
+    };
+
+    use super::Core;
+
+    #[test]
+    fn share_across_threads() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let tolerance = Tolerance::from_scalar(0.1)?;
+
+        let core = &core;
+        let shell = &tetrahedron.shell.shell;
+
+        thread::scope(|scope| {
+            let approximations = shell
+                .faces()
+                .iter()
+                .map(|face| {
+                    scope.spawn(move || {
+                        face.clone().approx(tolerance, &core.layers.geometry)
+                    })
+                })
+                .collect::<Vec<_>>();
+            let errors = scope.spawn(move || {
+                core.validate::<CurveGeometryMismatch>(&**shell)
+            });
+
+            for approx in approximations {
+                let approx = approx.join().unwrap();
+                assert_eq!(approx.points().len(), 3);
+            }
+            assert!(errors.join().unwrap().is_empty());
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn fork_does_not_leak_objects() {
+        let mut core = Core::new();
+        Solid::washer(2., 1., 0.5, &mut core);
+        let serialized = SerializedCore::from_core(&core);
+        let objects = core.stats(0.1).objects;
+
+        let mut fork = core.fork();
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut fork,
+        );
+        assert_eq!(fork.stats(0.1).objects.shells, objects.shells + 1);
+        drop(fork);
+
+        // The objects of the fork are still in the shared stores, as long as
+        // a handle references them, but the original doesn't see them.
+        assert_eq!(core.stats(0.1).objects, objects);
+        assert_eq!(SerializedCore::from_core(&core), serialized);
+        drop(tetrahedron);
+    }
+
+    #[test]
+    fn fork() {
+        let mut core = Core::new();
+        let num_surfaces = core.layers.geometry.surfaces().count();
+
+        // Changes to a fork don't affect the original.
+        let mut fork = core.fork();
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut fork,
+        );
+        assert_eq!(fork.layers.geometry.surfaces().count(), num_surfaces + 4);
+        assert_eq!(core.layers.geometry.surfaces().count(), num_surfaces);
+
+        // Once the fork is dropped, its objects can be removed.
+        drop(tetrahedron);
+        drop(fork);
+        let collected = core.collect_garbage(Vec::<Handle<Solid>>::new());
+        assert_eq!(collected.objects.faces, 4);
+
+        // A fork can replace the original.
+        let mut fork = core.fork();
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut fork,
+        );
+        core = fork;
+        for face in tetrahedron.shell.shell.faces() {
+            let _ = core.layers.geometry.of_surface(face.surface());
+        }
     }
 }
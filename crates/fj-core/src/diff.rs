@@ -0,0 +1,381 @@
+//! # Compare two versions of a shape
+//!
+//! When editing model code, it is useful to know what actually changed
+//! geometrically. [`SolidDiff`] compares two solids and reports which faces and
+//! edges have been added, removed, or modified.
+//!
+//! The comparison is purely geometric. It doesn't rely on object identity, so
+//! the two solids can come from the same [`Core`](crate::Core) instance, or
+//! from two separate ones (for example, one built from the previous version of
+//! the model code, one built from the current version).
+//!
+//! Edges are compared by their endpoints and the point in their middle.
+//! Faces are considered unchanged, if they have the same edges. Faces that
+//! changed, but still lie on the same surface, are reported as modified.
+//! Surfaces are considered the same, if they describe the same plane or
+//! cylinder, regardless of how they are parametrized.
+
+use std::fmt;
+
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{Geometry, GlobalPath, SurfaceGeom},
+    storage::Handle,
+    topology::{Face, Solid},
+};
+
+/// The difference between two versions of a solid
+///
+/// See [module documentation](self) for more information.
+#[derive(Clone, Debug)]
+pub struct SolidDiff {
+    /// The faces that only exist in the new version
+    pub added_faces: Vec<Handle<Face>>,
+
+    /// The faces that only exist in the old version
+    pub removed_faces: Vec<Handle<Face>>,
+
+    /// The faces that lie on the same surface in both versions, but changed
+    pub modified_faces: Vec<ModifiedFace>,
+
+    /// The edges that only exist in the new version
+    pub added_edges: Vec<EdgeSummary>,
+
+    /// The edges that only exist in the old version
+    pub removed_edges: Vec<EdgeSummary>,
+}
+
+impl SolidDiff {
+    /// Compare two versions of a solid
+    ///
+    /// Each version comes with the geometry layer that it is defined in, which
+    /// can be the same for both. Points that are closer to each other than the
+    /// provided tolerance are considered equal.
+    pub fn new(
+        before: &Solid,
+        before_geometry: &Geometry,
+        after: &Solid,
+        after_geometry: &Geometry,
+        tolerance: impl Into<Scalar>,
+    ) -> Self {
+        let tolerance = tolerance.into();
+
+        let before = FaceSummary::all_of(before, before_geometry);
+        let after = FaceSummary::all_of(after, after_geometry);
+
+        let added_edges = edges_not_in(&after, &before, tolerance);
+        let removed_edges = edges_not_in(&before, &after, tolerance);
+
+        let mut removed = before
+            .iter()
+            .filter(|face| {
+                !after.iter().any(|other| face.matches(other, tolerance))
+            })
+            .collect::<Vec<_>>();
+
+        let mut added_faces = Vec::new();
+        let mut modified_faces = Vec::new();
+
+        for face in &after {
+            let is_unchanged =
+                before.iter().any(|other| face.matches(other, tolerance));
+            if is_unchanged {
+                continue;
+            }
+
+            let same_surface = removed.iter().position(|other| {
+                surfaces_match(&face.surface, &other.surface, tolerance)
+            });
+
+            match same_surface {
+                Some(index) => {
+                    let before = removed.remove(index);
+                    modified_faces.push(ModifiedFace {
+                        before: before.face.clone(),
+                        after: face.face.clone(),
+                        before_aabb: before.aabb,
+                        after_aabb: face.aabb,
+                    });
+                }
+                None => added_faces.push(face.face.clone()),
+            }
+        }
+
+        Self {
+            added_faces,
+            removed_faces: removed
+                .into_iter()
+                .map(|face| face.face.clone())
+                .collect(),
+            modified_faces,
+            added_edges,
+            removed_edges,
+        }
+    }
+
+    /// Indicate whether the two versions are geometrically identical
+    pub fn is_empty(&self) -> bool {
+        self.added_faces.is_empty()
+            && self.removed_faces.is_empty()
+            && self.modified_faces.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+impl fmt::Display for SolidDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No geometric changes");
+        }
+
+        writeln!(
+            f,
+            "Faces: {} added, {} removed, {} modified",
+            self.added_faces.len(),
+            self.removed_faces.len(),
+            self.modified_faces.len(),
+        )?;
+        for face in &self.modified_faces {
+            writeln!(
+                f,
+                "- modified face: bounding box {:?}..{:?} -> {:?}..{:?}",
+                face.before_aabb.min,
+                face.before_aabb.max,
+                face.after_aabb.min,
+                face.after_aabb.max,
+            )?;
+        }
+
+        writeln!(
+            f,
+            "Edges: {} added, {} removed",
+            self.added_edges.len(),
+            self.removed_edges.len(),
+        )?;
+        for (change, edges) in [
+            ("added", &self.added_edges),
+            ("removed", &self.removed_edges),
+        ] {
+            for edge in edges {
+                let [start, middle, end] = edge.points;
+                writeln!(
+                    f,
+                    "- {change} edge: {start:?} -> {middle:?} -> {end:?}"
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A face that lies on the same surface in both versions, but changed
+#[derive(Clone, Debug)]
+pub struct ModifiedFace {
+    /// The face in the old version
+    pub before: Handle<Face>,
+
+    /// The face in the new version
+    pub after: Handle<Face>,
+
+    /// The bounding box of the face's boundary in the old version
+    pub before_aabb: Aabb<3>,
+
+    /// The bounding box of the face's boundary in the new version
+    pub after_aabb: Aabb<3>,
+}
+
+/// A summary of an edge's geometry
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct EdgeSummary {
+    /// The start point, middle point, and end point of the edge
+    ///
+    /// Since both half-edges of an edge result in the same summary, the start
+    /// and end points are sorted, and don't necessarily reflect the direction
+    /// of any specific half-edge.
+    pub points: [Point<3>; 3],
+}
+
+impl EdgeSummary {
+    fn matches(&self, other: &Self, tolerance: Scalar) -> bool {
+        self.points
+            .iter()
+            .zip(other.points)
+            .all(|(a, b)| a.distance_to(&b) <= tolerance)
+    }
+}
+
+struct FaceSummary {
+    face: Handle<Face>,
+    surface: SurfaceGeom,
+    edges: Vec<EdgeSummary>,
+    aabb: Aabb<3>,
+}
+
+impl FaceSummary {
+    fn all_of(solid: &Solid, geometry: &Geometry) -> Vec<Self> {
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces().iter())
+            .map(|face| Self::new(face, geometry))
+            .collect()
+    }
+
+    fn new(face: &Handle<Face>, geometry: &Geometry) -> Self {
+        let surface = *geometry.of_surface(face.surface());
+
+        let edges = face
+            .region()
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges().iter())
+            .map(|half_edge| {
+                let half_edge = geometry.of_half_edge(half_edge);
+                let [start, end] = half_edge.boundary.inner;
+                let middle = Point::from([(start.t + end.t) / 2.]);
+
+                let [start, middle, end] = [start, middle, end].map(|point| {
+                    surface.point_from_surface_coords(
+                        half_edge.path.point_from_path_coords(point),
+                    )
+                });
+                let [start, end] = if start <= end {
+                    [start, end]
+                } else {
+                    [end, start]
+                };
+
+                EdgeSummary {
+                    points: [start, middle, end],
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let aabb =
+            Aabb::<3>::from_points(edges.iter().flat_map(|edge| edge.points));
+
+        Self {
+            face: face.clone(),
+            surface,
+            edges,
+            aabb,
+        }
+    }
+
+    fn matches(&self, other: &Self, tolerance: Scalar) -> bool {
+        self.edges.len() == other.edges.len()
+            && self.edges.iter().all(|edge| {
+                other
+                    .edges
+                    .iter()
+                    .any(|other| edge.matches(other, tolerance))
+            })
+    }
+}
+
+fn edges_not_in(
+    faces: &[FaceSummary],
+    others: &[FaceSummary],
+    tolerance: Scalar,
+) -> Vec<EdgeSummary> {
+    let mut edges: Vec<EdgeSummary> = Vec::new();
+
+    for edge in faces.iter().flat_map(|face| &face.edges) {
+        let is_in_others = others
+            .iter()
+            .flat_map(|face| &face.edges)
+            .any(|other| edge.matches(other, tolerance));
+        let is_duplicate =
+            edges.iter().any(|other| edge.matches(other, tolerance));
+
+        if !is_in_others && !is_duplicate {
+            edges.push(*edge);
+        }
+    }
+
+    edges
+}
+
+fn surfaces_match(a: &SurfaceGeom, b: &SurfaceGeom, tolerance: Scalar) -> bool {
+    // Surfaces can describe the same geometry using different
+    // parametrizations. A swept surface, for example, depends on the length of
+    // the sweep. So we compare the geometry that is described, not the
+    // definitions.
+    let directions_match = |a: Vector<3>, b: Vector<3>| {
+        (a.normalize() - b.normalize()).magnitude() <= tolerance
+    };
+    let is_on_line = |point: Point<3>,
+                      origin: Point<3>,
+                      direction: Vector<3>| {
+        (point - origin).cross(&direction.normalize()).magnitude() <= tolerance
+    };
+
+    match (a.u, b.u) {
+        (GlobalPath::Line(line_a), GlobalPath::Line(line_b)) => {
+            let normal_a = line_a.direction().cross(&a.v).normalize();
+            let normal_b = line_b.direction().cross(&b.v).normalize();
+
+            let offset = (line_b.origin() - line_a.origin()).dot(&normal_a);
+
+            directions_match(normal_a, normal_b) && offset.abs() <= tolerance
+        }
+        (GlobalPath::Circle(circle_a), GlobalPath::Circle(circle_b)) => {
+            let radius_a = circle_a.a().magnitude();
+            let radius_b = circle_b.a().magnitude();
+
+            // The orientation of the surface depends on the direction of the
+            // circle, relative to the direction of the sweep.
+            let orientation_a = circle_a.a().cross(&circle_a.b()).dot(&a.v);
+            let orientation_b = circle_b.a().cross(&circle_b.b()).dot(&b.v);
+
+            (radius_a - radius_b).abs() <= tolerance
+                && directions_match(a.v, b.v)
+                && is_on_line(circle_b.center(), circle_a.center(), a.v)
+                && orientation_a.sign() == orientation_b.sign()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{operations::build::BuildSolid, topology::Solid, Core};
+
+    use super::SolidDiff;
+
+    #[test]
+    fn diff_solids() {
+        let mut before = Core::new();
+        let mut after = Core::new();
+
+        let cube = Solid::rounded_box([1., 1., 1.], 0., &mut before);
+        let same_cube = Solid::rounded_box([1., 1., 1.], 0., &mut after);
+        let taller_cube = Solid::rounded_box([1., 1., 2.], 0., &mut after);
+
+        let diff = SolidDiff::new(
+            &cube,
+            &before.layers.geometry,
+            &same_cube,
+            &after.layers.geometry,
+            1e-9,
+        );
+        assert!(diff.is_empty(), "{diff}");
+
+        let diff = SolidDiff::new(
+            &cube,
+            &before.layers.geometry,
+            &taller_cube,
+            &after.layers.geometry,
+            1e-9,
+        );
+        assert_eq!(diff.added_faces.len(), 1);
+        assert_eq!(diff.removed_faces.len(), 1);
+        assert_eq!(diff.modified_faces.len(), 4);
+        assert_eq!(diff.added_edges.len(), 8);
+        assert_eq!(diff.removed_edges.len(), 8);
+    }
+}
@@ -0,0 +1,381 @@
+//! # Garbage collection of objects that are no longer needed
+//!
+//! Objects are never removed from the stores while a core instance is used,
+//! so long-running sessions that rebuild a model over and over keep growing.
+//! See [`Core::collect_garbage`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Deref,
+};
+
+use crate::{
+    annotations::NamedGeometry,
+    geometry::Geometry,
+    layers::{Command, Event, Layer},
+    materials::Materials,
+    presentation::Presentation,
+    provenance::Provenance,
+    stats::{store_memory, ObjectCounts},
+    storage::ObjectId,
+    topology::{AnyObject, Stored, Topology},
+    validation::Validation,
+    Core,
+};
+
+/// The result of a garbage collection
+///
+/// Returned by [`Core::collect_garbage`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CollectedGarbage {
+    /// The number of removed objects, per type of object
+    ///
+    /// This includes objects that were inserted through a fork that has been
+    /// dropped. See [`Core::fork`].
+    pub objects: ObjectCounts,
+
+    /// The memory that the stores have deallocated, in bytes
+    ///
+    /// Stores deallocate memory in blocks, once all objects in a block have
+    /// been removed. Memory that objects allocate themselves is not included.
+    /// See [`Store::memory_usage`].
+    ///
+    /// [`Store::memory_usage`]: crate::storage::Store::memory_usage
+    pub store_memory: usize,
+}
+
+impl Core {
+    /// Remove all objects that can't be reached from the provided roots
+    ///
+    /// Objects are reachable from the roots, if they are a root, or are
+    /// referenced by a reachable object. The basis planes, and all geometry
+    /// that has been given a name through the annotations layer, are always
+    /// reachable.
+    ///
+    /// All data that layers and caches keep about unreachable objects is
+    /// removed, including the validation errors of those objects. Provenance
+    /// of reachable objects is kept, but ends at the first object that is not
+    /// reachable.
+    ///
+    /// Objects that are referenced by a [`Handle`] outside of this instance
+    /// are treated as roots too. Their geometry and other layer data stays
+    /// available, as long as that handle exists.
+    ///
+    /// [`Handle`]: crate::storage::Handle
+    pub fn collect_garbage(
+        &mut self,
+        roots: impl IntoIterator<Item = impl Into<AnyObject<Stored>>>,
+    ) -> CollectedGarbage {
+        let reachable = self.reachable_objects(roots);
+
+        let objects_before =
+            ObjectCounts::including_shared(&self.layers.topology);
+        let memory_before = store_memory(&self.layers.topology);
+
+        let command = CollectGarbage {
+            reachable: &reachable,
+        };
+
+        // The layers and caches need to go first, as the handles they contain
+        // would prevent objects from being removed from the stores.
+        collect(&mut self.layers.geometry, command);
+        collect(&mut self.layers.validation, command);
+        collect(&mut self.layers.presentation, command);
+        collect(&mut self.layers.materials, command);
+        collect(&mut self.layers.provenance, command);
+        self.approx_cache
+            .retain_objects(|object| command.is_reachable(object));
+
+        collect(&mut self.layers.topology, command);
+
+        CollectedGarbage {
+            objects: objects_before
+                - ObjectCounts::including_shared(&self.layers.topology),
+            store_memory: memory_before - store_memory(&self.layers.topology),
+        }
+    }
+
+    fn reachable_objects(
+        &self,
+        roots: impl IntoIterator<Item = impl Into<AnyObject<Stored>>>,
+    ) -> BTreeSet<ObjectId> {
+        let surfaces = &self.layers.topology.surfaces;
+
+        let mut objects: Vec<AnyObject<Stored>> =
+            roots.into_iter().map(Into::into).collect();
+
+        objects.extend(
+            [
+                surfaces.space_2d(),
+                surfaces.xy_plane(),
+                surfaces.xz_plane(),
+                surfaces.yz_plane(),
+            ]
+            .map(Into::into),
+        );
+        for named in self.layers.annotations.names.values() {
+            let (NamedGeometry::Vertex { half_edge, surface }
+            | NamedGeometry::Edge { half_edge, surface }) = named;
+
+            objects.push(half_edge.clone().into());
+            objects.push(surface.clone().into());
+        }
+
+        objects.extend(self.objects_referenced_by_handles());
+
+        let mut reachable = BTreeSet::new();
+
+        while let Some(object) = objects.pop() {
+            if !reachable.insert(object.id()) {
+                continue;
+            }
+
+            push_referenced_objects(&object, &mut objects);
+        }
+
+        reachable
+    }
+
+    /// Find all objects that are referenced by handles outside of this instance
+    ///
+    /// Handles don't know who owns them, so this counts the handles that the
+    /// objects and layers of this instance hold, and compares that to the
+    /// total number of handles of each object.
+    fn objects_referenced_by_handles(&self) -> Vec<AnyObject<Stored>> {
+        let topology = &self.layers.topology;
+
+        let mut objects: Vec<AnyObject<Stored>> = Vec::new();
+        objects.extend(topology.curves.iter().map(Into::into));
+        objects.extend(topology.cycles.iter().map(Into::into));
+        objects.extend(topology.faces.iter().map(Into::into));
+        objects.extend(topology.half_edges.iter().map(Into::into));
+        objects.extend(topology.regions.iter().map(Into::into));
+        objects.extend(topology.shells.iter().map(Into::into));
+        objects.extend(topology.sketches.iter().map(Into::into));
+        objects.extend(topology.solids.iter().map(Into::into));
+        objects.extend(topology.surfaces.iter().map(Into::into));
+        objects.extend(topology.vertices.iter().map(Into::into));
+
+        // Each object is referenced by the handle in `objects`, and by the
+        // objects that reference it.
+        let mut internal: BTreeMap<ObjectId, usize> =
+            objects.iter().map(|object| (object.id(), 1)).collect();
+        let mut referenced = Vec::new();
+        for object in &objects {
+            push_referenced_objects(object, &mut referenced);
+            for object in referenced.drain(..) {
+                *internal.entry(object.id()).or_default() += 1;
+            }
+        }
+
+        // The layers and caches hold handles in many places. Instead of
+        // looking for them, clone the data, which clones each of those handles
+        // once.
+        let before: Vec<usize> =
+            objects.iter().map(AnyObject::num_handles).collect();
+        let layers = (
+            self.layers.geometry.deref().clone(),
+            self.layers.validation.errors.clone(),
+            self.layers.presentation.deref().clone(),
+            self.layers.materials.deref().clone(),
+            self.layers.provenance.deref().clone(),
+            self.layers.annotations.deref().clone(),
+            self.approx_cache.clone(),
+        );
+        for (object, before) in objects.iter().zip(&before) {
+            *internal.entry(object.id()).or_default() +=
+                object.num_handles() - before;
+        }
+        drop(layers);
+
+        objects
+            .into_iter()
+            .zip(before)
+            .filter(|(object, handles)| *handles > internal[&object.id()])
+            .map(|(object, _)| object)
+            .collect()
+    }
+}
+
+/// Push all objects that the provided object references directly
+fn push_referenced_objects(
+    object: &AnyObject<Stored>,
+    objects: &mut Vec<AnyObject<Stored>>,
+) {
+    match object {
+        AnyObject::Curve(_) | AnyObject::Surface(_) | AnyObject::Vertex(_) => {}
+        AnyObject::Cycle(cycle) => {
+            objects.extend(cycle.half_edges().iter().cloned().map(Into::into));
+        }
+        AnyObject::Face(face) => {
+            objects.push(face.surface().clone().into());
+            objects.push(face.region().clone().into());
+        }
+        AnyObject::HalfEdge(half_edge) => {
+            objects.push(half_edge.curve().clone().into());
+            objects.push(half_edge.start_vertex().clone().into());
+        }
+        AnyObject::Region(region) => {
+            objects.extend(region.all_cycles().cloned().map(Into::into));
+        }
+        AnyObject::Shell(shell) => {
+            objects.extend(shell.faces().iter().cloned().map(Into::into));
+        }
+        AnyObject::Sketch(sketch) => {
+            objects.push(sketch.surface().clone().into());
+            objects.extend(sketch.regions().iter().cloned().map(Into::into));
+        }
+        AnyObject::Solid(solid) => {
+            objects.extend(solid.shells().iter().cloned().map(Into::into));
+        }
+    }
+}
+
+fn collect<S>(layer: &mut Layer<S>, command: CollectGarbage)
+where
+    for<'r> CollectGarbage<'r>: Command<S>,
+{
+    let mut events = Vec::new();
+    layer.process(command, &mut events);
+}
+
+/// Remove all data about objects that are not reachable
+#[derive(Clone, Copy)]
+struct CollectGarbage<'r> {
+    reachable: &'r BTreeSet<ObjectId>,
+}
+
+impl CollectGarbage<'_> {
+    fn is_reachable(&self, object: ObjectId) -> bool {
+        self.reachable.contains(&object)
+    }
+}
+
+impl<'r, S> Command<S> for CollectGarbage<'r>
+where
+    Self: Event<S>,
+{
+    type Result = ();
+    type Event = Self;
+
+    fn decide(self, _: &S, events: &mut Vec<Self::Event>) {
+        events.push(self);
+    }
+}
+
+impl Event<Geometry> for CollectGarbage<'_> {
+    fn evolve(&self, state: &mut Geometry) {
+        state.retain_objects(|object| self.is_reachable(object));
+    }
+}
+
+impl Event<Validation> for CollectGarbage<'_> {
+    fn evolve(&self, state: &mut Validation) {
+        state.errors.retain(|object, _| self.is_reachable(*object));
+    }
+}
+
+impl Event<Presentation> for CollectGarbage<'_> {
+    fn evolve(&self, state: &mut Presentation) {
+        state
+            .color
+            .retain(|region, _| self.is_reachable(region.id()));
+    }
+}
+
+impl Event<Materials> for CollectGarbage<'_> {
+    fn evolve(&self, state: &mut Materials) {
+        state
+            .material
+            .retain(|region, _| self.is_reachable(region.id()));
+    }
+}
+
+impl Event<Provenance> for CollectGarbage<'_> {
+    fn evolve(&self, state: &mut Provenance) {
+        state.origins.retain(|object, origin| {
+            let original_is_reachable = origin
+                .original
+                .as_ref()
+                .is_some_and(|original| self.is_reachable(original.id()));
+            if !original_is_reachable {
+                origin.original = None;
+            }
+
+            self.is_reachable(*object)
+        });
+    }
+}
+
+impl Event<Topology> for CollectGarbage<'_> {
+    fn evolve(&self, state: &mut Topology) {
+        state.collect(|object| self.is_reachable(object));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{build::BuildSolid, insert::Insert},
+        storage::Handle,
+        topology::Solid,
+        Core,
+    };
+
+    fn tetrahedron(core: &mut Core) -> Handle<Solid> {
+        Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            core,
+        )
+        .solid
+        .insert(core)
+    }
+
+    #[test]
+    fn collect_unreachable_objects() {
+        let mut core = Core::new();
+
+        let kept = tetrahedron(&mut core);
+        let _ = tetrahedron(&mut core);
+
+        let collected = core.collect_garbage([kept.clone()]);
+        assert_eq!(collected.objects.solids, 1);
+        assert_eq!(collected.objects.shells, 1);
+        assert_eq!(collected.objects.faces, 4);
+
+        // The kept solid is fully usable.
+        for face in kept.shells().iter().flat_map(|shell| shell.faces()) {
+            let _ = core.layers.geometry.of_surface(face.surface());
+        }
+
+        let collected = core.collect_garbage([kept]);
+        assert_eq!(collected.objects.total(), 0);
+    }
+
+    #[test]
+    fn keep_objects_that_are_referenced_by_handles() {
+        let mut core = Core::new();
+
+        let solid = tetrahedron(&mut core);
+
+        // Building the solid leaves behind intermediate objects, so some
+        // objects are removed anyway.
+        let collected = core.collect_garbage(Vec::<Handle<Solid>>::new());
+        assert_eq!(collected.objects.solids, 0);
+        assert_eq!(collected.objects.shells, 0);
+        for face in solid.shells().iter().flat_map(|shell| shell.faces()) {
+            let _ = core.layers.geometry.of_surface(face.surface());
+            for cycle in face.region().all_cycles() {
+                assert_eq!(cycle.half_edges().len(), 3);
+                for half_edge in cycle.half_edges() {
+                    let _ = core.layers.geometry.of_half_edge(half_edge);
+                }
+            }
+        }
+
+        drop(solid);
+
+        let collected = core.collect_garbage(Vec::<Handle<Solid>>::new());
+        assert_eq!(collected.objects.solids, 1);
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use fj_math::Point;
+use fj_math::{Point, Scalar};
 
 use crate::{storage::Handle, topology::Vertex};
 
@@ -12,6 +12,12 @@ use crate::{storage::Handle, topology::Vertex};
 /// This struct is generic, because different situations require different
 /// representations of a boundary. In some cases, curve coordinates are enough,
 /// in other cases, vertices are required, and sometimes you need both.
+///
+/// Boundaries in curve coordinates support interval arithmetic, like
+/// [`CurveBoundary::union`], [`CurveBoundary::intersection`], and
+/// [`CurveBoundary::difference`]. Those operations don't consider the period
+/// of a curve, so boundaries on periodic curves need to be wrapped first,
+/// using [`CurveBoundary::wrap`].
 #[derive(Clone, Copy, Debug)]
 pub struct CurveBoundary<T: CurveBoundaryElement> {
     /// The raw representation of the boundary
@@ -52,6 +58,163 @@ impl<T: CurveBoundaryElement> CurveBoundary<T> {
     }
 }
 
+impl CurveBoundary<Point<1>> {
+    /// Move the boundary by a whole number of periods, into the first one
+    ///
+    /// On a periodic curve, like a circle, boundaries that are a whole number
+    /// of periods apart refer to the same part of the curve. Wrapping them
+    /// makes them comparable. The direction of the boundary is kept, while its
+    /// lower bound ends up in the range from zero to `period`.
+    #[must_use]
+    pub fn wrap(self, period: Scalar) -> Self {
+        let [a, b] = self.inner;
+        let offset = (a.t.min(b.t) / period).floor() * period;
+
+        Self {
+            inner: [a, b].map(|point| Point::from([point.t - offset])),
+        }
+    }
+
+    /// Indicate whether the boundary is empty
+    ///
+    /// This is the case, if both of its bounding points are the same.
+    pub fn is_empty(&self) -> bool {
+        let [a, b] = self.inner;
+        a == b
+    }
+
+    /// Indicate whether the boundary contains a point
+    ///
+    /// The bounding points themselves are considered to be contained.
+    pub fn contains(&self, point: impl Into<Point<1>>) -> bool {
+        let point = point.into();
+        let [min, max] = self.normalize().inner;
+
+        min <= point && point <= max
+    }
+
+    /// Indicate whether the boundary contains another one
+    ///
+    /// The direction of the boundaries doesn't matter.
+    pub fn contains_boundary(&self, other: &Self) -> bool {
+        let [a, b] = other.inner;
+        self.contains(a) && self.contains(b)
+    }
+
+    /// Indicate whether the boundary overlaps with another one
+    ///
+    /// Boundaries that only touch at a single point don't overlap. The
+    /// direction of the boundaries doesn't matter.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Compute the part of the curve that both boundaries cover
+    ///
+    /// Returns `None`, if the boundaries don't overlap. Otherwise, the result
+    /// has the direction of `self`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let [a_min, a_max] = self.normalize().inner;
+        let [b_min, b_max] = other.normalize().inner;
+
+        let intersection = Self::from([a_min.max(b_min), a_max.min(b_max)]);
+        if !intersection.is_normalized() || intersection.is_empty() {
+            return None;
+        }
+
+        Some(intersection.with_direction_of(self))
+    }
+
+    /// Compute the part of the curve that either boundary covers
+    ///
+    /// Returns `None`, if there is a gap between the boundaries, as the result
+    /// can't be represented by a single boundary. Boundaries that touch at a
+    /// single point are joined. Otherwise, the result has the direction of
+    /// `self`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        let [a_min, a_max] = self.normalize().inner;
+        let [b_min, b_max] = other.normalize().inner;
+
+        if a_max < b_min || b_max < a_min {
+            return None;
+        }
+
+        let union = Self::from([a_min.min(b_min), a_max.max(b_max)]);
+        Some(union.with_direction_of(self))
+    }
+
+    /// Compute the parts of `self` that `other` doesn't cover
+    ///
+    /// Returns up to two boundaries, which are ordered along, and have the
+    /// direction of, `self`. The result is empty, if `other` covers all of
+    /// `self`.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+
+        let [a_min, a_max] = self.normalize().inner;
+        let [b_min, b_max] = other.normalize().inner;
+
+        let mut difference: Vec<_> = [
+            Self::from([a_min, a_max.min(b_min)]),
+            Self::from([a_min.max(b_max), a_max]),
+        ]
+        .into_iter()
+        .filter(|boundary| boundary.is_normalized() && !boundary.is_empty())
+        .map(|boundary| boundary.with_direction_of(self))
+        .collect();
+
+        if !self.is_normalized() {
+            difference.reverse();
+        }
+
+        difference
+    }
+
+    /// Merge boundaries that overlap or touch
+    ///
+    /// Returns boundaries that neither overlap nor touch each other, and that
+    /// cover the same parts of the curve as the provided ones. The returned
+    /// boundaries are normalized, and sorted along the curve. Empty boundaries
+    /// are dropped.
+    pub fn union_all(boundaries: impl IntoIterator<Item = Self>) -> Vec<Self> {
+        let mut boundaries: Vec<_> = boundaries
+            .into_iter()
+            .filter(|boundary| !boundary.is_empty())
+            .map(Self::normalize)
+            .collect();
+        boundaries.sort();
+
+        let mut union: Vec<Self> = Vec::new();
+        for boundary in boundaries {
+            if let Some(last) = union.last_mut() {
+                if let Some(merged) = last.union(&boundary) {
+                    *last = merged;
+                    continue;
+                }
+            }
+
+            union.push(boundary);
+        }
+
+        union
+    }
+
+    fn with_direction_of(self, other: &Self) -> Self {
+        if other.is_normalized() {
+            self
+        } else {
+            self.reverse()
+        }
+    }
+}
+
 impl Default for CurveBoundary<Point<1>> {
     fn default() -> Self {
         Self {
@@ -113,3 +276,131 @@ impl CurveBoundaryElement for Point<1> {
 impl CurveBoundaryElement for Vertex {
     type Repr = Handle<Vertex>;
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use super::CurveBoundary;
+
+    #[test]
+    fn wrap() {
+        let period = Scalar::from(4.);
+        let wrap = |boundary: [f64; 2]| {
+            CurveBoundary::<Point<1>>::from(boundary.map(|t| [t]))
+                .wrap(period)
+                .inner
+                .map(|point| point.t.into_f64())
+        };
+
+        assert_eq!(wrap([1., 3.]), [1., 3.]);
+        assert_eq!(wrap([9., 5.]), [5., 1.]);
+        assert_eq!(wrap([-3., 2.]), [1., 6.]);
+    }
+
+    #[test]
+    fn contains() {
+        let a = boundary([1., 3.]);
+
+        assert!(a.contains([2.]));
+        assert!(a.contains([1.]));
+        assert!(a.contains([3.]));
+        assert!(!a.contains([0.]));
+        assert!(boundary([3., 1.]).contains([2.]));
+
+        assert!(a.contains_boundary(&boundary([1.5, 2.5])));
+        assert!(a.contains_boundary(&boundary([3., 1.])));
+        assert!(!a.contains_boundary(&boundary([2., 4.])));
+    }
+
+    #[test]
+    fn overlaps() {
+        let a = boundary([1., 3.]);
+
+        assert!(a.overlaps(&boundary([2., 4.])));
+        assert!(a.overlaps(&boundary([4., 0.])));
+        assert!(!a.overlaps(&boundary([3., 4.])));
+        assert!(!a.overlaps(&boundary([4., 5.])));
+        assert!(!a.overlaps(&boundary([2., 2.])));
+    }
+
+    #[test]
+    fn intersection() {
+        let intersection =
+            |a: [f64; 2], b: [f64; 2]| boundary(a).intersection(&boundary(b));
+
+        assert_eq!(intersection([1., 3.], [2., 4.]), Some(boundary([2., 3.])));
+        assert_eq!(intersection([1., 3.], [0., 4.]), Some(boundary([1., 3.])));
+        assert_eq!(intersection([0., 4.], [1., 3.]), Some(boundary([1., 3.])));
+        assert_eq!(intersection([1., 3.], [3., 4.]), None);
+        assert_eq!(intersection([1., 3.], [4., 5.]), None);
+
+        // The result has the direction of the first boundary.
+        assert_eq!(intersection([3., 1.], [2., 4.]), Some(boundary([3., 2.])));
+        assert_eq!(intersection([1., 3.], [4., 2.]), Some(boundary([2., 3.])));
+    }
+
+    #[test]
+    fn union() {
+        let union = |a: [f64; 2], b: [f64; 2]| boundary(a).union(&boundary(b));
+
+        assert_eq!(union([1., 3.], [2., 4.]), Some(boundary([1., 4.])));
+        assert_eq!(union([1., 3.], [0., 4.]), Some(boundary([0., 4.])));
+        assert_eq!(union([1., 3.], [3., 4.]), Some(boundary([1., 4.])));
+        assert_eq!(union([1., 3.], [4., 5.]), None);
+
+        // The result has the direction of the first boundary.
+        assert_eq!(union([3., 1.], [2., 4.]), Some(boundary([4., 1.])));
+        assert_eq!(union([1., 3.], [4., 2.]), Some(boundary([1., 4.])));
+    }
+
+    #[test]
+    fn difference() {
+        let difference =
+            |a: [f64; 2], b: [f64; 2]| boundary(a).difference(&boundary(b));
+
+        assert_eq!(
+            difference([0., 4.], [1., 3.]),
+            [boundary([0., 1.]), boundary([3., 4.])]
+        );
+        assert_eq!(difference([0., 4.], [2., 5.]), [boundary([0., 2.])]);
+        assert_eq!(difference([0., 4.], [-1., 2.]), [boundary([2., 4.])]);
+        assert_eq!(difference([0., 4.], [0., 2.]), [boundary([2., 4.])]);
+        assert_eq!(difference([1., 3.], [0., 4.]), []);
+        assert_eq!(difference([1., 3.], [1., 3.]), []);
+
+        // Boundaries that don't overlap, don't affect each other.
+        assert_eq!(difference([1., 3.], [3., 4.]), [boundary([1., 3.])]);
+        assert_eq!(difference([1., 3.], [2., 2.]), [boundary([1., 3.])]);
+        assert_eq!(difference([2., 2.], [0., 4.]), []);
+
+        // The result is ordered along, and has the direction of, the first
+        // boundary.
+        assert_eq!(
+            difference([4., 0.], [1., 3.]),
+            [boundary([4., 3.]), boundary([1., 0.])]
+        );
+        assert_eq!(
+            difference([0., 4.], [3., 1.]),
+            [boundary([0., 1.]), boundary([3., 4.])]
+        );
+    }
+
+    #[test]
+    fn union_all() {
+        let union_all = |boundaries: &[[f64; 2]]| {
+            CurveBoundary::union_all(boundaries.iter().copied().map(boundary))
+        };
+
+        assert_eq!(union_all(&[]), []);
+        assert_eq!(
+            union_all(&[[5., 6.], [2., 0.], [1., 3.], [3., 4.], [7., 7.]]),
+            [boundary([0., 4.]), boundary([5., 6.])]
+        );
+        assert_eq!(union_all(&[[0., 4.], [1., 2.]]), [boundary([0., 4.])]);
+    }
+
+    fn boundary(boundary: [f64; 2]) -> CurveBoundary<Point<1>> {
+        CurveBoundary::from(boundary.map(|t| [t]))
+    }
+}
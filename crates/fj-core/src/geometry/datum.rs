@@ -0,0 +1,532 @@
+//! # Datum geometry
+//!
+//! Datums are reference geometry: points, axes, and planes that are not part
+//! of a shape's boundary representation, but can be derived from it, and used
+//! as input when creating or modifying other shapes.
+//!
+//! Deriving positions from existing geometry like this, instead of recomputing
+//! them from the parameters that were used to create that geometry, means they
+//! stay correct, if those parameters change.
+//!
+//! Datums implement `Serialize` and `Deserialize`, so they can be stored next
+//! to a [serialized object graph](crate::serialization).
+
+use fj_math::{Line, Plane, Point, Scalar, Transform, Vector};
+
+use crate::{
+    operations::build::BuildSurface,
+    serialization::{
+        SerializedDatumAxis, SerializedDatumPlane, SerializedDatumPoint,
+    },
+    storage::Handle,
+    topology::{Face, HalfEdge, Surface},
+    Core,
+};
+
+use super::{Geometry, GlobalPath, SurfacePath, SurfaceProfile};
+
+/// A reference point
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Hash,
+    Ord,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(from = "SerializedDatumPoint", into = "SerializedDatumPoint")]
+pub struct DatumPoint {
+    /// The position of the point
+    pub position: Point<3>,
+}
+
+impl DatumPoint {
+    /// Create a datum point at the provided position
+    pub fn from_position(position: impl Into<Point<3>>) -> Self {
+        Self {
+            position: position.into(),
+        }
+    }
+
+    /// Create a datum point at the start of a half-edge
+    ///
+    /// Requires the surface that the half-edge is defined on.
+    pub fn at_start_of_half_edge(
+        half_edge: &Handle<HalfEdge>,
+        surface: &Handle<Surface>,
+        geometry: &Geometry,
+    ) -> Self {
+        let position_surface =
+            geometry.of_half_edge(half_edge).start_position();
+        let position = geometry
+            .of_surface(surface)
+            .point_from_surface_coords(position_surface);
+
+        Self { position }
+    }
+}
+
+/// A reference axis
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Hash,
+    Ord,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(from = "SerializedDatumAxis", into = "SerializedDatumAxis")]
+pub struct DatumAxis {
+    /// The line that defines the axis
+    ///
+    /// The direction of the line defines the direction of the axis. Its length
+    /// carries no meaning.
+    pub line: Line<3>,
+}
+
+impl DatumAxis {
+    /// Create a datum axis that goes through the two provided points
+    pub fn through_points(points: [DatumPoint; 2]) -> Self {
+        let (line, _) = Line::from_points(points.map(|point| point.position));
+        Self { line }
+    }
+
+    /// Create a datum axis from a half-edge
+    ///
+    /// Returns the line that a straight half-edge is on, pointing in the
+    /// direction of the half-edge. Returns the axis of the circle, if the
+    /// half-edge is circular. The axis points towards the side from which the
+    /// half-edge appears to run counter-clockwise.
+    ///
+    /// Requires the surface that the half-edge is defined on. On a plane, all
+    /// half-edges are either straight or circular. On a surface whose u-axis is
+    /// a circle, like a cylinder, a half-edge that runs along the u-axis is
+    /// circular, and its axis is the axis of the surface. A half-edge that runs
+    /// along the v-axis is straight, unless the surface is a sphere.
+    ///
+    /// Returns an error, if the half-edge is neither straight nor circular.
+    pub fn of_half_edge(
+        half_edge: &Handle<HalfEdge>,
+        surface: &Handle<Surface>,
+        geometry: &Geometry,
+    ) -> Result<Self, DatumError> {
+        let half_edge = geometry.of_half_edge(half_edge);
+        let surface = geometry.of_surface(surface);
+
+        let [start, end] = half_edge.boundary.inner;
+        let [start_global, end_global] = [start, end].map(|point| {
+            surface.point_from_surface_coords(
+                half_edge.path.point_from_path_coords(point),
+            )
+        });
+
+        let line = match (surface.u, half_edge.path) {
+            (GlobalPath::Line(_), SurfacePath::Circle(circle)) => {
+                let center = surface.point_from_surface_coords(circle.center());
+                let a = surface
+                    .vector_from_surface_coords(circle.center(), circle.a());
+                let b = surface
+                    .vector_from_surface_coords(circle.center(), circle.b());
+
+                let normal = a.cross(&b);
+                let direction = if start < end { normal } else { -normal };
+
+                Line::from_origin_and_direction(center, direction)
+            }
+            (GlobalPath::Line(_), SurfacePath::Line(line)) => {
+                let direction = surface.vector_from_surface_coords(
+                    line.origin(),
+                    line.direction(),
+                );
+                let direction =
+                    if start < end { direction } else { -direction };
+
+                Line::from_origin_and_direction(start_global, direction)
+            }
+            (GlobalPath::Circle(circle), SurfacePath::Line(line))
+                if line.direction().v == Scalar::ZERO =>
+            {
+                // The half-edge runs along a copy of the surface's u-axis,
+                // which has been moved along its v-axis.
+                let v = line.origin().v;
+                let offset = match surface.profile {
+                    SurfaceProfile::Linear { .. } => v,
+                    SurfaceProfile::Spherical | SurfaceProfile::Toroidal => {
+                        v.sin_cos().0
+                    }
+                };
+                let center = circle.center() + surface.v * offset;
+
+                let normal = circle.a().cross(&circle.b());
+                let is_ccw =
+                    (line.direction().u > Scalar::ZERO) == (start < end);
+                let direction = if is_ccw { normal } else { -normal };
+
+                Line::from_origin_and_direction(center, direction)
+            }
+            (GlobalPath::Circle(_), SurfacePath::Line(line))
+                if line.direction().u == Scalar::ZERO
+                    && !surface.is_curved_along_v()
+                    && start_global != end_global =>
+            {
+                Line::from_origin_and_direction(
+                    start_global,
+                    end_global - start_global,
+                )
+            }
+            (GlobalPath::Circle(_), _) => {
+                return Err(DatumError::NotStraightOrCircular);
+            }
+        };
+
+        Ok(Self { line })
+    }
+
+    /// Create a datum axis from the axis of a cylindrical face
+    ///
+    /// Returns `None`, if the face is not cylindrical. This includes faces on
+    /// cones, spheres, and tori, which have an axis too, but aren't cylinders.
+    pub fn of_cylindrical_face(
+        face: &Face,
+        geometry: &Geometry,
+    ) -> Option<Self> {
+        let surface = geometry.of_surface(face.surface());
+
+        let GlobalPath::Circle(circle) = surface.u else {
+            return None;
+        };
+        if surface.profile != SurfaceProfile::STRAIGHT {
+            return None;
+        }
+
+        let line = Line::from_origin_and_direction(circle.center(), surface.v);
+        Some(Self { line })
+    }
+
+    /// Access the origin of the axis
+    pub fn origin(&self) -> Point<3> {
+        self.line.origin()
+    }
+
+    /// Access the normalized direction of the axis
+    pub fn direction(&self) -> Vector<3> {
+        self.line.direction().normalize()
+    }
+
+    /// Compute a transform that rotates around this axis
+    ///
+    /// The rotation follows the right-hand rule, in regards to the direction
+    /// of the axis.
+    pub fn rotation(&self, angle_rad: impl Into<Scalar>) -> Transform {
+        let offset = self.origin().coords;
+
+        Transform::translation(offset)
+            * Transform::rotation(self.direction() * angle_rad.into())
+            * Transform::translation(-offset)
+    }
+}
+
+/// A reference plane
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Hash,
+    Ord,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(from = "SerializedDatumPlane", into = "SerializedDatumPlane")]
+pub struct DatumPlane {
+    /// The plane
+    pub plane: Plane,
+}
+
+impl DatumPlane {
+    /// Create a datum plane that goes through the three provided points
+    ///
+    /// The plane's origin is the first point. Its u- and v-axes point from the
+    /// first point to the second and third points, respectively.
+    pub fn through_points(points: [DatumPoint; 3]) -> Self {
+        let [a, b, c] = points.map(|point| point.position);
+        let plane = Plane::from_parametric(a, b - a, c - a);
+
+        Self { plane }
+    }
+
+    /// Create a datum plane from a planar face
+    ///
+    /// Returns `None`, if the face is not planar.
+    pub fn of_face(face: &Face, geometry: &Geometry) -> Option<Self> {
+        let surface = geometry.of_surface(face.surface());
+
+        let GlobalPath::Line(u) = surface.u else {
+            return None;
+        };
+
+        let plane =
+            Plane::from_parametric(u.origin(), u.direction(), surface.v);
+        Some(Self { plane })
+    }
+
+    /// Create a datum plane that is offset from this one along its normal
+    #[must_use]
+    pub fn offset(&self, distance: impl Into<Scalar>) -> Self {
+        let offset = self.plane.normal() * distance.into();
+        let plane = Plane::from_parametric(
+            self.plane.origin() + offset,
+            self.plane.u(),
+            self.plane.v(),
+        );
+
+        Self { plane }
+    }
+
+    /// Create a datum axis from the normal of the plane, through its origin
+    pub fn normal_axis(&self) -> DatumAxis {
+        let line = Line::from_origin_and_direction(
+            self.plane.origin(),
+            self.plane.normal(),
+        );
+
+        DatumAxis { line }
+    }
+
+    /// Create a surface that matches the plane
+    ///
+    /// This can be used to create or sweep sketches on the plane.
+    pub fn to_surface(&self, core: &mut Core) -> Handle<Surface> {
+        let u = GlobalPath::Line(Line::from_origin_and_direction(
+            self.plane.origin(),
+            self.plane.u(),
+        ));
+
+        Surface::from_uv(u, self.plane.v(), core)
+    }
+}
+
+/// Error creating a datum
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum DatumError {
+    /// The half-edge is neither straight nor circular
+    #[error("Half-edge is neither straight nor circular")]
+    NotStraightOrCircular,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        geometry::GlobalPath,
+        operations::{
+            build::{BuildFace, BuildSolid},
+            reverse::Reverse,
+        },
+        topology::{Cycle, Face, Solid},
+        Core,
+    };
+
+    use super::{DatumAxis, DatumError, DatumPlane, DatumPoint};
+
+    #[test]
+    fn datum_plane_of_face() {
+        let mut core = Core::new();
+
+        let face = Face::circle(
+            core.layers.topology.surfaces.xz_plane(),
+            [0., 0.],
+            1.,
+            &mut core,
+        );
+        let plane = DatumPlane::of_face(&face, &core.layers.geometry)
+            .unwrap()
+            .offset(2.);
+
+        assert_eq!(plane.plane.origin(), Point::from([0., -2., 0.]));
+        assert_eq!(
+            plane.normal_axis().direction(),
+            Vector::from([0., -1., 0.])
+        );
+    }
+
+    #[test]
+    fn datum_axis_of_cylindrical_face() {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+        let axes = cylinder
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .filter_map(|face| {
+                DatumAxis::of_cylindrical_face(face, &core.layers.geometry)
+            })
+            .collect::<Vec<_>>();
+
+        let [axis] = axes.as_slice() else {
+            panic!("Expected cylinder to have exactly one curved face");
+        };
+        assert_eq!(axis.origin(), Point::origin());
+        assert_eq!(
+            axis.direction().cross(&Vector::unit_z()),
+            Vector::from([0., 0., 0.])
+        );
+    }
+
+    #[test]
+    fn datum_axis_of_face_that_is_not_cylindrical() {
+        let mut core = Core::new();
+
+        let cone = Solid::cone(2., 1., 3., &mut core);
+        let sphere = Solid::sphere(1., &mut core);
+
+        for solid in [cone, sphere] {
+            for face in solid.shells().only().faces() {
+                assert_eq!(
+                    DatumAxis::of_cylindrical_face(face, &core.layers.geometry),
+                    None,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn datum_axis_of_reversed_half_edge() {
+        let mut core = Core::new();
+
+        let circle = Face::circle(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0.],
+            1.,
+            &mut core,
+        );
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+        let side = cylinder
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .find(|face| {
+                matches!(
+                    core.layers.geometry.of_surface(face.surface()).u,
+                    GlobalPath::Circle(_)
+                )
+            })
+            .expect("Cylinder has a curved face");
+
+        for face in [&circle, side] {
+            let exterior = face.region().exterior();
+            let reversed = exterior.reverse(&mut core);
+
+            let axes = |cycle: &Cycle| {
+                cycle
+                    .half_edges()
+                    .iter()
+                    .map(|half_edge| {
+                        DatumAxis::of_half_edge(
+                            half_edge,
+                            face.surface(),
+                            &core.layers.geometry,
+                        )
+                        .expect("Half-edge is straight or circular")
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            // Reversing a cycle also reverses the order of its half-edges.
+            let mut reversed = axes(&reversed);
+            reversed.reverse();
+
+            for (axis, reversed) in axes(exterior).into_iter().zip(reversed) {
+                assert_eq!(axis.direction(), -reversed.direction());
+            }
+        }
+    }
+
+    #[test]
+    fn datum_axis_of_half_edge_on_curved_surface() {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+        let sphere = Solid::sphere(1., &mut core);
+
+        let [cylinder, sphere] = [cylinder, sphere].map(|solid| {
+            solid
+                .shells()
+                .only()
+                .faces()
+                .iter()
+                .find(|face| {
+                    matches!(
+                        core.layers.geometry.of_surface(face.surface()).u,
+                        GlobalPath::Circle(_)
+                    )
+                })
+                .expect("Solid has a curved face")
+                .clone()
+        });
+
+        let axes = |face: &Face| {
+            face.region()
+                .exterior()
+                .half_edges()
+                .iter()
+                .map(|half_edge| {
+                    DatumAxis::of_half_edge(
+                        half_edge,
+                        face.surface(),
+                        &core.layers.geometry,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The circular edges of the cylinder are centered on its axis, at
+        // both ends. The straight edge, along its seam, is parallel to it.
+        let cylinder = axes(&cylinder);
+        assert_eq!(cylinder.len(), 4);
+        let mut origins = Vec::new();
+        for axis in cylinder {
+            let axis = axis.expect("Cylinder edges are straight or circular");
+            assert_eq!(
+                axis.direction().cross(&Vector::unit_z()),
+                Vector::from([0., 0., 0.])
+            );
+            origins.push(axis.origin());
+        }
+        assert!(origins.contains(&Point::from([0., 0., 0.])));
+        assert!(origins.contains(&Point::from([0., 0., 2.])));
+
+        // The seam of the sphere is neither straight nor circular, as far as
+        // the surface's coordinate system is concerned.
+        assert!(axes(&sphere).contains(&Err(DatumError::NotStraightOrCircular)));
+    }
+
+    #[test]
+    fn datum_axis_rotation() {
+        let axis = DatumAxis::through_points(
+            [[1., 0., 0.], [1., 0., 1.]].map(DatumPoint::from_position),
+        );
+        let rotation = axis.rotation(Scalar::PI);
+
+        let point = rotation.transform_point(&Point::from([2., 0., 0.]));
+        assert!(
+            (point - Point::from([0., 0., 0.])).magnitude()
+                < Scalar::from(1e-12)
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! # Geometric data that is derived from other geometry
+//!
+//! Normals, areas, lengths, and bounding boxes of objects follow from the
+//! geometry that is defined for them, but can be expensive to compute. This
+//! module computes them on demand, and caches the results within [`Geometry`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::bounding_volume::BoundingVolume,
+    storage::Handle,
+    topology::{Cycle, Face, HalfEdge, Surface},
+};
+
+use super::{
+    DatumPlane, Geometry, GlobalPath, HalfEdgeGeom, SurfaceGeom, SurfacePath,
+};
+
+impl Geometry {
+    /// # Access the length of the provided half-edge
+    ///
+    /// The length is measured in 3D space, on the provided surface. Curved
+    /// half-edges, and half-edges on curved surfaces, are approximated for
+    /// this, so their length is an estimate.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the geometry of the half-edge or the surface is not defined.
+    pub fn length_of_half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+        surface: &Handle<Surface>,
+    ) -> Scalar {
+        let cached = self
+            .derived
+            .cache()
+            .half_edge_lengths
+            .get(half_edge)
+            .and_then(|lengths| lengths.get(surface))
+            .copied();
+        if let Some(length) = cached {
+            return length;
+        }
+
+        let length = length_of_half_edge(
+            self.of_half_edge(half_edge),
+            Some(self.of_surface(surface)),
+        );
+
+        let mut cache = self.derived.cache();
+        cache
+            .half_edge_lengths
+            .entry(half_edge.clone())
+            .or_default()
+            .insert(surface.clone(), length);
+        cache.surfaces.insert(surface.clone());
+
+        length
+    }
+
+    /// # Access the area of the provided face
+    ///
+    /// The area of any holes in the face is not included. Like lengths, areas
+    /// of curved faces are estimates.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the geometry of the face is not fully defined.
+    pub fn area_of_face(&self, face: &Handle<Face>) -> Scalar {
+        self.derived_of_face(face).area
+    }
+
+    /// # Access the perimeter of the provided face
+    ///
+    /// This is the sum of the lengths of all half-edges of the face, including
+    /// the ones that bound its holes.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the geometry of the face is not fully defined.
+    pub fn perimeter_of_face(&self, face: &Handle<Face>) -> Scalar {
+        self.derived_of_face(face).perimeter
+    }
+
+    /// # Access the normal of the provided face
+    ///
+    /// Returns the unit normal of the face's surface, or `None`, if the face is
+    /// not planar.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the geometry of the face's surface is not defined.
+    pub fn normal_of_face(&self, face: &Handle<Face>) -> Option<Vector<3>> {
+        self.derived_of_face(face).normal
+    }
+
+    /// # Access the axis-aligned bounding box of the provided face
+    ///
+    /// See [`BoundingVolume`] for details.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the geometry of the face is not fully defined.
+    pub fn aabb_of_face(&self, face: &Handle<Face>) -> Option<Aabb<3>> {
+        self.derived_of_face(face).aabb
+    }
+
+    fn derived_of_face(&self, face: &Handle<Face>) -> DerivedFaceGeom {
+        if let Some(derived) = self.derived.cache().faces.get(face) {
+            return *derived;
+        }
+
+        // The lengths of the half-edges get cached here too. The invalidation
+        // logic relies on that.
+        let perimeter = face
+            .region()
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges())
+            .fold(Scalar::ZERO, |perimeter, half_edge| {
+                perimeter + self.length_of_half_edge(half_edge, face.surface())
+            });
+
+        let derived = DerivedFaceGeom {
+            area: area_of_face(face, self),
+            perimeter,
+            normal: DatumPlane::of_face(face, self)
+                .map(|datum| datum.plane.normal()),
+            aabb: face.aabb(self),
+        };
+
+        let mut cache = self.derived.cache();
+        cache.faces.insert(face.clone(), derived);
+        cache.surfaces.insert(face.surface().clone());
+
+        derived
+    }
+}
+
+/// # Cache for geometry that is derived from other geometry
+///
+/// Entries are removed, when the geometry they are derived from is redefined.
+#[derive(Default)]
+pub struct DerivedGeometry {
+    cache: Mutex<DerivedGeometryCache>,
+}
+
+impl DerivedGeometry {
+    /// Remove all entries that depend on the provided half-edge
+    pub fn invalidate_half_edge(&mut self, half_edge: &Handle<HalfEdge>) {
+        let cache = self.cache_mut();
+
+        // The derived geometry of a face includes the lengths of its half-edges,
+        // so if no length is cached, no face can depend on the half-edge.
+        if cache.half_edge_lengths.remove(half_edge).is_none() {
+            return;
+        }
+
+        cache.faces.retain(|face, _| {
+            !face
+                .region()
+                .all_cycles()
+                .flat_map(|cycle| cycle.half_edges())
+                .any(|h| h == half_edge)
+        });
+    }
+
+    /// Remove all entries that depend on the provided surface
+    pub fn invalidate_surface(&mut self, surface: &Handle<Surface>) {
+        let cache = self.cache_mut();
+
+        if !cache.surfaces.remove(surface) {
+            return;
+        }
+
+        for lengths in cache.half_edge_lengths.values_mut() {
+            lengths.remove(surface);
+        }
+        cache.faces.retain(|face, _| face.surface() != surface);
+    }
+
+    fn cache(&self) -> MutexGuard<DerivedGeometryCache> {
+        // The cache is never left in an inconsistent state, so it remains
+        // usable, even if another thread panicked while holding the lock.
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn cache_mut(&mut self) -> &mut DerivedGeometryCache {
+        self.cache.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for DerivedGeometry {
+    fn clone(&self) -> Self {
+        Self {
+            cache: Mutex::new(self.cache().clone()),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct DerivedGeometryCache {
+    half_edge_lengths:
+        BTreeMap<Handle<HalfEdge>, BTreeMap<Handle<Surface>, Scalar>>,
+    faces: BTreeMap<Handle<Face>, DerivedFaceGeom>,
+
+    /// All surfaces that any cached entry depends on
+    surfaces: BTreeSet<Handle<Surface>>,
+}
+
+#[derive(Clone, Copy)]
+struct DerivedFaceGeom {
+    area: Scalar,
+    perimeter: Scalar,
+    normal: Option<Vector<3>>,
+    aabb: Option<Aabb<3>>,
+}
+
+/// Sample points on a half-edge, in surface coordinates
+///
+/// Includes the start and end points. Points on curved half-edges are spaced
+/// evenly, and are only used to estimate lengths and areas, so their exact
+/// number doesn't matter much.
+///
+/// If a surface is provided, half-edges on that surface are considered curved,
+/// if the surface is.
+fn sample_half_edge(
+    half_edge: &HalfEdgeGeom,
+    surface: Option<&SurfaceGeom>,
+) -> Vec<Point<2>> {
+    const NUM_SEGMENTS_OF_CURVE: u64 = 32;
+
+    let is_surface_curved = surface
+        .map(|surface| matches!(surface.u, GlobalPath::Circle(_)))
+        .unwrap_or(false);
+    let num_segments = match half_edge.path {
+        SurfacePath::Line(_) if !is_surface_curved => 1,
+        _ => NUM_SEGMENTS_OF_CURVE,
+    };
+
+    let [start, end] = half_edge.boundary.inner;
+    (0..=num_segments)
+        .map(|i| {
+            let t = Scalar::from_u64(i) / Scalar::from_u64(num_segments);
+            half_edge
+                .path
+                .point_from_path_coords(start + (end - start) * t)
+        })
+        .collect()
+}
+
+/// Compute the length of a half-edge
+///
+/// The length is measured in 3D space, if a surface is provided, and in surface
+/// coordinates otherwise.
+pub(crate) fn length_of_half_edge(
+    half_edge: &HalfEdgeGeom,
+    surface: Option<&SurfaceGeom>,
+) -> Scalar {
+    let points = sample_half_edge(half_edge, surface)
+        .into_iter()
+        .map(|point| match surface {
+            Some(surface) => surface.point_from_surface_coords(point),
+            None => Point::from([point.u, point.v, Scalar::ZERO]),
+        })
+        .collect::<Vec<_>>();
+
+    points.windows(2).fold(Scalar::ZERO, |length, segment| {
+        length + segment[0].distance_to(&segment[1])
+    })
+}
+
+/// Compute the area of a face in 3D space, without caching it
+pub(crate) fn area_of_face(face: &Face, geometry: &Geometry) -> Scalar {
+    let surface = geometry.of_surface(face.surface());
+
+    // The exterior and interior cycles have opposite winding, so summing their
+    // signed areas subtracts the holes from the exterior.
+    let (signed_area, centroid) = face
+        .region()
+        .all_cycles()
+        .map(|cycle| signed_area_in_surface(cycle, geometry))
+        .fold((Scalar::ZERO, Vector::from([0., 0.])), |a, b| {
+            (a.0 + b.0, a.1 + b.1)
+        });
+    let centroid = if signed_area == Scalar::ZERO {
+        Point::origin()
+    } else {
+        Point {
+            coords: centroid / signed_area,
+        }
+    };
+
+    signed_area.abs() * area_scale(surface, centroid)
+}
+
+/// Compute the signed area of a cycle in surface coordinates
+///
+/// Also returns the centroid of the area, multiplied by the signed area, so
+/// the centroids of multiple cycles can be combined.
+fn signed_area_in_surface(
+    cycle: &Handle<Cycle>,
+    geometry: &Geometry,
+) -> (Scalar, Vector<2>) {
+    let points = cycle
+        .half_edges()
+        .iter()
+        .flat_map(|half_edge| {
+            // Straight lines stay straight in surface coordinates, so the
+            // area can be computed there, without further subdivision.
+            let mut points =
+                sample_half_edge(geometry.of_half_edge(half_edge), None);
+            // The end point is the start point of the next half-edge.
+            points.pop();
+            points
+        })
+        .collect::<Vec<_>>();
+
+    points.iter().zip(points.iter().cycle().skip(1)).fold(
+        (Scalar::ZERO, Vector::from([0., 0.])),
+        |(area, centroid), (a, b)| {
+            let cross = a.u * b.v - b.u * a.v;
+            (
+                area + cross / 2.,
+                centroid + (a.coords + b.coords) * cross / 6.,
+            )
+        },
+    )
+}
+
+/// Compute the factor that scales areas in surface coordinates to 3D space
+///
+/// Surfaces can stretch their coordinates differently in different places, like
+/// the u-coordinates of a sphere, which shrink towards its poles. The factor
+/// is only computed at the provided point, which is good enough for the
+/// estimates that this module provides.
+fn area_scale(surface: &SurfaceGeom, at: Point<2>) -> Scalar {
+    let du = match surface.u {
+        GlobalPath::Circle(circle) => {
+            // The derivative of a point on the circle is the same as the point,
+            // rotated by a quarter turn.
+            circle.vector_from_circle_coords([at.u + Scalar::PI / 2.])
+        }
+        GlobalPath::Line(line) => line.direction(),
+    };
+
+    du.cross(&surface.v).magnitude() * surface.scale_at(at.v).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Scalar, Vector};
+
+    use crate::{
+        geometry::{GlobalPath, SurfaceGeom, SurfaceProfile},
+        operations::{
+            build::{BuildFace, BuildSurface},
+            insert::Insert,
+        },
+        topology::{Face, Surface},
+        Core,
+    };
+
+    #[test]
+    fn derived_geometry_of_face() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let face = Face::polygon(
+            surface,
+            [[0., 0.], [2., 0.], [2., 1.], [0., 1.]],
+            &mut core,
+        )
+        .insert(&mut core);
+
+        let geometry = &core.layers.geometry;
+        assert_eq!(geometry.area_of_face(&face), Scalar::from(2.));
+        assert_eq!(geometry.perimeter_of_face(&face), Scalar::from(6.));
+        assert_eq!(geometry.normal_of_face(&face), Some(Vector::unit_z()));
+        assert_eq!(
+            geometry.aabb_of_face(&face).map(|aabb| aabb.max),
+            Some([2., 1., 0.].into()),
+        );
+    }
+
+    #[test]
+    fn derived_geometry_is_invalidated_on_redefinition() {
+        let mut core = Core::new();
+
+        let surface =
+            Surface::from_uv(GlobalPath::x_axis(), [0., 1., 0.], &mut core);
+        let face = Face::polygon(
+            surface.clone(),
+            [[0., 0.], [1., 0.], [0., 1.]],
+            &mut core,
+        )
+        .insert(&mut core);
+        assert_eq!(core.layers.geometry.area_of_face(&face), Scalar::from(0.5));
+
+        // Scaling the surface by a factor of 2 in both directions quadruples
+        // the area of the face.
+        let (u, _) = GlobalPath::line_from_points([[0., 0., 0.], [2., 0., 0.]]);
+        core.layers.geometry.define_surface(
+            surface,
+            SurfaceGeom {
+                u,
+                v: Vector::from([0., 2., 0.]),
+                profile: SurfaceProfile::STRAIGHT,
+            },
+        );
+        assert_eq!(core.layers.geometry.area_of_face(&face), Scalar::from(2.));
+    }
+}
@@ -3,18 +3,24 @@ use std::collections::BTreeMap;
 use fj_math::Vector;
 
 use crate::{
-    storage::Handle,
+    storage::{Handle, ObjectId},
     topology::{Curve, HalfEdge, Surface, Topology},
 };
 
-use super::{CurveGeom, GlobalPath, HalfEdgeGeom, LocalCurveGeom, SurfaceGeom};
+use super::{
+    CurveGeom, DerivedGeometry, GlobalPath, HalfEdgeGeom, LocalCurveGeom,
+    SurfaceGeom, SurfaceProfile,
+};
 
 /// Geometric data that is associated with topological objects
+#[derive(Clone)]
 pub struct Geometry {
     curve: BTreeMap<Handle<Curve>, CurveGeom>,
     half_edge: BTreeMap<Handle<HalfEdge>, HalfEdgeGeom>,
     surface: BTreeMap<Handle<Surface>, SurfaceGeom>,
 
+    pub(super) derived: DerivedGeometry,
+
     space_2d: Handle<Surface>,
 
     xy_plane: Handle<Surface>,
@@ -30,6 +36,8 @@ impl Geometry {
             half_edge: BTreeMap::new(),
             surface: BTreeMap::new(),
 
+            derived: DerivedGeometry::default(),
+
             space_2d: topology.surfaces.space_2d(),
 
             xy_plane: topology.surfaces.xy_plane(),
@@ -42,6 +50,7 @@ impl Geometry {
             SurfaceGeom {
                 u: GlobalPath::x_axis(),
                 v: Vector::unit_y(),
+                profile: SurfaceProfile::STRAIGHT,
             },
         );
         self_.define_surface_inner(
@@ -49,6 +58,7 @@ impl Geometry {
             SurfaceGeom {
                 u: GlobalPath::x_axis(),
                 v: Vector::unit_z(),
+                profile: SurfaceProfile::STRAIGHT,
             },
         );
         self_.define_surface_inner(
@@ -56,6 +66,7 @@ impl Geometry {
             SurfaceGeom {
                 u: GlobalPath::y_axis(),
                 v: Vector::unit_z(),
+                profile: SurfaceProfile::STRAIGHT,
             },
         );
 
@@ -80,6 +91,7 @@ impl Geometry {
         half_edge: Handle<HalfEdge>,
         geometry: HalfEdgeGeom,
     ) {
+        self.derived.invalidate_half_edge(&half_edge);
         self.half_edge.insert(half_edge, geometry);
     }
 
@@ -100,9 +112,24 @@ impl Geometry {
             panic!("Attempting to redefine basis plane.");
         }
 
+        self.derived.invalidate_surface(&surface);
         self.surface.insert(surface, geometry);
     }
 
+    /// Remove the geometry of all objects that are not to be kept
+    ///
+    /// Also clears all derived geometry, as it might refer to removed objects.
+    pub(crate) fn retain_objects(&mut self, keep: impl Fn(ObjectId) -> bool) {
+        self.curve.retain(|curve, geometry| {
+            geometry.definitions.retain(|surface, _| keep(surface.id()));
+            keep(curve.id())
+        });
+        self.half_edge.retain(|half_edge, _| keep(half_edge.id()));
+        self.surface.retain(|surface, _| keep(surface.id()));
+
+        self.derived = DerivedGeometry::default();
+    }
+
     /// # Access the geometry of the provided curve
     ///
     /// ## Panics
@@ -134,6 +161,20 @@ impl Geometry {
             .expect("Expected geometry of surface to be defined")
     }
 
+    /// Iterate over the geometry of all half-edges
+    pub(crate) fn half_edges(
+        &self,
+    ) -> impl Iterator<Item = (&Handle<HalfEdge>, &HalfEdgeGeom)> {
+        self.half_edge.iter()
+    }
+
+    /// Iterate over the geometry of all surfaces
+    pub(crate) fn surfaces(
+        &self,
+    ) -> impl Iterator<Item = (&Handle<Surface>, &SurfaceGeom)> {
+        self.surface.iter()
+    }
+
     /// Access the geometry of the xy-plane
     pub fn xy_plane(&self) -> &SurfaceGeom {
         self.of_surface(&self.xy_plane)
@@ -33,6 +33,14 @@ pub struct HalfEdgeGeom {
     pub path: SurfacePath,
 
     /// # The boundary of the half-edge on its curve
+    ///
+    /// The half-edge runs from the first to the second point of the boundary.
+    /// This doesn't need to be the direction of the path. If the boundary is
+    /// not normalized, the half-edge runs against it. This allows sibling
+    /// half-edges to share the same path, with reversed boundaries.
+    ///
+    /// See [`HalfEdgeGeom::runs_along_path`], [`HalfEdgeGeom::reverse`], and
+    /// [`HalfEdgeGeom::reverse_path`].
     pub boundary: CurveBoundary<Point<1>>,
 }
 
@@ -55,4 +63,82 @@ impl HalfEdgeGeom {
         let [start, _] = self.boundary.inner;
         self.path.point_from_path_coords(start)
     }
+
+    /// Compute the surface position where the half-edge ends
+    pub fn end_position(&self) -> Point<2> {
+        let [_, end] = self.boundary.inner;
+        self.path.point_from_path_coords(end)
+    }
+
+    /// Indicate whether the half-edge runs along the direction of its path
+    ///
+    /// Returns `false`, if it runs against the direction of its path.
+    pub fn runs_along_path(&self) -> bool {
+        self.boundary.is_normalized()
+    }
+
+    /// Reverse the direction of the half-edge
+    ///
+    /// The path stays the same, which makes this suitable for creating the
+    /// geometry of a sibling half-edge.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.with_boundary(self.boundary.reverse())
+    }
+
+    /// Reverse the direction of the path
+    ///
+    /// The half-edge keeps its start and end positions, and its direction. Its
+    /// boundary is converted to coordinates on the reversed path.
+    #[must_use]
+    pub fn reverse_path(self) -> Self {
+        Self {
+            path: self.path.reverse(),
+            boundary: CurveBoundary {
+                inner: self
+                    .boundary
+                    .inner
+                    .map(|point| self.path.reverse_path_coords(point)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::geometry::{CurveBoundary, SurfacePath};
+
+    use super::HalfEdgeGeom;
+
+    #[test]
+    fn reverse() {
+        let (line, _) = SurfacePath::line_from_points([[0., 0.], [1., 0.]]);
+        let circle = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+
+        for geometry in [
+            HalfEdgeGeom {
+                path: line,
+                boundary: CurveBoundary::from([[0.5], [2.]]),
+            },
+            HalfEdgeGeom {
+                path: circle,
+                boundary: CurveBoundary::from([[Scalar::ZERO], [Scalar::PI]])
+                    .reverse(),
+            },
+        ] {
+            let reversed = geometry.reverse();
+            assert_eq!(reversed.path, geometry.path);
+            assert_eq!(reversed.start_position(), geometry.end_position());
+            assert_eq!(reversed.end_position(), geometry.start_position());
+            assert_ne!(reversed.runs_along_path(), geometry.runs_along_path());
+
+            let reversed = geometry.reverse_path();
+            assert_eq!(reversed.path, geometry.path.reverse());
+            assert_eq!(reversed.start_position(), geometry.start_position());
+            assert_eq!(reversed.end_position(), geometry.end_position());
+            assert_ne!(reversed.runs_along_path(), geometry.runs_along_path());
+        }
+    }
 }
@@ -2,6 +2,8 @@
 
 mod boundary;
 mod curve;
+mod datum;
+mod derived;
 mod geometry;
 mod half_edge;
 mod path;
@@ -10,8 +12,13 @@ mod surface;
 pub use self::{
     boundary::{CurveBoundary, CurveBoundaryElement},
     curve::{CurveGeom, LocalCurveGeom},
+    datum::{DatumAxis, DatumError, DatumPlane, DatumPoint},
     geometry::Geometry,
     half_edge::HalfEdgeGeom,
     path::{GlobalPath, SurfacePath},
-    surface::SurfaceGeom,
+    surface::{SurfaceGeom, SurfaceProfile},
+};
+
+pub(crate) use self::derived::{
+    area_of_face, length_of_half_edge, DerivedGeometry,
 };
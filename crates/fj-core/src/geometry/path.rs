@@ -4,6 +4,8 @@
 
 use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
 
+use super::CurveBoundary;
+
 /// A path through surface (2D) space
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum SurfacePath {
@@ -69,7 +71,105 @@ impl SurfacePath {
         }
     }
 
+    /// Project a point onto the path
+    ///
+    /// Returns the path coordinates of the point on the path that is closest
+    /// to the provided point.
+    pub fn project_point(&self, point: impl Into<Point<2>>) -> Point<1> {
+        let point = point.into();
+
+        match self {
+            Self::Circle(circle) => {
+                let center_to_point = point - circle.center();
+                let u = center_to_point.dot(&circle.a());
+                let v = center_to_point.dot(&circle.b());
+
+                project_onto_circle(u, v)
+            }
+            Self::Line(line) => line.point_to_line_coords(point),
+        }
+    }
+
+    /// Compute the length of the path between the points of a boundary
+    pub fn arc_length(&self, boundary: CurveBoundary<Point<1>>) -> Scalar {
+        let [a, b] = boundary.inner;
+        (b.t - a.t).abs() * self.length_per_path_unit()
+    }
+
+    /// Find the point at the given distance along the path
+    ///
+    /// Starts at the first point of the boundary and walks towards the second
+    /// one. Returns the point, in path coordinates. The distance is not
+    /// limited to the boundary, so the returned point might be outside of it.
+    pub fn point_at_arc_length(
+        &self,
+        boundary: CurveBoundary<Point<1>>,
+        arc_length: impl Into<Scalar>,
+    ) -> Point<1> {
+        let [a, b] = boundary.inner;
+        let direction = (b.t - a.t).sign().to_scalar();
+
+        a + Vector::from([
+            direction * arc_length.into() / self.length_per_path_unit()
+        ])
+    }
+
+    /// Compute the unit tangent vector at the given point on the path
+    ///
+    /// The tangent points in the direction of increasing path coordinates.
+    pub fn tangent_at(&self, point: impl Into<Point<1>>) -> Vector<2> {
+        let point = point.into();
+
+        let derivative = match self {
+            Self::Circle(circle) => {
+                let (sin, cos) = point.t.sin_cos();
+                circle.b() * cos - circle.a() * sin
+            }
+            Self::Line(line) => line.direction(),
+        };
+
+        derivative.normalize()
+    }
+
+    /// Compute the curvature of the path at the given point
+    ///
+    /// The curvature is the inverse of the radius of the circle that best
+    /// approximates the path at that point. It is zero for lines.
+    pub fn curvature_at(&self, _: impl Into<Point<1>>) -> Scalar {
+        match self {
+            Self::Circle(circle) => Scalar::ONE / circle.radius(),
+            Self::Line(_) => Scalar::ZERO,
+        }
+    }
+
+    /// Access the period of the path, if it is periodic
+    ///
+    /// Circles are periodic, as they connect to themselves: Path coordinates
+    /// that are a whole number of periods apart refer to the same point.
+    /// Returns `None` for lines.
+    pub fn period(&self) -> Option<Scalar> {
+        match self {
+            Self::Circle(_) => Some(Scalar::TAU),
+            Self::Line(_) => None,
+        }
+    }
+
+    /// The length of the path that one unit of path coordinates covers
+    ///
+    /// Both lines and circles are parametrized with constant speed, which
+    /// makes this independent of the position on the path.
+    fn length_per_path_unit(&self) -> Scalar {
+        match self {
+            Self::Circle(circle) => circle.radius(),
+            Self::Line(line) => line.direction().magnitude(),
+        }
+    }
+
     /// Create a new path that is the reverse of this one
+    ///
+    /// The reversed path passes through the same points, but its coordinates
+    /// run the other way. Use [`SurfacePath::reverse_path_coords`] to convert
+    /// coordinates between both paths.
     #[must_use]
     pub fn reverse(self) -> Self {
         match self {
@@ -77,6 +177,26 @@ impl SurfacePath {
             Self::Line(line) => Self::Line(line.reverse()),
         }
     }
+
+    /// Convert path coordinates into those of the reversed path
+    ///
+    /// Returns the coordinates that the point at `point` on this path has, on
+    /// the path that [`SurfacePath::reverse`] returns. As reversing twice
+    /// returns the original path, this also converts coordinates back.
+    pub fn reverse_path_coords(&self, point: impl Into<Point<1>>) -> Point<1> {
+        let t = point.into().t;
+
+        let t = match self {
+            // Reversing a circle mirrors it, which maps each angle to its
+            // negation.
+            Self::Circle(_) => -t,
+
+            // Reversing a line moves its origin to where `1` was.
+            Self::Line(_) => Scalar::ONE - t,
+        };
+
+        Point::from([t])
+    }
 }
 
 /// A path through global (3D) space
@@ -139,6 +259,16 @@ impl GlobalPath {
         }
     }
 
+    /// Access the period of the path, if it is periodic
+    ///
+    /// See [`SurfacePath::period`].
+    pub fn period(&self) -> Option<Scalar> {
+        match self {
+            Self::Circle(_) => Some(Scalar::TAU),
+            Self::Line(_) => None,
+        }
+    }
+
     /// Convert a point on the path into global coordinates
     pub fn point_from_path_coords(
         &self,
@@ -161,6 +291,89 @@ impl GlobalPath {
         }
     }
 
+    /// Project a point onto the path
+    ///
+    /// Returns the path coordinates of the point on the path that is closest
+    /// to the provided point. For circles, this is the closest point on the
+    /// circle to the projection of the point into the circle's plane.
+    pub fn project_point(&self, point: impl Into<Point<3>>) -> Point<1> {
+        let point = point.into();
+
+        match self {
+            Self::Circle(circle) => {
+                let center_to_point = point - circle.center();
+                let u = center_to_point.dot(&circle.a());
+                let v = center_to_point.dot(&circle.b());
+
+                project_onto_circle(u, v)
+            }
+            Self::Line(line) => line.point_to_line_coords(point),
+        }
+    }
+
+    /// Compute the length of the path between the points of a boundary
+    pub fn arc_length(&self, boundary: CurveBoundary<Point<1>>) -> Scalar {
+        let [a, b] = boundary.inner;
+        (b.t - a.t).abs() * self.length_per_path_unit()
+    }
+
+    /// Find the point at the given distance along the path
+    ///
+    /// Starts at the first point of the boundary and walks towards the second
+    /// one. Returns the point, in path coordinates. The distance is not
+    /// limited to the boundary, so the returned point might be outside of it.
+    pub fn point_at_arc_length(
+        &self,
+        boundary: CurveBoundary<Point<1>>,
+        arc_length: impl Into<Scalar>,
+    ) -> Point<1> {
+        let [a, b] = boundary.inner;
+        let direction = (b.t - a.t).sign().to_scalar();
+
+        a + Vector::from([
+            direction * arc_length.into() / self.length_per_path_unit()
+        ])
+    }
+
+    /// Compute the unit tangent vector at the given point on the path
+    ///
+    /// The tangent points in the direction of increasing path coordinates.
+    pub fn tangent_at(&self, point: impl Into<Point<1>>) -> Vector<3> {
+        let point = point.into();
+
+        let derivative = match self {
+            Self::Circle(circle) => {
+                let (sin, cos) = point.t.sin_cos();
+                circle.b() * cos - circle.a() * sin
+            }
+            Self::Line(line) => line.direction(),
+        };
+
+        derivative.normalize()
+    }
+
+    /// Compute the curvature of the path at the given point
+    ///
+    /// The curvature is the inverse of the radius of the circle that best
+    /// approximates the path at that point. It is zero for lines.
+    pub fn curvature_at(&self, _: impl Into<Point<1>>) -> Scalar {
+        match self {
+            Self::Circle(circle) => Scalar::ONE / circle.radius(),
+            Self::Line(_) => Scalar::ZERO,
+        }
+    }
+
+    /// The length of the path that one unit of path coordinates covers
+    ///
+    /// Both lines and circles are parametrized with constant speed, which
+    /// makes this independent of the position on the path.
+    fn length_per_path_unit(&self) -> Scalar {
+        match self {
+            Self::Circle(circle) => circle.radius(),
+            Self::Line(line) => line.direction().magnitude(),
+        }
+    }
+
     /// Transform the path
     #[must_use]
     pub fn transform(self, transform: &Transform) -> Self {
@@ -172,3 +385,81 @@ impl GlobalPath {
         }
     }
 }
+
+/// Compute the circle coordinate of a point, given in the circle's plane
+///
+/// Expects the coordinates to be scaled by the circle's radius, which doesn't
+/// affect the result. Returns a coordinate between `0` (inclusive) and `2 * PI`
+/// (exclusive).
+fn project_onto_circle(u: Scalar, v: Scalar) -> Point<1> {
+    let angle = v.atan2(u);
+    let angle = if angle < Scalar::ZERO {
+        angle + Scalar::TAU
+    } else {
+        angle
+    };
+
+    Point::from([angle])
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+    use pretty_assertions::assert_eq;
+
+    use crate::geometry::CurveBoundary;
+
+    use super::{GlobalPath, SurfacePath};
+
+    #[test]
+    fn reverse_path_coords() {
+        let (line, _) = SurfacePath::line_from_points([[1., 2.], [3., 2.]]);
+        let circle = SurfacePath::circle_from_center_and_radius([1., 1.], 2.);
+
+        for (path, t) in [(line, -0.5), (line, 2.), (circle, 1.), (circle, 5.)]
+        {
+            let reversed = path.reverse();
+            let reversed_t = path.reverse_path_coords([t]);
+
+            assert_eq!(
+                reversed.point_from_path_coords(reversed_t),
+                path.point_from_path_coords([t]),
+            );
+            assert_eq!(
+                reversed.reverse_path_coords(reversed_t),
+                Point::from([t])
+            );
+        }
+    }
+
+    #[test]
+    fn arc_length_of_line() {
+        let (path, _) = SurfacePath::line_from_points([[0., 0.], [3., 4.]]);
+        let boundary = CurveBoundary {
+            inner: [[2.], [0.]].map(Point::from),
+        };
+
+        assert_eq!(path.arc_length(boundary), Scalar::from(10.));
+        assert_eq!(path.point_at_arc_length(boundary, 5.), Point::from([1.]));
+        assert_eq!(path.tangent_at([0.]), Vector::from([0.6, 0.8]));
+        assert_eq!(path.curvature_at([0.]), Scalar::ZERO);
+    }
+
+    #[test]
+    fn arc_length_of_circle() {
+        let path = GlobalPath::circle_from_radius(2.);
+        let boundary = CurveBoundary {
+            inner: [[Scalar::ZERO], [Scalar::PI]].map(Point::from),
+        };
+
+        assert_eq!(path.arc_length(boundary), Scalar::TAU);
+        assert_eq!(
+            path.point_at_arc_length(boundary, Scalar::PI),
+            Point::from([Scalar::PI / 2.])
+        );
+        assert_eq!(path.curvature_at([0.]), Scalar::from(0.5));
+
+        let tangent = path.tangent_at([0.]);
+        assert!((tangent - Vector::unit_y()).magnitude() < Scalar::from(1e-12));
+    }
+}
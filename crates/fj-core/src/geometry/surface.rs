@@ -1,10 +1,17 @@
 //! The geometry that defines a surface
 
-use fj_math::{Line, Plane, Point, Transform, Vector};
+use std::f64::consts::TAU;
 
-use super::GlobalPath;
+use fj_math::{Line, Plane, Point, Scalar, Transform, Vector};
+
+use super::{GlobalPath, SurfacePath};
 
 /// The geometry that defines a surface
+///
+/// The surface is swept out by moving its u-axis along its v-axis. If the
+/// u-axis is a line, the surface is a plane. If it is a circle, the surface is
+/// a cylinder, a cone, a sphere, or a torus, depending on how the circle
+/// changes along the way (see [`SurfaceProfile`]).
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct SurfaceGeom {
     /// The u-axis of the surface
@@ -12,6 +19,9 @@ pub struct SurfaceGeom {
 
     /// The v-axis of the surface
     pub v: Vector<3>,
+
+    /// How the u-axis changes, as it moves along the v-axis
+    pub profile: SurfaceProfile,
 }
 
 impl SurfaceGeom {
@@ -21,33 +31,349 @@ impl SurfaceGeom {
         point: impl Into<Point<2>>,
     ) -> Point<3> {
         let point = point.into();
+
+        if let (GlobalPath::Circle(circle), SurfaceProfile::Spherical) =
+            (self.u, self.profile)
+        {
+            // Reducing the longitude makes sure that points on the seam of the
+            // sphere are the same, regardless of which side they come from.
+            let u = Scalar::from_f64(point.u.into_f64().rem_euclid(TAU));
+            let (sin, cos) = latitude_sin_cos(point.v);
+
+            return circle.center()
+                + circle.vector_from_circle_coords([u]) * cos
+                + self.v * sin;
+        }
+        if let (GlobalPath::Circle(circle), SurfaceProfile::Toroidal) =
+            (self.u, self.profile)
+        {
+            // Both coordinates are reduced, as the torus has a seam along
+            // either axis.
+            let [u, v] = [point.u, point.v]
+                .map(|t| Scalar::from_f64(t.into_f64().rem_euclid(TAU)));
+            let (sin, _) = v.sin_cos();
+
+            return circle.center()
+                + circle.vector_from_circle_coords([u]) * self.scale_at(v)
+                + self.v * sin;
+        }
+
         self.u.point_from_path_coords([point.u])
             + self.path_to_line().vector_from_line_coords([point.v])
+            + self.widening(point.u) * point.v
     }
 
     /// Convert a vector in surface coordinates to model coordinates
+    ///
+    /// The vector is treated as being attached to `point`, and is converted
+    /// using the derivatives of the surface there. On a plane, the result does
+    /// not depend on `point`. On curved surfaces, including cones, spheres,
+    /// and tori, the result is tangent to the surface at `point`.
     pub fn vector_from_surface_coords(
         &self,
+        point: impl Into<Point<2>>,
         vector: impl Into<Vector<2>>,
     ) -> Vector<3> {
+        let point = point.into();
         let vector = vector.into();
-        self.u.vector_from_path_coords([vector.u])
-            + self.path_to_line().vector_from_line_coords([vector.v])
+
+        let [du, dv] = match (self.u, self.profile) {
+            (GlobalPath::Line(line), _) => [line.direction(), self.v],
+            (GlobalPath::Circle(circle), SurfaceProfile::Linear { .. }) => {
+                let (sin, cos) = point.u.sin_cos();
+                let tangent = circle.b() * cos - circle.a() * sin;
+
+                [
+                    tangent * self.scale_at(point.v),
+                    self.v + self.widening(point.u),
+                ]
+            }
+            (GlobalPath::Circle(circle), SurfaceProfile::Spherical) => {
+                let (sin, cos) = point.u.sin_cos();
+                let tangent = circle.b() * cos - circle.a() * sin;
+                let radial = circle.a() * cos + circle.b() * sin;
+                let (sin, cos) = latitude_sin_cos(point.v);
+
+                [tangent * cos, self.v * cos - radial * sin]
+            }
+            (GlobalPath::Circle(circle), SurfaceProfile::Toroidal) => {
+                let (sin, cos) = point.u.sin_cos();
+                let tangent = circle.b() * cos - circle.a() * sin;
+                let radial = circle.a() * cos + circle.b() * sin;
+                let (sin, cos) = point.v.sin_cos();
+                let ratio = self.v.magnitude() / circle.radius();
+
+                [
+                    tangent * self.scale_at(point.v),
+                    self.v * cos - radial * ratio * sin,
+                ]
+            }
+        };
+
+        du * vector.u + dv * vector.v
     }
 
     fn path_to_line(&self) -> Line<3> {
         Line::from_origin_and_direction(self.u.origin(), self.v)
     }
 
+    /// The offset, per unit along the v-axis, that makes the surface a cone
+    fn widening(&self, u: Scalar) -> Vector<3> {
+        match (self.u, self.profile) {
+            (GlobalPath::Circle(circle), SurfaceProfile::Linear { taper }) => {
+                circle.vector_from_circle_coords([u]) * taper
+            }
+            _ => Vector::default(),
+        }
+    }
+
+    /// Compute the factor by which the u-axis is scaled at `v`
+    ///
+    /// This is always one, unless the surface is a cone, a sphere, or a torus.
+    pub fn scale_at(&self, v: Scalar) -> Scalar {
+        match (self.u, self.profile) {
+            (GlobalPath::Circle(_), SurfaceProfile::Linear { taper }) => {
+                Scalar::ONE + taper * v
+            }
+            (GlobalPath::Circle(_), SurfaceProfile::Spherical) => {
+                let (_, cos) = latitude_sin_cos(v);
+                cos
+            }
+            (GlobalPath::Circle(circle), SurfaceProfile::Toroidal) => {
+                let (_, cos) = v.sin_cos();
+                Scalar::ONE + self.v.magnitude() / circle.radius() * cos
+            }
+            (GlobalPath::Line(_), _) => Scalar::ONE,
+        }
+    }
+
+    /// Access the period of the surface's u-coordinate, if it is periodic
+    ///
+    /// This is the case, if the u-axis is a circle. Points whose u-coordinates
+    /// are a whole number of periods apart are the same, which means the
+    /// surface has a seam.
+    pub fn u_period(&self) -> Option<Scalar> {
+        self.u.period()
+    }
+
+    /// Access the period of the surface's v-coordinate, if it is periodic
+    ///
+    /// This is only the case for tori, which have a second seam along their
+    /// u-axis. See [`SurfaceGeom::u_period`].
+    pub fn v_period(&self) -> Option<Scalar> {
+        self.is_torus().then_some(Scalar::TAU)
+    }
+
+    /// Access the period of a path on this surface, if it is periodic
+    ///
+    /// Circles are always periodic. Lines are, if they run along the u-axis of
+    /// a surface that has a u-period, like the circles that bound a cylinder,
+    /// or along the v-axis of a surface that has a v-period.
+    pub fn period_of_path(&self, path: &SurfacePath) -> Option<Scalar> {
+        match path {
+            SurfacePath::Circle(_) => path.period(),
+            SurfacePath::Line(line) => {
+                let direction = line.direction();
+                match (direction.u == Scalar::ZERO, direction.v == Scalar::ZERO)
+                {
+                    (false, true) => Some(self.u_period()? / direction.u.abs()),
+                    (true, false) => Some(self.v_period()? / direction.v.abs()),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Compute the unit normal of the surface at the provided point
+    ///
+    /// The normal is the cross product of the directions along the u- and the
+    /// v-axis. Which side of the surface it points to depends on the
+    /// orientation of the surface's coordinate system.
+    pub fn normal_at(&self, point: impl Into<Point<2>>) -> Vector<3> {
+        let point = point.into();
+
+        match (self.u, self.profile) {
+            (GlobalPath::Circle(circle), SurfaceProfile::Spherical) => {
+                // The normal is radial, which also covers the poles, where the
+                // direction along the u-axis is zero. It points outwards, if
+                // the v-axis points the same way as the normal of the equator.
+                let radial =
+                    self.point_from_surface_coords(point) - circle.center();
+                let orientation =
+                    self.v.dot(&circle.a().cross(&circle.b())).sign();
+
+                radial.normalize() * orientation.to_scalar()
+            }
+            (GlobalPath::Circle(circle), SurfaceProfile::Toroidal) => {
+                // Like on a sphere, the normal points away from the center of
+                // the circle that the v-axis moves along.
+                let center = circle.center()
+                    + circle.vector_from_circle_coords([point.u]);
+                let radial = self.point_from_surface_coords(point) - center;
+                let orientation =
+                    self.v.dot(&circle.a().cross(&circle.b())).sign();
+
+                radial.normalize() * orientation.to_scalar()
+            }
+            (u, _) => {
+                // On a cone, the direction along the v-axis tilts towards or
+                // away from the axis. Beyond the apex, the u-axis is mirrored,
+                // which flips the normal.
+                let du = u.tangent_at([point.u]);
+                let dv = self.v + self.widening(point.u);
+                let normal = du.cross(&dv).normalize();
+
+                if self.scale_at(point.v) < Scalar::ZERO {
+                    -normal
+                } else {
+                    normal
+                }
+            }
+        }
+    }
+
+    /// Indicate whether this is a sphere
+    pub fn is_sphere(&self) -> bool {
+        matches!(
+            (self.u, self.profile),
+            (GlobalPath::Circle(_), SurfaceProfile::Spherical)
+        )
+    }
+
+    /// Indicate whether this is a torus
+    pub fn is_torus(&self) -> bool {
+        matches!(
+            (self.u, self.profile),
+            (GlobalPath::Circle(_), SurfaceProfile::Toroidal)
+        )
+    }
+
+    /// Indicate whether lines along the v-axis are curved
+    ///
+    /// This is the case for spheres and tori, whose u-axis moves along a
+    /// circle. The edges of faces on those surfaces don't define their
+    /// curvature, unlike on cylinders and cones.
+    pub fn is_curved_along_v(&self) -> bool {
+        self.is_sphere() || self.is_torus()
+    }
+
+    /// Indicate whether `v` is the coordinate of a pole
+    ///
+    /// At a pole, the u-axis collapses into a single point. This is the case at
+    /// the poles of a sphere, and at the apex of a cone. Always returns `false`
+    /// for other surfaces.
+    pub fn is_pole(&self, v: Scalar) -> bool {
+        match (self.u, self.profile) {
+            (GlobalPath::Circle(_), SurfaceProfile::Spherical) => {
+                v.abs() == Scalar::PI / 2.
+            }
+            (GlobalPath::Circle(_), SurfaceProfile::Toroidal) => false,
+            (GlobalPath::Circle(_), SurfaceProfile::Linear { .. }) => {
+                self.scale_at(v) == Scalar::ZERO
+            }
+            (GlobalPath::Line(_), _) => false,
+        }
+    }
+
+    /// Indicate whether the path runs along a pole
+    ///
+    /// See [`SurfaceGeom::is_pole`]. All points of such a path are the same,
+    /// so it has zero length.
+    pub fn is_path_along_pole(&self, path: &SurfacePath) -> bool {
+        match path {
+            SurfacePath::Line(line) => {
+                line.direction().v == Scalar::ZERO
+                    && self.is_pole(line.origin().v)
+            }
+            SurfacePath::Circle(_) => false,
+        }
+    }
+
     /// Project the global point into the surface
+    ///
+    /// Returns the surface coordinates of the point on the surface that is
+    /// closest to the provided point.
+    ///
+    /// For cylinders and cones whose axis (defined by `v`) is not
+    /// perpendicular to the plane of the circle (defined by `u`), the point is
+    /// projected along the axis instead, which is not necessarily the closest
+    /// point. The same goes for cones in general, where the point is projected
+    /// onto the cone's axis, not onto its surface. Points are projected onto
+    /// spheres along the line through their center. The center itself, as well
+    /// as the poles, project to `u = 0`. Points are projected onto tori along
+    /// the line through the center of the tube.
     pub fn project_global_point(&self, point: impl Into<Point<3>>) -> Point<2> {
-        let GlobalPath::Line(line) = self.u else {
-            todo!("Projecting point into non-plane surface is not supported")
-        };
+        let point = point.into();
+
+        match self.u {
+            GlobalPath::Circle(circle) if self.is_sphere() => {
+                let offset = point - circle.center();
+                let axis = self.v.normalize();
+
+                let height = offset.dot(&axis);
+                let point_in_circle_plane = point - axis * height;
+                let distance_from_axis =
+                    (point_in_circle_plane - circle.center()).magnitude();
+
+                let v = Scalar::from_f64(
+                    height.into_f64().atan2(distance_from_axis.into_f64()),
+                );
+                let u = if distance_from_axis == Scalar::ZERO {
+                    Scalar::ZERO
+                } else {
+                    let [u] = self
+                        .u
+                        .project_point(point_in_circle_plane)
+                        .coords
+                        .components;
+                    u
+                };
+
+                Point::from([u, v])
+            }
+            GlobalPath::Circle(circle) if self.is_torus() => {
+                let axis = self.v.normalize();
+                let height = (point - circle.center()).dot(&axis);
+                let point_in_circle_plane = point - axis * height;
+
+                let [u] = self
+                    .u
+                    .project_point(point_in_circle_plane)
+                    .coords
+                    .components;
+
+                let radial = circle.vector_from_circle_coords([u]).normalize();
+                let offset = point_in_circle_plane
+                    - (circle.center() + circle.vector_from_circle_coords([u]));
+                let v = Scalar::from_f64(
+                    height.into_f64().atan2(offset.dot(&radial).into_f64()),
+                );
+
+                Point::from([u, v])
+            }
+            GlobalPath::Circle(circle) => {
+                let normal = circle.a().cross(&circle.b());
+                let v = (point - circle.center()).dot(&normal)
+                    / self.v.dot(&normal);
 
-        let plane =
-            Plane::from_parametric(line.origin(), line.direction(), self.v);
-        plane.project_point(point)
+                let point_in_circle_plane = point - self.v * v;
+                let [u] = self
+                    .u
+                    .project_point(point_in_circle_plane)
+                    .coords
+                    .components;
+
+                Point::from([u, v])
+            }
+            GlobalPath::Line(line) => {
+                let plane = Plane::from_parametric(
+                    line.origin(),
+                    line.direction(),
+                    self.v,
+                );
+                plane.project_point(point)
+            }
+        }
     }
 
     /// Transform the surface geometry
@@ -55,16 +381,243 @@ impl SurfaceGeom {
     pub fn transform(self, transform: &Transform) -> Self {
         let u = self.u.transform(transform);
         let v = transform.transform_vector(&self.v);
-        Self { u, v }
+
+        // The profile is relative to the radius of the u-axis, so it doesn't
+        // change.
+        Self {
+            u,
+            v,
+            profile: self.profile,
+        }
+    }
+}
+
+/// Compute sine and cosine of a latitude on a sphere
+///
+/// `cos(π/2)` is not exactly zero in floating point. Here it is, so all points
+/// at a pole end up being the same, regardless of their u-coordinate.
+fn latitude_sin_cos(v: Scalar) -> (Scalar, Scalar) {
+    if v.abs() == Scalar::PI / 2. {
+        return (v.sign().to_scalar(), Scalar::ZERO);
+    }
+
+    v.sin_cos()
+}
+
+/// How the u-axis of a [`SurfaceGeom`] changes, as it moves along the v-axis
+///
+/// This only affects surfaces whose u-axis is a circle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum SurfaceProfile {
+    /// The u-axis moves along the v-axis in a straight line
+    Linear {
+        /// How much the u-axis widens, per unit along the v-axis
+        ///
+        /// At `v`, the radius of the circle is scaled by `1 + taper * v`. Zero
+        /// makes the surface a cylinder, anything else a cone, whose apex is
+        /// at `v = -1 / taper`.
+        taper: Scalar,
+    },
+
+    /// The u-axis moves along a half circle, which makes the surface a sphere
+    ///
+    /// The u-axis is the equator of the sphere, and the v-axis points from its
+    /// center to the north pole. It must be perpendicular to the plane of the
+    /// equator, and as long as its radius. The v-coordinate is the latitude,
+    /// in radians, so the poles are at `v = ±π/2`. At `v`, the equator is
+    /// scaled by `cos(v)`, and moved by `sin(v)` along the v-axis.
+    Spherical,
+
+    /// The u-axis moves around a circle, which makes the surface a torus
+    ///
+    /// The u-axis is the circle through the center of the torus' tube, and the
+    /// v-axis is perpendicular to its plane. The length of the v-axis is the
+    /// radius of the tube, which must be smaller than the radius of the u-axis.
+    /// The v-coordinate is the angle around the tube, in radians, starting at
+    /// its outside. At `v`, the u-axis is widened by `cos(v)` times the radius
+    /// of the tube, and moved by `sin(v)` along the v-axis.
+    Toroidal,
+}
+
+impl SurfaceProfile {
+    /// The profile of planes and cylinders
+    pub const STRAIGHT: Self = Self::Linear {
+        taper: Scalar::ZERO,
+    };
+}
+
+impl Default for SurfaceProfile {
+    fn default() -> Self {
+        Self::STRAIGHT
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use fj_math::{Line, Point, Vector};
+    use fj_math::{Line, Point, Scalar, Vector};
     use pretty_assertions::assert_eq;
 
-    use crate::geometry::{GlobalPath, SurfaceGeom};
+    use crate::geometry::{GlobalPath, SurfaceGeom, SurfaceProfile};
+
+    #[test]
+    fn point_from_surface_coords_on_cone() {
+        // This cone has its apex at `v = 2`, where the radius is zero.
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(2.),
+            v: Vector::from([0., 0., 1.]),
+            profile: SurfaceProfile::Linear {
+                taper: Scalar::from(-0.5),
+            },
+        };
+
+        assert_eq!(
+            surface.point_from_surface_coords([0., 1.]),
+            Point::from([1., 0., 1.]),
+        );
+        assert_eq!(
+            surface.point_from_surface_coords([Scalar::PI, Scalar::from(2.)]),
+            Point::from([0., 0., 2.]),
+        );
+    }
+
+    #[test]
+    fn project_global_point_onto_cone() {
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::from([0., 0., 1.]),
+            profile: SurfaceProfile::Linear { taper: Scalar::ONE },
+        };
+
+        assert_eq!(
+            surface.project_global_point([0., 3., 2.]),
+            Point::from([Scalar::PI / 2., Scalar::from(2.)]),
+        );
+    }
+
+    #[test]
+    fn point_from_surface_coords_on_sphere() {
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(2.),
+            v: Vector::from([0., 0., 2.]),
+            profile: SurfaceProfile::Spherical,
+        };
+
+        let equator =
+            surface.point_from_surface_coords([Scalar::PI / 2., Scalar::ZERO]);
+        assert!(equator.distance_to(&Point::from([0., 2., 0.])) < 1e-12.into());
+
+        let pole =
+            surface.point_from_surface_coords([Scalar::ONE, Scalar::PI / 2.]);
+        assert!(pole.distance_to(&Point::from([0., 0., 2.])) < 1e-12.into());
+    }
+
+    #[test]
+    fn project_global_point_onto_sphere() {
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::from([0., 0., 1.]),
+            profile: SurfaceProfile::Spherical,
+        };
+
+        // Points are projected along the line through the center, so their
+        // distance from it doesn't matter.
+        assert_eq!(
+            surface.project_global_point([0., 3., 3.]),
+            Point::from([Scalar::PI / 2., Scalar::PI / 4.]),
+        );
+        assert_eq!(
+            surface.project_global_point([0., 0., -2.]),
+            Point::from([Scalar::ZERO, -Scalar::PI / 2.]),
+        );
+    }
+
+    #[test]
+    fn point_from_surface_coords_on_torus() {
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(2.),
+            v: Vector::from([0., 0., 0.5]),
+            profile: SurfaceProfile::Toroidal,
+        };
+
+        let outside =
+            surface.point_from_surface_coords([Scalar::PI / 2., Scalar::ZERO]);
+        assert!(
+            outside.distance_to(&Point::from([0., 2.5, 0.])) < 1e-12.into()
+        );
+
+        let top =
+            surface.point_from_surface_coords([Scalar::ZERO, Scalar::PI / 2.]);
+        assert!(top.distance_to(&Point::from([2., 0., 0.5])) < 1e-12.into());
+
+        let inside =
+            surface.point_from_surface_coords([Scalar::ZERO, Scalar::PI]);
+        assert!(inside.distance_to(&Point::from([1.5, 0., 0.])) < 1e-12.into());
+    }
+
+    #[test]
+    fn project_global_point_onto_torus() {
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(2.),
+            v: Vector::from([0., 0., 0.5]),
+            profile: SurfaceProfile::Toroidal,
+        };
+
+        for point in [[0.3, 1.2], [2., -2.5], [5., 3.]] {
+            let point = Point::from(point);
+            let projected = surface
+                .project_global_point(surface.point_from_surface_coords(point));
+            assert!(projected.distance_to(&point) < 1e-12.into());
+        }
+
+        // Points are projected along the line through the center of the tube,
+        // so their distance from it doesn't matter.
+        let projected = surface.project_global_point([0., 3., 1.]);
+        assert!(
+            projected
+                .distance_to(&Point::from([Scalar::PI / 2., Scalar::PI / 4.]))
+                < 1e-12.into()
+        );
+    }
+
+    #[test]
+    fn normal_of_cone() {
+        // This cone has its apex at `v = 1`, and its side rises at 45 degrees.
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::from([0., 0., 1.]),
+            profile: SurfaceProfile::Linear {
+                taper: -Scalar::ONE,
+            },
+        };
+
+        let expected = Vector::from([1., 0., 1.]).normalize();
+        for v in [0., 0.5, 1.] {
+            let normal = surface.normal_at([Scalar::ZERO, Scalar::from(v)]);
+            assert!((normal - expected).magnitude() < 1e-12.into());
+        }
+    }
+
+    #[test]
+    fn normal_of_sphere() {
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(2.),
+            v: Vector::from([0., 0., 2.]),
+            profile: SurfaceProfile::Spherical,
+        };
+
+        let at_equator = surface.normal_at([Scalar::PI / 2., Scalar::ZERO]);
+        assert!(
+            (at_equator - Vector::from([0., 1., 0.])).magnitude()
+                < 1e-12.into()
+        );
+
+        // At the pole, the normal doesn't depend on the u-coordinate.
+        for u in [0., 1., 2.] {
+            let at_pole =
+                surface.normal_at([Scalar::from(u), -Scalar::PI / 2.]);
+            assert_eq!(at_pole, Vector::from([0., 0., -1.]));
+        }
+    }
 
     #[test]
     fn point_from_surface_coords() {
@@ -74,6 +627,7 @@ mod tests {
                 Vector::from([0., 2., 0.]),
             )),
             v: Vector::from([0., 0., 2.]),
+            profile: SurfaceProfile::STRAIGHT,
         };
 
         assert_eq!(
@@ -82,6 +636,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn project_global_point_onto_cylinder() {
+        let surface = SurfaceGeom {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::from([0., 0., 2.]),
+            profile: SurfaceProfile::STRAIGHT,
+        };
+
+        assert_eq!(
+            surface.project_global_point([2., 0., 3.]),
+            Point::from([0., 1.5]),
+        );
+
+        let point = surface.project_global_point([0., -2., 1.]);
+        assert_eq!(point, Point::from([Scalar::PI * 1.5, Scalar::from(0.5)]));
+    }
+
     #[test]
     fn vector_from_surface_coords() {
         let surface = SurfaceGeom {
@@ -90,11 +661,44 @@ mod tests {
                 Vector::from([0., 2., 0.]),
             )),
             v: Vector::from([0., 0., 2.]),
+            profile: SurfaceProfile::STRAIGHT,
         };
 
         assert_eq!(
-            surface.vector_from_surface_coords([2., 4.]),
+            surface.vector_from_surface_coords([3., 5.], [2., 4.]),
             Vector::from([0., 4., 8.]),
         );
     }
+
+    #[test]
+    fn vector_from_surface_coords_on_curved_surfaces() {
+        let u = GlobalPath::circle_from_radius(1.);
+        let v = Vector::from([0., 0., 1.]);
+
+        let cone = SurfaceGeom {
+            u,
+            v,
+            profile: SurfaceProfile::Linear {
+                taper: Scalar::from(-0.5),
+            },
+        };
+        assert_eq!(
+            cone.vector_from_surface_coords([0., 1.], [1., 0.]),
+            Vector::from([0., 0.5, 0.]),
+        );
+        assert_eq!(
+            cone.vector_from_surface_coords([0., 1.], [0., 1.]),
+            Vector::from([-0.5, 0., 1.]),
+        );
+
+        let sphere = SurfaceGeom {
+            u,
+            v,
+            profile: SurfaceProfile::Spherical,
+        };
+        assert_eq!(
+            sphere.vector_from_surface_coords([0., 0.], [2., 3.]),
+            Vector::from([0., 2., 3.]),
+        );
+    }
 }
@@ -0,0 +1,121 @@
+//! Layer infrastructure for [`Annotations`]
+
+use crate::{
+    annotations::{Annotation, AnnotationError, Annotations, NamedGeometry},
+    geometry::Geometry,
+    storage::Handle,
+    topology::{HalfEdge, Surface},
+};
+
+use super::{Command, Event, Layer};
+
+impl Layer<Annotations> {
+    /// Give a name to the start vertex of a half-edge
+    pub fn name_vertex(
+        &mut self,
+        name: impl Into<String>,
+        half_edge: Handle<HalfEdge>,
+        surface: Handle<Surface>,
+    ) -> Result<(), AnnotationError> {
+        let mut events = Vec::new();
+        self.process(
+            Name {
+                name: name.into(),
+                geometry: NamedGeometry::Vertex { half_edge, surface },
+            },
+            &mut events,
+        )
+    }
+
+    /// Give a name to the edge that a half-edge belongs to
+    pub fn name_edge(
+        &mut self,
+        name: impl Into<String>,
+        half_edge: Handle<HalfEdge>,
+        surface: Handle<Surface>,
+    ) -> Result<(), AnnotationError> {
+        let mut events = Vec::new();
+        self.process(
+            Name {
+                name: name.into(),
+                geometry: NamedGeometry::Edge { half_edge, surface },
+            },
+            &mut events,
+        )
+    }
+
+    /// Add an annotation
+    ///
+    /// Fails, if the annotation doesn't refer to named geometry that it can
+    /// annotate.
+    pub fn annotate(
+        &mut self,
+        annotation: Annotation,
+        geometry: &Geometry,
+    ) -> Result<(), AnnotationError> {
+        self.check(&annotation, geometry)?;
+
+        let mut events = Vec::new();
+        self.process(Annotate { annotation }, &mut events);
+
+        Ok(())
+    }
+}
+
+/// Give a name to geometry
+pub struct Name {
+    /// The name
+    name: String,
+
+    /// The geometry to name
+    geometry: NamedGeometry,
+}
+
+impl Command<Annotations> for Name {
+    type Result = Result<(), AnnotationError>;
+    type Event = Self;
+
+    fn decide(
+        self,
+        state: &Annotations,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        if state.names.contains_key(&self.name) {
+            return Err(AnnotationError::DuplicateName(self.name));
+        }
+
+        events.push(self);
+        Ok(())
+    }
+}
+
+impl Event<Annotations> for Name {
+    fn evolve(&self, state: &mut Annotations) {
+        state.names.insert(self.name.clone(), self.geometry.clone());
+    }
+}
+
+/// Add an annotation
+pub struct Annotate {
+    /// The annotation to add
+    annotation: Annotation,
+}
+
+impl Command<Annotations> for Annotate {
+    type Result = ();
+    type Event = Self;
+
+    fn decide(
+        self,
+        _: &Annotations,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(self);
+    }
+}
+
+impl Event<Annotations> for Annotate {
+    fn evolve(&self, state: &mut Annotations) {
+        state.annotations.push(self.annotation.clone());
+    }
+}
@@ -0,0 +1,225 @@
+//! Layer infrastructure for [`DebugGeometry`]
+
+use fj_interop::{DebugGeometry, DebugPrimitive};
+use fj_math::Point;
+
+use super::{Command, Event, Layer};
+
+impl Layer<DebugGeometry> {
+    /// Emit a point into a debug channel
+    pub fn point(
+        &mut self,
+        channel: impl Into<String>,
+        point: impl Into<Point<3>>,
+    ) {
+        self.emit(channel, DebugPrimitive::Point(point.into()));
+    }
+
+    /// Emit a line segment into a debug channel
+    pub fn segment(
+        &mut self,
+        channel: impl Into<String>,
+        points: [impl Into<Point<3>>; 2],
+    ) {
+        self.emit(channel, DebugPrimitive::Segment(points.map(Into::into)));
+    }
+
+    /// Emit an arrow into a debug channel
+    pub fn arrow(
+        &mut self,
+        channel: impl Into<String>,
+        from: impl Into<Point<3>>,
+        to: impl Into<Point<3>>,
+    ) {
+        self.emit(
+            channel,
+            DebugPrimitive::Arrow {
+                from: from.into(),
+                to: to.into(),
+            },
+        );
+    }
+
+    /// Emit text into a debug channel, to be displayed next to a point
+    pub fn text(
+        &mut self,
+        channel: impl Into<String>,
+        at: impl Into<Point<3>>,
+        text: impl Into<String>,
+    ) {
+        self.emit(
+            channel,
+            DebugPrimitive::Text {
+                at: at.into(),
+                text: text.into(),
+            },
+        );
+    }
+
+    /// Emit a primitive into a debug channel
+    pub fn emit(
+        &mut self,
+        channel: impl Into<String>,
+        primitive: DebugPrimitive,
+    ) {
+        let mut events = Vec::new();
+        self.process(
+            Emit {
+                channel: channel.into(),
+                primitive,
+            },
+            &mut events,
+        );
+    }
+
+    /// Take all debug geometry that has been emitted so far
+    ///
+    /// This leaves the layer empty, so a host application that rebuilds a
+    /// model can call this after each rebuild, to get the debug geometry of
+    /// that rebuild only.
+    pub fn take(&mut self) -> DebugGeometry {
+        let mut events = Vec::new();
+        self.process(Take, &mut events)
+    }
+}
+
+/// Emit a primitive into a debug channel
+pub struct Emit {
+    /// The channel to emit the primitive into
+    channel: String,
+
+    /// The primitive to emit
+    primitive: DebugPrimitive,
+}
+
+impl Command<DebugGeometry> for Emit {
+    type Result = ();
+    type Event = Self;
+
+    fn decide(
+        self,
+        _: &DebugGeometry,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(self);
+    }
+}
+
+impl Event<DebugGeometry> for Emit {
+    fn evolve(&self, state: &mut DebugGeometry) {
+        state.push(self.channel.clone(), self.primitive.clone());
+    }
+}
+
+/// Take all debug geometry
+///
+/// Serves both as a command for and event produced by `Layer<DebugGeometry>`.
+pub struct Take;
+
+impl Command<DebugGeometry> for Take {
+    type Result = DebugGeometry;
+    type Event = Self;
+
+    fn decide(
+        self,
+        state: &DebugGeometry,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(self);
+        state.clone()
+    }
+}
+
+impl Event<DebugGeometry> for Take {
+    fn evolve(&self, state: &mut DebugGeometry) {
+        *state = DebugGeometry::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{Color, DebugGeometry, DebugPrimitive};
+    use fj_math::Point;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        operations::{
+            build::BuildSolid,
+            presentation::{GetColor, SetColor},
+        },
+        storage::Handle,
+        topology::Solid,
+        Core,
+    };
+
+    #[test]
+    fn record_and_take() {
+        let mut core = Core::new();
+
+        let debug = &mut core.layers.debug;
+        debug.point("points", [0., 0., 0.]);
+        debug.segment("lines", [[0., 0., 0.], [1., 0., 0.]]);
+        debug.arrow("lines", [0., 0., 0.], [0., 1., 0.]);
+        debug.text("points", [0., 0., 0.], "origin");
+
+        let origin = Point::from([0., 0., 0.]);
+        let mut expected = DebugGeometry::new();
+        expected.push("points", DebugPrimitive::Point(origin));
+        expected.push(
+            "lines",
+            DebugPrimitive::Segment([origin, Point::from([1., 0., 0.])]),
+        );
+        expected.push(
+            "lines",
+            DebugPrimitive::Arrow {
+                from: origin,
+                to: Point::from([0., 1., 0.]),
+            },
+        );
+        expected.push(
+            "points",
+            DebugPrimitive::Text {
+                at: origin,
+                text: "origin".into(),
+            },
+        );
+        assert_eq!(*core.layers.debug, expected);
+
+        assert_eq!(core.layers.debug.take(), expected);
+        assert!(core.layers.debug.is_empty());
+        assert!(core.layers.debug.take().is_empty());
+    }
+
+    #[test]
+    fn keep_alongside_other_layers() {
+        let mut core = Core::new();
+
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let face = tetrahedron.shell.shell.faces().first().clone();
+        face.region().set_color([255, 0, 0, 255], &mut core);
+        core.layers.debug.point("points", [0., 0., 0.]);
+
+        // Debug geometry doesn't refer to any objects, so collecting garbage
+        // doesn't remove any of it.
+        let collected = core.collect_garbage(Vec::<Handle<Solid>>::new());
+        assert!(collected.objects.total() > 0);
+        assert_eq!(core.layers.debug.channel_names().count(), 1);
+
+        // A fork has its own copy.
+        let mut fork = core.fork();
+        assert_eq!(fork.layers.debug.take(), *core.layers.debug);
+        assert!(fork.layers.debug.is_empty());
+        assert!(!core.layers.debug.is_empty());
+
+        // Taking debug geometry leaves the other layers alone.
+        let _ = core.layers.debug.take();
+        assert!(core.layers.debug.is_empty());
+        assert_eq!(
+            face.region().get_color(&core),
+            Some(Color([255, 0, 0, 255]))
+        );
+    }
+}
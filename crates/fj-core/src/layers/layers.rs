@@ -1,6 +1,11 @@
+use fj_interop::DebugGeometry;
+
 use crate::{
+    annotations::Annotations,
     geometry::Geometry,
+    materials::Materials,
     presentation::Presentation,
+    provenance::Provenance,
     topology::Topology,
     validation::{Validation, ValidationConfig},
 };
@@ -41,6 +46,26 @@ pub struct Layers {
     ///
     /// Stores data concerning the presentation of objects.
     pub presentation: Layer<Presentation>,
+
+    /// The materials layer
+    ///
+    /// Stores the materials that objects are made of.
+    pub materials: Layer<Materials>,
+
+    /// The provenance layer
+    ///
+    /// Records where objects came from.
+    pub provenance: Layer<Provenance>,
+
+    /// The annotations layer
+    ///
+    /// Stores names of geometry, and the annotations that refer to them.
+    pub annotations: Layer<Annotations>,
+
+    /// The debug layer
+    ///
+    /// Collects geometry that algorithms and models emit for debugging.
+    pub debug: Layer<DebugGeometry>,
 }
 
 impl Layers {
@@ -54,6 +79,10 @@ impl Layers {
             geometry: Layer::new(geometry),
             validation: Layer::default(),
             presentation: Layer::default(),
+            materials: Layer::default(),
+            provenance: Layer::default(),
+            annotations: Layer::default(),
+            debug: Layer::default(),
         }
     }
 
@@ -64,6 +93,26 @@ impl Layers {
             ..Self::new()
         }
     }
+
+    /// Create a fork of these layers
+    ///
+    /// See [`Core::fork`].
+    ///
+    /// [`Core::fork`]: crate::Core::fork
+    pub fn fork(&self) -> Self {
+        Self {
+            topology: Layer::new(self.topology.share()),
+            geometry: Layer::new((*self.geometry).clone()),
+            validation: Layer::new(Validation::with_validation_config(
+                self.validation.config,
+            )),
+            presentation: Layer::new((*self.presentation).clone()),
+            materials: Layer::new((*self.materials).clone()),
+            provenance: Layer::new((*self.provenance).clone()),
+            annotations: Layer::new((*self.annotations).clone()),
+            debug: Layer::new((*self.debug).clone()),
+        }
+    }
 }
 
 impl Default for Layers {
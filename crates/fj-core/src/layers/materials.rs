@@ -0,0 +1,90 @@
+//! Layer infrastructure for [`Materials`]
+
+use fj_interop::Material;
+
+use crate::{
+    materials::Materials,
+    storage::Handle,
+    topology::{AnyObject, Region, Stored},
+};
+
+use super::{Command, Event, Layer};
+
+impl Layer<Materials> {
+    /// Set the material of a region
+    pub fn set_material(&mut self, region: Handle<Region>, material: Material) {
+        let mut events = Vec::new();
+        self.process(SetMaterial { region, material }, &mut events);
+    }
+
+    /// Mark an object as being derived from another
+    pub fn derive_object(
+        &mut self,
+        original: AnyObject<Stored>,
+        derived: AnyObject<Stored>,
+    ) {
+        let mut events = Vec::new();
+        self.process(DeriveObject { original, derived }, &mut events);
+    }
+}
+
+/// Set the material of a region
+pub struct SetMaterial {
+    /// The region to set the material for
+    region: Handle<Region>,
+
+    /// The material to set
+    material: Material,
+}
+
+impl Command<Materials> for SetMaterial {
+    type Result = ();
+    type Event = Self;
+
+    fn decide(
+        self,
+        _: &Materials,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(self);
+    }
+}
+
+impl Event<Materials> for SetMaterial {
+    fn evolve(&self, state: &mut Materials) {
+        state
+            .material
+            .insert(self.region.clone(), self.material.clone());
+    }
+}
+
+/// Handle an object being derived from another
+pub struct DeriveObject {
+    /// The original object
+    original: AnyObject<Stored>,
+
+    /// The derived object
+    derived: AnyObject<Stored>,
+}
+
+impl Command<Materials> for DeriveObject {
+    type Result = ();
+    type Event = SetMaterial;
+
+    fn decide(
+        self,
+        state: &Materials,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        if let (AnyObject::Region(original), AnyObject::Region(derived)) =
+            (self.original, self.derived)
+        {
+            if let Some(material) = state.material.get(&original).cloned() {
+                events.push(SetMaterial {
+                    region: derived,
+                    material,
+                });
+            }
+        }
+    }
+}
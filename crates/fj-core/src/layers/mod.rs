@@ -2,8 +2,12 @@
 //!
 //! See [`Layers`].
 
+pub mod annotations;
+pub mod debug;
 pub mod geometry;
+pub mod materials;
 pub mod presentation;
+pub mod provenance;
 pub mod topology;
 pub mod validation;
 
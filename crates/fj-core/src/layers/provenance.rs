@@ -0,0 +1,182 @@
+//! Layer infrastructure for [`Provenance`]
+
+use crate::{
+    provenance::{Operation, Origin, Provenance},
+    topology::{AnyObject, Stored},
+};
+
+use super::{Command, Event, Layer};
+
+impl Layer<Provenance> {
+    /// Start an operation
+    ///
+    /// Objects that are inserted or derived until the operation ends, are
+    /// recorded as originating from it.
+    pub fn begin_operation(&mut self, operation: Operation) {
+        let mut events = Vec::new();
+        self.process(BeginOperation { operation }, &mut events);
+    }
+
+    /// End the innermost running operation
+    pub fn end_operation(&mut self) {
+        let mut events = Vec::new();
+        self.process(EndOperation, &mut events);
+    }
+
+    /// Record that an object has been inserted
+    pub fn insert_object(&mut self, object: AnyObject<Stored>) {
+        let mut events = Vec::new();
+        self.process(InsertObject { object }, &mut events);
+    }
+
+    /// Mark an object as being derived from another
+    pub fn derive_object(
+        &mut self,
+        original: AnyObject<Stored>,
+        derived: AnyObject<Stored>,
+    ) {
+        let mut events = Vec::new();
+        self.process(DeriveObject { original, derived }, &mut events);
+    }
+
+    /// Set the origin of an object directly
+    ///
+    /// This is useful for restoring provenance that has been recorded
+    /// elsewhere, for example when loading a serialized object graph.
+    pub fn set_origin(&mut self, object: AnyObject<Stored>, origin: Origin) {
+        let mut events = Vec::new();
+        self.process(SetOrigin { object, origin }, &mut events);
+    }
+}
+
+/// Start an operation
+pub struct BeginOperation {
+    operation: Operation,
+}
+
+impl Command<Provenance> for BeginOperation {
+    type Result = ();
+    type Event = Self;
+
+    fn decide(
+        self,
+        _: &Provenance,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(self);
+    }
+}
+
+impl Event<Provenance> for BeginOperation {
+    fn evolve(&self, state: &mut Provenance) {
+        state.operations.push(self.operation.clone());
+    }
+}
+
+/// End the innermost running operation
+pub struct EndOperation;
+
+impl Command<Provenance> for EndOperation {
+    type Result = ();
+    type Event = Self;
+
+    fn decide(
+        self,
+        state: &Provenance,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        if !state.operations.is_empty() {
+            events.push(self);
+        }
+    }
+}
+
+impl Event<Provenance> for EndOperation {
+    fn evolve(&self, state: &mut Provenance) {
+        state.operations.pop();
+    }
+}
+
+/// Record that an object has been inserted
+pub struct InsertObject {
+    object: AnyObject<Stored>,
+}
+
+impl Command<Provenance> for InsertObject {
+    type Result = ();
+    type Event = SetOrigin;
+
+    fn decide(
+        self,
+        state: &Provenance,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        // Objects that are inserted outside of any operation have no origin
+        // worth recording.
+        if !state.operations.is_empty() {
+            events.push(SetOrigin {
+                object: self.object,
+                origin: Origin {
+                    original: None,
+                    operations: state.operations.clone(),
+                },
+            });
+        }
+    }
+}
+
+/// Handle an object being derived from another
+pub struct DeriveObject {
+    /// The original object
+    original: AnyObject<Stored>,
+
+    /// The derived object
+    derived: AnyObject<Stored>,
+}
+
+impl Command<Provenance> for DeriveObject {
+    type Result = ();
+    type Event = SetOrigin;
+
+    fn decide(
+        self,
+        state: &Provenance,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(SetOrigin {
+            object: self.derived,
+            origin: Origin {
+                original: Some(self.original),
+                operations: state.operations.clone(),
+            },
+        });
+    }
+}
+
+/// Set the origin of an object
+pub struct SetOrigin {
+    /// The object whose origin is being set
+    object: AnyObject<Stored>,
+
+    /// The origin of the object
+    origin: Origin,
+}
+
+impl Command<Provenance> for SetOrigin {
+    type Result = ();
+    type Event = Self;
+
+    fn decide(
+        self,
+        _: &Provenance,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(self);
+    }
+}
+
+impl Event<Provenance> for SetOrigin {
+    fn evolve(&self, state: &mut Provenance) {
+        state.origins.insert(self.object.id(), self.origin.clone());
+    }
+}
@@ -82,12 +82,22 @@
 //! [Fornjot]: https://www.fornjot.app/
 
 pub mod algorithms;
+pub mod annotations;
+pub mod assembly;
+pub mod diff;
+pub mod garbage;
 pub mod geometry;
 pub mod layers;
+pub mod materials;
 pub mod operations;
 pub mod presentation;
+pub mod provenance;
 pub mod queries;
+pub mod serialization;
+pub mod stats;
 pub mod storage;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod stress;
 pub mod topology;
 pub mod validate;
 pub mod validation;
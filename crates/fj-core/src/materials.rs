@@ -0,0 +1,29 @@
+//! Materials of the object graph
+//!
+//! See [`Materials`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::Material;
+
+use crate::{storage::Handle, topology::Region};
+
+/// Materials of the object graph
+///
+/// Assigns materials to objects (currently only to regions). Unlike the
+/// [`Presentation`] data, materials also describe physical properties, which
+/// calculations like [mass properties] can use.
+///
+/// This data is made available through [`Layers`].
+///
+/// [`Presentation`]: crate::presentation::Presentation
+/// [mass properties]: crate::algorithms::mass_properties
+/// [`Layers`]: crate::layers::Layers
+#[derive(Clone, Default)]
+pub struct Materials {
+    /// Material assigned to regions
+    ///
+    /// Having a material is optional, so map does not necessarily contain
+    /// assignments for all existing regions.
+    pub material: BTreeMap<Handle<Region>, Material>,
+}
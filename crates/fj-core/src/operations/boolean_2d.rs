@@ -0,0 +1,898 @@
+//! # Boolean operations on sketches
+//!
+//! See [`Boolean2d`].
+
+use std::iter;
+
+use fj_interop::{Cancelled, ProgressSink};
+use fj_math::{
+    intersect::{CircleCircleIntersection, CircleLineIntersection},
+    Point, Polygon, Scalar, Tolerances,
+};
+
+use crate::{
+    algorithms::approx::{Approx, Tolerance},
+    geometry::{Geometry, HalfEdgeGeom, LocalCurveGeom, SurfacePath},
+    operations::{build::BuildHalfEdge, insert::Insert},
+    storage::Handle,
+    topology::{Cycle, HalfEdge, Region, Sketch, Surface},
+    Core,
+};
+
+/// Combine sketches, using boolean operations
+///
+/// The sketches are combined in the coordinates of their surface, so both
+/// must be defined on the same one. To combine faces, build sketches from
+/// their regions first.
+///
+/// To find out where the boundaries of both sketches intersect, they are
+/// approximated using the provided tolerance. The parts of the original
+/// boundaries that end up in the result keep their original path, so arcs
+/// stay arcs.
+///
+/// The regions within each sketch must not overlap each other.
+///
+/// # Progress
+///
+/// Progress is reported to [`Core::progress`], while the boundaries are
+/// intersected and the resulting pieces classified. If that cancels the
+/// operation, [`Cancelled`] is returned.
+///
+/// # Panics
+///
+/// Panics, if the sketches are defined on different surfaces.
+pub trait Boolean2d {
+    /// Compute the union of both sketches
+    fn union(
+        &self,
+        other: &Sketch,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Sketch, Cancelled>;
+
+    /// Subtract the other sketch from this one
+    fn difference(
+        &self,
+        other: &Sketch,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Sketch, Cancelled>;
+
+    /// Compute the intersection of both sketches
+    fn intersection(
+        &self,
+        other: &Sketch,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Sketch, Cancelled>;
+}
+
+impl Boolean2d for Sketch {
+    fn union(
+        &self,
+        other: &Sketch,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Sketch, Cancelled> {
+        combine([self, other], Operation::Union, tolerance.into(), core)
+    }
+
+    fn difference(
+        &self,
+        other: &Sketch,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Sketch, Cancelled> {
+        combine([self, other], Operation::Difference, tolerance.into(), core)
+    }
+
+    fn intersection(
+        &self,
+        other: &Sketch,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Sketch, Cancelled> {
+        combine(
+            [self, other],
+            Operation::Intersection,
+            tolerance.into(),
+            core,
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operation {
+    Union,
+    Difference,
+    Intersection,
+}
+
+fn combine(
+    sketches: [&Sketch; 2],
+    operation: Operation,
+    tolerance: Tolerance,
+    core: &mut Core,
+) -> Result<Sketch, Cancelled> {
+    let [a, b] = sketches;
+    assert!(
+        a.surface() == b.surface(),
+        "Can't combine sketches that are defined on different surfaces"
+    );
+    let surface = a.surface().clone();
+    let _span = tracing::debug_span!("boolean", ?operation).entered();
+
+    let mut paths = Vec::new();
+    let rings = tracing::debug_span!("approximate_boundaries").in_scope(|| {
+        sketches.map(|sketch| {
+            rings(sketch, tolerance, &core.layers.geometry, &mut paths)
+        })
+    });
+
+    let mut vertices = Vertices::new(core.tolerances());
+    let pieces = split(&rings, &mut vertices, &mut *core.progress)?;
+
+    let span = tracing::debug_span!(
+        "classify",
+        pieces = pieces.len(),
+        kept = tracing::field::Empty,
+    )
+    .entered();
+    let mut kept = Vec::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        core.progress.step("classify", i, pieces.len())?;
+
+        let other = 1 - piece.operand;
+        let coincident = pieces.iter().find(|candidate| {
+            candidate.operand == other
+                && (candidate.vertices == piece.vertices
+                    || candidate.vertices == reversed(piece.vertices))
+        });
+
+        let keep = match coincident {
+            Some(coincident) => {
+                // Coincident pieces end up in the result at most once. We
+                // only ever keep the one from the first sketch.
+                let same_direction = coincident.vertices == piece.vertices;
+                piece.operand == 0
+                    && match operation {
+                        Operation::Union | Operation::Intersection => {
+                            same_direction
+                        }
+                        Operation::Difference => !same_direction,
+                    }
+            }
+            None => {
+                let [a, b] = piece.vertices.map(|i| vertices.points[i]);
+                let is_inside = contains(
+                    rings[other].iter().flatten().map(|edge| edge.points),
+                    a + (b - a) / 2.,
+                );
+
+                match (operation, piece.operand) {
+                    (Operation::Union, _) | (Operation::Difference, 0) => {
+                        !is_inside
+                    }
+                    (Operation::Intersection, _)
+                    | (Operation::Difference, _) => is_inside,
+                }
+            }
+        };
+
+        if keep {
+            if operation == Operation::Difference && piece.operand == 1 {
+                kept.push(piece.reverse());
+            } else {
+                kept.push(*piece);
+            }
+        }
+    }
+
+    span.record("kept", kept.len());
+    drop(span);
+
+    let _span = tracing::debug_span!("build_regions").entered();
+    let mut exteriors = Vec::new();
+    let mut interiors = Vec::new();
+    for pieces in link(&kept, &vertices) {
+        let points = pieces
+            .iter()
+            .map(|piece| vertices.points[piece.vertices[0]])
+            .collect::<Vec<_>>();
+        let area = Polygon::from_points(points.iter().copied()).signed_area();
+
+        if area > Scalar::ZERO {
+            exteriors.push((pieces, points, area));
+        } else {
+            interiors.push((pieces, points));
+        }
+    }
+
+    let mut regions = exteriors
+        .iter()
+        .map(|(pieces, _, _)| (pieces, Vec::new()))
+        .collect::<Vec<_>>();
+    for (pieces, points) in &interiors {
+        let [a, b] = [points[0], points[1 % points.len()]];
+        let point = a + (b - a) / 2.;
+
+        // An interior cycle belongs to the smallest exterior it is in.
+        let exterior = exteriors
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, exterior, _))| contains(segments(exterior), point))
+            .min_by_key(|(_, (_, _, area))| *area)
+            .map(|(i, _)| i);
+
+        if let Some(i) = exterior {
+            regions[i].1.push(pieces);
+        }
+    }
+
+    let regions = regions
+        .into_iter()
+        .map(|(exterior, interiors)| {
+            let exterior =
+                build_cycle(exterior, &vertices, &paths, &surface, core)
+                    .insert(core);
+            let interiors = interiors
+                .into_iter()
+                .map(|interior| {
+                    build_cycle(interior, &vertices, &paths, &surface, core)
+                        .insert(core)
+                })
+                .collect::<Vec<_>>();
+
+            Region::new(exterior, interiors).insert(core)
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Sketch::new(surface, regions))
+}
+
+/// A straight edge of an approximated cycle
+#[derive(Clone, Copy)]
+struct Edge {
+    points: [Point<2>; 2],
+
+    /// The index of the path of the half-edge this edge approximates
+    path: usize,
+
+    /// The path coordinates of `points`
+    range: [Scalar; 2],
+}
+
+impl Edge {
+    fn reverse(self) -> Self {
+        Self {
+            points: reversed(self.points),
+            path: self.path,
+            range: reversed(self.range),
+        }
+    }
+}
+
+/// Approximate all cycles of a sketch
+///
+/// Exterior cycles are oriented counter-clockwise, interior cycles clockwise.
+fn rings(
+    sketch: &Sketch,
+    tolerance: Tolerance,
+    geometry: &Geometry,
+    paths: &mut Vec<SurfacePath>,
+) -> Vec<Vec<Edge>> {
+    let mut rings = Vec::new();
+
+    for region in sketch.regions() {
+        for (i, cycle) in region.all_cycles().enumerate() {
+            let mut ring = Vec::new();
+
+            for half_edge in cycle.half_edges() {
+                let HalfEdgeGeom { path, boundary } =
+                    *geometry.of_half_edge(half_edge);
+
+                let index = paths.len();
+                paths.push(path);
+
+                let [start, end] = boundary.inner;
+                let points = iter::once(start)
+                    .chain(
+                        (&path, boundary)
+                            .approx(tolerance, geometry)
+                            .into_iter()
+                            .map(|(point_curve, _)| point_curve),
+                    )
+                    .chain(iter::once(end))
+                    .collect::<Vec<_>>();
+
+                for window in points.windows(2) {
+                    let range = [window[0].t, window[1].t];
+                    ring.push(Edge {
+                        points: range.map(|t| path.point_from_path_coords([t])),
+                        path: index,
+                        range,
+                    });
+                }
+            }
+
+            let points =
+                ring.iter().map(|edge| edge.points[0]).collect::<Vec<_>>();
+            let is_exterior = i == 0;
+            let area =
+                Polygon::from_points(points.iter().copied()).signed_area();
+            if (area > Scalar::ZERO) != is_exterior {
+                ring = ring.into_iter().rev().map(Edge::reverse).collect();
+            }
+
+            rings.push(ring);
+        }
+    }
+
+    rings
+}
+
+/// A straight piece of the boundary of one of the sketches
+#[derive(Clone, Copy)]
+struct Piece {
+    /// The sketch this piece comes from, `0` or `1`
+    operand: usize,
+    vertices: [usize; 2],
+    path: usize,
+    range: [Scalar; 2],
+}
+
+impl Piece {
+    fn reverse(self) -> Self {
+        Self {
+            vertices: reversed(self.vertices),
+            range: reversed(self.range),
+            ..self
+        }
+    }
+}
+
+/// Split the edges of both sketches where they intersect each other
+fn split(
+    rings: &[Vec<Vec<Edge>>; 2],
+    vertices: &mut Vertices,
+    progress: &mut dyn ProgressSink,
+) -> Result<Vec<Piece>, Cancelled> {
+    let [a, b] = rings.each_ref().map(|rings| rings.concat());
+    let span = tracing::debug_span!(
+        "intersect",
+        edges = a.len() + b.len(),
+        pieces = tracing::field::Empty,
+    )
+    .entered();
+
+    let mut splits = [vec![Vec::new(); a.len()], vec![Vec::new(); b.len()]];
+    for (i, edge_a) in a.iter().enumerate() {
+        progress.step("intersect", i, a.len())?;
+
+        for (j, edge_b) in b.iter().enumerate() {
+            for [s, t] in
+                intersect(edge_a.points, edge_b.points, &vertices.tolerances)
+            {
+                splits[0][i].push(s);
+                splits[1][j].push(t);
+            }
+        }
+    }
+
+    let mut pieces = Vec::new();
+    for (operand, (edges, splits)) in [a, b].iter().zip(splits).enumerate() {
+        for (edge, mut splits) in edges.iter().zip(splits) {
+            splits.extend([Scalar::ZERO, Scalar::ONE]);
+            splits.sort();
+
+            let [a, b] = edge.points;
+            let [start, end] = edge.range;
+            for window in splits.windows(2) {
+                let [s, t] = [window[0], window[1]];
+                let piece = Piece {
+                    operand,
+                    vertices: [s, t].map(|s| vertices.insert(a + (b - a) * s)),
+                    path: edge.path,
+                    range: [s, t].map(|s| start + (end - start) * s),
+                };
+
+                if piece.vertices[0] != piece.vertices[1] {
+                    pieces.push(piece);
+                }
+            }
+        }
+    }
+
+    span.record("pieces", pieces.len());
+    Ok(pieces)
+}
+
+/// Compute where two line segments intersect
+///
+/// Returns the intersections as pairs of parameters, `0` to `1` along each of
+/// the segments. Where the segments overlap, the endpoints of the overlap are
+/// returned.
+fn intersect(
+    a: [Point<2>; 2],
+    b: [Point<2>; 2],
+    tolerances: &Tolerances,
+) -> Vec<[Scalar; 2]> {
+    let [r, s] = [a, b].map(|[start, end]| end - start);
+    let q = b[0] - a[0];
+    let [length_r, length_s] = [r, s].map(|v| v.magnitude());
+    if length_r == Scalar::ZERO || length_s == Scalar::ZERO {
+        return Vec::new();
+    }
+
+    let in_range = |t: Scalar, length: Scalar| {
+        let margin = tolerances.linear / length;
+        t >= -margin && t <= Scalar::ONE + margin
+    };
+    let clamp = |t: Scalar| t.max(Scalar::ZERO).min(Scalar::ONE);
+
+    let denominator = r.cross2d(&s);
+    if denominator.abs() > tolerances.angular * length_r * length_s {
+        let t = q.cross2d(&s) / denominator;
+        let u = q.cross2d(&r) / denominator;
+
+        if in_range(t, length_r) && in_range(u, length_s) {
+            return vec![[clamp(t), clamp(u)]];
+        }
+
+        return Vec::new();
+    }
+
+    // The segments are parallel. Unless they are on the same line, they don't
+    // intersect.
+    if q.cross2d(&r).abs() / length_r > tolerances.linear {
+        return Vec::new();
+    }
+
+    let mut intersections = Vec::new();
+    for (u, point) in [Scalar::ZERO, Scalar::ONE].into_iter().zip(b) {
+        let t = (point - a[0]).dot(&r) / (length_r * length_r);
+        if in_range(t, length_r) {
+            intersections.push([clamp(t), u]);
+        }
+    }
+    for (t, point) in [Scalar::ZERO, Scalar::ONE].into_iter().zip(a) {
+        let u = (point - b[0]).dot(&s) / (length_s * length_s);
+        if in_range(u, length_s) {
+            intersections.push([t, clamp(u)]);
+        }
+    }
+
+    intersections
+}
+
+/// The vertices of the pieces of both sketches
+///
+/// Points that are closer than the linear tolerance are merged into a single
+/// vertex.
+struct Vertices {
+    points: Vec<Point<2>>,
+    tolerances: Tolerances,
+}
+
+impl Vertices {
+    fn new(tolerances: Tolerances) -> Self {
+        Self {
+            points: Vec::new(),
+            tolerances,
+        }
+    }
+
+    fn insert(&mut self, point: Point<2>) -> usize {
+        if let Some(index) = self
+            .points
+            .iter()
+            .position(|&vertex| self.tolerances.are_coincident(vertex, point))
+        {
+            return index;
+        }
+
+        self.points.push(point);
+        self.points.len() - 1
+    }
+}
+
+/// Link the pieces that make up the result into closed loops
+///
+/// Where multiple pieces start at the same vertex, the loop turns left as much
+/// as possible. This separates regions that only touch in a single vertex.
+fn link(pieces: &[Piece], vertices: &Vertices) -> Vec<Vec<Piece>> {
+    let mut is_used = vec![false; pieces.len()];
+    let mut loops = Vec::new();
+
+    for first in 0..pieces.len() {
+        if is_used[first] {
+            continue;
+        }
+        is_used[first] = true;
+
+        let mut current = vec![pieces[first]];
+        let is_closed = loop {
+            let [from, to] = current[current.len() - 1].vertices;
+            if to == pieces[first].vertices[0] {
+                break true;
+            }
+
+            let incoming = vertices.points[to] - vertices.points[from];
+            let next = (0..pieces.len())
+                .filter(|&i| !is_used[i] && pieces[i].vertices[0] == to)
+                .max_by_key(|&i| {
+                    let outgoing = vertices.points[pieces[i].vertices[1]]
+                        - vertices.points[to];
+                    incoming.cross2d(&outgoing).atan2(incoming.dot(&outgoing))
+                });
+
+            let Some(next) = next else {
+                break false;
+            };
+            is_used[next] = true;
+            current.push(pieces[next]);
+        };
+
+        if is_closed {
+            loops.push(current);
+        }
+    }
+
+    loops
+}
+
+/// Build a cycle from a loop of pieces
+///
+/// Consecutive pieces that come from the same half-edge are combined into a
+/// single half-edge that uses the original path.
+fn build_cycle(
+    pieces: &[Piece],
+    vertices: &Vertices,
+    paths: &[SurfacePath],
+    surface: &Handle<Surface>,
+    core: &mut Core,
+) -> Cycle {
+    // Don't compare the path coordinates here. Where a piece of a circle ends
+    // at a full turn, the next one might start at zero.
+    let continues = |a: &Piece, b: &Piece| {
+        a.path == b.path && a.vertices[1] == b.vertices[0]
+    };
+
+    // Start at a piece that doesn't continue the previous one, so no group of
+    // pieces wraps around the end of the loop.
+    let start = (0..pieces.len())
+        .find(|&i| {
+            let previous = &pieces[(i + pieces.len() - 1) % pieces.len()];
+            !continues(previous, &pieces[i])
+        })
+        .unwrap_or(0);
+
+    let mut groups: Vec<Vec<Piece>> = Vec::new();
+    for i in 0..pieces.len() {
+        let piece = pieces[(start + i) % pieces.len()];
+        match groups.last_mut() {
+            Some(group) if continues(&group[group.len() - 1], &piece) => {
+                group.push(piece);
+            }
+            _ => groups.push(vec![piece]),
+        }
+    }
+
+    if let [group] = groups.as_slice() {
+        let path = paths[group[0].path];
+        let half_edge =
+            build_half_edge(path, range(group), surface.clone(), core);
+        return Cycle::new([half_edge]);
+    }
+
+    // The vertices between the groups are where the original boundaries
+    // intersect. Compute them from the original paths, so the half-edges that
+    // meet there actually connect.
+    let corners = (0..groups.len())
+        .map(|i| {
+            let previous = &groups[(i + groups.len() - 1) % groups.len()];
+            let group = &groups[i];
+
+            let approx = vertices.points[group[0].vertices[0]];
+            refine(
+                approx,
+                paths[previous[previous.len() - 1].path],
+                paths[group[0].path],
+                &vertices.tolerances,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let half_edges = groups
+        .iter()
+        .enumerate()
+        .map(|(i, group)| {
+            let points = [corners[i], corners[(i + 1) % corners.len()]];
+            let path = paths[group[0].path];
+
+            match path {
+                SurfacePath::Line(_) => {
+                    HalfEdge::line_segment(points, None, surface.clone(), core)
+                }
+                SurfacePath::Circle(_) => {
+                    let approx = range(group);
+                    let range = [0, 1].map(|j| {
+                        let t = path.project_point(points[j]).t;
+                        approx[j] + (t - approx[j]).wrap_angle()
+                    });
+
+                    build_half_edge(path, range, surface.clone(), core)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Cycle::new(half_edges)
+}
+
+/// Compute the range of path coordinates covered by a group of pieces
+fn range(group: &[Piece]) -> [Scalar; 2] {
+    let start = group[0].range[0];
+    let end = group
+        .iter()
+        .fold(start, |end, piece| end + piece.range[1] - piece.range[0]);
+
+    [start, end]
+}
+
+fn build_half_edge(
+    path: SurfacePath,
+    range: [Scalar; 2],
+    surface: Handle<Surface>,
+    core: &mut Core,
+) -> Handle<HalfEdge> {
+    let half_edge = HalfEdge::unjoined(core).insert(core);
+
+    core.layers.geometry.define_curve(
+        half_edge.curve().clone(),
+        surface,
+        LocalCurveGeom { path },
+    );
+    core.layers.geometry.define_half_edge(
+        half_edge.clone(),
+        HalfEdgeGeom {
+            path,
+            boundary: range.map(|t| Point::from([t])).into(),
+        },
+    );
+
+    half_edge
+}
+
+/// Compute the exact intersection of two paths, close to an approximate one
+fn refine(
+    approx: Point<2>,
+    a: SurfacePath,
+    b: SurfacePath,
+    tolerances: &Tolerances,
+) -> Point<2> {
+    let candidates = match (a, b) {
+        (SurfacePath::Line(_), SurfacePath::Line(_)) => return approx,
+        (SurfacePath::Line(line), SurfacePath::Circle(circle))
+        | (SurfacePath::Circle(circle), SurfacePath::Line(line)) => {
+            match CircleLineIntersection::compute(&circle, &line, tolerances) {
+                Some(CircleLineIntersection::Points { points }) => {
+                    points.to_vec()
+                }
+                Some(CircleLineIntersection::Tangent { point }) => vec![point],
+                None => Vec::new(),
+            }
+        }
+        (SurfacePath::Circle(a), SurfacePath::Circle(b)) => {
+            match CircleCircleIntersection::compute(&a, &b, tolerances) {
+                Some(CircleCircleIntersection::Points { points }) => {
+                    points.to_vec()
+                }
+                Some(CircleCircleIntersection::Tangent { point }) => {
+                    vec![point]
+                }
+                Some(CircleCircleIntersection::Coincident) | None => Vec::new(),
+            }
+        }
+    };
+
+    candidates
+        .into_iter()
+        .min_by_key(|candidate| candidate.distance_to(&approx))
+        .unwrap_or_else(|| {
+            // There is no exact intersection, which can happen, if both paths
+            // are the same circle. Stay on one of the paths.
+            let path = match a {
+                SurfacePath::Circle(_) => a,
+                SurfacePath::Line(_) => b,
+            };
+            path.point_from_path_coords(path.project_point(approx))
+        })
+}
+
+/// Check whether a point is inside of the area bounded by the segments
+fn contains(
+    segments: impl IntoIterator<Item = [Point<2>; 2]>,
+    point: Point<2>,
+) -> bool {
+    let mut is_inside = false;
+
+    for [a, b] in segments {
+        if (a.v > point.v) != (b.v > point.v) {
+            let u = a.u + (point.v - a.v) * (b.u - a.u) / (b.v - a.v);
+            if point.u < u {
+                is_inside = !is_inside;
+            }
+        }
+    }
+
+    is_inside
+}
+
+/// Iterate over the segments of a closed polygon
+fn segments(polygon: &[Point<2>]) -> impl Iterator<Item = [Point<2>; 2]> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| [a, b])
+}
+
+fn reversed<T>([a, b]: [T; 2]) -> [T; 2] {
+    [b, a]
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{CancellationToken, Cancelled};
+    use fj_math::{Polygon, Scalar};
+
+    use crate::{
+        algorithms::approx::ApproxPolylines,
+        geometry::SurfacePath,
+        operations::{build::BuildRegion, insert::Insert},
+        topology::{Face, Region, Sketch},
+        Core,
+    };
+
+    use super::Boolean2d;
+
+    #[test]
+    fn overlapping_squares() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = square([0., 0.], 2., &mut core);
+        let b = square([1., 1.], 2., &mut core);
+
+        let union = a.union(&b, 0.01, &mut core)?;
+        let difference = a.difference(&b, 0.01, &mut core)?;
+        let intersection = a.intersection(&b, 0.01, &mut core)?;
+
+        for (sketch, num_half_edges, expected_area) in
+            [(union, 8, 7.), (difference, 6, 3.), (intersection, 4, 1.)]
+        {
+            let region = sketch.regions().only();
+            assert_eq!(region.exterior().half_edges().len(), num_half_edges);
+            assert_eq!(area(&sketch, &core), Scalar::from(expected_area));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn disjoint_and_contained() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = square([0., 0.], 1., &mut core);
+        let b = square([2., 0.], 1., &mut core);
+        let union = a.union(&b, 0.01, &mut core)?;
+        assert_eq!(union.regions().len(), 2);
+        assert!(a.intersection(&b, 0.01, &mut core)?.regions().is_empty());
+
+        let outer = square([0., 0.], 4., &mut core);
+        let inner = square([1., 1.], 2., &mut core);
+        let difference = outer.difference(&inner, 0.01, &mut core)?;
+        let region = difference.regions().only();
+        assert_eq!(region.interiors().len(), 1);
+        assert_eq!(area(&difference, &core), Scalar::from(12.));
+
+        Ok(())
+    }
+
+    #[test]
+    fn arcs_are_preserved() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let square = square([0., 0.], 2., &mut core);
+        let a = circle([2., 1.], 0.5, &mut core);
+        let b = circle([2.5, 1.], 0.5, &mut core);
+
+        let difference = square.difference(&a, 0.01, &mut core)?;
+        let union = a.union(&b, 0.01, &mut core)?;
+
+        // The square loses part of its right side, and gains a half circle.
+        // The union of the circles consists of two arcs.
+        for (sketch, num_half_edges, num_arcs) in
+            [(difference, 6, 1), (union, 2, 2)]
+        {
+            let exterior = sketch.regions().only().exterior().clone();
+            assert_eq!(exterior.half_edges().len(), num_half_edges);
+
+            let arcs = exterior
+                .half_edges()
+                .iter()
+                .filter(|half_edge| {
+                    matches!(
+                        core.layers.geometry.of_half_edge(half_edge).path,
+                        SurfacePath::Circle(_)
+                    )
+                })
+                .count();
+            assert_eq!(arcs, num_arcs);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel() {
+        let mut core = Core::new();
+
+        let a = square([0., 0.], 2., &mut core);
+        let b = square([1., 1.], 2., &mut core);
+
+        let token = CancellationToken::new();
+        core.progress = Box::new(token.clone());
+        token.cancel();
+
+        let num_regions = core.layers.topology.regions.iter().count();
+        assert!(matches!(a.union(&b, 0.01, &mut core), Err(Cancelled)));
+        assert_eq!(
+            core.layers.topology.regions.iter().count(),
+            num_regions,
+            "Cancelled operation must not insert regions",
+        );
+    }
+
+    fn square(min: [f64; 2], size: f64, core: &mut Core) -> Sketch {
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let [u, v] = min;
+
+        let region = Region::polygon(
+            [[u, v], [u + size, v], [u + size, v + size], [u, v + size]],
+            surface.clone(),
+            core,
+        )
+        .insert(core);
+
+        Sketch::new(surface, [region])
+    }
+
+    fn circle(center: [f64; 2], radius: f64, core: &mut Core) -> Sketch {
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let region =
+            Region::circle(center, radius, surface.clone(), core).insert(core);
+
+        Sketch::new(surface, [region])
+    }
+
+    fn area(sketch: &Sketch, core: &Core) -> Scalar {
+        let mut area = Scalar::ZERO;
+
+        for region in sketch.regions() {
+            let face = Face::new(sketch.surface().clone(), region.clone());
+            let polylines = face.approx_polylines(0.01, &core.layers.geometry);
+
+            for polyline in [&polylines.exterior]
+                .into_iter()
+                .chain(&polylines.interiors)
+            {
+                let polygon = Polygon::from_points(
+                    polyline.points_surface.iter().copied(),
+                );
+                area += polygon.signed_area();
+            }
+        }
+
+        area
+    }
+}
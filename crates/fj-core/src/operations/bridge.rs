@@ -0,0 +1,528 @@
+//! # Bridge gaps between open edges
+//!
+//! See [`Bridge`].
+
+use fj_math::{Point, Scalar};
+
+use crate::{
+    geometry::{CurveBoundary, GlobalPath, HalfEdgeGeom, SurfacePath},
+    operations::{
+        build::BuildHalfEdge,
+        geometry::{UpdateCurveGeometry, UpdateHalfEdgeGeometry},
+        insert::Insert,
+        sweep::SweepSurfacePath,
+        update::{UpdateHalfEdge, UpdateShell},
+    },
+    queries::{
+        AllHalfEdgesWithSurface, BoundingVerticesOfHalfEdge, SiblingOfHalfEdge,
+    },
+    storage::Handle,
+    topology::{Curve, Cycle, Face, HalfEdge, Region, Shell, Surface, Vertex},
+    Core,
+};
+
+/// Bridge the gap between two chains of open half-edges
+pub trait Bridge: Sized {
+    /// Add faces that bridge the gap between the provided chains
+    ///
+    /// Both chains consist of half-edges of the shell that don't have a
+    /// sibling yet, like the edges on either side of a gap. Each half-edge of a
+    /// chain must start where the previous one ends. The chains must face each
+    /// other, meaning `b` runs in the opposite direction of `a`: The first
+    /// half-edge of `a` is bridged to the last one of `b`, the second one to
+    /// the second-to-last, and so on.
+    ///
+    /// A face is added for each pair of half-edges, which becomes the sibling
+    /// of both. Such a face can be created, if one half-edge of the pair is a
+    /// translated copy of the other (which also works for arcs), or if both are
+    /// lines in the same plane. The half-edges of `a` must be defined on planar
+    /// faces.
+    ///
+    /// Where the shell already has open half-edges between the ends of the
+    /// chains, the new faces are joined to them. If both chains are closed,
+    /// the first and last new face are joined to each other.
+    fn bridge(
+        &self,
+        a: &[Handle<HalfEdge>],
+        b: &[Handle<HalfEdge>],
+        core: &mut Core,
+    ) -> Result<Self, BridgeError>;
+}
+
+impl Bridge for Shell {
+    fn bridge(
+        &self,
+        a: &[Handle<HalfEdge>],
+        b: &[Handle<HalfEdge>],
+        core: &mut Core,
+    ) -> Result<Self, BridgeError> {
+        if a.len() != b.len() {
+            return Err(BridgeError::DifferentLengths {
+                a: a.len(),
+                b: b.len(),
+            });
+        }
+        let n = a.len();
+
+        let [a, b] = [a, b].map(|chain| open_chain(self, chain, core));
+        let [a, b] = [a?, b?];
+
+        // The vertices on both sides of the gap, with `q[k]` being across the
+        // gap from `p[k]`.
+        let p = a
+            .iter()
+            .map(|open| open.vertices[0].clone())
+            .chain(a.last().map(|open| open.vertices[1].clone()))
+            .collect::<Vec<_>>();
+        let q = b
+            .iter()
+            .rev()
+            .map(|open| open.vertices[1].clone())
+            .chain(b.first().map(|open| open.vertices[0].clone()))
+            .collect::<Vec<_>>();
+        let is_closed = n > 0 && p[0] == p[n] && q[0] == q[n];
+
+        let mut sides: Vec<(Handle<Curve>, CurveBoundary<Point<1>>)> =
+            Vec::new();
+        for k in 0..=n {
+            if is_closed && k == n {
+                sides.push(sides[0].clone());
+                continue;
+            }
+
+            // At the first side, the new face goes from `p` to `q`. At the
+            // last one, it goes from `q` to `p`. A sibling must go the other
+            // way.
+            let vertices = if k == 0 {
+                [q[k].clone(), p[k].clone()]
+            } else {
+                [p[k].clone(), q[k].clone()]
+            };
+            let existing = if k == 0 || k == n {
+                find_open_line(self, vertices, core)
+            } else {
+                None
+            };
+
+            let side = match existing {
+                Some(half_edge) => {
+                    let boundary =
+                        core.layers.geometry.of_half_edge(&half_edge).boundary;
+                    let boundary =
+                        if k == 0 { boundary.reverse() } else { boundary };
+                    (half_edge.curve().clone(), boundary)
+                }
+                None => (Curve::new().insert(core), CurveBoundary::default()),
+            };
+            sides.push(side);
+        }
+
+        let mut faces = Vec::new();
+        for i in 0..n {
+            let face = bridge_pair(
+                [&a[i], &b[n - 1 - i]],
+                [&p[i], &p[i + 1], &q[i], &q[i + 1]],
+                [&sides[i], &sides[i + 1]],
+                core,
+            )?;
+            faces.push(face);
+        }
+
+        Ok(self.add_faces(faces, core))
+    }
+}
+
+/// An open half-edge of a shell, with the information needed to bridge it
+struct OpenHalfEdge {
+    half_edge: Handle<HalfEdge>,
+    surface: Handle<Surface>,
+    vertices: [Handle<Vertex>; 2],
+}
+
+impl OpenHalfEdge {
+    /// Compute the global position of a point on the half-edge
+    fn position(&self, point: Point<1>, core: &Core) -> Point<3> {
+        let path = core.layers.geometry.of_half_edge(&self.half_edge).path;
+        core.layers
+            .geometry
+            .of_surface(&self.surface)
+            .point_from_surface_coords(path.point_from_path_coords(point))
+    }
+
+    fn boundary(&self, core: &Core) -> [Point<1>; 2] {
+        core.layers
+            .geometry
+            .of_half_edge(&self.half_edge)
+            .boundary
+            .inner
+    }
+}
+
+/// Look up the half-edges of a chain in the shell
+fn open_chain(
+    shell: &Shell,
+    chain: &[Handle<HalfEdge>],
+    core: &Core,
+) -> Result<Vec<OpenHalfEdge>, BridgeError> {
+    let mut open: Vec<OpenHalfEdge> = Vec::new();
+
+    for half_edge in chain {
+        let surface = shell
+            .all_half_edges_with_surface()
+            .find(|(h, _)| h == half_edge)
+            .map(|(_, surface)| surface);
+        let vertices = shell.bounding_vertices_of_half_edge(half_edge);
+        let has_sibling = shell
+            .get_sibling_of(half_edge, &core.layers.geometry)
+            .is_some();
+
+        let (Some(surface), Some(vertices), false) =
+            (surface, vertices, has_sibling)
+        else {
+            return Err(BridgeError::NotOpen {
+                half_edge: half_edge.clone(),
+            });
+        };
+
+        if let Some(previous) = open.last() {
+            if previous.vertices[1] != vertices.inner[0] {
+                return Err(BridgeError::NotAChain {
+                    half_edge: half_edge.clone(),
+                });
+            }
+        }
+
+        open.push(OpenHalfEdge {
+            half_edge: half_edge.clone(),
+            surface,
+            vertices: vertices.inner,
+        });
+    }
+
+    Ok(open)
+}
+
+/// Find an open line segment of the shell between the provided vertices
+fn find_open_line(
+    shell: &Shell,
+    vertices: [Handle<Vertex>; 2],
+    core: &Core,
+) -> Option<Handle<HalfEdge>> {
+    shell
+        .all_half_edges_with_surface()
+        .map(|(half_edge, _)| half_edge)
+        .find(|half_edge| {
+            let is_line = matches!(
+                core.layers.geometry.of_half_edge(half_edge).path,
+                SurfacePath::Line(_)
+            );
+            let has_vertices = shell
+                .bounding_vertices_of_half_edge(half_edge)
+                .is_some_and(|boundary| boundary.inner == vertices);
+            let has_sibling = shell
+                .get_sibling_of(half_edge, &core.layers.geometry)
+                .is_some();
+
+            is_line && has_vertices && !has_sibling
+        })
+}
+
+/// Create the face that bridges a pair of half-edges
+///
+/// `a` goes from `p0` to `p1`, `b` from `q1` to `q0`.
+fn bridge_pair(
+    [a, b]: [&OpenHalfEdge; 2],
+    [p0, p1, q0, q1]: [&Handle<Vertex>; 4],
+    sides: [&(Handle<Curve>, CurveBoundary<Point<1>>); 2],
+    core: &mut Core,
+) -> Result<Face, BridgeError> {
+    let tolerances = core.tolerances();
+    let unsupported = || BridgeError::Unsupported {
+        a: a.half_edge.clone(),
+        b: b.half_edge.clone(),
+    };
+
+    let a_geom = *core.layers.geometry.of_half_edge(&a.half_edge);
+    let a_surface = *core.layers.geometry.of_surface(&a.surface);
+    if let GlobalPath::Circle(_) = a_surface.u {
+        return Err(BridgeError::CurvedSurface {
+            half_edge: a.half_edge.clone(),
+        });
+    }
+
+    let [t0, t1] = a_geom.boundary.inner.map(|point| point.t);
+    let [b1, b0] = b.boundary(core);
+    let [p0_pos, q0_pos, q1_pos] = [
+        a.position([t0].into(), core),
+        b.position(b0, core),
+        b.position(b1, core),
+    ];
+
+    let sweep = q0_pos - p0_pos;
+    if tolerances.is_zero_length(sweep.magnitude()) {
+        return Err(unsupported());
+    }
+
+    let surface = a_geom.path.sweep_surface_path(&a_surface, sweep, core);
+    let surface_geom = *core.layers.geometry.of_surface(&surface);
+
+    let p0_uv = Point::from([t0, Scalar::ZERO]);
+    let p1_uv = Point::from([t1, Scalar::ZERO]);
+    let q0_uv = Point::from([t0, Scalar::ONE]);
+    let q1_uv = {
+        // Prefer the translated point. On a closed curve, projecting the
+        // point might end up on the wrong end.
+        let translated = Point::from([t1, Scalar::ONE]);
+        if tolerances.are_coincident(
+            surface_geom.point_from_surface_coords(translated),
+            q1_pos,
+        ) {
+            translated
+        } else {
+            surface_geom.project_global_point(q1_pos)
+        }
+    };
+
+    // Make sure that `b` is actually on the surface, and that its coordinates
+    // match the ones we're about to define on it.
+    for s in [0., 0.25, 0.5, 0.75, 1.] {
+        let on_surface =
+            surface_geom.point_from_surface_coords(q0_uv + (q1_uv - q0_uv) * s);
+        let on_b = b.position(b0 + (b1 - b0) * s, core);
+
+        if !tolerances.are_coincident(on_surface, on_b) {
+            return Err(unsupported());
+        }
+    }
+
+    let [(side0, side0_boundary), (side1, side1_boundary)] = sides;
+    let edges = [
+        (
+            p1,
+            a.half_edge.curve(),
+            [p1_uv, p0_uv],
+            a_geom.boundary.reverse(),
+        ),
+        (p0, side0, [p0_uv, q0_uv], *side0_boundary),
+        (
+            q0,
+            b.half_edge.curve(),
+            [q0_uv, q1_uv],
+            CurveBoundary::from([b0, b1]),
+        ),
+        (q1, side1, [q1_uv, p1_uv], side1_boundary.reverse()),
+    ];
+
+    let half_edges = edges
+        .into_iter()
+        .map(|(start_vertex, curve, points, boundary)| {
+            let curve = curve.clone().make_line_on_surface(
+                points,
+                Some(boundary),
+                surface.clone(),
+                &mut core.layers.geometry,
+            );
+            let path = core
+                .layers
+                .geometry
+                .of_curve(&curve)
+                .and_then(|curve| curve.local_on(&surface))
+                .expect("Curve geometry was just defined in same function")
+                .path;
+
+            HalfEdge::unjoined(core)
+                .update_start_vertex(|_, _| start_vertex.clone(), core)
+                .update_curve(|_, _| curve, core)
+                .insert(core)
+                .set_geometry(
+                    HalfEdgeGeom { path, boundary },
+                    &mut core.layers.geometry,
+                )
+        })
+        .collect::<Vec<_>>();
+
+    let exterior = Cycle::new(half_edges).insert(core);
+    let region = Region::new(exterior, []).insert(core);
+
+    Ok(Face::new(surface, region))
+}
+
+/// Error bridging a gap between half-edges
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum BridgeError {
+    /// The chains consist of different numbers of half-edges
+    #[error("Can't bridge chains of {a} and {b} half-edges")]
+    DifferentLengths {
+        /// The number of half-edges in the first chain
+        a: usize,
+
+        /// The number of half-edges in the second chain
+        b: usize,
+    },
+
+    /// A half-edge is not part of the shell, or already has a sibling
+    #[error("Half-edge is not an open half-edge of the shell: {half_edge:?}")]
+    NotOpen {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// A half-edge doesn't start where the previous one in the chain ends
+    #[error("Half-edge doesn't continue the chain: {half_edge:?}")]
+    NotAChain {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// A half-edge of the first chain is defined on a curved surface
+    #[error("Can't bridge half-edge on curved surface: {half_edge:?}")]
+    CurvedSurface {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// No supported surface connects a pair of half-edges
+    #[error("Can't bridge the gap between half-edges {a:?} and {b:?}")]
+    Unsupported {
+        /// The half-edge from the first chain
+        a: Handle<HalfEdge>,
+
+        /// The half-edge from the second chain
+        b: Handle<HalfEdge>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        geometry::GlobalPath,
+        operations::{
+            build::{BuildShell, BuildSolid},
+            insert::Insert,
+            update::UpdateShell,
+        },
+        queries::SiblingOfHalfEdge,
+        storage::Handle,
+        topology::{Face, HalfEdge, Shell, Solid},
+        Core,
+    };
+
+    use super::{Bridge, BridgeError};
+
+    #[test]
+    fn bridge_removed_side_of_cuboid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = Shell::from_vertices_and_polygons(
+            [
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [1., 1., 0.],
+                [0., 1., 0.],
+                [0., 0., 1.],
+                [1., 0., 1.],
+                [1., 1., 1.],
+                [0., 1., 1.],
+            ],
+            [
+                vec![0, 3, 2, 1],
+                vec![4, 5, 6, 7],
+                vec![0, 1, 5, 4],
+                vec![1, 2, 6, 5],
+                vec![2, 3, 7, 6],
+                vec![3, 0, 4, 7],
+            ],
+            &mut core,
+        );
+        let faces = cuboid.faces().clone();
+        let shell = cuboid.remove_face(faces.nth(5).unwrap());
+
+        // The bottom face goes from vertex 0 to vertex 3, the top face from 7
+        // to 4, along the gap.
+        let a = half_edge(faces.nth(0).unwrap(), 0);
+        let b = half_edge(faces.nth(1).unwrap(), 3);
+
+        let shell = shell.bridge(&[a.clone()], &[b.clone()], &mut core)?;
+        assert_eq!(shell.faces().len(), 6);
+        assert!(shell.get_sibling_of(&a, &core.layers.geometry).is_some());
+        assert!(shell.get_sibling_of(&b, &core.layers.geometry).is_some());
+
+        let _ = shell.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn bridge_removed_side_of_cylinder() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+        let cylinder = cylinder.shells().only().clone();
+
+        let side = cylinder
+            .faces()
+            .iter()
+            .find(|face| {
+                let surface = core.layers.geometry.of_surface(face.surface());
+                matches!(surface.u, GlobalPath::Circle(_))
+            })
+            .unwrap()
+            .clone();
+        let shell = cylinder.remove_face(&side);
+
+        let [bottom, top] = ["bottom", "top"].map(|which| {
+            let face = shell
+                .faces()
+                .iter()
+                .find(|face| {
+                    let z = core
+                        .layers
+                        .geometry
+                        .of_surface(face.surface())
+                        .point_from_surface_coords([0., 0.])
+                        .z;
+                    (z == Scalar::ZERO) == (which == "bottom")
+                })
+                .unwrap();
+            half_edge(face, 0)
+        });
+
+        let shell = shell.bridge(&[bottom], &[top], &mut core)?;
+        assert_eq!(shell.faces().len(), 3);
+
+        let _ = shell.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_chains_of_different_lengths() {
+        let mut core = Core::new();
+
+        let tetrahedron = Shell::from_vertices_and_polygons(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            [vec![0, 2, 1], vec![0, 1, 3], vec![1, 2, 3], vec![2, 0, 3]],
+            &mut core,
+        );
+        let a = half_edge(tetrahedron.faces().first(), 0);
+
+        let result = tetrahedron.bridge(&[a], &[], &mut core);
+        assert!(matches!(
+            result,
+            Err(BridgeError::DifferentLengths { a: 1, b: 0 })
+        ));
+    }
+
+    fn half_edge(face: &Handle<Face>, index: usize) -> Handle<HalfEdge> {
+        face.region()
+            .exterior()
+            .half_edges()
+            .nth(index)
+            .unwrap()
+            .clone()
+    }
+}
@@ -1,8 +1,13 @@
-use fj_math::{Point, Scalar};
+use fj_math::{Point, Scalar, Vector};
 use itertools::Itertools;
 
 use crate::{
-    operations::{build::BuildHalfEdge, update::UpdateCycle},
+    geometry::{HalfEdgeGeom, LocalCurveGeom, SurfacePath},
+    operations::{
+        build::{BuildHalfEdge, SpurGear},
+        insert::Insert,
+        update::UpdateCycle,
+    },
     storage::Handle,
     topology::{Cycle, HalfEdge, Surface},
     Core,
@@ -56,6 +61,245 @@ pub trait BuildCycle {
 
         Cycle::new(edges)
     }
+
+    /// Build a rectangle with rounded corners
+    ///
+    /// The rectangle is centered on `center` and wound counter-clockwise. Each
+    /// of its corners is replaced by a quarter circle with the given radius.
+    /// If the radius is zero, this is equivalent to [`BuildCycle::polygon`].
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the radius is negative, or if it isn't smaller than half of
+    /// the shorter side of the rectangle.
+    fn rounded_rectangle(
+        center: impl Into<Point<2>>,
+        size: impl Into<Vector<2>>,
+        radius: impl Into<Scalar>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Cycle {
+        let center = center.into();
+        let [x, y] = size.into().components.map(|s| s / 2.);
+        let radius = radius.into();
+
+        assert!(
+            radius >= Scalar::ZERO,
+            "Corner radius of rounded rectangle must not be negative"
+        );
+        assert!(
+            radius < x.min(y),
+            "Corner radius of rounded rectangle must be smaller than half of \
+            its shorter side"
+        );
+
+        if radius == Scalar::ZERO {
+            return Cycle::polygon(
+                [[-x, -y], [x, -y], [x, y], [-x, y]]
+                    .map(|offset| center + Vector::from(offset)),
+                surface,
+                core,
+            );
+        }
+
+        let [x_inner, y_inner] = [x - radius, y - radius];
+
+        arcs_connected_by_lines(
+            center,
+            radius,
+            [
+                ([x_inner, -y_inner], -Scalar::PI / 2.),
+                ([x_inner, y_inner], Scalar::ZERO),
+                ([-x_inner, y_inner], Scalar::PI / 2.),
+                ([-x_inner, -y_inner], Scalar::PI),
+            ]
+            .map(|(offset, start_angle)| {
+                (offset, [start_angle, start_angle + Scalar::PI / 2.])
+            }),
+            surface,
+            core,
+        )
+    }
+
+    /// Build a regular polygon
+    ///
+    /// The polygon is centered on `center`, and its vertices are located on a
+    /// circle with the given radius. The first vertex is located in positive
+    /// u-direction from the center. The polygon is wound counter-clockwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `num_sides` is smaller than 3.
+    fn regular_polygon(
+        center: impl Into<Point<2>>,
+        radius: impl Into<Scalar>,
+        num_sides: usize,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Cycle {
+        assert!(num_sides >= 3, "Regular polygon must have at least 3 sides");
+
+        let center = center.into();
+        let radius = radius.into();
+
+        let points = (0..num_sides).map(|i| {
+            let angle = Scalar::TAU / num_sides as f64 * i as f64;
+            let (sin, cos) = angle.sin_cos();
+
+            center + Vector::from([cos, sin]) * radius
+        });
+
+        Cycle::polygon(points, surface, core)
+    }
+
+    /// Build a slot (a rectangle with semicircular ends)
+    ///
+    /// The slot is centered on `center` and extends along the u-axis. `length`
+    /// is the overall length of the slot, including its rounded ends. `width`
+    /// is the diameter of the rounded ends. The slot is wound
+    /// counter-clockwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `width` is not positive, or if `length` is not larger than
+    /// `width`.
+    fn slot(
+        center: impl Into<Point<2>>,
+        length: impl Into<Scalar>,
+        width: impl Into<Scalar>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Cycle {
+        let center = center.into();
+        let length = length.into();
+        let width = width.into();
+
+        assert!(width > Scalar::ZERO, "Width of slot must be positive");
+        assert!(
+            length > width,
+            "Length of slot must be larger than its width"
+        );
+
+        let radius = width / 2.;
+        let offset = (length - width) / 2.;
+
+        arcs_connected_by_lines(
+            center,
+            radius,
+            [
+                ([offset, Scalar::ZERO], -Scalar::PI / 2.),
+                ([-offset, Scalar::ZERO], Scalar::PI / 2.),
+            ]
+            .map(|(offset, start_angle)| {
+                (offset, [start_angle, start_angle + Scalar::PI])
+            }),
+            surface,
+            core,
+        )
+    }
+
+    /// Build the outline of an involute spur gear
+    ///
+    /// The gear is centered on `center`. See [`SpurGear::outline`] for more
+    /// information.
+    fn spur_gear(
+        center: impl Into<Point<2>>,
+        gear: SpurGear,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Cycle {
+        Cycle::polygon(gear.outline(center), surface, core)
+    }
 }
 
 impl BuildCycle for Cycle {}
+
+/// Build a cycle from arcs, connecting each to the next one with a line
+///
+/// All arcs have the same radius. Each arc is defined by the offset of its
+/// center from `center`, and the angles at which it starts and ends.
+fn arcs_connected_by_lines<const N: usize>(
+    center: Point<2>,
+    radius: Scalar,
+    arcs: [([Scalar; 2], [Scalar; 2]); N],
+    surface: Handle<Surface>,
+    core: &mut Core,
+) -> Cycle {
+    let arcs = arcs.map(|(offset, angles)| {
+        let path = SurfacePath::circle_from_center_and_radius(
+            center + Vector::from(offset),
+            radius,
+        );
+        let boundary = angles.map(|angle| Point::from([angle]));
+
+        let half_edge = HalfEdge::unjoined(core).insert(core);
+
+        core.layers.geometry.define_curve(
+            half_edge.curve().clone(),
+            surface.clone(),
+            LocalCurveGeom { path },
+        );
+        core.layers.geometry.define_half_edge(
+            half_edge.clone(),
+            HalfEdgeGeom {
+                path,
+                boundary: boundary.into(),
+            },
+        );
+
+        let [start, end] =
+            boundary.map(|point| path.point_from_path_coords(point));
+
+        (half_edge, [start, end])
+    });
+
+    let mut half_edges = Vec::new();
+    for ((arc, [_, end]), (_, [next_start, _])) in
+        arcs.into_iter().circular_tuple_windows()
+    {
+        let line = HalfEdge::line_segment(
+            [end, next_start],
+            None,
+            surface.clone(),
+            core,
+        );
+
+        half_edges.extend([arc, line]);
+    }
+
+    Cycle::new(half_edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{build::BuildFace, insert::Insert},
+        topology::Face,
+        Core,
+    };
+
+    #[test]
+    fn regular_polygon_slot_and_rounded_rectangle() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        let regular_polygon =
+            Face::regular_polygon(surface.clone(), [1., 2.], 3., 6, &mut core)
+                .insert(&mut core);
+        let slot = Face::slot(surface.clone(), [0., 0.], 5., 2., &mut core)
+            .insert(&mut core);
+        let rounded_rectangle =
+            Face::rounded_rectangle(surface, [0., 0.], [4., 3.], 1., &mut core)
+                .insert(&mut core);
+
+        for (face, num_half_edges) in
+            [(regular_polygon, 6), (slot, 4), (rounded_rectangle, 8)]
+        {
+            let exterior = face.region().exterior();
+
+            assert_eq!(exterior.half_edges().len(), num_half_edges);
+            assert!(exterior.winding(&core.layers.geometry).is_ccw());
+        }
+    }
+}
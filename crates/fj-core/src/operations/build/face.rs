@@ -1,11 +1,11 @@
 use std::{array, borrow::Borrow};
 
 use fj_interop::ext::ArrayExt;
-use fj_math::{Point, Scalar};
+use fj_math::{Point, Scalar, Vector};
 
 use crate::{
     operations::{
-        build::{BuildCycle, BuildRegion, BuildSurface},
+        build::{BuildCycle, BuildRegion, BuildSurface, SpurGear},
         insert::{Insert, IsInserted, IsInsertedNo},
     },
     storage::Handle,
@@ -86,6 +86,77 @@ pub trait BuildFace {
             Region::polygon(points, surface.clone(), core).insert(core);
         Face::new(surface, region)
     }
+
+    /// Build a rectangle with rounded corners
+    ///
+    /// See [`BuildCycle::rounded_rectangle`] for more information.
+    fn rounded_rectangle(
+        surface: Handle<Surface>,
+        center: impl Into<Point<2>>,
+        size: impl Into<Vector<2>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Face {
+        let region = Region::rounded_rectangle(
+            center,
+            size,
+            radius,
+            surface.clone(),
+            core,
+        )
+        .insert(core);
+        Face::new(surface, region)
+    }
+
+    /// Build a regular polygon
+    ///
+    /// See [`BuildCycle::regular_polygon`] for more information.
+    fn regular_polygon(
+        surface: Handle<Surface>,
+        center: impl Into<Point<2>>,
+        radius: impl Into<Scalar>,
+        num_sides: usize,
+        core: &mut Core,
+    ) -> Face {
+        let region = Region::regular_polygon(
+            center,
+            radius,
+            num_sides,
+            surface.clone(),
+            core,
+        )
+        .insert(core);
+        Face::new(surface, region)
+    }
+
+    /// Build a slot
+    ///
+    /// See [`BuildCycle::slot`] for more information.
+    fn slot(
+        surface: Handle<Surface>,
+        center: impl Into<Point<2>>,
+        length: impl Into<Scalar>,
+        width: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Face {
+        let region = Region::slot(center, length, width, surface.clone(), core)
+            .insert(core);
+        Face::new(surface, region)
+    }
+
+    /// Build an involute spur gear
+    ///
+    /// See [`BuildCycle::spur_gear`] for more information.
+    fn spur_gear(
+        surface: Handle<Surface>,
+        center: impl Into<Point<2>>,
+        gear: SpurGear,
+        core: &mut Core,
+    ) -> Face {
+        let region =
+            Region::spur_gear(center, gear, surface.clone(), core).insert(core);
+        Face::new(surface, region)
+    }
 }
 
 impl BuildFace for Face {}
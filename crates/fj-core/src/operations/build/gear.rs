@@ -0,0 +1,199 @@
+use fj_math::{Point, Scalar, Vector};
+
+/// The parameters of an involute spur gear
+///
+/// Used by [`BuildCycle::spur_gear`] and the methods that build on it.
+///
+/// The gear follows the standard proportions: The addendum (the height of the
+/// teeth above the pitch circle) is equal to the module, the dedendum (their
+/// depth below it) is 1.25 times the module.
+///
+/// [`BuildCycle::spur_gear`]: super::BuildCycle::spur_gear
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SpurGear {
+    /// The module of the gear
+    ///
+    /// This is the pitch diameter divided by the number of teeth. Gears must
+    /// have the same module and pressure angle, to mesh.
+    pub module: Scalar,
+
+    /// The number of teeth
+    pub num_teeth: usize,
+
+    /// The pressure angle
+    pub pressure_angle_rad: Scalar,
+}
+
+impl SpurGear {
+    /// The most common pressure angle, 20 degrees
+    pub const DEFAULT_PRESSURE_ANGLE_RAD: f64 = std::f64::consts::PI / 9.;
+
+    /// The number of segments that approximate each flank of a tooth
+    pub const SEGMENTS_PER_FLANK: usize = 8;
+
+    /// Create a spur gear with the default pressure angle
+    pub fn new(module: impl Into<Scalar>, num_teeth: usize) -> Self {
+        Self {
+            module: module.into(),
+            num_teeth,
+            pressure_angle_rad: Scalar::from(Self::DEFAULT_PRESSURE_ANGLE_RAD),
+        }
+    }
+
+    /// Use the provided pressure angle
+    #[must_use]
+    pub fn with_pressure_angle(
+        mut self,
+        pressure_angle_rad: impl Into<Scalar>,
+    ) -> Self {
+        self.pressure_angle_rad = pressure_angle_rad.into();
+        self
+    }
+
+    /// The radius of the pitch circle
+    pub fn pitch_radius(&self) -> Scalar {
+        self.module * Scalar::from_u64(self.num_teeth as u64) / 2.
+    }
+
+    /// The radius of the base circle, from which the flanks are unwound
+    pub fn base_radius(&self) -> Scalar {
+        self.pitch_radius() * self.pressure_angle_rad.cos()
+    }
+
+    /// The radius of the tip circle, at the top of the teeth
+    pub fn tip_radius(&self) -> Scalar {
+        self.pitch_radius() + self.module
+    }
+
+    /// The radius of the root circle, at the bottom of the gaps between teeth
+    pub fn root_radius(&self) -> Scalar {
+        self.pitch_radius() - self.module * 1.25
+    }
+
+    /// Compute the outline of the gear, around the provided center
+    ///
+    /// Returns a polygon that approximates the outline, wound
+    /// counter-clockwise. The first tooth is centered on the positive u-axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the module is not positive, if the pressure angle is not
+    /// between zero and 90 degrees, or if the teeth are so few that their tips
+    /// would be pointed.
+    pub fn outline(&self, center: impl Into<Point<2>>) -> Vec<Point<2>> {
+        assert!(
+            self.module > Scalar::ZERO,
+            "Module of spur gear must be positive"
+        );
+        assert!(
+            self.pressure_angle_rad > Scalar::ZERO
+                && self.pressure_angle_rad < Scalar::PI / 2.,
+            "Pressure angle of spur gear must be between 0 and 90 degrees"
+        );
+
+        let center = center.into();
+        let num_teeth = Scalar::from_u64(self.num_teeth as u64);
+
+        // The half of a tooth's thickness, measured as an angle, at the
+        // provided radius. At the pitch circle, teeth and gaps are equally
+        // thick. Further out, the involute flanks converge.
+        let half_thickness = |radius: Scalar| {
+            Scalar::PI / (num_teeth * 2.) + involute(self.pressure_angle_rad)
+                - involute((self.base_radius() / radius).acos())
+        };
+
+        assert!(
+            half_thickness(self.tip_radius()) > Scalar::ZERO,
+            "Spur gear has too few teeth; their tips would be pointed"
+        );
+
+        // The involute only exists outside of the base circle. If the root
+        // circle is smaller, the flank continues radially down to it.
+        let flank_start = self.base_radius().max(self.root_radius());
+        let flank = (0..=Self::SEGMENTS_PER_FLANK)
+            .map(|i| {
+                let t = Scalar::from_u64(i as u64)
+                    / Scalar::from_u64(Self::SEGMENTS_PER_FLANK as u64);
+                let radius =
+                    flank_start + (self.tip_radius() - flank_start) * t;
+                (radius, half_thickness(radius))
+            })
+            .collect::<Vec<_>>();
+
+        let mut points = Vec::new();
+        for i in 0..self.num_teeth {
+            let angle = Scalar::TAU / num_teeth * Scalar::from_u64(i as u64);
+            let point = |radius: Scalar, angle: Scalar| {
+                let (sin, cos) = angle.sin_cos();
+                center + Vector::from([cos, sin]) * radius
+            };
+
+            let (_, root_half_thickness) = flank[0];
+            if self.root_radius() < flank_start {
+                points.push(point(
+                    self.root_radius(),
+                    angle - root_half_thickness,
+                ));
+            }
+            points.extend(flank.iter().map(|&(radius, half_thickness)| {
+                point(radius, angle - half_thickness)
+            }));
+            points.extend(flank.iter().rev().map(
+                |&(radius, half_thickness)| {
+                    point(radius, angle + half_thickness)
+                },
+            ));
+            if self.root_radius() < flank_start {
+                points.push(point(
+                    self.root_radius(),
+                    angle + root_half_thickness,
+                ));
+            }
+        }
+
+        points
+    }
+}
+
+/// The involute function, the polar angle of a point on an involute
+fn involute(angle: Scalar) -> Scalar {
+    let (sin, cos) = angle.sin_cos();
+    sin / cos - angle
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{
+            build::{BuildSketch, SpurGear},
+            insert::Insert,
+        },
+        topology::Sketch,
+        Core,
+    };
+
+    #[test]
+    fn spur_gear() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // With few teeth, the root circle is smaller than the base circle, and
+        // each flank gets an additional radial segment.
+        for (num_teeth, half_edges_per_tooth) in [(12, 20), (50, 18)] {
+            let gear = SpurGear::new(2., num_teeth);
+            let surface = core.layers.topology.surfaces.xy_plane();
+
+            let sketch = Sketch::spur_gear(surface, [0., 0.], gear, &mut core);
+            let exterior = sketch.regions().first().exterior();
+            assert_eq!(
+                exterior.half_edges().len(),
+                num_teeth * half_edges_per_tooth
+            );
+            assert!(exterior.winding(&core.layers.geometry).is_ccw());
+
+            let _ = sketch.insert(&mut core);
+            core.layers.validation.take_errors()?;
+        }
+
+        Ok(())
+    }
+}
@@ -31,8 +31,7 @@ pub trait BuildHalfEdge {
         start_vertex: Handle<Vertex>,
         core: &mut Core,
     ) -> Handle<HalfEdge> {
-        let mut geometry = *core.layers.geometry.of_half_edge(sibling);
-        geometry.boundary = geometry.boundary.reverse();
+        let geometry = core.layers.geometry.of_half_edge(sibling).reverse();
 
         HalfEdge::new(sibling.curve().clone(), start_vertex)
             .insert(core)
@@ -20,6 +20,7 @@
 mod curve;
 mod cycle;
 mod face;
+mod gear;
 mod half_edge;
 mod region;
 mod shell;
@@ -31,9 +32,10 @@ pub use self::{
     curve::BuildCurve,
     cycle::BuildCycle,
     face::{BuildFace, Polygon},
+    gear::SpurGear,
     half_edge::BuildHalfEdge,
     region::BuildRegion,
-    shell::{BuildShell, TetrahedronShell},
+    shell::{BuildShell, PolyhedronError, TetrahedronShell},
     sketch::BuildSketch,
     solid::{BuildSolid, Tetrahedron},
     surface::BuildSurface,
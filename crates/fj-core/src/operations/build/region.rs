@@ -1,7 +1,11 @@
-use fj_math::{Point, Scalar};
+use fj_math::{Point, Scalar, Vector};
 
 use crate::{
-    operations::{build::BuildCycle, insert::Insert},
+    operations::{
+        build::{BuildCycle, SpurGear},
+        insert::Insert,
+        reverse::Reverse,
+    },
     storage::Handle,
     topology::{Cycle, Region, Surface},
     Core,
@@ -33,6 +37,89 @@ pub trait BuildRegion {
         Region::new(exterior, [])
     }
 
+    /// Build an annulus (a circle with a concentric circular hole)
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the inner radius is not positive, or not smaller than the
+    /// outer radius.
+    fn annulus(
+        center: impl Into<Point<2>>,
+        outer_radius: impl Into<Scalar>,
+        inner_radius: impl Into<Scalar>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Region {
+        let center = center.into();
+        let outer_radius = outer_radius.into();
+        let inner_radius = inner_radius.into();
+
+        assert!(
+            inner_radius > Scalar::ZERO,
+            "Inner radius of annulus must be positive"
+        );
+        assert!(
+            inner_radius < outer_radius,
+            "Inner radius of annulus must be smaller than outer radius"
+        );
+
+        let exterior =
+            Cycle::circle(center, outer_radius, surface.clone(), core)
+                .insert(core);
+        let interior = Cycle::circle(center, inner_radius, surface, core)
+            .reverse(core)
+            .insert(core);
+
+        Region::new(exterior, [interior])
+    }
+
+    /// Build a rectangle with rounded corners
+    ///
+    /// See [`BuildCycle::rounded_rectangle`] for more information.
+    fn rounded_rectangle(
+        center: impl Into<Point<2>>,
+        size: impl Into<Vector<2>>,
+        radius: impl Into<Scalar>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Region {
+        let exterior =
+            Cycle::rounded_rectangle(center, size, radius, surface, core)
+                .insert(core);
+        Region::new(exterior, [])
+    }
+
+    /// Build a regular polygon
+    ///
+    /// See [`BuildCycle::regular_polygon`] for more information.
+    fn regular_polygon(
+        center: impl Into<Point<2>>,
+        radius: impl Into<Scalar>,
+        num_sides: usize,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Region {
+        let exterior =
+            Cycle::regular_polygon(center, radius, num_sides, surface, core)
+                .insert(core);
+        Region::new(exterior, [])
+    }
+
+    /// Build a slot
+    ///
+    /// See [`BuildCycle::slot`] for more information.
+    fn slot(
+        center: impl Into<Point<2>>,
+        length: impl Into<Scalar>,
+        width: impl Into<Scalar>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Region {
+        let exterior =
+            Cycle::slot(center, length, width, surface, core).insert(core);
+        Region::new(exterior, [])
+    }
+
     /// Build a polygon
     fn polygon<P, Ps>(
         points: Ps,
@@ -47,6 +134,20 @@ pub trait BuildRegion {
         let exterior = Cycle::polygon(points, surface, core).insert(core);
         Region::new(exterior, [])
     }
+
+    /// Build an involute spur gear
+    ///
+    /// See [`BuildCycle::spur_gear`] for more information.
+    fn spur_gear(
+        center: impl Into<Point<2>>,
+        gear: SpurGear,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Region {
+        let exterior =
+            Cycle::spur_gear(center, gear, surface, core).insert(core);
+        Region::new(exterior, [])
+    }
 }
 
 impl BuildRegion for Region {}
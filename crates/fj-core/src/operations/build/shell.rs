@@ -1,12 +1,15 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use fj_interop::ext::ArrayExt;
-use fj_math::Point;
+use fj_interop::Mesh;
+use fj_math::{predicates, Aabb, Point, Scalar, Tolerances, Vector};
 
 use crate::{
+    algorithms::{
+        fit_surface::boundary_loops, polygonize::Polygonize, sdf::VoxelGrid,
+    },
     geometry::{CurveBoundary, HalfEdgeGeom},
     operations::{
-        build::{BuildFace, BuildHalfEdge, BuildSurface, Polygon},
+        build::{BuildCycle, BuildFace, BuildHalfEdge, BuildSurface, Polygon},
         geometry::{UpdateCurveGeometry, UpdateHalfEdgeGeometry},
         insert::{Insert, IsInserted, IsInsertedNo, IsInsertedYes},
         join::JoinCycle,
@@ -15,7 +18,8 @@ use crate::{
             UpdateCycle, UpdateFace, UpdateHalfEdge, UpdateRegion, UpdateShell,
         },
     },
-    topology::{Curve, Face, HalfEdge, Shell, Surface, Vertex},
+    storage::Handle,
+    topology::{Curve, Cycle, Face, HalfEdge, Shell, Surface, Vertex},
     Core,
 };
 
@@ -35,6 +39,48 @@ pub trait BuildShell {
         vertices: impl IntoIterator<Item = impl Into<Point<3>>>,
         indices: impl IntoIterator<Item = [usize; 3]>,
         core: &mut Core,
+    ) -> Shell {
+        Shell::from_vertices_and_polygons(vertices, indices, core)
+    }
+
+    /// Build a polyhedron by specifying its vertices and polygonal faces
+    ///
+    /// Each face is specified by the indices of its vertices, in
+    /// counter-clockwise order, when viewed from outside of the polyhedron.
+    /// The vertices of each face must be on one plane, and the first two of
+    /// them must not be the same.
+    ///
+    /// See [`BuildShell::from_vertices_and_indices`], for a polyhedron whose
+    /// faces are all triangles.
+    fn from_vertices_and_polygons(
+        vertices: impl IntoIterator<Item = impl Into<Point<3>>>,
+        polygons: impl IntoIterator<Item = impl IntoIterator<Item = usize>>,
+        core: &mut Core,
+    ) -> Shell {
+        Shell::from_vertices_and_polygons_with_holes(
+            vertices,
+            polygons
+                .into_iter()
+                .map(|exterior| (exterior, Vec::<Vec<usize>>::new())),
+            core,
+        )
+    }
+
+    /// Build a polyhedron whose polygonal faces can have holes
+    ///
+    /// Like [`BuildShell::from_vertices_and_polygons`], but each face is
+    /// specified by its exterior polygon, and any number of interior polygons.
+    /// Interior polygons are in clockwise order, when viewed from outside of
+    /// the polyhedron. They must be on the same plane as the exterior.
+    fn from_vertices_and_polygons_with_holes(
+        vertices: impl IntoIterator<Item = impl Into<Point<3>>>,
+        faces: impl IntoIterator<
+            Item = (
+                impl IntoIterator<Item = usize>,
+                impl IntoIterator<Item = impl IntoIterator<Item = usize>>,
+            ),
+        >,
+        core: &mut Core,
     ) -> Shell {
         let vertices = vertices
             .into_iter()
@@ -49,89 +95,64 @@ pub trait BuildShell {
 
         let mut curves = BTreeMap::new();
 
-        let faces = indices
+        let faces = faces
             .into_iter()
-            .map(|indices| {
-                let [(a, a_pos), (b, b_pos), (c, c_pos)] = indices
-                    .map(|index| vertices.get(&index).expect("Invalid index"));
+            .map(|(exterior, interiors)| {
+                let vertex =
+                    |index| vertices.get(&index).expect("Invalid index");
 
-                let (surface, _) = Surface::plane_from_points(
-                    [a_pos, b_pos, c_pos].map(Clone::clone),
+                let exterior =
+                    exterior.into_iter().map(vertex).collect::<Vec<_>>();
+                let (surface, positions) = plane_through_polygon(
+                    exterior.iter().map(|(_, position)| *position),
+                    core,
+                );
+                let exterior = polygon_half_edges(
+                    &exterior,
+                    &positions,
+                    &surface,
+                    &mut curves,
                     core,
                 );
 
-                let curves_and_boundaries =
-                    [[a, b], [b, c], [c, a]].map(|vertices| {
-                        let vertices = vertices.map(Clone::clone);
-                        let vertices = CurveBoundary::<Vertex>::from(vertices);
-
-                        curves
-                            .get(&vertices.clone().reverse())
-                            .cloned()
-                            .unwrap_or_else(|| {
-                                let curve = Curve::new().insert(core);
-                                let boundary = CurveBoundary::default();
-
-                                curves.insert(
-                                    vertices,
-                                    (curve.clone(), boundary),
-                                );
-
-                                (curve, boundary.reverse())
+                let interiors = interiors
+                    .into_iter()
+                    .map(|interior| {
+                        let interior = interior
+                            .into_iter()
+                            .map(vertex)
+                            .collect::<Vec<_>>();
+                        let positions = interior
+                            .iter()
+                            .map(|(_, position)| {
+                                core.layers
+                                    .geometry
+                                    .of_surface(&surface)
+                                    .project_global_point(*position)
                             })
-                    });
-
-                let half_edges = {
-                    let vertices = [a, b, c].map(Clone::clone);
-                    let [a, b, c] = [[0., 0.], [1., 0.], [0., 1.]];
-                    vertices
-                        .zip_ext([[a, b], [b, c], [c, a]])
-                        .zip_ext(curves_and_boundaries)
-                        .map(|((vertex, positions), (curve, boundary))| {
-                            let boundary = boundary.reverse();
-
-                            let curve = curve.make_line_on_surface(
-                                positions,
-                                Some(boundary),
-                                surface.clone(),
-                                &mut core.layers.geometry,
-                            );
-
-                            HalfEdge::unjoined(core)
-                                .update_start_vertex(|_, _| vertex, core)
-                                .update_curve(|_, _| curve.clone(), core)
-                                .insert(core)
-                                .set_geometry(
-                                    HalfEdgeGeom {
-                                        path: core
-                                            .layers
-                                            .geometry
-                                            .of_curve(&curve)
-                                            .expect(
-                                                "Curve geometry was just \
-                                                defined in same function",
-                                            )
-                                            .local_on(&surface)
-                                            .expect(
-                                                "Curve geometry was just \
-                                                defined in same function",
-                                            )
-                                            .path,
-                                        boundary,
-                                    },
-                                    &mut core.layers.geometry,
-                                )
-                        })
-                };
+                            .collect::<Vec<_>>();
+                        let half_edges = polygon_half_edges(
+                            &interior,
+                            &positions,
+                            &surface,
+                            &mut curves,
+                            core,
+                        );
+
+                        Cycle::empty().add_half_edges(half_edges, core)
+                    })
+                    .collect::<Vec<_>>();
 
                 Face::unbound(surface, core).update_region(
                     |region, core| {
-                        region.update_exterior(
-                            |cycle, core| {
-                                cycle.add_half_edges(half_edges, core)
-                            },
-                            core,
-                        )
+                        region
+                            .update_exterior(
+                                |cycle, core| {
+                                    cycle.add_half_edges(exterior, core)
+                                },
+                                core,
+                            )
+                            .add_interiors(interiors, core)
                     },
                     core,
                 )
@@ -141,6 +162,194 @@ pub trait BuildShell {
         Shell::empty().add_faces(faces, core)
     }
 
+    /// Build a polyhedron from an indexed description of its faces
+    ///
+    /// Like [`BuildShell::from_vertices_and_polygons`], but meant for input
+    /// that might be invalid, like geometry imported from a file. Before
+    /// anything is built, the description is checked:
+    ///
+    /// - Each index must refer to one of the vertices.
+    /// - Each face must have at least 3 distinct vertices, which must not all
+    ///   be on one line, but must all be on one plane, within the tolerances
+    ///   of the core instance (see [`Core::tolerances`]).
+    /// - The polyhedron must be closed. Each edge must be shared by exactly two
+    ///   faces, which traverse it in opposite directions.
+    ///
+    /// If all checks pass, neighboring faces share their edges and vertices.
+    fn from_vertices_and_faces(
+        vertices: &[Point<3>],
+        faces: &[Vec<usize>],
+        core: &mut Core,
+    ) -> Result<Shell, PolyhedronError> {
+        check_polyhedron(vertices, faces, &core.tolerances())?;
+
+        Ok(Shell::from_vertices_and_polygons(
+            vertices.iter().copied(),
+            faces.iter().map(|indices| indices.iter().copied()),
+            core,
+        ))
+    }
+
+    /// Build a polyhedron that approximates an implicit shape
+    ///
+    /// `sdf` defines the shape. It must return negative values inside of the
+    /// shape, and positive values outside of it, like a signed distance field.
+    /// It is sampled on a grid that spans `aabb`, with `resolution` samples
+    /// along each axis. The resulting polyhedron has one triangular face per
+    /// triangle of the extracted surface.
+    ///
+    /// The shape should be contained within `aabb`. Otherwise, the resulting
+    /// shell is not closed.
+    ///
+    /// See [`Polygonize`] for more information.
+    ///
+    /// [`Polygonize`]: crate::algorithms::polygonize::Polygonize
+    fn from_implicit(
+        sdf: impl Fn(Point<3>) -> Scalar,
+        aabb: Aabb<3>,
+        resolution: [usize; 3],
+        core: &mut Core,
+    ) -> Shell {
+        let mesh = VoxelGrid::from_fn(aabb, resolution, sdf).polygonize();
+        Shell::from_mesh(&mesh, core)
+    }
+
+    /// Build a polyhedron from the triangles of a mesh
+    ///
+    /// Convenience wrapper around [`BuildShell::from_vertices_and_indices`].
+    /// The mesh is expected to be closed, with vertices shared between
+    /// neighboring triangles.
+    fn from_mesh(mesh: &Mesh<Point<3>>, core: &mut Core) -> Shell {
+        Shell::from_vertices_and_indices(
+            mesh.vertices(),
+            mesh.triangle_indices()
+                .map(|indices| indices.map(|index| index as usize)),
+            core,
+        )
+    }
+
+    /// Build a polyhedron from a mesh, merging coplanar triangles into faces
+    ///
+    /// Like [`BuildShell::from_mesh`], but neighboring triangles that are on
+    /// the same plane become a single face. If the triangles of a face
+    /// surround those of other faces, like around a hole, those become
+    /// interior cycles of the face.
+    ///
+    /// The triangles are checked like the faces passed to
+    /// [`BuildShell::from_vertices_and_faces`]. Triangles are merged, if they
+    /// face the same way, and all of their vertices are within the linear
+    /// tolerance (see [`Core::tolerances`]) of the plane of a neighboring
+    /// triangle.
+    fn from_mesh_with_planar_faces(
+        mesh: &Mesh<Point<3>>,
+        core: &mut Core,
+    ) -> Result<Shell, PolyhedronError> {
+        let tolerances = core.tolerances();
+
+        let vertices = mesh.vertices().collect::<Vec<_>>();
+        let triangles = mesh
+            .triangle_indices()
+            .map(|indices| indices.map(|index| index as usize))
+            .collect::<Vec<_>>();
+        check_polyhedron(
+            &vertices,
+            &triangles
+                .iter()
+                .map(|triangle| triangle.to_vec())
+                .collect::<Vec<_>>(),
+            &tolerances,
+        )?;
+
+        let triangle_by_edge = triangles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &[a, b, c])| {
+                [(a, b), (b, c), (c, a)].map(|edge| (edge, i))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        // Grow each group of coplanar triangles from a seed triangle. All
+        // triangles of the group are compared to the plane of the seed, so
+        // slightly curved surfaces don't end up in one group.
+        let mut group_of_triangle = vec![None; triangles.len()];
+        let mut groups = Vec::new();
+
+        for seed in 0..triangles.len() {
+            if group_of_triangle[seed].is_some() {
+                continue;
+            }
+
+            let [a, b, c] = triangles[seed].map(|index| vertices[index]);
+            let normal = (b - a).cross(&(c - a)).normalize();
+            let is_coplanar = |triangle: [usize; 3]| {
+                let [pa, pb, pc] = triangle.map(|index| vertices[index]);
+                (pb - pa).cross(&(pc - pa)).dot(&normal) > Scalar::ZERO
+                    && [pa, pb, pc].iter().all(|&point| {
+                        tolerances.is_zero_length((point - a).dot(&normal))
+                    })
+            };
+
+            let group = groups.len();
+            let mut members = vec![seed];
+            let mut queue = vec![seed];
+            group_of_triangle[seed] = Some(group);
+
+            while let Some(current) = queue.pop() {
+                let [a, b, c] = triangles[current];
+
+                for (from, to) in [(a, b), (b, c), (c, a)] {
+                    let neighbor = triangle_by_edge[&(to, from)];
+
+                    if group_of_triangle[neighbor].is_none()
+                        && is_coplanar(triangles[neighbor])
+                    {
+                        group_of_triangle[neighbor] = Some(group);
+                        members.push(neighbor);
+                        queue.push(neighbor);
+                    }
+                }
+            }
+
+            groups.push((normal, members));
+        }
+
+        let mut faces = Vec::new();
+        for (normal, members) in groups {
+            let invalid = PolyhedronError::InvalidFacet {
+                triangle: members[0],
+            };
+
+            let loops = boundary_loops(
+                &members
+                    .iter()
+                    .map(|&triangle| triangles[triangle])
+                    .collect::<Vec<_>>(),
+            )
+            .ok_or(invalid.clone())?;
+
+            // The exterior is counter-clockwise, when viewed from outside,
+            // and the interiors are clockwise.
+            let (exteriors, interiors): (Vec<_>, Vec<_>) =
+                loops.into_iter().partition(|indices| {
+                    let area = indices
+                        .iter()
+                        .zip(indices.iter().cycle().skip(1))
+                        .map(|(&a, &b)| {
+                            vertices[a].coords.cross(&vertices[b].coords)
+                        })
+                        .fold(Vector::from([0., 0., 0.]), |sum, v| sum + v);
+                    area.dot(&normal) > Scalar::ZERO
+                });
+            let [exterior] = exteriors.try_into().map_err(|_| invalid)?;
+
+            faces.push((exterior, interiors));
+        }
+
+        Ok(Shell::from_vertices_and_polygons_with_holes(
+            vertices, faces, core,
+        ))
+    }
+
     /// Build a tetrahedron from the provided points
     ///
     /// Accepts 4 points, naturally. For the purposes of the following
@@ -322,6 +531,229 @@ pub trait BuildShell {
 
 impl BuildShell for Shell {}
 
+/// Check an indexed description of a polyhedron
+///
+/// See [`BuildShell::from_vertices_and_faces`].
+fn check_polyhedron(
+    vertices: &[Point<3>],
+    faces: &[Vec<usize>],
+    tolerances: &Tolerances,
+) -> Result<(), PolyhedronError> {
+    let mut edges = BTreeSet::new();
+
+    for (face, indices) in faces.iter().enumerate() {
+        if let Some(&index) =
+            indices.iter().find(|&&index| index >= vertices.len())
+        {
+            return Err(PolyhedronError::InvalidIndex { face, index });
+        }
+
+        let distinct = indices.iter().collect::<BTreeSet<_>>();
+        if distinct.len() < 3 || distinct.len() != indices.len() {
+            return Err(PolyhedronError::DegenerateFace { face });
+        }
+
+        let points = indices
+            .iter()
+            .map(|&index| vertices[index])
+            .collect::<Vec<_>>();
+        let [a, b] = [points[0], points[1]];
+        let Some(c) = points[2..]
+            .iter()
+            .find(|&&c| !predicates::are_collinear([a, b, c]))
+        else {
+            return Err(PolyhedronError::DegenerateFace { face });
+        };
+        let normal = (b - a).cross(&(*c - a)).normalize();
+        if points
+            .iter()
+            .any(|&point| !tolerances.is_zero_length((point - a).dot(&normal)))
+        {
+            return Err(PolyhedronError::NonPlanarFace { face });
+        }
+
+        for (&from, &to) in indices.iter().zip(indices.iter().cycle().skip(1)) {
+            if !edges.insert((from, to)) {
+                return Err(PolyhedronError::NonManifoldEdge { from, to });
+            }
+        }
+    }
+
+    if let Some(&(from, to)) = edges
+        .iter()
+        .find(|(from, to)| !edges.contains(&(*to, *from)))
+    {
+        return Err(PolyhedronError::OpenEdge { from, to });
+    }
+
+    Ok(())
+}
+
+/// Build the half-edges of a polygon on a plane
+///
+/// `curves` tracks the curves of the edges that have already been built. If
+/// the edge of a half-edge is already there, the half-edge becomes its sibling.
+fn polygon_half_edges(
+    polygon: &[&(Handle<Vertex>, Point<3>)],
+    positions: &[Point<2>],
+    surface: &Handle<Surface>,
+    curves: &mut BTreeMap<
+        CurveBoundary<Vertex>,
+        (Handle<Curve>, CurveBoundary<Point<1>>),
+    >,
+    core: &mut Core,
+) -> Vec<Handle<HalfEdge>> {
+    polygon
+        .iter()
+        .zip(positions)
+        .zip(polygon.iter().zip(positions).cycle().skip(1))
+        .map(|(((a, _), a_pos), ((b, _), b_pos))| {
+            let vertices =
+                CurveBoundary::<Vertex>::from([a, b].map(Clone::clone));
+
+            let (curve, boundary) = curves
+                .get(&vertices.clone().reverse())
+                .cloned()
+                .unwrap_or_else(|| {
+                    let curve = Curve::new().insert(core);
+                    let boundary = CurveBoundary::default();
+
+                    curves.insert(vertices, (curve.clone(), boundary));
+
+                    (curve, boundary.reverse())
+                });
+            let boundary = boundary.reverse();
+
+            let curve = curve.make_line_on_surface(
+                [*a_pos, *b_pos],
+                Some(boundary),
+                surface.clone(),
+                &mut core.layers.geometry,
+            );
+
+            HalfEdge::unjoined(core)
+                .update_start_vertex(|_, _| a.clone(), core)
+                .update_curve(|_, _| curve.clone(), core)
+                .insert(core)
+                .set_geometry(
+                    HalfEdgeGeom {
+                        path: core
+                            .layers
+                            .geometry
+                            .of_curve(&curve)
+                            .expect(
+                                "Curve geometry was just defined in same \
+                                function",
+                            )
+                            .local_on(surface)
+                            .expect(
+                                "Curve geometry was just defined in same \
+                                function",
+                            )
+                            .path,
+                        boundary,
+                    },
+                    &mut core.layers.geometry,
+                )
+        })
+        .collect()
+}
+
+/// Build a plane through the points of a polygon
+///
+/// Returns the plane, and the positions of the points on it. The plane is
+/// built from the first two points, and the first one after those that is not
+/// collinear with them.
+fn plane_through_polygon(
+    points: impl IntoIterator<Item = Point<3>>,
+    core: &mut Core,
+) -> (Handle<Surface>, Vec<Point<2>>) {
+    let points = points.into_iter().collect::<Vec<_>>();
+
+    let [a, b] = [points[0], points[1]];
+    let c = (2..points.len())
+        .find(|&i| !predicates::are_collinear([a, b, points[i]]))
+        .expect("Polygon must not be degenerate");
+
+    let (surface, [a_surface, b_surface, c_surface]) =
+        Surface::plane_from_points([a, b, points[c]], core);
+
+    let surface_geom = core.layers.geometry.of_surface(&surface);
+    let positions = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| match i {
+            0 => a_surface,
+            1 => b_surface,
+            i if i == c => c_surface,
+            _ => surface_geom.project_global_point(*point),
+        })
+        .collect();
+
+    (surface, positions)
+}
+
+/// Error building a polyhedron from vertices and faces
+///
+/// Returned by [`BuildShell::from_vertices_and_faces`]. Vertices are referred to
+/// by their index, faces by their position in the list of faces.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum PolyhedronError {
+    /// A face refers to a vertex that doesn't exist
+    #[error("Face {face} refers to vertex {index}, which doesn't exist")]
+    InvalidIndex {
+        /// The face
+        face: usize,
+
+        /// The invalid index
+        index: usize,
+    },
+
+    /// A face has too few distinct vertices, or they are all on one line
+    #[error("Face {face} is degenerate")]
+    DegenerateFace {
+        /// The face
+        face: usize,
+    },
+
+    /// The vertices of a face are not all on one plane
+    #[error("Vertices of face {face} are not on one plane")]
+    NonPlanarFace {
+        /// The face
+        face: usize,
+    },
+
+    /// An edge is traversed in the same direction by more than one face
+    #[error("Edge from vertex {from} to {to} is used by more than one face")]
+    NonManifoldEdge {
+        /// The vertex at which the edge starts
+        from: usize,
+
+        /// The vertex at which the edge ends
+        to: usize,
+    },
+
+    /// An edge is not traversed in the opposite direction by another face
+    #[error("Edge from vertex {from} to {to} has no neighboring face")]
+    OpenEdge {
+        /// The vertex at which the edge starts
+        from: usize,
+
+        /// The vertex at which the edge ends
+        to: usize,
+    },
+
+    /// Coplanar triangles of a mesh don't form a valid face
+    ///
+    /// This is the case, if they touch each other only at a vertex, or if they
+    /// don't have exactly one outer boundary.
+    #[error("Coplanar triangles around triangle {triangle} don't form a face")]
+    InvalidFacet {
+        /// One of the triangles
+        triangle: usize,
+    },
+}
+
 /// A tetrahedron
 ///
 /// A tetrahedron is constructed from 4 points and has 4 faces. For the purpose
@@ -345,3 +777,159 @@ pub struct TetrahedronShell<I: IsInserted = IsInsertedNo> {
     /// The face formed by the points `c`, `b`, and `d`.
     pub cbd: Polygon<3, IsInsertedYes>,
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{Color, Mesh};
+    use fj_math::{Point, Scalar, Tolerances};
+
+    use crate::{
+        operations::insert::Insert, topology::Shell,
+        validation::ValidationConfig, Core,
+    };
+
+    use super::{BuildShell, PolyhedronError};
+
+    #[test]
+    fn triangular_prism() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let vertices = [
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [0., 1., 0.],
+            [0., 0., 1.],
+            [1., 0., 1.],
+            [0., 1., 1.],
+        ]
+        .map(Point::from);
+        let faces = [
+            vec![0, 2, 1],
+            vec![3, 4, 5],
+            vec![0, 1, 4, 3],
+            vec![1, 2, 5, 4],
+            vec![2, 0, 3, 5],
+        ];
+
+        let shell =
+            Shell::from_vertices_and_faces(&vertices, &faces, &mut core)?;
+        assert_eq!(shell.faces().len(), 5);
+
+        let _ = shell.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn configured_tolerances() {
+        // One of the top vertices of this prism is slightly out of place, so
+        // one of its side faces is not quite planar.
+        let vertices = [
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [0., 1., 0.],
+            [0., 0., 1.],
+            [1.001, 0., 1.],
+            [0., 1., 1.],
+        ]
+        .map(Point::from);
+        let faces = [
+            vec![0, 2, 1],
+            vec![3, 4, 5],
+            vec![0, 1, 4, 3],
+            vec![1, 2, 5, 4],
+            vec![2, 0, 3, 5],
+        ];
+
+        let mut core = Core::new();
+        assert!(matches!(
+            Shell::from_vertices_and_faces(&vertices, &faces, &mut core),
+            Err(PolyhedronError::NonPlanarFace { .. })
+        ));
+
+        let mut core = Core::with_validation_config(ValidationConfig {
+            tolerances: Tolerances {
+                linear: Scalar::from(0.01),
+                ..Tolerances::default()
+            },
+            ..ValidationConfig::default()
+        });
+        assert!(Shell::from_vertices_and_faces(&vertices, &faces, &mut core)
+            .is_ok());
+    }
+
+    #[test]
+    fn mesh_with_planar_faces() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A box with a square hole through it, from top to bottom.
+        let outer = [[0., 0.], [3., 0.], [3., 3.], [0., 3.]];
+        let hole = [[1., 1.], [2., 1.], [2., 2.], [1., 2.]];
+        let at = |[x, y]: [f64; 2], z: f64| Point::from([x, y, z]);
+
+        let mut mesh = Mesh::new();
+        let mut quad = |[a, b, c, d]: [Point<3>; 4]| {
+            mesh.push_triangle([a, b, c], Color::default());
+            mesh.push_triangle([a, c, d], Color::default());
+        };
+        for i in 0..4 {
+            let j = (i + 1) % 4;
+            let [o_i, o_j, h_i, h_j] = [outer[i], outer[j], hole[i], hole[j]];
+
+            quad([at(o_i, 1.), at(o_j, 1.), at(h_j, 1.), at(h_i, 1.)]);
+            quad([at(h_i, 0.), at(h_j, 0.), at(o_j, 0.), at(o_i, 0.)]);
+            quad([at(o_i, 0.), at(o_j, 0.), at(o_j, 1.), at(o_i, 1.)]);
+            quad([at(h_j, 0.), at(h_i, 0.), at(h_i, 1.), at(h_j, 1.)]);
+        }
+
+        let shell = Shell::from_mesh_with_planar_faces(&mesh, &mut core)?;
+        assert_eq!(shell.faces().len(), 10);
+        assert_eq!(
+            shell
+                .faces()
+                .iter()
+                .filter(|face| face.region().interiors().len() == 1)
+                .count(),
+            2
+        );
+
+        let _ = shell.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_descriptions() {
+        let mut core = Core::new();
+
+        let vertices = [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+            .map(Point::from);
+        let mut check = |faces: &[Vec<usize>]| {
+            Shell::from_vertices_and_faces(&vertices, faces, &mut core)
+                .map(|_| ())
+        };
+
+        assert_eq!(
+            check(&[vec![0, 2, 4]]),
+            Err(PolyhedronError::InvalidIndex { face: 0, index: 4 })
+        );
+        assert_eq!(
+            check(&[vec![0, 2, 2]]),
+            Err(PolyhedronError::DegenerateFace { face: 0 })
+        );
+        assert_eq!(
+            check(&[vec![0, 1, 2, 3]]),
+            Err(PolyhedronError::NonPlanarFace { face: 0 })
+        );
+        assert_eq!(
+            check(&[vec![0, 2, 1], vec![0, 2, 3]]),
+            Err(PolyhedronError::NonManifoldEdge { from: 0, to: 2 })
+        );
+        assert_eq!(
+            check(&[vec![0, 2, 1], vec![0, 1, 3], vec![1, 2, 3]]),
+            Err(PolyhedronError::OpenEdge { from: 0, to: 2 })
+        );
+    }
+}
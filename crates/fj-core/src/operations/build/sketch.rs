@@ -1,4 +1,14 @@
-use crate::topology::{Sketch, Topology};
+use fj_math::Point;
+
+use crate::{
+    operations::{
+        build::{BuildRegion, SpurGear},
+        insert::Insert,
+    },
+    storage::Handle,
+    topology::{Region, Sketch, Surface, Topology},
+    Core,
+};
 
 /// Build a [`Sketch`]
 ///
@@ -10,6 +20,22 @@ pub trait BuildSketch {
     fn empty(topology: &Topology) -> Sketch {
         Sketch::new(topology.surfaces.space_2d(), [])
     }
+
+    /// Create a sketch of an involute spur gear, ready to be swept
+    ///
+    /// See [`BuildCycle::spur_gear`] for more information.
+    ///
+    /// [`BuildCycle::spur_gear`]: super::BuildCycle::spur_gear
+    fn spur_gear(
+        surface: Handle<Surface>,
+        center: impl Into<Point<2>>,
+        gear: SpurGear,
+        core: &mut Core,
+    ) -> Sketch {
+        let region =
+            Region::spur_gear(center, gear, surface.clone(), core).insert(core);
+        Sketch::new(surface, [region])
+    }
 }
 
 impl BuildSketch for Sketch {}
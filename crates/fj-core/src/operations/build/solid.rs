@@ -1,12 +1,25 @@
-use fj_math::Point;
+use std::array;
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Circle, Line, Point, Scalar, Vector};
 
 use crate::{
+    geometry::{CurveBoundary, GlobalPath, HalfEdgeGeom, SurfacePath},
     operations::{
-        build::{BuildShell, TetrahedronShell},
+        build::{
+            BuildRegion, BuildShell, BuildSketch, BuildSurface,
+            TetrahedronShell,
+        },
+        geometry::{UpdateCurveGeometry, UpdateHalfEdgeGeometry},
         insert::{Insert, IsInsertedYes},
-        update::UpdateSolid,
+        sweep::SweepSketch,
+        update::{UpdateSketch, UpdateSolid},
+    },
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface,
+        Vertex,
     },
-    topology::{Shell, Solid},
     Core,
 };
 
@@ -33,10 +46,403 @@ pub trait BuildSolid {
 
         Tetrahedron { solid, shell }
     }
+
+    /// Build a box with rounded vertical edges
+    ///
+    /// The box is centered on the z-axis, with its bottom face in the xy-plane.
+    /// Its vertical edges are rounded with the provided radius.
+    ///
+    /// See [`BuildCycle::rounded_rectangle`] for more information.
+    ///
+    /// [`BuildCycle::rounded_rectangle`]: super::BuildCycle::rounded_rectangle
+    fn rounded_box(
+        size: impl Into<Vector<3>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid {
+        let [x, y, z] = size.into().components;
+
+        let region = Region::rounded_rectangle(
+            Point::origin(),
+            [x, y],
+            radius,
+            core.layers.topology.surfaces.space_2d(),
+            core,
+        );
+
+        extrude_region(region, z, core)
+    }
+
+    /// Build a cylinder
+    ///
+    /// The cylinder is centered on the z-axis, with its bottom face in the
+    /// xy-plane.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `radius` or `height` are not positive.
+    fn cylinder(
+        radius: impl Into<Scalar>,
+        height: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid {
+        let radius = radius.into();
+        let height = height.into();
+
+        assert!(radius > Scalar::ZERO, "Radius of cylinder must be positive");
+        assert!(height > Scalar::ZERO, "Height of cylinder must be positive");
+
+        let region = Region::circle(
+            Point::origin(),
+            radius,
+            core.layers.topology.surfaces.space_2d(),
+            core,
+        );
+
+        extrude_region(region, height, core)
+    }
+
+    /// Build a washer
+    ///
+    /// The washer is centered on the z-axis, with its bottom face in the
+    /// xy-plane.
+    ///
+    /// See [`BuildRegion::annulus`] for more information.
+    fn washer(
+        outer_radius: impl Into<Scalar>,
+        inner_radius: impl Into<Scalar>,
+        thickness: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid {
+        let region = Region::annulus(
+            Point::origin(),
+            outer_radius,
+            inner_radius,
+            core.layers.topology.surfaces.space_2d(),
+            core,
+        );
+
+        extrude_region(region, thickness, core)
+    }
+
+    /// Build a tube (a hollow cylinder)
+    ///
+    /// The tube is centered on the z-axis, starting in the xy-plane. Its inner
+    /// radius is the outer radius minus the wall thickness.
+    ///
+    /// See [`BuildRegion::annulus`] for more information.
+    fn tube(
+        outer_radius: impl Into<Scalar>,
+        wall_thickness: impl Into<Scalar>,
+        length: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid {
+        let outer_radius = outer_radius.into();
+        let inner_radius = outer_radius - wall_thickness.into();
+
+        let region = Region::annulus(
+            Point::origin(),
+            outer_radius,
+            inner_radius,
+            core.layers.topology.surfaces.space_2d(),
+            core,
+        );
+
+        extrude_region(region, length, core)
+    }
+
+    /// Build a cone
+    ///
+    /// The cone is centered on the z-axis, with its bottom face in the
+    /// xy-plane. If `top_radius` is zero, the cone ends in an apex. Otherwise,
+    /// it is a truncated cone with a top face.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `bottom_radius` or `height` are not positive, or if
+    /// `top_radius` is negative.
+    fn cone(
+        bottom_radius: impl Into<Scalar>,
+        top_radius: impl Into<Scalar>,
+        height: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid {
+        let bottom_radius = bottom_radius.into();
+        let top_radius = top_radius.into();
+        let height = height.into();
+
+        assert!(
+            bottom_radius > Scalar::ZERO,
+            "Bottom radius of cone must be positive"
+        );
+        assert!(
+            top_radius >= Scalar::ZERO,
+            "Top radius of cone must not be negative"
+        );
+        assert!(height > Scalar::ZERO, "Height of cone must be positive");
+
+        let surface = Surface::cone(
+            Circle::from_center_and_radius(Point::origin(), bottom_radius),
+            [Scalar::ZERO, Scalar::ZERO, height],
+            top_radius,
+            core,
+        );
+
+        let bottom = core.layers.topology.surfaces.xy_plane();
+        let top = (top_radius != Scalar::ZERO).then(|| {
+            let u = Line::from_origin_and_direction(
+                Point::from([Scalar::ZERO, Scalar::ZERO, height]),
+                Vector::unit_x(),
+            );
+            Surface::from_uv(GlobalPath::Line(u), Vector::unit_y(), core)
+        });
+
+        revolve_surface(
+            surface,
+            [Scalar::ZERO, Scalar::ONE],
+            [
+                Some((bottom, bottom_radius)),
+                top.map(|top| (top, top_radius)),
+            ],
+            core,
+        )
+    }
+
+    /// Build a sphere
+    ///
+    /// The sphere is centered on the origin. It has a single face, whose seam
+    /// runs from pole to pole, through the point on the positive x-axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `radius` is not positive.
+    fn sphere(radius: impl Into<Scalar>, core: &mut Core) -> Solid {
+        let radius = radius.into();
+
+        assert!(radius > Scalar::ZERO, "Radius of sphere must be positive");
+
+        let surface = Surface::sphere(Point::origin(), radius, core);
+
+        revolve_surface(
+            surface,
+            [-Scalar::PI / 2., Scalar::PI / 2.],
+            [None, None],
+            core,
+        )
+    }
+
+    /// Build a torus
+    ///
+    /// The torus is centered on the origin, and its axis is the z-axis. The
+    /// major radius is the radius of the circle at the center of its tube, the
+    /// minor radius that of the tube itself.
+    ///
+    /// The torus has a single face. One of its seams runs around the outside
+    /// of the torus, the other around the tube, through the point on the
+    /// positive x-axis. Both seams meet in a single vertex.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `minor_radius` is not positive, or if `major_radius` is not
+    /// larger than `minor_radius`.
+    fn torus(
+        major_radius: impl Into<Scalar>,
+        minor_radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid {
+        let major_radius = major_radius.into();
+        let minor_radius = minor_radius.into();
+
+        assert!(
+            minor_radius > Scalar::ZERO,
+            "Minor radius of torus must be positive"
+        );
+        assert!(
+            major_radius > minor_radius,
+            "Major radius of torus must be larger than its minor radius"
+        );
+
+        let surface =
+            Surface::torus(Point::origin(), major_radius, minor_radius, core);
+
+        let [curve_outside, curve_tube] =
+            array::from_fn(|_| Curve::new().insert(core));
+        let vertex = Vertex::new().insert(core);
+
+        // Both coordinates wrap around. Each pair of opposite half-edges runs
+        // along the same curve, in opposite directions.
+        let [start, end] = [Scalar::ZERO, Scalar::TAU];
+        let half_edges = [
+            (&curve_outside, [[start, start], [end, start]], [start, end]),
+            (&curve_tube, [[end, start], [end, end]], [start, end]),
+            (&curve_outside, [[end, end], [start, end]], [end, start]),
+            (&curve_tube, [[start, end], [start, start]], [end, start]),
+        ]
+        .map(|(curve, points_surface, boundary)| {
+            half_edge_on_surface(
+                curve,
+                &vertex,
+                points_surface,
+                boundary,
+                &surface,
+                core,
+            )
+        });
+
+        let face = face_from_half_edges(surface, half_edges, core);
+        let shell = Shell::new([face]).insert(core);
+        Solid::new([shell])
+    }
 }
 
 impl BuildSolid for Solid {}
 
+fn extrude_region(
+    region: Region,
+    height: impl Into<Scalar>,
+    core: &mut Core,
+) -> Solid {
+    let bottom_surface = core.layers.topology.surfaces.xy_plane();
+    let sweep_path = Vector::from([Scalar::ZERO, Scalar::ZERO, height.into()]);
+
+    Sketch::empty(&core.layers.topology)
+        .add_regions([region], core)
+        .sweep_sketch(bottom_surface, sweep_path, core)
+}
+
+/// Build a solid that is bounded by a surface whose u-axis is a circle
+///
+/// The side face covers the full circle, between the two provided
+/// v-coordinates. Both ends of the side face must either be a pole of the
+/// surface, or be closed by a cap. A cap is defined by a plane and the radius
+/// of the circle that bounds it, which must be centered on the plane's origin
+/// and match the side face's end.
+fn revolve_surface(
+    surface: Handle<Surface>,
+    [v_bottom, v_top]: [Scalar; 2],
+    caps: [Option<(Handle<Surface>, Scalar)>; 2],
+    core: &mut Core,
+) -> Solid {
+    let [curve_bottom, curve_seam, curve_top] =
+        array::from_fn(|_| Curve::new().insert(core));
+    let [vertex_bottom, vertex_top] =
+        array::from_fn(|_| Vertex::new().insert(core));
+
+    // The seam is where the u-coordinate wraps around. Both of the half-edges
+    // that run along it are on the same curve, in opposite directions.
+    let [u_start, u_end] = [Scalar::ZERO, Scalar::TAU];
+    let [t_start, t_end] = [Scalar::ZERO, Scalar::ONE];
+    let side_half_edges = [
+        (
+            &curve_bottom,
+            &vertex_bottom,
+            [[u_start, v_bottom], [u_end, v_bottom]],
+            [u_start, u_end],
+        ),
+        (
+            &curve_seam,
+            &vertex_bottom,
+            [[u_end, v_bottom], [u_end, v_top]],
+            [t_start, t_end],
+        ),
+        (
+            &curve_top,
+            &vertex_top,
+            [[u_end, v_top], [u_start, v_top]],
+            [u_end, u_start],
+        ),
+        (
+            &curve_seam,
+            &vertex_top,
+            [[u_start, v_top], [u_start, v_bottom]],
+            [t_end, t_start],
+        ),
+    ]
+    .map(|(curve, start_vertex, points_surface, boundary)| {
+        half_edge_on_surface(
+            curve,
+            start_vertex,
+            points_surface,
+            boundary,
+            &surface,
+            core,
+        )
+    });
+
+    let mut faces =
+        vec![face_from_half_edges(surface, side_half_edges.clone(), core)];
+
+    let [bottom, _, top, _] = side_half_edges;
+    for (cap, sibling) in caps.into_iter().zip([bottom, top]) {
+        let Some((plane, radius)) = cap else {
+            continue;
+        };
+
+        let path =
+            SurfacePath::circle_from_center_and_radius(Point::origin(), radius);
+        let boundary = core.layers.geometry.of_half_edge(&sibling).boundary;
+
+        let curve = sibling.curve().clone().make_path_on_surface(
+            path,
+            plane.clone(),
+            &mut core.layers.geometry,
+        );
+        let half_edge = HalfEdge::new(curve, sibling.start_vertex().clone())
+            .insert(core)
+            .set_geometry(
+                HalfEdgeGeom {
+                    path,
+                    boundary: boundary.reverse(),
+                },
+                &mut core.layers.geometry,
+            );
+
+        faces.push(face_from_half_edges(plane, [half_edge], core));
+    }
+
+    let shell = Shell::new(faces).insert(core);
+    Solid::new([shell])
+}
+
+/// Build a half-edge along a straight line in surface coordinates
+fn half_edge_on_surface(
+    curve: &Handle<Curve>,
+    start_vertex: &Handle<Vertex>,
+    points_surface: [[Scalar; 2]; 2],
+    boundary: [Scalar; 2],
+    surface: &Handle<Surface>,
+    core: &mut Core,
+) -> Handle<HalfEdge> {
+    let boundary = CurveBoundary::from(boundary.map(|t| [t]));
+    let path = SurfacePath::line_from_points_with_coords(
+        boundary.inner.zip_ext(points_surface),
+    );
+
+    let curve = curve.clone().make_path_on_surface(
+        path,
+        surface.clone(),
+        &mut core.layers.geometry,
+    );
+
+    HalfEdge::new(curve, start_vertex.clone())
+        .insert(core)
+        .set_geometry(
+            HalfEdgeGeom { path, boundary },
+            &mut core.layers.geometry,
+        )
+}
+
+fn face_from_half_edges(
+    surface: Handle<Surface>,
+    half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+    core: &mut Core,
+) -> Handle<Face> {
+    let exterior = Cycle::new(half_edges).insert(core);
+    let region = Region::new(exterior, []).insert(core);
+
+    Face::new(surface, region).insert(core)
+}
+
 /// A tetrahedron
 ///
 /// Returned by [`BuildSolid::tetrahedron`].
@@ -47,3 +453,150 @@ pub struct Tetrahedron {
     /// The shell of the tetrahedron
     pub shell: TetrahedronShell<IsInsertedYes>,
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        operations::{build::BuildSolid, insert::Insert},
+        topology::Solid,
+        Core,
+    };
+
+    #[test]
+    fn rounded_box() {
+        let mut core = Core::new();
+
+        let solid = Solid::rounded_box([4., 3., 2.], 0.5, &mut core);
+        let shell = solid.shells().only();
+
+        // Top, bottom, four flat sides, and four rounded corners.
+        assert_eq!(shell.faces().len(), 10);
+    }
+
+    #[test]
+    fn cylinder() {
+        let mut core = Core::new();
+
+        let solid = Solid::cylinder(1., 2., &mut core);
+        let shell = solid.shells().only();
+
+        // Top, bottom, and side.
+        assert_eq!(shell.faces().len(), 3);
+    }
+
+    #[test]
+    fn washer_and_tube() {
+        let mut core = Core::new();
+
+        let washer = Solid::washer(2., 1., 0.25, &mut core);
+        let tube = Solid::tube(2., 0.25, 5., &mut core);
+
+        for solid in [washer, tube] {
+            let shell = solid.shells().only();
+
+            // Top, bottom, outer side, and inner side.
+            assert_eq!(shell.faces().len(), 4);
+
+            // Only top and bottom have a hole.
+            let faces_with_holes = shell
+                .faces()
+                .iter()
+                .filter(|face| face.region().interiors().len() == 1)
+                .count();
+            assert_eq!(faces_with_holes, 2);
+        }
+    }
+
+    #[test]
+    fn cone() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cone = Solid::cone(1., 0., 3., &mut core).insert(&mut core);
+        let frustum = Solid::cone(2., 1., 3., &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // Bottom and side. The apex doesn't need a face.
+        assert_eq!(cone.shells().only().faces().len(), 2);
+        // Bottom, side, and top.
+        assert_eq!(frustum.shells().only().faces().len(), 3);
+
+        let pi = Scalar::PI;
+        assert_volume(&cone, pi, &mut core)?;
+        assert_volume(&frustum, pi * 7., &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn sphere() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sphere = Solid::sphere(2., &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // The whole sphere is a single face, bounded by its seam and poles.
+        assert_eq!(sphere.shells().only().faces().len(), 1);
+
+        assert_volume(&sphere, Scalar::PI * 32. / 3., &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Height of cone must be positive")]
+    fn cone_with_negative_height() {
+        let mut core = Core::new();
+        Solid::cone(1., 0., -3., &mut core);
+    }
+
+    #[test]
+    fn torus() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let torus = Solid::torus(2., 0.5, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // The whole torus is a single face, bounded by its two seams.
+        assert_eq!(torus.shells().only().faces().len(), 1);
+
+        assert_volume(
+            &torus,
+            Scalar::PI * Scalar::PI * 2. * 2. * 0.25,
+            &mut core,
+        )?;
+
+        Ok(())
+    }
+
+    /// Triangulate the solid, and compare the volume of the mesh
+    ///
+    /// The volume is only positive, if all triangles point outwards.
+    fn assert_volume(
+        solid: &Solid,
+        expected: Scalar,
+        core: &mut Core,
+    ) -> anyhow::Result<()> {
+        let mesh = (solid, Tolerance::from_scalar(0.01)?).triangulate(core);
+
+        let volume = mesh
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] =
+                    triangle.inner.points().map(|point| point.coords);
+                a.dot(&b.cross(&c)) / 6.
+            })
+            .fold(Scalar::ZERO, |volume, triangle| volume + triangle);
+
+        // The mesh is inscribed into the curved surfaces, so it's a bit
+        // smaller than the solid.
+        assert!(
+            volume <= expected && volume > expected * 0.95,
+            "Expected volume {expected}, got {volume}"
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
-use fj_math::{Point, Scalar, Vector};
+use fj_math::{Circle, Point, Scalar, Vector};
 
 use crate::{
-    geometry::{GlobalPath, SurfaceGeom},
+    geometry::{GlobalPath, SurfaceGeom, SurfaceProfile},
     operations::insert::Insert,
     storage::Handle,
     topology::Surface,
@@ -50,6 +50,99 @@ pub trait BuildSurface {
             SurfaceGeom {
                 u: u.into(),
                 v: v.into(),
+                profile: SurfaceProfile::STRAIGHT,
+            },
+        );
+
+        surface
+    }
+
+    /// Build a cone from the provided circle, axis, and radius
+    ///
+    /// The circle is the u-axis of the cone, and `axis` its v-axis. `radius`
+    /// is the radius of the cone at the end of `axis`. If it is equal to the
+    /// radius of the circle, the result is a cylinder.
+    fn cone(
+        circle: Circle<3>,
+        axis: impl Into<Vector<3>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Handle<Surface> {
+        let surface = Surface::new().insert(core);
+
+        core.layers.geometry.define_surface(
+            surface.clone(),
+            SurfaceGeom {
+                u: GlobalPath::Circle(circle),
+                v: axis.into(),
+                profile: SurfaceProfile::Linear {
+                    taper: radius.into() / circle.radius() - Scalar::ONE,
+                },
+            },
+        );
+
+        surface
+    }
+
+    /// Build a sphere from the provided center and radius
+    ///
+    /// The equator of the sphere is parallel to the xy-plane, and its poles
+    /// are on the z-axis through the center. In surface coordinates, `u` is
+    /// the longitude and `v` the latitude, both in radians.
+    fn sphere(
+        center: impl Into<Point<3>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Handle<Surface> {
+        let center = center.into();
+        let radius = radius.into();
+
+        let surface = Surface::new().insert(core);
+
+        core.layers.geometry.define_surface(
+            surface.clone(),
+            SurfaceGeom {
+                u: GlobalPath::Circle(Circle::from_center_and_radius(
+                    center, radius,
+                )),
+                v: Vector::unit_z() * radius,
+                profile: SurfaceProfile::Spherical,
+            },
+        );
+
+        surface
+    }
+
+    /// Build a torus from the provided center and radii
+    ///
+    /// The torus is parallel to the xy-plane, and its axis is the z-axis
+    /// through the center. The major radius is the radius of the circle at the
+    /// center of its tube, the minor radius that of the tube itself.
+    ///
+    /// In surface coordinates, `u` is the angle around the axis, and `v` the
+    /// angle around the tube, both in radians. `v` is zero on the outside of
+    /// the torus, and a quarter turn on its top.
+    fn torus(
+        center: impl Into<Point<3>>,
+        major_radius: impl Into<Scalar>,
+        minor_radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Handle<Surface> {
+        let center = center.into();
+        let major_radius = major_radius.into();
+        let minor_radius = minor_radius.into();
+
+        let surface = Surface::new().insert(core);
+
+        core.layers.geometry.define_surface(
+            surface.clone(),
+            SurfaceGeom {
+                u: GlobalPath::Circle(Circle::from_center_and_radius(
+                    center,
+                    major_radius,
+                )),
+                v: Vector::unit_z() * minor_radius,
+                profile: SurfaceProfile::Toroidal,
             },
         );
 
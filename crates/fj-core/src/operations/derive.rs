@@ -9,6 +9,12 @@ use crate::{
 };
 
 /// Mark a store object as derived from another
+///
+/// Derived objects inherit the presentation data and material of the original.
+/// Their origin, including the operations that are running, is recorded in the
+/// [provenance layer].
+///
+/// [provenance layer]: crate::provenance::Provenance
 pub trait DeriveFrom {
     /// Mark this object as derived from the other object provided
     fn derive_from(self, original: &Self, core: &mut Core) -> Self;
@@ -22,6 +28,58 @@ where
         core.layers
             .presentation
             .derive_object(original.clone().into(), self.clone().into());
+        core.layers
+            .materials
+            .derive_object(original.clone().into(), self.clone().into());
+        core.layers
+            .provenance
+            .derive_object(original.clone().into(), self.clone().into());
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{build::BuildSolid, insert::Insert, reverse::Reverse},
+        provenance::Operation,
+        topology::Solid,
+        Core,
+    };
+
+    #[test]
+    fn provenance_survives_later_operations() {
+        let mut core = Core::new();
+
+        let tagged = core.record_operation(
+            Operation::new("tetrahedron").with_tag("tagged"),
+            |core| {
+                Solid::tetrahedron(
+                    [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+                    core,
+                )
+            },
+        );
+        let untagged = Solid::tetrahedron(
+            [[2., 0., 0.], [2., 1., 0.], [3., 0., 0.], [2., 0., 1.]],
+            &mut core,
+        );
+
+        let face = tagged.shell.abc.face.clone();
+        let reversed = core
+            .record_operation(Operation::new("reverse"), |core| {
+                face.reverse(core).insert(core)
+            });
+
+        let provenance = &core.layers.provenance;
+        assert!(provenance.is_from_tag(face.id(), "tagged"));
+        assert!(provenance.is_from_tag(reversed.region().id(), "tagged"));
+        assert!(!provenance.is_from_tag(untagged.shell.abc.face.id(), "tagged"));
+
+        let operations = provenance
+            .history(reversed.region().id())
+            .map(|origin| origin.operations.last().unwrap().name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(operations, ["reverse", "tetrahedron"]);
+    }
+}
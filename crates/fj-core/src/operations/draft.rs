@@ -0,0 +1,285 @@
+//! # Operations to add draft to faces
+//!
+//! See [`Draft`].
+
+use std::collections::BTreeMap;
+
+use fj_math::{Point, Rotation, Scalar, Vector};
+
+use crate::{
+    operations::{
+        insert::Insert,
+        polyhedron::{intersect_planes, Polyhedron},
+    },
+    storage::Handle,
+    topology::{Face, Shell, Solid, Vertex},
+    Core,
+};
+
+/// Tilt faces by a draft angle
+///
+/// Parts that are cast or injection-molded need to be removed from their mold.
+/// Faces that are parallel to the direction in which the part is pulled out of
+/// the mold would rub against the mold while that happens. Draft tilts those
+/// faces slightly, so the part comes free.
+pub trait Draft: Sized {
+    /// Tilt the provided faces by the provided angle, in radians
+    ///
+    /// Each face is rotated around the line where it intersects the neutral
+    /// plane, which is the plane through `neutral_point` that is perpendicular
+    /// to `pull`. A positive angle tilts the faces such that the shape gets
+    /// narrower in the pull direction. The edges around the tilted faces are
+    /// rebuilt, by intersecting the planes of the faces that meet there.
+    ///
+    /// Only supports polyhedra, meaning shapes whose faces are planar
+    /// polygons without holes. Faces that are not part of the shape are
+    /// ignored.
+    fn draft(
+        &self,
+        faces: &[Handle<Face>],
+        pull: impl Into<Vector<3>>,
+        angle_rad: impl Into<Scalar>,
+        neutral_point: impl Into<Point<3>>,
+        core: &mut Core,
+    ) -> Result<Self, DraftError>;
+}
+
+impl Draft for Shell {
+    fn draft(
+        &self,
+        faces: &[Handle<Face>],
+        pull: impl Into<Vector<3>>,
+        angle_rad: impl Into<Scalar>,
+        neutral_point: impl Into<Point<3>>,
+        core: &mut Core,
+    ) -> Result<Self, DraftError> {
+        let pull = pull.into().normalize();
+        let angle_rad = angle_rad.into();
+        let neutral_point = neutral_point.into();
+
+        let tolerances = core.tolerances();
+        let mut polyhedron = Polyhedron::from_shell(self, core)
+            .map_err(|face| DraftError::UnsupportedFace { face })?;
+
+        let planes = polyhedron
+            .faces
+            .iter()
+            .zip(&polyhedron.polygons)
+            .map(|(face, polygon)| {
+                let plane = polyhedron.plane_of(polygon);
+                if !faces.contains(face) {
+                    return Ok(plane);
+                }
+
+                let (normal, _) = plane;
+                if tolerances.are_parallel(&normal, &pull) {
+                    return Err(DraftError::FacePerpendicularToPull {
+                        face: face.clone(),
+                    });
+                }
+
+                let hinge = point_on_intersection(
+                    plane,
+                    (pull, pull.dot(&neutral_point.coords)),
+                );
+                let axis = normal.cross(&pull);
+                let normal = Rotation::from_axis_angle(axis, angle_rad)
+                    .rotate_vector(&normal);
+
+                Ok((normal, normal.dot(&hinge.coords)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut planes_by_vertex = BTreeMap::<_, Vec<_>>::new();
+        for (polygon, plane) in polyhedron.polygons.iter().zip(&planes) {
+            for &vertex in polygon {
+                planes_by_vertex.entry(vertex).or_default().push(*plane);
+            }
+        }
+
+        let positions = polyhedron
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(index, (vertex, position))| {
+                let planes = &planes_by_vertex[&index];
+                let is_on_all_planes = |position: Point<3>| {
+                    planes.iter().all(|&(normal, distance)| {
+                        tolerances.is_zero_length(
+                            normal.dot(&position.coords) - distance,
+                        )
+                    })
+                };
+
+                // Vertices whose faces have not been tilted don't move.
+                if is_on_all_planes(*position) {
+                    return Ok(*position);
+                }
+
+                intersect_planes(planes, &tolerances)
+                    .filter(|&position| is_on_all_planes(position))
+                    .ok_or_else(|| DraftError::VertexNotOnPlanes {
+                        vertex: vertex
+                            .clone()
+                            .expect("Vertices of shell have handles"),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for ((_, position), new_position) in
+            polyhedron.vertices.iter_mut().zip(positions)
+        {
+            *position = new_position;
+        }
+
+        Ok(polyhedron.into_shell(core))
+    }
+}
+
+impl Draft for Solid {
+    fn draft(
+        &self,
+        faces: &[Handle<Face>],
+        pull: impl Into<Vector<3>>,
+        angle_rad: impl Into<Scalar>,
+        neutral_point: impl Into<Point<3>>,
+        core: &mut Core,
+    ) -> Result<Self, DraftError> {
+        let pull = pull.into();
+        let angle_rad = angle_rad.into();
+        let neutral_point = neutral_point.into();
+
+        let shells = self
+            .shells()
+            .iter()
+            .map(|shell| {
+                let shell =
+                    shell.draft(faces, pull, angle_rad, neutral_point, core)?;
+                Ok(shell.insert(core))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Solid::new(shells))
+    }
+}
+
+/// Error adding draft to faces
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum DraftError {
+    /// The shape contains a face that is not a planar polygon without holes
+    #[error("Draft only supports planar polygons without holes: {face:?}")]
+    UnsupportedFace {
+        /// The face that is not supported
+        face: Handle<Face>,
+    },
+
+    /// A face is perpendicular to the pull direction, so it can't be tilted
+    #[error("Can't add draft to face that is perpendicular to pull: {face:?}")]
+    FacePerpendicularToPull {
+        /// The face that is perpendicular to the pull direction
+        face: Handle<Face>,
+    },
+
+    /// The tilted faces around a vertex don't meet in a single point
+    ///
+    /// This happens, if more than three faces meet at a vertex, and tilting
+    /// some of them moves their planes apart.
+    #[error("Faces around vertex don't meet after adding draft: {vertex:?}")]
+    VertexNotOnPlanes {
+        /// The vertex whose faces don't meet
+        vertex: Handle<Vertex>,
+    },
+}
+
+/// Find a point on the line where two planes intersect
+///
+/// Expects the planes to not be parallel.
+fn point_on_intersection(
+    (n1, d1): (Vector<3>, Scalar),
+    (n2, d2): (Vector<3>, Scalar),
+) -> Point<3> {
+    let n1n2 = n1.dot(&n2);
+    let n1n1 = n1.dot(&n1);
+    let n2n2 = n2.dot(&n2);
+    let determinant = n1n1 * n2n2 - n1n2 * n1n2;
+
+    let c1 = (d1 * n2n2 - d2 * n1n2) / determinant;
+    let c2 = (d2 * n1n1 - d1 * n1n2) / determinant;
+
+    Point::origin() + n1 * c1 + n2 * c2
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::TriangulateFiltered},
+        operations::build::BuildShell,
+        topology::Shell,
+        Core,
+    };
+
+    use super::Draft;
+
+    #[test]
+    fn draft_sides_of_cube() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cube = Shell::from_vertices_and_polygons(
+            [
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [1., 1., 0.],
+                [0., 1., 0.],
+                [0., 0., 1.],
+                [1., 0., 1.],
+                [1., 1., 1.],
+                [0., 1., 1.],
+            ],
+            [
+                vec![0, 3, 2, 1],
+                vec![4, 5, 6, 7],
+                vec![0, 1, 5, 4],
+                vec![1, 2, 6, 5],
+                vec![2, 3, 7, 6],
+                vec![3, 0, 4, 7],
+            ],
+            &mut core,
+        );
+        let sides = cube.faces().iter().skip(2).cloned().collect::<Vec<_>>();
+
+        // With the neutral plane at the bottom, this angle moves the top edges
+        // inwards by a quarter of the cube's height.
+        let angle = 0.25_f64.atan();
+        let drafted =
+            cube.draft(&sides, [0., 0., 1.], angle, [0., 0., 0.], &mut core)?;
+
+        let tolerance = Tolerance::from_scalar(1.)?;
+        let mesh =
+            (&drafted, tolerance).triangulate_filtered(|_| true, &mut core);
+
+        for vertex in mesh.vertices() {
+            let expected = if vertex.z > Scalar::from(0.5) {
+                let [x, y] = [vertex.x, vertex.y].map(|c| {
+                    if c > Scalar::from(0.5) {
+                        0.75
+                    } else {
+                        0.25
+                    }
+                });
+                Point::from([x, y, 1.])
+            } else {
+                let [x, y] = [vertex.x, vertex.y].map(Scalar::round);
+                Point::from([x, y, Scalar::ZERO])
+            };
+
+            assert!(
+                vertex.distance_to(&expected) < Scalar::from(1e-9),
+                "{vertex:?} != {expected:?}"
+            );
+        }
+
+        Ok(())
+    }
+}
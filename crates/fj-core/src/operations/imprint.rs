@@ -0,0 +1,466 @@
+//! # Operations to imprint shapes onto each other
+//!
+//! See [`Imprint`].
+
+use fj_math::{Point, Scalar, Tolerances, Vector};
+
+use crate::{
+    operations::{insert::Insert, polyhedron::Polyhedron},
+    storage::Handle,
+    topology::{Face, Shell, Solid},
+    Core,
+};
+
+/// Imprint two shapes onto each other
+///
+/// Where faces of two shapes touch, meaning they are coincident and face in
+/// opposite directions, both faces are split, such that the area in which they
+/// touch becomes a face of its own in each shape. Those faces have matching
+/// boundaries. This is required to mesh assemblies for simulations, which need
+/// to know where the parts are in contact.
+pub trait Imprint: Sized {
+    /// Imprint `self` and `other` onto each other
+    ///
+    /// Returns the imprinted versions of both shapes, in the same order.
+    ///
+    /// Only supports polyhedra, meaning shapes whose faces are planar polygons
+    /// without holes. Faces that touch also need to be convex.
+    fn imprint(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<(Self, Self), ImprintError>;
+}
+
+impl Imprint for Shell {
+    fn imprint(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<(Self, Self), ImprintError> {
+        let tolerances = core.tolerances();
+
+        let mut a = Polyhedron::from_shell(self, core)
+            .map_err(|face| ImprintError::UnsupportedFace { face })?;
+        let mut b = Polyhedron::from_shell(other, core)
+            .map_err(|face| ImprintError::UnsupportedFace { face })?;
+
+        // Polygons that are split are replaced by the contact area, and the
+        // rest of them is added to the end. The rest still needs to be checked
+        // against polygons of the other shape, which happens as the loops get
+        // to it.
+        let mut i = 0;
+        while i < a.polygons.len() {
+            let mut j = 0;
+            while j < b.polygons.len() {
+                if let Some(contact) =
+                    Contact::between(&a, i, &b, j, &tolerances)?
+                {
+                    let [contact_a, contact_b] = contact.contact;
+                    let tolerance = tolerances.linear;
+                    replace_polygon(
+                        &mut a,
+                        i,
+                        contact_a,
+                        contact.rest_a,
+                        tolerance,
+                    );
+                    replace_polygon(
+                        &mut b,
+                        j,
+                        contact_b,
+                        contact.rest_b,
+                        tolerance,
+                    );
+                }
+
+                j += 1;
+            }
+
+            i += 1;
+        }
+
+        a.split_edges_at_vertices(tolerances.linear);
+        b.split_edges_at_vertices(tolerances.linear);
+
+        Ok((a.into_shell(core), b.into_shell(core)))
+    }
+}
+
+impl Imprint for Solid {
+    fn imprint(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<(Self, Self), ImprintError> {
+        let mut shells_a = self
+            .shells()
+            .iter()
+            .map(|shell| Shell::clone(shell))
+            .collect::<Vec<_>>();
+        let mut shells_b = other
+            .shells()
+            .iter()
+            .map(|shell| Shell::clone(shell))
+            .collect::<Vec<_>>();
+
+        for shell_a in &mut shells_a {
+            for shell_b in &mut shells_b {
+                let (a, b) = shell_a.imprint(shell_b, core)?;
+                *shell_a = a;
+                *shell_b = b;
+            }
+        }
+
+        let [a, b] = [shells_a, shells_b].map(|shells| {
+            Solid::new(shells.into_iter().map(|shell| shell.insert(core)))
+        });
+
+        Ok((a, b))
+    }
+}
+
+/// Error imprinting shapes onto each other
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ImprintError {
+    /// A shape contains a face that is not a planar polygon without holes
+    #[error("Imprint only supports planar polygons without holes: {face:?}")]
+    UnsupportedFace {
+        /// The face that is not supported
+        face: Handle<Face>,
+    },
+
+    /// A face that touches a face of the other shape is not convex
+    #[error(
+        "Imprint only supports convex faces, where shapes touch: {face:?}"
+    )]
+    NonConvexContact {
+        /// The face that is not convex
+        face: Handle<Face>,
+    },
+}
+
+/// The area in which two polygons touch
+struct Contact {
+    /// The contact area, as a polygon of each polyhedron
+    contact: [Vec<Point<3>>; 2],
+
+    /// The rest of the polygon of the first polyhedron, as convex polygons
+    rest_a: Vec<Vec<Point<3>>>,
+
+    /// The rest of the polygon of the second polyhedron, as convex polygons
+    rest_b: Vec<Vec<Point<3>>>,
+}
+
+impl Contact {
+    /// Compute where two polygons touch
+    ///
+    /// Returns `None`, if they don't touch, or if they already have matching
+    /// boundaries where they do.
+    fn between(
+        a: &Polyhedron,
+        i: usize,
+        b: &Polyhedron,
+        j: usize,
+        tolerances: &Tolerances,
+    ) -> Result<Option<Self>, ImprintError> {
+        let (normal_a, distance_a) = a.plane_of(&a.polygons[i]);
+        let (normal_b, distance_b) = b.plane_of(&b.polygons[j]);
+
+        let touch = tolerances.are_parallel(&normal_a, &normal_b)
+            && normal_a.dot(&normal_b) < Scalar::ZERO
+            && tolerances.is_zero_length(distance_a + distance_b);
+        if !touch {
+            return Ok(None);
+        }
+
+        // Both polygons are converted into the same coordinate system, on the
+        // plane of the first one. The second one is reversed, which makes it
+        // counter-clockwise in that coordinate system too.
+        let origin = a.position(a.polygons[i][0]);
+        let u = (a.position(a.polygons[i][1]) - origin).normalize();
+        let v = normal_a.cross(&u);
+        let to_plane = |point: Point<3>| {
+            let offset = point - origin;
+            Point::from([offset.dot(&u), offset.dot(&v)])
+        };
+        let from_plane = |point: Point<2>| origin + u * point.u + v * point.v;
+        let polygons_from_plane = |polygons: Vec<Vec<Point<2>>>| {
+            polygons
+                .into_iter()
+                .map(|polygon| polygon.into_iter().map(from_plane).collect())
+                .collect::<Vec<_>>()
+        };
+
+        let polygon_a = a.polygons[i]
+            .iter()
+            .map(|&index| to_plane(a.position(index)))
+            .collect::<Vec<_>>();
+        let polygon_b = b.polygons[j]
+            .iter()
+            .rev()
+            .map(|&index| to_plane(b.position(index)))
+            .collect::<Vec<_>>();
+
+        let clip = Clip {
+            tolerance: tolerances.linear,
+        };
+        for (polygon, polyhedron, index) in
+            [(&polygon_a, a, i), (&polygon_b, b, j)]
+        {
+            if !clip.is_convex(polygon) {
+                return Err(ImprintError::NonConvexContact {
+                    face: polyhedron.faces[index].clone(),
+                });
+            }
+        }
+
+        let contact = edges(&polygon_b)
+            .fold(polygon_a.clone(), |polygon, edge| {
+                clip.keep_left(&polygon, edge)
+            });
+        if clip.is_degenerate(&contact) {
+            return Ok(None);
+        }
+
+        let rest_a = clip.difference(&polygon_a, &contact);
+        let rest_b = clip.difference(&polygon_b, &contact);
+        if rest_a.is_empty() && rest_b.is_empty() {
+            return Ok(None);
+        }
+
+        let contact_a =
+            contact.iter().copied().map(from_plane).collect::<Vec<_>>();
+        let contact_b = contact_a.iter().rev().copied().collect();
+
+        // The rest of the second polygon is clockwise in the coordinate system
+        // of the first, and needs to be reversed back.
+        let rest_b = rest_b
+            .into_iter()
+            .map(|polygon| polygon.into_iter().rev().collect())
+            .collect();
+
+        Ok(Some(Self {
+            contact: [contact_a, contact_b],
+            rest_a: polygons_from_plane(rest_a),
+            rest_b: polygons_from_plane(rest_b),
+        }))
+    }
+}
+
+/// Replace a polygon with the contact area and the rest of the polygon
+fn replace_polygon(
+    polyhedron: &mut Polyhedron,
+    index: usize,
+    contact: Vec<Point<3>>,
+    rest: Vec<Vec<Point<3>>>,
+    tolerance: Scalar,
+) {
+    let face = polyhedron.faces[index].clone();
+
+    let mut to_indices = |polygon: Vec<Point<3>>| {
+        polygon
+            .into_iter()
+            .map(|point| polyhedron.vertex_at(point, tolerance))
+            .collect::<Vec<_>>()
+    };
+
+    let contact = to_indices(contact);
+    let rest = rest.into_iter().map(to_indices).collect::<Vec<_>>();
+
+    polyhedron.polygons[index] = contact;
+    for polygon in rest {
+        polyhedron.polygons.push(polygon);
+        polyhedron.faces.push(face.clone());
+    }
+}
+
+/// Clipping of convex polygons
+///
+/// All polygons are expected to be counter-clockwise.
+struct Clip {
+    tolerance: Scalar,
+}
+
+impl Clip {
+    /// Keep the part of the polygon that is left of the directed edge
+    ///
+    /// This is a single step of the Sutherland-Hodgman algorithm.
+    fn keep_left(
+        &self,
+        polygon: &[Point<2>],
+        [a, b]: [Point<2>; 2],
+    ) -> Vec<Point<2>> {
+        let direction = (b - a).normalize();
+        let distance = |point: Point<2>| {
+            let offset = point - a;
+            direction.u * offset.v - direction.v * offset.u
+        };
+        let is_inside = |point: Point<2>| distance(point) >= -self.tolerance;
+
+        let mut clipped = Vec::new();
+        let mut push = |point: Point<2>| {
+            let is_duplicate = clipped.last().is_some_and(|last: &Point<2>| {
+                last.distance_to(&point) <= self.tolerance
+            });
+            if !is_duplicate {
+                clipped.push(point);
+            }
+        };
+
+        for [start, end] in edges(polygon) {
+            let intersection = || {
+                let [d_start, d_end] = [start, end].map(distance);
+                start + (end - start) * (d_start / (d_start - d_end))
+            };
+
+            match (is_inside(start), is_inside(end)) {
+                (true, true) => push(end),
+                (true, false) => push(intersection()),
+                (false, true) => {
+                    push(intersection());
+                    push(end);
+                }
+                (false, false) => {}
+            }
+        }
+
+        if clipped.len() > 1
+            && clipped[0].distance_to(&clipped[clipped.len() - 1])
+                <= self.tolerance
+        {
+            clipped.pop();
+        }
+
+        clipped
+    }
+
+    /// Split the part of `polygon` that is outside of `hole` into convex parts
+    fn difference(
+        &self,
+        polygon: &[Point<2>],
+        hole: &[Point<2>],
+    ) -> Vec<Vec<Point<2>>> {
+        let mut rest = polygon.to_vec();
+        let mut parts = Vec::new();
+
+        for [a, b] in edges(hole) {
+            let outside = self.keep_left(&rest, [b, a]);
+            if !self.is_degenerate(&outside) {
+                parts.push(outside);
+            }
+
+            rest = self.keep_left(&rest, [a, b]);
+        }
+
+        parts
+    }
+
+    fn is_convex(&self, polygon: &[Point<2>]) -> bool {
+        let num_points = polygon.len();
+
+        (0..num_points).all(|i| {
+            let [a, b, c] =
+                [i, i + 1, i + 2].map(|index| polygon[index % num_points]);
+            self.is_left_or_on(c, [a, b])
+        })
+    }
+
+    /// Check whether the polygon has no area, within the tolerance
+    ///
+    /// Compares the polygon's area to its perimeter, which makes thin slivers
+    /// count as degenerate, regardless of their length.
+    fn is_degenerate(&self, polygon: &[Point<2>]) -> bool {
+        if polygon.len() < 3 {
+            return true;
+        }
+
+        let (double_area, perimeter) = edges(polygon).fold(
+            (Scalar::ZERO, Scalar::ZERO),
+            |(double_area, perimeter), [a, b]| {
+                (
+                    double_area + (a.u * b.v - b.u * a.v),
+                    perimeter + a.distance_to(&b),
+                )
+            },
+        );
+
+        double_area / 2. <= self.tolerance * perimeter
+    }
+
+    fn is_left_or_on(&self, point: Point<2>, [a, b]: [Point<2>; 2]) -> bool {
+        let direction: Vector<2> = (b - a).normalize();
+        let offset = point - a;
+
+        direction.u * offset.v - direction.v * offset.u >= -self.tolerance
+    }
+}
+
+/// Iterate over the edges of a polygon
+fn edges(polygon: &[Point<2>]) -> impl Iterator<Item = [Point<2>; 2]> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| [a, b])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{build::BuildShell, insert::Insert},
+        topology::Shell,
+        Core,
+    };
+
+    use super::Imprint;
+
+    #[test]
+    fn imprint_stacked_boxes() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let lower = cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let upper = cuboid([0.5, 0., 1.], [1.5, 1., 2.], &mut core);
+
+        let (lower, upper) = lower.imprint(&upper, &mut core)?;
+
+        // The top face of the lower box and the bottom face of the upper one
+        // are split into the contact area and the rest.
+        assert_eq!(lower.faces().len(), 7);
+        assert_eq!(upper.faces().len(), 7);
+
+        // The new vertices are also inserted into the edges of the adjacent
+        // faces, so the shells are still valid.
+        let _ = lower.insert(&mut core);
+        let _ = upper.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    fn cuboid(min: [f64; 3], max: [f64; 3], core: &mut Core) -> Shell {
+        let [x0, y0, z0] = min;
+        let [x1, y1, z1] = max;
+
+        Shell::from_vertices_and_polygons(
+            [
+                [x0, y0, z0],
+                [x1, y0, z0],
+                [x1, y1, z0],
+                [x0, y1, z0],
+                [x0, y0, z1],
+                [x1, y0, z1],
+                [x1, y1, z1],
+                [x0, y1, z1],
+            ],
+            [
+                vec![0, 3, 2, 1],
+                vec![4, 5, 6, 7],
+                vec![0, 1, 5, 4],
+                vec![1, 2, 6, 5],
+                vec![2, 3, 7, 6],
+                vec![3, 0, 4, 7],
+            ],
+            core,
+        )
+    }
+}
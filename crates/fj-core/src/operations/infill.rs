@@ -0,0 +1,360 @@
+//! # Lattice infill of solids
+//!
+//! See [`Infill`].
+
+use fj_interop::Mesh;
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::Tolerance,
+        bounding_volume::BoundingVolume,
+        polygonize::Polygonize,
+        sdf::{SignedDistanceField, VoxelGrid},
+    },
+    operations::{build::BuildShell, insert::Insert},
+    topology::{Shell, Solid},
+    Core,
+};
+
+use super::{build::BuildSolid, update::UpdateSolid};
+
+/// Fill the interior of a [`Solid`] with a lattice
+///
+/// This is used to make parts lighter, while keeping them stiff.
+pub trait Infill {
+    /// Replace the interior of the solid with a lattice
+    ///
+    /// If `wall_thickness` is positive, the solid is shelled out first,
+    /// keeping a wall of that thickness along its surface, and the lattice
+    /// fills the space within that wall. Otherwise, the lattice fills the
+    /// whole solid, and is cut off at its surface.
+    ///
+    /// The result is extracted from the combination of the solid's
+    /// [signed distance field] with that of the lattice, sampled on a grid
+    /// with `resolution` cells along its longest axis. For the lattice to come
+    /// out right, the cells need to be considerably smaller than the
+    /// thickness of the lattice's walls.
+    ///
+    /// [signed distance field]: SignedDistanceField
+    #[must_use]
+    fn infill(
+        &self,
+        lattice: Lattice,
+        wall_thickness: impl Into<Scalar>,
+        resolution: usize,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Solid;
+}
+
+impl Infill for Solid {
+    fn infill(
+        &self,
+        lattice: Lattice,
+        wall_thickness: impl Into<Scalar>,
+        resolution: usize,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Solid {
+        let mesh = infill_mesh(
+            self,
+            lattice,
+            wall_thickness.into(),
+            resolution,
+            tolerance,
+            core,
+        );
+
+        let shell = Shell::from_mesh(&mesh, core).insert(core);
+        Solid::empty().add_shells([shell], core)
+    }
+}
+
+/// A periodic lattice that fills space
+///
+/// Each lattice consists of walls or beams of the given thickness, repeating
+/// in cells of the given size.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Lattice {
+    /// The kind of lattice
+    pub kind: LatticeKind,
+
+    /// The size of the cells in which the lattice repeats
+    pub cell_size: Scalar,
+
+    /// The thickness of the lattice's walls or beams
+    pub thickness: Scalar,
+}
+
+impl Lattice {
+    /// Compute the signed distance of a point from the surface of the lattice
+    ///
+    /// Like for a [`SignedDistanceField`], the distance is negative inside of
+    /// the lattice's material. It is exact for [`LatticeKind::Grid`], and
+    /// approximate for the other kinds.
+    pub fn distance(&self, point: impl Into<Point<3>>) -> Scalar {
+        let point = point.into();
+        let half_thickness = self.thickness / 2.;
+
+        match self.kind {
+            LatticeKind::Gyroid => {
+                let [x, y, z] = point
+                    .coords
+                    .components
+                    .map(|c| (c * Scalar::TAU / self.cell_size).sin_cos());
+                let ((sin_x, cos_x), (sin_y, cos_y), (sin_z, cos_z)) =
+                    (x, y, z);
+
+                let gyroid = sin_x * cos_y + sin_y * cos_z + sin_z * cos_x;
+
+                // The gradient of the gyroid function has a magnitude of
+                // roughly 1.5 near its zero set, in coordinates scaled to a
+                // period of 2π. Dividing by it makes the value an estimate of
+                // the distance from the gyroid surface.
+                gyroid.abs() * self.cell_size / (Scalar::TAU * 1.5)
+                    - half_thickness
+            }
+            LatticeKind::Grid => {
+                // The distance from the nearest plane of the grid, along each
+                // axis.
+                let [dx, dy, dz] = point
+                    .coords
+                    .components
+                    .map(|c| distance_to_nearest_multiple(c, self.cell_size));
+
+                let to_beam =
+                    |a: Scalar, b: Scalar| Vector::from([a, b]).magnitude();
+
+                [to_beam(dy, dz), to_beam(dx, dz), to_beam(dx, dy)]
+                    .into_iter()
+                    .min()
+                    .expect("Array is not empty")
+                    - half_thickness
+            }
+            LatticeKind::Honeycomb => {
+                let apothem = self.cell_size / 2.;
+                let point = Point::from([point.x, point.y]);
+
+                // The centers of the hexagons form a lattice with these basis
+                // vectors.
+                let [a, b] = [
+                    Vector::from([self.cell_size, Scalar::ZERO]),
+                    Vector::from([
+                        apothem,
+                        self.cell_size * Scalar::from(3_f64.sqrt()) / 2.,
+                    ]),
+                ];
+
+                let v = point.v / b.v;
+                let u = (point.u - v * b.u) / a.u;
+
+                // The nearest center is one of the corners of the lattice's
+                // parallelogram that contains the point.
+                let to_center = [u.floor(), u.ceil()]
+                    .into_iter()
+                    .flat_map(|u| [v.floor(), v.ceil()].map(|v| (u, v)))
+                    .map(|(u, v)| {
+                        let center = Point::origin() + a * u + b * v;
+                        hexagon_norm(point - center)
+                    })
+                    .min()
+                    .expect("Array is not empty");
+
+                apothem - to_center - half_thickness
+            }
+        }
+    }
+}
+
+/// The kind of a [`Lattice`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LatticeKind {
+    /// A gyroid, a smooth triply periodic surface
+    ///
+    /// The gyroid divides space into two intertwined, connected halves. The
+    /// lattice is a sheet of material along that surface.
+    Gyroid,
+
+    /// A grid of beams, parallel to the axes
+    Grid,
+
+    /// A honeycomb of hexagonal walls, parallel to the z-axis
+    ///
+    /// The cell size is the distance between the centers of neighboring
+    /// hexagons.
+    Honeycomb,
+}
+
+fn infill_mesh(
+    solid: &Solid,
+    lattice: Lattice,
+    wall_thickness: Scalar,
+    resolution: usize,
+    tolerance: impl Into<Tolerance>,
+    core: &mut Core,
+) -> Mesh<Point<3>> {
+    let Some(aabb) = solid.aabb(&core.layers.geometry) else {
+        return Mesh::new();
+    };
+
+    let sdf = SignedDistanceField::new(solid, tolerance, core);
+    let wall_thickness = wall_thickness.max(Scalar::ZERO);
+
+    // The grid needs some room around the solid, so the surface is closed.
+    let longest = aabb
+        .size()
+        .components
+        .into_iter()
+        .max()
+        .expect("Vector has components");
+    let cell_size = longest / Scalar::from_u64(resolution.max(1) as u64);
+    let margin = Vector::from([cell_size; 3]);
+
+    let grid_aabb = Aabb {
+        min: aabb.min - margin,
+        max: aabb.max + margin,
+    };
+    let grid_resolution = grid_aabb
+        .size()
+        .components
+        .map(|length| (length / cell_size).ceil().into_u64() as usize + 1);
+
+    VoxelGrid::from_fn(grid_aabb, grid_resolution, |point| {
+        let distance = sdf.distance(point, &core.layers.geometry);
+
+        // Intersecting two shapes means taking the maximum of their distances,
+        // uniting them means taking the minimum.
+        let interior = distance + wall_thickness;
+        let infill = interior.max(lattice.distance(point));
+
+        if wall_thickness > Scalar::ZERO {
+            let wall = distance.max(-interior);
+            wall.min(infill)
+        } else {
+            infill
+        }
+    })
+    .polygonize()
+}
+
+fn distance_to_nearest_multiple(value: Scalar, period: Scalar) -> Scalar {
+    let offset = value - (value / period).floor() * period;
+    offset.min(period - offset)
+}
+
+/// The norm of a vector, relative to a hexagon with flat sides on the left and
+/// right
+///
+/// This is the distance from the center of the hexagon, to the boundary of the
+/// hexagon that is scaled to go through the vector's tip, measured
+/// perpendicular to the nearest side.
+fn hexagon_norm(vector: Vector<2>) -> Scalar {
+    [0., 60., 120.]
+        .map(|angle: f64| {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            vector.dot(&Vector::from([cos, sin])).abs()
+        })
+        .into_iter()
+        .max()
+        .expect("Array is not empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point, Scalar};
+
+    use crate::{
+        algorithms::approx::Tolerance, operations::build::BuildSolid,
+        topology::Solid, Core,
+    };
+
+    use super::{infill_mesh, Lattice, LatticeKind};
+
+    #[test]
+    fn lattice_distance() {
+        let lattice = |kind| Lattice {
+            kind,
+            cell_size: Scalar::from(2.),
+            thickness: Scalar::from(0.2),
+        };
+
+        let grid = lattice(LatticeKind::Grid);
+        assert_eq!(grid.distance([0., 0., 0.5]), Scalar::from(-0.1));
+        assert!(
+            (grid.distance([1., 1., 1.]) - Scalar::from(2_f64.sqrt() - 0.1))
+                .abs()
+                < Scalar::from(1e-9)
+        );
+
+        // The walls between the hexagons around the origin and its neighbor
+        // in positive x-direction are at x = 1.
+        let honeycomb = lattice(LatticeKind::Honeycomb);
+        assert!(
+            (honeycomb.distance([1., 0., 0.]) + Scalar::from(0.1)).abs()
+                < Scalar::from(1e-9)
+        );
+        assert!(
+            (honeycomb.distance([0., 0., 5.]) - Scalar::from(0.9)).abs()
+                < Scalar::from(1e-9)
+        );
+
+        let gyroid = lattice(LatticeKind::Gyroid);
+        assert!(gyroid.distance([0., 0., 0.]) < Scalar::ZERO);
+    }
+
+    #[test]
+    fn infill_box_with_wall() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = Solid::rounded_box([2., 2., 2.], 0.25, &mut core);
+        let lattice = Lattice {
+            kind: LatticeKind::Grid,
+            cell_size: Scalar::from(1.),
+            thickness: Scalar::from(0.3),
+        };
+        let tolerance = Tolerance::from_scalar(0.1)?;
+
+        // Converting the mesh into a solid takes a while, and doesn't change
+        // its shape. Test the mesh directly.
+        let mesh =
+            infill_mesh(&solid, lattice, 0.2.into(), 12, tolerance, &mut core);
+        let aabb = Aabb::<3>::from_points(mesh.vertices());
+
+        // The wall follows the surface of the box, which is only approximated
+        // by the grid.
+        let max_error = Scalar::from(0.2);
+        for (actual, expected) in [aabb.min, aabb.max]
+            .into_iter()
+            .zip([Point::from([-1., -1., 0.]), Point::from([1., 1., 2.])])
+        {
+            assert!((actual - expected).magnitude() < max_error, "{aabb:?}");
+        }
+
+        // Well within the inside of the wall, there's only the surface of the
+        // lattice.
+        let is_within_wall = |point: &Point<3>| {
+            point.x.abs() < Scalar::from(0.6)
+                && point.y.abs() < Scalar::from(0.6)
+                && (point.z - Scalar::ONE).abs() < Scalar::from(0.6)
+        };
+        assert!(mesh.vertices().any(|point| is_within_wall(&point)));
+
+        let without_lattice = infill_mesh(
+            &solid,
+            Lattice {
+                thickness: Scalar::ZERO,
+                ..lattice
+            },
+            0.2.into(),
+            12,
+            tolerance,
+            &mut core,
+        );
+        assert!(!without_lattice
+            .vertices()
+            .any(|point| is_within_wall(&point)));
+
+        Ok(())
+    }
+}
@@ -45,6 +45,7 @@ macro_rules! impl_insert {
                         &core.layers.geometry,
                         &mut core.layers.validation,
                     );
+                    core.layers.provenance.insert_object(handle.clone().into());
                     handle
                 }
             }
@@ -0,0 +1,29 @@
+//! Operations to control the materials of objects
+
+use fj_interop::Material;
+
+use crate::{storage::Handle, topology::Region, Core};
+
+/// Get the material of an object
+pub trait GetMaterial {
+    /// Get the material of the object
+    fn get_material(&self, core: &Core) -> Option<Material>;
+}
+
+impl GetMaterial for Handle<Region> {
+    fn get_material(&self, core: &Core) -> Option<Material> {
+        core.layers.materials.material.get(self).cloned()
+    }
+}
+
+/// Set the material of an object
+pub trait SetMaterial {
+    /// Set the material of the object
+    fn set_material(&self, material: Material, core: &mut Core);
+}
+
+impl SetMaterial for Handle<Region> {
+    fn set_material(&self, material: Material, core: &mut Core) {
+        core.layers.materials.set_material(self.clone(), material);
+    }
+}
@@ -1,11 +1,19 @@
 //! # Operations to merge objects
 //!
-//! See [`Merge`], which is currently the only trait in this module, for more
-//! information.
+//! See [`Merge`] and [`MergeCoplanarFaces`].
 
-use crate::{topology::Solid, Core};
+use fj_math::{Scalar, Tolerances};
 
-use super::update::UpdateSolid;
+use crate::{
+    operations::{insert::Insert, polyhedron::Polyhedron},
+    storage::Handle,
+    topology::{Face, Shell, Solid},
+    Core,
+};
+
+use super::{
+    material::GetMaterial, presentation::GetColor, update::UpdateSolid,
+};
 
 /// Merge two [`Solid`]s
 pub trait Merge {
@@ -19,3 +27,303 @@ impl Merge for Solid {
         self.add_shells(other.shells().iter().cloned(), core)
     }
 }
+
+/// Merge adjacent faces that are on the same plane
+///
+/// Operations like sweeps and joins can produce shapes whose faces are split up
+/// more than necessary. This operation cleans those up, which makes the shape
+/// cheaper to validate and export.
+pub trait MergeCoplanarFaces: Sized {
+    /// Merge adjacent coplanar faces that have the same color and material
+    ///
+    /// Afterwards, vertices that are between two collinear edges are removed,
+    /// merging those edges into one.
+    ///
+    /// Only supports polyhedra, meaning shapes whose faces are planar polygons
+    /// without holes. Faces are not merged, if the result would have a hole.
+    fn merge_coplanar_faces(
+        &self,
+        core: &mut Core,
+    ) -> Result<Self, MergeCoplanarFacesError>;
+}
+
+impl MergeCoplanarFaces for Shell {
+    fn merge_coplanar_faces(
+        &self,
+        core: &mut Core,
+    ) -> Result<Self, MergeCoplanarFacesError> {
+        let tolerances = core.tolerances();
+
+        let mut polyhedron =
+            Polyhedron::from_shell(self, core).map_err(|face| {
+                MergeCoplanarFacesError::UnsupportedFace { face }
+            })?;
+        let mut appearances = polyhedron
+            .faces
+            .iter()
+            .map(|face| {
+                let region = face.region();
+                (region.get_color(core), region.get_material(core))
+            })
+            .collect::<Vec<_>>();
+
+        while let Some((i, j, merged)) =
+            find_mergeable(&polyhedron, &appearances, &tolerances)
+        {
+            polyhedron.polygons[i] = merged;
+            polyhedron.polygons.remove(j);
+            polyhedron.faces.remove(j);
+            appearances.remove(j);
+        }
+
+        while remove_collinear_vertex(&mut polyhedron, tolerances.linear) {}
+
+        Ok(polyhedron.into_shell(core))
+    }
+}
+
+impl MergeCoplanarFaces for Solid {
+    fn merge_coplanar_faces(
+        &self,
+        core: &mut Core,
+    ) -> Result<Self, MergeCoplanarFacesError> {
+        let shells = self
+            .shells()
+            .iter()
+            .map(|shell| Ok(shell.merge_coplanar_faces(core)?.insert(core)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Solid::new(shells))
+    }
+}
+
+/// Error merging coplanar faces
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum MergeCoplanarFacesError {
+    /// The shape contains a face that is not a planar polygon without holes
+    #[error("Merging faces only supports planar polygons: {face:?}")]
+    UnsupportedFace {
+        /// The face that is not supported
+        face: Handle<Face>,
+    },
+}
+
+/// Find two adjacent polygons that can be merged
+///
+/// Returns their indices, with the first being smaller, and the merged polygon.
+fn find_mergeable<C: PartialEq>(
+    polyhedron: &Polyhedron,
+    appearances: &[C],
+    tolerances: &Tolerances,
+) -> Option<(usize, usize, Vec<usize>)> {
+    let polygons = &polyhedron.polygons;
+
+    for i in 0..polygons.len() {
+        for j in i + 1..polygons.len() {
+            if appearances[i] != appearances[j] {
+                continue;
+            }
+
+            let Some(edge) = shared_edge(&polygons[i], &polygons[j]) else {
+                continue;
+            };
+
+            let [(normal_a, distance_a), (normal_b, distance_b)] =
+                [i, j].map(|index| polyhedron.plane_of(&polygons[index]));
+            let are_coplanar = tolerances.are_parallel(&normal_a, &normal_b)
+                && normal_a.dot(&normal_b) > Scalar::ZERO
+                && tolerances.is_zero_length(distance_a - distance_b);
+            if !are_coplanar {
+                continue;
+            }
+
+            if let Some(merged) =
+                merge_polygons(&polygons[i], &polygons[j], edge)
+            {
+                return Some((i, j, merged));
+            }
+        }
+    }
+
+    None
+}
+
+/// Find an edge `[a, b]` of `p`, that `q` contains as `[b, a]`
+fn shared_edge(p: &[usize], q: &[usize]) -> Option<[usize; 2]> {
+    edges(p).find(|&[a, b]| edges(q).any(|edge| edge == [b, a]))
+}
+
+/// Merge two polygons along a shared edge
+///
+/// Returns `None`, if the merged polygon would not be simple. This happens, if
+/// the polygons share edges in more than one place, which would result in a
+/// polygon with a hole.
+fn merge_polygons(
+    p: &[usize],
+    q: &[usize],
+    [a, b]: [usize; 2],
+) -> Option<Vec<usize>> {
+    // Start `p` at `b`, so it ends at `a`, and start `q` at `a`, so it ends at
+    // `b`. Then the merged polygon goes around both of them.
+    let p = rotate_to(p, b);
+    let q = rotate_to(q, a);
+
+    let mut merged = p;
+    merged.extend_from_slice(&q[1..q.len() - 1]);
+
+    // If the polygons share more than one edge in a row, the merged polygon
+    // goes back and forth along the other shared edges. Remove those spikes.
+    loop {
+        let n = merged.len();
+        let spike = (0..n).find(|&k| merged[k] == merged[(k + 2) % n]);
+
+        match spike {
+            Some(k) if n > 3 => {
+                let [first, second] = {
+                    let mut indices = [(k + 1) % n, (k + 2) % n];
+                    indices.sort();
+                    indices
+                };
+                merged.remove(second);
+                merged.remove(first);
+            }
+            _ => break,
+        }
+    }
+
+    let mut sorted = merged.clone();
+    sorted.sort();
+    sorted.dedup();
+    let is_simple = sorted.len() == merged.len() && merged.len() >= 3;
+
+    is_simple.then_some(merged)
+}
+
+/// Remove one vertex that is between two collinear edges
+///
+/// The vertex is removed from all polygons that share it, which is only
+/// possible, if it is between the same collinear edges in all of them. Returns
+/// whether a vertex was removed.
+fn remove_collinear_vertex(
+    polyhedron: &mut Polyhedron,
+    tolerance: Scalar,
+) -> bool {
+    let num_vertices = polyhedron.vertices.len();
+
+    for vertex in 0..num_vertices {
+        let mut neighbors = Vec::new();
+        for polygon in &polyhedron.polygons {
+            let n = polygon.len();
+            if let Some(k) = polygon.iter().position(|&v| v == vertex) {
+                neighbors.push((
+                    [polygon[(k + n - 1) % n], polygon[(k + 1) % n]],
+                    n,
+                ));
+            }
+        }
+
+        let Some(&([u, w], _)) = neighbors.first() else {
+            continue;
+        };
+        let is_between_same_edges = neighbors.iter().all(|&([x, y], n)| {
+            n > 3 && ([x, y] == [u, w] || [x, y] == [w, u])
+        });
+        if !is_between_same_edges {
+            continue;
+        }
+
+        let [pu, pv, pw] =
+            [u, vertex, w].map(|index| polyhedron.position(index));
+        let direction = (pw - pu).normalize();
+        let offset = pv - pu;
+        let distance =
+            (offset - direction * offset.dot(&direction)).magnitude();
+        if distance > tolerance {
+            continue;
+        }
+
+        for polygon in &mut polyhedron.polygons {
+            polygon.retain(|&v| v != vertex);
+        }
+        return true;
+    }
+
+    false
+}
+
+/// Rotate a polygon, so it starts at the provided vertex
+fn rotate_to(polygon: &[usize], vertex: usize) -> Vec<usize> {
+    let start = polygon
+        .iter()
+        .position(|&v| v == vertex)
+        .expect("Vertex must be part of polygon");
+
+    let mut rotated = polygon.to_vec();
+    rotated.rotate_left(start);
+    rotated
+}
+
+/// Iterate over the edges of a polygon
+fn edges(polygon: &[usize]) -> impl Iterator<Item = [usize; 2]> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| [a, b])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{build::BuildShell, insert::Insert},
+        topology::Shell,
+        Core,
+    };
+
+    use super::MergeCoplanarFaces;
+
+    #[test]
+    fn merge_split_cube_faces() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A unit cube, whose top and front faces are each split in two, with
+        // vertices in the middle of the edges that cross the split.
+        let shell = Shell::from_vertices_and_polygons(
+            [
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [1., 1., 0.],
+                [0., 1., 0.],
+                [0., 0., 1.],
+                [1., 0., 1.],
+                [1., 1., 1.],
+                [0., 1., 1.],
+                [0.5, 0., 0.],
+                [0.5, 0., 1.],
+                [0.5, 1., 1.],
+            ],
+            [
+                vec![0, 3, 2, 1, 8],
+                vec![4, 9, 10, 7],
+                vec![9, 5, 6, 10],
+                vec![0, 8, 9, 4],
+                vec![8, 1, 5, 9],
+                vec![1, 2, 6, 5],
+                vec![2, 3, 7, 10, 6],
+                vec![3, 0, 4, 7],
+            ],
+            &mut core,
+        );
+
+        let shell = shell.merge_coplanar_faces(&mut core)?;
+
+        assert_eq!(shell.faces().len(), 6);
+        for face in shell.faces() {
+            assert_eq!(face.region().exterior().half_edges().len(), 4);
+        }
+
+        let _ = shell.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+}
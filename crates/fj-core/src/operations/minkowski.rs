@@ -0,0 +1,225 @@
+//! # Minkowski sums and offsets of solids
+//!
+//! See [`MinkowskiSum`].
+
+use fj_interop::Mesh;
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::Tolerance,
+        bounding_volume::BoundingVolume,
+        convex_hull::{convex_hull, ConvexHullError},
+        polygonize::Polygonize,
+        sdf::{SignedDistanceField, VoxelGrid},
+        triangulate::TriangulateFiltered,
+    },
+    operations::{build::BuildShell, insert::Insert},
+    topology::{Shell, Solid},
+    Core,
+};
+
+use super::{build::BuildSolid, update::UpdateSolid};
+
+/// Compute Minkowski sums of [`Solid`]s
+///
+/// Both operations in this trait work on approximations of the solids, and
+/// create solids whose faces are triangles.
+pub trait MinkowskiSum {
+    /// Compute the Minkowski sum of this solid and a convex solid
+    ///
+    /// The result is computed from the triangulations of both solids, using
+    /// the provided tolerance. It is the convex hull of the sums of all pairs
+    /// of their points.
+    ///
+    /// Both solids are treated as convex. If this solid is not, the result is
+    /// the Minkowski sum of its convex hull. Use [`MinkowskiSum::offset`] for
+    /// the Minkowski sum of a non-convex solid and a sphere.
+    fn minkowski_sum(
+        &self,
+        convex: &Solid,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Solid, ConvexHullError>;
+
+    /// Offset the surface of the solid by the provided distance
+    ///
+    /// A positive distance grows the solid, which is the Minkowski sum of the
+    /// solid and a sphere with that radius. This rounds all edges. A negative
+    /// distance shrinks the solid.
+    ///
+    /// The result is extracted from the solid's [signed distance field], which
+    /// is sampled on a grid with `resolution` cells along its longest axis.
+    /// Its accuracy depends on that resolution, and on the tolerance that is
+    /// used to approximate curved faces.
+    ///
+    /// [signed distance field]: SignedDistanceField
+    #[must_use]
+    fn offset(
+        &self,
+        distance: impl Into<Scalar>,
+        resolution: usize,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Solid;
+}
+
+impl MinkowskiSum for Solid {
+    fn minkowski_sum(
+        &self,
+        convex: &Solid,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Solid, ConvexHullError> {
+        let tolerance = tolerance.into();
+
+        let [a, b] = [self, convex].map(|solid| {
+            (solid, tolerance)
+                .triangulate_filtered(|_| true, core)
+                .vertices()
+                .collect::<Vec<_>>()
+        });
+
+        let sums = a
+            .iter()
+            .flat_map(|&a| b.iter().map(move |b| a + b.coords))
+            .collect::<Vec<_>>();
+
+        convex_hull(sums, core)
+    }
+
+    fn offset(
+        &self,
+        distance: impl Into<Scalar>,
+        resolution: usize,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Solid {
+        let mesh =
+            offset_mesh(self, distance.into(), resolution, tolerance, core);
+
+        let shell = Shell::from_mesh(&mesh, core).insert(core);
+        Solid::empty().add_shells([shell], core)
+    }
+}
+
+fn offset_mesh(
+    solid: &Solid,
+    distance: Scalar,
+    resolution: usize,
+    tolerance: impl Into<Tolerance>,
+    core: &mut Core,
+) -> Mesh<Point<3>> {
+    let Some(aabb) = solid.aabb(&core.layers.geometry) else {
+        return Mesh::new();
+    };
+
+    let sdf = SignedDistanceField::new(solid, tolerance, core);
+
+    // The grid needs to contain the offset surface, with some room to spare,
+    // so the surface is closed.
+    let size = aabb.size() + Vector::from([distance.abs() * 2.; 3]);
+    let longest = size
+        .components
+        .into_iter()
+        .max()
+        .expect("Vector has components");
+    let cell_size = longest / Scalar::from_u64(resolution.max(1) as u64);
+    let margin = Vector::from([distance.abs() + cell_size; 3]);
+
+    let grid_aabb = Aabb {
+        min: aabb.min - margin,
+        max: aabb.max + margin,
+    };
+    let grid_resolution = grid_aabb
+        .size()
+        .components
+        .map(|length| (length / cell_size).ceil().into_u64() as usize + 1);
+
+    VoxelGrid::from_fn(grid_aabb, grid_resolution, |point| {
+        sdf.distance(point, &core.layers.geometry) - distance
+    })
+    .polygonize()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point, Scalar};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::TriangulateFiltered},
+        operations::build::BuildSolid,
+        topology::Solid,
+        Core,
+    };
+
+    use super::{offset_mesh, MinkowskiSum};
+
+    #[test]
+    fn minkowski_sum_of_tetrahedra() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = Solid::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        )
+        .solid;
+        let b = Solid::tetrahedron(
+            [[2., 2., 2.], [2., 4., 2.], [4., 2., 2.], [2., 2., 4.]],
+            &mut core,
+        )
+        .solid;
+        let tolerance = Tolerance::from_scalar(1.)?;
+
+        let sum = a.minkowski_sum(&b, tolerance, &mut core)?;
+        let aabb = aabb_of_triangulation(&sum, &mut core)?;
+
+        assert_eq!(aabb.min, Point::from([2., 2., 2.]));
+        assert_eq!(aabb.max, Point::from([5., 5., 5.]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn offset_tetrahedron() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let tetrahedron = Solid::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        )
+        .solid;
+        let tolerance = Tolerance::from_scalar(1.)?;
+
+        // Converting the mesh into a solid takes a while, and doesn't
+        // change its shape. Test the mesh directly.
+        let mesh =
+            offset_mesh(&tetrahedron, 0.2.into(), 12, tolerance, &mut core);
+        let aabb = Aabb::<3>::from_points(mesh.vertices());
+
+        // The offset surface is only approximated by the grid.
+        let max_error = Scalar::from(0.15);
+        for (actual, expected) in
+            aabb.min.coords.components.into_iter().zip([-0.2; 3])
+        {
+            assert!((actual - Scalar::from(expected)).abs() < max_error);
+        }
+        for (actual, expected) in
+            aabb.max.coords.components.into_iter().zip([1.2; 3])
+        {
+            assert!((actual - Scalar::from(expected)).abs() < max_error);
+        }
+
+        Ok(())
+    }
+
+    fn aabb_of_triangulation(
+        solid: &Solid,
+        core: &mut Core,
+    ) -> anyhow::Result<Aabb<3>> {
+        let tolerance = Tolerance::from_scalar(1.)?;
+        let mesh = (solid, tolerance).triangulate_filtered(|_| true, core);
+
+        Ok(Aabb::<3>::from_points(mesh.vertices()))
+    }
+}
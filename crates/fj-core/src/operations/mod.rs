@@ -38,17 +38,31 @@
 //! assume that the code in question is outdated. Feel free to open an issue or
 //! send a pull request!
 
+pub mod boolean_2d;
+pub mod bridge;
 pub mod build;
 pub mod derive;
+pub mod draft;
 pub mod geometry;
 pub mod holes;
+pub mod imprint;
+pub mod infill;
 pub mod insert;
 pub mod join;
+pub mod material;
 pub mod merge;
+pub mod minkowski;
 pub mod presentation;
+pub mod project;
+pub mod remove_face;
 pub mod replace;
 pub mod reverse;
+pub mod sheet_metal;
 pub mod split;
 pub mod sweep;
+pub mod texture;
+pub mod thread;
 pub mod transform;
 pub mod update;
+
+mod polyhedron;
@@ -0,0 +1,303 @@
+//! # Shells whose faces are planar polygons
+//!
+//! Some operations are much simpler to implement on a polyhedron, a list of
+//! vertex positions and polygons, than on the full topology. This module
+//! converts between the two.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_math::{Point, Scalar, Tolerances, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfacePath},
+    operations::{
+        build::BuildShell,
+        material::{GetMaterial, SetMaterial},
+        presentation::{GetColor, SetColor},
+    },
+    storage::Handle,
+    topology::{Face, Shell, Vertex},
+    Core,
+};
+
+/// A shell, reduced to the positions of its vertices, and its polygons
+pub struct Polyhedron {
+    /// The vertices of the shell and their positions
+    ///
+    /// Operations can add positions to the polyhedron. Those don't have a
+    /// vertex, until the polyhedron is converted back into a shell.
+    pub vertices: Vec<(Option<Handle<Vertex>>, Point<3>)>,
+
+    /// The polygons, as indices into `vertices`
+    ///
+    /// Each polygon is counter-clockwise, when viewed from outside.
+    pub polygons: Vec<Vec<usize>>,
+
+    /// The face that each polygon originates from
+    pub faces: Vec<Handle<Face>>,
+}
+
+impl Polyhedron {
+    /// Convert a shell into a polyhedron
+    ///
+    /// Returns the first face that isn't a planar polygon without holes, if
+    /// there is one.
+    pub fn from_shell(
+        shell: &Shell,
+        core: &Core,
+    ) -> Result<Self, Handle<Face>> {
+        let geometry = &core.layers.geometry;
+
+        let mut indices = BTreeMap::new();
+        let mut vertices = Vec::new();
+        let mut polygons = Vec::new();
+
+        for face in shell.faces() {
+            let surface = geometry.of_surface(face.surface());
+            let is_polygon = matches!(surface.u, GlobalPath::Line(_))
+                && face.region().interiors().is_empty()
+                && face.region().exterior().half_edges().iter().all(
+                    |half_edge| {
+                        matches!(
+                            geometry.of_half_edge(half_edge).path,
+                            SurfacePath::Line(_)
+                        )
+                    },
+                );
+            if !is_polygon {
+                return Err(face.clone());
+            }
+
+            let polygon = face
+                .region()
+                .exterior()
+                .half_edges()
+                .iter()
+                .map(|half_edge| {
+                    let vertex = half_edge.start_vertex();
+                    *indices.entry(vertex.clone()).or_insert_with(|| {
+                        let position = surface.point_from_surface_coords(
+                            geometry.of_half_edge(half_edge).start_position(),
+                        );
+                        vertices.push((Some(vertex.clone()), position));
+                        vertices.len() - 1
+                    })
+                })
+                .collect();
+
+            polygons.push(polygon);
+        }
+
+        Ok(Self {
+            vertices,
+            polygons,
+            faces: shell.faces().iter().cloned().collect(),
+        })
+    }
+
+    /// Access the position of the vertex with the provided index
+    pub fn position(&self, index: usize) -> Point<3> {
+        let (_, position) = self.vertices[index];
+        position
+    }
+
+    /// Find the vertex at the provided position, or add it
+    ///
+    /// Vertices that are within `tolerance` of the position are considered to
+    /// be at that position.
+    pub fn vertex_at(
+        &mut self,
+        position: Point<3>,
+        tolerance: Scalar,
+    ) -> usize {
+        let existing = self
+            .vertices
+            .iter()
+            .position(|(_, p)| p.distance_to(&position) <= tolerance);
+
+        existing.unwrap_or_else(|| {
+            self.vertices.push((None, position));
+            self.vertices.len() - 1
+        })
+    }
+
+    /// Split the edges of polygons at vertices of other polygons
+    ///
+    /// After an operation splits polygons, their new vertices can be located
+    /// on the edges of neighboring polygons. To keep the shell closed, those
+    /// vertices are inserted into the neighboring polygons too.
+    pub fn split_edges_at_vertices(&mut self, tolerance: Scalar) {
+        let used = self
+            .polygons
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        for i in 0..self.polygons.len() {
+            let polygon = &self.polygons[i];
+            let mut split = Vec::new();
+
+            for (&a, &b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+                split.push(a);
+
+                let [pa, pb] = [a, b].map(|index| self.position(index));
+                let direction = pb - pa;
+                let length = direction.magnitude();
+
+                let mut on_edge = used
+                    .iter()
+                    .filter(|&&index| index != a && index != b)
+                    .filter_map(|&index| {
+                        let point = self.position(index);
+                        let t = (point - pa).dot(&direction) / length;
+                        let distance =
+                            (pa + direction / length * t).distance_to(&point);
+
+                        let is_on_edge = distance <= tolerance
+                            && t > tolerance
+                            && t < length - tolerance;
+                        is_on_edge.then_some((t, index))
+                    })
+                    .collect::<Vec<_>>();
+                on_edge.sort();
+
+                split.extend(on_edge.into_iter().map(|(_, index)| index));
+            }
+
+            self.polygons[i] = split;
+        }
+    }
+
+    /// Compute the plane of a polygon
+    ///
+    /// Returns the plane's unit normal and its distance from the origin. Uses
+    /// Newell's method, so the normal points to the side from which the
+    /// polygon appears counter-clockwise.
+    pub fn plane_of(&self, polygon: &[usize]) -> (Vector<3>, Scalar) {
+        let points = polygon
+            .iter()
+            .map(|&index| self.position(index))
+            .collect::<Vec<_>>();
+
+        let normal = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .fold(Vector::from([0., 0., 0.]), |normal, (a, b)| {
+                normal + a.coords.cross(&b.coords)
+            })
+            .normalize();
+
+        (normal, normal.dot(&points[0].coords))
+    }
+
+    /// Convert the polyhedron back into a shell
+    ///
+    /// Each face gets the color and material of the face that its polygon
+    /// originates from.
+    pub fn into_shell(self, core: &mut Core) -> Shell {
+        let shell = Shell::from_vertices_and_polygons(
+            self.vertices.iter().map(|&(_, position)| position),
+            self.polygons,
+            core,
+        );
+
+        for (before, after) in self.faces.iter().zip(shell.faces()) {
+            if let Some(color) = before.region().get_color(core) {
+                after.region().set_color(color, core);
+            }
+            if let Some(material) = before.region().get_material(core) {
+                after.region().set_material(material, core);
+            }
+        }
+
+        shell
+    }
+}
+
+/// Find the point that is closest to all of the provided planes
+///
+/// Solves the linear least squares problem, which results in the intersection
+/// point, if the planes intersect in one. Returns `None`, if the planes don't
+/// define a single point, because they are all parallel to one line, within
+/// the angular tolerance.
+pub fn intersect_planes(
+    planes: &[(Vector<3>, Scalar)],
+    tolerances: &Tolerances,
+) -> Option<Point<3>> {
+    // The columns of the normal matrix, and the right-hand side of the normal
+    // equations.
+    let mut columns = [Vector::from([0., 0., 0.]); 3];
+    let mut rhs = Vector::from([0., 0., 0.]);
+    for (normal, distance) in planes {
+        for (column, component) in columns.iter_mut().zip(normal.components) {
+            *column = *column + *normal * component;
+        }
+        rhs = rhs + *normal * *distance;
+    }
+
+    // If the normals are all perpendicular to one direction, within the
+    // angular tolerance, the smallest eigenvalue of the normal matrix is at
+    // most `n * sin(angular)^2`, for `n` planes. The other two are at most `n`
+    // each, which bounds the determinant.
+    let n = Scalar::from_u64(planes.len() as u64);
+    let sin_angular = tolerances.angular.sin_cos().0;
+    let min_determinant = n * n * n * sin_angular * sin_angular;
+
+    // Cramer's rule
+    let [c0, c1, c2] = columns;
+    let determinant = c0.dot(&c1.cross(&c2));
+    if determinant.abs() <= min_determinant {
+        return None;
+    }
+
+    Some(Point::from([
+        rhs.dot(&c1.cross(&c2)) / determinant,
+        c0.dot(&rhs.cross(&c2)) / determinant,
+        c0.dot(&c1.cross(&rhs)) / determinant,
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Tolerances, Vector};
+
+    use super::intersect_planes;
+
+    #[test]
+    fn intersect_planes_in_point() {
+        let tolerances = Tolerances::default();
+
+        let planes = [
+            (Vector::from([1., 0., 0.]), Scalar::from(1.)),
+            (Vector::from([0., 1., 0.]), Scalar::from(2.)),
+            (Vector::from([0., 0., 1.]), Scalar::from(3.)),
+        ];
+        assert_eq!(
+            intersect_planes(&planes, &tolerances),
+            Some(Point::from([1., 2., 3.]))
+        );
+    }
+
+    #[test]
+    fn intersect_planes_that_are_parallel_to_line() {
+        let tolerances = Tolerances::default();
+
+        // All of these planes are parallel to the z-axis, within the angular
+        // tolerance.
+        let tilt = tolerances.angular.into_f64() / 2.;
+        let planes = [
+            (Vector::from([1., 0., 0.]), Scalar::from(1.)),
+            (Vector::from([0., 1., 0.]), Scalar::from(2.)),
+            (Vector::from([1., 1., tilt]).normalize(), Scalar::from(3.)),
+        ];
+        assert_eq!(intersect_planes(&planes, &tolerances), None);
+
+        // With a finer angular tolerance, they intersect in a single point.
+        let finer = Tolerances {
+            angular: tolerances.angular / 1000.,
+            ..tolerances
+        };
+        assert!(intersect_planes(&planes, &finer).is_some());
+    }
+}
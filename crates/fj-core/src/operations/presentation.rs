@@ -7,11 +7,11 @@ use crate::{storage::Handle, topology::Region, Core};
 /// Get the color of an object
 pub trait GetColor {
     /// Get the color of the object
-    fn get_color(&self, core: &mut Core) -> Option<Color>;
+    fn get_color(&self, core: &Core) -> Option<Color>;
 }
 
 impl GetColor for Handle<Region> {
-    fn get_color(&self, core: &mut Core) -> Option<Color> {
+    fn get_color(&self, core: &Core) -> Option<Color> {
         core.layers.presentation.color.get(self).copied()
     }
 }
@@ -0,0 +1,457 @@
+//! # Operations to project shapes onto surfaces
+//!
+//! See [`ProjectOntoSurface`].
+
+use fj_math::{predicates, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::approx::Tolerance,
+    geometry::{
+        GlobalPath, HalfEdgeGeom, LocalCurveGeom, SurfaceGeom, SurfacePath,
+    },
+    operations::{build::BuildHalfEdge, insert::Insert},
+    storage::Handle,
+    topology::{Cycle, Face, HalfEdge, Region, Sketch, Surface},
+    Core,
+};
+
+/// Project a shape onto a surface
+///
+/// This is implemented for shapes that know their surface, like [`Face`] and
+/// [`Sketch`], and for tuples of a shape and the surface it is defined on.
+pub trait ProjectOntoSurface {
+    /// The projected shape
+    type Projected;
+
+    /// Project the shape onto the provided surface
+    ///
+    /// Each point of the shape is moved to the point on the surface that it
+    /// projects to (see [`SurfaceGeom::project_global_point`]), and the
+    /// resulting curves are defined in the surface coordinates of `surface`.
+    ///
+    /// Where a projected curve is a line or circle in those coordinates, it is
+    /// represented exactly. Otherwise, it is approximated using line segments,
+    /// within the provided tolerance.
+    #[must_use]
+    fn project_onto_surface(
+        self,
+        surface: Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Self::Projected;
+}
+
+impl ProjectOntoSurface for (&Cycle, &Handle<Surface>) {
+    type Projected = Cycle;
+
+    fn project_onto_surface(
+        self,
+        surface: Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Self::Projected {
+        let (cycle, from) = self;
+        let tolerance = tolerance.into();
+
+        let mut half_edges = Vec::new();
+        let mut previous_end = None;
+
+        for half_edge in cycle.half_edges() {
+            let projection = Projection {
+                from: *core.layers.geometry.of_surface(from),
+                onto: *core.layers.geometry.of_surface(&surface),
+                half_edge: *core.layers.geometry.of_half_edge(half_edge),
+                tolerance: tolerance.inner(),
+            };
+
+            for segment in projection.segments(previous_end) {
+                previous_end = Some(segment.end());
+                half_edges.push(segment.build(surface.clone(), core));
+            }
+        }
+
+        Cycle::new(half_edges)
+    }
+}
+
+impl ProjectOntoSurface for (&Region, &Handle<Surface>) {
+    type Projected = Region;
+
+    fn project_onto_surface(
+        self,
+        surface: Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Self::Projected {
+        let (region, from) = self;
+        let tolerance = tolerance.into();
+
+        let exterior = (&**region.exterior(), from)
+            .project_onto_surface(surface.clone(), tolerance, core)
+            .insert(core);
+        let interiors = region
+            .interiors()
+            .iter()
+            .map(|cycle| {
+                (&**cycle, from)
+                    .project_onto_surface(surface.clone(), tolerance, core)
+                    .insert(core)
+            })
+            .collect::<Vec<_>>();
+
+        Region::new(exterior, interiors)
+    }
+}
+
+impl ProjectOntoSurface for &Face {
+    type Projected = Face;
+
+    fn project_onto_surface(
+        self,
+        surface: Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Self::Projected {
+        let region = (&**self.region(), self.surface())
+            .project_onto_surface(surface.clone(), tolerance, core)
+            .insert(core);
+
+        Face::new(surface, region)
+    }
+}
+
+impl ProjectOntoSurface for &Sketch {
+    type Projected = Sketch;
+
+    fn project_onto_surface(
+        self,
+        surface: Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Self::Projected {
+        let tolerance = tolerance.into();
+
+        let regions = self
+            .regions()
+            .iter()
+            .map(|region| {
+                (&**region, self.surface())
+                    .project_onto_surface(surface.clone(), tolerance, core)
+                    .insert(core)
+            })
+            .collect::<Vec<_>>();
+
+        Sketch::new(surface, regions)
+    }
+}
+
+/// The projection of a single half-edge
+struct Projection {
+    from: SurfaceGeom,
+    onto: SurfaceGeom,
+    half_edge: HalfEdgeGeom,
+    tolerance: Scalar,
+}
+
+impl Projection {
+    /// The number of samples used to check, whether a projection is exact
+    const NUM_SAMPLES: usize = 16;
+
+    /// The maximum depth of subdivision, when approximating a projection
+    const MAX_DEPTH: usize = 16;
+
+    /// Compute the segments that make up the projected half-edge
+    ///
+    /// If the surface is periodic, `near` selects between the equivalent
+    /// coordinates of the start point, so it connects to the previous segment.
+    fn segments(&self, near: Option<Point<2>>) -> Vec<Segment> {
+        let start = self.point(Scalar::ZERO, near);
+
+        let mut samples = vec![(Scalar::ZERO, start)];
+        for i in 1..=Self::NUM_SAMPLES {
+            let t = Scalar::from_u64(i as u64)
+                / Scalar::from_u64(Self::NUM_SAMPLES as u64);
+            let (_, previous) = samples[i - 1];
+            samples.push((t, self.point(t, Some(previous))));
+        }
+        let points =
+            samples.iter().map(|&(_, point)| point).collect::<Vec<_>>();
+        let end = points[Self::NUM_SAMPLES];
+
+        if self.fits_line([start, end], &points) {
+            return vec![Segment::Line([start, end])];
+        }
+        if let Some(arc) = self.fit_arc(&points) {
+            return vec![arc];
+        }
+
+        let mut segments = Vec::new();
+        for window in samples.windows(2) {
+            let [a, b] = [window[0], window[1]];
+            self.subdivide(a, b, 0, &mut segments);
+        }
+        segments
+    }
+
+    /// Approximate the projection between two samples with line segments
+    fn subdivide(
+        &self,
+        (t_a, a): (Scalar, Point<2>),
+        (t_b, b): (Scalar, Point<2>),
+        depth: usize,
+        segments: &mut Vec<Segment>,
+    ) {
+        let t_mid = (t_a + t_b) / 2.;
+        let mid = self.point(t_mid, Some(a));
+
+        let checks = [(t_a + t_mid) / 2., (t_mid + t_b) / 2.]
+            .map(|t| self.point(t, Some(a)));
+        let points = [checks[0], mid, checks[1]];
+
+        if depth >= Self::MAX_DEPTH || self.fits_line([a, b], &points) {
+            segments.push(Segment::Line([a, b]));
+            return;
+        }
+
+        self.subdivide((t_a, a), (t_mid, mid), depth + 1, segments);
+        self.subdivide((t_mid, mid), (t_b, b), depth + 1, segments);
+    }
+
+    /// Project the point at the provided fraction of the half-edge
+    ///
+    /// On periodic surfaces, the coordinates closest to `near` are returned.
+    fn point(&self, t: Scalar, near: Option<Point<2>>) -> Point<2> {
+        let [start, end] = self.half_edge.boundary.inner;
+        let point_curve = start + (end - start) * t;
+        let point_surface =
+            self.half_edge.path.point_from_path_coords(point_curve);
+        let point_global = self.from.point_from_surface_coords(point_surface);
+
+        let mut point = self.onto.project_global_point(point_global);
+        if let (GlobalPath::Circle(_), Some(near)) = (self.onto.u, near) {
+            point.u = near.u + (point.u - near.u).wrap_angle();
+        }
+
+        point
+    }
+
+    /// Compute the distance between two points on the surface
+    fn distance(&self, a: Point<2>, b: Point<2>) -> Scalar {
+        self.onto
+            .point_from_surface_coords(a)
+            .distance_to(&self.onto.point_from_surface_coords(b))
+    }
+
+    /// Check whether the points are on the line segment between `a` and `b`
+    fn fits_line(&self, [a, b]: [Point<2>; 2], points: &[Point<2>]) -> bool {
+        let direction = b - a;
+        let length_squared = direction.dot(&direction);
+
+        points.iter().all(|&point| {
+            let closest = if length_squared == Scalar::ZERO {
+                a
+            } else {
+                let t = (point - a).dot(&direction) / length_squared;
+                a + direction * t.max(Scalar::ZERO).min(Scalar::ONE)
+            };
+
+            self.distance(point, closest) <= self.tolerance
+        })
+    }
+
+    /// Fit an arc to the points, if they are on a circle
+    fn fit_arc(&self, points: &[Point<2>]) -> Option<Segment> {
+        let n = points.len() - 1;
+        let [a, b, c] = [0, n / 3, n * 2 / 3].map(|i| points[i]);
+        if predicates::are_collinear([a, b, c]) {
+            return None;
+        }
+
+        let path = circle_through([a, b, c]);
+
+        let mut angles = Vec::new();
+        for &point in points {
+            let angle = path.project_point(point).t;
+            let angle = match angles.last() {
+                Some(&previous) => previous + (angle - previous).wrap_angle(),
+                None => angle,
+            };
+
+            let on_circle = path.point_from_path_coords([angle]);
+            if self.distance(point, on_circle) > self.tolerance {
+                return None;
+            }
+
+            angles.push(angle);
+        }
+
+        Some(Segment::Arc {
+            path,
+            boundary: [angles[0], angles[n]],
+        })
+    }
+}
+
+/// A segment of a projected half-edge
+enum Segment {
+    Line([Point<2>; 2]),
+    Arc {
+        path: SurfacePath,
+        boundary: [Scalar; 2],
+    },
+}
+
+impl Segment {
+    fn end(&self) -> Point<2> {
+        match self {
+            Self::Line([_, end]) => *end,
+            Self::Arc { path, boundary } => {
+                path.point_from_path_coords([boundary[1]])
+            }
+        }
+    }
+
+    fn build(
+        self,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Handle<HalfEdge> {
+        match self {
+            Self::Line(points) => {
+                HalfEdge::line_segment(points, None, surface, core)
+            }
+            Self::Arc { path, boundary } => {
+                let half_edge = HalfEdge::unjoined(core).insert(core);
+
+                core.layers.geometry.define_curve(
+                    half_edge.curve().clone(),
+                    surface,
+                    LocalCurveGeom { path },
+                );
+                core.layers.geometry.define_half_edge(
+                    half_edge.clone(),
+                    HalfEdgeGeom {
+                        path,
+                        boundary: boundary.map(|t| Point::from([t])).into(),
+                    },
+                );
+
+                half_edge
+            }
+        }
+    }
+}
+
+/// Compute the circle through three points that are not collinear
+fn circle_through([a, b, c]: [Point<2>; 3]) -> SurfacePath {
+    let [ab, ac] = [b - a, c - a];
+    let [ab2, ac2] = [ab.dot(&ab), ac.dot(&ac)];
+    let d = (ab.u * ac.v - ab.v * ac.u) * 2.;
+
+    let center = a + Vector::from([
+        (ac.v * ab2 - ab.v * ac2) / d,
+        (ab.u * ac2 - ac.u * ab2) / d,
+    ]);
+    let radius = center.distance_to(&a);
+
+    SurfacePath::circle_from_center_and_radius(center, radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::approx::Tolerance,
+        geometry::{GlobalPath, SurfacePath},
+        operations::build::{BuildCycle, BuildSurface},
+        topology::{Cycle, Surface},
+        Core,
+    };
+
+    use super::ProjectOntoSurface;
+
+    #[test]
+    fn project_circle_onto_parallel_plane() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let xy_plane = core.layers.topology.surfaces.xy_plane();
+        let circle = Cycle::circle([1., 2.], 0.5, xy_plane.clone(), &mut core);
+
+        let (plane, _) = Surface::plane_from_points(
+            [[0., 0., 3.], [1., 0., 3.], [0., 1., 3.]],
+            &mut core,
+        );
+        let tolerance = Tolerance::from_scalar(0.001)?;
+        let projected = (&circle, &xy_plane)
+            .project_onto_surface(plane, tolerance, &mut core);
+
+        let [half_edge] = projected.half_edges().iter().collect::<Vec<_>>()[..]
+        else {
+            panic!("Expected projected circle to be a single half-edge");
+        };
+        let geometry = core.layers.geometry.of_half_edge(half_edge);
+        let SurfacePath::Circle(circle) = geometry.path else {
+            panic!("Expected projected circle to be a circle");
+        };
+        assert!(
+            circle.center().distance_to(&Point::from([1., 2.]))
+                < Scalar::from(1e-9)
+        );
+        assert!((circle.radius() - 0.5).abs() < Scalar::from(1e-9));
+
+        let [start, end] = geometry.boundary.inner;
+        assert!(
+            ((end - start).t.abs() - Scalar::TAU).abs() < Scalar::from(1e-9)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn project_square_onto_cylinder() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A square on a plane at x = 2, crossing the seam of the cylinder.
+        let (plane, _) = Surface::plane_from_points(
+            [[2., 0., 0.], [2., 1., 0.], [2., 0., 1.]],
+            &mut core,
+        );
+        let square = Cycle::polygon(
+            [[-0.5, 0.], [0.5, 0.], [0.5, 1.], [-0.5, 1.]],
+            plane.clone(),
+            &mut core,
+        );
+
+        let cylinder = Surface::from_uv(
+            GlobalPath::circle_from_radius(1.),
+            [0., 0., 1.],
+            &mut core,
+        );
+        let tolerance = Tolerance::from_scalar(0.001)?;
+        let projected = (&square, &plane)
+            .project_onto_surface(cylinder, tolerance, &mut core);
+
+        // The edges of the square project to lines in the surface coordinates
+        // of the cylinder, and connect to each other across its seam.
+        let half_edges = projected.half_edges();
+        assert_eq!(half_edges.len(), 4);
+
+        let mut previous_end = None;
+        for half_edge in half_edges {
+            let geometry = core.layers.geometry.of_half_edge(half_edge);
+            assert!(matches!(geometry.path, SurfacePath::Line(_)));
+
+            let [start, end] = geometry
+                .boundary
+                .inner
+                .map(|point| geometry.path.point_from_path_coords(point));
+            if let Some(previous_end) = previous_end {
+                assert!(start.distance_to(&previous_end) < Scalar::from(1e-9));
+            }
+            previous_end = Some(end);
+        }
+
+        Ok(())
+    }
+}
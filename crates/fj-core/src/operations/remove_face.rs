@@ -0,0 +1,908 @@
+//! # Operations to remove faces from shapes
+//!
+//! See [`RemoveFace`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Circle, Point, Scalar, Tolerances, Vector};
+
+use crate::{
+    geometry::{
+        CurveBoundary, Geometry, GlobalPath, HalfEdgeGeom, SurfaceGeom,
+        SurfacePath, SurfaceProfile,
+    },
+    operations::{
+        derive::DeriveFrom,
+        geometry::{UpdateCurveGeometry, UpdateHalfEdgeGeometry},
+        insert::Insert,
+        polyhedron::intersect_planes,
+    },
+    queries::{BoundingVerticesOfHalfEdge, SiblingOfHalfEdge},
+    storage::{Handle, ObjectId},
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+/// Remove faces from a shape, and heal the resulting hole
+///
+/// This is used to remove features, like chamfers, from a shape without
+/// rebuilding it.
+pub trait RemoveFace: Sized {
+    /// Remove the provided faces
+    ///
+    /// The hole left by each face is closed by extending its neighboring faces,
+    /// until they meet. For that to work, the removed face needs to collapse
+    /// into a single vertex, where all of its neighbors meet, or a single edge,
+    /// where two of its neighbors meet. This is the case for chamfers, for
+    /// example.
+    ///
+    /// The neighbors of a removed face, the faces that share a vertex with it,
+    /// must be planes or cylinders. They are rebuilt, while all other faces of
+    /// the shape are kept as they are. An edge that the removed face collapses
+    /// into has the same shape as the edges of the neighbors that it replaces,
+    /// like a line, or a circle around the same center.
+    ///
+    /// Faces that are not part of the shape are ignored.
+    fn remove_faces(
+        &self,
+        faces: &[Handle<Face>],
+        core: &mut Core,
+    ) -> Result<Self, RemoveFaceError>;
+}
+
+impl RemoveFace for Shell {
+    fn remove_faces(
+        &self,
+        faces: &[Handle<Face>],
+        core: &mut Core,
+    ) -> Result<Self, RemoveFaceError> {
+        let mut shell = self.clone();
+
+        // Removing a face rebuilds its neighbors, which might be removed later.
+        let mut rebuilt = BTreeMap::new();
+
+        for face in faces {
+            let mut face = face.clone();
+            while let Some(neighbor) = rebuilt.get(&face.id()) {
+                face = Handle::clone(neighbor);
+            }
+
+            if shell.faces().index_of(&face).is_none() {
+                continue;
+            }
+
+            shell = remove_face(&shell, &face, &mut rebuilt, core)?;
+        }
+
+        Ok(shell)
+    }
+}
+
+impl RemoveFace for Solid {
+    fn remove_faces(
+        &self,
+        faces: &[Handle<Face>],
+        core: &mut Core,
+    ) -> Result<Self, RemoveFaceError> {
+        let shells = self
+            .shells()
+            .iter()
+            .map(|shell| Ok(shell.remove_faces(faces, core)?.insert(core)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Solid::new(shells))
+    }
+}
+
+/// Error removing faces from a shape
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum RemoveFaceError {
+    /// A removed face has holes, or one of its neighbors is not supported
+    ///
+    /// Neighbors of a removed face must be planes or cylinders.
+    #[error("Removing faces only supports planes and cylinders: {face:?}")]
+    UnsupportedFace {
+        /// The face that is not supported
+        face: Handle<Face>,
+    },
+
+    /// The neighbors of a face can't be extended to close the hole
+    ///
+    /// This happens, if the face doesn't collapse into a vertex or an edge.
+    #[error("Can't close hole left by removing face: {face:?}")]
+    CantHeal {
+        /// The face whose hole can't be closed
+        face: Handle<Face>,
+    },
+}
+
+/// Remove a face, and extend its neighbors to close the hole
+///
+/// The rebuilt neighbors are recorded in `rebuilt`, by the ID of the face they
+/// replace.
+fn remove_face(
+    shell: &Shell,
+    face: &Handle<Face>,
+    rebuilt: &mut BTreeMap<ObjectId, Handle<Face>>,
+    core: &mut Core,
+) -> Result<Shell, RemoveFaceError> {
+    let cant_heal = || RemoveFaceError::CantHeal { face: face.clone() };
+
+    if !face.region().interiors().is_empty() {
+        return Err(RemoveFaceError::UnsupportedFace { face: face.clone() });
+    }
+
+    let tolerances = core.tolerances();
+    let geometry = &core.layers.geometry;
+    let half_edges = face.region().exterior().half_edges();
+
+    // The vertices of the face, in the order of its half-edges.
+    let mut vertices: Vec<Handle<Vertex>> = Vec::new();
+    let mut positions = BTreeMap::new();
+    for half_edge in half_edges {
+        let vertex = half_edge.start_vertex();
+        if positions.contains_key(&vertex.id()) {
+            continue;
+        }
+
+        let position = geometry
+            .of_surface(face.surface())
+            .point_from_surface_coords(
+                geometry.of_half_edge(half_edge).start_position(),
+            );
+        positions.insert(vertex.id(), position);
+        vertices.push(vertex.clone());
+    }
+
+    let neighbors = shell
+        .faces()
+        .iter()
+        .filter(|f| {
+            f.id() != face.id()
+                && f.region().all_cycles().any(|cycle| {
+                    cycle.half_edges().iter().any(|half_edge| {
+                        positions.contains_key(&half_edge.start_vertex().id())
+                    })
+                })
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    for neighbor in &neighbors {
+        let surface = geometry.of_surface(neighbor.surface());
+        let is_supported = match (surface.u, surface.profile) {
+            (GlobalPath::Line(_), _) => true,
+            (GlobalPath::Circle(_), SurfaceProfile::Linear { taper }) => {
+                taper == Scalar::ZERO
+            }
+            (GlobalPath::Circle(_), SurfaceProfile::Spherical)
+            | (GlobalPath::Circle(_), SurfaceProfile::Toroidal) => false,
+        };
+        if !is_supported {
+            return Err(RemoveFaceError::UnsupportedFace {
+                face: neighbor.clone(),
+            });
+        }
+    }
+
+    // The removed face collapses, and its vertices merge into new vertices.
+    // Try to merge them all into one vertex, and then each way to split them
+    // into two runs of adjacent vertices, which merge into the two vertices of
+    // a new edge.
+    let num_vertices = vertices.len();
+    let mut candidates = vec![vec![(0..num_vertices).collect::<Vec<_>>()]];
+    for i in 0..num_vertices {
+        for j in i + 1..num_vertices {
+            let (first, second): (Vec<_>, Vec<_>) =
+                (0..num_vertices).partition(|&k| k > i && k <= j);
+            candidates.push(vec![first, second]);
+        }
+    }
+
+    let merged = candidates
+        .into_iter()
+        .find_map(|groups| {
+            let groups = groups
+                .into_iter()
+                .map(|group| {
+                    group.into_iter().map(|i| &vertices[i]).collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            merge_groups(&groups, &positions, &neighbors, geometry, &tolerances)
+        })
+        .ok_or_else(cant_heal)?;
+    let group_of = |vertex: &Handle<Vertex>| {
+        let (representative, _) = &merged[&vertex.id()];
+        representative.id()
+    };
+
+    // Half-edges of the removed face that connect two groups, or that are
+    // closed, don't collapse. Their siblings in the neighbors are joined into
+    // one edge. The siblings of all other half-edges disappear.
+    let mut dropped = BTreeSet::new();
+    let mut joined = Vec::new();
+    for (half_edge, next) in half_edges.pairs() {
+        let [start, end] = [half_edge, next].map(|h| h.start_vertex());
+
+        let sibling = shell
+            .get_sibling_of(half_edge, geometry)
+            .ok_or_else(cant_heal)?;
+
+        if start.id() != end.id() && group_of(start) == group_of(end) {
+            dropped.insert(sibling.id());
+        } else {
+            let neighbor = neighbors
+                .iter()
+                .find(|neighbor| {
+                    neighbor.region().all_cycles().any(|cycle| {
+                        cycle.half_edges().index_of(&sibling).is_some()
+                    })
+                })
+                .ok_or_else(cant_heal)?;
+            joined.push((sibling, neighbor.surface().clone()));
+        }
+    }
+
+    let mut replacements = BTreeMap::new();
+    match joined.as_slice() {
+        [] => {}
+        [a, b] => {
+            let new =
+                join_half_edges([a, b], shell, &merged, core, &tolerances)
+                    .ok_or_else(cant_heal)?;
+            for ((old, _), new) in [a, b].into_iter().zip(new) {
+                replacements.insert(old.id(), new);
+            }
+        }
+        _ => return Err(cant_heal()),
+    }
+
+    // Only the neighbors need to be rebuilt. All of their half-edges that
+    // start or end at a merged vertex are extended to it.
+    let mut boundaries = BoundariesOfCurves::default();
+    for neighbor in &neighbors {
+        let surface = *core.layers.geometry.of_surface(neighbor.surface());
+
+        let mut cycles = Vec::new();
+        for cycle in neighbor.region().all_cycles() {
+            let mut half_edges = Vec::new();
+
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                if dropped.contains(&half_edge.id()) {
+                    continue;
+                }
+                if let Some(replacement) = replacements.get(&half_edge.id()) {
+                    half_edges.push(Handle::clone(replacement));
+                    continue;
+                }
+
+                let [start, end] = [half_edge, next]
+                    .map(|h| merged.get(&h.start_vertex().id()));
+                if start.is_none() && end.is_none() {
+                    half_edges.push(half_edge.clone());
+                    continue;
+                }
+
+                let HalfEdgeGeom { path, boundary } =
+                    *core.layers.geometry.of_half_edge(half_edge);
+                let boundary = match boundaries.of_sibling(half_edge, boundary)
+                {
+                    Some(boundary) => boundary,
+                    None => {
+                        let is_closed = half_edge.start_vertex().id()
+                            == next.start_vertex().id();
+                        let new = extend_boundary(
+                            path,
+                            boundary,
+                            &surface,
+                            [start, end].map(|merged| {
+                                merged.map(|&(_, position)| position)
+                            }),
+                            is_closed,
+                            &tolerances,
+                        )
+                        .ok_or_else(cant_heal)?;
+
+                        boundaries.insert(half_edge, boundary, new);
+                        new
+                    }
+                };
+
+                let start_vertex = match start {
+                    Some((vertex, _)) => vertex.clone(),
+                    None => half_edge.start_vertex().clone(),
+                };
+
+                half_edges.push(
+                    HalfEdge::new(half_edge.curve().clone(), start_vertex)
+                        .insert(core)
+                        .derive_from(half_edge, core)
+                        .set_geometry(
+                            HalfEdgeGeom { path, boundary },
+                            &mut core.layers.geometry,
+                        ),
+                );
+            }
+
+            cycles.push((cycle, half_edges));
+        }
+
+        let mut cycles = cycles.into_iter();
+        let (exterior, exterior_half_edges) =
+            cycles.next().expect("Region always has an exterior");
+
+        // A face that collapses along with the removed one would leave behind
+        // half-edges without a sibling.
+        let is_collapsed = exterior_half_edges.len() < 3
+            && exterior_half_edges.iter().all(|half_edge| {
+                matches!(
+                    core.layers.geometry.of_half_edge(half_edge).path,
+                    SurfacePath::Line(_)
+                )
+            });
+        if is_collapsed {
+            return Err(cant_heal());
+        }
+
+        let exterior = Cycle::new(exterior_half_edges)
+            .insert(core)
+            .derive_from(exterior, core);
+        let interiors = cycles
+            .filter(|(_, half_edges)| !half_edges.is_empty())
+            .map(|(interior, half_edges)| {
+                Cycle::new(half_edges)
+                    .insert(core)
+                    .derive_from(interior, core)
+            })
+            .collect::<Vec<_>>();
+
+        let region = Region::new(exterior, interiors)
+            .insert(core)
+            .derive_from(neighbor.region(), core);
+        let new = Face::new(neighbor.surface().clone(), region)
+            .insert(core)
+            .derive_from(neighbor, core);
+
+        rebuilt.insert(neighbor.id(), new);
+    }
+
+    let faces = shell
+        .faces()
+        .iter()
+        .filter(|f| f.id() != face.id())
+        .map(|f| rebuilt.get(&f.id()).unwrap_or(f).clone());
+
+    Ok(Shell::new(faces))
+}
+
+/// Compute the positions that groups of vertices merge into
+///
+/// Each group merges into the point where the surfaces of all neighbors that
+/// share its vertices meet. Returns the first vertex of the group and that
+/// point, for each vertex. Returns `None`, if there is no such point for any
+/// group, or if the groups merge into the same point.
+fn merge_groups(
+    groups: &[Vec<&Handle<Vertex>>],
+    positions: &BTreeMap<ObjectId, Point<3>>,
+    neighbors: &[Handle<Face>],
+    geometry: &Geometry,
+    tolerances: &Tolerances,
+) -> Option<BTreeMap<ObjectId, (Handle<Vertex>, Point<3>)>> {
+    let mut merged = BTreeMap::new();
+    let mut points: Vec<Point<3>> = Vec::new();
+
+    for group in groups {
+        let surfaces = neighbors
+            .iter()
+            .filter(|neighbor| {
+                neighbor.region().all_cycles().any(|cycle| {
+                    cycle.half_edges().iter().any(|half_edge| {
+                        group.iter().any(|vertex| {
+                            vertex.id() == half_edge.start_vertex().id()
+                        })
+                    })
+                })
+            })
+            .map(|neighbor| *geometry.of_surface(neighbor.surface()))
+            .collect::<Vec<_>>();
+
+        let centroid = group
+            .iter()
+            .map(|vertex| positions[&vertex.id()].coords)
+            .fold(Vector::from([0., 0., 0.]), |sum, coords| sum + coords)
+            / group.len() as f64;
+
+        let point = intersect_surfaces(
+            &surfaces,
+            Point { coords: centroid },
+            tolerances,
+        )?;
+        if points.iter().any(|&p| tolerances.are_coincident(p, point)) {
+            return None;
+        }
+        points.push(point);
+
+        for vertex in group {
+            merged.insert(vertex.id(), (Handle::clone(group[0]), point));
+        }
+    }
+
+    Some(merged)
+}
+
+/// Find the point where the provided surfaces meet
+///
+/// Starts at the provided point, and repeatedly moves to the point where the
+/// tangent planes of the surfaces meet. For planes, that takes a single step.
+/// Returns `None`, if the surfaces don't meet in a single point.
+fn intersect_surfaces(
+    surfaces: &[SurfaceGeom],
+    mut point: Point<3>,
+    tolerances: &Tolerances,
+) -> Option<Point<3>> {
+    const MAX_ITERATIONS: usize = 32;
+
+    let closest_point_on = |surface: &SurfaceGeom, point: Point<3>| {
+        let point_surface = surface.project_global_point(point);
+        (
+            point_surface,
+            surface.point_from_surface_coords(point_surface),
+        )
+    };
+
+    for _ in 0..MAX_ITERATIONS {
+        let planes = surfaces
+            .iter()
+            .map(|surface| {
+                let (point_surface, point) = closest_point_on(surface, point);
+                let normal = surface.normal_at(point_surface);
+                (normal, normal.dot(&point.coords))
+            })
+            .collect::<Vec<_>>();
+
+        let next = intersect_planes(&planes, tolerances)?;
+        let has_converged = tolerances.are_coincident(point, next);
+        point = next;
+
+        if has_converged {
+            break;
+        }
+    }
+
+    let is_on_all_surfaces = surfaces.iter().all(|surface| {
+        let (_, closest) = closest_point_on(surface, point);
+        tolerances.are_coincident(closest, point)
+    });
+    is_on_all_surfaces.then_some(point)
+}
+
+/// Join the siblings of the two half-edges of a removed face that remain
+///
+/// Returns a half-edge for each, on a new curve that runs between the merged
+/// vertices. Each of them has the shape of the half-edge it replaces. Returns
+/// `None`, if those shapes are different, or if both are circles.
+fn join_half_edges(
+    half_edges: [&(Handle<HalfEdge>, Handle<Surface>); 2],
+    shell: &Shell,
+    merged: &BTreeMap<ObjectId, (Handle<Vertex>, Point<3>)>,
+    core: &mut Core,
+    tolerances: &Tolerances,
+) -> Option<[Handle<HalfEdge>; 2]> {
+    let fit = |(half_edge, surface): &(Handle<HalfEdge>, Handle<Surface>)| {
+        let vertices = shell.bounding_vertices_of_half_edge(half_edge)?;
+        let [start, end] = vertices
+            .inner
+            .each_ref_ext()
+            .map(|vertex| &merged[&vertex.id()]);
+
+        let (path, boundary) = fit_path(
+            *core.layers.geometry.of_half_edge(half_edge),
+            core.layers.geometry.of_surface(surface),
+            [start, end].map(|&(_, position)| position),
+            vertices.inner[0].id() == vertices.inner[1].id(),
+        );
+
+        Some((path, boundary, start.0.clone()))
+    };
+    let [a, b] = half_edges;
+    let fitted = [fit(a)?, fit(b)?];
+    let [(path_a, boundary_a, _), (path_b, boundary_b, _)] = fitted.clone();
+
+    // Both half-edges are on the same curve, so they need to agree on the
+    // curve coordinates. Circles keep theirs, as those are angles.
+    let (path_a, path_b, boundary_a) = match (path_a, path_b) {
+        (SurfacePath::Circle(_), SurfacePath::Circle(_)) => return None,
+        (SurfacePath::Line(_), SurfacePath::Circle(_)) => (
+            reparametrize_line(path_a, boundary_a, boundary_b.reverse()),
+            path_b,
+            boundary_b.reverse(),
+        ),
+        _ => (
+            path_a,
+            reparametrize_line(path_b, boundary_b, boundary_a.reverse()),
+            boundary_a,
+        ),
+    };
+    let boundary_b = boundary_a.reverse();
+
+    let [(_, surface_a), (_, surface_b)] = half_edges;
+    let [t_start, t_end] = boundary_a.inner;
+    let t_mid = Point::from([(t_start.t + t_end.t) / 2.]);
+    let is_on_both_surfaces = [t_start, t_mid, t_end].into_iter().all(|t| {
+        let [a, b] = [(path_a, surface_a), (path_b, surface_b)].map(
+            |(path, surface)| {
+                core.layers
+                    .geometry
+                    .of_surface(surface)
+                    .point_from_surface_coords(path.point_from_path_coords(t))
+            },
+        );
+        tolerances.are_coincident(a, b)
+    });
+    if !is_on_both_surfaces {
+        return None;
+    }
+
+    let curve = Curve::new()
+        .insert(core)
+        .make_path_on_surface(
+            path_a,
+            surface_a.clone(),
+            &mut core.layers.geometry,
+        )
+        .make_path_on_surface(
+            path_b,
+            surface_b.clone(),
+            &mut core.layers.geometry,
+        );
+
+    let [(_, _, start_a), (_, _, start_b)] = fitted;
+    let [(a, _), (b, _)] = half_edges;
+    let new = [
+        (a, start_a, path_a, boundary_a),
+        (b, start_b, path_b, boundary_b),
+    ]
+    .map(|(half_edge, start_vertex, path, boundary)| {
+        HalfEdge::new(curve.clone(), start_vertex)
+            .insert(core)
+            .derive_from(half_edge, core)
+            .set_geometry(
+                HalfEdgeGeom { path, boundary },
+                &mut core.layers.geometry,
+            )
+    });
+
+    Some(new)
+}
+
+/// Fit the path of a half-edge to new start and end points
+///
+/// The path keeps its shape. Lines stay parallel to the original, circles keep
+/// their center and orientation. Returns the new path, and the boundary of the
+/// half-edge on it.
+fn fit_path(
+    half_edge: HalfEdgeGeom,
+    surface: &SurfaceGeom,
+    [start, end]: [Point<3>; 2],
+    is_closed: bool,
+) -> (SurfacePath, CurveBoundary<Point<1>>) {
+    let HalfEdgeGeom { path, boundary } = half_edge;
+    let [old_start, old_end] =
+        boundary.inner.map(|t| path.point_from_path_coords(t));
+
+    let start = point_near(surface, start, old_start);
+    let end = if is_closed {
+        start + (old_end - old_start)
+    } else {
+        point_near(surface, end, old_end)
+    };
+
+    match path {
+        SurfacePath::Circle(circle) => {
+            let scale = start.distance_to(&circle.center()) / circle.radius();
+            let path = SurfacePath::Circle(Circle::new(
+                circle.center(),
+                circle.a() * scale,
+                circle.b() * scale,
+            ));
+
+            let [t_start, t_end] = boundary.inner;
+            let start = angle_near(&path, start, t_start);
+            let end = if is_closed {
+                start + (t_end - t_start)
+            } else {
+                angle_near(&path, end, t_end)
+            };
+
+            (path, CurveBoundary::from([start, end]))
+        }
+        SurfacePath::Line(_) => (
+            SurfacePath::line_from_points_with_coords(
+                boundary.inner.zip_ext([start, end]),
+            ),
+            boundary,
+        ),
+    }
+}
+
+/// Extend the boundary of a half-edge to the positions of merged vertices
+///
+/// Returns `None`, if the path of the half-edge doesn't pass through them.
+fn extend_boundary(
+    path: SurfacePath,
+    boundary: CurveBoundary<Point<1>>,
+    surface: &SurfaceGeom,
+    positions: [Option<Point<3>>; 2],
+    is_closed: bool,
+    tolerances: &Tolerances,
+) -> Option<CurveBoundary<Point<1>>> {
+    let mut inner = boundary.inner;
+
+    for (t, position) in inner.iter_mut().zip(positions) {
+        let Some(position) = position else {
+            continue;
+        };
+
+        let point =
+            point_near(surface, position, path.point_from_path_coords(*t));
+        *t = match path {
+            SurfacePath::Circle(_) => angle_near(&path, point, *t),
+            SurfacePath::Line(line) => line.point_to_line_coords(point),
+        };
+
+        let point =
+            surface.point_from_surface_coords(path.point_from_path_coords(*t));
+        if !tolerances.are_coincident(point, position) {
+            return None;
+        }
+    }
+
+    if is_closed {
+        let [start, end] = boundary.inner;
+        inner[1] = inner[0] + (end - start);
+    }
+
+    Some(CurveBoundary::from(inner))
+}
+
+/// Convert a point into surface coordinates, choosing those near another point
+///
+/// This matters for surfaces whose u-coordinate is periodic.
+fn point_near(
+    surface: &SurfaceGeom,
+    point: Point<3>,
+    near: Point<2>,
+) -> Point<2> {
+    let mut point = surface.project_global_point(point);
+    if surface.u_period().is_some() {
+        point.u = near.u + (point.u - near.u).wrap_angle();
+    }
+    point
+}
+
+/// Compute the coordinate of a point on a circle, choosing the angle near `t`
+fn angle_near(path: &SurfacePath, point: Point<2>, t: Point<1>) -> Point<1> {
+    let angle = path.project_point(point);
+    t + Vector::from([(angle.t - t.t).wrap_angle()])
+}
+
+/// Assign new coordinates to the points that bound a line
+fn reparametrize_line(
+    path: SurfacePath,
+    from: CurveBoundary<Point<1>>,
+    to: CurveBoundary<Point<1>>,
+) -> SurfacePath {
+    let points = from.inner.map(|t| path.point_from_path_coords(t));
+    SurfacePath::line_from_points_with_coords(to.inner.zip_ext(points))
+}
+
+/// The new boundaries of half-edges, by curve
+///
+/// Siblings need to have the exact same boundary, in reverse. So the boundary
+/// of a half-edge is computed once, and then reused for its sibling.
+#[derive(Default)]
+struct BoundariesOfCurves {
+    inner: BTreeMap<ObjectId, Vec<[CurveBoundary<Point<1>>; 2]>>,
+}
+
+impl BoundariesOfCurves {
+    fn insert(
+        &mut self,
+        half_edge: &Handle<HalfEdge>,
+        old: CurveBoundary<Point<1>>,
+        new: CurveBoundary<Point<1>>,
+    ) {
+        self.inner
+            .entry(half_edge.curve().id())
+            .or_default()
+            .push([old, new]);
+    }
+
+    fn of_sibling(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+        old: CurveBoundary<Point<1>>,
+    ) -> Option<CurveBoundary<Point<1>>> {
+        self.inner
+            .get(&half_edge.curve().id())?
+            .iter()
+            .find(|[sibling_old, _]| sibling_old.reverse() == old)
+            .map(|[_, sibling_new]| sibling_new.reverse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        geometry::GlobalPath,
+        operations::{
+            build::{BuildHalfEdge, BuildShell, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        topology::{Cycle, HalfEdge, Region, Shell, Sketch},
+        Core,
+    };
+
+    use super::RemoveFace;
+
+    #[test]
+    fn remove_chamfer() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A unit cube, with the edge at the top of its front face chamfered.
+        let shell = Shell::from_vertices_and_polygons(
+            [
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [1., 1., 0.],
+                [0., 1., 0.],
+                [0., 0.25, 1.],
+                [1., 0.25, 1.],
+                [1., 1., 1.],
+                [0., 1., 1.],
+                [0., 0., 0.75],
+                [1., 0., 0.75],
+            ],
+            [
+                vec![0, 3, 2, 1],
+                vec![4, 5, 6, 7],
+                vec![0, 1, 9, 8],
+                vec![8, 9, 5, 4],
+                vec![1, 2, 6, 5, 9],
+                vec![2, 3, 7, 6],
+                vec![3, 0, 8, 4, 7],
+            ],
+            &mut core,
+        );
+        let chamfer = shell.faces().iter().nth(3).expect("Face exists").clone();
+
+        let removed = shell.remove_faces(&[chamfer], &mut core)?;
+
+        assert_eq!(removed.faces().len(), 6);
+        for face in removed.faces() {
+            for half_edge in face.region().exterior().half_edges() {
+                let position = core
+                    .layers
+                    .geometry
+                    .of_surface(face.surface())
+                    .point_from_surface_coords(
+                        core.layers
+                            .geometry
+                            .of_half_edge(half_edge)
+                            .start_position(),
+                    );
+
+                // All vertices are corners of the unit cube.
+                let is_corner = position.coords.components.iter().all(|c| {
+                    c.abs() < Scalar::from(1e-9)
+                        || (*c - 1.).abs() < Scalar::from(1e-9)
+                });
+                assert!(is_corner, "{position:?}");
+            }
+        }
+
+        // The bottom and the back face don't touch the chamfer, and are kept.
+        for index in [0, 5] {
+            let face = shell.faces().nth(index).expect("Face exists");
+            assert!(removed.faces().index_of(face).is_some());
+        }
+
+        let _ = removed.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn remove_chamfer_next_to_cylinder() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A cylinder that is cut off at `y = -0.5`, with the edge at `x > 0`
+        // chamfered.
+        let surface = core.layers.topology.surfaces.space_2d();
+        let [a, b, c] = [
+            Point::from([1., 0.]),
+            Point::from([-(3_f64.sqrt()) / 2., -0.5]),
+            Point::from([0.5, -0.5]),
+        ];
+        let exterior = Cycle::new([
+            HalfEdge::arc(
+                a,
+                b,
+                Scalar::PI * 7. / 6.,
+                surface.clone(),
+                &mut core,
+            ),
+            HalfEdge::line_segment([b, c], None, surface.clone(), &mut core),
+            HalfEdge::line_segment([c, a], None, surface, &mut core),
+        ])
+        .insert(&mut core);
+        let region = Region::new(exterior, []);
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions([region], &mut core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                &mut core,
+            );
+        let shell = solid.shells().first().clone();
+
+        let chamfer = shell
+            .faces()
+            .iter()
+            .find(|face| {
+                let surface = core.layers.geometry.of_surface(face.surface());
+                let point = Point::from([0.75, -0.25, 0.5]);
+
+                matches!(surface.u, GlobalPath::Line(_))
+                    && surface
+                        .point_from_surface_coords(
+                            surface.project_global_point(point),
+                        )
+                        .distance_to(&point)
+                        < Scalar::from(1e-9)
+            })
+            .expect("Chamfer exists")
+            .clone();
+
+        let removed = shell.remove_faces(&[chamfer], &mut core)?;
+
+        // Top, bottom, and the flat and cylindrical sides.
+        assert_eq!(removed.faces().len(), 4);
+        for face in removed.faces() {
+            for half_edge in face.region().exterior().half_edges() {
+                let position = core
+                    .layers
+                    .geometry
+                    .of_surface(face.surface())
+                    .point_from_surface_coords(
+                        core.layers
+                            .geometry
+                            .of_half_edge(half_edge)
+                            .start_position(),
+                    );
+
+                // All vertices are where the cylinder meets the flat side.
+                let [x, y, z] = position.coords.components;
+                assert!(
+                    (x.abs() - 3_f64.sqrt() / 2.).abs() < Scalar::from(1e-9)
+                        && (y + 0.5).abs() < Scalar::from(1e-9)
+                        && (z.abs() < Scalar::from(1e-9)
+                            || (z - 1.).abs() < Scalar::from(1e-9)),
+                    "{position:?}"
+                );
+            }
+        }
+
+        let _ = removed.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+}
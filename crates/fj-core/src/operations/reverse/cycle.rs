@@ -15,8 +15,8 @@ impl Reverse for Cycle {
             .half_edges()
             .pairs()
             .map(|(current, next)| {
-                let mut geometry = *core.layers.geometry.of_half_edge(current);
-                geometry.boundary = geometry.boundary.reverse();
+                let geometry =
+                    core.layers.geometry.of_half_edge(current).reverse();
 
                 HalfEdge::new(
                     current.curve().clone(),
@@ -16,9 +16,8 @@ impl ReverseCurveCoordinateSystems for (&Handle<HalfEdge>, &Handle<Surface>) {
     ) -> Self::Reversed {
         let (half_edge, surface) = self;
 
-        let mut half_edge_geom = *core.layers.geometry.of_half_edge(half_edge);
-        half_edge_geom.path = half_edge_geom.path.reverse();
-        half_edge_geom.boundary = half_edge_geom.boundary.reverse();
+        let half_edge_geom =
+            core.layers.geometry.of_half_edge(half_edge).reverse_path();
 
         let curve =
             (half_edge.curve(), surface).reverse_curve_coordinate_systems(core);
@@ -0,0 +1,442 @@
+//! # Sheet metal parts
+//!
+//! See [`SheetMetal`].
+
+use std::f64::consts::PI;
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfacePath},
+    operations::{
+        build::{BuildRegion, BuildShell},
+        insert::Insert,
+    },
+    storage::Handle,
+    topology::{Face, Region, Shell, Sketch, Solid, Surface},
+    Core,
+};
+
+/// A part that is bent from a flat sheet of metal
+///
+/// A sheet metal part starts out as a planar face, its base, which is given a
+/// thickness. Flanges can be added to the edges of the base. Each flange is
+/// bent away from the base, around a bend of a given radius.
+///
+/// The part can be converted into a [`Solid`] with [`SheetMetal::fold`], or
+/// unfolded into its flat pattern with [`SheetMetal::unfold`]. The flat pattern
+/// is the shape that needs to be cut from the sheet, before bending it.
+pub struct SheetMetal {
+    surface: Handle<Surface>,
+    profile: Vec<Point<2>>,
+    thickness: Scalar,
+    k_factor: Scalar,
+    flanges: Vec<Option<Flange>>,
+}
+
+impl SheetMetal {
+    /// The default k-factor
+    ///
+    /// This is a common value for bending steel in a press brake, but the
+    /// right value depends on the material and the bending process.
+    pub const DEFAULT_K_FACTOR: f64 = 0.44;
+
+    /// The maximum angle of a bend that is approximated by a single facet
+    ///
+    /// See [`SheetMetal::fold`].
+    pub const MAX_FACET_ANGLE_RAD: f64 = PI / 12.;
+
+    /// Create a sheet metal part from a face, by giving it a thickness
+    ///
+    /// The face must be a planar polygon without holes. The sheet extends from
+    /// the face towards its front side, from which its exterior cycle appears
+    /// counter-clockwise.
+    ///
+    /// The edges of the part, which flanges can be added to, are numbered in
+    /// the order of the half-edges of the face's exterior cycle.
+    pub fn from_face(
+        face: &Face,
+        thickness: impl Into<Scalar>,
+        core: &Core,
+    ) -> Result<Self, SheetMetalError> {
+        let thickness = thickness.into();
+        if thickness <= Scalar::ZERO {
+            return Err(SheetMetalError::InvalidThickness { thickness });
+        }
+
+        let geometry = &core.layers.geometry;
+
+        let is_polygon =
+            matches!(
+                geometry.of_surface(face.surface()).u,
+                GlobalPath::Line(_)
+            ) && face.region().interiors().is_empty()
+                && face.region().exterior().half_edges().iter().all(
+                    |half_edge| {
+                        matches!(
+                            geometry.of_half_edge(half_edge).path,
+                            SurfacePath::Line(_)
+                        )
+                    },
+                );
+        if !is_polygon {
+            return Err(SheetMetalError::UnsupportedFace);
+        }
+
+        let profile = face
+            .region()
+            .exterior()
+            .half_edges()
+            .iter()
+            .map(|half_edge| geometry.of_half_edge(half_edge).start_position())
+            .collect::<Vec<_>>();
+        let num_edges = profile.len();
+
+        Ok(Self {
+            surface: face.surface().clone(),
+            profile,
+            thickness,
+            k_factor: Scalar::from(Self::DEFAULT_K_FACTOR),
+            flanges: vec![None; num_edges],
+        })
+    }
+
+    /// Use the provided k-factor
+    ///
+    /// The k-factor defines the location of the neutral axis within the sheet,
+    /// which keeps its length during bending, as a fraction of the thickness,
+    /// measured from the inside of the bend. It determines the length of the
+    /// bends in the flat pattern.
+    #[must_use]
+    pub fn with_k_factor(mut self, k_factor: impl Into<Scalar>) -> Self {
+        self.k_factor = k_factor.into();
+        self
+    }
+
+    /// Add a flange to the edge with the provided index
+    pub fn flange(
+        mut self,
+        edge: usize,
+        flange: Flange,
+    ) -> Result<Self, SheetMetalError> {
+        let Some(slot) = self.flanges.get_mut(edge) else {
+            return Err(SheetMetalError::EdgeDoesNotExist { edge });
+        };
+        if slot.is_some() {
+            return Err(SheetMetalError::EdgeAlreadyHasFlange { edge });
+        }
+
+        let is_valid = flange.length > Scalar::ZERO
+            && flange.bend_radius > Scalar::ZERO
+            && flange.angle_rad > Scalar::ZERO
+            && flange.angle_rad < Scalar::PI;
+        if !is_valid {
+            return Err(SheetMetalError::InvalidFlange { flange });
+        }
+
+        *slot = Some(flange);
+        Ok(self)
+    }
+
+    /// Compute the length of the provided flange's bend in the flat pattern
+    ///
+    /// This is the length of the neutral axis within the bend.
+    pub fn bend_allowance(&self, flange: &Flange) -> Scalar {
+        flange.angle_rad * (flange.bend_radius + self.k_factor * self.thickness)
+    }
+
+    /// Unfold the part into its flat pattern
+    ///
+    /// The flat pattern is a sketch on the surface of the original face. Each
+    /// flange extends the base by the length of its bend in the flat pattern,
+    /// plus its own length.
+    pub fn unfold(&self, core: &mut Core) -> Sketch {
+        let base = self.base(core);
+
+        let mut points = Vec::new();
+        for (i, flange) in self.flanges.iter().enumerate() {
+            let [a, b] = [i, (i + 1) % self.profile.len()];
+            points.push(self.profile[a]);
+
+            if let Some(flange) = flange {
+                let extension = base.outward[i]
+                    * (self.bend_allowance(flange) + flange.length);
+
+                let surface = core.layers.geometry.of_surface(&self.surface);
+                points.extend([a, b].map(|index| {
+                    surface.project_global_point(base.bottom[index] + extension)
+                }));
+            }
+        }
+
+        let region = Region::polygon(points, self.surface.clone(), core);
+        Sketch::new(self.surface.clone(), [region.insert(core)])
+    }
+
+    /// Fold the part into a solid
+    ///
+    /// Bends are approximated by planar facets, one per
+    /// [`SheetMetal::MAX_FACET_ANGLE_RAD`] of bend angle, or fraction thereof.
+    pub fn fold(&self, core: &mut Core) -> Solid {
+        let base = self.base(core);
+        let num_points = self.profile.len();
+
+        let mut vertices = base.bottom.clone();
+        vertices.extend(base.bottom.iter().map(|&point| point + base.up));
+
+        let bottom = |i: usize| i;
+        let top = |i: usize| num_points + i;
+
+        let mut polygons = Vec::new();
+        polygons.push((0..num_points).rev().map(bottom).collect::<Vec<_>>());
+        polygons.push((0..num_points).map(top).collect::<Vec<_>>());
+
+        for (i, flange) in self.flanges.iter().enumerate() {
+            let [a, b] = [i, (i + 1) % num_points];
+
+            let Some(flange) = flange else {
+                polygons.push(vec![bottom(a), bottom(b), top(b), top(a)]);
+                continue;
+            };
+
+            // The cross-section of the flange, perpendicular to its edge. The
+            // first coordinate points outward from the base, the second one
+            // points up.
+            let cross_section = self.cross_section(flange);
+
+            // The first and last points of the cross-section are on the bottom
+            // and top of the base, respectively. The other ones are new.
+            let [at_a, at_b] = [a, b].map(|index| {
+                let mut indices = vec![bottom(index)];
+                for point in &cross_section[1..cross_section.len() - 1] {
+                    indices.push(vertices.len());
+                    vertices.push(
+                        base.bottom[index]
+                            + base.outward[i] * point.u
+                            + base.up * (point.v / self.thickness),
+                    );
+                }
+                indices.push(top(index));
+
+                indices
+            });
+
+            for k in 0..cross_section.len() - 1 {
+                polygons.push(vec![at_a[k], at_b[k], at_b[k + 1], at_a[k + 1]]);
+            }
+            polygons.push(at_a);
+            polygons.push(at_b.into_iter().rev().collect());
+        }
+
+        let shell = Shell::from_vertices_and_polygons(vertices, polygons, core);
+        Solid::new([shell.insert(core)])
+    }
+
+    fn base(&self, core: &Core) -> Base {
+        let surface = core.layers.geometry.of_surface(&self.surface);
+
+        let bottom = self
+            .profile
+            .iter()
+            .map(|&point| surface.point_from_surface_coords(point))
+            .collect::<Vec<_>>();
+
+        // Newell's method results in a normal towards the side from which the
+        // profile appears counter-clockwise.
+        let normal = bottom
+            .iter()
+            .zip(bottom.iter().cycle().skip(1))
+            .fold(Vector::from([0., 0., 0.]), |normal, (a, b)| {
+                normal + a.coords.cross(&b.coords)
+            })
+            .normalize();
+
+        let outward = bottom
+            .iter()
+            .zip(bottom.iter().cycle().skip(1))
+            .map(|(a, b)| (*b - *a).normalize().cross(&normal))
+            .collect();
+
+        Base {
+            bottom,
+            up: normal * self.thickness,
+            outward,
+        }
+    }
+
+    fn cross_section(&self, flange: &Flange) -> Vec<Point<2>> {
+        let num_facets = (flange.angle_rad / Self::MAX_FACET_ANGLE_RAD)
+            .ceil()
+            .into_u64();
+
+        let center =
+            Point::from([Scalar::ZERO, self.thickness + flange.bend_radius]);
+        let arc = |radius: Scalar| {
+            (0..=num_facets).map(move |i| {
+                let angle = flange.angle_rad * Scalar::from_u64(i)
+                    / Scalar::from_u64(num_facets);
+                let (sin, cos) = angle.sin_cos();
+                center + Vector::from([sin, -cos]) * radius
+            })
+        };
+        let direction = {
+            let (sin, cos) = flange.angle_rad.sin_cos();
+            Vector::from([cos, sin])
+        };
+
+        let outside =
+            arc(flange.bend_radius + self.thickness).collect::<Vec<_>>();
+        let inside = arc(flange.bend_radius).collect::<Vec<_>>();
+
+        let mut points = outside.clone();
+        points.push(outside[outside.len() - 1] + direction * flange.length);
+        points.push(inside[inside.len() - 1] + direction * flange.length);
+        points.extend(inside.into_iter().rev());
+
+        points
+    }
+}
+
+/// A flange of a [`SheetMetal`] part
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Flange {
+    /// The length of the flat part of the flange, after the bend
+    pub length: Scalar,
+
+    /// The angle by which the flange is bent away from the base
+    ///
+    /// Must be larger than zero and smaller than π. The flange is bent towards
+    /// the top of the base.
+    pub angle_rad: Scalar,
+
+    /// The radius of the bend, on its inside
+    pub bend_radius: Scalar,
+}
+
+/// Error creating or modifying a [`SheetMetal`] part
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum SheetMetalError {
+    /// The face is not a planar polygon without holes
+    #[error("Sheet metal parts can only be created from planar polygons")]
+    UnsupportedFace,
+
+    /// The thickness is not positive
+    #[error("Thickness of sheet metal must be positive: {thickness}")]
+    InvalidThickness {
+        /// The invalid thickness
+        thickness: Scalar,
+    },
+
+    /// The edge that a flange was added to doesn't exist
+    #[error("Sheet metal part has no edge with index {edge}")]
+    EdgeDoesNotExist {
+        /// The index of the edge
+        edge: usize,
+    },
+
+    /// The edge that a flange was added to already has one
+    #[error("Edge {edge} of sheet metal part already has a flange")]
+    EdgeAlreadyHasFlange {
+        /// The index of the edge
+        edge: usize,
+    },
+
+    /// The parameters of a flange are out of range
+    #[error("Invalid flange: {flange:?}")]
+    InvalidFlange {
+        /// The invalid flange
+        flange: Flange,
+    },
+}
+
+/// The base of a sheet metal part, in 3D space
+struct Base {
+    /// The points of the bottom side of the base
+    bottom: Vec<Point<3>>,
+
+    /// The vector from the bottom side of the base to its top side
+    up: Vector<3>,
+
+    /// The outward-pointing unit normals of the base's edges
+    outward: Vec<Vector<3>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Scalar};
+
+    use crate::{
+        algorithms::bounding_volume::BoundingVolume,
+        operations::{build::BuildFace, insert::Insert},
+        topology::Face,
+        Core,
+    };
+
+    use super::{Flange, SheetMetal};
+
+    #[test]
+    fn fold_and_unfold_flange() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let face = Face::polygon(
+            core.layers.topology.surfaces.xy_plane(),
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        );
+        let flange = Flange {
+            length: Scalar::from(0.5),
+            angle_rad: Scalar::PI / 2.,
+            bend_radius: Scalar::from(0.2),
+        };
+        let part = SheetMetal::from_face(&face, 0.1, &core)?
+            .with_k_factor(0.5)
+            .flange(0, flange)?;
+
+        let solid = part.fold(&mut core);
+        let aabb = Aabb::<3>::from_points(
+            solid
+                .shells()
+                .iter()
+                .flat_map(|shell| shell.faces())
+                .flat_map(|face| {
+                    let geometry = &core.layers.geometry;
+                    let surface = geometry.of_surface(face.surface());
+
+                    face.region().exterior().half_edges().iter().map(
+                        |half_edge| {
+                            surface.point_from_surface_coords(
+                                geometry
+                                    .of_half_edge(half_edge)
+                                    .start_position(),
+                            )
+                        },
+                    )
+                }),
+        );
+        let expected_min = [0., -0.3, 0.];
+        let expected_max = [1., 1., 0.8];
+        for (actual, expected) in [aabb.min, aabb.max]
+            .into_iter()
+            .zip([expected_min, expected_max])
+        {
+            for (a, e) in actual.coords.components.into_iter().zip(expected) {
+                assert!((a - e).abs() < Scalar::from(1e-9), "{aabb:?}");
+            }
+        }
+        let _ = solid.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let flat_pattern = part.unfold(&mut core);
+        let region = flat_pattern.regions().first();
+        assert_eq!(region.exterior().half_edges().len(), 6);
+
+        let extension = Scalar::PI / 2. * (0.2 + 0.5 * 0.1) + 0.5;
+        let bounds = region
+            .exterior()
+            .aabb(&core.layers.geometry)
+            .expect("Region is not empty");
+        assert!((bounds.min.v + extension).abs() < Scalar::from(1e-9));
+
+        Ok(())
+    }
+}
@@ -236,7 +236,7 @@ mod tests {
                 .shell
                 .split_face(&triangle.face, split_line, &mut core);
 
-        assert_eq!(face_a.region().get_color(&mut core), Some(color));
-        assert_eq!(face_b.region().get_color(&mut core), Some(color));
+        assert_eq!(face_a.region().get_color(&core), Some(color));
+        assert_eq!(face_b.region().get_color(&core), Some(color));
     }
 }
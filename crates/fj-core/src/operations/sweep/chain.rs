@@ -0,0 +1,116 @@
+use fj_math::Vector;
+
+use crate::{
+    operations::insert::Insert,
+    storage::Handle,
+    topology::{HalfEdge, Shell, Surface, Vertex},
+    Core,
+};
+
+use super::{SweepCache, SweepHalfEdge};
+
+/// # Sweep an open chain of [`HalfEdge`]s
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait SweepChain {
+    /// # Sweep the chain into an open [`Shell`]
+    ///
+    /// The chain is a sequence of half-edges, each starting where the previous
+    /// one ends. Unlike a cycle, the chain doesn't need to be closed, and each
+    /// of its half-edges is swept into a face of the resulting shell. That
+    /// shell is a sheet, not the boundary of a solid.
+    ///
+    /// All half-edges must be defined on the provided surface. Since a
+    /// half-edge only refers to its start vertex, a new vertex is created for
+    /// the end of the chain.
+    fn sweep_chain(
+        &self,
+        surface: Handle<Surface>,
+        path: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Shell;
+}
+
+impl SweepChain for [Handle<HalfEdge>] {
+    fn sweep_chain(
+        &self,
+        surface: Handle<Surface>,
+        path: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Shell {
+        let path = path.into();
+        let mut cache = SweepCache::default();
+
+        let end_vertices = self
+            .iter()
+            .skip(1)
+            .map(|half_edge| half_edge.start_vertex().clone())
+            .chain([Vertex::new().insert(core)])
+            .collect::<Vec<_>>();
+
+        let faces = self
+            .iter()
+            .zip(end_vertices)
+            .map(|(half_edge, end_vertex)| {
+                let (face, _) = half_edge.sweep_half_edge(
+                    end_vertex,
+                    surface.clone(),
+                    None,
+                    path,
+                    &mut cache,
+                    core,
+                );
+                face.insert(core)
+            })
+            .collect::<Vec<_>>();
+
+        Shell::new(faces)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{build::BuildHalfEdge, insert::Insert},
+        topology::HalfEdge,
+        validation::checks::HalfEdgeHasNoSibling,
+        Core,
+    };
+
+    use super::SweepChain;
+
+    #[test]
+    fn sweep_open_chain() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let chain = [[0., 0.], [1., 0.], [1., 1.]]
+            .windows(2)
+            .map(|points| {
+                HalfEdge::line_segment(
+                    [points[0], points[1]],
+                    None,
+                    surface.clone(),
+                    &mut core,
+                )
+                .insert(&mut core)
+            })
+            .collect::<Vec<_>>();
+
+        let sheet = chain.sweep_chain(surface, [0., 0., 1.], &mut core);
+        assert_eq!(sheet.faces().len(), 2);
+
+        // The two faces share one edge. All other half-edges are on the
+        // boundary of the sheet.
+        let boundary = core.validate::<HalfEdgeHasNoSibling>(&sheet);
+        assert_eq!(boundary.len(), 6);
+
+        // Open shells are valid, as long as they are not part of a solid.
+        let _ = sheet.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+}
@@ -1,9 +1,16 @@
-use fj_math::Vector;
+use fj_math::{Scalar, Vector, Winding};
 
 use crate::{
-    operations::{insert::Insert, presentation::GetColor},
+    geometry::GlobalPath,
+    operations::{
+        derive::DeriveFrom,
+        insert::Insert,
+        material::{GetMaterial, SetMaterial},
+        presentation::GetColor,
+        reverse::Reverse,
+    },
     storage::Handle,
-    topology::{Face, Shell},
+    topology::{Cycle, Face, Region, Shell, Solid},
     Core,
 };
 
@@ -22,6 +29,21 @@ pub trait SweepFace {
         cache: &mut SweepCache,
         core: &mut Core,
     ) -> Shell;
+
+    /// # Sweep a [`Face`] with holes into a [`Solid`]
+    ///
+    /// Unlike [`SweepFace::sweep_face`], this doesn't expect the face to be
+    /// oriented in a specific way. Its exterior cycle is reversed as needed,
+    /// for the face to point away from `path`, and each interior cycle is made
+    /// to wind opposite to the exterior. The holes of the face then become
+    /// cavities through the solid, with side walls that point into them.
+    ///
+    /// Returns an error, if the face is defined on a curved surface.
+    fn sweep_face_with_holes(
+        &self,
+        path: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Result<Solid, SweepFaceError>;
 }
 
 impl SweepFace for Handle<Face> {
@@ -45,6 +67,9 @@ impl SweepFace for Handle<Face> {
         // might actually be opposite.
 
         let path = path.into();
+        let span =
+            tracing::debug_span!("sweep_face", faces = tracing::field::Empty,)
+                .entered();
 
         let bottom_face = self;
         let other_faces = bottom_face
@@ -57,12 +82,192 @@ impl SweepFace for Handle<Face> {
                 core,
             )
             .all_faces()
-            .map(|face| face.insert(core));
+            .map(|face| face.insert(core))
+            .collect::<Vec<_>>();
+
+        // The new faces are made of the same material as the one they were
+        // swept from.
+        if let Some(material) = bottom_face.region().get_material(core) {
+            for face in &other_faces {
+                face.region().set_material(material.clone(), core);
+            }
+        }
 
         let mut faces = Vec::new();
         faces.push(bottom_face.clone());
         faces.extend(other_faces);
 
+        span.record("faces", faces.len());
         Shell::new(faces)
     }
+
+    fn sweep_face_with_holes(
+        &self,
+        path: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Result<Solid, SweepFaceError> {
+        let path = path.into();
+
+        let normal = {
+            let surface = core.layers.geometry.of_surface(self.surface());
+            let u = match surface.u {
+                GlobalPath::Circle(_) => {
+                    return Err(SweepFaceError::CurvedSurface {
+                        face: self.clone(),
+                    });
+                }
+                GlobalPath::Line(line) => line.direction(),
+            };
+
+            u.cross(&surface.v)
+        };
+
+        // The face must point away from the path. A counter-clockwise
+        // exterior means it points in the direction of the surface normal.
+        let exterior_winding = if normal.dot(&path) < Scalar::ZERO {
+            Winding::Ccw
+        } else {
+            Winding::Cw
+        };
+        let interior_winding = match exterior_winding {
+            Winding::Ccw => Winding::Cw,
+            Winding::Cw => Winding::Ccw,
+        };
+
+        let mut orient = |cycle: &Handle<Cycle>, winding: Winding| {
+            if cycle.winding(&core.layers.geometry) == winding {
+                cycle.clone()
+            } else {
+                cycle.reverse(core).insert(core).derive_from(cycle, core)
+            }
+        };
+
+        let region = self.region();
+        let exterior = orient(region.exterior(), exterior_winding);
+        let interiors = region
+            .interiors()
+            .iter()
+            .map(|cycle| orient(cycle, interior_winding))
+            .collect::<Vec<_>>();
+
+        let region = Region::new(exterior, interiors)
+            .insert(core)
+            .derive_from(region, core);
+        let face = Face::new(self.surface().clone(), region)
+            .insert(core)
+            .derive_from(self, core);
+
+        let shell = face
+            .sweep_face(path, &mut SweepCache::default(), core)
+            .insert(core);
+
+        Ok(Solid::new([shell]))
+    }
+}
+
+/// Error sweeping a [`Face`]
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum SweepFaceError {
+    /// The face is defined on a curved surface
+    #[error("Can't sweep face with holes on curved surface: {face:?}")]
+    CurvedSurface {
+        /// The face that is defined on a curved surface
+        face: Handle<Face>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, mass_properties::ComputeMassProperties,
+        },
+        geometry::GlobalPath,
+        operations::{
+            build::{BuildCycle, BuildSurface},
+            insert::Insert,
+        },
+        topology::{Cycle, Face, Region, Surface},
+        Core,
+    };
+
+    use super::{SweepFace, SweepFaceError};
+
+    #[test]
+    fn sweep_face_with_holes() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        // Both cycles are wound counter-clockwise, so the hole is wound the
+        // wrong way for a valid face.
+        let exterior = Cycle::polygon(
+            [[0., 0.], [3., 0.], [3., 3.], [0., 3.]],
+            surface.clone(),
+            &mut core,
+        )
+        .insert(&mut core);
+        let interior = Cycle::polygon(
+            [[1., 1.], [2., 1.], [2., 2.], [1., 2.]],
+            surface.clone(),
+            &mut core,
+        )
+        .insert(&mut core);
+        let region = Region::new(exterior, [interior]).insert(&mut core);
+        let face = Face::new(surface, region).insert(&mut core);
+        assert!(core.layers.validation.take_errors().is_err());
+
+        for path in [[0., 0., 2.], [0., 0., -2.]] {
+            let solid = face.sweep_face_with_holes(path, &mut core)?;
+
+            let shell = solid.shells().only();
+            assert_eq!(shell.faces().len(), 10);
+
+            // A negative volume would mean the solid is inside out.
+            let tolerance = Tolerance::from_scalar(0.1)?;
+            let properties = solid.mass_properties(tolerance, &mut core);
+            assert!((properties.volume - 16.).abs() < Scalar::from(1e-9));
+
+            let _ = solid.insert(&mut core);
+        }
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_face_with_holes_on_curved_surface() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = Surface::from_uv(
+            GlobalPath::circle_from_radius(1.),
+            [0., 0., 1.],
+            &mut core,
+        );
+
+        let exterior = Cycle::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            surface.clone(),
+            &mut core,
+        )
+        .insert(&mut core);
+        let interior = Cycle::polygon(
+            [[0.25, 0.25], [0.25, 0.75], [0.75, 0.75], [0.75, 0.25]],
+            surface.clone(),
+            &mut core,
+        )
+        .insert(&mut core);
+        let region = Region::new(exterior, [interior]).insert(&mut core);
+        let face = Face::new(surface, region).insert(&mut core);
+
+        let result = face.sweep_face_with_holes([0., 0., 1.], &mut core);
+        assert_eq!(result.err(), Some(SweepFaceError::CurvedSurface { face }));
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
 }
@@ -3,6 +3,7 @@
 //! Sweeps 1D or 2D objects along a straight path, creating a 2D or 3D object,
 //! respectively.
 
+mod chain;
 mod cycle;
 mod face;
 mod half_edge;
@@ -13,8 +14,9 @@ mod sketch;
 mod vertex;
 
 pub use self::{
+    chain::SweepChain,
     cycle::{SweepCycle, SweptCycle},
-    face::SweepFace,
+    face::{SweepFace, SweepFaceError},
     half_edge::SweepHalfEdge,
     path::SweepSurfacePath,
     region::{SweepRegion, SweptRegion},
@@ -66,8 +66,10 @@ impl SweepSurfacePath for SurfacePath {
         let u = match self {
             SurfacePath::Circle(circle) => {
                 let center = surface.point_from_surface_coords(circle.center());
-                let a = surface.vector_from_surface_coords(circle.a());
-                let b = surface.vector_from_surface_coords(circle.b());
+                let a = surface
+                    .vector_from_surface_coords(circle.center(), circle.a());
+                let b = surface
+                    .vector_from_surface_coords(circle.center(), circle.b());
 
                 let circle = Circle::new(center, a, b);
 
@@ -75,8 +77,10 @@ impl SweepSurfacePath for SurfacePath {
             }
             SurfacePath::Line(line) => {
                 let origin = surface.point_from_surface_coords(line.origin());
-                let direction =
-                    surface.vector_from_surface_coords(line.direction());
+                let direction = surface.vector_from_surface_coords(
+                    line.origin(),
+                    line.direction(),
+                );
 
                 let line = Line::from_origin_and_direction(origin, direction);
 
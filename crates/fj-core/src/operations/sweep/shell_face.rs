@@ -4,6 +4,7 @@ use crate::{
     operations::{
         derive::DeriveFrom,
         insert::Insert,
+        material::{GetMaterial, SetMaterial},
         presentation::GetColor,
         reverse::Reverse,
         sweep::{SweepCache, SweepRegion},
@@ -73,6 +74,12 @@ impl SweepFaceOfShell for Shell {
             core,
         );
 
+        if let Some(material) = face.region().get_material(core) {
+            for face in swept_region.clone().all_faces() {
+                face.region().set_material(material.clone(), core);
+            }
+        }
+
         let shell = self
             .remove_face(&face)
             .add_faces(swept_region.clone().all_faces(), core);
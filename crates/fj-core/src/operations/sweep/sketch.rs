@@ -33,6 +33,11 @@ impl SweepSketch for Sketch {
         core: &mut Core,
     ) -> Solid {
         let path = path.into();
+        let _span = tracing::debug_span!(
+            "sweep_sketch",
+            regions = self.regions().len()
+        )
+        .entered();
         let mut cache = SweepCache::default();
 
         let mut shells = Vec::new();
@@ -0,0 +1,439 @@
+//! # Surface textures on faces
+//!
+//! See [`TextureFace`].
+
+use fj_interop::Mesh;
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::Tolerance,
+        bounding_volume::BoundingVolume,
+        polygonize::Polygonize,
+        sdf::{SignedDistanceField, VoxelGrid},
+    },
+    geometry::{Geometry, GlobalPath, SurfaceGeom, SurfaceProfile},
+    operations::{build::BuildShell, insert::Insert},
+    queries::ClosestPointOnFace,
+    storage::Handle,
+    topology::{Face, Shell, Solid},
+    Core,
+};
+
+use super::{build::BuildSolid, update::UpdateSolid};
+
+/// Apply a surface texture to a face of a [`Solid`]
+pub trait TextureFace {
+    /// Cover the face with a repeated pattern
+    ///
+    /// The face must be planar or cylindrical, otherwise an error is returned.
+    /// The pattern is laid out in the face's surface coordinates, scaled to
+    /// the pitch of the texture. Around a cylinder, the pitch is adjusted
+    /// slightly, so a whole number of repetitions fits and the pattern wraps
+    /// around seamlessly.
+    ///
+    /// Like [`MinkowskiSum::offset`], the result is extracted from the solid's
+    /// [signed distance field], which is displaced by the texture close to the
+    /// face. It is sampled on a grid with `resolution` cells along its longest
+    /// axis, which needs to be fine enough to resolve the pitch and depth of
+    /// the texture.
+    ///
+    /// [`MinkowskiSum::offset`]: super::minkowski::MinkowskiSum::offset
+    /// [signed distance field]: SignedDistanceField
+    fn texture_face(
+        &self,
+        face: &Handle<Face>,
+        texture: Texture,
+        resolution: usize,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Solid, TextureError>;
+}
+
+impl TextureFace for Solid {
+    fn texture_face(
+        &self,
+        face: &Handle<Face>,
+        texture: Texture,
+        resolution: usize,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Solid, TextureError> {
+        let mesh =
+            texture_mesh(self, face, texture, resolution, tolerance, core)?;
+
+        let shell = Shell::from_mesh(&mesh, core).insert(core);
+        Ok(Solid::empty().add_shells([shell], core))
+    }
+}
+
+/// A surface texture
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Texture {
+    /// The pattern that is repeated across the face
+    pub pattern: TexturePattern,
+
+    /// The distance at which the pattern repeats
+    pub pitch: Scalar,
+
+    /// The height of the pattern
+    ///
+    /// A positive depth raises the pattern above the face, adding material. A
+    /// negative depth cuts it into the face, removing material.
+    pub depth: Scalar,
+}
+
+/// The pattern of a [`Texture`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TexturePattern {
+    /// Diamond knurling, as formed by two sets of crossing grooves
+    Knurl,
+
+    /// Ribs that run along the v-axis of the surface
+    ///
+    /// Around a cylinder, these are straight ribs parallel to its axis.
+    Ribs,
+
+    /// Round bumps, arranged in a square grid
+    Bumps,
+}
+
+impl TexturePattern {
+    /// Compute the height of the pattern at the provided coordinates
+    ///
+    /// The coordinates are measured in units of the pitch, meaning the pattern
+    /// repeats with a period of one along both of them. The height is between
+    /// zero and one.
+    pub fn height(&self, point: impl Into<Point<2>>) -> Scalar {
+        let point = point.into();
+
+        // Zero at whole numbers, one halfway between them.
+        let triangle = |x: Scalar| {
+            let fraction = x - x.floor();
+            Scalar::ONE - (fraction * 2. - Scalar::ONE).abs()
+        };
+
+        match self {
+            Self::Knurl => {
+                triangle(point.u + point.v).min(triangle(point.u - point.v))
+            }
+            Self::Ribs => triangle(point.u),
+            Self::Bumps => {
+                let [u, v] = [point.u, point.v]
+                    .map(|x| x - x.floor() - Scalar::from(0.5));
+                let radius = Scalar::from(0.4);
+
+                (Scalar::ONE - (u * u + v * v) / (radius * radius))
+                    .max(Scalar::ZERO)
+            }
+        }
+    }
+}
+
+/// Error applying a [`Texture`] to a face
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum TextureError {
+    /// The face is neither planar nor cylindrical
+    ///
+    /// Cones, spheres, and tori are not supported, as their circumference
+    /// changes along the v-axis, which would distort the texture.
+    #[error("Only planar and cylindrical faces can be textured")]
+    UnsupportedSurface,
+}
+
+fn texture_mesh(
+    solid: &Solid,
+    face: &Handle<Face>,
+    texture: Texture,
+    resolution: usize,
+    tolerance: impl Into<Tolerance>,
+    core: &mut Core,
+) -> Result<Mesh<Point<3>>, TextureError> {
+    let layout =
+        TextureLayout::new(face, texture.pitch, &core.layers.geometry)?;
+    let Some(aabb) = solid.aabb(&core.layers.geometry) else {
+        return Ok(Mesh::new());
+    };
+
+    let sdf = SignedDistanceField::new(solid, tolerance, core);
+
+    // The grid needs to contain the raised texture, with some room to spare,
+    // so the surface is closed.
+    let longest = aabb
+        .size()
+        .components
+        .into_iter()
+        .max()
+        .expect("Vector has components");
+    let cell_size = longest / Scalar::from_u64(resolution.max(1) as u64);
+    let margin = Vector::from([texture.depth.max(Scalar::ZERO) + cell_size; 3]);
+
+    let grid_aabb = Aabb {
+        min: aabb.min - margin,
+        max: aabb.max + margin,
+    };
+    let grid_resolution = grid_aabb
+        .size()
+        .components
+        .map(|length| (length / cell_size).ceil().into_u64() as usize + 1);
+
+    let mesh = VoxelGrid::from_fn(grid_aabb, grid_resolution, |point| {
+        let geometry = &core.layers.geometry;
+        let distance = sdf.distance(point, geometry);
+
+        // The texture only applies where the face is the closest part of the
+        // solid's surface.
+        let Some((distance_to_face, coords)) = layout.project(point, geometry)
+        else {
+            return distance;
+        };
+        if distance_to_face > distance.abs() + cell_size {
+            return distance;
+        }
+
+        distance - texture.depth * texture.pattern.height(coords)
+    })
+    .polygonize();
+
+    Ok(mesh)
+}
+
+/// The layout of a texture on a face
+struct TextureLayout<'r> {
+    face: &'r Handle<Face>,
+    surface: SurfaceGeom,
+
+    /// The scale from surface coordinates to texture coordinates
+    scale: Vector<2>,
+
+    /// The range of v-coordinates covered by a cylindrical face
+    v_range: Option<[Scalar; 2]>,
+}
+
+impl<'r> TextureLayout<'r> {
+    /// # Panics
+    ///
+    /// Panics, if the face's surface is a cylinder whose axis is not
+    /// perpendicular to its circle, or if the pitch is not positive.
+    fn new(
+        face: &'r Handle<Face>,
+        pitch: Scalar,
+        geometry: &Geometry,
+    ) -> Result<Self, TextureError> {
+        assert!(pitch > Scalar::ZERO, "Pitch of texture must be positive");
+
+        let surface = *geometry.of_surface(face.surface());
+        let v_length = surface.v.magnitude();
+
+        let (scale, v_range) = match (surface.u, surface.profile) {
+            (GlobalPath::Line(line), _) => {
+                let u_length = line.direction().magnitude();
+                (Vector::from([u_length / pitch, v_length / pitch]), None)
+            }
+            (GlobalPath::Circle(circle), SurfaceProfile::Linear { taper })
+                if taper == Scalar::ZERO =>
+            {
+                // The u-coordinate is an angle here. A whole number of
+                // repetitions needs to fit into a full turn.
+                let circumference = circle.radius() * Scalar::TAU;
+                let repetitions =
+                    (circumference / pitch).round().max(Scalar::ONE);
+
+                let mut v_coords =
+                    face.region().exterior().half_edges().iter().map(
+                        |half_edge| {
+                            geometry.of_half_edge(half_edge).start_position().v
+                        },
+                    );
+                let first = v_coords.next().unwrap_or(Scalar::ZERO);
+                let v_range = v_coords.fold([first, first], |[min, max], v| {
+                    [min.min(v), max.max(v)]
+                });
+
+                (
+                    Vector::from([repetitions / Scalar::TAU, v_length / pitch]),
+                    Some(v_range),
+                )
+            }
+            (GlobalPath::Circle(_), _) => {
+                return Err(TextureError::UnsupportedSurface);
+            }
+        };
+
+        Ok(Self {
+            face,
+            surface,
+            scale,
+            v_range,
+        })
+    }
+
+    /// Project a point onto the face
+    ///
+    /// Returns the distance of the point from the face, and the texture
+    /// coordinates of its projection. Returns `None`, if the point doesn't
+    /// project into the face.
+    fn project(
+        &self,
+        point: Point<3>,
+        geometry: &Geometry,
+    ) -> Option<(Scalar, Point<2>)> {
+        let surface_coords = self.surface.project_global_point(point);
+        let projected = self.surface.point_from_surface_coords(surface_coords);
+
+        let is_within_face = match self.v_range {
+            Some([min, max]) => {
+                surface_coords.v >= min && surface_coords.v <= max
+            }
+            None => {
+                // If the point projects outside of the face, the closest point
+                // is on the face's boundary instead.
+                self.face.closest_point_on_face(point, geometry)
+                    == Some(projected)
+            }
+        };
+        if !is_within_face {
+            return None;
+        }
+        let distance = projected.distance_to(&point);
+
+        let texture_coords = Point::from([
+            surface_coords.u * self.scale.u,
+            surface_coords.v * self.scale.v,
+        ]);
+
+        Some((distance, texture_coords))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point, Scalar};
+
+    use crate::{
+        algorithms::approx::Tolerance, geometry::GlobalPath,
+        operations::build::BuildSolid, topology::Solid, Core,
+    };
+
+    use super::{
+        texture_mesh, Texture, TextureError, TextureLayout, TexturePattern,
+    };
+
+    #[test]
+    fn texture_patterns() {
+        for pattern in [
+            TexturePattern::Knurl,
+            TexturePattern::Ribs,
+            TexturePattern::Bumps,
+        ] {
+            assert_eq!(pattern.height([0., 0.]), Scalar::ZERO);
+            assert_eq!(
+                pattern.height([0.25, 0.125]),
+                pattern.height([2.25, -0.875])
+            );
+        }
+
+        assert_eq!(TexturePattern::Knurl.height([0.5, 0.]), Scalar::ONE);
+        assert_eq!(TexturePattern::Ribs.height([0.5, 0.]), Scalar::ONE);
+        assert_eq!(TexturePattern::Bumps.height([0.5, 0.5]), Scalar::ONE);
+    }
+
+    #[test]
+    fn texture_wraps_around_cylinder() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+        let side = cylinder
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .find(|face| {
+                matches!(
+                    core.layers.geometry.of_surface(face.surface()).u,
+                    GlobalPath::Circle(_)
+                )
+            })
+            .expect("Cylinder has a side face");
+
+        // The circumference is about 6.28, so the pitch is adjusted to fit six
+        // repetitions.
+        let layout =
+            TextureLayout::new(side, Scalar::ONE, &core.layers.geometry)?;
+        assert_eq!(layout.scale.u * Scalar::TAU, Scalar::from(6.));
+
+        Ok(())
+    }
+
+    #[test]
+    fn texture_rejects_cone_and_sphere() {
+        let mut core = Core::new();
+
+        let cone = Solid::cone(1., 0.5, 1., &mut core);
+        let sphere = Solid::sphere(1., &mut core);
+
+        for solid in [cone, sphere] {
+            let face = solid
+                .shells()
+                .only()
+                .faces()
+                .iter()
+                .find(|face| {
+                    matches!(
+                        core.layers.geometry.of_surface(face.surface()).u,
+                        GlobalPath::Circle(_)
+                    )
+                })
+                .expect("Solid has a curved face");
+
+            assert!(matches!(
+                TextureLayout::new(face, Scalar::ONE, &core.layers.geometry),
+                Err(TextureError::UnsupportedSurface)
+            ));
+        }
+    }
+
+    #[test]
+    fn raised_ribs_on_top_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = Solid::rounded_box([2., 2., 2.], 0.25, &mut core);
+        let top = solid
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .find(|face| {
+                let geometry = &core.layers.geometry;
+                let surface = geometry.of_surface(face.surface());
+                surface.point_from_surface_coords([0., 0.]).z
+                    == Scalar::from(2.)
+                    && matches!(surface.u, GlobalPath::Line(_))
+            })
+            .expect("Box has a top face")
+            .clone();
+        let texture = Texture {
+            pattern: TexturePattern::Ribs,
+            pitch: Scalar::from(0.5),
+            depth: Scalar::from(0.2),
+        };
+        let tolerance = Tolerance::from_scalar(0.1)?;
+
+        // Converting the mesh into a solid takes a while, and doesn't change
+        // its shape. Test the mesh directly.
+        let mesh =
+            texture_mesh(&solid, &top, texture, 20, tolerance, &mut core)?;
+        let aabb = Aabb::<3>::from_points(mesh.vertices());
+
+        // Only the top is raised. The grid only approximates the surfaces.
+        let max_error = Scalar::from(0.1);
+        for (actual, expected) in [aabb.min, aabb.max]
+            .into_iter()
+            .zip([Point::from([-1., -1., 0.]), Point::from([1., 1., 2.2])])
+        {
+            assert!((actual - expected).magnitude() < max_error, "{aabb:?}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,477 @@
+//! # Threads
+//!
+//! See [`ThreadFace`].
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceProfile},
+    operations::{build::BuildShell, insert::Insert},
+    storage::Handle,
+    topology::{Face, Shell, Solid},
+    Core,
+};
+
+/// Add a thread to a cylindrical [`Face`]
+pub trait ThreadFace {
+    /// Thread the face
+    ///
+    /// The face must be a full cylinder whose diameter fits the thread. For an
+    /// external thread, that is a diameter between the pitch diameter and the
+    /// major diameter. For an internal thread, it is a diameter between the
+    /// minor diameter and the pitch diameter, as would be left by a tap drill.
+    ///
+    /// The returned [`Thread`] is a cosmetic representation, which doesn't
+    /// change the shape of the face. See [`Thread::solid`] for real thread
+    /// geometry.
+    fn thread(
+        &self,
+        thread: MetricThread,
+        core: &Core,
+    ) -> Result<Thread, ThreadError>;
+}
+
+impl ThreadFace for Handle<Face> {
+    fn thread(
+        &self,
+        thread: MetricThread,
+        core: &Core,
+    ) -> Result<Thread, ThreadError> {
+        let geometry = &core.layers.geometry;
+        let tolerances = core.layers.validation.config.tolerances;
+        let surface = geometry.of_surface(self.surface());
+
+        let circle = match (surface.u, surface.profile) {
+            (GlobalPath::Circle(circle), SurfaceProfile::Linear { taper })
+                if taper == Scalar::ZERO =>
+            {
+                circle
+            }
+            (GlobalPath::Circle(_), _) | (GlobalPath::Line(_), _) => {
+                return Err(ThreadError::NotCylindrical);
+            }
+        };
+        let normal = circle.a().cross(&circle.b()).normalize();
+        let axis = surface.v.normalize();
+        if normal.cross(&axis).magnitude() > tolerances.angular {
+            return Err(ThreadError::NotCylindrical);
+        }
+
+        let mut coords = self
+            .region()
+            .exterior()
+            .half_edges()
+            .iter()
+            .map(|half_edge| geometry.of_half_edge(half_edge).start_position());
+        let Some(first) = coords.next() else {
+            return Err(ThreadError::NotCylindrical);
+        };
+        let [min, max] = coords.fold([first, first], |[min, max], point| {
+            [
+                Point::from([min.u.min(point.u), min.v.min(point.v)]),
+                Point::from([max.u.max(point.u), max.v.max(point.v)]),
+            ]
+        });
+        if (max.u - min.u - Scalar::TAU).abs() > tolerances.angular {
+            return Err(ThreadError::NotCylindrical);
+        }
+
+        let diameter = circle.radius() * 2.;
+        let [min_diameter, max_diameter] = match thread.kind {
+            ThreadKind::External => {
+                [thread.pitch_diameter(), thread.major_diameter()]
+            }
+            ThreadKind::Internal => {
+                [thread.minor_diameter(), thread.pitch_diameter()]
+            }
+        };
+        if diameter < min_diameter - tolerances.linear
+            || diameter > max_diameter + tolerances.linear
+        {
+            return Err(ThreadError::DiameterMismatch {
+                diameter,
+                min_diameter,
+                max_diameter,
+            });
+        }
+
+        // The v-coordinate increases in the direction of `surface.v`, which
+        // might point either way along the axis of the circle. Either way,
+        // the thread runs in the direction of increasing v.
+        let [start, end] =
+            [min.v, max.v].map(|v| circle.center() + surface.v * v);
+
+        Ok(Thread {
+            face: self.clone(),
+            thread,
+            start,
+            end,
+            reference: circle.a().normalize(),
+        })
+    }
+}
+
+/// A thread on a cylindrical face
+///
+/// Returned by [`ThreadFace::thread`].
+#[derive(Clone, Debug)]
+pub struct Thread {
+    /// The face that is threaded
+    pub face: Handle<Face>,
+
+    /// The specification of the thread
+    pub thread: MetricThread,
+
+    /// The point on the axis of the face, where the thread starts
+    pub start: Point<3>,
+
+    /// The point on the axis of the face, where the thread ends
+    pub end: Point<3>,
+
+    /// The direction, perpendicular to the axis, where the thread starts
+    pub reference: Vector<3>,
+}
+
+impl Thread {
+    /// Build the threaded rod that replaces a cylinder with an external thread
+    ///
+    /// The thread is right-handed and follows the basic profile of ISO 68-1,
+    /// with flat crests and roots. Its flanks are approximated by triangles,
+    /// with `segments_per_turn` triangle pairs per turn and per axial step.
+    /// Axial steps are as close to a `segments_per_turn`th of the pitch as the
+    /// length of the thread allows.
+    ///
+    /// Real geometry for internal threads requires subtracting the thread from
+    /// the solid around the face, which is not supported yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `segments_per_turn` is smaller than 3.
+    pub fn solid(
+        &self,
+        segments_per_turn: usize,
+        core: &mut Core,
+    ) -> Result<Solid, ThreadError> {
+        if self.thread.kind == ThreadKind::Internal {
+            return Err(ThreadError::InternalGeometryUnsupported);
+        }
+        assert!(
+            segments_per_turn >= 3,
+            "Thread needs at least 3 segments per turn"
+        );
+
+        let pitch = self.thread.pitch;
+        let length = (self.end - self.start).magnitude();
+        let axis = (self.end - self.start).normalize();
+        let [x, y] = [self.reference, axis.cross(&self.reference)];
+
+        let num_rows = ((length / pitch * segments_per_turn as f64)
+            .round()
+            .into_u64() as usize)
+            .max(1)
+            + 1;
+        let step = length / Scalar::from_u64(num_rows as u64 - 1);
+
+        let mut vertices = Vec::new();
+        for j in 0..num_rows {
+            let height = step * Scalar::from_u64(j as u64);
+
+            for i in 0..segments_per_turn {
+                let turns = Scalar::from_u64(i as u64)
+                    / Scalar::from_u64(segments_per_turn as u64);
+                let radius = self.thread.radius_at(height - pitch * turns);
+
+                let (sin, cos) = (Scalar::TAU * turns).sin_cos();
+                vertices.push(
+                    self.start + axis * height + (x * cos + y * sin) * radius,
+                );
+            }
+        }
+
+        let index =
+            |i: usize, j: usize| j * segments_per_turn + i % segments_per_turn;
+
+        let mut polygons = Vec::new();
+        for j in 0..num_rows - 1 {
+            for i in 0..segments_per_turn {
+                // Splitting along this diagonal lets the triangles follow the
+                // helix, as the phase of the thread is nearly constant along
+                // it.
+                polygons.push(vec![
+                    index(i, j),
+                    index(i + 1, j),
+                    index(i + 1, j + 1),
+                ]);
+                polygons.push(vec![
+                    index(i, j),
+                    index(i + 1, j + 1),
+                    index(i, j + 1),
+                ]);
+            }
+        }
+        polygons
+            .push((0..segments_per_turn).rev().map(|i| index(i, 0)).collect());
+        polygons.push(
+            (0..segments_per_turn)
+                .map(|i| index(i, num_rows - 1))
+                .collect(),
+        );
+
+        let shell = Shell::from_vertices_and_polygons(vertices, polygons, core);
+        Ok(Solid::new([shell.insert(core)]))
+    }
+}
+
+/// An ISO metric thread
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MetricThread {
+    /// The nominal diameter, which is also the major diameter
+    pub nominal_diameter: Scalar,
+
+    /// The pitch, the axial distance between neighboring threads
+    pub pitch: Scalar,
+
+    /// Whether this is an external or internal thread
+    pub kind: ThreadKind,
+}
+
+impl MetricThread {
+    /// The coarse pitches of ISO 261, by nominal diameter
+    pub const COARSE_PITCHES: &'static [(f64, f64)] = &[
+        (1., 0.25),
+        (1.2, 0.25),
+        (1.4, 0.3),
+        (1.6, 0.35),
+        (2., 0.4),
+        (2.5, 0.45),
+        (3., 0.5),
+        (4., 0.7),
+        (5., 0.8),
+        (6., 1.),
+        (8., 1.25),
+        (10., 1.5),
+        (12., 1.75),
+        (14., 2.),
+        (16., 2.),
+        (20., 2.5),
+        (24., 3.),
+        (30., 3.5),
+        (36., 4.),
+        (42., 4.5),
+        (48., 5.),
+        (56., 5.5),
+        (64., 6.),
+    ];
+
+    /// Create a thread from its nominal diameter and pitch
+    pub fn new(
+        nominal_diameter: impl Into<Scalar>,
+        pitch: impl Into<Scalar>,
+        kind: ThreadKind,
+    ) -> Self {
+        Self {
+            nominal_diameter: nominal_diameter.into(),
+            pitch: pitch.into(),
+            kind,
+        }
+    }
+
+    /// Create a thread with the coarse pitch for the nominal diameter
+    ///
+    /// Returns `None`, if the nominal diameter is not in
+    /// [`MetricThread::COARSE_PITCHES`].
+    pub fn coarse(
+        nominal_diameter: impl Into<Scalar>,
+        kind: ThreadKind,
+    ) -> Option<Self> {
+        let nominal_diameter = nominal_diameter.into();
+
+        Self::COARSE_PITCHES
+            .iter()
+            .find(|(diameter, _)| Scalar::from(*diameter) == nominal_diameter)
+            .map(|&(_, pitch)| Self::new(nominal_diameter, pitch, kind))
+    }
+
+    /// The height of the fundamental triangle of the thread profile
+    pub fn fundamental_height(&self) -> Scalar {
+        self.pitch * Scalar::from(3_f64.sqrt()) / 2.
+    }
+
+    /// The major diameter, at the crests of an external thread
+    pub fn major_diameter(&self) -> Scalar {
+        self.nominal_diameter
+    }
+
+    /// The pitch diameter, where threads and gaps are equally wide
+    pub fn pitch_diameter(&self) -> Scalar {
+        self.major_diameter() - self.fundamental_height() * 0.75
+    }
+
+    /// The minor diameter, at the roots of an external thread
+    pub fn minor_diameter(&self) -> Scalar {
+        self.major_diameter() - self.fundamental_height() * 1.25
+    }
+
+    /// The radius of the basic profile at the provided axial position
+    ///
+    /// Position zero is the start of a crest.
+    fn radius_at(&self, position: Scalar) -> Scalar {
+        let major = self.major_diameter() / 2.;
+        let minor = self.minor_diameter() / 2.;
+
+        // The profile in sixteenths of the pitch: a crest of 2, a flank of 5,
+        // a root of 4, and another flank of 5.
+        let phase = position / self.pitch;
+        let phase = (phase - phase.floor()) * 16.;
+
+        let depth = if phase < Scalar::from(2.) {
+            Scalar::ZERO
+        } else if phase < Scalar::from(7.) {
+            (phase - 2.) / 5.
+        } else if phase < Scalar::from(11.) {
+            Scalar::ONE
+        } else {
+            (Scalar::from(16.) - phase) / 5.
+        };
+
+        major - (major - minor) * depth
+    }
+}
+
+/// The kind of a [`MetricThread`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ThreadKind {
+    /// An external thread, like on a bolt
+    External,
+
+    /// An internal thread, like in a nut
+    Internal,
+}
+
+/// Error threading a face
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ThreadError {
+    /// The face is not a full cylinder
+    #[error("Only full cylindrical faces can be threaded")]
+    NotCylindrical,
+
+    /// The diameter of the face doesn't fit the thread
+    #[error(
+        "Diameter of face ({diameter}) doesn't fit thread; expected diameter \
+        between {min_diameter} and {max_diameter}"
+    )]
+    DiameterMismatch {
+        /// The diameter of the face
+        diameter: Scalar,
+
+        /// The smallest diameter that fits the thread
+        min_diameter: Scalar,
+
+        /// The largest diameter that fits the thread
+        max_diameter: Scalar,
+    },
+
+    /// Real geometry was requested for an internal thread
+    #[error("Geometry of internal threads is not supported")]
+    InternalGeometryUnsupported,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        geometry::GlobalPath,
+        operations::{build::BuildSolid, insert::Insert},
+        storage::Handle,
+        topology::{Face, Solid},
+        Core,
+    };
+
+    use super::{MetricThread, ThreadError, ThreadFace, ThreadKind};
+
+    #[test]
+    fn thread_cylindrical_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1.5, 0.125, &mut core);
+        let [side, flat] = {
+            let faces = cylinder.shells().only().faces();
+            let is_side = |face: &&Handle<Face>| {
+                matches!(
+                    core.layers.geometry.of_surface(face.surface()).u,
+                    GlobalPath::Circle(_)
+                )
+            };
+
+            [
+                faces.iter().find(is_side),
+                faces.iter().find(|face| !is_side(face)),
+            ]
+            .map(|face| face.expect("Cylinder has side and flat faces").clone())
+        };
+
+        let m3 = MetricThread::coarse(3., ThreadKind::External)
+            .expect("M3 is a standard thread");
+        assert_eq!(m3.pitch, Scalar::from(0.5));
+
+        let thread = side.thread(m3, &core)?;
+        assert_eq!(
+            (thread.end - thread.start).magnitude(),
+            Scalar::from(0.125)
+        );
+
+        let rod = thread.solid(8, &mut core)?;
+        let shell = rod.shells().only();
+        assert_eq!(shell.faces().len(), 2 * 8 * 2 + 2);
+        let _ = rod.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(
+            flat.thread(m3, &core).err(),
+            Some(ThreadError::NotCylindrical)
+        );
+        assert!(matches!(
+            side.thread(
+                MetricThread::coarse(4., ThreadKind::External)
+                    .expect("M4 is a standard thread"),
+                &core
+            ),
+            Err(ThreadError::DiameterMismatch { .. })
+        ));
+
+        let sphere = Solid::sphere(1.5, &mut core);
+        let sphere = sphere.shells().only().faces().only();
+        assert_eq!(
+            sphere.thread(m3, &core).err(),
+            Some(ThreadError::NotCylindrical)
+        );
+
+        let cone = Solid::cone(1.5, 1.25, 0.125, &mut core);
+        let cone = cone
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .find(|face| {
+                matches!(
+                    core.layers.geometry.of_surface(face.surface()).u,
+                    GlobalPath::Circle(_)
+                )
+            })
+            .expect("Cone has a side face");
+        assert_eq!(
+            cone.thread(m3, &core).err(),
+            Some(ThreadError::NotCylindrical)
+        );
+
+        let internal = MetricThread::new(3.5, 0.6, ThreadKind::Internal);
+        let internal = side.thread(internal, &core)?;
+        assert_eq!(
+            internal.solid(8, &mut core).err(),
+            Some(ThreadError::InternalGeometryUnsupported)
+        );
+
+        Ok(())
+    }
+}
@@ -12,10 +12,11 @@ mod vertex;
 
 use std::collections::{btree_map, BTreeMap};
 
-use fj_math::{Transform, Vector};
+use fj_math::{Rotation, Scalar, Transform, Vector};
 use type_map::TypeMap;
 
 use crate::{
+    geometry::DatumAxis,
     operations::insert::Insert,
     storage::{Handle, ObjectId},
     topology::{AnyObject, Stored},
@@ -65,6 +66,25 @@ pub trait TransformObject: Sized {
     ) -> Self {
         self.transform(&Transform::rotation(axis_angle), core)
     }
+
+    /// Rotate the object around the origin, using a [`Rotation`]
+    ///
+    /// Convenience wrapper around [`TransformObject::transform`].
+    fn rotate_by(&self, rotation: Rotation, core: &mut Core) -> Self {
+        self.transform(&Transform::from_rotation(rotation), core)
+    }
+
+    /// Rotate the object around the provided axis
+    ///
+    /// Convenience wrapper around [`TransformObject::transform`].
+    fn rotate_around(
+        &self,
+        axis: &DatumAxis,
+        angle_rad: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Self {
+        self.transform(&axis.rotation(angle_rad), core)
+    }
 }
 
 impl<T> TransformObject for Handle<T>
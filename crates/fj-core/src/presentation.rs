@@ -16,7 +16,7 @@ use crate::{storage::Handle, topology::Region};
 /// This data is made available through [`Layers`].
 ///
 /// [`Layers`]: crate::layers::Layers
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Presentation {
     /// Color assigned to regions
     ///
@@ -0,0 +1,114 @@
+//! Provenance of objects in the object graph
+//!
+//! See [`Provenance`].
+
+use std::collections::BTreeMap;
+
+use crate::{
+    storage::ObjectId,
+    topology::{AnyObject, Stored},
+};
+
+/// Provenance of objects in the object graph
+///
+/// Records where objects came from: Which object they were derived from (see
+/// [`DeriveFrom`]), and which operations were running, when they were created
+/// or derived (see [`Core::record_operation`]).
+///
+/// This allows finding objects after a series of operations, for example to
+/// select the faces that were created by a specific operation, even after
+/// later operations have modified them.
+///
+/// This data is made available through [`Layers`].
+///
+/// [`DeriveFrom`]: crate::operations::derive::DeriveFrom
+/// [`Core::record_operation`]: crate::Core::record_operation
+/// [`Layers`]: crate::layers::Layers
+#[derive(Clone, Default)]
+pub struct Provenance {
+    /// The origin of objects
+    ///
+    /// Only contains objects that were derived from another, or that were
+    /// inserted while an operation was running.
+    pub origins: BTreeMap<ObjectId, Origin>,
+
+    /// The operations that are currently running, outermost first
+    pub operations: Vec<Operation>,
+}
+
+impl Provenance {
+    /// Access the origin of an object
+    pub fn origin(&self, object: ObjectId) -> Option<&Origin> {
+        self.origins.get(&object)
+    }
+
+    /// Iterate over the history of an object
+    ///
+    /// Yields the origin of the object, then the origin of the object it was
+    /// derived from, and so on.
+    pub fn history(&self, object: ObjectId) -> impl Iterator<Item = &Origin> {
+        let mut next = Some(object);
+
+        std::iter::from_fn(move || {
+            let origin = self.origin(next?)?;
+            next = origin.original.as_ref().map(|original| original.id());
+            Some(origin)
+        })
+    }
+
+    /// Determine whether an operation with the provided tag affected an object
+    ///
+    /// Returns `true`, if the object, or any object it was derived from, was
+    /// created or derived while an operation with that tag was running.
+    pub fn is_from_tag(&self, object: ObjectId, tag: &str) -> bool {
+        self.history(object).any(|origin| {
+            origin
+                .operations
+                .iter()
+                .any(|operation| operation.tag.as_deref() == Some(tag))
+        })
+    }
+}
+
+/// The origin of an object
+///
+/// See [`Provenance`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Origin {
+    /// The object that this object was derived from, if any
+    pub original: Option<AnyObject<Stored>>,
+
+    /// The operations that were running, outermost first
+    pub operations: Vec<Operation>,
+}
+
+/// An operation that objects can originate from
+///
+/// See [`Core::record_operation`].
+///
+/// [`Core::record_operation`]: crate::Core::record_operation
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Operation {
+    /// The name of the operation, like "sweep" or "fillet"
+    pub name: String,
+
+    /// A tag that the user provided, to find the operation's objects later
+    pub tag: Option<String>,
+}
+
+impl Operation {
+    /// Create an operation with the provided name and no tag
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            tag: None,
+        }
+    }
+
+    /// Tag the operation
+    #[must_use]
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    storage::Handle,
+    topology::{Face, Shell, Solid},
+};
+
+/// Access all faces referenced by an object
+pub trait AllFaces {
+    /// Access all faces of the object
+    fn all_faces(&self) -> impl Iterator<Item = Handle<Face>>;
+}
+
+impl AllFaces for Shell {
+    fn all_faces(&self) -> impl Iterator<Item = Handle<Face>> {
+        self.faces().iter().cloned()
+    }
+}
+
+impl AllFaces for Solid {
+    fn all_faces(&self) -> impl Iterator<Item = Handle<Face>> {
+        self.shells().iter().flat_map(|shell| shell.all_faces())
+    }
+}
+
+impl<T> AllFaces for &T
+where
+    T: AllFaces,
+{
+    fn all_faces(&self) -> impl Iterator<Item = Handle<Face>> {
+        (*self).all_faces()
+    }
+}
@@ -0,0 +1,265 @@
+use fj_math::{Point, Scalar, Tolerances, Vector};
+
+use crate::{
+    geometry::{CurveBoundary, Geometry},
+    storage::Handle,
+    topology::{Face, HalfEdge, Handedness, Shell},
+};
+
+use super::{
+    project_point_onto_half_edge::tangent_of_half_edge, SiblingOfHalfEdge,
+};
+
+/// Classify the edges of a [`Shell`] by the angle between their faces
+pub trait ClassifyEdges {
+    /// Classify all edges that are shared by two faces
+    ///
+    /// The angle between the faces is measured at the middle of each edge.
+    /// Edges whose faces meet at an angle within the angular tolerance of a
+    /// straight continuation are classified as tangent.
+    ///
+    /// Each edge is returned once, in the order in which one of its half-edges
+    /// is first encountered in the shell. Half-edges without a sibling are
+    /// skipped.
+    fn classify_edges(
+        &self,
+        tolerances: &Tolerances,
+        geometry: &Geometry,
+    ) -> Vec<ClassifiedEdge>;
+}
+
+impl ClassifyEdges for Shell {
+    fn classify_edges(
+        &self,
+        tolerances: &Tolerances,
+        geometry: &Geometry,
+    ) -> Vec<ClassifiedEdge> {
+        let half_edges = self
+            .faces()
+            .iter()
+            .flat_map(|face| {
+                face.region()
+                    .all_cycles()
+                    .flat_map(|cycle| cycle.half_edges().iter())
+                    .map(move |half_edge| (half_edge, face))
+            })
+            .collect::<Vec<_>>();
+
+        let mut edges: Vec<ClassifiedEdge> = Vec::new();
+
+        for &(half_edge, face) in &half_edges {
+            if edges.iter().any(|edge| &edge.sibling == half_edge) {
+                continue;
+            }
+
+            let Some(&(sibling, sibling_face)) =
+                half_edges.iter().find(|(sibling, _)| {
+                    self.are_siblings(half_edge, sibling, geometry)
+                })
+            else {
+                continue;
+            };
+
+            // Siblings have reversed boundaries on the same curve, so the
+            // middle of one is the middle of the other.
+            let [start, end] = geometry.of_half_edge(half_edge).boundary.inner;
+            let middle = start + (end - start) * 0.5;
+
+            let a = normal_at(face, half_edge, middle, geometry);
+            let b = normal_at(sibling_face, sibling, middle, geometry);
+            let tangent = tangent_at(face, half_edge, middle, geometry);
+
+            // The faces bend away from each other, if the normals rotate
+            // around the edge in its direction. The dihedral angle is the
+            // angle on the inside of the shell.
+            let bend = a.cross(&b).dot(&tangent).atan2(a.dot(&b));
+            let dihedral_angle = Scalar::PI - bend;
+
+            let kind = if bend.abs() < tolerances.angular {
+                EdgeKind::Tangent
+            } else if bend > Scalar::ZERO {
+                EdgeKind::Convex
+            } else {
+                EdgeKind::Concave
+            };
+
+            edges.push(ClassifiedEdge {
+                half_edge: half_edge.clone(),
+                sibling: sibling.clone(),
+                dihedral_angle,
+                kind,
+            });
+        }
+
+        edges
+    }
+}
+
+/// An edge classified by [`ClassifyEdges`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClassifiedEdge {
+    /// The half-edge that was encountered first
+    pub half_edge: Handle<HalfEdge>,
+
+    /// The sibling of the half-edge
+    pub sibling: Handle<HalfEdge>,
+
+    /// The angle between the faces, as measured on the inside of the shell
+    ///
+    /// This is smaller than π for convex edges, larger than π for concave ones,
+    /// and close to π for tangent ones.
+    pub dihedral_angle: Scalar,
+
+    /// The kind of the edge
+    pub kind: EdgeKind,
+}
+
+/// The kind of an edge, depending on the angle between its faces
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum EdgeKind {
+    /// The faces form an outside corner
+    Convex,
+
+    /// The faces form an inside corner
+    Concave,
+
+    /// The faces continue smoothly into each other
+    Tangent,
+}
+
+/// Compute the unit normal of a face, at a point on one of its half-edges
+///
+/// The normal points to the front side of the face.
+fn normal_at(
+    face: &Face,
+    half_edge: &Handle<HalfEdge>,
+    point: Point<1>,
+    geometry: &Geometry,
+) -> Vector<3> {
+    let surface = geometry.of_surface(face.surface());
+    let point_surface = geometry
+        .of_half_edge(half_edge)
+        .path
+        .point_from_path_coords(point);
+
+    let normal = surface
+        .u
+        .tangent_at([point_surface.u])
+        .cross(&surface.v)
+        .normalize();
+
+    match face.coord_handedness(geometry) {
+        Handedness::RightHanded => normal,
+        Handedness::LeftHanded => -normal,
+    }
+}
+
+/// Compute the direction of a half-edge at the given point, in 3D
+fn tangent_at(
+    face: &Face,
+    half_edge: &Handle<HalfEdge>,
+    point: Point<1>,
+    geometry: &Geometry,
+) -> Vector<3> {
+    let surface = geometry.of_surface(face.surface());
+    let half_edge = geometry.of_half_edge(half_edge);
+    let point_surface = half_edge.path.point_from_path_coords(point);
+    let tangent = tangent_of_half_edge(half_edge, point);
+
+    // `tangent_at` returns a unit vector. Scaling it by the length of one unit
+    // of path coordinates yields the derivative along the u-axis.
+    let du = surface.u.tangent_at([point_surface.u])
+        * surface.u.arc_length(CurveBoundary::from([[0.], [1.]]));
+
+    du * tangent.u + surface.v * tangent.v
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Scalar, Tolerances};
+
+    use crate::{
+        operations::build::{BuildShell, BuildSolid},
+        topology::{Shell, Solid},
+        Core,
+    };
+
+    use super::{ClassifyEdges, EdgeKind};
+
+    #[test]
+    fn cuboid() {
+        let mut core = Core::new();
+
+        let solid = Solid::rounded_box([2., 2., 2.], 0., &mut core);
+        let shell = solid.shells().only();
+
+        let edges =
+            shell.classify_edges(&Tolerances::default(), &core.layers.geometry);
+        assert_eq!(edges.len(), 12);
+
+        for edge in edges {
+            assert_eq!(edge.kind, EdgeKind::Convex);
+            assert!(
+                (edge.dihedral_angle - Scalar::PI / 2.).abs()
+                    < Scalar::from(1e-12)
+            );
+        }
+    }
+
+    #[test]
+    fn l_shaped_prism() {
+        let mut core = Core::new();
+
+        // An L-shape in the xy-plane, extruded along z. The edge between
+        // vertices 3 and 9 is on the inside of the L.
+        let outline =
+            [[0., 0.], [2., 0.], [2., 1.], [1., 1.], [1., 2.], [0., 2.]];
+        let vertices = outline
+            .iter()
+            .map(|[x, y]| [*x, *y, 0.])
+            .chain(outline.iter().map(|[x, y]| [*x, *y, 1.]));
+        let sides = (0..6).map(|i| {
+            let j = (i + 1) % 6;
+            vec![i, j, j + 6, i + 6]
+        });
+        let polygons = [vec![5, 4, 3, 2, 1, 0], vec![6, 7, 8, 9, 10, 11]]
+            .into_iter()
+            .chain(sides);
+
+        let shell =
+            Shell::from_vertices_and_polygons(vertices, polygons, &mut core);
+
+        let edges =
+            shell.classify_edges(&Tolerances::default(), &core.layers.geometry);
+        assert_eq!(edges.len(), 18);
+
+        let concave = edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::Concave)
+            .collect::<Vec<_>>();
+        assert_eq!(concave.len(), 1);
+        assert!(
+            (concave[0].dihedral_angle - Scalar::PI * 1.5).abs()
+                < Scalar::from(1e-12)
+        );
+    }
+
+    #[test]
+    fn rounded_box() {
+        let mut core = Core::new();
+
+        let solid = Solid::rounded_box([4., 3., 2.], 0.5, &mut core);
+        let shell = solid.shells().only();
+
+        let edges =
+            shell.classify_edges(&Tolerances::default(), &core.layers.geometry);
+        let count =
+            |kind| edges.iter().filter(|edge| edge.kind == kind).count();
+
+        // Each rounded corner continues smoothly into two flat sides. All
+        // other edges are on the top and bottom.
+        assert_eq!(count(EdgeKind::Tangent), 8);
+        assert_eq!(count(EdgeKind::Convex), 16);
+        assert_eq!(count(EdgeKind::Concave), 0);
+    }
+}
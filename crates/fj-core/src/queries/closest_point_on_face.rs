@@ -0,0 +1,228 @@
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{Geometry, GlobalPath},
+    topology::Face,
+};
+
+use super::project_point_onto_half_edge::{
+    tangent_of_half_edge, ClosestPointOnHalfEdge,
+};
+
+/// Find the point on a face that is closest to another point
+pub trait ClosestPointOnFace {
+    /// Find the point on the face that is closest to the provided point
+    ///
+    /// If the provided point projects into the face's boundary, the projected
+    /// point is returned. Otherwise, the closest point on the face's boundary
+    /// is returned.
+    ///
+    /// Returns `None`, if the face's surface is not a plane. Neither projecting
+    /// onto curved surfaces, nor finding the closest point on their boundaries
+    /// is supported yet.
+    fn closest_point_on_face(
+        &self,
+        point: impl Into<Point<3>>,
+        geometry: &Geometry,
+    ) -> Option<Point<3>>;
+}
+
+impl ClosestPointOnFace for Face {
+    fn closest_point_on_face(
+        &self,
+        point: impl Into<Point<3>>,
+        geometry: &Geometry,
+    ) -> Option<Point<3>> {
+        let surface = geometry.of_surface(self.surface());
+        let GlobalPath::Line(_) = surface.u else {
+            return None;
+        };
+
+        let point_surface = surface.project_global_point(point);
+        let point_on_surface = surface.point_from_surface_coords(point_surface);
+
+        let closest =
+            match closest_point_on_boundary(self, point_on_surface, geometry) {
+                Some((closest, false)) => closest.point_global,
+                Some((_, true)) | None => point_on_surface,
+            };
+
+        Some(closest)
+    }
+}
+
+/// Find the closest point on the boundary of a face
+///
+/// Expects the provided point to be located on the face's surface. Returns the
+/// closest point, and whether the provided point is contained within the face.
+/// Returns `None`, if the face has no boundary.
+pub(super) fn closest_point_on_boundary(
+    face: &Face,
+    point: Point<3>,
+    geometry: &Geometry,
+) -> Option<(ClosestPointOnHalfEdge, bool)> {
+    let surface = geometry.of_surface(face.surface());
+
+    let mut closest: Option<(ClosestPointOnHalfEdge, Vector<2>, Vector<2>)> =
+        None;
+
+    for cycle in face.region().all_cycles() {
+        for (half_edge, next) in cycle.half_edges().pairs() {
+            let half_edge_geom = geometry.of_half_edge(half_edge);
+            let candidate =
+                ClosestPointOnHalfEdge::compute(half_edge_geom, surface, point);
+
+            if let Some((current, _, _)) = &closest {
+                if candidate.distance >= current.distance {
+                    continue;
+                }
+            }
+
+            let [start, end] = half_edge_geom.boundary.inner;
+
+            // If the closest point is a vertex, we need the directions of both
+            // half-edges that meet there, to figure out which side of the
+            // boundary the point is on.
+            let tangents = if candidate.point_curve == start {
+                let previous = cycle
+                    .half_edges()
+                    .iter()
+                    .find(|previous| {
+                        cycle.half_edges().after(previous) == Some(half_edge)
+                    })
+                    .expect("Cycle must contain half-edge before this one");
+                let previous_geom = geometry.of_half_edge(previous);
+                let [_, previous_end] = previous_geom.boundary.inner;
+
+                [
+                    tangent_of_half_edge(previous_geom, previous_end),
+                    tangent_of_half_edge(half_edge_geom, start),
+                ]
+            } else if candidate.point_curve == end {
+                let next_geom = geometry.of_half_edge(next);
+                let [next_start, _] = next_geom.boundary.inner;
+
+                [
+                    tangent_of_half_edge(half_edge_geom, end),
+                    tangent_of_half_edge(next_geom, next_start),
+                ]
+            } else {
+                let tangent =
+                    tangent_of_half_edge(half_edge_geom, candidate.point_curve);
+                [tangent, tangent]
+            };
+
+            let [incoming, outgoing] = tangents;
+            closest = Some((candidate, incoming, outgoing));
+        }
+    }
+
+    let (closest, incoming, outgoing) = closest?;
+
+    if closest.distance == Scalar::ZERO {
+        return Some((closest, true));
+    }
+
+    // Whether the face is on the left side of its boundary, in surface
+    // coordinates, depends on the winding of its exterior.
+    let orientation = if face.region().exterior().winding(geometry).is_ccw() {
+        Scalar::ONE
+    } else {
+        -Scalar::ONE
+    };
+
+    let to_point = surface.project_global_point(point) - closest.point_surface;
+    let is_left_of = |tangent: Vector<2>| {
+        tangent.cross2d(&to_point) * orientation > Scalar::ZERO
+    };
+
+    let is_convex = incoming.cross2d(&outgoing) * orientation >= Scalar::ZERO;
+    let is_contained = if is_convex {
+        is_left_of(incoming) && is_left_of(outgoing)
+    } else {
+        is_left_of(incoming) || is_left_of(outgoing)
+    };
+
+    Some((closest, is_contained))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        geometry::GlobalPath,
+        operations::{
+            build::{BuildCycle, BuildFace, BuildSolid},
+            update::{UpdateFace, UpdateRegion},
+        },
+        queries::ClosestPointOnFace,
+        topology::{Cycle, Face, Solid},
+        Core,
+    };
+
+    #[test]
+    fn closest_point_on_face() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let face = Face::polygon(
+            surface.clone(),
+            [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+            &mut core,
+        )
+        .update_region(
+            |region, core| {
+                region.add_interiors(
+                    [Cycle::polygon(
+                        [[1., 1.], [1., 2.], [2., 2.], [2., 1.]],
+                        surface,
+                        core,
+                    )],
+                    core,
+                )
+            },
+            &mut core,
+        );
+
+        let closest = |point: [f64; 3]| {
+            face.closest_point_on_face(point, &core.layers.geometry)
+        };
+
+        // Inside of the face
+        assert_eq!(closest([3., 3., 1.]), Some(Point::from([3., 3., 0.])));
+
+        // Outside of the exterior
+        assert_eq!(closest([5., 2., -1.]), Some(Point::from([4., 2., 0.])));
+        assert_eq!(closest([-1., -1., 0.]), Some(Point::from([0., 0., 0.])));
+
+        // Inside of the hole
+        assert_eq!(closest([1.5, 1.25, 0.]), Some(Point::from([1.5, 1., 0.])));
+    }
+
+    #[test]
+    fn closest_point_on_curved_face() {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+        let side = cylinder
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .find(|face| {
+                matches!(
+                    core.layers.geometry.of_surface(face.surface()).u,
+                    GlobalPath::Circle(_)
+                )
+            })
+            .expect("Cylinder must have a side face")
+            .clone();
+
+        assert_eq!(
+            side.closest_point_on_face([0., 3., -3.], &core.layers.geometry),
+            None,
+        );
+    }
+}
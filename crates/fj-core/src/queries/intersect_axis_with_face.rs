@@ -0,0 +1,371 @@
+use fj_math::{Circle, Line, Point, Scalar, Tolerances, Vector};
+
+use crate::{
+    geometry::{Geometry, GlobalPath, SurfaceGeom, SurfaceProfile},
+    topology::Face,
+};
+
+use super::closest_point_on_face::closest_point_on_boundary;
+
+/// Intersect an axis with a face
+pub trait IntersectAxisWithFace {
+    /// Intersect the provided axis with the face
+    ///
+    /// The axis is treated as an infinite line. Returns the point where the
+    /// axis pierces the face, or `None`, if it doesn't. An axis that is
+    /// parallel to the face is considered to not intersect it, even if it lies
+    /// within the face. This includes axes that are parallel to the axis of a
+    /// cylindrical face.
+    ///
+    /// A curved face can be pierced more than once. In that case, the point
+    /// that is closest to the origin of the axis is returned.
+    fn intersect_axis_with_face(
+        &self,
+        axis: &Line<3>,
+        tolerances: &Tolerances,
+        geometry: &Geometry,
+    ) -> Option<Point<3>>;
+}
+
+impl IntersectAxisWithFace for Face {
+    fn intersect_axis_with_face(
+        &self,
+        axis: &Line<3>,
+        tolerances: &Tolerances,
+        geometry: &Geometry,
+    ) -> Option<Point<3>> {
+        let surface = geometry.of_surface(self.surface());
+
+        let candidates = match surface.u {
+            GlobalPath::Line(u) => {
+                let normal = u.direction().cross(&surface.v);
+
+                if tolerances.are_perpendicular(&normal, &axis.direction()) {
+                    return None;
+                }
+                let denominator = normal.dot(&axis.direction());
+
+                let t = normal.dot(&(u.origin() - axis.origin())) / denominator;
+                vec![t]
+            }
+            GlobalPath::Circle(circle) => intersect_axis_with_circular_surface(
+                axis, circle, surface, tolerances,
+            ),
+        };
+
+        candidates
+            .into_iter()
+            .map(|t| (t.abs(), axis.point_from_line_coords([t])))
+            .filter(|&(_, intersection)| {
+                closest_point_on_boundary(self, intersection, geometry)
+                    .is_some_and(|(_, is_contained)| is_contained)
+            })
+            .min_by_key(|&(distance, _)| distance)
+            .map(|(_, intersection)| intersection)
+    }
+}
+
+/// Intersect an axis with a surface whose u-axis is a circle
+///
+/// Returns the line coordinates of all intersections.
+///
+/// In the coordinate system that is spanned by the two axes of the circle and
+/// the v-axis of the surface, with its origin at the center of the circle,
+/// cylinders, cones, and spheres are quadrics. Cylinders and cones (see
+/// [`SurfaceProfile::Linear`]) fulfill `x² + y² = (1 + taper * z)²`, spheres
+/// fulfill `x² + y² + z² = 1`. Substituting the axis results in a quadratic
+/// equation.
+///
+/// Tori fulfill `(x² + y² + 1 + ρ² * (z² - 1))² = 4 * (x² + y²)`, where `ρ`
+/// is the ratio of the radius of their tube to the radius of their u-axis.
+/// Substituting the axis results in a quartic equation.
+fn intersect_axis_with_circular_surface(
+    axis: &Line<3>,
+    circle: Circle<3>,
+    surface: &SurfaceGeom,
+    tolerances: &Tolerances,
+) -> Vec<Scalar> {
+    let [a, b, v] = [circle.a(), circle.b(), surface.v];
+    let determinant = a.dot(&b.cross(&v));
+    let to_basis = |x: Vector<3>| {
+        [
+            x.dot(&b.cross(&v)),
+            a.dot(&x.cross(&v)),
+            a.dot(&b.cross(&x)),
+        ]
+        .map(|component| component / determinant)
+    };
+
+    let [o0, o1, o2] = to_basis(axis.origin() - circle.center());
+    let [d0, d1, d2] = to_basis(axis.direction());
+
+    let [qa, qb, qc] = match surface.profile {
+        SurfaceProfile::Linear { taper } => {
+            if taper == Scalar::ZERO
+                && tolerances.are_parallel(&axis.direction(), &v)
+            {
+                return Vec::new();
+            }
+
+            let radius = Scalar::ONE + taper * o2;
+            [
+                d0 * d0 + d1 * d1 - taper * taper * d2 * d2,
+                (o0 * d0 + o1 * d1 - taper * d2 * radius) * 2.,
+                o0 * o0 + o1 * o1 - radius * radius,
+            ]
+        }
+        SurfaceProfile::Spherical => [
+            d0 * d0 + d1 * d1 + d2 * d2,
+            (o0 * d0 + o1 * d1 + o2 * d2) * 2.,
+            o0 * o0 + o1 * o1 + o2 * o2 - Scalar::ONE,
+        ],
+        SurfaceProfile::Toroidal => {
+            let ratio = v.magnitude() / circle.radius();
+            let ratio_sq = ratio * ratio;
+
+            // `x² + y²` and the left side of the equation, before squaring it,
+            // are both quadratic in the line coordinate.
+            let q = [
+                o0 * o0 + o1 * o1,
+                (o0 * d0 + o1 * d1) * 2.,
+                d0 * d0 + d1 * d1,
+            ];
+            let s = [
+                q[0] + Scalar::ONE + ratio_sq * (o2 * o2 - Scalar::ONE),
+                q[1] + ratio_sq * o2 * d2 * 2.,
+                q[2] + ratio_sq * d2 * d2,
+            ];
+
+            return real_roots(&[
+                s[0] * s[0] - q[0] * 4.,
+                s[0] * s[1] * 2. - q[1] * 4.,
+                s[1] * s[1] + s[0] * s[2] * 2. - q[2] * 4.,
+                s[1] * s[2] * 2.,
+                s[2] * s[2],
+            ]);
+        }
+    };
+
+    if qa == Scalar::ZERO {
+        // This happens, if the axis is parallel to a straight line on a cone.
+        // It intersects the cone once, unless it lies on it.
+        if qb == Scalar::ZERO {
+            return Vec::new();
+        }
+        return vec![-qc / qb];
+    }
+
+    let discriminant = qb * qb - qa * qc * 4.;
+    if discriminant < Scalar::ZERO {
+        return Vec::new();
+    }
+
+    let root = Scalar::from(discriminant.into_f64().sqrt());
+    vec![(-qb - root) / (qa * 2.), (-qb + root) / (qa * 2.)]
+}
+
+/// Find the real roots of a polynomial
+///
+/// Expects the coefficients in order of increasing degree. Roots of even
+/// multiplicity, where the polynomial touches zero without changing its sign,
+/// are not found.
+///
+/// The roots of the polynomial's derivative split the real line into intervals,
+/// on which the polynomial is monotonic. Each of them contains at most one
+/// root, which is found by bisection.
+fn real_roots(coefficients: &[Scalar]) -> Vec<Scalar> {
+    let Some(degree) = coefficients.iter().rposition(|&c| c != Scalar::ZERO)
+    else {
+        return Vec::new();
+    };
+    let coefficients = &coefficients[..=degree];
+
+    if degree == 0 {
+        return Vec::new();
+    }
+    if degree == 1 {
+        return vec![-coefficients[0] / coefficients[1]];
+    }
+
+    let evaluate = |t: Scalar| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(Scalar::ZERO, |value, &c| value * t + c)
+    };
+
+    // All roots are within this bound (Cauchy's bound).
+    let bound = Scalar::ONE
+        + coefficients[..degree]
+            .iter()
+            .map(|&c| (c / coefficients[degree]).abs())
+            .fold(Scalar::ZERO, Scalar::max);
+
+    let derivative = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| c * i as f64)
+        .collect::<Vec<_>>();
+    let mut limits = real_roots(&derivative);
+    limits.sort();
+    limits.insert(0, -bound);
+    limits.push(bound);
+
+    limits
+        .windows(2)
+        .filter_map(|interval| {
+            let [mut min, mut max] = [interval[0], interval[1]];
+            let sign_at_min = evaluate(min).sign();
+            if sign_at_min == evaluate(max).sign() {
+                return None;
+            }
+
+            for _ in 0..100 {
+                let mid = (min + max) / 2.;
+                if evaluate(mid).sign() == sign_at_min {
+                    min = mid;
+                } else {
+                    max = mid;
+                }
+            }
+
+            Some((min + max) / 2.)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Line, Point, Scalar, Vector};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        geometry::GlobalPath,
+        operations::build::{BuildFace, BuildSolid},
+        queries::IntersectAxisWithFace,
+        topology::{Face, Solid},
+        Core,
+    };
+
+    #[test]
+    fn intersect_axis_with_face() {
+        let mut core = Core::new();
+
+        let face = Face::circle(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0.],
+            1.,
+            &mut core,
+        );
+
+        let intersect = |origin: [f64; 3], direction: [f64; 3]| {
+            face.intersect_axis_with_face(
+                &Line::from_origin_and_direction(
+                    Point::from(origin),
+                    Vector::from(direction),
+                ),
+                &core.tolerances(),
+                &core.layers.geometry,
+            )
+        };
+
+        assert_eq!(
+            intersect([0.5, 0., 3.], [0., 0., -1.]),
+            Some(Point::from([0.5, 0., 0.]))
+        );
+        assert_eq!(intersect([2., 0., 3.], [0., 0., 1.]), None);
+        assert_eq!(intersect([0., 0., 1.], [1., 0., 0.]), None);
+    }
+
+    #[test]
+    fn intersect_axis_with_curved_faces() {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core);
+        let cone = Solid::cone(1., 0., 3., &mut core);
+        let sphere = Solid::sphere(2., &mut core);
+        let torus = Solid::torus(2., 0.5, &mut core);
+
+        let [cylinder, cone, sphere, torus] = [cylinder, cone, sphere, torus]
+            .map(|solid| {
+                solid
+                    .shells()
+                    .only()
+                    .faces()
+                    .iter()
+                    .find(|face| {
+                        matches!(
+                            core.layers.geometry.of_surface(face.surface()).u,
+                            GlobalPath::Circle(_)
+                        )
+                    })
+                    .expect("Solid has a curved face")
+                    .clone()
+            });
+
+        let intersect = |face: &Face, origin: [f64; 3], direction: [f64; 3]| {
+            face.intersect_axis_with_face(
+                &Line::from_origin_and_direction(
+                    Point::from(origin),
+                    Vector::from(direction),
+                ),
+                &core.tolerances(),
+                &core.layers.geometry,
+            )
+        };
+        let assert_close = |actual: Option<Point<3>>, expected: [f64; 3]| {
+            let actual = actual.expect("Expected intersection");
+            assert!(
+                actual.distance_to(&Point::from(expected))
+                    < Scalar::from(1e-12),
+                "{actual:?}"
+            );
+        };
+
+        // Of the two intersections, the one closer to the origin is returned.
+        assert_close(
+            intersect(&cylinder, [0., 3., 1.], [0., -1., 0.]),
+            [0., 1., 1.],
+        );
+        assert_close(
+            intersect(&cylinder, [0., -3., 1.], [0., 2., 0.]),
+            [0., -1., 1.],
+        );
+
+        // Parallel to the cylinder's axis, or beyond its top
+        assert_eq!(intersect(&cylinder, [0.5, 0., 1.], [0., 0., 1.]), None);
+        assert_eq!(intersect(&cylinder, [0., 3., 3.], [0., -1., 0.]), None);
+
+        // The radius of the cone is 0.5, halfway up. Above the apex, the axis
+        // only hits the other nappe of the cone, which is not part of the face.
+        assert_close(
+            intersect(&cone, [0., 3., 1.5], [0., -1., 0.]),
+            [0., 0.5, 1.5],
+        );
+        assert_eq!(intersect(&cone, [0., 3., 4.5], [0., -1., 0.]), None);
+
+        assert_close(
+            intersect(&sphere, [0., 5., 0.], [0., -1., 0.]),
+            [0., 2., 0.],
+        );
+        assert_eq!(intersect(&sphere, [0., 5., 3.], [0., -1., 0.]), None);
+
+        // The axis crosses the torus twice, on either side of its hole. From
+        // within the hole, the inside of the torus is closest. The outermost
+        // circle of the torus is a seam, and not part of its face.
+        assert_close(
+            intersect(&torus, [0., 5., 0.3], [0., -1., 0.]),
+            [0., 2.4, 0.3],
+        );
+        assert_close(
+            intersect(&torus, [0., 0.5, 0.3], [0., 1., 0.]),
+            [0., 1.6, 0.3],
+        );
+        assert_close(
+            intersect(&torus, [0., 2., 3.], [0., 0., -1.]),
+            [0., 2., 0.5],
+        );
+        assert_eq!(intersect(&torus, [0., 0., 3.], [0., 0., -1.]), None);
+    }
+}
@@ -9,12 +9,22 @@
 //! This module provides traits express such non-trivial queries, and implements
 //! them for various objects that have the information to answer the query.
 
+mod all_faces;
 mod all_half_edges_with_surface;
 mod bounding_vertices_of_half_edge;
+mod classify_edges;
+mod closest_point_on_face;
+mod intersect_axis_with_face;
+mod project_point_onto_half_edge;
 mod sibling_of_half_edge;
 
 pub use self::{
+    all_faces::AllFaces,
     all_half_edges_with_surface::AllHalfEdgesWithSurface,
     bounding_vertices_of_half_edge::BoundingVerticesOfHalfEdge,
+    classify_edges::{ClassifiedEdge, ClassifyEdges, EdgeKind},
+    closest_point_on_face::ClosestPointOnFace,
+    intersect_axis_with_face::IntersectAxisWithFace,
+    project_point_onto_half_edge::ProjectPointOntoHalfEdge,
     sibling_of_half_edge::SiblingOfHalfEdge,
 };
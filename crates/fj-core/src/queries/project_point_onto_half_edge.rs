@@ -0,0 +1,261 @@
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{Geometry, HalfEdgeGeom, SurfaceGeom, SurfacePath},
+    storage::Handle,
+    topology::{Face, HalfEdge, Shell},
+};
+
+/// Project a point onto a half-edge of an object
+///
+/// This is useful for deriving positions from existing geometry, instead of
+/// recomputing them from the parameters that were used to create that
+/// geometry.
+pub trait ProjectPointOntoHalfEdge {
+    /// Project a point onto a half-edge of the object
+    ///
+    /// Returns the point on the half-edge that is closest to the provided
+    /// point, or `None`, if the half-edge is not part of the object.
+    ///
+    /// # Implementation Note
+    ///
+    /// Points on circular half-edges are only exact, if the surface the
+    /// half-edge is defined on is not distorted (i.e. its axes are
+    /// perpendicular and of equal length).
+    fn project_point_onto_half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+        point: impl Into<Point<3>>,
+        geometry: &Geometry,
+    ) -> Option<Point<3>>;
+}
+
+impl ProjectPointOntoHalfEdge for Face {
+    fn project_point_onto_half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+        point: impl Into<Point<3>>,
+        geometry: &Geometry,
+    ) -> Option<Point<3>> {
+        let is_part_of_face = self
+            .region()
+            .all_cycles()
+            .any(|cycle| cycle.half_edges().contains(half_edge));
+        if !is_part_of_face {
+            return None;
+        }
+
+        let closest = ClosestPointOnHalfEdge::compute(
+            geometry.of_half_edge(half_edge),
+            geometry.of_surface(self.surface()),
+            point.into(),
+        );
+
+        Some(closest.point_global)
+    }
+}
+
+impl ProjectPointOntoHalfEdge for Shell {
+    fn project_point_onto_half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+        point: impl Into<Point<3>>,
+        geometry: &Geometry,
+    ) -> Option<Point<3>> {
+        let point = point.into();
+
+        self.faces().iter().find_map(|face| {
+            face.project_point_onto_half_edge(half_edge, point, geometry)
+        })
+    }
+}
+
+/// The point on a half-edge that is closest to another point
+pub(super) struct ClosestPointOnHalfEdge {
+    /// The closest point, in curve coordinates
+    pub point_curve: Point<1>,
+
+    /// The closest point, in surface coordinates
+    pub point_surface: Point<2>,
+
+    /// The closest point, in global coordinates
+    pub point_global: Point<3>,
+
+    /// The distance between the closest point and the original point
+    pub distance: Scalar,
+}
+
+impl ClosestPointOnHalfEdge {
+    pub fn compute(
+        half_edge: &HalfEdgeGeom,
+        surface: &SurfaceGeom,
+        point: Point<3>,
+    ) -> Self {
+        let [start, end] = half_edge.boundary.inner;
+        let [min, max] = if start < end {
+            [start, end]
+        } else {
+            [end, start]
+        };
+
+        let t = match half_edge.path {
+            SurfacePath::Circle(circle) => {
+                let center = surface.point_from_surface_coords(circle.center());
+                let a = surface
+                    .vector_from_surface_coords(circle.center(), circle.a());
+                let b = surface
+                    .vector_from_surface_coords(circle.center(), circle.b());
+
+                let to_point = point - center;
+                let angle = Scalar::atan2(
+                    to_point.dot(&b) / b.dot(&b),
+                    to_point.dot(&a) / a.dot(&a),
+                );
+
+                // The angle we computed is in the range (-pi, pi], but the
+                // boundary could be anywhere. Let's move it as close to the
+                // boundary as possible.
+                let turns = ((min.t - angle) / Scalar::TAU).ceil();
+                let angle = angle + turns * Scalar::TAU;
+
+                if angle <= max.t {
+                    angle
+                } else {
+                    // The angle is outside of the boundary. Whatever end of the
+                    // boundary is closer, is the closest point.
+                    let [distance_to_min, distance_to_max] =
+                        [min, max].map(|t| {
+                            let point_global = surface
+                                .point_from_surface_coords(
+                                    half_edge.path.point_from_path_coords(t),
+                                );
+                            (point_global - point).magnitude()
+                        });
+
+                    if distance_to_min < distance_to_max {
+                        min.t
+                    } else {
+                        max.t
+                    }
+                }
+            }
+            SurfacePath::Line(_)
+                if surface.is_path_along_pole(&half_edge.path) =>
+            {
+                // All points of the half-edge are the same, so any of them is
+                // the closest one.
+                min.t
+            }
+            SurfacePath::Line(line) => {
+                let origin = surface.point_from_surface_coords(line.origin());
+                let direction = surface.vector_from_surface_coords(
+                    line.origin(),
+                    line.direction(),
+                );
+
+                let t = (point - origin).dot(&direction)
+                    / direction.dot(&direction);
+
+                t.max(min.t).min(max.t)
+            }
+        };
+
+        let point_curve = Point::from([t]);
+        let point_surface = half_edge.path.point_from_path_coords(point_curve);
+        let point_global = surface.point_from_surface_coords(point_surface);
+        let distance = (point_global - point).magnitude();
+
+        Self {
+            point_curve,
+            point_surface,
+            point_global,
+            distance,
+        }
+    }
+}
+
+/// Compute the direction of a half-edge at the given point, in surface coords
+///
+/// Takes into account, whether the half-edge runs along its path in positive or
+/// negative direction.
+pub(super) fn tangent_of_half_edge(
+    half_edge: &HalfEdgeGeom,
+    point_curve: Point<1>,
+) -> Vector<2> {
+    let tangent = match half_edge.path {
+        SurfacePath::Circle(circle) => {
+            let (sin, cos) = point_curve.t.sin_cos();
+            circle.b() * cos - circle.a() * sin
+        }
+        SurfacePath::Line(line) => line.direction(),
+    };
+
+    let [start, end] = half_edge.boundary.inner;
+    if start < end {
+        tangent
+    } else {
+        -tangent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        operations::build::{BuildFace, BuildShell},
+        queries::ProjectPointOntoHalfEdge,
+        topology::{Face, Shell},
+        Core,
+    };
+
+    #[test]
+    fn project_point_onto_line_segment() {
+        let mut core = Core::new();
+
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [2., 0., 0.], [0., 2., 0.], [0., 0., 2.]],
+            &mut core,
+        );
+        let [ab, ..] = &tetrahedron.abc.half_edges;
+
+        let project = |point: [f64; 3]| {
+            tetrahedron.shell.project_point_onto_half_edge(
+                ab,
+                point,
+                &core.layers.geometry,
+            )
+        };
+
+        assert_eq!(project([1., -1., 3.]), Some(Point::from([1., 0., 0.])));
+        assert_eq!(project([-1., 1., 0.]), Some(Point::from([0., 0., 0.])));
+        assert_eq!(project([5., 1., 0.]), Some(Point::from([2., 0., 0.])));
+    }
+
+    #[test]
+    fn project_point_onto_circle() {
+        let mut core = Core::new();
+
+        let face = Face::circle(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0.],
+            2.,
+            &mut core,
+        );
+        let circle = face.region().exterior().half_edges().only();
+
+        let projected = face
+            .project_point_onto_half_edge(
+                circle,
+                [0., 3., 1.],
+                &core.layers.geometry,
+            )
+            .unwrap();
+
+        assert!(
+            (projected - Point::from([0., 2., 0.])).magnitude()
+                < Scalar::from(1e-12)
+        );
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     topology::{HalfEdge, Shell},
 };
 
-use super::BoundingVerticesOfHalfEdge;
+use super::{AllHalfEdgesWithSurface, BoundingVerticesOfHalfEdge};
 
 /// Queries related to the sibling of a [`HalfEdge`]
 pub trait SiblingOfHalfEdge {
@@ -36,8 +36,27 @@ impl SiblingOfHalfEdge for Shell {
         geometry: &Geometry,
     ) -> bool {
         let same_curve = a.curve().id() == b.curve().id();
-        let same_boundary = geometry.of_half_edge(a).boundary
-            == geometry.of_half_edge(b).boundary.reverse();
+        let same_boundary = {
+            let a_boundary = geometry.of_half_edge(a).boundary;
+            let b_boundary = geometry.of_half_edge(b).boundary.reverse();
+
+            // On a periodic curve, the boundaries can be a whole number of
+            // periods apart, and still refer to the same part of the curve.
+            let period = || {
+                let (_, surface) = self
+                    .all_half_edges_with_surface()
+                    .find(|(half_edge, _)| half_edge.id() == a.id())?;
+                geometry
+                    .of_surface(&surface)
+                    .period_of_path(&geometry.of_half_edge(a).path)
+            };
+
+            a_boundary == b_boundary
+                || same_curve
+                    && period().is_some_and(|period| {
+                        a_boundary.wrap(period) == b_boundary.wrap(period)
+                    })
+        };
         let same_vertices = {
             let Some(a_vertices) = self.bounding_vertices_of_half_edge(a)
             else {
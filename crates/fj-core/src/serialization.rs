@@ -0,0 +1,1225 @@
+//! # Serialization of the object graph
+//!
+//! Building a model can be expensive. This module provides a way to store the
+//! object graph of a [`Core`] instance, meaning its topological objects, their
+//! geometry, their presentation data, their materials, their annotations, their
+//! provenance, and the approximation tolerances that have been set for them,
+//! and to load it again later.
+//!
+//! A stored object graph is represented by [`SerializedCore`]. Each object
+//! refers to other objects by their position within the respective store. This
+//! preserves object identity: Objects that are shared between multiple other
+//! objects are still shared, after the object graph has been loaded.
+//!
+//! Objects are loaded into the stores of the new [`Core`] instance in the same
+//! order in which they were stored in the original one. Iterating over a store
+//! of the loaded instance yields the objects that correspond to the objects of
+//! the original store, in the same order.
+//!
+//! [`SerializedCore`] can be written to and read from files directly. This is
+//! not available on WebAssembly, where there is no file system. It implements
+//! `Serialize` and `Deserialize`, so it can be stored elsewhere there.
+//!
+//! Datums, like [`DatumPlane`], are not part of the object graph. They
+//! implement `Serialize` and `Deserialize` too, so they can be stored next to
+//! it.
+//!
+//! [`DatumPlane`]: crate::geometry::DatumPlane
+
+use std::{collections::BTreeMap, io};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs::File, path::Path};
+
+use fj_interop::{Color, Material};
+use fj_math::{Circle, Line, Plane, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::approx::{InvalidTolerance, Tolerance},
+    annotations::{Annotation, AnnotationError, NamedGeometry},
+    geometry::{
+        CurveBoundary, DatumAxis, DatumPlane, DatumPoint, GlobalPath,
+        HalfEdgeGeom, LocalCurveGeom, SurfaceGeom, SurfacePath, SurfaceProfile,
+    },
+    operations::{
+        insert::Insert, material::SetMaterial, presentation::SetColor,
+    },
+    provenance::{Operation, Origin},
+    storage::{Handle, ObjectId},
+    topology::{
+        AnyObject, Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid,
+        Stored, Surface, Vertex,
+    },
+    validation::ValidationErrors,
+    Core,
+};
+
+/// The version of the serialization format
+///
+/// Must be increased, whenever the format changes.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The serialized object graph of a [`Core`] instance
+///
+/// See [module documentation](self) for more information.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SerializedCore {
+    format_version: u32,
+
+    num_vertices: usize,
+    num_curves: usize,
+    num_surfaces: usize,
+
+    half_edges: Vec<SerializedHalfEdge>,
+    cycles: Vec<Vec<usize>>,
+    regions: Vec<SerializedRegion>,
+    faces: Vec<SerializedFace>,
+    shells: Vec<Vec<usize>>,
+    sketches: Vec<SerializedSketch>,
+    solids: Vec<Vec<usize>>,
+
+    curve_geometry: Vec<SerializedCurveGeom>,
+    half_edge_geometry: Vec<SerializedHalfEdgeGeom>,
+    surface_geometry: Vec<SerializedSurfaceGeom>,
+
+    colors: Vec<(usize, [u8; 4])>,
+    materials: Vec<SerializedMaterial>,
+
+    names: Vec<SerializedName>,
+    annotations: Vec<SerializedAnnotation>,
+
+    origins: Vec<SerializedOrigin>,
+
+    tolerances: Vec<SerializedTolerance>,
+}
+
+impl SerializedCore {
+    /// Serialize the object graph of the provided [`Core`] instance
+    pub fn from_core(core: &Core) -> Self {
+        let topology = &core.layers.topology;
+        let geometry = &core.layers.geometry;
+
+        let vertices = Indices::new(topology.vertices.iter());
+        let curves = Indices::new(topology.curves.iter());
+        let surfaces = Indices::new(topology.surfaces.iter());
+        let half_edges = Indices::new(topology.half_edges.iter());
+        let cycles = Indices::new(topology.cycles.iter());
+        let regions = Indices::new(topology.regions.iter());
+        let faces = Indices::new(topology.faces.iter());
+        let shells = Indices::new(topology.shells.iter());
+        let sketches = Indices::new(topology.sketches.iter());
+        let solids = Indices::new(topology.solids.iter());
+
+        let objects = [
+            (&curves, SerializedObject::Curve as fn(_) -> _),
+            (&cycles, SerializedObject::Cycle),
+            (&faces, SerializedObject::Face),
+            (&half_edges, SerializedObject::HalfEdge),
+            (&regions, SerializedObject::Region),
+            (&shells, SerializedObject::Shell),
+            (&sketches, SerializedObject::Sketch),
+            (&solids, SerializedObject::Solid),
+            (&surfaces, SerializedObject::Surface),
+            (&vertices, SerializedObject::Vertex),
+        ]
+        .into_iter()
+        .flat_map(|(indices, object)| {
+            indices
+                .inner
+                .iter()
+                .map(move |(&id, &index)| (id, object(index)))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+        let mut curve_geometry = Vec::new();
+        for curve in topology.curves.iter() {
+            let Some(curve_geom) = geometry.of_curve(&curve) else {
+                continue;
+            };
+
+            for (surface, local) in &curve_geom.definitions {
+                curve_geometry.push(SerializedCurveGeom {
+                    curve: curves.get(&curve),
+                    surface: surfaces.get(surface),
+                    path: local.path.into(),
+                });
+            }
+        }
+
+        let mut half_edge_geometry = geometry
+            .half_edges()
+            .map(|(half_edge, half_edge_geom)| SerializedHalfEdgeGeom {
+                half_edge: half_edges.get(half_edge),
+                path: half_edge_geom.path.into(),
+                boundary: half_edge_geom
+                    .boundary
+                    .inner
+                    .map(|point| point.t.into_f64()),
+            })
+            .collect::<Vec<_>>();
+        half_edge_geometry.sort_by_key(|geom| geom.half_edge);
+
+        let mut surface_geometry = geometry
+            .surfaces()
+            .map(|(surface, surface_geom)| SerializedSurfaceGeom {
+                surface: surfaces.get(surface),
+                u: surface_geom.u.into(),
+                v: vector_to_array(surface_geom.v),
+                profile: surface_geom.profile.into(),
+            })
+            .collect::<Vec<_>>();
+        surface_geometry.sort_by_key(|geom| geom.surface);
+
+        let mut colors = core
+            .layers
+            .presentation
+            .color
+            .iter()
+            .map(|(region, color)| (regions.get(region), color.0))
+            .collect::<Vec<_>>();
+        colors.sort();
+
+        let mut materials = core
+            .layers
+            .materials
+            .material
+            .iter()
+            .map(|(region, material)| SerializedMaterial {
+                region: regions.get(region),
+                name: material.name.clone(),
+                roughness: material.roughness,
+                metallic: material.metallic,
+                density: material.density,
+            })
+            .collect::<Vec<_>>();
+        materials.sort_by_key(|material| material.region);
+
+        let annotations = &core.layers.annotations;
+        let names = annotations
+            .names
+            .iter()
+            .map(|(name, geometry)| {
+                let (half_edge, surface) = match geometry {
+                    NamedGeometry::Vertex { half_edge, surface }
+                    | NamedGeometry::Edge { half_edge, surface } => {
+                        (half_edges.get(half_edge), surfaces.get(surface))
+                    }
+                };
+                let is_edge = matches!(geometry, NamedGeometry::Edge { .. });
+
+                SerializedName {
+                    name: name.clone(),
+                    half_edge,
+                    surface,
+                    is_edge,
+                }
+            })
+            .collect();
+        let annotations = annotations
+            .annotations
+            .iter()
+            .map(|annotation| annotation.clone().into())
+            .collect();
+
+        let mut origins = core
+            .layers
+            .provenance
+            .origins
+            .iter()
+            .filter_map(|(id, origin)| {
+                Some(SerializedOrigin {
+                    object: *objects.get(id)?,
+                    original: origin
+                        .original
+                        .as_ref()
+                        .map(|original| objects[&original.id()]),
+                    operations: origin
+                        .operations
+                        .iter()
+                        .map(|operation| SerializedOperation {
+                            name: operation.name.clone(),
+                            tag: operation.tag.clone(),
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Vec<_>>();
+        origins.sort_by_key(|origin| origin.object);
+
+        let mut tolerances = core
+            .approx_cache
+            .tolerances
+            .curves()
+            .map(|(curve, tolerance)| SerializedTolerance {
+                curve: curves.get(curve),
+                value: tolerance.inner().into_f64(),
+                is_nested: tolerance.is_nested(),
+            })
+            .collect::<Vec<_>>();
+        tolerances.sort_by_key(|tolerance| tolerance.curve);
+
+        Self {
+            format_version: FORMAT_VERSION,
+
+            num_vertices: vertices.len(),
+            num_curves: curves.len(),
+            num_surfaces: surfaces.len(),
+
+            half_edges: topology
+                .half_edges
+                .iter()
+                .map(|half_edge| SerializedHalfEdge {
+                    curve: curves.get(half_edge.curve()),
+                    start_vertex: vertices.get(half_edge.start_vertex()),
+                })
+                .collect(),
+            cycles: topology
+                .cycles
+                .iter()
+                .map(|cycle| half_edges.get_all(cycle.half_edges()))
+                .collect(),
+            regions: topology
+                .regions
+                .iter()
+                .map(|region| SerializedRegion {
+                    exterior: cycles.get(region.exterior()),
+                    interiors: cycles.get_all(region.interiors()),
+                })
+                .collect(),
+            faces: topology
+                .faces
+                .iter()
+                .map(|face| SerializedFace {
+                    surface: surfaces.get(face.surface()),
+                    region: regions.get(face.region()),
+                })
+                .collect(),
+            shells: topology
+                .shells
+                .iter()
+                .map(|shell| faces.get_all(shell.faces()))
+                .collect(),
+            sketches: topology
+                .sketches
+                .iter()
+                .map(|sketch| SerializedSketch {
+                    surface: surfaces.get(sketch.surface()),
+                    regions: regions.get_all(sketch.regions()),
+                })
+                .collect(),
+            solids: topology
+                .solids
+                .iter()
+                .map(|solid| shells.get_all(solid.shells()))
+                .collect(),
+
+            curve_geometry,
+            half_edge_geometry,
+            surface_geometry,
+
+            colors,
+            materials,
+
+            names,
+            annotations,
+
+            origins,
+
+            tolerances,
+        }
+    }
+
+    /// Load the serialized object graph into a new [`Core`] instance
+    ///
+    /// The loaded objects are validated, as they are inserted into the new
+    /// instance. Returns an error, if any of them are invalid.
+    pub fn to_core(&self) -> Result<Core, SerializationError> {
+        if self.format_version != FORMAT_VERSION {
+            return Err(SerializationError::FormatVersion {
+                expected: FORMAT_VERSION,
+                actual: self.format_version,
+            });
+        }
+
+        let mut core = Core::new();
+        let result = self.load_into(&mut core);
+
+        // The validation errors need to be taken in any case. Otherwise,
+        // dropping the instance would panic, if loading failed.
+        let validation = core.layers.validation.take_errors();
+        result?;
+        validation?;
+
+        Ok(core)
+    }
+
+    fn load_into(&self, core: &mut Core) -> Result<(), SerializationError> {
+        let vertices = (0..self.num_vertices)
+            .map(|_| Vertex::new().insert(core))
+            .collect::<Vec<_>>();
+        let curves = (0..self.num_curves)
+            .map(|_| Curve::new().insert(core))
+            .collect::<Vec<_>>();
+
+        // A new `Core` already contains some special surfaces, which are
+        // always the first ones in the store. Since the serialized `Core` also
+        // contained those, we only need to create the rest.
+        let mut surfaces =
+            core.layers.topology.surfaces.iter().collect::<Vec<_>>();
+        let num_special_surfaces = surfaces.len();
+        for _ in num_special_surfaces..self.num_surfaces {
+            surfaces.push(Surface::new().insert(core));
+        }
+
+        for geom in &self.surface_geometry {
+            if geom.surface < num_special_surfaces {
+                continue;
+            }
+
+            core.layers.geometry.define_surface(
+                get(&surfaces, geom.surface)?,
+                SurfaceGeom {
+                    u: geom.u.into(),
+                    v: Vector::from(geom.v),
+                    profile: geom.profile.into(),
+                },
+            );
+        }
+        for geom in &self.curve_geometry {
+            core.layers.geometry.define_curve(
+                get(&curves, geom.curve)?,
+                get(&surfaces, geom.surface)?,
+                LocalCurveGeom {
+                    path: geom.path.into(),
+                },
+            );
+        }
+
+        let mut half_edges = Vec::new();
+        for half_edge in &self.half_edges {
+            half_edges.push(
+                HalfEdge::new(
+                    get(&curves, half_edge.curve)?,
+                    get(&vertices, half_edge.start_vertex)?,
+                )
+                .insert(core),
+            );
+        }
+        for geom in &self.half_edge_geometry {
+            core.layers.geometry.define_half_edge(
+                get(&half_edges, geom.half_edge)?,
+                HalfEdgeGeom {
+                    path: geom.path.into(),
+                    boundary: CurveBoundary::from(geom.boundary.map(|t| [t])),
+                },
+            );
+        }
+
+        let mut cycles = Vec::new();
+        for cycle in &self.cycles {
+            cycles.push(Cycle::new(get_all(&half_edges, cycle)?).insert(core));
+        }
+
+        let mut regions = Vec::new();
+        for region in &self.regions {
+            regions.push(
+                Region::new(
+                    get(&cycles, region.exterior)?,
+                    get_all(&cycles, &region.interiors)?,
+                )
+                .insert(core),
+            );
+        }
+        for &(region, color) in &self.colors {
+            get(&regions, region)?.set_color(Color(color), core);
+        }
+        for material in &self.materials {
+            get(&regions, material.region)?.set_material(
+                Material {
+                    name: material.name.clone(),
+                    roughness: material.roughness,
+                    metallic: material.metallic,
+                    density: material.density,
+                },
+                core,
+            );
+        }
+
+        let mut faces = Vec::new();
+        for face in &self.faces {
+            faces.push(
+                Face::new(
+                    get(&surfaces, face.surface)?,
+                    get(&regions, face.region)?,
+                )
+                .insert(core),
+            );
+        }
+
+        let mut shells = Vec::new();
+        for shell in &self.shells {
+            shells.push(Shell::new(get_all(&faces, shell)?).insert(core));
+        }
+
+        let mut sketches = Vec::new();
+        for sketch in &self.sketches {
+            sketches.push(
+                Sketch::new(
+                    get(&surfaces, sketch.surface)?,
+                    get_all(&regions, &sketch.regions)?,
+                )
+                .insert(core),
+            );
+        }
+
+        let mut solids = Vec::new();
+        for solid in &self.solids {
+            solids.push(Solid::new(get_all(&shells, solid)?).insert(core));
+        }
+
+        for name in &self.names {
+            let half_edge = get(&half_edges, name.half_edge)?;
+            let surface = get(&surfaces, name.surface)?;
+
+            let annotations = &mut core.layers.annotations;
+            if name.is_edge {
+                annotations.name_edge(&name.name, half_edge, surface)?;
+            } else {
+                annotations.name_vertex(&name.name, half_edge, surface)?;
+            }
+        }
+        for annotation in &self.annotations {
+            core.layers
+                .annotations
+                .annotate(annotation.clone().into(), &core.layers.geometry)?;
+        }
+
+        let any_object = |object: SerializedObject| {
+            let object: AnyObject<Stored> = match object {
+                SerializedObject::Curve(index) => get(&curves, index)?.into(),
+                SerializedObject::Cycle(index) => get(&cycles, index)?.into(),
+                SerializedObject::Face(index) => get(&faces, index)?.into(),
+                SerializedObject::HalfEdge(index) => {
+                    get(&half_edges, index)?.into()
+                }
+                SerializedObject::Region(index) => get(&regions, index)?.into(),
+                SerializedObject::Shell(index) => get(&shells, index)?.into(),
+                SerializedObject::Sketch(index) => {
+                    get(&sketches, index)?.into()
+                }
+                SerializedObject::Solid(index) => get(&solids, index)?.into(),
+                SerializedObject::Surface(index) => {
+                    get(&surfaces, index)?.into()
+                }
+                SerializedObject::Vertex(index) => {
+                    get(&vertices, index)?.into()
+                }
+            };
+
+            Ok::<_, SerializationError>(object)
+        };
+        for origin in &self.origins {
+            let original = origin.original.map(any_object).transpose()?;
+            let operations = origin
+                .operations
+                .iter()
+                .map(|operation| Operation {
+                    name: operation.name.clone(),
+                    tag: operation.tag.clone(),
+                })
+                .collect();
+
+            core.layers.provenance.set_origin(
+                any_object(origin.object)?,
+                Origin {
+                    original,
+                    operations,
+                },
+            );
+        }
+
+        for tolerance in &self.tolerances {
+            let mut value = Tolerance::from_scalar(tolerance.value)?;
+            if tolerance.is_nested {
+                value = value.nested();
+            }
+
+            core.approx_cache
+                .tolerances
+                .set_for_curve(&get(&curves, tolerance.curve)?, value);
+        }
+
+        Ok(())
+    }
+
+    /// Write the serialized object graph to a file
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<(), SerializationError> {
+        let file = io::BufWriter::new(File::create(path)?);
+        serde_json::to_writer(file, self)?;
+        Ok(())
+    }
+
+    /// Read a serialized object graph from a file
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SerializationError> {
+        let file = io::BufReader::new(File::open(path)?);
+        let serialized = serde_json::from_reader(file)?;
+        Ok(serialized)
+    }
+}
+
+/// Error serializing or deserializing an object graph
+#[derive(Debug, thiserror::Error)]
+pub enum SerializationError {
+    /// I/O error while reading or writing a file
+    #[error("I/O error while reading or writing serialized object graph")]
+    Io(#[from] io::Error),
+
+    /// Error encoding or decoding the file format
+    #[error("Error encoding or decoding serialized object graph")]
+    Format(#[from] serde_json::Error),
+
+    /// The serialized object graph has an unsupported format version
+    #[error(
+        "Unsupported format version of serialized object graph (expected \
+        {expected}, got {actual})"
+    )]
+    FormatVersion {
+        /// The format version that is supported
+        expected: u32,
+
+        /// The format version of the serialized object graph
+        actual: u32,
+    },
+
+    /// The serialized object graph refers to an object that doesn't exist
+    #[error("Serialized object graph refers to non-existing object {index}")]
+    InvalidReference {
+        /// The index of the object that doesn't exist
+        index: usize,
+    },
+
+    /// The serialized object graph contains an invalid annotation
+    #[error("Serialized object graph contains invalid annotation")]
+    Annotation(#[from] AnnotationError),
+
+    /// The serialized object graph contains an invalid tolerance
+    #[error("Serialized object graph contains invalid tolerance")]
+    Tolerance(#[from] InvalidTolerance),
+
+    /// The loaded objects are not valid
+    #[error("Serialized object graph contains invalid objects")]
+    Validation(#[from] ValidationErrors),
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedHalfEdge {
+    curve: usize,
+    start_vertex: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedRegion {
+    exterior: usize,
+    interiors: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedFace {
+    surface: usize,
+    region: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedMaterial {
+    region: usize,
+    name: String,
+    roughness: f64,
+    metallic: f64,
+    density: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedName {
+    name: String,
+    half_edge: usize,
+    surface: usize,
+    is_edge: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+enum SerializedAnnotation {
+    Linear {
+        from: String,
+        to: String,
+        offset: f64,
+    },
+    Radial {
+        edge: String,
+    },
+    Note {
+        at: String,
+        text: String,
+    },
+}
+
+impl From<Annotation> for SerializedAnnotation {
+    fn from(annotation: Annotation) -> Self {
+        match annotation {
+            Annotation::Linear { from, to, offset } => Self::Linear {
+                from,
+                to,
+                offset: offset.into_f64(),
+            },
+            Annotation::Radial { edge } => Self::Radial { edge },
+            Annotation::Note { at, text } => Self::Note { at, text },
+        }
+    }
+}
+
+impl From<SerializedAnnotation> for Annotation {
+    fn from(annotation: SerializedAnnotation) -> Self {
+        match annotation {
+            SerializedAnnotation::Linear { from, to, offset } => Self::Linear {
+                from,
+                to,
+                offset: Scalar::from(offset),
+            },
+            SerializedAnnotation::Radial { edge } => Self::Radial { edge },
+            SerializedAnnotation::Note { at, text } => Self::Note { at, text },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedOrigin {
+    object: SerializedObject,
+    original: Option<SerializedObject>,
+    operations: Vec<SerializedOperation>,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+enum SerializedObject {
+    Curve(usize),
+    Cycle(usize),
+    Face(usize),
+    HalfEdge(usize),
+    Region(usize),
+    Shell(usize),
+    Sketch(usize),
+    Solid(usize),
+    Surface(usize),
+    Vertex(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedOperation {
+    name: String,
+    tag: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedTolerance {
+    curve: usize,
+    value: f64,
+    is_nested: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedSketch {
+    surface: usize,
+    regions: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedCurveGeom {
+    curve: usize,
+    surface: usize,
+    path: SerializedSurfacePath,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedHalfEdgeGeom {
+    half_edge: usize,
+    path: SerializedSurfacePath,
+    boundary: [f64; 2],
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SerializedSurfaceGeom {
+    surface: usize,
+    u: SerializedGlobalPath,
+    v: [f64; 3],
+    profile: SerializedSurfaceProfile,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+enum SerializedSurfaceProfile {
+    Linear { taper: f64 },
+    Spherical,
+    Toroidal,
+}
+
+impl From<SurfaceProfile> for SerializedSurfaceProfile {
+    fn from(profile: SurfaceProfile) -> Self {
+        match profile {
+            SurfaceProfile::Linear { taper } => Self::Linear {
+                taper: taper.into_f64(),
+            },
+            SurfaceProfile::Spherical => Self::Spherical,
+            SurfaceProfile::Toroidal => Self::Toroidal,
+        }
+    }
+}
+
+impl From<SerializedSurfaceProfile> for SurfaceProfile {
+    fn from(profile: SerializedSurfaceProfile) -> Self {
+        match profile {
+            SerializedSurfaceProfile::Linear { taper } => Self::Linear {
+                taper: Scalar::from(taper),
+            },
+            SerializedSurfaceProfile::Spherical => Self::Spherical,
+            SerializedSurfaceProfile::Toroidal => Self::Toroidal,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+enum SerializedSurfacePath {
+    Circle {
+        center: [f64; 2],
+        a: [f64; 2],
+        b: [f64; 2],
+    },
+    Line {
+        origin: [f64; 2],
+        direction: [f64; 2],
+    },
+}
+
+impl From<SurfacePath> for SerializedSurfacePath {
+    fn from(path: SurfacePath) -> Self {
+        match path {
+            SurfacePath::Circle(circle) => Self::Circle {
+                center: point_to_array(circle.center()),
+                a: vector_to_array(circle.a()),
+                b: vector_to_array(circle.b()),
+            },
+            SurfacePath::Line(line) => Self::Line {
+                origin: point_to_array(line.origin()),
+                direction: vector_to_array(line.direction()),
+            },
+        }
+    }
+}
+
+impl From<SerializedSurfacePath> for SurfacePath {
+    fn from(path: SerializedSurfacePath) -> Self {
+        match path {
+            SerializedSurfacePath::Circle { center, a, b } => {
+                Self::Circle(Circle::new(center, a, b))
+            }
+            SerializedSurfacePath::Line { origin, direction } => {
+                Self::Line(Line::from_origin_and_direction(
+                    Point::from(origin),
+                    Vector::from(direction),
+                ))
+            }
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+enum SerializedGlobalPath {
+    Circle {
+        center: [f64; 3],
+        a: [f64; 3],
+        b: [f64; 3],
+    },
+    Line {
+        origin: [f64; 3],
+        direction: [f64; 3],
+    },
+}
+
+impl From<GlobalPath> for SerializedGlobalPath {
+    fn from(path: GlobalPath) -> Self {
+        match path {
+            GlobalPath::Circle(circle) => Self::Circle {
+                center: point_to_array(circle.center()),
+                a: vector_to_array(circle.a()),
+                b: vector_to_array(circle.b()),
+            },
+            GlobalPath::Line(line) => Self::Line {
+                origin: point_to_array(line.origin()),
+                direction: vector_to_array(line.direction()),
+            },
+        }
+    }
+}
+
+impl From<SerializedGlobalPath> for GlobalPath {
+    fn from(path: SerializedGlobalPath) -> Self {
+        match path {
+            SerializedGlobalPath::Circle { center, a, b } => {
+                Self::Circle(Circle::new(center, a, b))
+            }
+            SerializedGlobalPath::Line { origin, direction } => {
+                Self::Line(Line::from_origin_and_direction(
+                    Point::from(origin),
+                    Vector::from(direction),
+                ))
+            }
+        }
+    }
+}
+
+/// The serialized form of [`DatumPoint`]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SerializedDatumPoint {
+    position: [f64; 3],
+}
+
+impl From<DatumPoint> for SerializedDatumPoint {
+    fn from(datum: DatumPoint) -> Self {
+        Self {
+            position: point_to_array(datum.position),
+        }
+    }
+}
+
+impl From<SerializedDatumPoint> for DatumPoint {
+    fn from(datum: SerializedDatumPoint) -> Self {
+        Self::from_position(datum.position)
+    }
+}
+
+/// The serialized form of [`DatumAxis`]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SerializedDatumAxis {
+    origin: [f64; 3],
+    direction: [f64; 3],
+}
+
+impl From<DatumAxis> for SerializedDatumAxis {
+    fn from(datum: DatumAxis) -> Self {
+        Self {
+            origin: point_to_array(datum.line.origin()),
+            direction: vector_to_array(datum.line.direction()),
+        }
+    }
+}
+
+impl From<SerializedDatumAxis> for DatumAxis {
+    fn from(datum: SerializedDatumAxis) -> Self {
+        Self {
+            line: Line::from_origin_and_direction(
+                Point::from(datum.origin),
+                Vector::from(datum.direction),
+            ),
+        }
+    }
+}
+
+/// The serialized form of [`DatumPlane`]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SerializedDatumPlane {
+    origin: [f64; 3],
+    u: [f64; 3],
+    v: [f64; 3],
+}
+
+impl From<DatumPlane> for SerializedDatumPlane {
+    fn from(datum: DatumPlane) -> Self {
+        Self {
+            origin: point_to_array(datum.plane.origin()),
+            u: vector_to_array(datum.plane.u()),
+            v: vector_to_array(datum.plane.v()),
+        }
+    }
+}
+
+impl From<SerializedDatumPlane> for DatumPlane {
+    fn from(datum: SerializedDatumPlane) -> Self {
+        Self {
+            plane: Plane::from_parametric(
+                Point::from(datum.origin),
+                Vector::from(datum.u),
+                Vector::from(datum.v),
+            ),
+        }
+    }
+}
+
+/// Maps objects to their position within their store
+struct Indices {
+    inner: BTreeMap<ObjectId, usize>,
+}
+
+impl Indices {
+    fn new<T>(objects: impl Iterator<Item = Handle<T>>) -> Self {
+        let inner = objects
+            .enumerate()
+            .map(|(index, object)| (object.id(), index))
+            .collect();
+
+        Self { inner }
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn get<T>(&self, object: &Handle<T>) -> usize {
+        *self
+            .inner
+            .get(&object.id())
+            .expect("Referenced object must be part of the same store")
+    }
+
+    fn get_all<'r, T: 'r>(
+        &self,
+        objects: impl IntoIterator<Item = &'r Handle<T>>,
+    ) -> Vec<usize> {
+        objects.into_iter().map(|object| self.get(object)).collect()
+    }
+}
+
+fn get<T>(
+    objects: &[Handle<T>],
+    index: usize,
+) -> Result<Handle<T>, SerializationError> {
+    objects
+        .get(index)
+        .cloned()
+        .ok_or(SerializationError::InvalidReference { index })
+}
+
+fn get_all<T>(
+    objects: &[Handle<T>],
+    indices: &[usize],
+) -> Result<Vec<Handle<T>>, SerializationError> {
+    indices.iter().map(|&index| get(objects, index)).collect()
+}
+
+fn point_to_array<const D: usize>(point: Point<D>) -> [f64; D] {
+    vector_to_array(point.coords)
+}
+
+fn vector_to_array<const D: usize>(vector: Vector<D>) -> [f64; D] {
+    vector.components.map(|component| component.into_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{Color, Material};
+    use fj_math::Scalar;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        algorithms::approx::Tolerance,
+        annotations::Annotation,
+        geometry::{DatumAxis, DatumPlane, DatumPoint},
+        operations::{
+            build::BuildSolid,
+            material::{GetMaterial, SetMaterial},
+            presentation::{GetColor, SetColor},
+        },
+        provenance::Operation,
+        queries::AllHalfEdgesWithSurface,
+        topology::Solid,
+        Core,
+    };
+
+    use super::{SerializationError, SerializedCore};
+
+    #[test]
+    fn serialize_and_deserialize() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let washer = core.record_operation(
+            Operation::new("washer").with_tag("washer"),
+            |core| Solid::washer(2., 1., 0.5, core),
+        );
+        Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+
+        let region = core.layers.topology.regions.iter().next().unwrap();
+        region.set_color([255, 0, 0, 255], &mut core);
+        let material = Material::new("steel")
+            .with_surface(0.4, 1.)
+            .with_density(7.85e-6);
+        region.set_material(material.clone(), &mut core);
+
+        let (half_edge, surface) = washer
+            .shells()
+            .only()
+            .all_half_edges_with_surface()
+            .next()
+            .unwrap();
+        core.layers.annotations.name_vertex(
+            "vertex",
+            half_edge.clone(),
+            surface.clone(),
+        )?;
+        core.layers.annotations.name_edge(
+            "edge",
+            half_edge.clone(),
+            surface,
+        )?;
+        core.layers.annotations.annotate(
+            Annotation::Note {
+                at: "edge".into(),
+                text: "note".into(),
+            },
+            &core.layers.geometry,
+        )?;
+
+        let face = washer.shells().only().faces().first().clone();
+        core.approx_cache
+            .tolerances
+            .set_for_face(&face, Tolerance::from_scalar(0.01)?.nested());
+
+        let serialized = SerializedCore::from_core(&core);
+        let loaded = serialized.to_core()?;
+
+        // Serializing the loaded instance again must result in the same data,
+        // which means all objects, their relations, and their geometry have
+        // been preserved.
+        assert_eq!(SerializedCore::from_core(&loaded), serialized);
+
+        let region = loaded.layers.topology.regions.iter().next().unwrap();
+        assert_eq!(region.get_color(&loaded), Some(Color([255, 0, 0, 255])));
+        assert_eq!(region.get_material(&loaded), Some(material));
+
+        assert_eq!(
+            loaded.layers.annotations.annotations,
+            core.layers.annotations.annotations,
+        );
+        assert_eq!(loaded.layers.annotations.names.len(), 2);
+
+        let shell = loaded.layers.topology.shells.iter().next().unwrap();
+        assert!(loaded.layers.provenance.is_from_tag(shell.id(), "washer"));
+
+        let curve = loaded
+            .layers
+            .topology
+            .faces
+            .iter()
+            .find_map(|face| {
+                let tolerance = Tolerance::from_scalar(1.).ok()?;
+                face.region()
+                    .exterior()
+                    .half_edges()
+                    .iter()
+                    .find(|half_edge| {
+                        loaded
+                            .approx_cache
+                            .tolerances
+                            .for_curve(half_edge.curve(), tolerance)
+                            != tolerance
+                    })
+                    .map(|half_edge| half_edge.curve().clone())
+            })
+            .unwrap();
+        let tolerance = loaded
+            .approx_cache
+            .tolerances
+            .for_curve(&curve, Tolerance::from_scalar(1.)?);
+        assert_eq!(tolerance.inner(), Scalar::from(0.01));
+        assert!(tolerance.is_nested());
+
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_and_deserialize_datums() -> anyhow::Result<()> {
+        let point = DatumPoint::from_position([1., 2., 3.]);
+        let axis = DatumAxis::through_points([
+            point,
+            DatumPoint::from_position([3., 2., 1.]),
+        ]);
+        let plane = DatumPlane::through_points([
+            point,
+            DatumPoint::from_position([2., 2., 3.]),
+            DatumPoint::from_position([1., 3., 3.]),
+        ]);
+
+        let datums = (point, axis, plane);
+        let json = serde_json::to_string(&datums)?;
+        let loaded: (DatumPoint, DatumAxis, DatumPlane) =
+            serde_json::from_str(&json)?;
+        assert_eq!(loaded, datums);
+
+        Ok(())
+    }
+
+    #[test]
+    fn load_invalid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+
+        // Collapse all half-edges to a single point, as a corrupted file might.
+        let mut serialized = SerializedCore::from_core(&core);
+        for geom in &mut serialized.half_edge_geometry {
+            geom.boundary = [0., 0.];
+        }
+
+        let path = std::env::temp_dir().join(format!(
+            "fj-core-serialization-test-invalid-{}.json",
+            std::process::id()
+        ));
+        serialized.save(&path)?;
+        let loaded = SerializedCore::load(&path)?;
+        std::fs::remove_file(&path)?;
+
+        assert!(matches!(
+            loaded.to_core(),
+            Err(SerializationError::Validation(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_and_load() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        Solid::rounded_box([3., 2., 1.], 0.25, &mut core);
+
+        let path = std::env::temp_dir().join(format!(
+            "fj-core-serialization-test-{}.json",
+            std::process::id()
+        ));
+
+        let serialized = SerializedCore::from_core(&core);
+        serialized.save(&path)?;
+        let loaded = SerializedCore::load(&path)?;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(loaded, serialized);
+        Ok(())
+    }
+}
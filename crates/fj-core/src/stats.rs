@@ -0,0 +1,278 @@
+//! # Statistics about the objects in a core instance
+//!
+//! Useful for tracking how complex a model is, and how much memory it takes
+//! up, for example to enforce a complexity budget in CI. See [`Core::stats`].
+
+use std::ops;
+
+use fj_math::Scalar;
+
+use crate::{
+    algorithms::approx::{Approx, Tolerance},
+    geometry::{GlobalPath, SurfaceGeom, SurfaceProfile},
+    topology::Topology,
+    Core,
+};
+
+/// Statistics about the objects in a core instance
+///
+/// Returned by [`Core::stats`]. The statistics cover all objects in the stores,
+/// including those that are no longer referenced by any shape. Objects that
+/// are only available through a fork are not included. See [`Core::fork`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// The number of objects, per type of object
+    pub objects: ObjectCounts,
+
+    /// The number of surfaces, per type of surface
+    pub surfaces: SurfaceCounts,
+
+    /// The estimated number of triangles in a triangulation of all faces
+    ///
+    /// This is the number of triangles that a triangulation of the points on
+    /// the boundaries of the faces results in. Triangulations of curved faces
+    /// can contain additional points, and thus more triangles.
+    pub estimated_triangles: usize,
+
+    /// The memory that the stores have allocated for objects, in bytes
+    ///
+    /// Forks share their memory with the original, so this includes the
+    /// memory of their objects. See [`Store::memory_usage`].
+    ///
+    /// [`Store::memory_usage`]: crate::storage::Store::memory_usage
+    pub store_memory: usize,
+}
+
+impl Core {
+    /// Compute statistics about the objects in this instance
+    ///
+    /// The triangle estimate is computed at the provided tolerance. This
+    /// requires approximating all faces, so it can take a while for big
+    /// models.
+    pub fn stats(&self, tolerance: impl Into<Tolerance>) -> Stats {
+        let tolerance = tolerance.into();
+        let topology = &self.layers.topology;
+        let geometry = &self.layers.geometry;
+
+        let mut surfaces = SurfaceCounts::default();
+        for (_, surface) in geometry.surfaces() {
+            surfaces.count(surface);
+        }
+
+        let estimated_triangles = topology
+            .faces
+            .iter()
+            .map(|face| {
+                let approx = face.approx(tolerance, geometry);
+
+                // A polygon with `n` points and `h` holes is triangulated into
+                // `n + 2h - 2` triangles, if no points are added.
+                let points = approx.points().len();
+                let holes = approx.interiors.len();
+                (points + 2 * holes).saturating_sub(2)
+            })
+            .sum();
+
+        Stats {
+            objects: ObjectCounts::new(topology),
+            surfaces,
+            estimated_triangles,
+            store_memory: store_memory(topology),
+        }
+    }
+}
+
+/// The number of objects, per type of object
+///
+/// See [`Stats`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ObjectCounts {
+    /// The number of curves
+    ///
+    /// Every edge has its own curve, which is shared by its half-edges. So
+    /// this is also an upper bound for the number of edges.
+    pub curves: usize,
+
+    /// The number of cycles
+    pub cycles: usize,
+
+    /// The number of faces
+    pub faces: usize,
+
+    /// The number of half-edges
+    pub half_edges: usize,
+
+    /// The number of regions
+    pub regions: usize,
+
+    /// The number of shells
+    pub shells: usize,
+
+    /// The number of sketches
+    pub sketches: usize,
+
+    /// The number of solids
+    pub solids: usize,
+
+    /// The number of surfaces
+    pub surfaces: usize,
+
+    /// The number of vertices
+    pub vertices: usize,
+}
+
+impl ObjectCounts {
+    /// Count the objects in the provided stores
+    pub fn new(topology: &Topology) -> Self {
+        Self {
+            curves: topology.curves.iter().count(),
+            cycles: topology.cycles.iter().count(),
+            faces: topology.faces.iter().count(),
+            half_edges: topology.half_edges.iter().count(),
+            regions: topology.regions.iter().count(),
+            shells: topology.shells.iter().count(),
+            sketches: topology.sketches.iter().count(),
+            solids: topology.solids.iter().count(),
+            surfaces: topology.surfaces.iter().count(),
+            vertices: topology.vertices.iter().count(),
+        }
+    }
+
+    /// Count the objects in the provided stores, and the stores they share
+    ///
+    /// Unlike [`ObjectCounts::new`], this includes the objects that are only
+    /// available through a fork. See [`Store::num_objects`].
+    ///
+    /// [`Store::num_objects`]: crate::storage::Store::num_objects
+    pub(crate) fn including_shared(topology: &Topology) -> Self {
+        Self {
+            curves: topology.curves.num_objects(),
+            cycles: topology.cycles.num_objects(),
+            faces: topology.faces.num_objects(),
+            half_edges: topology.half_edges.num_objects(),
+            regions: topology.regions.num_objects(),
+            shells: topology.shells.num_objects(),
+            sketches: topology.sketches.num_objects(),
+            solids: topology.solids.num_objects(),
+            surfaces: topology.surfaces.num_objects(),
+            vertices: topology.vertices.num_objects(),
+        }
+    }
+
+    /// Compute the total number of objects
+    pub fn total(&self) -> usize {
+        self.curves
+            + self.cycles
+            + self.faces
+            + self.half_edges
+            + self.regions
+            + self.shells
+            + self.sketches
+            + self.solids
+            + self.surfaces
+            + self.vertices
+    }
+}
+
+impl ops::Sub for ObjectCounts {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            curves: self.curves - other.curves,
+            cycles: self.cycles - other.cycles,
+            faces: self.faces - other.faces,
+            half_edges: self.half_edges - other.half_edges,
+            regions: self.regions - other.regions,
+            shells: self.shells - other.shells,
+            sketches: self.sketches - other.sketches,
+            solids: self.solids - other.solids,
+            surfaces: self.surfaces - other.surfaces,
+            vertices: self.vertices - other.vertices,
+        }
+    }
+}
+
+/// The number of surfaces, per type of surface
+///
+/// Only surfaces whose geometry is defined are counted. See [`Stats`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SurfaceCounts {
+    /// The number of planes
+    pub planes: usize,
+
+    /// The number of cylinders
+    pub cylinders: usize,
+
+    /// The number of cones
+    pub cones: usize,
+
+    /// The number of spheres
+    pub spheres: usize,
+
+    /// The number of tori
+    pub tori: usize,
+}
+
+impl SurfaceCounts {
+    fn count(&mut self, surface: &SurfaceGeom) {
+        let count = match (surface.u, surface.profile) {
+            (GlobalPath::Line(_), _) => &mut self.planes,
+            (GlobalPath::Circle(_), SurfaceProfile::Spherical) => {
+                &mut self.spheres
+            }
+            (GlobalPath::Circle(_), SurfaceProfile::Toroidal) => &mut self.tori,
+            (GlobalPath::Circle(_), SurfaceProfile::Linear { taper }) => {
+                if taper == Scalar::ZERO {
+                    &mut self.cylinders
+                } else {
+                    &mut self.cones
+                }
+            }
+        };
+
+        *count += 1;
+    }
+}
+
+pub(crate) fn store_memory(topology: &Topology) -> usize {
+    topology.curves.memory_usage()
+        + topology.cycles.memory_usage()
+        + topology.faces.memory_usage()
+        + topology.half_edges.memory_usage()
+        + topology.regions.memory_usage()
+        + topology.shells.memory_usage()
+        + topology.sketches.memory_usage()
+        + topology.solids.memory_usage()
+        + topology.surfaces.memory_usage()
+        + topology.vertices.memory_usage()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::approx::Tolerance, operations::build::BuildSolid,
+        topology::Solid, Core,
+    };
+
+    #[test]
+    fn tetrahedron() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let before = core.stats(Tolerance::from_scalar(0.1)?);
+
+        let _ = Solid::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let stats = core.stats(Tolerance::from_scalar(0.1)?);
+
+        assert_eq!(stats.objects.shells, 1);
+        assert_eq!(stats.objects.faces, 4);
+        assert_eq!(stats.surfaces.planes - before.surfaces.planes, 4);
+        assert_eq!(stats.estimated_triangles, 4);
+        assert!(stats.store_memory > before.store_memory);
+
+        Ok(())
+    }
+}
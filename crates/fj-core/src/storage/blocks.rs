@@ -1,9 +1,21 @@
-use std::iter;
+use std::{
+    iter,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+use super::ObjectId;
+
+/// The ID of the next instance of `Blocks` that is created
+///
+/// Object IDs are made up of this ID and the position of the object within
+/// its blocks. This makes them unique across all stores.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug)]
 pub struct Blocks<T> {
     inner: Vec<Block<T>>,
     block_size: usize,
+    id: u64,
 }
 
 impl<T> Blocks<T> {
@@ -11,31 +23,33 @@ impl<T> Blocks<T> {
         Self {
             inner: Vec::new(),
             block_size,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
         }
     }
 
-    pub fn reserve(&mut self) -> (Index, *const Option<T>) {
+    /// Reserve a slot, on behalf of the provided view
+    pub fn reserve(&mut self, view: ViewId) -> (Index, Slot<T>) {
         let mut current_block = match self.inner.pop() {
             Some(block) => block,
-            None => Block::new(self.block_size),
+            None => self.new_block(),
         };
 
         let ret = loop {
-            match current_block.reserve() {
-                Ok((object_index, ptr)) => {
+            match current_block.reserve(view) {
+                Ok((object_index, slot)) => {
                     let block_index = BlockIndex(self.inner.len());
                     break (
                         Index {
                             block_index,
                             object_index,
                         },
-                        ptr,
+                        slot,
                     );
                 }
                 Err(()) => {
                     // Block is full. Need to create a new one and retry.
                     self.inner.push(current_block);
-                    current_block = Block::new(self.block_size);
+                    current_block = self.new_block();
                 }
             }
         };
@@ -50,45 +64,100 @@ impl<T> Blocks<T> {
         block.insert(index.object_index, object);
     }
 
-    pub fn get_and_inc(&self, index: &mut Index) -> Option<&Option<T>> {
+    pub fn collect(
+        &mut self,
+        mut keep: impl FnMut(ObjectId) -> bool,
+        garbage: &mut Vec<T>,
+    ) {
+        for block in &mut self.inner {
+            block.collect(&mut keep, garbage);
+        }
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.inner.iter().map(Block::memory_usage).sum()
+    }
+
+    pub fn num_objects(&self) -> usize {
+        self.inner.iter().map(Block::num_objects).sum()
+    }
+
+    /// Access the slot at the index, and the view it was reserved by
+    ///
+    /// Returns `None`, if the index is past the last block, and `Some(None)`,
+    /// if the slot doesn't contain an object.
+    pub fn get_and_inc(
+        &self,
+        index: &mut Index,
+    ) -> Option<Option<(Slot<T>, ViewId)>> {
         let block = self.inner.get(index.block_index.0)?;
-        let object = block.get(index.object_index);
+        let slot = block.get(index.object_index);
 
         index.inc(block);
 
-        Some(object)
+        Some(slot)
+    }
+
+    /// Compute the ID that the next reserved slot is going to have
+    pub fn next_id(&self) -> ObjectId {
+        match self.inner.last() {
+            Some(block) => ObjectId(block.first_id + block.next.0 as u64),
+            None => ObjectId(self.id << 32),
+        }
+    }
+
+    fn new_block(&self) -> Block<T> {
+        // Object IDs have 32 bits for the position of the object within its
+        // blocks. That should be plenty.
+        let first_id =
+            (self.id << 32) + (self.inner.len() * self.block_size) as u64;
+        Block::new(self.block_size, first_id)
     }
 }
 
 #[derive(Debug)]
 pub struct Block<T> {
     objects: Box<[Option<T>]>,
+    handles: Box<[AtomicUsize]>,
+    views: Box<[ViewId]>,
     next: ObjectIndex,
+    first_id: u64,
 }
 
 impl<T> Block<T> {
-    pub fn new(size: usize) -> Self {
-        let vec = iter::repeat_with(|| None)
-            .take(size)
-            .collect::<Vec<Option<T>>>();
-        let objects = vec.into_boxed_slice();
+    pub fn new(size: usize, first_id: u64) -> Self {
+        let objects = iter::repeat_with(|| None).take(size).collect();
+        let handles =
+            iter::repeat_with(AtomicUsize::default).take(size).collect();
+        let views = iter::repeat(ViewId(0)).take(size).collect();
 
         Self {
             objects,
+            handles,
+            views,
             next: ObjectIndex(0),
+            first_id,
         }
     }
 
-    pub fn reserve(&mut self) -> Result<(ObjectIndex, *const Option<T>), ()> {
+    pub fn reserve(
+        &mut self,
+        view: ViewId,
+    ) -> Result<(ObjectIndex, Slot<T>), ()> {
         if self.next.0 >= self.objects.len() {
             return Err(());
         }
 
         let index = self.next;
-        let ptr = &mut self.objects[self.next.0];
+        self.views[index.0] = view;
+        let slot = Slot {
+            object: &self.objects[self.next.0],
+            handles: &self.handles[self.next.0],
+            id: self.id(index),
+        };
         self.next.0 += 1;
 
-        Ok((index, ptr))
+        Ok((index, slot))
     }
 
     pub fn insert(&mut self, index: ObjectIndex, object: T) {
@@ -99,13 +168,92 @@ impl<T> Block<T> {
         *slot = Some(object);
     }
 
-    pub fn get(&self, index: ObjectIndex) -> &Option<T> {
-        &self.objects[index.0]
+    /// Remove all objects that have no handles and are not to be kept
+    ///
+    /// If no objects remain afterwards, and no slots are reserved, the memory
+    /// of the block is released. No handle can reference the block then, and
+    /// none can be created, as the block is also empty for iterators.
+    pub fn collect(
+        &mut self,
+        keep: &mut impl FnMut(ObjectId) -> bool,
+        garbage: &mut Vec<T>,
+    ) {
+        for index in 0..self.objects.len() {
+            // This pairs with the `Release` ordering in `Handle`'s `Drop`
+            // implementation. Any use of the object through a handle happens
+            // before that handle is dropped.
+            let unreferenced = self.handles[index].load(Ordering::Acquire) == 0;
+
+            let id = self.id(ObjectIndex(index));
+            if unreferenced && self.objects[index].is_some() && !keep(id) {
+                garbage.extend(self.objects[index].take());
+            }
+        }
+
+        let released = self.objects.is_empty();
+        let unused = self.objects.iter().all(Option::is_none)
+            && self
+                .handles
+                .iter()
+                .all(|handles| handles.load(Ordering::Acquire) == 0);
+
+        if unused && !released {
+            self.objects = Box::new([]);
+            self.handles = Box::new([]);
+            self.views = Box::new([]);
+        }
+    }
+
+    pub fn get(&self, index: ObjectIndex) -> Option<(Slot<T>, ViewId)> {
+        // Slots of a released block are never referenced again, so it is
+        // fine to skip them.
+        let object = self.objects.get(index.0)?;
+        object.as_ref()?;
+
+        let slot = Slot {
+            object,
+            handles: &self.handles[index.0],
+            id: self.id(index),
+        };
+        Some((slot, self.views[index.0]))
     }
 
     pub fn len(&self) -> usize {
         self.next.0
     }
+
+    pub fn id(&self, index: ObjectIndex) -> ObjectId {
+        ObjectId(self.first_id + index.0 as u64)
+    }
+
+    pub fn num_objects(&self) -> usize {
+        self.objects
+            .iter()
+            .filter(|object| object.is_some())
+            .count()
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.objects.len()
+            * (std::mem::size_of::<Option<T>>()
+                + std::mem::size_of::<AtomicUsize>()
+                + std::mem::size_of::<ViewId>())
+    }
+}
+
+/// Identifies one of the instances that share a store
+///
+/// Each slot records the view it was reserved by. See [`Store::share`].
+///
+/// [`Store::share`]: super::Store::share
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ViewId(pub u64);
+
+/// A slot in a block, which an object can be stored in
+pub struct Slot<T> {
+    pub object: *const Option<T>,
+    pub handles: *const AtomicUsize,
+    pub id: ObjectId,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1,6 +1,16 @@
-use std::{any::type_name, borrow::Borrow, fmt, hash::Hash, ops::Deref};
+use std::{
+    any::type_name,
+    borrow::Borrow,
+    fmt,
+    hash::Hash,
+    ops::Deref,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use super::{blocks::Index, store::StoreInner};
+use super::{
+    blocks::{Index, Slot},
+    store::StoreInner,
+};
 
 /// # A handle that references a stored object
 ///
@@ -46,16 +56,54 @@ use super::{blocks::Index, store::StoreInner};
 /// objects and expects them to be the same, must do that comparison based on
 /// identity, not equality. That way, this problem can never happen, because we
 /// never expect non-identical objects to be equal.
+///
+/// ## Lifetime of Objects
+///
+/// The store keeps track of how many handles reference each object. Objects
+/// that are not referenced by any handle can be removed by
+/// [`Core::collect_garbage`]. As long as a handle exists, the object it
+/// references stays in place.
+///
+/// [`Core::collect_garbage`]: crate::Core::collect_garbage
 pub struct Handle<T> {
     pub(super) store: StoreInner<T>,
     pub(super) index: Index,
     pub(super) ptr: *const Option<T>,
+    handles: *const AtomicUsize,
+    id: ObjectId,
 }
 
 impl<T> Handle<T> {
+    pub(super) fn new(
+        store: StoreInner<T>,
+        index: Index,
+        slot: Slot<T>,
+    ) -> Self {
+        // The slot's handle count lives as long as the slot it belongs to, and
+        // the store only releases slots that no handle references. See the
+        // `Deref` implementation for details.
+        let handles = unsafe { &*slot.handles };
+        handles.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            store,
+            index,
+            ptr: slot.object,
+            handles: slot.handles,
+            id: slot.id,
+        }
+    }
+
     /// Access the object's unique id
     pub fn id(&self) -> ObjectId {
-        ObjectId::from_ptr(self.ptr)
+        self.id
+    }
+
+    /// Count the handles that reference the object, including this one
+    pub(crate) fn num_handles(&self) -> usize {
+        // See `Handle::new` for why this is sound.
+        let handles = unsafe { &*self.handles };
+        handles.load(Ordering::Acquire)
     }
 
     /// Return a bare object, which is a clone of the referenced stored object
@@ -80,7 +128,10 @@ impl<T> Deref for Handle<T> {
         //
         // 1. That no `Handle` is ever created, until the object it references
         //    has at least been reserved.
-        // 2. That the memory objects live in is never deallocated.
+        // 2. That an object is never removed, and the memory it lives in never
+        //    deallocated, while a `Handle` references it. Every `Handle`
+        //    counts itself in the slot of its object, and the store only
+        //    collects objects whose slot has a count of zero.
         //
         // That means that as long as a `Handle` exists, the object it
         // references has at least been reserved, and has not been deallocated.
@@ -115,11 +166,23 @@ impl<T> Borrow<T> for Handle<T> {
 
 impl<T> Clone for Handle<T> {
     fn clone(&self) -> Self {
-        Self {
-            store: self.store.clone(),
-            index: self.index,
-            ptr: self.ptr,
-        }
+        Self::new(
+            self.store.clone(),
+            self.index,
+            Slot {
+                object: self.ptr,
+                handles: self.handles,
+                id: self.id,
+            },
+        )
+    }
+}
+
+impl<T> Drop for Handle<T> {
+    fn drop(&mut self) {
+        // See `Handle::new` for why this is sound.
+        let handles = unsafe { &*self.handles };
+        handles.fetch_sub(1, Ordering::Release);
     }
 }
 
@@ -181,18 +244,26 @@ unsafe impl<T> Sync for Handle<T> {}
 ///
 /// You can access a stored object's ID via [`Handle::id`]. Please refer to the
 /// documentation of [`Handle`] for an explanation of object identity.
+///
+/// IDs are derived from the store an object lives in, and its position within
+/// that store, not from its address in memory. Given the same sequence of
+/// operations, objects end up with the same IDs, and IDs are ordered by when
+/// objects were reserved within each store. An ID is never reused, not even
+/// after its object has been removed by [`Core::collect_garbage`].
+///
+/// [`Core::collect_garbage`]: crate::Core::collect_garbage
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct ObjectId(pub(crate) u64);
 
-impl ObjectId {
-    pub(crate) fn from_ptr<T>(ptr: *const T) -> ObjectId {
-        Self(ptr as u64)
-    }
-}
-
 impl fmt::Debug for ObjectId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let id = self.0;
         write!(f, "object id {id:#x}")
     }
 }
+
+impl From<ObjectId> for fj_interop::FaceId {
+    fn from(id: ObjectId) -> Self {
+        Self(id.0)
+    }
+}
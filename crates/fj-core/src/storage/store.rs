@@ -26,14 +26,15 @@ use std::{marker::PhantomData, sync::Arc};
 use parking_lot::RwLock;
 
 use super::{
-    blocks::{Blocks, Index},
-    Handle,
+    blocks::{Blocks, Index, ViewId},
+    Handle, ObjectId,
 };
 
 /// Append-only object storage
 #[derive(Debug)]
 pub struct Store<T> {
     inner: StoreInner<T>,
+    view: Arc<View>,
 }
 
 impl<T> Store<T> {
@@ -49,9 +50,38 @@ impl<T> Store<T> {
     pub fn with_block_size(block_size: usize) -> Self {
         let inner = Arc::new(RwLock::new(StoreInnerInner {
             blocks: Blocks::new(block_size),
+            next_view: 1,
         }));
+        let view = Arc::new(View {
+            id: ViewId(0),
+            parent: None,
+        });
 
-        Self { inner }
+        Self { inner, view }
+    }
+
+    /// Create another instance of `Store` that shares this one's objects
+    ///
+    /// Both instances share the same memory, and the objects that existed
+    /// when this method was called are available through both of them.
+    /// Objects that are inserted later are only available through the
+    /// instance they were inserted through. They don't show up when iterating
+    /// over the other instance. Handles to them stay valid regardless.
+    pub fn share(&self) -> Self {
+        let mut inner = self.inner.write();
+
+        let id = ViewId(inner.next_view);
+        inner.next_view += 1;
+
+        let view = Arc::new(View {
+            id,
+            parent: Some((self.view.clone(), inner.blocks.next_id())),
+        });
+
+        Self {
+            inner: self.inner.clone(),
+            view,
+        }
     }
 
     /// Reserve a slot for an object in the store
@@ -67,13 +97,9 @@ impl<T> Store<T> {
     pub fn reserve(&self) -> Handle<T> {
         let mut inner = self.inner.write();
 
-        let (index, ptr) = inner.blocks.reserve();
+        let (index, slot) = inner.blocks.reserve(self.view.id);
 
-        Handle {
-            store: self.inner.clone(),
-            index,
-            ptr,
-        }
+        Handle::new(self.inner.clone(), index, slot)
     }
 
     /// Insert an object into the store
@@ -88,10 +114,50 @@ impl<T> Store<T> {
         inner.blocks.insert(handle.index, object);
     }
 
+    /// Remove all objects that no handle references
+    ///
+    /// Objects for which `keep` returns `true` stay in the store. Returns the
+    /// number of removed objects.
+    ///
+    /// Removed objects are dropped, which drops the handles they contain, so
+    /// objects they reference may become unreferenced in turn. Blocks that no
+    /// longer contain any objects are deallocated.
+    pub fn collect(&mut self, keep: impl Fn(ObjectId) -> bool) -> usize {
+        let mut garbage = Vec::new();
+
+        self.inner.write().blocks.collect(keep, &mut garbage);
+
+        // The lock has been released at this point, so dropping the objects
+        // (and the handles they contain) can't interfere with it.
+        garbage.len()
+    }
+
+    /// Compute the memory that the store has allocated for objects, in bytes
+    ///
+    /// Memory is allocated in blocks, so this includes slots that are not
+    /// used yet. Memory that objects allocate themselves, like the list of
+    /// half-edges of a cycle, is not included.
+    pub fn memory_usage(&self) -> usize {
+        self.inner.read().blocks.memory_usage()
+    }
+
+    /// Count the objects in the store
+    ///
+    /// Like [`Store::memory_usage`], this includes the objects of all
+    /// instances that share this one's memory, unlike [`Store::iter`]. See
+    /// [`Store::share`].
+    pub fn num_objects(&self) -> usize {
+        self.inner.read().blocks.num_objects()
+    }
+
     /// Iterate over all objects in this store
+    ///
+    /// Objects that are only available through another instance that shares
+    /// this one's objects are skipped. See [`Store::share`].
     pub fn iter(&self) -> Iter<T> {
         Iter {
             store: self.inner.clone(),
+            view: self.view.clone(),
             next_index: Index::zero(),
             _a: PhantomData,
         }
@@ -116,6 +182,7 @@ impl<'a, T> IntoIterator for &'a Store<T> {
 /// An iterator over objects in a [`Store`]
 pub struct Iter<'a, T> {
     store: StoreInner<T>,
+    view: Arc<View>,
     next_index: Index,
     _a: PhantomData<&'a ()>,
 }
@@ -128,18 +195,19 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
 
         loop {
             let index = self.next_index;
-            let ptr = inner.blocks.get_and_inc(&mut self.next_index)?;
-
-            if ptr.is_none() {
+            let Some((slot, view)) =
+                inner.blocks.get_and_inc(&mut self.next_index)?
+            else {
                 // This is a reserved slot.
                 continue;
+            };
+
+            if !self.view.contains(view, slot.id) {
+                // This object was inserted through another instance.
+                continue;
             }
 
-            return Some(Handle {
-                store: self.store.clone(),
-                index,
-                ptr,
-            });
+            return Some(Handle::new(self.store.clone(), index, slot));
         }
     }
 }
@@ -149,6 +217,35 @@ pub type StoreInner<T> = Arc<RwLock<StoreInnerInner<T>>>;
 #[derive(Debug)]
 pub struct StoreInnerInner<T> {
     blocks: Blocks<T>,
+    next_view: u64,
+}
+
+/// The objects that are available through an instance of [`Store`]
+#[derive(Debug)]
+struct View {
+    id: ViewId,
+
+    /// The view this one was shared from, and the first object ID after that
+    parent: Option<(Arc<View>, ObjectId)>,
+}
+
+impl View {
+    /// Determine whether an object is available through this view
+    ///
+    /// Objects are available, if they were inserted through this view, or if
+    /// they were available through the parent view before the sharing.
+    fn contains(&self, view: ViewId, id: ObjectId) -> bool {
+        if view == self.id {
+            return true;
+        }
+
+        match &self.parent {
+            Some((parent, next_id)) => {
+                id < *next_id && parent.contains(view, id)
+            }
+            None => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -181,4 +278,65 @@ mod tests {
         let objects = store.iter().collect::<Vec<_>>();
         assert_eq!(objects, [a, b]);
     }
+
+    #[test]
+    fn collect() {
+        let mut store = Store::with_block_size(1);
+
+        let a: Handle<i32> = store.reserve();
+        let b = store.reserve();
+        store.insert(a.clone(), 0);
+        store.insert(b.clone(), 1);
+        let memory_usage = store.memory_usage();
+
+        // Objects that are referenced by a handle are never removed.
+        assert_eq!(store.collect(|_| false), 0);
+
+        drop(b);
+        assert_eq!(store.collect(|_| false), 1);
+        assert!(store.memory_usage() < memory_usage);
+
+        let objects = store.iter().collect::<Vec<_>>();
+        assert_eq!(objects, [a]);
+    }
+
+    #[test]
+    fn share() {
+        let mut store = Store::with_block_size(1);
+
+        let a: Handle<i32> = store.reserve();
+        store.insert(a.clone(), 0);
+
+        let mut shared = store.share();
+        let b = shared.reserve();
+        shared.insert(b.clone(), 1);
+        let c = store.reserve();
+        store.insert(c.clone(), 2);
+
+        // Objects that exist before sharing are available through both
+        // instances. Objects inserted later, only through the one they were
+        // inserted through.
+        assert_eq!(store.iter().collect::<Vec<_>>(), [a.clone(), c]);
+        assert_eq!(shared.iter().collect::<Vec<_>>(), [a, b.clone()]);
+        assert_eq!(*b, 1);
+    }
+
+    #[test]
+    fn ids() {
+        let mut store = Store::with_block_size(1);
+
+        let a: Handle<i32> = store.reserve();
+        let b = store.reserve();
+        store.insert(a.clone(), 0);
+        store.insert(b.clone(), 1);
+        assert!(a.id() < b.id());
+
+        // IDs of removed objects are not reused.
+        let b_id = b.id();
+        drop(b);
+        store.collect(|_| false);
+
+        let c: Handle<i32> = store.reserve();
+        assert!(c.id() > b_id);
+    }
 }
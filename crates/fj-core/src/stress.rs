@@ -0,0 +1,261 @@
+//! # Stress models for detecting performance regressions
+//!
+//! This module provides a suite of models that are expensive to build and
+//! triangulate, along with budgets for how long that is allowed to take, and
+//! how many objects it is allowed to create. Kernel contributors and downstream
+//! packagers can use [`run_suite`] to check, whether a change or a build
+//! environment makes any of those models exceed its budget.
+//!
+//! The default budgets are generous, to account for unoptimized builds and slow
+//! machines. They are meant to catch substantial regressions. Callers with
+//! stricter requirements can adjust the budgets before running the models.
+//! Custom models can be checked the same way, by creating a [`StressModel`]
+//! and calling [`StressModel::run`].
+//!
+//! Not available on WebAssembly, where time can't be measured.
+
+use std::time::{Duration, Instant};
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{approx::Tolerance, triangulate::Triangulate},
+    operations::{
+        build::{BuildCycle, BuildRegion, BuildSketch, BuildSolid},
+        reverse::Reverse,
+        sweep::SweepSketch,
+        update::{UpdateRegion, UpdateSketch},
+    },
+    stats::ObjectCounts,
+    topology::{Cycle, Region, Sketch, Solid},
+    Core,
+};
+
+/// A model that is used to detect performance regressions
+///
+/// See [module documentation](self) for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct StressModel {
+    /// The name of the model
+    pub name: &'static str,
+
+    /// The tolerance that the model is triangulated with
+    pub tolerance: f64,
+
+    /// The budget that the model must stay within
+    pub budget: StressBudget,
+
+    /// Build the model
+    ///
+    /// The time this takes counts against the budget, as does triangulating
+    /// the solid it returns.
+    pub build: fn(&mut Core) -> Solid,
+}
+
+impl StressModel {
+    /// Build and triangulate the model, measuring it against its budget
+    pub fn run(&self) -> StressReport {
+        let mut core = Core::new();
+
+        let start = Instant::now();
+
+        let solid = (self.build)(&mut core);
+        let mesh =
+            (&solid, Tolerance::from(self.tolerance)).triangulate(&mut core);
+
+        let duration = start.elapsed();
+
+        StressReport {
+            name: self.name,
+            budget: self.budget,
+            duration,
+            objects: ObjectCounts::new(&core.layers.topology),
+            num_triangles: mesh.num_triangles(),
+        }
+    }
+}
+
+/// The budget for a [`StressModel`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StressBudget {
+    /// The maximum time that building and triangulating the model may take
+    pub max_duration: Duration,
+
+    /// The maximum number of topological objects that the model may create
+    pub max_objects: usize,
+}
+
+/// The result of running a [`StressModel`]
+#[derive(Clone, Copy, Debug)]
+pub struct StressReport {
+    /// The name of the model
+    pub name: &'static str,
+
+    /// The budget that the model had to stay within
+    pub budget: StressBudget,
+
+    /// The time that building and triangulating the model took
+    pub duration: Duration,
+
+    /// The number of topological objects that the model created
+    pub objects: ObjectCounts,
+
+    /// The number of triangles in the triangulated model
+    pub num_triangles: usize,
+}
+
+impl StressReport {
+    /// Indicate whether the model exceeded its time budget
+    pub fn exceeded_duration(&self) -> bool {
+        self.duration > self.budget.max_duration
+    }
+
+    /// Indicate whether the model exceeded its object budget
+    pub fn exceeded_objects(&self) -> bool {
+        self.objects.total() > self.budget.max_objects
+    }
+
+    /// Indicate whether the model stayed within its budget
+    pub fn passed(&self) -> bool {
+        !self.exceeded_duration() && !self.exceeded_objects()
+    }
+}
+
+/// Access all stress models, with their default budgets
+pub fn suite() -> Vec<StressModel> {
+    vec![
+        StressModel {
+            name: "many-holes plate",
+            tolerance: 0.01,
+            budget: StressBudget {
+                max_duration: Duration::from_secs(30),
+                max_objects: 750,
+            },
+            build: many_holes_plate,
+        },
+        StressModel {
+            name: "many-regions sweep",
+            tolerance: 0.01,
+            budget: StressBudget {
+                max_duration: Duration::from_secs(30),
+                max_objects: 2_500,
+            },
+            build: many_regions_sweep,
+        },
+        StressModel {
+            name: "high-curvature shell",
+            tolerance: 0.0001,
+            budget: StressBudget {
+                max_duration: Duration::from_secs(30),
+                max_objects: 100,
+            },
+            build: high_curvature_shell,
+        },
+    ]
+}
+
+/// Run all stress models with their default budgets
+///
+/// Returns a report for each model.
+pub fn run_suite() -> Vec<StressReport> {
+    suite().iter().map(StressModel::run).collect()
+}
+
+/// A square plate with a grid of through-holes
+fn many_holes_plate(core: &mut Core) -> Solid {
+    let num_holes_per_side = 4;
+    let spacing = 1.;
+    let size = spacing * num_holes_per_side as f64;
+
+    let surface = core.layers.topology.surfaces.space_2d();
+
+    let holes = (0..num_holes_per_side)
+        .flat_map(|i| (0..num_holes_per_side).map(move |j| (i, j)))
+        .map(|(i, j)| {
+            let center = Point::from(
+                [i, j].map(|index| (index as f64 + 0.5) * spacing - size / 2.),
+            );
+
+            Cycle::circle(center, spacing / 4., surface.clone(), core)
+                .reverse(core)
+        })
+        .collect::<Vec<_>>();
+
+    let plate = Region::polygon(
+        [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]]
+            .map(|[x, y]| [x * size / 2., y * size / 2.]),
+        surface,
+        core,
+    )
+    .add_interiors(holes, core);
+
+    sweep(plate, 1., core)
+}
+
+/// A sketch made up of many small regions, similar to swept text
+fn many_regions_sweep(core: &mut Core) -> Solid {
+    let num_regions = 12;
+
+    let surface = core.layers.topology.surfaces.space_2d();
+
+    let regions = (0..num_regions)
+        .map(|i| {
+            let center = Point::from([i as f64 * 2., 0.]);
+
+            match i % 3 {
+                0 => Region::regular_polygon(
+                    center,
+                    0.5,
+                    5 + i % 4,
+                    surface.clone(),
+                    core,
+                ),
+                1 => Region::slot(center, 1.5, 0.5, surface.clone(), core),
+                _ => Region::rounded_rectangle(
+                    center,
+                    [1., 1.5],
+                    0.2,
+                    surface.clone(),
+                    core,
+                ),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Sketch::empty(&core.layers.topology)
+        .add_regions(regions, core)
+        .sweep_sketch(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0., 0.5],
+            core,
+        )
+}
+
+/// A thin-walled tube, triangulated at a tolerance that is small in relation
+/// to its radius
+fn high_curvature_shell(core: &mut Core) -> Solid {
+    Solid::tube(1., 0.01, 2., core)
+}
+
+fn sweep(region: Region, height: impl Into<Scalar>, core: &mut Core) -> Solid {
+    Sketch::empty(&core.layers.topology)
+        .add_regions([region], core)
+        .sweep_sketch(
+            core.layers.topology.surfaces.xy_plane(),
+            Vector::from([Scalar::ZERO, Scalar::ZERO, height.into()]),
+            core,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    // The default budgets are generous enough for unoptimized builds, so this
+    // runs with the other tests, and catches substantial regressions.
+    #[test]
+    fn stress_models_stay_within_budget() {
+        for report in super::run_suite() {
+            assert!(report.passed(), "{report:#?}");
+            assert!(report.num_triangles > 0, "{report:#?}");
+        }
+    }
+}
@@ -35,6 +35,15 @@ macro_rules! any_object {
                 }
             }
 
+            /// Count the handles that reference the object
+            pub(crate) fn num_handles(&self) -> usize {
+                match self {
+                    $(
+                        Self::$ty(handle) => handle.num_handles(),
+                    )*
+                }
+            }
+
             /// Validate the object with a pre-defined validation configuration
             pub fn validate(&self,
                 config: &ValidationConfig,
@@ -43,11 +52,15 @@ macro_rules! any_object {
             ) {
                 match self {
                     $(
-                        Self::$ty(object) => object.validate(
-                            config,
-                            errors,
-                            geometry,
-                        ),
+                        Self::$ty(object) => {
+                            let _span = tracing::trace_span!(
+                                "validate",
+                                object = $name,
+                            )
+                            .entered();
+
+                            object.validate(config, errors, geometry);
+                        }
                     )*
                 }
             }
@@ -1,4 +1,4 @@
-use fj_math::{Scalar, Winding};
+use fj_math::{Polygon, Scalar, Winding};
 
 use crate::{
     geometry::{Geometry, SurfacePath},
@@ -61,26 +61,14 @@ impl Cycle {
         }
 
         // Now that we got the special case out of the way, we can treat the
-        // cycle as a polygon:
-        // https://stackoverflow.com/a/1165943
-
-        let mut sum = Scalar::ZERO;
-
-        for (a, b) in self.half_edges().pairs() {
-            let [a, b] = [a, b].map(|half_edge| {
+        // cycle as a polygon.
+        let polygon =
+            Polygon::from_points(self.half_edges().iter().map(|half_edge| {
                 geometry.of_half_edge(half_edge).start_position()
-            });
-
-            sum += (b.u - a.u) * (b.v + a.v);
-        }
-
-        if sum > Scalar::ZERO {
-            return Winding::Cw;
-        }
-        if sum < Scalar::ZERO {
-            return Winding::Ccw;
-        }
+            }));
 
-        unreachable!("Encountered invalid cycle: {self:#?}");
+        polygon.winding().unwrap_or_else(|| {
+            unreachable!("Encountered invalid cycle: {self:#?}")
+        })
     }
 }
@@ -3,7 +3,14 @@ use crate::{
     topology::{Face, ObjectSet},
 };
 
-/// A 3-dimensional closed shell
+/// A 3-dimensional shell
+///
+/// Shells that bound a [`Solid`] are closed. Shells that are not part of a
+/// solid may also be open, as sheets, for example as a result of
+/// [`SweepChain`].
+///
+/// [`Solid`]: crate::topology::Solid
+/// [`SweepChain`]: crate::operations::sweep::SweepChain
 #[derive(Clone, Debug)]
 pub struct Shell {
     faces: ObjectSet<Face>,
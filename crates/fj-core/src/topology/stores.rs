@@ -1,4 +1,4 @@
-use crate::storage::{Handle, Store};
+use crate::storage::{Handle, Iter, ObjectId, Store};
 
 use super::{
     Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface, Vertex,
@@ -43,6 +43,55 @@ impl Topology {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Create another instance of `Topology` that shares this one's stores
+    ///
+    /// See [`Store::share`].
+    pub fn share(&self) -> Self {
+        Self {
+            curves: self.curves.share(),
+            cycles: self.cycles.share(),
+            faces: self.faces.share(),
+            half_edges: self.half_edges.share(),
+            regions: self.regions.share(),
+            shells: self.shells.share(),
+            sketches: self.sketches.share(),
+            solids: self.solids.share(),
+            surfaces: self.surfaces.share(),
+            vertices: self.vertices.share(),
+        }
+    }
+
+    /// Remove all objects that no handle references from the stores
+    ///
+    /// Repeats, until no more objects can be removed, as removing an object
+    /// can leave the objects it referenced unreferenced. Returns the number
+    /// of removed objects. See [`Store::collect`].
+    pub fn collect(&mut self, keep: impl Fn(ObjectId) -> bool) -> usize {
+        let mut total = 0;
+
+        loop {
+            // Stores are collected in an order where referencing objects come
+            // before the objects they reference, so most objects are removed
+            // in the first pass.
+            let removed = self.solids.collect(&keep)
+                + self.sketches.collect(&keep)
+                + self.shells.collect(&keep)
+                + self.faces.collect(&keep)
+                + self.regions.collect(&keep)
+                + self.cycles.collect(&keep)
+                + self.half_edges.collect(&keep)
+                + self.curves.collect(&keep)
+                + self.vertices.collect(&keep)
+                + self.surfaces.collect(&keep);
+
+            if removed == 0 {
+                break total;
+            }
+
+            total += removed;
+        }
+    }
 }
 
 /// Store for [`Surface`]s
@@ -68,6 +117,45 @@ impl Surfaces {
         self.store.insert(handle, surface);
     }
 
+    /// Iterate over all surfaces in the store
+    pub fn iter(&self) -> Iter<Surface> {
+        self.store.iter()
+    }
+
+    /// Create another instance of `Surfaces` that shares this one's surfaces
+    ///
+    /// See [`Store::share`].
+    pub fn share(&self) -> Self {
+        Self {
+            store: self.store.share(),
+            space_2d: self.space_2d.clone(),
+            xy_plane: self.xy_plane.clone(),
+            xz_plane: self.xz_plane.clone(),
+            yz_plane: self.yz_plane.clone(),
+        }
+    }
+
+    /// Remove all surfaces that no handle references
+    ///
+    /// See [`Store::collect`].
+    pub fn collect(&mut self, keep: impl Fn(ObjectId) -> bool) -> usize {
+        self.store.collect(keep)
+    }
+
+    /// Compute the memory that the store has allocated, in bytes
+    ///
+    /// See [`Store::memory_usage`].
+    pub fn memory_usage(&self) -> usize {
+        self.store.memory_usage()
+    }
+
+    /// Count the surfaces in the store
+    ///
+    /// See [`Store::num_objects`].
+    pub fn num_objects(&self) -> usize {
+        self.store.num_objects()
+    }
+
     /// Access the surface representing 2D space
     ///
     /// Every other surface is a 2D subspace within a 3D space. This surface is
@@ -1,17 +1,23 @@
 use crate::{
     geometry::Geometry,
     topology::Cycle,
-    validation::{ValidationConfig, ValidationError},
+    validation::{
+        checks::AdjacentHalfEdgesNotConnected, ValidationConfig,
+        ValidationError,
+    },
 };
 
-use super::Validate;
+use super::{run_check, Validate};
 
 impl Validate for Cycle {
     fn validate(
         &self,
-        _: &ValidationConfig,
-        _: &mut Vec<ValidationError>,
-        _: &Geometry,
+        config: &ValidationConfig,
+        errors: &mut Vec<ValidationError>,
+        geometry: &Geometry,
     ) {
+        run_check::<AdjacentHalfEdgesNotConnected, _>(
+            self, config, errors, geometry,
+        );
     }
 }
@@ -3,14 +3,15 @@ use crate::{
     topology::Face,
     validation::{
         checks::{
-            AdjacentHalfEdgesNotConnected, FaceHasNoBoundary,
+            AdjacentHalfEdgesNotConnected, FaceHasNoBoundary, FaceHasZeroArea,
+            FaceIsSliver, HalfEdgeHasZeroLength,
             InteriorCycleHasInvalidWinding,
         },
-        ValidationCheck, ValidationConfig, ValidationError,
+        ValidationConfig, ValidationError,
     },
 };
 
-use super::Validate;
+use super::{run_check, Validate};
 
 impl Validate for Face {
     fn validate(
@@ -19,16 +20,15 @@ impl Validate for Face {
         errors: &mut Vec<ValidationError>,
         geometry: &Geometry,
     ) {
-        errors.extend(
-            AdjacentHalfEdgesNotConnected::check(self, geometry, config)
-                .map(Into::into),
+        run_check::<AdjacentHalfEdgesNotConnected, _>(
+            self, config, errors, geometry,
         );
-        errors.extend(
-            FaceHasNoBoundary::check(self, geometry, config).map(Into::into),
-        );
-        errors.extend(
-            InteriorCycleHasInvalidWinding::check(self, geometry, config)
-                .map(Into::into),
+        run_check::<FaceHasNoBoundary, _>(self, config, errors, geometry);
+        run_check::<FaceHasZeroArea, _>(self, config, errors, geometry);
+        run_check::<FaceIsSliver, _>(self, config, errors, geometry);
+        run_check::<HalfEdgeHasZeroLength, _>(self, config, errors, geometry);
+        run_check::<InteriorCycleHasInvalidWinding, _>(
+            self, config, errors, geometry,
         );
     }
 }
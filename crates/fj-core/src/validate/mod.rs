@@ -73,9 +73,11 @@ mod solid;
 mod surface;
 mod vertex;
 
+use std::any;
+
 use crate::{
     geometry::Geometry,
-    validation::{ValidationConfig, ValidationError},
+    validation::{ValidationCheck, ValidationConfig, ValidationError},
 };
 
 pub use self::{
@@ -128,3 +130,29 @@ pub trait Validate: Sized {
         geometry: &Geometry,
     );
 }
+
+/// Run a validation check on an object, and collect the errors it finds
+///
+/// Each check runs in its own tracing span, which makes it possible to find
+/// out which checks are slow.
+fn run_check<C, T>(
+    object: &T,
+    config: &ValidationConfig,
+    errors: &mut Vec<ValidationError>,
+    geometry: &Geometry,
+) where
+    C: ValidationCheck<T> + Into<ValidationError>,
+{
+    let check = any::type_name::<C>()
+        .rsplit("::")
+        .next()
+        .unwrap_or_default();
+    let span =
+        tracing::trace_span!("check", check, errors = tracing::field::Empty)
+            .entered();
+
+    let num_errors = errors.len();
+    errors.extend(C::check(object, geometry, config).map(Into::into));
+
+    span.record("errors", errors.len() - num_errors);
+}
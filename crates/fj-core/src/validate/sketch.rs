@@ -5,12 +5,14 @@ use crate::{
     storage::Handle,
     topology::{Cycle, Sketch},
     validate_references,
-    validation::{checks::AdjacentHalfEdgesNotConnected, ValidationCheck},
+    validation::checks::{
+        AdjacentHalfEdgesNotConnected, HalfEdgeHasZeroLength,
+    },
 };
 
 use super::{
     references::{ReferenceCountError, ReferenceCounter},
-    Validate, ValidationConfig, ValidationError,
+    run_check, Validate, ValidationConfig, ValidationError,
 };
 
 impl Validate for Sketch {
@@ -20,10 +22,10 @@ impl Validate for Sketch {
         errors: &mut Vec<ValidationError>,
         geometry: &Geometry,
     ) {
-        errors.extend(
-            AdjacentHalfEdgesNotConnected::check(self, geometry, config)
-                .map(Into::into),
+        run_check::<AdjacentHalfEdgesNotConnected, _>(
+            self, config, errors, geometry,
         );
+        run_check::<HalfEdgeHasZeroLength, _>(self, config, errors, geometry);
         SketchValidationError::check_object_references(self, config, errors);
         SketchValidationError::check_exterior_cycles(
             self, geometry, config, errors,
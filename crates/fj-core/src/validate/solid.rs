@@ -1,16 +1,16 @@
-use std::iter::repeat;
-
 use crate::{
     geometry::Geometry,
-    storage::Handle,
-    topology::{Solid, Vertex},
+    topology::Solid,
     validate_references,
+    validation::checks::{
+        DistinctVerticesCoincide, HalfEdgeHasNoSibling,
+        IdenticalVerticesNotCoincident,
+    },
 };
-use fj_math::Point;
 
 use super::{
     references::{ReferenceCountError, ReferenceCounter},
-    Validate, ValidationConfig, ValidationError,
+    run_check, Validate, ValidationConfig, ValidationError,
 };
 
 impl Validate for Solid {
@@ -20,7 +20,13 @@ impl Validate for Solid {
         errors: &mut Vec<ValidationError>,
         geometry: &Geometry,
     ) {
-        SolidValidationError::check_vertices(self, geometry, config, errors);
+        run_check::<DistinctVerticesCoincide, _>(
+            self, config, errors, geometry,
+        );
+        run_check::<HalfEdgeHasNoSibling, _>(self, config, errors, geometry);
+        run_check::<IdenticalVerticesNotCoincident, _>(
+            self, config, errors, geometry,
+        );
         SolidValidationError::check_object_references(self, config, errors);
     }
 }
@@ -28,120 +34,12 @@ impl Validate for Solid {
 /// [`Solid`] validation failed
 #[derive(Clone, Debug, thiserror::Error)]
 pub enum SolidValidationError {
-    /// [`Solid`] contains vertices that are coincident, but not identical
-    #[error(
-        "Solid contains Vertices that are coincident but not identical\n
-        Vertex 1: {vertex_a:#?} ({position_a:?})
-        Vertex 2: {vertex_b:#?} ({position_b:?})"
-    )]
-    DistinctVerticesCoincide {
-        /// The first vertex
-        vertex_a: Handle<Vertex>,
-
-        /// The second vertex
-        vertex_b: Handle<Vertex>,
-
-        /// Position of first vertex
-        position_a: Point<3>,
-
-        /// Position of second vertex
-        position_b: Point<3>,
-    },
-
-    /// [`Solid`] contains vertices that are identical, but do not coincide
-    #[error(
-        "Solid contains Vertices that are identical but do not coincide\n
-        Vertex 1: {vertex_a:#?} ({position_a:?})
-        Vertex 2: {vertex_b:#?} ({position_b:?})"
-    )]
-    IdenticalVerticesNotCoincident {
-        /// The first vertex
-        vertex_a: Handle<Vertex>,
-
-        /// The second vertex
-        vertex_b: Handle<Vertex>,
-
-        /// Position of first vertex
-        position_a: Point<3>,
-
-        /// Position of second vertex
-        position_b: Point<3>,
-    },
-
     /// Object within solid referenced by more than one other object
     #[error("Object within solid referenced by more than one other Object")]
     MultipleReferences(#[from] ReferenceCountError),
 }
 
 impl SolidValidationError {
-    fn check_vertices(
-        solid: &Solid,
-        geometry: &Geometry,
-        config: &ValidationConfig,
-        errors: &mut Vec<ValidationError>,
-    ) {
-        let vertices: Vec<(Point<3>, Handle<Vertex>)> = solid
-            .shells()
-            .iter()
-            .flat_map(|s| s.faces())
-            .flat_map(|face| {
-                face.region()
-                    .all_cycles()
-                    .flat_map(|cycle| cycle.half_edges().iter().cloned())
-                    .zip(repeat(face.surface()))
-            })
-            .map(|(h, s)| {
-                (
-                    geometry.of_surface(s).point_from_surface_coords(
-                        geometry.of_half_edge(&h).start_position(),
-                    ),
-                    h.start_vertex().clone(),
-                )
-            })
-            .collect();
-
-        // This is O(N^2) which isn't great, but we can't use a HashMap since we
-        // need to deal with float inaccuracies. Maybe we could use some smarter
-        // data-structure like an octree.
-        for (position_a, vertex_a) in &vertices {
-            for (position_b, vertex_b) in &vertices {
-                let vertices_are_identical = vertex_a.id() == vertex_b.id();
-                let vertices_are_not_identical = !vertices_are_identical;
-
-                let too_far_to_be_identical = position_a
-                    .distance_to(position_b)
-                    > config.identical_max_distance;
-                let too_close_to_be_distinct = position_a
-                    .distance_to(position_b)
-                    < config.distinct_min_distance;
-
-                if vertices_are_identical && too_far_to_be_identical {
-                    errors.push(
-                        Self::IdenticalVerticesNotCoincident {
-                            vertex_a: vertex_a.clone(),
-                            vertex_b: vertex_b.clone(),
-                            position_a: *position_a,
-                            position_b: *position_b,
-                        }
-                        .into(),
-                    )
-                }
-
-                if vertices_are_not_identical && too_close_to_be_distinct {
-                    errors.push(
-                        Self::DistinctVerticesCoincide {
-                            vertex_a: vertex_a.clone(),
-                            vertex_b: vertex_b.clone(),
-                            position_a: *position_a,
-                            position_b: *position_b,
-                        }
-                        .into(),
-                    )
-                }
-            }
-        }
-    }
-
     fn check_object_references(
         solid: &Solid,
         _config: &ValidationConfig,
@@ -1,5 +1,6 @@
-use fj_math::{Point, Scalar};
-use itertools::Itertools;
+use std::collections::BTreeMap;
+
+use fj_math::{Point, Scalar, Vector};
 
 use crate::{
     geometry::Geometry,
@@ -63,19 +64,30 @@ impl ValidationCheck<Shell> for CurveGeometryMismatch {
         geometry: &'r Geometry,
         config: &'r ValidationConfig,
     ) -> impl Iterator<Item = Self> + 'r {
-        let edges_and_surfaces =
-            object.all_half_edges_with_surface().collect::<Vec<_>>();
-
-        edges_and_surfaces
-            .clone()
-            .into_iter()
-            .cartesian_product(edges_and_surfaces)
+        // We only care about edges referring to the same curve. Grouping them
+        // by curve first means we only need to compare edges within each
+        // group, instead of comparing all edges to each other.
+        let mut edges_by_curve = BTreeMap::<_, Vec<_>>::new();
+        for (edge, surface) in object.all_half_edges_with_surface() {
+            edges_by_curve
+                .entry(edge.curve().id())
+                .or_default()
+                .push((edge, surface));
+        }
+
+        edges_by_curve
+            .into_values()
+            .flat_map(|edges_and_surfaces| {
+                edges_and_surfaces
+                    .iter()
+                    .flat_map(|a| {
+                        edges_and_surfaces
+                            .iter()
+                            .map(move |b| (a.clone(), b.clone()))
+                    })
+                    .collect::<Vec<_>>()
+            })
             .filter_map(|((edge_a, surface_a), (edge_b, surface_b))| {
-                // We only care about edges referring to the same curve.
-                if edge_a.curve().id() != edge_b.curve().id() {
-                    return None;
-                }
-
                 // No need to check an edge against itself.
                 if edge_a.id() == edge_b.id() {
                     return None;
@@ -92,6 +104,22 @@ impl ValidationCheck<Shell> for CurveGeometryMismatch {
                 let b = a + (d - a) * 1. / 3.;
                 let c = a + (d - a) * 2. / 3.;
 
+                // If the curve is periodic, the boundaries of the half-edges
+                // can be on different sides of a seam. Then the points need to
+                // be compared in the period that each half-edge is in.
+                let offset_b = match surface_a
+                    .period_of_path(&geometry.of_half_edge(&edge_a).path)
+                {
+                    Some(period) => {
+                        let offset = |edge| {
+                            let boundary = geometry.of_half_edge(edge).boundary;
+                            boundary.inner[0] - boundary.wrap(period).inner[0]
+                        };
+                        offset(&edge_b) - offset(&edge_a)
+                    }
+                    None => Vector::from([0.]),
+                };
+
                 let mut errors: Vec<Self> = Vec::new();
 
                 for point_curve in [a, b, c, d] {
@@ -102,7 +130,7 @@ impl ValidationCheck<Shell> for CurveGeometryMismatch {
                     let b_surface = geometry
                         .of_half_edge(&edge_b)
                         .path
-                        .point_from_path_coords(point_curve);
+                        .point_from_path_coords(point_curve + offset_b);
 
                     let a_global =
                         surface_a.point_from_surface_coords(a_surface);
@@ -111,7 +139,7 @@ impl ValidationCheck<Shell> for CurveGeometryMismatch {
 
                     let distance = (a_global - b_global).magnitude();
 
-                    if distance > config.identical_max_distance {
+                    if distance > config.tolerances.identical_point_distance {
                         errors.push(Self {
                             half_edge_a: edge_a.clone(),
                             half_edge_b: edge_b.clone(),
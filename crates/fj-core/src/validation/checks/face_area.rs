@@ -0,0 +1,217 @@
+use fj_math::Scalar;
+
+use crate::{
+    geometry::{area_of_face, Geometry},
+    topology::Face,
+    validation::{ValidationCheck, ValidationConfig},
+};
+
+/// [`Face`] has zero area
+///
+/// A face whose boundary encloses no area, or an area that is so thin that it
+/// can't be told apart from a curve, doesn't contribute anything to the shape
+/// it is part of. It can't be triangulated in a meaningful way either.
+///
+/// The face is considered to have zero area, if its width is below
+/// [`Tolerances::linear`]. The width is estimated from the area and the
+/// perimeter of the face, as `2 * area / perimeter`.
+///
+/// [`Tolerances::linear`]: fj_math::Tolerances::linear
+#[derive(Clone, Debug, thiserror::Error)]
+#[error(
+    "`Face` has zero area\n\
+    - Area: {area}\n\
+    - Perimeter: {perimeter}\n\
+    - `Face`: {face:#?}"
+)]
+pub struct FaceHasZeroArea {
+    /// The area of the [`Face`]
+    pub area: Scalar,
+
+    /// The perimeter of the [`Face`]
+    pub perimeter: Scalar,
+
+    /// The [`Face`] that has zero area
+    pub face: Face,
+}
+
+impl ValidationCheck<Face> for FaceHasZeroArea {
+    fn check<'r>(
+        object: &'r Face,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        let error = FaceDimensions::of(object, geometry).and_then(
+            |FaceDimensions { area, perimeter }| {
+                let width = area * 2. / perimeter;
+
+                config.tolerances.is_zero_length(width).then(|| {
+                    FaceHasZeroArea {
+                        area,
+                        perimeter,
+                        face: object.clone(),
+                    }
+                })
+            },
+        );
+
+        error.into_iter()
+    }
+}
+
+/// [`Face`] is a sliver
+///
+/// A sliver is a face that is very long compared to its width. Like faces with
+/// zero area, slivers are often the result of numerical inaccuracies in the
+/// operation that created them, and lead to badly shaped triangles.
+///
+/// The aspect ratio of a face is its perimeter, divided by its width, as
+/// defined by [`FaceHasZeroArea`]. It is compared to
+/// [`ValidationConfig::max_face_aspect_ratio`].
+///
+/// Faces that are reported by [`FaceHasZeroArea`] are not reported by this
+/// check too.
+#[derive(Clone, Debug, thiserror::Error)]
+#[error(
+    "`Face` is a sliver\n\
+    - Aspect ratio: {aspect_ratio}\n\
+    - Maximum aspect ratio: {max_aspect_ratio}\n\
+    - `Face`: {face:#?}"
+)]
+pub struct FaceIsSliver {
+    /// The aspect ratio of the [`Face`]
+    pub aspect_ratio: Scalar,
+
+    /// The maximum aspect ratio that is considered valid
+    pub max_aspect_ratio: Scalar,
+
+    /// The [`Face`] that is a sliver
+    pub face: Face,
+}
+
+impl ValidationCheck<Face> for FaceIsSliver {
+    fn check<'r>(
+        object: &'r Face,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        let error = FaceDimensions::of(object, geometry).and_then(
+            |FaceDimensions { area, perimeter }| {
+                let width = area * 2. / perimeter;
+                if config.tolerances.is_zero_length(width) {
+                    // Already reported by `FaceHasZeroArea`.
+                    return None;
+                }
+
+                let aspect_ratio = perimeter / width;
+                let max_aspect_ratio = config.max_face_aspect_ratio;
+
+                (aspect_ratio > max_aspect_ratio).then(|| FaceIsSliver {
+                    aspect_ratio,
+                    max_aspect_ratio,
+                    face: object.clone(),
+                })
+            },
+        );
+
+        error.into_iter()
+    }
+}
+
+/// The area and perimeter of a face, in 3D space
+struct FaceDimensions {
+    area: Scalar,
+    perimeter: Scalar,
+}
+
+impl FaceDimensions {
+    /// Compute the dimensions of a face
+    ///
+    /// Returns `None`, if the face has no perimeter. That case is reported by
+    /// other checks.
+    fn of(face: &Face, geometry: &Geometry) -> Option<Self> {
+        let perimeter = face
+            .region()
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges())
+            .map(|half_edge| {
+                geometry.length_of_half_edge(half_edge, face.surface())
+            })
+            .fold(Scalar::ZERO, |a, b| a + b);
+        if perimeter == Scalar::ZERO {
+            return None;
+        }
+
+        let area = area_of_face(face, geometry);
+
+        Some(Self { area, perimeter })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::build::BuildFace,
+        topology::Face,
+        validation::{ValidationCheck, ValidationConfig},
+        Core,
+    };
+
+    use super::{FaceHasZeroArea, FaceIsSliver};
+
+    #[test]
+    fn face_has_zero_area() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        let valid = Face::polygon(
+            surface.clone(),
+            [[0., 0.], [1., 0.], [1., 1.]],
+            &mut core,
+        );
+        FaceHasZeroArea::check_and_return_first_error(
+            &valid,
+            &core.layers.geometry,
+        )?;
+
+        let invalid =
+            Face::polygon(surface, [[0., 0.], [1., 0.], [2., 1e-9]], &mut core);
+        FaceHasZeroArea::check_and_expect_one_error(
+            &invalid,
+            &core.layers.geometry,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn face_is_sliver() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        let valid = Face::polygon(
+            surface.clone(),
+            [[0., 0.], [1., 0.], [1., 1.]],
+            &mut core,
+        );
+        FaceIsSliver::check_and_return_first_error(
+            &valid,
+            &core.layers.geometry,
+        )?;
+
+        let invalid =
+            Face::polygon(surface, [[0., 0.], [1., 0.], [1., 0.01]], &mut core);
+        let config = ValidationConfig {
+            max_face_aspect_ratio: 100.0.into(),
+            ..ValidationConfig::default()
+        };
+        let errors =
+            FaceIsSliver::check(&invalid, &core.layers.geometry, &config)
+                .collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+
+        Ok(())
+    }
+}
@@ -15,7 +15,9 @@ use crate::{
 /// subject to a validation check.
 ///
 /// However, the *position* of that shared vertex is redundantly defined in both
-/// [`HalfEdge`]s. This check verifies that both positions are the same.
+/// [`HalfEdge`]s. This check verifies that both positions are the same. Since
+/// the last [`HalfEdge`] is followed by the first, this also verifies that the
+/// [`Cycle`] is closed.
 ///
 /// ## Implementation Note
 ///
@@ -41,6 +43,7 @@ use crate::{
     - End position of first `HalfEdge`: {end_pos_of_first_half_edge:?}\n\
     - Start position of second `HalfEdge`: {start_pos_of_second_half_edge:?}\n\
     - Distance between vertices: {distance_between_positions}\n\
+    - Index of first `HalfEdge` in `Cycle`: {index_of_first_half_edge}\n\
     - The unconnected `HalfEdge`s: {unconnected_half_edges:#?}"
 )]
 pub struct AdjacentHalfEdgesNotConnected {
@@ -55,6 +58,21 @@ pub struct AdjacentHalfEdgesNotConnected {
 
     /// The edges
     pub unconnected_half_edges: [Handle<HalfEdge>; 2],
+
+    /// The index of the first [`HalfEdge`] within the [`Cycle`]
+    ///
+    /// The [`Cycle`] is broken between this [`HalfEdge`] and the next one.
+    pub index_of_first_half_edge: usize,
+}
+
+impl ValidationCheck<Cycle> for AdjacentHalfEdgesNotConnected {
+    fn check<'r>(
+        object: &'r Cycle,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        check_cycle(object, geometry, config)
+    }
 }
 
 impl ValidationCheck<Face> for AdjacentHalfEdgesNotConnected {
@@ -96,32 +114,32 @@ fn check_cycle<'r>(
     geometry: &'r Geometry,
     config: &'r ValidationConfig,
 ) -> impl Iterator<Item = AdjacentHalfEdgesNotConnected> + 'r {
-    cycle.half_edges().pairs().filter_map(|(first, second)| {
-        let end_pos_of_first_half_edge = {
-            let [_, end] = geometry.of_half_edge(first).boundary.inner;
-            geometry
-                .of_half_edge(first)
-                .path
-                .point_from_path_coords(end)
-        };
-        let start_pos_of_second_half_edge =
-            geometry.of_half_edge(second).start_position();
-
-        let distance_between_positions = (end_pos_of_first_half_edge
-            - start_pos_of_second_half_edge)
-            .magnitude();
-
-        if distance_between_positions > config.identical_max_distance {
-            return Some(AdjacentHalfEdgesNotConnected {
-                end_pos_of_first_half_edge,
-                start_pos_of_second_half_edge,
-                distance_between_positions,
-                unconnected_half_edges: [first.clone(), second.clone()],
-            });
-        }
-
-        None
-    })
+    cycle.half_edges().pairs().enumerate().filter_map(
+        |(index_of_first_half_edge, (first, second))| {
+            let end_pos_of_first_half_edge =
+                geometry.of_half_edge(first).end_position();
+            let start_pos_of_second_half_edge =
+                geometry.of_half_edge(second).start_position();
+
+            let distance_between_positions = (end_pos_of_first_half_edge
+                - start_pos_of_second_half_edge)
+                .magnitude();
+
+            if distance_between_positions
+                > config.tolerances.identical_point_distance
+            {
+                return Some(AdjacentHalfEdgesNotConnected {
+                    end_pos_of_first_half_edge,
+                    start_pos_of_second_half_edge,
+                    distance_between_positions,
+                    unconnected_half_edges: [first.clone(), second.clone()],
+                    index_of_first_half_edge,
+                });
+            }
+
+            None
+        },
+    )
 }
 
 #[cfg(test)]
@@ -132,7 +150,7 @@ mod tests {
             build::{BuildFace, BuildHalfEdge},
             update::{UpdateCycle, UpdateFace, UpdateRegion},
         },
-        topology::{Face, HalfEdge},
+        topology::{Cycle, Face, HalfEdge},
         validation::ValidationCheck,
         Core,
     };
@@ -184,6 +202,37 @@ mod tests {
             &core.layers.geometry,
         );
 
+        // The invalid cycle was already reported, when it was inserted.
+        let _ = core.layers.validation.take_errors();
+
         Ok(())
     }
+
+    #[test]
+    fn cycle_is_not_closed() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+
+        let open = Cycle::new([
+            HalfEdge::line_segment(
+                [[0., 0.], [1., 0.]],
+                None,
+                surface.clone(),
+                &mut core,
+            ),
+            HalfEdge::line_segment(
+                [[1., 0.], [1., 1.]],
+                None,
+                surface,
+                &mut core,
+            ),
+        ]);
+
+        let err = AdjacentHalfEdgesNotConnected::check_and_expect_one_error(
+            &open,
+            &core.layers.geometry,
+        );
+        assert_eq!(err.index_of_first_half_edge, 1);
+    }
 }
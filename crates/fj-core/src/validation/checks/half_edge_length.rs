@@ -0,0 +1,163 @@
+use fj_math::Scalar;
+
+use crate::{
+    geometry::{length_of_half_edge, Geometry},
+    storage::Handle,
+    topology::{Face, HalfEdge, Region, Sketch},
+    validation::{ValidationCheck, ValidationConfig},
+};
+
+/// [`HalfEdge`] has zero length
+///
+/// A half-edge whose length is below [`Tolerances::linear`] can't be told apart
+/// from a single point. Such half-edges are usually the result of a mistake in
+/// the operation that created them, and cause problems downstream, during
+/// approximation and triangulation.
+///
+/// The length of half-edges in a [`Face`] is measured in 3D space, so
+/// half-edges on surfaces whose coordinates are scaled are treated correctly.
+/// The exception are half-edges along a pole, like the pole of a sphere or the
+/// apex of a cone. Those have zero length by definition, but are needed to
+/// bound faces that touch the pole.
+/// The surface of a [`Sketch`] doesn't need to have geometry yet, so half-edges
+/// in sketches are measured in surface coordinates.
+///
+/// [`Tolerances::linear`]: fj_math::Tolerances::linear
+#[derive(Clone, Debug, thiserror::Error)]
+#[error(
+    "`HalfEdge` has zero length\n\
+    - Length: {length}\n\
+    - `HalfEdge`: {half_edge:#?}"
+)]
+pub struct HalfEdgeHasZeroLength {
+    /// The length of the [`HalfEdge`]
+    pub length: Scalar,
+
+    /// The [`HalfEdge`] that has zero length
+    pub half_edge: Handle<HalfEdge>,
+}
+
+impl ValidationCheck<Face> for HalfEdgeHasZeroLength {
+    fn check<'r>(
+        object: &'r Face,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        let surface = geometry.of_surface(object.surface());
+
+        check_region(object.region(), config, move |half_edge| {
+            if surface
+                .is_path_along_pole(&geometry.of_half_edge(half_edge).path)
+            {
+                return None;
+            }
+
+            Some(geometry.length_of_half_edge(half_edge, object.surface()))
+        })
+    }
+}
+
+impl ValidationCheck<Sketch> for HalfEdgeHasZeroLength {
+    fn check<'r>(
+        object: &'r Sketch,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        object.regions().iter().flat_map(move |region| {
+            check_region(region, config, move |half_edge| {
+                Some(length_of_half_edge(
+                    geometry.of_half_edge(half_edge),
+                    None,
+                ))
+            })
+        })
+    }
+}
+
+fn check_region<'r>(
+    region: &'r Region,
+    config: &'r ValidationConfig,
+    length_of_half_edge: impl Fn(&Handle<HalfEdge>) -> Option<Scalar> + 'r,
+) -> impl Iterator<Item = HalfEdgeHasZeroLength> + 'r {
+    region
+        .all_cycles()
+        .flat_map(|cycle| cycle.half_edges())
+        .filter_map(move |half_edge| {
+            let length = length_of_half_edge(half_edge)?;
+
+            if config.tolerances.is_zero_length(length) {
+                return Some(HalfEdgeHasZeroLength {
+                    length,
+                    half_edge: half_edge.clone(),
+                });
+            }
+
+            None
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{
+            build::{BuildFace, BuildHalfEdge},
+            update::{UpdateCycle, UpdateFace, UpdateRegion},
+        },
+        topology::{Face, HalfEdge},
+        validation::ValidationCheck,
+        Core,
+    };
+
+    use super::HalfEdgeHasZeroLength;
+
+    #[test]
+    fn half_edge_has_zero_length() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        let valid = Face::polygon(
+            surface.clone(),
+            [[0., 0.], [1., 0.], [1., 1.]],
+            &mut core,
+        );
+        HalfEdgeHasZeroLength::check_and_return_first_error(
+            &valid,
+            &core.layers.geometry,
+        )?;
+
+        let invalid = valid.update_region(
+            |region, core| {
+                region.update_exterior(
+                    |cycle, core| {
+                        cycle.update_half_edge(
+                            cycle.half_edges().first(),
+                            |_, core| {
+                                [[0., 0.], [1e-9, 0.], [1., 0.]]
+                                    .windows(2)
+                                    .map(|points| {
+                                        HalfEdge::line_segment(
+                                            [points[0], points[1]],
+                                            None,
+                                            surface.clone(),
+                                            core,
+                                        )
+                                    })
+                                    .collect::<Vec<_>>()
+                            },
+                            core,
+                        )
+                    },
+                    core,
+                )
+            },
+            &mut core,
+        );
+        HalfEdgeHasZeroLength::check_and_expect_one_error(
+            &invalid,
+            &core.layers.geometry,
+        );
+
+        Ok(())
+    }
+}
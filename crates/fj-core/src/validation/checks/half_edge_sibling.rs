@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    geometry::Geometry,
+    queries::{BoundingVerticesOfHalfEdge, SiblingOfHalfEdge},
+    storage::Handle,
+    topology::{HalfEdge, Shell, Solid},
+    validation::{ValidationCheck, ValidationConfig},
+};
+
+/// [`HalfEdge`] has no sibling
+///
+/// In a closed shell, every half-edge is paired with a sibling, the half-edge
+/// of the neighboring face that runs along the same edge, in the opposite
+/// direction. A half-edge without a sibling is on the boundary of a hole.
+///
+/// Half-edges along a pole, like the pole of a sphere or the apex of a cone,
+/// are the exception. They have zero length, and there is no face on the other
+/// side of them.
+///
+/// Shells are allowed to be open, as sheets, so this check only runs
+/// automatically on the shells of a [`Solid`]. It can be run on a [`Shell`]
+/// explicitly, to check that the shell is closed.
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("Half-edge has no sibling: {half_edge:#?}")]
+pub struct HalfEdgeHasNoSibling {
+    /// The half-edge that has no sibling
+    pub half_edge: Handle<HalfEdge>,
+}
+
+impl ValidationCheck<Shell> for HalfEdgeHasNoSibling {
+    fn check<'r>(
+        object: &'r Shell,
+        geometry: &'r Geometry,
+        _: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        let mut unmatched_half_edges = BTreeMap::new();
+
+        for face in object.faces() {
+            let surface = geometry.of_surface(face.surface());
+
+            for cycle in face.region().all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    let curve = half_edge.curve().clone();
+                    let half_edge_geom = geometry.of_half_edge(half_edge);
+
+                    if surface.is_path_along_pole(&half_edge_geom.path) {
+                        continue;
+                    }
+
+                    // Siblings on a periodic curve can have boundaries that
+                    // are a whole number of periods apart.
+                    let boundary = match surface
+                        .period_of_path(&half_edge_geom.path)
+                    {
+                        Some(period) => half_edge_geom.boundary.wrap(period),
+                        None => half_edge_geom.boundary,
+                    };
+                    let vertices =
+                        cycle.bounding_vertices_of_half_edge(half_edge).expect(
+                            "`half_edge` came from `cycle`, must exist there",
+                        );
+
+                    let key = (curve.clone(), boundary, vertices.clone());
+                    let key_reversed =
+                        (curve, boundary.reverse(), vertices.reverse());
+
+                    match unmatched_half_edges.remove(&key_reversed) {
+                        Some(sibling) => {
+                            // This must be the sibling of the half-edge we're
+                            // currently looking at. Let's make sure the logic
+                            // we use here to determine that matches the
+                            // "official" definition.
+                            assert!(object
+                                .are_siblings(half_edge, sibling, geometry));
+                        }
+                        None => {
+                            // If this half-edge has a sibling, we haven't seen
+                            // it yet. Let's store this half-edge then, in case
+                            // we come across the sibling later.
+                            unmatched_half_edges.insert(key, half_edge);
+                        }
+                    }
+                }
+            }
+        }
+
+        unmatched_half_edges
+            .into_values()
+            .cloned()
+            .map(|half_edge| HalfEdgeHasNoSibling { half_edge })
+    }
+}
+
+impl ValidationCheck<Solid> for HalfEdgeHasNoSibling {
+    fn check<'r>(
+        object: &'r Solid,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        object.shells().iter().flat_map(|shell| {
+            <Self as ValidationCheck<Shell>>::check(shell, geometry, config)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{
+            build::{BuildShell, BuildSolid},
+            update::{UpdateShell, UpdateSolid},
+        },
+        topology::{Shell, Solid},
+        validation::ValidationCheck,
+        Core,
+    };
+
+    use super::HalfEdgeHasNoSibling;
+
+    #[test]
+    fn half_edge_has_no_sibling() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let valid = Shell::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let invalid = valid.shell.remove_face(&valid.abc.face);
+
+        HalfEdgeHasNoSibling::check_and_return_first_error(
+            &valid.shell,
+            &core.layers.geometry,
+        )?;
+        let errors = HalfEdgeHasNoSibling::check(
+            &invalid,
+            &core.layers.geometry,
+            &core.layers.validation.config,
+        );
+        assert_eq!(errors.count(), 3);
+
+        let invalid = Solid::empty().add_shells([invalid], &mut core);
+        let errors = HalfEdgeHasNoSibling::check(
+            &invalid,
+            &core.layers.geometry,
+            &core.layers.validation.config,
+        );
+        assert_eq!(errors.count(), 3);
+
+        Ok(())
+    }
+}
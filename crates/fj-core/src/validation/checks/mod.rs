@@ -3,13 +3,23 @@
 //! See documentation of [parent module](super) for more information.
 
 mod curve_geometry_mismatch;
+mod face_area;
 mod face_boundary;
 mod face_winding;
 mod half_edge_connection;
+mod half_edge_length;
+mod half_edge_sibling;
+mod vertex_coincidence;
 
 pub use self::{
     curve_geometry_mismatch::CurveGeometryMismatch,
+    face_area::{FaceHasZeroArea, FaceIsSliver},
     face_boundary::FaceHasNoBoundary,
     face_winding::InteriorCycleHasInvalidWinding,
     half_edge_connection::AdjacentHalfEdgesNotConnected,
+    half_edge_length::HalfEdgeHasZeroLength,
+    half_edge_sibling::HalfEdgeHasNoSibling,
+    vertex_coincidence::{
+        DistinctVerticesCoincide, IdenticalVerticesNotCoincident,
+    },
 };
@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+
+use fj_math::Point;
+
+use crate::{
+    geometry::Geometry,
+    storage::Handle,
+    topology::{Face, Shell, Solid, Surface, Vertex},
+    validation::{ValidationCheck, ValidationConfig},
+};
+
+/// Distinct [`Vertex`] objects are coincident
+///
+/// Two vertices whose positions are closer than [`Tolerances::linear`] are
+/// considered to be the same point in space. Then they must be represented by
+/// the same [`Vertex`] object. Otherwise, any code that relies on vertex
+/// identity, like approximation or triangulation, will treat them as separate
+/// points, and create gaps or overlaps.
+///
+/// [`Tolerances::linear`]: fj_math::Tolerances::linear
+#[derive(Clone, Debug, thiserror::Error)]
+#[error(
+    "Distinct `Vertex` objects are coincident\n\
+    - Position of first `Vertex`: {position_a:?}\n\
+    - Position of second `Vertex`: {position_b:?}\n\
+    - First `Vertex`: {vertex_a:#?}\n\
+    - Second `Vertex`: {vertex_b:#?}"
+)]
+pub struct DistinctVerticesCoincide {
+    /// The first vertex
+    pub vertex_a: Handle<Vertex>,
+
+    /// The second vertex
+    pub vertex_b: Handle<Vertex>,
+
+    /// The position of the first vertex
+    pub position_a: Point<3>,
+
+    /// The position of the second vertex
+    pub position_b: Point<3>,
+}
+
+impl ValidationCheck<Shell> for DistinctVerticesCoincide {
+    fn check<'r>(
+        object: &'r Shell,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        check_distinct_vertices(object.faces(), geometry, config)
+    }
+}
+
+impl ValidationCheck<Solid> for DistinctVerticesCoincide {
+    fn check<'r>(
+        object: &'r Solid,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        check_distinct_vertices(
+            object.shells().iter().flat_map(|shell| shell.faces()),
+            geometry,
+            config,
+        )
+    }
+}
+
+/// Identical [`Vertex`] objects are not coincident
+///
+/// A vertex is referenced by every half-edge that starts there, and each of
+/// those half-edges defines the vertex' position in the coordinates of its own
+/// surface. If those positions are further apart than
+/// [`Tolerances::identical_point_distance`], they can't refer to the same
+/// point, and the shape has inconsistent geometry.
+///
+/// [`Tolerances::identical_point_distance`]: fj_math::Tolerances::identical_point_distance
+#[derive(Clone, Debug, thiserror::Error)]
+#[error(
+    "Identical `Vertex` objects are not coincident\n\
+    - First position: {position_a:?}\n\
+    - Second position: {position_b:?}\n\
+    - `Vertex`: {vertex:#?}\n\
+    - Surface of first position: {surface_a:#?}\n\
+    - Surface of second position: {surface_b:#?}"
+)]
+pub struct IdenticalVerticesNotCoincident {
+    /// The vertex
+    pub vertex: Handle<Vertex>,
+
+    /// The first position of the vertex
+    pub position_a: Point<3>,
+
+    /// The second position of the vertex
+    pub position_b: Point<3>,
+
+    /// The surface that defines the first position
+    pub surface_a: Handle<Surface>,
+
+    /// The surface that defines the second position
+    pub surface_b: Handle<Surface>,
+}
+
+impl ValidationCheck<Shell> for IdenticalVerticesNotCoincident {
+    fn check<'r>(
+        object: &'r Shell,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        check_identical_vertices(object.faces(), geometry, config)
+    }
+}
+
+impl ValidationCheck<Solid> for IdenticalVerticesNotCoincident {
+    fn check<'r>(
+        object: &'r Solid,
+        geometry: &'r Geometry,
+        config: &'r ValidationConfig,
+    ) -> impl Iterator<Item = Self> + 'r {
+        check_identical_vertices(
+            object.shells().iter().flat_map(|shell| shell.faces()),
+            geometry,
+            config,
+        )
+    }
+}
+
+fn check_distinct_vertices<'r>(
+    faces: impl IntoIterator<Item = &'r Handle<Face>>,
+    geometry: &Geometry,
+    config: &ValidationConfig,
+) -> impl Iterator<Item = DistinctVerticesCoincide> {
+    // Each vertex is represented by the first of its positions here. If its
+    // positions don't agree, that is reported by the other check.
+    let mut vertices = positions_of_vertices(faces, geometry)
+        .into_iter()
+        .map(|(vertex, positions)| {
+            let (position, _) = positions[0];
+            (vertex, position)
+        })
+        .collect::<Vec<_>>();
+
+    // Sorting by one coordinate means that only vertices that are close in
+    // that coordinate need to be compared with each other.
+    vertices.sort_by_key(|(_, position)| position.x);
+
+    let mut errors = Vec::new();
+
+    for (i, (vertex_a, position_a)) in vertices.iter().enumerate() {
+        for (vertex_b, position_b) in &vertices[i + 1..] {
+            if position_b.x - position_a.x >= config.tolerances.linear {
+                break;
+            }
+
+            if position_a.distance_to(position_b) < config.tolerances.linear {
+                errors.push(DistinctVerticesCoincide {
+                    vertex_a: vertex_a.clone(),
+                    vertex_b: vertex_b.clone(),
+                    position_a: *position_a,
+                    position_b: *position_b,
+                });
+            }
+        }
+    }
+
+    errors.into_iter()
+}
+
+fn check_identical_vertices<'r>(
+    faces: impl IntoIterator<Item = &'r Handle<Face>>,
+    geometry: &Geometry,
+    config: &ValidationConfig,
+) -> impl Iterator<Item = IdenticalVerticesNotCoincident> {
+    let mut errors = Vec::new();
+
+    for (vertex, positions) in positions_of_vertices(faces, geometry) {
+        let (position_a, surface_a) = &positions[0];
+
+        // Reporting the first position that doesn't agree is enough to point
+        // at the problem, and avoids flooding the caller with errors.
+        let mismatch = positions[1..].iter().find(|(position_b, _)| {
+            position_a.distance_to(position_b)
+                > config.tolerances.identical_point_distance
+        });
+
+        if let Some((position_b, surface_b)) = mismatch {
+            errors.push(IdenticalVerticesNotCoincident {
+                vertex,
+                position_a: *position_a,
+                position_b: *position_b,
+                surface_a: surface_a.clone(),
+                surface_b: surface_b.clone(),
+            });
+        }
+    }
+
+    errors.into_iter()
+}
+
+/// Collect the positions that the half-edges of the faces define for vertices
+///
+/// Each vertex comes with at least one position, and the surface that the
+/// position has been computed from.
+fn positions_of_vertices<'r>(
+    faces: impl IntoIterator<Item = &'r Handle<Face>>,
+    geometry: &Geometry,
+) -> BTreeMap<Handle<Vertex>, Vec<(Point<3>, Handle<Surface>)>> {
+    let mut vertices = BTreeMap::<_, Vec<_>>::new();
+
+    for face in faces {
+        let surface = geometry.of_surface(face.surface());
+
+        for half_edge in face
+            .region()
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges())
+        {
+            let position = surface.point_from_surface_coords(
+                geometry.of_half_edge(half_edge).start_position(),
+            );
+
+            vertices
+                .entry(half_edge.start_vertex().clone())
+                .or_default()
+                .push((position, face.surface().clone()));
+        }
+    }
+
+    vertices
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{
+            build::{BuildShell, BuildSolid},
+            geometry::UpdateHalfEdgeGeometry,
+            insert::Insert,
+            update::{
+                UpdateCycle, UpdateFace, UpdateHalfEdge, UpdateRegion,
+                UpdateShell, UpdateSolid,
+            },
+        },
+        topology::{Shell, Solid},
+        validation::ValidationCheck,
+        Core,
+    };
+
+    use super::{DistinctVerticesCoincide, IdenticalVerticesNotCoincident};
+
+    #[test]
+    fn distinct_vertices_coincide() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let valid = Shell::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        DistinctVerticesCoincide::check_and_return_first_error(
+            &valid.shell,
+            &core.layers.geometry,
+        )?;
+
+        // Two tetrahedra that touch in one corner, with each of them having
+        // its own vertex there.
+        let other = Shell::tetrahedron(
+            [[0., 0., 0.], [0., -1., 0.], [-1., 0., 0.], [0., 0., -1.]],
+            &mut core,
+        );
+        let invalid = Solid::empty()
+            .add_shells([valid.shell.clone(), other.shell], &mut core);
+        DistinctVerticesCoincide::check_and_expect_one_error(
+            &invalid,
+            &core.layers.geometry,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn identical_vertices_not_coincident() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let valid = Shell::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        IdenticalVerticesNotCoincident::check_and_return_first_error(
+            &valid.shell,
+            &core.layers.geometry,
+        )?;
+
+        // Replace the start vertex of a half-edge with the start vertex of
+        // another half-edge in the same face, which is somewhere else.
+        let invalid = valid.shell.update_face(
+            &valid.abc.face,
+            |face, core| {
+                [face.update_region(
+                    |region, core| {
+                        region.update_exterior(
+                            |cycle, core| {
+                                let [a, b] = [0, 1].map(|i| {
+                                    cycle
+                                        .half_edges()
+                                        .nth(i)
+                                        .expect("Half-edge exists")
+                                        .clone()
+                                });
+
+                                cycle.update_half_edge(
+                                    &a,
+                                    |half_edge, core| {
+                                        [half_edge
+                                            .update_start_vertex(
+                                                |_, _| b.start_vertex().clone(),
+                                                core,
+                                            )
+                                            .insert(core)
+                                            .set_geometry(
+                                                *core
+                                                    .layers
+                                                    .geometry
+                                                    .of_half_edge(half_edge),
+                                                &mut core.layers.geometry,
+                                            )]
+                                    },
+                                    core,
+                                )
+                            },
+                            core,
+                        )
+                    },
+                    core,
+                )]
+            },
+            &mut core,
+        );
+        IdenticalVerticesNotCoincident::check_and_expect_one_error(
+            &invalid,
+            &core.layers.geometry,
+        );
+
+        // The modified objects were validated, when they were inserted.
+        let _ = core.layers.validation.take_errors();
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use fj_math::Scalar;
+use fj_math::{Scalar, Tolerances};
 
 /// Configuration required for the validation process
 #[derive(Debug, Clone, Copy)]
@@ -19,32 +19,38 @@ pub struct ValidationConfig {
     /// Defaults to `false`.
     pub panic_on_error: bool,
 
-    /// The minimum distance between distinct objects
+    /// The tolerances used to compare positions of objects
     ///
-    /// Objects whose distance is less than the value defined in this field, are
-    /// considered identical.
-    pub distinct_min_distance: Scalar,
+    /// Objects whose distance is less than [`Tolerances::linear`], are
+    /// considered identical. Objects that are considered identical might still
+    /// have a distance between them, due to inaccuracies of the numerical
+    /// representation. If that distance is larger than
+    /// [`Tolerances::identical_point_distance`], they can not be considered
+    /// identical.
+    ///
+    /// Operations use the same tolerances, so they agree with validation. See
+    /// [`Core::tolerances`].
+    ///
+    /// [`Core::tolerances`]: crate::Core::tolerances
+    pub tolerances: Tolerances,
 
-    /// The maximum distance between identical objects
+    /// The maximum aspect ratio of a face, before it is considered a sliver
     ///
-    /// Objects that are considered identical might still have a distance
-    /// between them, due to inaccuracies of the numerical representation. If
-    /// that distance is less than the one defined in this field, can not be
-    /// considered identical.
-    pub identical_max_distance: Scalar,
+    /// The aspect ratio of a face is the ratio of its perimeter to its width.
+    /// Its width is approximated as twice its area, divided by its perimeter.
+    /// A square has an aspect ratio of 8, while long and thin faces have much
+    /// larger ones. Those can cause problems during triangulation.
+    ///
+    /// Defaults to `1e5`.
+    pub max_face_aspect_ratio: Scalar,
 }
 
 impl Default for ValidationConfig {
     fn default() -> Self {
         Self {
             panic_on_error: false,
-            distinct_min_distance: Scalar::from_f64(5e-7), // 0.5 µm,
-
-            // This value was chosen pretty arbitrarily. Seems small enough to
-            // catch errors. If it turns out it's too small (because it produces
-            // false positives due to floating-point accuracy issues), we can
-            // adjust it.
-            identical_max_distance: Scalar::from_f64(5e-14),
+            tolerances: Tolerances::default(),
+            max_face_aspect_ratio: Scalar::from_f64(1e5),
         }
     }
 }
@@ -5,8 +5,10 @@ use crate::validate::{
 };
 
 use super::checks::{
-    AdjacentHalfEdgesNotConnected, CurveGeometryMismatch, FaceHasNoBoundary,
-    InteriorCycleHasInvalidWinding,
+    AdjacentHalfEdgesNotConnected, CurveGeometryMismatch,
+    DistinctVerticesCoincide, FaceHasNoBoundary, FaceHasZeroArea, FaceIsSliver,
+    HalfEdgeHasNoSibling, HalfEdgeHasZeroLength,
+    IdenticalVerticesNotCoincident, InteriorCycleHasInvalidWinding,
 };
 
 /// An error that can occur during a validation
@@ -20,10 +22,34 @@ pub enum ValidationError {
     #[error(transparent)]
     CurveGeometryMismatch(#[from] CurveGeometryMismatch),
 
+    /// Distinct vertices are coincident
+    #[error(transparent)]
+    DistinctVerticesCoincide(#[from] DistinctVerticesCoincide),
+
     /// Face has no boundary
     #[error(transparent)]
     FaceHasNoBoundary(#[from] FaceHasNoBoundary),
 
+    /// Face has zero area
+    #[error(transparent)]
+    FaceHasZeroArea(#[from] FaceHasZeroArea),
+
+    /// Face is a sliver
+    #[error(transparent)]
+    FaceIsSliver(#[from] FaceIsSliver),
+
+    /// Half-edge has no sibling
+    #[error(transparent)]
+    HalfEdgeHasNoSibling(#[from] HalfEdgeHasNoSibling),
+
+    /// Half-edge has zero length
+    #[error(transparent)]
+    HalfEdgeHasZeroLength(#[from] HalfEdgeHasZeroLength),
+
+    /// Identical vertices are not coincident
+    #[error(transparent)]
+    IdenticalVerticesNotCoincident(#[from] IdenticalVerticesNotCoincident),
+
     /// Interior cycle has invalid winding
     #[error(transparent)]
     InteriorCycleHasInvalidWinding(#[from] InteriorCycleHasInvalidWinding),
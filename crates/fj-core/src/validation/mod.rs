@@ -24,6 +24,7 @@ mod config;
 mod error;
 mod validation;
 mod validation_check;
+mod validation_checks;
 
 pub mod checks;
 
@@ -31,5 +32,6 @@ pub use self::{
     config::ValidationConfig,
     error::{ValidationError, ValidationErrors},
     validation::Validation,
-    validation_check::ValidationCheck,
+    validation_check::{CheckedBy, ValidationCheck},
+    validation_checks::ValidationChecks,
 };
@@ -62,3 +62,35 @@ pub trait ValidationCheck<T>: Sized {
         err
     }
 }
+
+/// An object that a specific validation check applies to
+///
+/// This trait is implemented for every object `T` and validation check `C`,
+/// for which `C` implements [`ValidationCheck<T>`]. It exists, so the type of
+/// the object can be inferred, while the validation check is specified
+/// explicitly, as in `core.validate::<CurveGeometryMismatch>(&shell)`.
+///
+/// See [`Core::validate`].
+///
+/// [`Core::validate`]: crate::Core::validate
+pub trait CheckedBy<C> {
+    /// Run the validation check `C` on this object, and collect all errors
+    fn check_with(
+        &self,
+        geometry: &Geometry,
+        config: &ValidationConfig,
+    ) -> Vec<C>;
+}
+
+impl<T, C> CheckedBy<C> for T
+where
+    C: ValidationCheck<T>,
+{
+    fn check_with(
+        &self,
+        geometry: &Geometry,
+        config: &ValidationConfig,
+    ) -> Vec<C> {
+        C::check(self, geometry, config).collect()
+    }
+}
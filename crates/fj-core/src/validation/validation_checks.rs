@@ -0,0 +1,103 @@
+use crate::geometry::Geometry;
+
+use super::{ValidationCheck, ValidationConfig, ValidationError};
+
+/// A selection of validation checks that apply to objects of type `T`
+///
+/// Objects are validated with all applicable checks, when they are inserted.
+/// This type can be used to run a subset of those checks on demand, for
+/// example in unit tests, where running the full set would be wasteful.
+///
+/// To run a single check, [`Core::validate`] is more convenient.
+///
+/// [`Core::validate`]: crate::Core::validate
+pub struct ValidationChecks<T> {
+    checks: Vec<CheckFn<T>>,
+}
+
+type CheckFn<T> = fn(&T, &Geometry, &ValidationConfig) -> Vec<ValidationError>;
+
+impl<T> ValidationChecks<T> {
+    /// Construct an empty selection of validation checks
+    pub fn new() -> Self {
+        Self { checks: Vec::new() }
+    }
+
+    /// Add a validation check to the selection
+    pub fn with<C>(mut self) -> Self
+    where
+        C: ValidationCheck<T> + Into<ValidationError>,
+    {
+        self.checks.push(|object, geometry, config| {
+            C::check(object, geometry, config).map(Into::into).collect()
+        });
+        self
+    }
+
+    /// Run all selected validation checks on an object
+    ///
+    /// Returns the errors found by all checks, in the order in which the checks
+    /// have been added.
+    pub fn run(
+        &self,
+        object: &T,
+        geometry: &Geometry,
+        config: &ValidationConfig,
+    ) -> Vec<ValidationError> {
+        self.checks
+            .iter()
+            .flat_map(|check| check(object, geometry, config))
+            .collect()
+    }
+}
+
+impl<T> Default for ValidationChecks<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::build::BuildFace,
+        topology::Face,
+        validation::{
+            checks::{FaceHasNoBoundary, FaceHasZeroArea, FaceIsSliver},
+            ValidationConfig, ValidationError,
+        },
+        Core,
+    };
+
+    use super::ValidationChecks;
+
+    #[test]
+    fn run_selected_checks() {
+        let mut core = Core::new();
+
+        let sliver = Face::polygon(
+            core.layers.topology.surfaces.xy_plane(),
+            [[0., 0.], [1., 0.], [1., 0.01]],
+            &mut core,
+        );
+        let config = ValidationConfig {
+            max_face_aspect_ratio: 100.0.into(),
+            ..ValidationConfig::default()
+        };
+
+        let errors =
+            core.validate_with_config::<FaceIsSliver>(&sliver, &config);
+        assert_eq!(errors.len(), 1);
+        assert!(core.validate::<FaceIsSliver>(&sliver).is_empty());
+
+        let checks = ValidationChecks::new()
+            .with::<FaceHasNoBoundary>()
+            .with::<FaceHasZeroArea>()
+            .with::<FaceIsSliver>();
+        let errors = checks.run(&sliver, &core.layers.geometry, &config);
+        assert!(matches!(
+            errors.as_slice(),
+            [ValidationError::FaceIsSliver(_)]
+        ));
+    }
+}
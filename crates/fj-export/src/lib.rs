@@ -16,8 +16,10 @@ use std::{
 
 use thiserror::Error;
 
-use fj_interop::Mesh;
-use fj_math::{Point, Triangle};
+use fj_interop::{
+    Annotation, Cancelled, Drawing, LineKind, Mesh, NoProgress, ProgressSink,
+};
+use fj_math::{Aabb, LengthUnit, Point, Scalar, Vector};
 
 /// Export the provided mesh to the file at the given path.
 ///
@@ -25,19 +27,55 @@ use fj_math::{Point, Triangle};
 ///
 /// Currently 3MF & STL file types are supported. The case insensitive file extension of
 /// the provided path is used to switch between supported types.
+///
+/// The mesh is assumed to be measured in millimeters. Use [`export_in_unit`],
+/// if that is not the case.
 pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
+    export_in_unit(mesh, LengthUnit::Millimeter, path)
+}
+
+/// Export the provided mesh, measured in the provided unit, to the given path
+///
+/// Works like [`export`], but takes the unit of the mesh into account. 3MF
+/// files are always measured in millimeters, so the mesh is converted, if it
+/// uses another unit. STL and OBJ files don't specify a unit, so the mesh is
+/// written as-is, and whoever reads the file needs to know its unit.
+pub fn export_in_unit(
+    mesh: &Mesh<Point<3>>,
+    unit: LengthUnit,
+    path: &Path,
+) -> Result<(), Error> {
+    export_with_progress(mesh, unit, path, &mut NoProgress)
+}
+
+/// Export the provided mesh, reporting progress to the provided sink
+///
+/// Works like [`export_in_unit`], but reports how many triangles have been
+/// converted so far. If the sink cancels the export, this returns
+/// [`Error::Cancelled`], and the file at `path` is left incomplete.
+pub fn export_with_progress(
+    mesh: &Mesh<Point<3>>,
+    unit: LengthUnit,
+    path: &Path,
+    progress: &mut dyn ProgressSink,
+) -> Result<(), Error> {
     match path.extension() {
         Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
             let mut file = File::create(path)?;
-            export_3mf(mesh, &mut file)
+            if unit == LengthUnit::Millimeter {
+                write_3mf(mesh, &mut file, progress)
+            } else {
+                let mesh = convert_mesh(mesh, unit, LengthUnit::Millimeter);
+                write_3mf(&mesh, &mut file, progress)
+            }
         }
         Some(extension) if extension.to_ascii_uppercase() == "STL" => {
             let mut file = File::create(path)?;
-            export_stl(mesh, &mut file)
+            write_stl(mesh, &mut file, progress)
         }
         Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
             let mut file = File::create(path)?;
-            export_obj(mesh, &mut file)
+            write_obj(mesh, &mut file, progress)
         }
         Some(extension) => Err(Error::InvalidExtension(
             extension.to_string_lossy().into_owned(),
@@ -51,6 +89,16 @@ pub fn export_3mf(
     mesh: &Mesh<Point<3>>,
     write: impl Write + Seek,
 ) -> Result<(), Error> {
+    write_3mf(mesh, write, &mut NoProgress)
+}
+
+fn write_3mf(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write + Seek,
+    progress: &mut dyn ProgressSink,
+) -> Result<(), Error> {
+    let num_triangles = mesh.num_triangles();
+
     let vertices = mesh
         .vertices()
         .map(|point| threemf::model::Vertex {
@@ -60,15 +108,17 @@ pub fn export_3mf(
         })
         .collect();
 
-    let indices: Vec<_> = mesh.indices().collect();
-    let triangles = indices
-        .chunks(3)
-        .map(|triangle| threemf::model::Triangle {
-            v1: triangle[0] as usize,
-            v2: triangle[1] as usize,
-            v3: triangle[2] as usize,
-        })
-        .collect();
+    let mut triangles = Vec::with_capacity(num_triangles);
+    for (i, [v1, v2, v3]) in mesh.triangle_indices().enumerate() {
+        report_progress(progress, i, num_triangles)?;
+
+        triangles.push(threemf::model::Triangle {
+            v1: v1 as usize,
+            v2: v2 as usize,
+            v3: v3 as usize,
+        });
+    }
+    report_progress(progress, num_triangles, num_triangles)?;
 
     let mesh = threemf::Mesh {
         vertices: threemf::model::Vertices { vertex: vertices },
@@ -84,34 +134,41 @@ pub fn export_3mf(
 
 /// Export the provided mesh to the provided writer in the STL format.
 pub fn export_stl(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write,
+) -> Result<(), Error> {
+    write_stl(mesh, write, &mut NoProgress)
+}
+
+fn write_stl(
     mesh: &Mesh<Point<3>>,
     mut write: impl Write,
+    progress: &mut dyn ProgressSink,
 ) -> Result<(), Error> {
-    let points = mesh
-        .triangles()
-        .map(|triangle| triangle.inner.points())
-        .collect::<Vec<_>>();
+    let num_triangles = mesh.num_triangles();
 
-    let vertices = points.iter().map(|points| {
-        points.map(|point| point.coords.components.map(|s| s.into_f32()))
+    let vertices = mesh.triangle_indices().map(|indices| {
+        mesh.triangle_vertices(indices)
+            .map(|point| point.coords.components.map(|s| s.into_f32()))
     });
 
-    let normals = points
-        .iter()
-        .map(|&points| points.into())
-        .map(|triangle: Triangle<3>| triangle.normal())
+    let normals = mesh
+        .normals()
         .map(|vector| vector.components.map(|s| s.into_f32()));
 
-    let triangles = vertices
-        .zip(normals)
-        .map(|([v1, v2, v3], normal)| stl::Triangle {
+    let mut triangles = Vec::with_capacity(num_triangles);
+    for (i, ([v1, v2, v3], normal)) in vertices.zip(normals).enumerate() {
+        report_progress(progress, i, num_triangles)?;
+
+        triangles.push(stl::Triangle {
             normal,
             v1,
             v2,
             v3,
             attr_byte_count: 0,
-        })
-        .collect::<Vec<_>>();
+        });
+    }
+    report_progress(progress, num_triangles, num_triangles)?;
 
     let binary_stl_file = stl::BinaryStlFile {
         header: stl::BinaryStlHeader {
@@ -131,55 +188,537 @@ pub fn export_stl(
 
 /// Export the provided mesh to the provided writer in the OBJ format.
 pub fn export_obj(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write,
+) -> Result<(), Error> {
+    write_obj(mesh, write, &mut NoProgress)
+}
+
+fn write_obj(
     mesh: &Mesh<Point<3>>,
     mut write: impl Write,
+    progress: &mut dyn ProgressSink,
 ) -> Result<(), Error> {
-    for (cnt, t) in mesh.triangles().enumerate() {
-        // write each point of the triangle
-        for v in t.inner.points() {
-            wavefront_rs::obj::writer::Writer { auto_newline: true }
-                .write(
-                    &mut write,
-                    &wavefront_rs::obj::entity::Entity::Vertex {
-                        x: v.x.into_f64(),
-                        y: v.y.into_f64(),
-                        z: v.z.into_f64(),
-                        w: None,
-                    },
-                )
-                .or(Err(Error::OBJ))?;
-        }
+    let num_triangles = mesh.num_triangles();
+
+    // write each vertex once, so triangles can share them
+    for v in mesh.vertices() {
+        wavefront_rs::obj::writer::Writer { auto_newline: true }
+            .write(
+                &mut write,
+                &wavefront_rs::obj::entity::Entity::Vertex {
+                    x: v.x.into_f64(),
+                    y: v.y.into_f64(),
+                    z: v.z.into_f64(),
+                    w: None,
+                },
+            )
+            .or(Err(Error::OBJ))?;
+    }
+
+    for (i, indices) in mesh.triangle_indices().enumerate() {
+        report_progress(progress, i, num_triangles)?;
 
-        // write the triangle
+        // write the triangle; OBJ indices start at 1
         wavefront_rs::obj::writer::Writer { auto_newline: true }
             .write(
                 &mut write,
                 &wavefront_rs::obj::entity::Entity::Face {
-                    vertices: vec![
+                    vertices: Vec::from(indices.map(|index| {
                         wavefront_rs::obj::entity::FaceVertex {
-                            vertex: (cnt * 3 + 1) as i64,
+                            vertex: i64::from(index) + 1,
                             texture: None,
                             normal: None,
-                        },
-                        wavefront_rs::obj::entity::FaceVertex {
-                            vertex: (cnt * 3 + 2) as i64,
-                            texture: None,
-                            normal: None,
-                        },
-                        wavefront_rs::obj::entity::FaceVertex {
-                            vertex: (cnt * 3 + 3) as i64,
-                            texture: None,
-                            normal: None,
-                        },
-                    ],
+                        }
+                    })),
                 },
             )
             .or(Err(Error::OBJ))?;
     }
+    report_progress(progress, num_triangles, num_triangles)?;
+
+    Ok(())
+}
+
+/// Report the progress of converting triangles, every once in a while
+///
+/// Reporting every single triangle would slow down the export of large
+/// meshes, for no benefit.
+fn report_progress(
+    progress: &mut dyn ProgressSink,
+    done: usize,
+    total: usize,
+) -> Result<(), Cancelled> {
+    if done % PROGRESS_INTERVAL == 0 || done == total {
+        progress.step("export", done, total)?;
+    }
+
+    Ok(())
+}
+
+/// The number of triangles between two progress reports
+const PROGRESS_INTERVAL: usize = 1024;
+
+/// Export the provided drawing to the file at the given path
+///
+/// This function will create a file if it does not exist, and will truncate it
+/// if it does.
+///
+/// SVG and DXF files are supported. The case insensitive file extension of the
+/// provided path is used to switch between them. The drawing is assumed to be
+/// measured in millimeters.
+pub fn export_drawing(
+    drawing: &Drawing,
+    hidden_lines: HiddenLines,
+    path: &Path,
+) -> Result<(), Error> {
+    match path.extension() {
+        Some(extension) if extension.to_ascii_uppercase() == "SVG" => {
+            let mut file = File::create(path)?;
+            export_svg(drawing, hidden_lines, &mut file)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "DXF" => {
+            let mut file = File::create(path)?;
+            export_dxf(drawing, hidden_lines, &mut file)
+        }
+        Some(extension) => Err(Error::InvalidExtension(
+            extension.to_string_lossy().into_owned(),
+        )),
+        None => Err(Error::NoExtension),
+    }
+}
+
+/// Export the provided drawing to the provided writer in the SVG format.
+///
+/// SVG's y-axis points down, so the drawing is flipped vertically. The size of
+/// the image is set in millimeters, so it prints at a scale of 1:1.
+pub fn export_svg(
+    drawing: &Drawing,
+    hidden_lines: HiddenLines,
+    mut write: impl Write,
+) -> Result<(), Error> {
+    const MARGIN: f64 = 5.;
+
+    let annotations = drawing
+        .annotations
+        .iter()
+        .map(AnnotationGraphics::new)
+        .collect::<Vec<_>>();
+
+    let points = lines(drawing, hidden_lines)
+        .flat_map(|(segment, _)| segment.points())
+        .chain(annotations.iter().flat_map(AnnotationGraphics::extent))
+        .collect::<Vec<_>>();
+    let Aabb { min, max } = if points.is_empty() {
+        Aabb::default()
+    } else {
+        Aabb::<2>::from_points(points)
+    };
+    let [x, y] = [min.u, -max.v].map(|s| s.into_f64() - MARGIN);
+    let [width, height] =
+        [max.u - min.u, max.v - min.v].map(|s| s.into_f64() + 2. * MARGIN);
+
+    writeln!(
+        write,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+        width=\"{width}mm\" height=\"{height}mm\" \
+        viewBox=\"{x} {y} {width} {height}\">"
+    )?;
+    writeln!(
+        write,
+        "<g fill=\"none\" stroke=\"black\" stroke-linecap=\"round\">"
+    )?;
+
+    for (segment, kind) in lines(drawing, hidden_lines) {
+        let [[x1, y1], [x2, y2]] = segment
+            .points()
+            .map(|point| [point.u, -point.v].map(Scalar::into_f64));
+        let style = match kind {
+            LineKind::Visible => "stroke-width=\"0.5\"",
+            LineKind::Hidden => {
+                "stroke-width=\"0.35\" stroke-dasharray=\"3 1.5\""
+            }
+        };
+
+        writeln!(
+            write,
+            "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" {style}/>"
+        )?;
+    }
+
+    for annotation in &annotations {
+        for [a, b] in &annotation.lines {
+            let [[x1, y1], [x2, y2]] =
+                [a, b].map(|point| [point.u, -point.v].map(Scalar::into_f64));
+
+            writeln!(
+                write,
+                "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" \
+                stroke-width=\"0.25\"/>"
+            )?;
+        }
+    }
+
+    writeln!(write, "</g>")?;
+
+    writeln!(
+        write,
+        "<g font-family=\"sans-serif\" font-size=\"{TEXT_HEIGHT}\">"
+    )?;
+    for text in annotations.iter().flat_map(|annotation| &annotation.texts) {
+        let [x, y] = [text.position.u, -text.position.v].map(Scalar::into_f64);
+        let angle = -text.angle.into_f64().to_degrees();
+        let anchor = if text.is_centered { "middle" } else { "start" };
+
+        let content = text
+            .content
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+
+        writeln!(
+            write,
+            "<text x=\"{x}\" y=\"{y}\" text-anchor=\"{anchor}\" \
+            transform=\"rotate({angle} {x} {y})\">{content}</text>"
+        )?;
+    }
+    writeln!(write, "</g>")?;
+
+    writeln!(write, "</svg>")?;
+
+    Ok(())
+}
+
+/// Export the provided drawing to the provided writer in the DXF format.
+///
+/// Writes an ASCII DXF file in the R12 format, which is the most widely
+/// supported one. Visible and hidden lines are placed on separate layers, with
+/// hidden lines using a dashed line type. Annotations are made up of lines and
+/// text on a third layer.
+pub fn export_dxf(
+    drawing: &Drawing,
+    hidden_lines: HiddenLines,
+    mut write: impl Write,
+) -> Result<(), Error> {
+    // DXF files consist of pairs of a group code and a value, each on its own
+    // line.
+    let mut pair = |code: u32, value: &dyn std::fmt::Display| {
+        writeln!(write, "{code}\n{value}")
+    };
+
+    pair(0, &"SECTION")?;
+    pair(2, &"TABLES")?;
+
+    pair(0, &"TABLE")?;
+    pair(2, &"LTYPE")?;
+    pair(70, &2)?;
+    for (name, description, pattern) in [
+        ("CONTINUOUS", "Solid line", &[][..]),
+        ("HIDDEN", "Hidden __ __ __", &[3., -1.5][..]),
+    ] {
+        pair(0, &"LTYPE")?;
+        pair(2, &name)?;
+        pair(70, &0)?;
+        pair(3, &description)?;
+        pair(72, &65)?;
+        pair(73, &pattern.len())?;
+        pair(
+            40,
+            &pattern.iter().map(|length: &f64| length.abs()).sum::<f64>(),
+        )?;
+        for length in pattern {
+            pair(49, length)?;
+        }
+    }
+    pair(0, &"ENDTAB")?;
+
+    pair(0, &"TABLE")?;
+    pair(2, &"LAYER")?;
+    pair(70, &3)?;
+    for (layer, line_type) in [
+        ("VISIBLE", "CONTINUOUS"),
+        ("HIDDEN", "HIDDEN"),
+        ("ANNOTATIONS", "CONTINUOUS"),
+    ] {
+        pair(0, &"LAYER")?;
+        pair(2, &layer)?;
+        pair(70, &0)?;
+        pair(62, &7)?;
+        pair(6, &line_type)?;
+    }
+    pair(0, &"ENDTAB")?;
+
+    pair(0, &"ENDSEC")?;
+
+    pair(0, &"SECTION")?;
+    pair(2, &"ENTITIES")?;
+    let annotations = drawing
+        .annotations
+        .iter()
+        .map(AnnotationGraphics::new)
+        .collect::<Vec<_>>();
+
+    let drawing_lines = lines(drawing, hidden_lines).map(|(segment, kind)| {
+        let layer = match kind {
+            LineKind::Visible => "VISIBLE",
+            LineKind::Hidden => "HIDDEN",
+        };
+        (segment.points(), layer)
+    });
+    let annotation_lines = annotations.iter().flat_map(|annotation| {
+        annotation.lines.iter().map(|&line| (line, "ANNOTATIONS"))
+    });
+
+    for ([a, b], layer) in drawing_lines.chain(annotation_lines) {
+        pair(0, &"LINE")?;
+        pair(8, &layer)?;
+        pair(10, &a.u.into_f64())?;
+        pair(20, &a.v.into_f64())?;
+        pair(30, &0.)?;
+        pair(11, &b.u.into_f64())?;
+        pair(21, &b.v.into_f64())?;
+        pair(31, &0.)?;
+    }
+
+    for text in annotations.iter().flat_map(|annotation| &annotation.texts) {
+        let [x, y] = [text.position.u, text.position.v].map(Scalar::into_f64);
+
+        pair(0, &"TEXT")?;
+        pair(8, &"ANNOTATIONS")?;
+        pair(10, &x)?;
+        pair(20, &y)?;
+        pair(30, &0.)?;
+        pair(40, &TEXT_HEIGHT)?;
+        pair(1, &text.content)?;
+        pair(50, &text.angle.into_f64().to_degrees())?;
+        if text.is_centered {
+            // Centered text is positioned using the alignment point.
+            pair(72, &1)?;
+            pair(11, &x)?;
+            pair(21, &y)?;
+            pair(31, &0.)?;
+        }
+    }
+
+    pair(0, &"ENDSEC")?;
+
+    pair(0, &"EOF")?;
 
     Ok(())
 }
 
+/// How hidden lines are presented, when exporting a drawing
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum HiddenLines {
+    /// Hidden lines are drawn dashed
+    Dashed,
+
+    /// Hidden lines are left out
+    Removed,
+}
+
+/// The height of annotation text in exported drawings, in millimeters
+const TEXT_HEIGHT: f64 = 3.5;
+
+/// The lines and text that make up an annotation, in an exported drawing
+struct AnnotationGraphics {
+    lines: Vec<[Point<2>; 2]>,
+    texts: Vec<AnnotationText>,
+}
+
+impl AnnotationGraphics {
+    fn new(annotation: &Annotation) -> Self {
+        const ARROW_LENGTH: f64 = 3.;
+        const ARROW_WIDTH: f64 = 1.;
+        const EXTENSION: f64 = 1.5;
+
+        let arrow = |tip: Point<2>, direction: Vector<2>| {
+            let back = tip - direction * ARROW_LENGTH;
+            let side = perpendicular(direction) * (ARROW_WIDTH / 2.);
+            [[tip, back + side], [tip, back - side]]
+        };
+
+        let mut lines = Vec::new();
+        let mut texts = Vec::new();
+
+        match annotation {
+            Annotation::Linear {
+                from,
+                to,
+                offset,
+                value,
+            } => {
+                let direction = direction_from(*from, *to);
+                let normal = perpendicular(direction);
+                let [a, b] = [*from, *to].map(|point| point + normal * *offset);
+
+                // The extension lines go from the measured points to slightly
+                // beyond the dimension line.
+                let extension = if *offset < Scalar::ZERO {
+                    normal * -EXTENSION
+                } else {
+                    normal * EXTENSION
+                };
+                lines.push([*from, a + extension]);
+                lines.push([*to, b + extension]);
+
+                lines.push([a, b]);
+                lines.extend(arrow(a, -direction));
+                lines.extend(arrow(b, direction));
+
+                texts.push(AnnotationText::along(a, b, format_value(*value)));
+            }
+            Annotation::Radial {
+                center,
+                point,
+                value,
+            } => {
+                lines.push([*center, *point]);
+                lines.extend(arrow(*point, direction_from(*center, *point)));
+
+                texts.push(AnnotationText::along(
+                    *center,
+                    *point,
+                    format!("R{}", format_value(*value)),
+                ));
+            }
+            Annotation::Note { position, text } => {
+                texts.push(AnnotationText {
+                    position: *position,
+                    angle: Scalar::ZERO,
+                    content: text.clone(),
+                    is_centered: false,
+                });
+            }
+        }
+
+        Self { lines, texts }
+    }
+
+    /// Approximate the area covered by the annotation
+    fn extent(&self) -> impl Iterator<Item = Point<2>> + '_ {
+        let texts = self.texts.iter().flat_map(|text| {
+            // Assume that the average character is a bit more than half as
+            // wide as it is high.
+            let width = text.content.chars().count() as f64 * TEXT_HEIGHT * 0.6;
+
+            let (sin, cos) = text.angle.sin_cos();
+            let along = Vector::from([cos, sin]);
+            let up = perpendicular(along) * TEXT_HEIGHT;
+
+            let start = if text.is_centered {
+                text.position - along * (width / 2.)
+            } else {
+                text.position
+            };
+            let end = start + along * width;
+
+            [start, end, start + up, end + up]
+        });
+
+        self.lines.iter().flatten().copied().chain(texts)
+    }
+}
+
+/// Text that is part of an annotation
+struct AnnotationText {
+    /// The position of the text
+    ///
+    /// This is the left end of the text's baseline, or the center of the
+    /// baseline, if the text is centered.
+    position: Point<2>,
+
+    /// The angle of the baseline, counter-clockwise from the x-axis in radians
+    angle: Scalar,
+
+    content: String,
+    is_centered: bool,
+}
+
+impl AnnotationText {
+    /// Place text above the middle of a line, in a readable orientation
+    fn along(a: Point<2>, b: Point<2>, content: String) -> Self {
+        const GAP: f64 = 1.;
+
+        let direction = direction_from(a, b);
+        let angle = direction.v.atan2(direction.u);
+
+        // Text that would be upside-down is turned around.
+        let angle = if angle > Scalar::PI / 2. {
+            angle - Scalar::PI
+        } else if angle <= -Scalar::PI / 2. {
+            angle + Scalar::PI
+        } else {
+            angle
+        };
+
+        let (sin, cos) = angle.sin_cos();
+        let up = perpendicular(Vector::from([cos, sin]));
+
+        Self {
+            position: a + (b - a) / 2. + up * GAP,
+            angle,
+            content,
+            is_centered: true,
+        }
+    }
+}
+
+fn direction_from(a: Point<2>, b: Point<2>) -> Vector<2> {
+    let direction = b - a;
+
+    if direction.magnitude() == Scalar::ZERO {
+        Vector::from([1., 0.])
+    } else {
+        direction.normalize()
+    }
+}
+
+fn perpendicular(vector: Vector<2>) -> Vector<2> {
+    Vector::from([-vector.v, vector.u])
+}
+
+fn format_value(value: Scalar) -> String {
+    let value = format!("{:.2}", value.into_f64());
+    value
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn lines(
+    drawing: &Drawing,
+    hidden_lines: HiddenLines,
+) -> impl Iterator<Item = (fj_math::Segment<2>, LineKind)> + '_ {
+    drawing.lines().filter(move |(_, kind)| {
+        *kind == LineKind::Visible || hidden_lines == HiddenLines::Dashed
+    })
+}
+
+fn convert_mesh(
+    mesh: &Mesh<Point<3>>,
+    from: LengthUnit,
+    to: LengthUnit,
+) -> Mesh<Point<3>> {
+    let mut converted = Mesh::new();
+
+    // Adding the materials in the same order keeps their IDs valid.
+    for material in mesh.materials() {
+        converted.add_material(material.clone());
+    }
+
+    converted.extend(mesh.triangles().map(|mut triangle| {
+        triangle.inner = triangle
+            .inner
+            .points()
+            .map(|point| from.convert_point(point, to))
+            .into();
+        triangle
+    }));
+
+    converted
+}
+
 /// An error that can occur while exporting
 #[derive(Debug, Error)]
 pub enum Error {
@@ -206,4 +745,8 @@ pub enum Error {
     /// OBJ exporter error whilst exporting to OBJ file
     #[error("obj error whilst exporting to OBJ file")]
     OBJ,
+
+    /// The export was cancelled
+    #[error("export was cancelled")]
+    Cancelled(#[from] Cancelled),
 }
@@ -0,0 +1,128 @@
+use std::{fmt, path::Path};
+
+/// A file format that geometry can be imported from
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Format {
+    /// STL, in its ASCII or binary variant
+    Stl,
+
+    /// Wavefront OBJ
+    Obj,
+
+    /// 3D Manufacturing Format
+    ThreeMf,
+
+    /// STEP (ISO 10303-21)
+    Step,
+}
+
+impl Format {
+    /// Detect the format of a file from its path and contents
+    ///
+    /// The contents take precedence, as file extensions are not always
+    /// reliable. The extension is only used, if the contents don't start with
+    /// any recognized magic bytes.
+    pub fn detect(path: &Path, bytes: &[u8]) -> Option<Self> {
+        Self::from_magic_bytes(bytes).or_else(|| Self::from_extension(path))
+    }
+
+    /// Detect the format of a file from the extension of its path
+    ///
+    /// The extension is matched case-insensitively.
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_ascii_uppercase();
+
+        match extension.to_str()? {
+            "STL" => Some(Self::Stl),
+            "OBJ" => Some(Self::Obj),
+            "3MF" => Some(Self::ThreeMf),
+            "STEP" | "STP" => Some(Self::Step),
+            _ => None,
+        }
+    }
+
+    /// Detect the format of a file from the bytes it starts with
+    ///
+    /// OBJ files have no magic bytes and are never detected by this method.
+    /// Binary STL files have no magic bytes either, but their size is fully
+    /// determined by the triangle count in their header, which is checked
+    /// instead.
+    pub fn from_magic_bytes(bytes: &[u8]) -> Option<Self> {
+        // 3MF files are ZIP archives.
+        if bytes.starts_with(b"PK\x03\x04") {
+            return Some(Self::ThreeMf);
+        }
+        if bytes.starts_with(b"ISO-10303-21;") {
+            return Some(Self::Step);
+        }
+
+        // Binary STL files are free to start with "solid" too, but unlike
+        // ASCII STL files, they are very unlikely to consist of ASCII only.
+        let text_start = bytes
+            .iter()
+            .position(|byte| !byte.is_ascii_whitespace())
+            .unwrap_or(bytes.len());
+        if bytes[text_start..].starts_with(b"solid") && bytes.is_ascii() {
+            return Some(Self::Stl);
+        }
+        if let Some(num_triangles) = bytes
+            .get(80..84)
+            .and_then(|count| count.try_into().ok())
+            .map(u32::from_le_bytes)
+        {
+            if bytes.len() as u64 == 84 + 50 * u64::from(num_triangles) {
+                return Some(Self::Stl);
+            }
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Stl => "STL",
+            Self::Obj => "OBJ",
+            Self::ThreeMf => "3MF",
+            Self::Step => "STEP",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::Format;
+
+    #[test]
+    fn detect() {
+        let binary_stl = {
+            let mut bytes = b"solid, but actually binary".to_vec();
+            bytes.resize(80, 0);
+            bytes.extend(1u32.to_le_bytes());
+            bytes.extend([0xff; 50]);
+            bytes
+        };
+
+        for (path, bytes, format) in [
+            (
+                "model.stl",
+                &b"solid model\nendsolid model\n"[..],
+                Format::Stl,
+            ),
+            ("model.bin", &binary_stl, Format::Stl),
+            ("model.STP", b"", Format::Step),
+            ("model.stl", b"ISO-10303-21;\nHEADER;", Format::Step),
+            ("model.3mf", b"PK\x03\x04", Format::ThreeMf),
+            ("model.Obj", b"v 0 0 0\n", Format::Obj),
+        ] {
+            assert_eq!(Format::detect(Path::new(path), bytes), Some(format));
+        }
+
+        assert_eq!(Format::detect(Path::new("model.txt"), b"v 0 0 0\n"), None);
+    }
+}
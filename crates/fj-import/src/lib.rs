@@ -0,0 +1,209 @@
+//! # Fornjot Importer
+//!
+//! [Fornjot] is an early-stage b-rep CAD kernel written in Rust. The kernel is
+//! split into multiple libraries that can be used semi-independently, and this
+//! is one of those.
+//!
+//! This library imports geometry from external file formats, for example to
+//! use it as reference geometry within a model. It is the counterpart to
+//! [`fj-export`].
+//!
+//! [Fornjot]: https://www.fornjot.app/
+//! [`fj-export`]: https://crates.io/crates/fj-export
+
+mod format;
+mod obj;
+mod stl;
+
+use std::{fs, io, path::Path};
+
+use thiserror::Error;
+
+use fj_core::{
+    operations::{
+        build::{BuildShell, BuildSolid},
+        insert::Insert,
+        update::UpdateSolid,
+    },
+    topology::{Shell, Solid},
+    Core,
+};
+use fj_interop::Mesh;
+use fj_math::Point;
+
+pub use self::{format::Format, obj::ObjImporter, stl::StlImporter};
+
+/// Import the file at the provided path
+///
+/// The format of the file is detected using [`Format::detect`]. STL and OBJ
+/// files are supported. Use [`Importers`], to support more formats.
+pub fn import(path: &Path, core: &mut Core) -> Result<Imported, Error> {
+    Importers::default().import(path, core)
+}
+
+/// An importer for a specific file format
+///
+/// Implement this trait, to add support for a format that this crate doesn't
+/// support out of the box, and register the implementation with
+/// [`Importers`].
+pub trait Import {
+    /// The format that this importer reads
+    fn format(&self) -> Format;
+
+    /// Import the provided file contents
+    ///
+    /// Importers of mesh-based formats ignore `core`. Importers that create
+    /// topology, like those for b-rep formats, use it to insert the objects
+    /// they create.
+    fn import(&self, bytes: &[u8], core: &mut Core) -> Result<Imported, Error>;
+}
+
+/// A set of importers, one per format
+///
+/// The [`Default`] implementation contains the importers of all formats that
+/// this crate supports out of the box.
+pub struct Importers {
+    importers: Vec<Box<dyn Import>>,
+}
+
+impl Importers {
+    /// Create an empty set of importers
+    pub fn new() -> Self {
+        Self {
+            importers: Vec::new(),
+        }
+    }
+
+    /// Register an importer
+    ///
+    /// Replaces any importer that was previously registered for the same
+    /// format.
+    pub fn register(&mut self, importer: impl Import + 'static) {
+        self.importers
+            .retain(|existing| existing.format() != importer.format());
+        self.importers.push(Box::new(importer));
+    }
+
+    /// Access the formats that can be imported
+    pub fn formats(&self) -> impl Iterator<Item = Format> + '_ {
+        self.importers.iter().map(|importer| importer.format())
+    }
+
+    /// Import the file at the provided path
+    ///
+    /// The format of the file is detected using [`Format::detect`].
+    pub fn import(
+        &self,
+        path: &Path,
+        core: &mut Core,
+    ) -> Result<Imported, Error> {
+        let bytes = fs::read(path)?;
+        let format =
+            Format::detect(path, &bytes).ok_or(Error::UnrecognizedFormat)?;
+
+        self.import_bytes(&bytes, format, core)
+    }
+
+    /// Import file contents of the provided format
+    pub fn import_bytes(
+        &self,
+        bytes: &[u8],
+        format: Format,
+        core: &mut Core,
+    ) -> Result<Imported, Error> {
+        let importer = self
+            .importers
+            .iter()
+            .find(|importer| importer.format() == format)
+            .ok_or(Error::UnsupportedFormat(format))?;
+
+        importer.import(bytes, core)
+    }
+}
+
+impl Default for Importers {
+    fn default() -> Self {
+        let mut importers = Self::new();
+        importers.register(StlImporter);
+        importers.register(ObjImporter);
+        importers
+    }
+}
+
+/// The result of an import
+///
+/// Depending on the format, an import results either in a triangle mesh, or
+/// in topology.
+#[derive(Clone, Debug)]
+pub enum Imported {
+    /// A triangle mesh, as imported from mesh-based formats like STL or OBJ
+    Mesh(Mesh<Point<3>>),
+
+    /// A solid, as imported from b-rep formats like STEP
+    Solid(Solid),
+}
+
+impl Imported {
+    /// Convert the imported geometry into a solid
+    ///
+    /// A mesh is converted into a single shell with one triangular face per
+    /// triangle. This only results in a valid solid, if the mesh is closed
+    /// and its triangles share their vertices.
+    pub fn into_solid(self, core: &mut Core) -> Solid {
+        match self {
+            Self::Mesh(mesh) => {
+                let shell = Shell::from_mesh(&mesh, core).insert(core);
+                Solid::empty().add_shells([shell], core)
+            }
+            Self::Solid(solid) => solid,
+        }
+    }
+}
+
+/// An error that can occur while importing
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The format of the file could not be detected
+    #[error("format of file could not be detected")]
+    UnrecognizedFormat,
+
+    /// No importer is available for the format of the file
+    #[error("no importer available for {0} files")]
+    UnsupportedFormat(Format),
+
+    /// The file is not valid for its format
+    #[error("malformed {format} file: {message}")]
+    Malformed {
+        /// The format that the file was read as
+        format: Format,
+
+        /// A description of the problem
+        message: String,
+    },
+
+    /// I/O error whilst importing from file
+    #[error("I/O error whilst importing from file")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_core::Core;
+
+    use crate::{Error, Format, Importers};
+
+    #[test]
+    fn import_bytes_requires_importer() {
+        let mut core = Core::new();
+
+        let result = Importers::default().import_bytes(
+            b"ISO-10303-21;",
+            Format::Step,
+            &mut core,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedFormat(Format::Step))
+        ));
+    }
+}
@@ -0,0 +1,144 @@
+use fj_core::Core;
+use fj_interop::{Color, Mesh};
+use fj_math::{Point, Triangle};
+
+use crate::{Error, Format, Import, Imported};
+
+/// Importer for Wavefront OBJ files
+///
+/// Only vertices and faces are read; texture coordinates, normals, groups,
+/// and materials are ignored. Faces with more than three vertices are split
+/// into triangle fans, which is correct for convex faces. Degenerate triangles
+/// are skipped.
+pub struct ObjImporter;
+
+impl Import for ObjImporter {
+    fn format(&self) -> Format {
+        Format::Obj
+    }
+
+    fn import(&self, bytes: &[u8], _: &mut Core) -> Result<Imported, Error> {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| malformed("file is not valid UTF-8"))?;
+
+        let mut vertices = Vec::new();
+        let mut mesh = Mesh::new();
+
+        for line in text.lines() {
+            let mut tokens = line.split_whitespace();
+
+            match tokens.next() {
+                Some("v") => {
+                    let mut coords = [0.; 3];
+                    for coord in &mut coords {
+                        *coord = tokens
+                            .next()
+                            .and_then(|token| token.parse().ok())
+                            .ok_or_else(|| malformed("invalid vertex"))?;
+                    }
+                    vertices.push(Point::from(coords));
+                }
+                Some("f") => {
+                    let face = tokens
+                        .map(|token| vertex(token, &vertices))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    if face.len() < 3 {
+                        return Err(malformed(
+                            "face with less than 3 vertices",
+                        ));
+                    }
+
+                    for pair in face[1..].windows(2) {
+                        if let Ok(triangle) =
+                            Triangle::from_points([face[0], pair[0], pair[1]])
+                        {
+                            mesh.push_triangle(triangle, Color::default());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Imported::Mesh(mesh))
+    }
+}
+
+/// Resolve a vertex reference of a face
+///
+/// References can be of the form `v`, `v/vt`, `v//vn`, or `v/vt/vn`, of which
+/// only the vertex index `v` is relevant here. Indices start at 1; negative
+/// indices count backwards from the most recently defined vertex.
+fn vertex(token: &str, vertices: &[Point<3>]) -> Result<Point<3>, Error> {
+    let index = token
+        .split('/')
+        .next()
+        .and_then(|index| index.parse::<i64>().ok())
+        .ok_or_else(|| malformed("invalid face"))?;
+
+    let index = if index < 0 {
+        vertices.len() as i64 + index
+    } else {
+        index - 1
+    };
+
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| vertices.get(index).copied())
+        .ok_or_else(|| malformed("face refers to undefined vertex"))
+}
+
+fn malformed(message: &str) -> Error {
+    Error::Malformed {
+        format: Format::Obj,
+        message: message.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_core::Core;
+
+    use crate::{Error, Format, Imported, Importers};
+
+    #[test]
+    fn quad() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let obj = b"# a square
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vn 0 0 1
+f 1//1 2//1 -2//1 -1//1
+";
+        let Imported::Mesh(mesh) =
+            Importers::default().import_bytes(obj, Format::Obj, &mut core)?
+        else {
+            panic!("Expected OBJ import to result in mesh");
+        };
+
+        assert_eq!(mesh.num_triangles(), 2);
+        assert!(mesh.contains_triangle([
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [1., 1., 0.]
+        ]));
+        assert!(mesh.contains_triangle([
+            [0., 0., 0.],
+            [1., 1., 0.],
+            [0., 1., 0.]
+        ]));
+
+        let result = Importers::default().import_bytes(
+            b"v 0 0 0\nf 1 2 3\n",
+            Format::Obj,
+            &mut core,
+        );
+        assert!(matches!(result, Err(Error::Malformed { .. })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use fj_core::Core;
+use fj_interop::{Color, Mesh};
+use fj_math::{Point, Triangle};
+
+use crate::{Error, Format, Import, Imported};
+
+/// Importer for STL files, in their ASCII or binary variant
+///
+/// Triangles that are degenerate, which is not unusual for STL files, are
+/// skipped. The normals stored in the file are ignored; the winding of the
+/// triangles defines their orientation.
+pub struct StlImporter;
+
+impl Import for StlImporter {
+    fn format(&self) -> Format {
+        Format::Stl
+    }
+
+    fn import(&self, bytes: &[u8], _: &mut Core) -> Result<Imported, Error> {
+        let triangles = match std::str::from_utf8(bytes) {
+            Ok(text) if text.trim_start().starts_with("solid") => {
+                read_ascii(text)?
+            }
+            _ => read_binary(bytes)?,
+        };
+
+        let mut mesh = Mesh::new();
+        for points in triangles {
+            if let Ok(triangle) = Triangle::from_points(points) {
+                mesh.push_triangle(triangle, Color::default());
+            }
+        }
+
+        Ok(Imported::Mesh(mesh))
+    }
+}
+
+fn read_ascii(text: &str) -> Result<Vec<[Point<3>; 3]>, Error> {
+    let mut triangles = Vec::new();
+    let mut points = Vec::new();
+
+    let mut tokens = text.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            "vertex" => {
+                let mut coords = [0.; 3];
+                for coord in &mut coords {
+                    *coord = tokens
+                        .next()
+                        .and_then(|token| token.parse().ok())
+                        .ok_or_else(|| malformed("invalid vertex"))?;
+                }
+                points.push(Point::from(coords));
+            }
+            "endfacet" => {
+                let triangle = std::mem::take(&mut points)
+                    .try_into()
+                    .map_err(|_| malformed("facet without 3 vertices"))?;
+                triangles.push(triangle);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(triangles)
+}
+
+fn read_binary(bytes: &[u8]) -> Result<Vec<[Point<3>; 3]>, Error> {
+    const HEADER_SIZE: usize = 84;
+    const TRIANGLE_SIZE: usize = 50;
+
+    let num_triangles = bytes
+        .get(80..HEADER_SIZE)
+        .and_then(|count| count.try_into().ok())
+        .map(u32::from_le_bytes)
+        .ok_or_else(|| malformed("missing header"))?;
+    let data = &bytes[HEADER_SIZE..];
+
+    if data.len() as u64 != num_triangles as u64 * TRIANGLE_SIZE as u64 {
+        return Err(malformed("size doesn't match triangle count"));
+    }
+
+    let triangles = data
+        .chunks_exact(TRIANGLE_SIZE)
+        .map(|triangle| {
+            // Each triangle starts with its normal, followed by its vertices,
+            // and ends with an attribute byte count.
+            let mut floats = triangle[..48].chunks_exact(4).map(|float| {
+                let float = float.try_into().expect("Chunk has 4 bytes");
+                f64::from(f32::from_le_bytes(float))
+            });
+            floats.by_ref().take(3).for_each(drop);
+
+            [(); 3].map(|()| {
+                Point::from([(); 3].map(|()| {
+                    floats.next().expect("Triangle has 9 coordinates")
+                }))
+            })
+        })
+        .collect();
+
+    Ok(triangles)
+}
+
+fn malformed(message: &str) -> Error {
+    Error::Malformed {
+        format: Format::Stl,
+        message: message.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_core::Core;
+
+    use crate::{Format, Imported, Importers};
+
+    #[test]
+    fn ascii_and_binary() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let ascii = b"solid triangle
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid triangle
+";
+        let binary = {
+            let mut bytes = vec![0; 80];
+            bytes.extend(1u32.to_le_bytes());
+            for coord in [0., 0., 1., 0., 0., 0., 1., 0., 0., 0., 1., 0.] {
+                bytes.extend(f32::to_le_bytes(coord));
+            }
+            bytes.extend([0; 2]);
+            bytes
+        };
+
+        for bytes in [&ascii[..], &binary] {
+            let Imported::Mesh(mesh) = Importers::default().import_bytes(
+                bytes,
+                Format::Stl,
+                &mut core,
+            )?
+            else {
+                panic!("Expected STL import to result in mesh");
+            };
+
+            assert_eq!(mesh.num_triangles(), 1);
+            assert!(mesh.contains_triangle([
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [0., 1., 0.]
+            ]));
+        }
+
+        Ok(())
+    }
+}
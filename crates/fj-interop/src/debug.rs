@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use fj_math::Point;
+
+/// Geometry that helps with debugging, but is not part of the model
+///
+/// Algorithms and models can emit debug primitives, to visualize intermediate
+/// results. Primitives are sorted into named channels, which the viewer can
+/// show and hide independently of each other.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DebugGeometry {
+    /// The channels, each with the primitives that were emitted into it
+    pub channels: BTreeMap<String, Vec<DebugPrimitive>>,
+}
+
+impl DebugGeometry {
+    /// Create empty debug geometry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a primitive to a channel
+    ///
+    /// Creates the channel, if it doesn't exist yet.
+    pub fn push(
+        &mut self,
+        channel: impl Into<String>,
+        primitive: DebugPrimitive,
+    ) {
+        self.channels
+            .entry(channel.into())
+            .or_default()
+            .push(primitive);
+    }
+
+    /// Indicate whether there are no primitives in any channel
+    pub fn is_empty(&self) -> bool {
+        self.channels.values().all(Vec::is_empty)
+    }
+
+    /// Access the names of all channels
+    pub fn channel_names(&self) -> impl Iterator<Item = &str> {
+        self.channels.keys().map(String::as_str)
+    }
+}
+
+/// A primitive that can be emitted as [`DebugGeometry`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DebugPrimitive {
+    /// A point
+    Point(Point<3>),
+
+    /// A line segment between two points
+    Segment([Point<3>; 2]),
+
+    /// An arrow that points from one point to another
+    Arrow {
+        /// The point that the arrow starts at
+        from: Point<3>,
+
+        /// The point that the arrow points to
+        to: Point<3>,
+    },
+
+    /// Text that is displayed next to a point
+    Text {
+        /// The point that the text refers to
+        at: Point<3>,
+
+        /// The text
+        text: String,
+    },
+}
@@ -0,0 +1,164 @@
+use fj_math::{Aabb, Point, Scalar, Segment, Vector};
+
+/// A 2D line drawing, like an orthographic view of a model
+///
+/// Lines are sorted by whether they are visible or hidden behind other parts of
+/// the model. It is up to whoever presents the drawing, to decide how hidden
+/// lines are shown, if at all.
+///
+/// In addition to lines, a drawing can contain annotations, like dimensions.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Drawing {
+    /// The lines that are visible
+    pub visible: Vec<Segment<2>>,
+
+    /// The lines that are hidden behind other parts of the model
+    pub hidden: Vec<Segment<2>>,
+
+    /// The annotations of the drawing
+    pub annotations: Vec<Annotation>,
+}
+
+impl Drawing {
+    /// Construct an empty drawing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterate over all lines, visible ones first
+    pub fn lines(&self) -> impl Iterator<Item = (Segment<2>, LineKind)> + '_ {
+        let visible = self
+            .visible
+            .iter()
+            .map(|&segment| (segment, LineKind::Visible));
+        let hidden = self
+            .hidden
+            .iter()
+            .map(|&segment| (segment, LineKind::Hidden));
+
+        visible.chain(hidden)
+    }
+
+    /// Compute the bounding box of all lines and annotations
+    ///
+    /// Only the points that annotations refer to are considered, not the space
+    /// that their presentation might take up.
+    ///
+    /// Returns `None`, if the drawing is empty.
+    pub fn aabb(&self) -> Option<Aabb<2>> {
+        let mut points = self
+            .lines()
+            .flat_map(|(segment, _)| segment.points())
+            .chain(self.annotations.iter().flat_map(Annotation::points))
+            .peekable();
+        points.peek()?;
+
+        Some(Aabb::<2>::from_points(points))
+    }
+
+    /// Move all lines and annotations by the provided offset
+    pub fn translate(mut self, offset: impl Into<Vector<2>>) -> Self {
+        let offset = offset.into();
+        let translate = |segment: &mut Segment<2>| {
+            *segment = Segment::from_points(
+                segment.points().map(|point: Point<2>| point + offset),
+            );
+        };
+
+        self.visible.iter_mut().for_each(translate);
+        self.hidden.iter_mut().for_each(translate);
+
+        for annotation in &mut self.annotations {
+            match annotation {
+                Annotation::Linear { from, to, .. } => {
+                    *from += offset;
+                    *to += offset;
+                }
+                Annotation::Radial { center, point, .. } => {
+                    *center += offset;
+                    *point += offset;
+                }
+                Annotation::Note { position, .. } => {
+                    *position += offset;
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Add all lines and annotations of another drawing to this one
+    pub fn merge(mut self, other: Self) -> Self {
+        self.visible.extend(other.visible);
+        self.hidden.extend(other.hidden);
+        self.annotations.extend(other.annotations);
+        self
+    }
+}
+
+/// An annotation in a [`Drawing`]
+///
+/// The values of dimensions are measured in the model, not in the drawing, so
+/// they stay correct, regardless of how the drawing is scaled.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Annotation {
+    /// The distance between two points
+    Linear {
+        /// The point that the distance is measured from
+        from: Point<2>,
+
+        /// The point that the distance is measured to
+        to: Point<2>,
+
+        /// The distance of the dimension line from the measured points
+        ///
+        /// Positive values place the dimension line to the left of the
+        /// direction from `from` to `to`, negative ones to the right.
+        offset: Scalar,
+
+        /// The measured distance
+        value: Scalar,
+    },
+
+    /// The radius of a circle or arc
+    Radial {
+        /// The center of the circle
+        center: Point<2>,
+
+        /// The point on the circle that the dimension points to
+        point: Point<2>,
+
+        /// The measured radius
+        value: Scalar,
+    },
+
+    /// A text note
+    Note {
+        /// The point that the note is attached to
+        position: Point<2>,
+
+        /// The text of the note
+        text: String,
+    },
+}
+
+impl Annotation {
+    /// Access the points that the annotation refers to
+    pub fn points(&self) -> Vec<Point<2>> {
+        match self {
+            Self::Linear { from, to, .. } => vec![*from, *to],
+            Self::Radial { center, point, .. } => vec![*center, *point],
+            Self::Note { position, .. } => vec![*position],
+        }
+    }
+}
+
+/// The kind of a line in a [`Drawing`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum LineKind {
+    /// The line is visible
+    Visible,
+
+    /// The line is hidden behind other parts of the model
+    Hidden,
+}
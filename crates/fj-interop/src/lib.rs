@@ -10,13 +10,23 @@
 //! [Fornjot]: https://www.fornjot.app/
 
 mod color;
+mod debug;
+mod drawing;
+mod material;
 mod mesh;
 mod model;
+mod progress;
 
 pub mod ext;
 
 pub use self::{
     color::Color,
-    mesh::{Index, Mesh, Triangle},
-    model::Model,
+    debug::{DebugGeometry, DebugPrimitive},
+    drawing::{Annotation, Drawing, LineKind},
+    material::{Material, MaterialId},
+    mesh::{F32Mesh, FaceId, Index, Mesh, Triangle},
+    model::{FailedFace, LevelOfDetail, Model},
+    progress::{
+        CancellationToken, Cancelled, NoProgress, Progress, ProgressSink,
+    },
 };
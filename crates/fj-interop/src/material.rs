@@ -0,0 +1,60 @@
+/// A material that an object is made of
+///
+/// Describes both how the material looks, using the metallic-roughness model
+/// common to physically based renderers and to formats like glTF, and its
+/// physical properties.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Material {
+    /// The name of the material
+    pub name: String,
+
+    /// How rough the surface is, from 0 (smooth) to 1 (rough)
+    pub roughness: f64,
+
+    /// How metallic the surface is, from 0 (dielectric) to 1 (metal)
+    pub metallic: f64,
+
+    /// The density of the material, if known
+    ///
+    /// This is mass per volume, measured in whatever units the model uses.
+    pub density: Option<f64>,
+}
+
+impl Material {
+    /// Create a material with the provided name
+    ///
+    /// The material is fully rough and not metallic, and its density is
+    /// unknown.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            roughness: 1.,
+            metallic: 0.,
+            density: None,
+        }
+    }
+
+    /// Use the provided roughness and metallic factors
+    #[must_use]
+    pub fn with_surface(mut self, roughness: f64, metallic: f64) -> Self {
+        self.roughness = roughness;
+        self.metallic = metallic;
+        self
+    }
+
+    /// Use the provided density
+    #[must_use]
+    pub fn with_density(mut self, density: f64) -> Self {
+        self.density = Some(density);
+        self
+    }
+}
+
+/// Identifies a material within a [`Mesh`]
+///
+/// See [`Mesh::add_material`].
+///
+/// [`Mesh`]: crate::Mesh
+/// [`Mesh::add_material`]: crate::Mesh::add_material
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct MaterialId(pub u32);
@@ -1,17 +1,31 @@
 use std::{collections::HashMap, hash::Hash};
 
-use fj_math::Point;
+use fj_math::{Point, Vector};
 
-use crate::Color;
+use crate::{Color, Material, MaterialId};
 
-/// A triangle mesh
+/// An indexed triangle mesh
+///
+/// Each vertex is stored once, in the vertex buffer, no matter how many
+/// triangles share it. Triangles are defined by the index buffer, each three
+/// consecutive indices referring to the vertices of one triangle.
+///
+/// In addition, the mesh has attribute channels, which store one value per
+/// triangle: Its color, the face it was created from, and its material. These
+/// are only populated by the methods that add triangles. A mesh that is built
+/// vertex-by-vertex, using [`Mesh::push_vertex`], has no attributes.
 #[derive(Clone, Debug)]
 pub struct Mesh<V> {
     vertices: Vec<V>,
     indices: Vec<Index>,
 
     indices_by_vertex: HashMap<V, Index>,
-    triangles: Vec<Triangle>,
+
+    colors: Vec<Color>,
+    faces: Vec<Option<FaceId>>,
+    material_ids: Vec<Option<MaterialId>>,
+
+    materials: Vec<Material>,
 }
 
 impl<V> Mesh<V>
@@ -35,6 +49,107 @@ where
         self.indices.push(index);
     }
 
+    /// Access the vertices of the mesh
+    pub fn vertices(&self) -> impl Iterator<Item = V> + '_ {
+        self.vertices.iter().copied()
+    }
+
+    /// Access the indices of the mesh
+    pub fn indices(&self) -> impl Iterator<Item = Index> + '_ {
+        self.indices.iter().copied()
+    }
+
+    /// Access the indices of the mesh, grouped by triangle
+    pub fn triangle_indices(&self) -> impl Iterator<Item = [Index; 3]> + '_ {
+        self.indices
+            .chunks_exact(3)
+            .map(|indices| [indices[0], indices[1], indices[2]])
+    }
+
+    /// Access a vertex, by its index
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the index doesn't refer to a vertex of the mesh.
+    pub fn vertex(&self, index: Index) -> V {
+        self.vertices[index as usize]
+    }
+
+    /// Access the vertices of a triangle, by their indices
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the indices doesn't refer to a vertex of the mesh.
+    pub fn triangle_vertices(&self, indices: [Index; 3]) -> [V; 3] {
+        indices.map(|index| self.vertex(index))
+    }
+
+    /// Access the number of triangles in the mesh
+    pub fn num_triangles(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Access the color channel of the mesh
+    ///
+    /// Yields one color per triangle, in the order of the triangles.
+    pub fn colors(&self) -> impl Iterator<Item = Color> + '_ {
+        self.colors.iter().copied()
+    }
+
+    /// Access the face ID channel of the mesh
+    ///
+    /// Yields one face ID per triangle, in the order of the triangles. See
+    /// [`Mesh::push_triangle_of_face`].
+    pub fn faces(&self) -> impl Iterator<Item = Option<FaceId>> + '_ {
+        self.faces.iter().copied()
+    }
+
+    /// Access the material channel of the mesh
+    ///
+    /// Yields one material ID per triangle, in the order of the triangles. Use
+    /// [`Mesh::material`] to access the material an ID refers to.
+    pub fn material_ids(
+        &self,
+    ) -> impl Iterator<Item = Option<MaterialId>> + '_ {
+        self.material_ids.iter().copied()
+    }
+
+    /// Add a material to the mesh
+    ///
+    /// Returns the ID that triangles can use to refer to the material. If an
+    /// equal material has been added before, its ID is returned instead.
+    pub fn add_material(&mut self, material: Material) -> MaterialId {
+        let index = self
+            .materials
+            .iter()
+            .position(|existing| existing == &material)
+            .unwrap_or_else(|| {
+                self.materials.push(material);
+                self.materials.len() - 1
+            });
+
+        MaterialId(index as u32)
+    }
+
+    /// Access a material, by its ID
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the ID doesn't refer to a material of the mesh.
+    pub fn material(&self, id: MaterialId) -> &Material {
+        &self.materials[id.0 as usize]
+    }
+
+    /// Access all materials of the mesh
+    ///
+    /// Yields the materials in the order they were added, meaning the ID of
+    /// each material is its position.
+    pub fn materials(&self) -> impl Iterator<Item = &Material> + '_ {
+        self.materials.iter()
+    }
+}
+
+impl Mesh<Point<3>> {
     /// Determine whether the mesh contains the provided triangle
     ///
     /// Returns true, if a triangle with any combination of the provided points
@@ -45,49 +160,89 @@ where
     ) -> bool {
         let triangle = triangle.into().normalize();
 
-        for t in &self.triangles {
-            let t = t.inner.normalize();
-            if triangle == t {
-                return true;
-            }
-        }
-
-        false
-    }
-
-    /// Access the vertices of the mesh
-    pub fn vertices(&self) -> impl Iterator<Item = V> + '_ {
-        self.vertices.iter().copied()
-    }
-
-    /// Access the indices of the mesh
-    pub fn indices(&self) -> impl Iterator<Item = Index> + '_ {
-        self.indices.iter().copied()
+        self.triangles().any(|t| t.inner.normalize() == triangle)
     }
 
     /// Access the triangles of the mesh
+    ///
+    /// Combines the vertices of each triangle with its attributes.
     pub fn triangles(&self) -> impl Iterator<Item = Triangle> + '_ {
-        self.triangles.iter().copied()
+        self.triangle_indices()
+            .zip(self.colors())
+            .zip(self.faces())
+            .zip(self.material_ids())
+            .map(|(((indices, color), face), material)| Triangle {
+                inner: self.triangle_vertices(indices).into(),
+                color,
+                face,
+                material,
+            })
+    }
+
+    /// Access the normal channel of the mesh
+    ///
+    /// Yields one unit normal per triangle, in the order of the triangles. The
+    /// normals are computed from the vertices, according to the winding of
+    /// each triangle, so they don't take up any memory.
+    pub fn normals(&self) -> impl Iterator<Item = Vector<3>> + '_ {
+        self.triangle_indices().map(|indices| {
+            let [a, b, c] = self.triangle_vertices(indices);
+            (b - a).cross(&(c - a)).normalize()
+        })
     }
-}
 
-impl Mesh<Point<3>> {
     /// Add a triangle to the mesh
     pub fn push_triangle(
         &mut self,
         triangle: impl Into<fj_math::Triangle<3>>,
         color: Color,
     ) {
-        let triangle = triangle.into();
+        self.push_triangle_inner(triangle.into(), color, None, None);
+    }
+
+    /// Add a triangle that was created from a face to the mesh
+    ///
+    /// Same as [`Mesh::push_triangle`], but records which face the triangle
+    /// was created from.
+    pub fn push_triangle_of_face(
+        &mut self,
+        triangle: impl Into<fj_math::Triangle<3>>,
+        color: Color,
+        face: FaceId,
+    ) {
+        self.push_triangle_inner(triangle.into(), color, Some(face), None);
+    }
 
+    /// Add triangles from another mesh to this one
+    ///
+    /// Material IDs are kept as they are, so they must refer to materials of
+    /// this mesh. Add those first, in the same order, if the triangles come
+    /// from another mesh.
+    pub fn extend(&mut self, triangles: impl IntoIterator<Item = Triangle>) {
+        for triangle in triangles {
+            self.push_triangle_inner(
+                triangle.inner,
+                triangle.color,
+                triangle.face,
+                triangle.material,
+            );
+        }
+    }
+
+    fn push_triangle_inner(
+        &mut self,
+        triangle: fj_math::Triangle<3>,
+        color: Color,
+        face: Option<FaceId>,
+        material: Option<MaterialId>,
+    ) {
         for point in triangle.points() {
             self.push_vertex(point);
         }
 
-        self.triangles.push(Triangle {
-            inner: triangle,
-            color,
-        });
+        self.colors.push(color);
+        self.faces.push(face);
+        self.material_ids.push(material);
     }
 }
 
@@ -99,7 +254,79 @@ impl<V> Default for Mesh<V> {
             vertices: Vec::default(),
             indices: Vec::default(),
             indices_by_vertex: HashMap::default(),
-            triangles: Vec::default(),
+            colors: Vec::default(),
+            faces: Vec::default(),
+            material_ids: Vec::default(),
+            materials: Vec::default(),
+        }
+    }
+}
+
+/// A triangle mesh with single-precision vertices
+///
+/// The kernel works with `f64` internally, as its tolerances are well below
+/// what `f32` can represent. Consumers that only render or store its output
+/// rarely need that precision, and can convert a [`Mesh`] into this type, to
+/// halve the memory that its vertices take up.
+///
+/// Vertices that are distinct in the original mesh stay distinct, even if
+/// they end up equal after the conversion, so the indices and the attribute
+/// channels are unchanged.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct F32Mesh {
+    /// The vertices of the mesh
+    pub vertices: Vec<[f32; 3]>,
+
+    /// The indices of the mesh, three per triangle
+    pub indices: Vec<Index>,
+
+    /// The color of each triangle
+    pub colors: Vec<Color>,
+
+    /// The face that each triangle was created from, if known
+    pub faces: Vec<Option<FaceId>>,
+
+    /// The material of each triangle, if any
+    pub material_ids: Vec<Option<MaterialId>>,
+
+    /// The materials that the material IDs refer to
+    pub materials: Vec<Material>,
+}
+
+impl From<&Mesh<Point<3>>> for F32Mesh {
+    fn from(mesh: &Mesh<Point<3>>) -> Self {
+        Self {
+            vertices: mesh.vertices().map(Into::into).collect(),
+            indices: mesh.indices.clone(),
+            colors: mesh.colors.clone(),
+            faces: mesh.faces.clone(),
+            material_ids: mesh.material_ids.clone(),
+            materials: mesh.materials.clone(),
+        }
+    }
+}
+
+impl From<&F32Mesh> for Mesh<Point<3>> {
+    fn from(mesh: &F32Mesh) -> Self {
+        let vertices = mesh
+            .vertices
+            .iter()
+            .map(|&[x, y, z]| Point::from([x, y, z].map(f64::from)))
+            .collect::<Vec<_>>();
+        let indices_by_vertex = vertices
+            .iter()
+            .enumerate()
+            .map(|(index, &vertex)| (vertex, index as Index))
+            .collect();
+
+        Self {
+            vertices,
+            indices: mesh.indices.clone(),
+            indices_by_vertex,
+            colors: mesh.colors.clone(),
+            faces: mesh.faces.clone(),
+            material_ids: mesh.material_ids.clone(),
+            materials: mesh.materials.clone(),
         }
     }
 }
@@ -109,7 +336,7 @@ pub type Index = u32;
 
 /// A triangle
 ///
-/// Extension of [`fj_math::Triangle`] that also includes a color.
+/// Extension of [`fj_math::Triangle`] that also includes its attributes.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Triangle {
     /// The points of the triangle
@@ -117,4 +344,123 @@ pub struct Triangle {
 
     /// The color of the triangle
     pub color: Color,
+
+    /// The face that the triangle was created from, if known
+    pub face: Option<FaceId>,
+
+    /// The material of the triangle, if any
+    pub material: Option<MaterialId>,
+}
+
+/// Identifies the face that a triangle was created from
+///
+/// This is an opaque identifier. It allows consumers of a mesh to tell which
+/// triangles belong to the same face, and allows the code that created the mesh
+/// to find the face again.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct FaceId(pub u64);
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::{Color, Material};
+
+    use super::{F32Mesh, FaceId, Mesh, Triangle};
+
+    #[test]
+    fn deduplicate_vertices() {
+        let mut mesh = Mesh::new();
+
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]];
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([a, c, d], Color::default());
+
+        assert_eq!(mesh.vertices().count(), 4);
+        assert_eq!(mesh.indices().collect::<Vec<_>>(), [0, 1, 2, 0, 2, 3]);
+        assert_eq!(
+            mesh.triangle_indices().collect::<Vec<_>>(),
+            [[0, 1, 2], [0, 2, 3]]
+        );
+    }
+
+    #[test]
+    fn indices_refer_to_vertices() {
+        let mut mesh = Mesh::new();
+
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        for triangle in [[a, b, c], [a, b, d], [a, c, d], [b, c, d]] {
+            mesh.push_triangle(triangle, Color::default());
+        }
+
+        let num_vertices = mesh.vertices().count();
+        assert!(mesh.indices().all(|index| (index as usize) < num_vertices));
+
+        for (indices, triangle) in mesh.triangle_indices().zip(mesh.triangles())
+        {
+            let vertices = mesh.triangle_vertices(indices);
+            assert_eq!(vertices, triangle.inner.points());
+        }
+    }
+
+    #[test]
+    fn one_attribute_per_triangle() {
+        let mut mesh = Mesh::new();
+
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle_of_face([a, b, d], Color::default(), FaceId(1));
+        mesh.push_triangle_of_face([a, c, d], Color::default(), FaceId(1));
+
+        assert_eq!(mesh.num_triangles(), 3);
+        assert_eq!(mesh.colors().count(), 3);
+        assert_eq!(mesh.faces().count(), 3);
+        assert_eq!(mesh.material_ids().count(), 3);
+        assert_eq!(mesh.normals().count(), 3);
+        assert_eq!(mesh.triangles().count(), 3);
+
+        // A mesh that is built vertex-by-vertex has no attributes.
+        let mut mesh = Mesh::new();
+        for vertex in [a, b, c] {
+            mesh.push_vertex(Point::from(vertex));
+        }
+        assert_eq!(mesh.num_triangles(), 1);
+        assert_eq!(mesh.colors().count(), 0);
+        assert_eq!(mesh.faces().count(), 0);
+        assert_eq!(mesh.material_ids().count(), 0);
+    }
+
+    #[test]
+    fn f32_mesh_round_trip() {
+        let mut mesh = Mesh::new();
+        let material = mesh.add_material(Material::new("steel"));
+
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        mesh.push_triangle([a, b, c], Color([255, 0, 0, 255]));
+        mesh.push_triangle_of_face([a, c, d], Color::default(), FaceId(3));
+        mesh.extend([Triangle {
+            inner: [b, c, d].into(),
+            color: Color::default(),
+            face: None,
+            material: Some(material),
+        }]);
+
+        let converted = F32Mesh::from(&mesh);
+        assert_eq!(converted.faces, [None, Some(FaceId(3)), None]);
+        assert_eq!(converted.material_ids, [None, None, Some(material)]);
+        assert_eq!(converted.materials, [Material::new("steel")]);
+
+        // All coordinates are exactly representable as `f32`, so nothing is
+        // lost in the round trip.
+        let restored = Mesh::<Point<3>>::from(&converted);
+        assert!(restored.vertices().eq(mesh.vertices()));
+        assert!(restored.indices().eq(mesh.indices()));
+        assert!(restored.triangles().eq(mesh.triangles()));
+        assert!(restored.materials().eq(mesh.materials()));
+        assert_eq!(F32Mesh::from(&restored), converted);
+    }
 }
@@ -1,6 +1,11 @@
-use fj_math::{Aabb, Point};
+use std::{iter, ops::Range};
 
-use crate::mesh::Mesh;
+use fj_math::{Aabb, Point, Scalar};
+
+use crate::{
+    debug::DebugGeometry,
+    mesh::{FaceId, Mesh},
+};
 
 /// An approximated model
 #[derive(Clone, Debug)]
@@ -10,4 +15,201 @@ pub struct Model {
 
     /// The axis-aligned bounding box of the model
     pub aabb: Aabb<3>,
+
+    /// The parts that make up the model
+    ///
+    /// Each part is defined by a range of indices into the triangles of
+    /// [`Model::mesh`]. A model that was created from a single shape consists
+    /// of a single part, which covers all triangles.
+    pub parts: Vec<Range<usize>>,
+
+    /// Coarser approximations of the model, for display at a distance
+    ///
+    /// [`Model::mesh`] is the most detailed approximation of the model. These
+    /// are additional meshes, each approximating the model with a larger
+    /// tolerance. They are sorted by tolerance, from finest to coarsest.
+    ///
+    /// Unlike [`Model::parts`], these don't relate to [`Model::mesh`]. They
+    /// are only meant for display.
+    pub levels_of_detail: Vec<LevelOfDetail>,
+
+    /// Geometry that was emitted for debugging, while building the model
+    ///
+    /// This is not part of the model, but the viewer can display it alongside.
+    pub debug: DebugGeometry,
+
+    /// The faces of the model that failed to triangulate
+    ///
+    /// These faces are missing from [`Model::mesh`], but the rest of the model
+    /// is still there.
+    pub failed_faces: Vec<FailedFace>,
+}
+
+impl Model {
+    /// Construct a model that consists of a single part
+    pub fn new(mesh: Mesh<Point<3>>, aabb: Aabb<3>) -> Self {
+        let parts = iter::once(0..mesh.num_triangles()).collect();
+        Self {
+            mesh,
+            aabb,
+            parts,
+            levels_of_detail: Vec::new(),
+            debug: DebugGeometry::default(),
+            failed_faces: Vec::new(),
+        }
+    }
+
+    /// Construct a model from multiple parts
+    ///
+    /// This can be used to display assemblies, where it is useful to know which
+    /// triangles belong to which part. The bounding box is computed from the
+    /// meshes.
+    pub fn from_parts(parts: impl IntoIterator<Item = Mesh<Point<3>>>) -> Self {
+        let mut mesh = Mesh::new();
+        let mut ranges = Vec::new();
+
+        for part in parts {
+            let start = mesh.num_triangles();
+            mesh.extend(part.triangles());
+            ranges.push(start..mesh.num_triangles());
+        }
+
+        let aabb = Aabb::<3>::from_points(mesh.vertices());
+
+        Self {
+            mesh,
+            aabb,
+            parts: ranges,
+            levels_of_detail: Vec::new(),
+            debug: DebugGeometry::default(),
+            failed_faces: Vec::new(),
+        }
+    }
+
+    /// Add geometry that was emitted for debugging
+    pub fn with_debug(mut self, debug: DebugGeometry) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    /// Add faces that failed to triangulate
+    pub fn with_failed_faces(
+        mut self,
+        failed_faces: impl IntoIterator<Item = FailedFace>,
+    ) -> Self {
+        self.failed_faces.extend(failed_faces);
+        self
+    }
+
+    /// Add a coarser approximation of the model
+    ///
+    /// `tolerance` is the maximum distance between `mesh` and the surface of
+    /// the model that it approximates.
+    pub fn with_level_of_detail(
+        mut self,
+        tolerance: impl Into<Scalar>,
+        mesh: Mesh<Point<3>>,
+    ) -> Self {
+        let tolerance = tolerance.into();
+
+        let index = self
+            .levels_of_detail
+            .partition_point(|level| level.tolerance <= tolerance);
+        self.levels_of_detail
+            .insert(index, LevelOfDetail { tolerance, mesh });
+
+        self
+    }
+
+    /// Select a level of detail, given the error that is acceptable
+    ///
+    /// Returns the index of the coarsest level of detail whose tolerance
+    /// doesn't exceed `max_error`, or `None`, if [`Model::mesh`] should be
+    /// used.
+    pub fn level_of_detail(&self, max_error: Scalar) -> Option<usize> {
+        self.levels_of_detail
+            .iter()
+            .rposition(|level| level.tolerance <= max_error)
+    }
+
+    /// Access the mesh of a level of detail
+    ///
+    /// Returns [`Model::mesh`] for `None`, or if there is no level of detail
+    /// with the provided index.
+    pub fn mesh_at(&self, level_of_detail: Option<usize>) -> &Mesh<Point<3>> {
+        level_of_detail
+            .and_then(|index| self.levels_of_detail.get(index))
+            .map_or(&self.mesh, |level| &level.mesh)
+    }
+
+    /// Create an exploded view of the model's mesh
+    ///
+    /// Moves each part away from the center of the model, along the direction
+    /// from the model's center to the part's center. The distance each part is
+    /// moved is that part's distance from the model's center, multiplied by
+    /// `factor`. A factor of zero results in the original mesh.
+    pub fn exploded_mesh(&self, factor: f64) -> Mesh<Point<3>> {
+        let center = self.aabb.center();
+        let triangles = self.mesh.triangles().collect::<Vec<_>>();
+
+        let mut mesh = Mesh::new();
+
+        for part in &self.parts {
+            let Some(part_triangles) = triangles.get(part.clone()) else {
+                continue;
+            };
+            if part_triangles.is_empty() {
+                continue;
+            }
+
+            let num_points = Scalar::from_u64(part_triangles.len() as u64 * 3);
+            let part_center = part_triangles
+                .iter()
+                .flat_map(|triangle| triangle.inner.points())
+                .fold(Point::origin(), |sum, point| sum + point.coords);
+            let part_center = Point {
+                coords: part_center.coords / num_points,
+            };
+
+            let offset = (part_center - center) * factor;
+
+            mesh.extend(part_triangles.iter().map(|triangle| {
+                let mut triangle = *triangle;
+                triangle.inner =
+                    triangle.inner.points().map(|point| point + offset).into();
+                triangle
+            }));
+        }
+
+        mesh
+    }
+}
+
+/// A coarser approximation of a [`Model`]
+///
+/// See [`Model::levels_of_detail`].
+#[derive(Clone, Debug)]
+pub struct LevelOfDetail {
+    /// The maximum distance between the mesh and the model's surface
+    pub tolerance: Scalar,
+
+    /// The mesh that approximates the model
+    pub mesh: Mesh<Point<3>>,
+}
+
+/// A face of a [`Model`] that failed to triangulate
+///
+/// See [`Model::failed_faces`].
+#[derive(Clone, Debug)]
+pub struct FailedFace {
+    /// The ID of the face
+    pub face: FaceId,
+
+    /// Why the face failed to triangulate
+    pub reason: String,
+
+    /// Lines along the boundary of the face
+    ///
+    /// The face has no triangles, so these can be used to show where it is.
+    pub boundary: Vec<[Point<3>; 2]>,
 }
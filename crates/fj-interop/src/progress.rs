@@ -0,0 +1,140 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Receives progress reports from long-running operations
+///
+/// Operations that can take a while, like triangulation or export, report
+/// their progress to a sink, and ask it whether they should stop early. This
+/// allows a host application to show a progress bar, and to cancel a rebuild
+/// whose result is no longer needed.
+///
+/// Reports can be frequent, so implementations should be cheap. Closures that
+/// accept a [`Progress`] implement this trait, and never cancel. To cancel
+/// from another thread, use a [`CancellationToken`], or implement this trait
+/// for a type that both forwards reports and checks a token.
+pub trait ProgressSink {
+    /// Receive a progress report
+    fn report(&mut self, progress: Progress);
+
+    /// Indicate whether the running operation should stop
+    ///
+    /// Operations check this regularly. Once it returns `true`, they stop as
+    /// soon as possible.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+
+    /// Report progress, then check whether the operation should stop
+    ///
+    /// This is a convenience method for operations, which can use `?` to stop
+    /// when they've been cancelled.
+    fn step(
+        &mut self,
+        task: &'static str,
+        done: usize,
+        total: usize,
+    ) -> Result<(), Cancelled> {
+        self.report(Progress { task, done, total });
+
+        if self.is_cancelled() {
+            return Err(Cancelled);
+        }
+
+        Ok(())
+    }
+}
+
+impl<F> ProgressSink for F
+where
+    F: FnMut(Progress),
+{
+    fn report(&mut self, progress: Progress) {
+        self(progress);
+    }
+}
+
+/// A progress report from a long-running operation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Progress {
+    /// The task that is being worked on, like `"triangulate"`
+    pub task: &'static str,
+
+    /// The number of steps of the task that are done
+    pub done: usize,
+
+    /// The total number of steps of the task
+    pub total: usize,
+}
+
+impl Progress {
+    /// Compute the fraction of the task that is done, between `0` and `1`
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 1.;
+        }
+
+        self.done as f64 / self.total as f64
+    }
+}
+
+/// A [`ProgressSink`] that ignores all reports, and never cancels
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn report(&mut self, _: Progress) {}
+}
+
+/// A [`ProgressSink`] that cancels, once [`CancellationToken::cancel`] has
+/// been called on any of its clones
+///
+/// Clones share their state, so a host application can keep one clone and
+/// hand another to the operation, possibly on another thread.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that hasn't been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel all operations that use this token, or any of its clones
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Indicate whether the token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl ProgressSink for CancellationToken {
+    fn report(&mut self, _: Progress) {}
+
+    fn is_cancelled(&self) -> bool {
+        CancellationToken::is_cancelled(self)
+    }
+}
+
+/// A long-running operation was cancelled
+///
+/// See [`ProgressSink::is_cancelled`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Operation was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
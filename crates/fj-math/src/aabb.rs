@@ -45,6 +45,17 @@ impl<const D: usize> Aabb<D> {
 
         true
     }
+
+    /// Determine whether the AABB intersects another AABB
+    ///
+    /// AABBs that only touch each other are considered to intersect.
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..D).all(|i| {
+            self.min.coords.components[i] <= other.max.coords.components[i]
+                && other.min.coords.components[i]
+                    <= self.max.coords.components[i]
+        })
+    }
 }
 
 impl Aabb<2> {
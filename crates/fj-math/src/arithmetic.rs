@@ -0,0 +1,240 @@
+use std::{
+    array, env,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+
+use crate::{Point, Scalar, Tolerances, Vector};
+
+/// The arithmetic used by intersection computations and tolerance checks
+///
+/// These computations use floating-point arithmetic by default. If a model
+/// fails validation only for some parameter values, it can be hard to tell
+/// whether that is caused by a logic bug, or by accumulated rounding errors.
+/// Selecting [`Arithmetic::Exact`] re-evaluates them using exact rational
+/// arithmetic. If the failure goes away, rounding errors are the likely cause.
+///
+/// Exact arithmetic is much slower, and meant for debugging only. It applies
+/// to the following computations:
+///
+/// - [`SegmentSegmentIntersection::compute`]
+/// - [`LinePlaneIntersection::compute`]
+/// - [`Tolerances::are_parallel`]
+/// - [`Tolerances::are_perpendicular`]
+///
+/// Their inputs are still the `f64` values that other computations produce,
+/// and their results are rounded to the nearest `f64`. The [`predicates`] are
+/// exact, regardless of the selected arithmetic.
+///
+/// The arithmetic is selected for the whole process, by calling
+/// [`Arithmetic::select`], or by setting the `FJ_ARITHMETIC` environment
+/// variable to `exact`.
+///
+/// [`SegmentSegmentIntersection::compute`]: crate::intersect::SegmentSegmentIntersection::compute
+/// [`LinePlaneIntersection::compute`]: crate::intersect::LinePlaneIntersection::compute
+/// [`predicates`]: crate::predicates
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Arithmetic {
+    /// Floating-point arithmetic
+    Float,
+
+    /// Exact rational arithmetic
+    Exact,
+}
+
+impl Arithmetic {
+    /// Access the arithmetic that is selected for the current process
+    ///
+    /// Unless [`Arithmetic::select`] has been called, this is determined by
+    /// the `FJ_ARITHMETIC` environment variable, and defaults to
+    /// [`Arithmetic::Float`].
+    pub fn current() -> Self {
+        match SELECTED.load(Ordering::Relaxed) {
+            FLOAT => Self::Float,
+            EXACT => Self::Exact,
+            _ => {
+                let arithmetic = match env::var("FJ_ARITHMETIC") {
+                    Ok(value) if value.eq_ignore_ascii_case("exact") => {
+                        Self::Exact
+                    }
+                    _ => Self::Float,
+                };
+
+                arithmetic.select();
+                arithmetic
+            }
+        }
+    }
+
+    /// Select this arithmetic for the current process
+    pub fn select(self) {
+        let selected = match self {
+            Self::Float => FLOAT,
+            Self::Exact => EXACT,
+        };
+
+        SELECTED.store(selected, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_exact() -> bool {
+        Self::current() == Self::Exact
+    }
+}
+
+static SELECTED: AtomicU8 = AtomicU8::new(UNSELECTED);
+
+const FLOAT: u8 = 0;
+const EXACT: u8 = 1;
+const UNSELECTED: u8 = 2;
+
+pub(crate) type Rational = BigRational;
+
+pub(crate) fn rational(scalar: Scalar) -> Rational {
+    Rational::from_float(scalar.into_f64())
+        .expect("Infinite values have no exact representation")
+}
+
+pub(crate) fn vector<const D: usize>(vector: Vector<D>) -> [Rational; D] {
+    vector.components.map(rational)
+}
+
+pub(crate) fn point<const D: usize>(point: Point<D>) -> [Rational; D] {
+    vector(point.coords)
+}
+
+pub(crate) fn to_scalar(value: &Rational) -> Scalar {
+    let value = value
+        .to_f64()
+        .expect("Rational numbers can always be converted to `f64`");
+    Scalar::from_f64(value)
+}
+
+pub(crate) fn to_point<const D: usize>(point: &[Rational; D]) -> Point<D> {
+    Point::from(array::from_fn(|i| to_scalar(&point[i])))
+}
+
+pub(crate) fn sub<const D: usize>(
+    a: &[Rational; D],
+    b: &[Rational; D],
+) -> [Rational; D] {
+    array::from_fn(|i| &a[i] - &b[i])
+}
+
+/// Compute `point + vector * t`
+pub(crate) fn add_scaled<const D: usize>(
+    point: &[Rational; D],
+    vector: &[Rational; D],
+    t: &Rational,
+) -> [Rational; D] {
+    array::from_fn(|i| &point[i] + &vector[i] * t)
+}
+
+pub(crate) fn dot<const D: usize>(
+    a: &[Rational; D],
+    b: &[Rational; D],
+) -> Rational {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+pub(crate) fn cross2d(a: &[Rational; 2], b: &[Rational; 2]) -> Rational {
+    &a[0] * &b[1] - &a[1] * &b[0]
+}
+
+pub(crate) fn cross(a: &[Rational; 3], b: &[Rational; 3]) -> [Rational; 3] {
+    [
+        &a[1] * &b[2] - &a[2] * &b[1],
+        &a[2] * &b[0] - &a[0] * &b[2],
+        &a[0] * &b[1] - &a[1] * &b[0],
+    ]
+}
+
+/// Exact version of [`Tolerances::are_parallel`]
+///
+/// Compares squared values, which avoids the square roots that normalizing
+/// the vectors would require.
+pub(crate) fn are_parallel<const D: usize>(
+    a: &[Rational; D],
+    b: &[Rational; D],
+    tolerances: &Tolerances,
+) -> bool {
+    let [a_len2, b_len2] = [dot(a, a), dot(b, b)];
+    if is_zero_length2(&a_len2, tolerances)
+        || is_zero_length2(&b_len2, tolerances)
+    {
+        return true;
+    }
+
+    // By Lagrange's identity, this is the squared sine of the angle between
+    // the vectors, times the product of their squared lengths.
+    let ab = dot(a, b);
+    let sin2 = &a_len2 * &b_len2 - &ab * &ab;
+
+    sin2 < sin_angular2(tolerances) * a_len2 * b_len2
+}
+
+/// Exact version of [`Tolerances::are_perpendicular`]
+pub(crate) fn are_perpendicular<const D: usize>(
+    a: &[Rational; D],
+    b: &[Rational; D],
+    tolerances: &Tolerances,
+) -> bool {
+    let [a_len2, b_len2] = [dot(a, a), dot(b, b)];
+    if is_zero_length2(&a_len2, tolerances)
+        || is_zero_length2(&b_len2, tolerances)
+    {
+        return true;
+    }
+
+    // This is the squared cosine of the angle between the vectors, times the
+    // product of their squared lengths.
+    let ab = dot(a, b);
+    let cos2 = &ab * &ab;
+
+    cos2 < sin_angular2(tolerances) * a_len2 * b_len2
+}
+
+/// Determine whether a length, given as its square, is considered zero
+pub(crate) fn is_zero_length2(
+    length2: &Rational,
+    tolerances: &Tolerances,
+) -> bool {
+    let linear = rational(tolerances.linear);
+    length2 < &(&linear * &linear)
+}
+
+fn sin_angular2(tolerances: &Tolerances) -> Rational {
+    let sin = rational(tolerances.angular.sin_cos().0);
+    &sin * &sin
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Tolerances, Vector};
+
+    use super::{are_parallel, are_perpendicular, vector, Rational};
+
+    type Check = fn(&[Rational; 3], &[Rational; 3], &Tolerances) -> bool;
+
+    #[test]
+    fn exact_tolerance_checks() {
+        let tolerances = Tolerances::default();
+        let check = |check: Check, a: [f64; 3], b: [f64; 3]| {
+            let [a, b] = [a, b].map(|v| vector(Vector::from(v)));
+            check(&a, &b, &tolerances)
+        };
+
+        let x = [1., 0., 0.];
+        assert!(check(are_parallel, x, [1., 1e-9, 0.]));
+        assert!(check(are_parallel, x, [-2., 0., 0.]));
+        assert!(!check(are_parallel, x, [1., 1e-3, 0.]));
+
+        assert!(check(are_perpendicular, x, [1e-9, 1., 0.]));
+        assert!(!check(are_perpendicular, x, [1e-3, 1., 0.]));
+
+        // Vectors of zero length count as both.
+        assert!(check(are_parallel, x, [0., 0., 0.]));
+        assert!(check(are_perpendicular, x, [0., 0., 0.]));
+    }
+}
@@ -0,0 +1,588 @@
+//! Analytic intersections between geometric primitives
+//!
+//! Each intersection type provides a `compute` function, which returns `None`,
+//! if the primitives don't intersect. Whether primitives that come close to
+//! each other intersect, or touch, is decided using the provided
+//! [`Tolerances`].
+//!
+//! Segment/segment and line/plane intersections can also be computed using
+//! exact arithmetic. See [`Arithmetic`].
+
+use num_traits::{One, Signed, Zero};
+
+use crate::{
+    arithmetic::{self, Rational},
+    Arithmetic, Circle, Line, Plane, Point, Scalar, Segment, Tolerances,
+    Vector,
+};
+
+/// An intersection between two [`Segment`]s in 2D
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SegmentSegmentIntersection {
+    /// The segments intersect, or touch, at a single point
+    Point {
+        /// The intersection point
+        point: Point<2>,
+
+        /// The position of the point along each segment
+        ///
+        /// Ranges from `0` at the first point of a segment to `1` at its
+        /// second point.
+        t: [Scalar; 2],
+    },
+
+    /// The segments are collinear, and overlap
+    Coincident {
+        /// The end points of the overlap, in the direction of the first segment
+        points: [Point<2>; 2],
+    },
+}
+
+impl SegmentSegmentIntersection {
+    /// Determine the intersection between two segments
+    pub fn compute(
+        a: &Segment<2>,
+        b: &Segment<2>,
+        tolerances: &Tolerances,
+    ) -> Option<Self> {
+        if Arithmetic::is_exact() {
+            return Self::compute_exact(a, b, tolerances);
+        }
+
+        let [p, p_end] = a.points();
+        let [q, q_end] = b.points();
+        let [r, s] = [p_end - p, q_end - q];
+        let [r_len, s_len] = [r.magnitude(), s.magnitude()];
+        let pq = q - p;
+
+        if tolerances.are_parallel(&r, &s) {
+            let distance = pq.cross2d(&r).abs() / r_len;
+            if !tolerances.is_zero_length(distance) {
+                return None;
+            }
+
+            // The segments are on the same line. Find the overlap, in terms of
+            // the position along `a`.
+            let t0 = pq.dot(&r) / (r_len * r_len);
+            let t1 = (q_end - p).dot(&r) / (r_len * r_len);
+            let start = t0.min(t1).max(Scalar::ZERO);
+            let end = t0.max(t1).min(Scalar::ONE);
+
+            let length = (end - start) * r_len;
+            if length < -tolerances.linear {
+                return None;
+            }
+            if tolerances.is_zero_length(length) {
+                let point = p + r * start;
+                let u = (point - q).dot(&s) / (s_len * s_len);
+
+                return Some(Self::Point {
+                    point,
+                    t: [start, u.max(Scalar::ZERO).min(Scalar::ONE)],
+                });
+            }
+
+            return Some(Self::Coincident {
+                points: [p + r * start, p + r * end],
+            });
+        }
+
+        let denominator = r.cross2d(&s);
+        let t = pq.cross2d(&s) / denominator;
+        let u = pq.cross2d(&r) / denominator;
+
+        let is_on_segment = |t: Scalar, length: Scalar| {
+            t * length >= -tolerances.linear
+                && (t - Scalar::ONE) * length <= tolerances.linear
+        };
+        if !is_on_segment(t, r_len) || !is_on_segment(u, s_len) {
+            return None;
+        }
+
+        let [t, u] = [t, u].map(|t| t.max(Scalar::ZERO).min(Scalar::ONE));
+
+        Some(Self::Point {
+            point: p + r * t,
+            t: [t, u],
+        })
+    }
+
+    fn compute_exact(
+        a: &Segment<2>,
+        b: &Segment<2>,
+        tolerances: &Tolerances,
+    ) -> Option<Self> {
+        use arithmetic::{add_scaled, cross2d, dot, is_zero_length2, sub};
+
+        let [p, p_end] = a.points().map(arithmetic::point);
+        let [q, q_end] = b.points().map(arithmetic::point);
+        let [r, s] = [sub(&p_end, &p), sub(&q_end, &q)];
+        let [r_len2, s_len2] = [dot(&r, &r), dot(&s, &s)];
+        let pq = sub(&q, &p);
+
+        let clamp = |t: Rational| t.max(Rational::zero()).min(Rational::one());
+
+        if arithmetic::are_parallel(&r, &s, tolerances) {
+            let cross = cross2d(&pq, &r);
+            let distance2 = &cross * &cross / &r_len2;
+            if !is_zero_length2(&distance2, tolerances) {
+                return None;
+            }
+
+            // The segments are on the same line. Find the overlap, in terms of
+            // the position along `a`.
+            let t0 = dot(&pq, &r) / &r_len2;
+            let t1 = dot(&sub(&q_end, &p), &r) / &r_len2;
+            let start = t0.clone().min(t1.clone()).max(Rational::zero());
+            let end = t0.max(t1).min(Rational::one());
+
+            let length = &end - &start;
+            let length2 = &length * &length * &r_len2;
+            if length.is_negative() && !is_zero_length2(&length2, tolerances) {
+                return None;
+            }
+            if is_zero_length2(&length2, tolerances) {
+                let point = add_scaled(&p, &r, &start);
+                let u = dot(&sub(&point, &q), &s) / &s_len2;
+
+                return Some(Self::Point {
+                    point: arithmetic::to_point(&point),
+                    t: [start, clamp(u)].map(|t| arithmetic::to_scalar(&t)),
+                });
+            }
+
+            return Some(Self::Coincident {
+                points: [start, end]
+                    .map(|t| arithmetic::to_point(&add_scaled(&p, &r, &t))),
+            });
+        }
+
+        let denominator = cross2d(&r, &s);
+        let t = cross2d(&pq, &s) / &denominator;
+        let u = cross2d(&pq, &r) / &denominator;
+
+        let is_on_segment = |t: &Rational, length2: &Rational| {
+            let one = Rational::one();
+            [-t, t - one].into_iter().all(|outside| {
+                !outside.is_positive()
+                    || is_zero_length2(
+                        &(&outside * &outside * length2),
+                        tolerances,
+                    )
+            })
+        };
+        if !is_on_segment(&t, &r_len2) || !is_on_segment(&u, &s_len2) {
+            return None;
+        }
+
+        let [t, u] = [t, u].map(clamp);
+
+        Some(Self::Point {
+            point: arithmetic::to_point(&add_scaled(&p, &r, &t)),
+            t: [t, u].map(|t| arithmetic::to_scalar(&t)),
+        })
+    }
+}
+
+/// An intersection between a [`Line`] and a [`Plane`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LinePlaneIntersection {
+    /// The line crosses the plane at a point
+    Point {
+        /// The intersection point
+        point: Point<3>,
+
+        /// The intersection point, given as a coordinate on the line
+        point_on_line: Point<1>,
+    },
+
+    /// The line lies within the plane
+    Coincident,
+}
+
+impl LinePlaneIntersection {
+    /// Determine the intersection between a line and a plane
+    pub fn compute(
+        line: &Line<3>,
+        plane: &Plane,
+        tolerances: &Tolerances,
+    ) -> Option<Self> {
+        if Arithmetic::is_exact() {
+            return Self::compute_exact(line, plane, tolerances);
+        }
+
+        let normal = plane.normal();
+        let origin_to_plane = plane.origin() - line.origin();
+
+        if tolerances.are_perpendicular(&normal, &line.direction()) {
+            if tolerances.is_zero_length(normal.dot(&origin_to_plane)) {
+                return Some(Self::Coincident);
+            }
+
+            return None;
+        }
+
+        let t = normal.dot(&origin_to_plane) / normal.dot(&line.direction());
+
+        Some(Self::Point {
+            point: line.point_from_line_coords([t]),
+            point_on_line: Point::from([t]),
+        })
+    }
+
+    fn compute_exact(
+        line: &Line<3>,
+        plane: &Plane,
+        tolerances: &Tolerances,
+    ) -> Option<Self> {
+        use arithmetic::{add_scaled, dot, sub};
+
+        // The normal is not normalized, as that would require a square root.
+        // Where its length matters, it is divided out below.
+        let normal = arithmetic::cross(
+            &arithmetic::vector(plane.u()),
+            &arithmetic::vector(plane.v()),
+        );
+        let origin = arithmetic::point(line.origin());
+        let direction = arithmetic::vector(line.direction());
+        let origin_to_plane = sub(&arithmetic::point(plane.origin()), &origin);
+
+        let distance = dot(&normal, &origin_to_plane);
+
+        if arithmetic::are_perpendicular(&normal, &direction, tolerances) {
+            let distance2 = &distance * &distance / dot(&normal, &normal);
+            if arithmetic::is_zero_length2(&distance2, tolerances) {
+                return Some(Self::Coincident);
+            }
+
+            return None;
+        }
+
+        let t = distance / dot(&normal, &direction);
+
+        Some(Self::Point {
+            point: arithmetic::to_point(&add_scaled(&origin, &direction, &t)),
+            point_on_line: Point::from([arithmetic::to_scalar(&t)]),
+        })
+    }
+}
+
+/// An intersection between a [`Circle`] and a [`Line`] in 2D
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircleLineIntersection {
+    /// The line crosses the circle at two points
+    Points {
+        /// The intersection points, in the direction of the line
+        points: [Point<2>; 2],
+    },
+
+    /// The line is tangent to the circle
+    Tangent {
+        /// The point where the line touches the circle
+        point: Point<2>,
+    },
+}
+
+impl CircleLineIntersection {
+    /// Determine the intersection between a circle and a line
+    pub fn compute(
+        circle: &Circle<2>,
+        line: &Line<2>,
+        tolerances: &Tolerances,
+    ) -> Option<Self> {
+        let direction = line.direction().normalize();
+        let closest = line.origin()
+            + direction * (circle.center() - line.origin()).dot(&direction);
+
+        let distance = closest.distance_to(&circle.center());
+        let radius = circle.radius();
+
+        if tolerances.is_zero_length(distance - radius) {
+            return Some(Self::Tangent { point: closest });
+        }
+        if distance > radius {
+            return None;
+        }
+
+        let half_chord = sqrt(radius * radius - distance * distance);
+
+        Some(Self::Points {
+            points: [
+                closest - direction * half_chord,
+                closest + direction * half_chord,
+            ],
+        })
+    }
+}
+
+/// An intersection between two [`Circle`]s in 2D
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircleCircleIntersection {
+    /// The circles cross each other at two points
+    Points {
+        /// The intersection points
+        ///
+        /// The first point is to the left, the second to the right, when
+        /// looking from the center of the first circle to that of the second.
+        points: [Point<2>; 2],
+    },
+
+    /// The circles touch each other at a single point
+    ///
+    /// This is the case, if they touch from the outside, or if one is inside
+    /// of the other and touches it from there.
+    Tangent {
+        /// The point where the circles touch
+        point: Point<2>,
+    },
+
+    /// The circles are the same circle
+    Coincident,
+}
+
+impl CircleCircleIntersection {
+    /// Determine the intersection between two circles
+    pub fn compute(
+        a: &Circle<2>,
+        b: &Circle<2>,
+        tolerances: &Tolerances,
+    ) -> Option<Self> {
+        let [ra, rb] = [a.radius(), b.radius()];
+        let between = b.center() - a.center();
+        let distance = between.magnitude();
+
+        if tolerances.is_zero_length(distance) {
+            if tolerances.is_zero_length(ra - rb) {
+                return Some(Self::Coincident);
+            }
+
+            return None;
+        }
+
+        let direction = between / distance;
+
+        if tolerances.is_zero_length(distance - (ra + rb)) {
+            return Some(Self::Tangent {
+                point: a.center() + direction * ra,
+            });
+        }
+        if tolerances.is_zero_length(distance - (ra - rb).abs()) {
+            // One circle is inside of the other. The touching point is on the
+            // far side of the smaller circle, as seen from the larger one.
+            let direction = if ra > rb { direction } else { -direction };
+
+            return Some(Self::Tangent {
+                point: a.center() + direction * ra,
+            });
+        }
+        if distance > ra + rb || distance < (ra - rb).abs() {
+            return None;
+        }
+
+        let along = (distance * distance + ra * ra - rb * rb) / (distance * 2.);
+        let across = sqrt(ra * ra - along * along);
+
+        let base = a.center() + direction * along;
+        let left = Vector::from([-direction.v, direction.u]);
+
+        Some(Self::Points {
+            points: [base + left * across, base - left * across],
+        })
+    }
+}
+
+fn sqrt(value: Scalar) -> Scalar {
+    Scalar::from(value.max(Scalar::ZERO).into_f64().sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circle, Line, Plane, Point, Scalar, Segment, Tolerances};
+
+    use super::{
+        CircleCircleIntersection, CircleLineIntersection,
+        LinePlaneIntersection, SegmentSegmentIntersection,
+    };
+
+    #[test]
+    fn segment_segment() {
+        let tolerances = Tolerances::default();
+        let intersect = |a: [[f64; 2]; 2], b: [[f64; 2]; 2]| {
+            SegmentSegmentIntersection::compute(
+                &Segment::from(a),
+                &Segment::from(b),
+                &tolerances,
+            )
+        };
+
+        assert_eq!(
+            intersect([[0., 0.], [2., 2.]], [[0., 2.], [2., 0.]]),
+            Some(SegmentSegmentIntersection::Point {
+                point: Point::from([1., 1.]),
+                t: [Scalar::from(0.5), Scalar::from(0.5)],
+            })
+        );
+
+        // Touching at an end point
+        assert_eq!(
+            intersect([[0., 0.], [2., 0.]], [[1., 0.], [1., 1.]]),
+            Some(SegmentSegmentIntersection::Point {
+                point: Point::from([1., 0.]),
+                t: [Scalar::from(0.5), Scalar::ZERO],
+            })
+        );
+
+        // Missing each other
+        assert_eq!(
+            intersect([[0., 0.], [1., 0.]], [[2., -1.], [2., 1.]]),
+            None
+        );
+        assert_eq!(intersect([[0., 0.], [1., 0.]], [[0., 1.], [1., 1.]]), None);
+
+        // Collinear
+        assert_eq!(
+            intersect([[0., 0.], [2., 0.]], [[3., 0.], [1., 0.]]),
+            Some(SegmentSegmentIntersection::Coincident {
+                points: [Point::from([1., 0.]), Point::from([2., 0.])],
+            })
+        );
+        assert_eq!(
+            intersect([[0., 0.], [1., 0.]], [[1., 0.], [2., 0.]]),
+            Some(SegmentSegmentIntersection::Point {
+                point: Point::from([1., 0.]),
+                t: [Scalar::ONE, Scalar::ZERO],
+            })
+        );
+        assert_eq!(intersect([[0., 0.], [1., 0.]], [[2., 0.], [3., 0.]]), None);
+    }
+
+    #[test]
+    fn line_plane() {
+        let tolerances = Tolerances::default();
+        let plane =
+            Plane::from_parametric([0., 0., 1.], [1., 0., 0.], [0., 1., 0.]);
+
+        let line = Line::from_points([[1., 2., 0.], [1., 2., 2.]]).0;
+        assert_eq!(
+            LinePlaneIntersection::compute(&line, &plane, &tolerances),
+            Some(LinePlaneIntersection::Point {
+                point: Point::from([1., 2., 1.]),
+                point_on_line: Point::from([0.5]),
+            })
+        );
+
+        let in_plane = Line::from_points([[0., 0., 1.], [1., 1., 1.]]).0;
+        assert_eq!(
+            LinePlaneIntersection::compute(&in_plane, &plane, &tolerances),
+            Some(LinePlaneIntersection::Coincident)
+        );
+
+        let parallel = Line::from_points([[0., 0., 2.], [1., 1., 2.]]).0;
+        assert_eq!(
+            LinePlaneIntersection::compute(&parallel, &plane, &tolerances),
+            None
+        );
+    }
+
+    #[test]
+    fn circle_line() {
+        let tolerances = Tolerances::default();
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+        let intersect = |points: [[f64; 2]; 2]| {
+            let line = Line::from_points(points).0;
+            CircleLineIntersection::compute(&circle, &line, &tolerances)
+        };
+
+        assert_eq!(
+            intersect([[-2., 0.], [2., 0.]]),
+            Some(CircleLineIntersection::Points {
+                points: [Point::from([-1., 0.]), Point::from([1., 0.])],
+            })
+        );
+        assert_eq!(
+            intersect([[2., 1.], [-2., 1.]]),
+            Some(CircleLineIntersection::Tangent {
+                point: Point::from([0., 1.]),
+            })
+        );
+        assert_eq!(intersect([[-2., 2.], [2., 2.]]), None);
+    }
+
+    #[test]
+    fn circle_circle() {
+        let tolerances = Tolerances::default();
+        let intersect = |a: ([f64; 2], f64), b: ([f64; 2], f64)| {
+            CircleCircleIntersection::compute(
+                &Circle::from_center_and_radius(a.0, a.1),
+                &Circle::from_center_and_radius(b.0, b.1),
+                &tolerances,
+            )
+        };
+
+        assert_eq!(
+            intersect(([0., 0.], 5.), ([8., 0.], 5.)),
+            Some(CircleCircleIntersection::Points {
+                points: [Point::from([4., 3.]), Point::from([4., -3.])],
+            })
+        );
+        assert_eq!(
+            intersect(([0., 0.], 1.), ([2., 0.], 1.)),
+            Some(CircleCircleIntersection::Tangent {
+                point: Point::from([1., 0.]),
+            })
+        );
+        assert_eq!(
+            intersect(([0., 0.], 1.), ([0., 1.], 2.)),
+            Some(CircleCircleIntersection::Tangent {
+                point: Point::from([0., -1.]),
+            })
+        );
+        assert_eq!(
+            intersect(([0., 0.], 1.), ([0., 0.], 1.)),
+            Some(CircleCircleIntersection::Coincident)
+        );
+        assert_eq!(intersect(([0., 0.], 1.), ([3., 0.], 1.)), None);
+        assert_eq!(intersect(([0., 0.], 3.), ([1., 0.], 1.)), None);
+    }
+
+    #[test]
+    fn exact_arithmetic_matches_float() {
+        let tolerances = Tolerances::default();
+
+        let segments = [
+            ([[0., 0.], [2., 2.]], [[0., 2.], [2., 0.]]),
+            ([[0., 0.], [2., 0.]], [[1., 0.], [1., 1.]]),
+            ([[0., 0.], [1., 0.]], [[2., -1.], [2., 1.]]),
+            ([[0., 0.], [1., 0.]], [[0., 1.], [1., 1.]]),
+            ([[0., 0.], [2., 0.]], [[3., 0.], [1., 0.]]),
+            ([[0., 0.], [1., 0.]], [[1., 0.], [2., 0.]]),
+            ([[0., 0.], [1., 0.]], [[2., 0.], [3., 0.]]),
+        ];
+        for (a, b) in segments {
+            let [a, b] = [a, b].map(Segment::from);
+            assert_eq!(
+                SegmentSegmentIntersection::compute_exact(&a, &b, &tolerances),
+                SegmentSegmentIntersection::compute(&a, &b, &tolerances),
+            );
+        }
+
+        let plane =
+            Plane::from_parametric([0., 0., 1.], [1., 0., 0.], [0., 1., 0.]);
+        let lines = [
+            [[1., 2., 0.], [1., 2., 2.]],
+            [[0., 0., 1.], [1., 1., 1.]],
+            [[0., 0., 2.], [1., 1., 2.]],
+        ];
+        for points in lines {
+            let line = Line::from_points(points).0;
+            assert_eq!(
+                LinePlaneIntersection::compute_exact(
+                    &line,
+                    &plane,
+                    &tolerances
+                ),
+                LinePlaneIntersection::compute(&line, &plane, &tolerances),
+            );
+        }
+    }
+}
@@ -31,32 +31,45 @@
 //! [nalgebra]: https://nalgebra.org/
 //! [Parry]: https://www.parry.rs/
 
+pub mod intersect;
+pub mod predicates;
+
 mod aabb;
 mod arc;
+mod arithmetic;
 mod circle;
 mod coordinates;
 mod line;
 mod plane;
 mod point;
 mod poly_chain;
+mod polygon;
+mod rotation;
 mod scalar;
 mod segment;
+mod tolerances;
 mod transform;
 mod triangle;
+mod units;
 mod vector;
 
 pub use self::{
     aabb::Aabb,
     arc::Arc,
+    arithmetic::Arithmetic,
     circle::Circle,
     coordinates::{Uv, Xyz, T},
     line::Line,
     plane::Plane,
     point::Point,
     poly_chain::PolyChain,
+    polygon::{PointInPolygon, Polygon},
+    rotation::Rotation,
     scalar::{Scalar, Sign},
     segment::Segment,
+    tolerances::Tolerances,
     transform::Transform,
     triangle::{Triangle, Winding},
+    units::LengthUnit,
     vector::Vector,
 };
@@ -0,0 +1,257 @@
+use std::f64::consts::PI;
+
+use crate::{predicates, Point, Scalar, Sign, Winding};
+
+/// A simple polygon
+///
+/// The polygon is closed implicitly, meaning its last point connects back to
+/// its first. The dimensionality of the polygon is defined by the const generic
+/// `D` parameter.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[repr(C)]
+pub struct Polygon<const D: usize> {
+    points: Vec<Point<D>>,
+}
+
+impl<const D: usize> Polygon<D> {
+    /// Construct a polygon from its points
+    ///
+    /// If the last point is the same as the first, it is dropped. This means
+    /// that it doesn't matter whether the points describe an explicitly closed
+    /// chain or not.
+    pub fn from_points(
+        points: impl IntoIterator<Item = impl Into<Point<D>>>,
+    ) -> Self {
+        let mut points = points.into_iter().map(Into::into).collect::<Vec<_>>();
+
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        Self { points }
+    }
+
+    /// Access the points of the polygon
+    pub fn points(&self) -> &[Point<D>] {
+        &self.points
+    }
+
+    /// Iterate over the edges of the polygon, including the closing one
+    pub fn edges(&self) -> impl Iterator<Item = [Point<D>; 2]> + '_ {
+        self.points
+            .iter()
+            .zip(self.points.iter().cycle().skip(1))
+            .map(|(&a, &b)| [a, b])
+    }
+}
+
+impl Polygon<2> {
+    /// Compute the signed area of the polygon
+    ///
+    /// The area is positive, if the polygon is wound counter-clockwise, and
+    /// negative, if it is wound clockwise.
+    pub fn signed_area(&self) -> Scalar {
+        let double_area = self.edges().fold(Scalar::ZERO, |area, [a, b]| {
+            area + a.coords.cross2d(&b.coords)
+        });
+
+        double_area / 2.
+    }
+
+    /// Determine the winding of the polygon
+    ///
+    /// Returns `None`, if the polygon has no area.
+    pub fn winding(&self) -> Option<Winding> {
+        match self.signed_area().sign() {
+            Sign::Positive => Some(Winding::Ccw),
+            Sign::Negative => Some(Winding::Cw),
+            Sign::Zero => None,
+        }
+    }
+
+    /// Determine whether the polygon is convex
+    ///
+    /// Collinear points don't affect the result. A polygon whose points are
+    /// all on one line is not considered convex.
+    pub fn is_convex(&self) -> bool {
+        let num_points = self.points.len();
+
+        let mut turn = None;
+        let mut total_angle = 0.;
+
+        for i in 0..num_points {
+            let [a, b, c] =
+                [i, i + 1, i + 2].map(|index| self.points[index % num_points]);
+
+            let sign = predicates::orient2d(a, b, c);
+            if sign == Sign::Zero {
+                continue;
+            }
+            if turn.is_some_and(|turn| turn != sign) {
+                return false;
+            }
+            turn = Some(sign);
+
+            let [ab, bc] = [b - a, c - b];
+            total_angle += ab.cross2d(&bc).atan2(ab.dot(&bc)).into_f64();
+        }
+
+        // All turns go in the same direction, but the polygon could still wind
+        // around more than once, like a pentagram does. The total angle is a
+        // multiple of a full turn, so this check doesn't need a tolerance.
+        turn.is_some() && total_angle.abs() < 3. * PI
+    }
+
+    /// Determine where a point is, relative to the polygon
+    ///
+    /// Uses robust predicates, so points on the boundary are reliably detected
+    /// as such. The result does not depend on the winding of the polygon.
+    pub fn contains_point(&self, point: impl Into<Point<2>>) -> PointInPolygon {
+        let point = point.into();
+        let mut winding_number = 0;
+
+        for [a, b] in self.edges() {
+            let sign = predicates::orient2d(a, b, point);
+
+            let is_within_edge_bounds = a.u.min(b.u) <= point.u
+                && point.u <= a.u.max(b.u)
+                && a.v.min(b.v) <= point.v
+                && point.v <= a.v.max(b.v);
+            if sign == Sign::Zero && is_within_edge_bounds {
+                return PointInPolygon::OnBoundary;
+            }
+
+            if a.v <= point.v {
+                if b.v > point.v && sign == Sign::Positive {
+                    winding_number += 1;
+                }
+            } else if b.v <= point.v && sign == Sign::Negative {
+                winding_number -= 1;
+            }
+        }
+
+        if winding_number == 0 {
+            PointInPolygon::Outside
+        } else {
+            PointInPolygon::Inside
+        }
+    }
+}
+
+impl<P, Ps, const D: usize> From<Ps> for Polygon<D>
+where
+    P: Into<Point<D>>,
+    Ps: IntoIterator<Item = P>,
+{
+    fn from(points: Ps) -> Self {
+        Self::from_points(points)
+    }
+}
+
+/// The location of a point relative to a polygon
+///
+/// Returned by [`Polygon::contains_point`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum PointInPolygon {
+    /// The point is inside of the polygon
+    Inside,
+
+    /// The point is outside of the polygon
+    Outside,
+
+    /// The point is on the boundary of the polygon
+    OnBoundary,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Scalar, Winding};
+
+    use super::{PointInPolygon, Polygon};
+
+    #[test]
+    fn signed_area_and_winding() {
+        let ccw = Polygon::from([[0., 0.], [2., 0.], [2., 1.], [0., 1.]]);
+        let cw = Polygon::from([[0., 0.], [0., 1.], [2., 1.], [2., 0.]]);
+        let degenerate = Polygon::from([[0., 0.], [1., 0.], [2., 0.]]);
+
+        assert_eq!(ccw.signed_area(), Scalar::from(2.));
+        assert_eq!(cw.signed_area(), Scalar::from(-2.));
+        assert_eq!(ccw.winding(), Some(Winding::Ccw));
+        assert_eq!(cw.winding(), Some(Winding::Cw));
+        assert_eq!(degenerate.winding(), None);
+    }
+
+    #[test]
+    fn explicitly_closed() {
+        let polygon = Polygon::from([[0., 0.], [1., 0.], [0., 1.], [0., 0.]]);
+        assert_eq!(polygon.points().len(), 3);
+    }
+
+    #[test]
+    fn convexity() {
+        let square =
+            Polygon::from([[0., 0.], [1., 0.], [2., 0.], [2., 2.], [0., 2.]]);
+        let arrow = Polygon::from([[0., 0.], [2., 1.], [0., 2.], [1., 1.]]);
+        let pentagram = Polygon::from([
+            [0., 3.],
+            [2., -3.],
+            [-3., 1.],
+            [3., 1.],
+            [-2., -3.],
+        ]);
+
+        assert!(square.is_convex());
+        assert!(
+            Polygon::from(square.points().iter().rev().copied()).is_convex()
+        );
+        assert!(!arrow.is_convex());
+        assert!(!pentagram.is_convex());
+    }
+
+    #[test]
+    fn contains_point() {
+        let polygon =
+            Polygon::from([[0., 0.], [2., 0.], [1., 1.], [2., 2.], [0., 2.]]);
+
+        for polygon in [
+            polygon.clone(),
+            Polygon::from(polygon.points().iter().rev().copied()),
+        ] {
+            assert_eq!(
+                polygon.contains_point([0.5, 1.]),
+                PointInPolygon::Inside
+            );
+            assert_eq!(
+                polygon.contains_point([1.5, 1.]),
+                PointInPolygon::Outside
+            );
+            assert_eq!(
+                polygon.contains_point([3., 1.]),
+                PointInPolygon::Outside
+            );
+            assert_eq!(
+                polygon.contains_point([1., 1.]),
+                PointInPolygon::OnBoundary
+            );
+            assert_eq!(
+                polygon.contains_point([0., 1.]),
+                PointInPolygon::OnBoundary
+            );
+            assert_eq!(
+                polygon.contains_point([2., 0.]),
+                PointInPolygon::OnBoundary
+            );
+
+            // The ray from this point passes through a vertex.
+            assert_eq!(
+                polygon.contains_point([-1., 2.]),
+                PointInPolygon::Outside
+            );
+            assert_eq!(
+                polygon.contains_point([0.5, 0.]),
+                PointInPolygon::OnBoundary
+            );
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Robust geometric predicates
+//!
+//! Comparing the result of a floating-point computation against zero (or some
+//! epsilon) can give the wrong answer for inputs that are degenerate, or close
+//! to it, like collinear or cocircular points. The predicates in this module
+//! use Shewchuk's adaptive exact arithmetic, via the [robust] crate: They are
+//! as fast as a plain floating-point computation for most inputs, and fall back
+//! to exact arithmetic where the sign of the result would otherwise be wrong.
+//!
+//! Each predicate returns the exact [`Sign`] of its determinant.
+//!
+//! [robust]: https://crates.io/crates/robust
+
+use crate::{Point, Scalar, Sign};
+
+/// Determine on which side of the line through `a` and `b`, `c` is
+///
+/// Returns [`Sign::Positive`], if `a`, `b`, and `c` are in counterclockwise
+/// order, [`Sign::Negative`], if they are in clockwise order, and
+/// [`Sign::Zero`], if they are collinear.
+pub fn orient2d(a: Point<2>, b: Point<2>, c: Point<2>) -> Sign {
+    let [a, b, c] = [a, b, c].map(coord_2d);
+    sign(robust::orient2d(a, b, c))
+}
+
+/// Determine on which side of the plane through `a`, `b`, and `c`, `d` is
+///
+/// Returns [`Sign::Positive`], if `d` is below the plane, where "below" means
+/// that `a`, `b`, and `c` appear in counterclockwise order, when viewed from
+/// above the plane. Returns [`Sign::Negative`], if `d` is above the plane, and
+/// [`Sign::Zero`], if all four points are coplanar.
+pub fn orient3d(a: Point<3>, b: Point<3>, c: Point<3>, d: Point<3>) -> Sign {
+    let [a, b, c, d] = [a, b, c, d].map(coord_3d);
+    sign(robust::orient3d(a, b, c, d))
+}
+
+/// Determine whether `d` is inside the circle through `a`, `b`, and `c`
+///
+/// Expects `a`, `b`, and `c` to be in counterclockwise order (the result is
+/// inverted otherwise). Returns [`Sign::Positive`], if `d` is inside the
+/// circle, [`Sign::Negative`], if it is outside, and [`Sign::Zero`], if all
+/// four points are cocircular.
+pub fn incircle(a: Point<2>, b: Point<2>, c: Point<2>, d: Point<2>) -> Sign {
+    let [a, b, c, d] = [a, b, c, d].map(coord_2d);
+    sign(robust::incircle(a, b, c, d))
+}
+
+/// Determine whether `e` is inside the sphere through `a`, `b`, `c`, and `d`
+///
+/// Expects the points to be ordered such that [`orient3d`] returns
+/// [`Sign::Positive`] for them (the result is inverted otherwise). Returns
+/// [`Sign::Positive`], if `e` is inside the sphere, [`Sign::Negative`], if it
+/// is outside, and [`Sign::Zero`], if all five points are cospherical.
+pub fn insphere(
+    a: Point<3>,
+    b: Point<3>,
+    c: Point<3>,
+    d: Point<3>,
+    e: Point<3>,
+) -> Sign {
+    let [a, b, c, d, e] = [a, b, c, d, e].map(coord_3d);
+    sign(robust::insphere(a, b, c, d, e))
+}
+
+/// Determine whether three points are on one line
+///
+/// Works for points of any dimension up to three, by checking the orientation
+/// of their projections into each coordinate plane. The points are collinear,
+/// if and only if all projections are.
+pub fn are_collinear<const D: usize>(points: [Point<D>; 3]) -> bool {
+    let [a, b, c] = points.map(Point::to_xyz);
+
+    [[0, 1], [1, 2], [2, 0]].into_iter().all(|[i, j]| {
+        let [a, b, c] = [a, b, c].map(|point| {
+            Point::from([
+                point.coords.components[i],
+                point.coords.components[j],
+            ])
+        });
+        orient2d(a, b, c) == Sign::Zero
+    })
+}
+
+fn coord_2d(point: Point<2>) -> robust::Coord<f64> {
+    robust::Coord {
+        x: point.u.into_f64(),
+        y: point.v.into_f64(),
+    }
+}
+
+fn coord_3d(point: Point<3>) -> robust::Coord3D<f64> {
+    robust::Coord3D {
+        x: point.x.into_f64(),
+        y: point.y.into_f64(),
+        z: point.z.into_f64(),
+    }
+}
+
+fn sign(value: f64) -> Sign {
+    Scalar::from_f64(value).sign()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Sign};
+
+    use super::{are_collinear, incircle, orient2d, orient3d};
+
+    #[test]
+    fn orient2d_nearly_collinear() {
+        // The determinants for these points are zero or close to it, which is
+        // where a naive computation gets affected by rounding errors.
+        let a = Point::from([0.5, 0.5]);
+        let b = Point::from([12., 12.]);
+        let c = Point::from([24., 24.]);
+        let d = Point::from([0.5 + f64::EPSILON, 0.5]);
+
+        assert_eq!(orient2d(a, b, c), Sign::Zero);
+        assert_eq!(orient2d(d, b, c), Sign::Negative);
+        assert_eq!(orient2d(c, b, d), Sign::Positive);
+    }
+
+    #[test]
+    fn orient3d_coplanar() {
+        let a = Point::from([0., 0., 0.]);
+        let b = Point::from([1., 0., 0.]);
+        let c = Point::from([0., 1., 0.]);
+
+        assert_eq!(orient3d(a, b, c, Point::from([0.3, 0.7, 0.])), Sign::Zero);
+        assert_eq!(
+            orient3d(a, b, c, Point::from([0., 0., -1.])),
+            Sign::Positive
+        );
+        assert_eq!(
+            orient3d(a, b, c, Point::from([0., 0., 1.])),
+            Sign::Negative
+        );
+    }
+
+    #[test]
+    fn incircle_cocircular() {
+        let a = Point::from([1., 0.]);
+        let b = Point::from([0., 1.]);
+        let c = Point::from([-1., 0.]);
+
+        assert_eq!(incircle(a, b, c, Point::from([0., -1.])), Sign::Zero);
+        assert_eq!(incircle(a, b, c, Point::from([0., 0.])), Sign::Positive);
+        assert_eq!(incircle(a, b, c, Point::from([2., 0.])), Sign::Negative);
+    }
+
+    #[test]
+    fn collinear_in_3d() {
+        assert!(are_collinear(
+            [[0., 0., 0.], [1., 1., 1.], [3., 3., 3.]].map(Point::from)
+        ));
+        assert!(!are_collinear(
+            [[0., 0., 0.], [1., 1., 1.], [3., 3., 3.1]].map(Point::from)
+        ));
+    }
+}
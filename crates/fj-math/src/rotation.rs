@@ -0,0 +1,254 @@
+use std::ops;
+
+use crate::{Point, Scalar, Transform, Vector};
+
+/// A rotation in 3D space
+///
+/// Can be constructed from, and converted to, the common representations of
+/// rotations. Use [`Transform::from_rotation`] to combine it with other
+/// transforms.
+///
+/// All angles are in radians.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rotation(nalgebra::UnitQuaternion<f64>);
+
+impl Rotation {
+    /// Construct the rotation that doesn't rotate anything
+    pub fn identity() -> Self {
+        Self(nalgebra::UnitQuaternion::identity())
+    }
+
+    /// Construct a rotation around an axis
+    ///
+    /// The length of `axis` is ignored. Returns the identity rotation, if it is
+    /// zero.
+    pub fn from_axis_angle(
+        axis: impl Into<Vector<3>>,
+        angle_rad: impl Into<Scalar>,
+    ) -> Self {
+        let axis = axis.into();
+        if axis.magnitude() == Scalar::ZERO {
+            return Self::identity();
+        }
+
+        Self::from_scaled_axis(axis.normalize() * angle_rad.into())
+    }
+
+    /// Construct a rotation from a scaled axis
+    ///
+    /// The direction of the vector defines the rotation axis. Its length
+    /// defines the angle of the rotation. This is the representation that
+    /// [`Transform::rotation`] expects.
+    pub fn from_scaled_axis(axis_angle: impl Into<Vector<3>>) -> Self {
+        Self(nalgebra::UnitQuaternion::from_scaled_axis(
+            axis_angle.into().to_na(),
+        ))
+    }
+
+    /// Construct a rotation from Euler angles
+    ///
+    /// The resulting rotation first rotates around the x-axis by `roll`, then
+    /// around the y-axis by `pitch`, then around the z-axis by `yaw`. All axes
+    /// are fixed, meaning they don't rotate with the object.
+    pub fn from_euler_angles(
+        roll: impl Into<Scalar>,
+        pitch: impl Into<Scalar>,
+        yaw: impl Into<Scalar>,
+    ) -> Self {
+        Self(nalgebra::UnitQuaternion::from_euler_angles(
+            roll.into().into_f64(),
+            pitch.into().into_f64(),
+            yaw.into().into_f64(),
+        ))
+    }
+
+    /// Construct the shortest rotation that rotates one direction onto another
+    ///
+    /// If the directions are opposite, the rotation is a half-turn around an
+    /// arbitrary axis that is perpendicular to both.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if either vector has zero length.
+    pub fn between(
+        from: impl Into<Vector<3>>,
+        to: impl Into<Vector<3>>,
+    ) -> Self {
+        let from = from.into();
+        let to = to.into();
+
+        assert!(
+            from.magnitude() != Scalar::ZERO && to.magnitude() != Scalar::ZERO,
+            "Can't compute rotation between zero-length vectors"
+        );
+
+        if let Some(rotation) = nalgebra::UnitQuaternion::rotation_between(
+            &from.to_na(),
+            &to.to_na(),
+        ) {
+            return Self(rotation);
+        }
+
+        // The directions are opposite. Any axis perpendicular to them works.
+        let axis = [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()]
+            .into_iter()
+            .map(|candidate| from.cross(&candidate))
+            .max_by_key(|axis| axis.magnitude())
+            .expect("Array is not empty");
+
+        Self::from_axis_angle(axis, Scalar::PI)
+    }
+
+    /// Construct a rotation from a quaternion
+    ///
+    /// Expects the components in the order `[w, x, y, z]`, where `w` is the
+    /// scalar part. The quaternion is normalized, so it doesn't need to be a
+    /// unit quaternion.
+    pub fn from_quaternion(components: [impl Into<Scalar>; 4]) -> Self {
+        let [w, x, y, z] = components.map(|c| c.into().into_f64());
+        Self(nalgebra::UnitQuaternion::from_quaternion(
+            nalgebra::Quaternion::new(w, x, y, z),
+        ))
+    }
+
+    /// Convert the rotation into a unit quaternion
+    ///
+    /// Returns the components in the order `[w, x, y, z]`, where `w` is the
+    /// scalar part.
+    pub fn to_quaternion(&self) -> [Scalar; 4] {
+        let q = self.0.quaternion();
+        [q.w, q.i, q.j, q.k].map(Scalar::from_f64)
+    }
+
+    /// Convert the rotation into a scaled axis
+    ///
+    /// See [`Rotation::from_scaled_axis`].
+    pub fn to_scaled_axis(&self) -> Vector<3> {
+        Vector::from(self.0.scaled_axis())
+    }
+
+    /// Convert the rotation into Euler angles
+    ///
+    /// Returns `[roll, pitch, yaw]`. See [`Rotation::from_euler_angles`].
+    pub fn to_euler_angles(&self) -> [Scalar; 3] {
+        let (roll, pitch, yaw) = self.0.euler_angles();
+        [roll, pitch, yaw].map(Scalar::from_f64)
+    }
+
+    /// Access the angle of the rotation
+    pub fn angle(&self) -> Scalar {
+        Scalar::from_f64(self.0.angle())
+    }
+
+    /// Compute the inverse rotation
+    pub fn inverse(&self) -> Self {
+        Self(self.0.inverse())
+    }
+
+    /// Rotate a vector
+    pub fn rotate_vector(&self, vector: &Vector<3>) -> Vector<3> {
+        Vector::from(self.0 * vector.to_na())
+    }
+
+    /// Rotate a point around the origin
+    pub fn rotate_point(&self, point: &Point<3>) -> Point<3> {
+        Point::from(self.0 * point.to_na())
+    }
+
+    /// Convert the rotation into an nalgebra unit quaternion
+    pub fn to_na(&self) -> nalgebra::UnitQuaternion<f64> {
+        self.0
+    }
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl ops::Mul<Self> for Rotation {
+    type Output = Self;
+
+    /// Combine two rotations
+    ///
+    /// The resulting rotation first applies `rhs`, then `self`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl From<Rotation> for Transform {
+    fn from(rotation: Rotation) -> Self {
+        Self::from_rotation(rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Scalar, Vector};
+
+    use super::Rotation;
+
+    #[test]
+    fn from_euler_angles() {
+        let rotation =
+            Rotation::from_euler_angles(Scalar::PI / 2., 0., Scalar::PI / 2.);
+
+        // Rotating around x moves y to z. Rotating around z leaves it there.
+        assert_abs_diff_eq!(
+            rotation.rotate_vector(&Vector::unit_y()),
+            Vector::unit_z(),
+            epsilon = Scalar::from(1e-12),
+        );
+
+        let [roll, pitch, yaw] = rotation.to_euler_angles();
+        assert_abs_diff_eq!(
+            roll,
+            Scalar::PI / 2.,
+            epsilon = Scalar::from(1e-12)
+        );
+        assert_abs_diff_eq!(pitch, Scalar::ZERO, epsilon = Scalar::from(1e-12));
+        assert_abs_diff_eq!(
+            yaw,
+            Scalar::PI / 2.,
+            epsilon = Scalar::from(1e-12)
+        );
+    }
+
+    #[test]
+    fn between() {
+        let from = Vector::from([1., 0., 0.]);
+
+        for to in [[0., 3., 0.], [1., 1., 1.], [-1., 0., 0.]] {
+            let to = Vector::from(to);
+            let rotated = Rotation::between(from, to).rotate_vector(&from);
+
+            assert_abs_diff_eq!(
+                rotated,
+                to.normalize(),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    #[test]
+    fn quaternion_round_trip() {
+        let rotation = Rotation::from_axis_angle([1., 2., 3.], 0.5);
+        let quaternion = rotation.to_quaternion();
+
+        let [w, ..] = quaternion;
+        assert_abs_diff_eq!(
+            w,
+            Scalar::from_f64(0.25_f64.cos()),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            Rotation::from_quaternion(quaternion).to_scaled_axis(),
+            rotation.to_scaled_axis(),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+}
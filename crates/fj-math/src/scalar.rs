@@ -148,6 +148,11 @@ impl Scalar {
     pub fn atan2(self, other: Self) -> Self {
         self.0.atan2(other.0).into()
     }
+
+    /// Wrap an angle, in radians, into the range `[-pi, pi]`
+    pub fn wrap_angle(self) -> Self {
+        self - Self::TAU * (self / Self::TAU).round()
+    }
 }
 
 impl PartialEq for Scalar {
@@ -592,7 +597,7 @@ impl approx::AbsDiffEq for Scalar {
 /// The sign of a [`Scalar`]
 ///
 /// See [`Scalar::sign`]
-#[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Sign {
     /// The scalar is negative
     Negative,
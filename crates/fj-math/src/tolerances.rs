@@ -0,0 +1,179 @@
+use crate::{
+    arithmetic::{self, Arithmetic},
+    Point, Scalar, Vector,
+};
+
+/// The tolerances used to compare geometric values
+///
+/// Floating-point computations accumulate errors, so values that should be
+/// equal are often not. This struct defines, in one place, how far apart
+/// values can be, while still being considered equal. Code that needs to make
+/// such a decision should use the methods provided here, instead of comparing
+/// against zero or against a magic number.
+///
+/// The defaults are suitable for models in millimeters, ranging from features
+/// of a few micrometers to parts of a few meters. If your model is far outside
+/// of that, adjust the tolerances accordingly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerances {
+    /// The minimum distance between distinct points
+    ///
+    /// Points that are closer than this are considered to be the same point.
+    /// The same goes for lengths: A length smaller than this is considered to
+    /// be zero.
+    pub linear: Scalar,
+
+    /// The minimum angle between distinct directions, in radians
+    ///
+    /// Directions whose angle is smaller than this are considered parallel.
+    pub angular: Scalar,
+
+    /// The maximum distance between points that are supposed to be identical
+    ///
+    /// Points that are the same point, as far as the shape's topology is
+    /// concerned, might still have a distance between them, due to the
+    /// inaccuracy of their numerical representation. If that distance is
+    /// larger than this, something went wrong.
+    pub identical_point_distance: Scalar,
+}
+
+impl Tolerances {
+    /// Determine whether a length is considered to be zero
+    pub fn is_zero_length(&self, length: impl Into<Scalar>) -> bool {
+        length.into().abs() < self.linear
+    }
+
+    /// Determine whether two points are considered to be the same point
+    pub fn are_coincident<const D: usize>(
+        &self,
+        a: impl Into<Point<D>>,
+        b: impl Into<Point<D>>,
+    ) -> bool {
+        self.is_zero_length(a.into().distance_to(&b.into()))
+    }
+
+    /// Determine whether two directions are considered parallel
+    ///
+    /// This is the case, if they point in the same or opposite directions.
+    /// Returns `true`, if either vector has zero length, as no direction can be
+    /// derived from such a vector.
+    pub fn are_parallel<const D: usize>(
+        &self,
+        a: &Vector<D>,
+        b: &Vector<D>,
+    ) -> bool {
+        if Arithmetic::is_exact() {
+            let [a, b] = [a, b].map(|vector| arithmetic::vector(*vector));
+            return arithmetic::are_parallel(&a, &b, self);
+        }
+
+        let (Some(a), Some(b)) = (self.direction_of(a), self.direction_of(b))
+        else {
+            return true;
+        };
+
+        // The part of `a` that is perpendicular to `b` is the sine of the
+        // angle between them.
+        let sin = (a - b * a.dot(&b)).magnitude();
+        sin < self.angular.sin_cos().0
+    }
+
+    /// Determine whether two directions are considered perpendicular
+    ///
+    /// Returns `true`, if either vector has zero length, as no direction can be
+    /// derived from such a vector.
+    pub fn are_perpendicular<const D: usize>(
+        &self,
+        a: &Vector<D>,
+        b: &Vector<D>,
+    ) -> bool {
+        if Arithmetic::is_exact() {
+            let [a, b] = [a, b].map(|vector| arithmetic::vector(*vector));
+            return arithmetic::are_perpendicular(&a, &b, self);
+        }
+
+        let (Some(a), Some(b)) = (self.direction_of(a), self.direction_of(b))
+        else {
+            return true;
+        };
+
+        // The dot product of two unit vectors is the cosine of the angle
+        // between them, which is the sine of that angle's deviation from a
+        // right angle.
+        a.dot(&b).abs() < self.angular.sin_cos().0
+    }
+
+    fn direction_of<const D: usize>(
+        &self,
+        vector: &Vector<D>,
+    ) -> Option<Vector<D>> {
+        if self.is_zero_length(vector.magnitude()) {
+            return None;
+        }
+
+        Some(vector.normalize())
+    }
+}
+
+impl Default for Tolerances {
+    fn default() -> Self {
+        Self {
+            linear: Scalar::from_f64(5e-7), // 0.5 µm
+
+            // About 0.01 arc seconds. Over a distance of 10 m, this results
+            // in a deviation of 0.5 µm, which matches the linear tolerance.
+            angular: Scalar::from_f64(5e-8),
+
+            // This value was chosen pretty arbitrarily. Seems small enough to
+            // catch errors. If it turns out it's too small (because it produces
+            // false positives due to floating-point accuracy issues), we can
+            // adjust it.
+            identical_point_distance: Scalar::from_f64(5e-14),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Scalar, Vector};
+
+    use super::Tolerances;
+
+    #[test]
+    fn are_parallel() {
+        let tolerances = Tolerances::default();
+
+        let a = Vector::from([1., 0., 0.]);
+        let almost_a = Vector::from([1., 1e-9, 0.]);
+        let opposite_a = Vector::from([-2., 0., 0.]);
+        let b = Vector::from([1., 1e-3, 0.]);
+
+        assert!(tolerances.are_parallel(&a, &almost_a));
+        assert!(tolerances.are_parallel(&a, &opposite_a));
+        assert!(!tolerances.are_parallel(&a, &b));
+    }
+
+    #[test]
+    fn are_perpendicular() {
+        let tolerances = Tolerances::default();
+
+        let a = Vector::from([1., 0., 0.]);
+        let b = Vector::from([1e-9, 1., 0.]);
+        let c = Vector::from([1e-3, 1., 0.]);
+
+        assert!(tolerances.are_perpendicular(&a, &b));
+        assert!(!tolerances.are_perpendicular(&a, &c));
+    }
+
+    #[test]
+    fn small_features_on_large_parts() {
+        // A small feature stays distinct, even far from the origin.
+        let tolerances = Tolerances::default();
+
+        let a = [1000., 0., 0.];
+        let b = [1000.0001, 0., 0.];
+
+        assert!(!tolerances.are_coincident(a, b));
+        assert!(tolerances.is_zero_length(Scalar::from(1e-7)));
+    }
+}
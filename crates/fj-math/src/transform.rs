@@ -1,8 +1,8 @@
 use std::ops;
 
-use nalgebra::Perspective3;
+use nalgebra::{Orthographic3, Perspective3};
 
-use crate::{Circle, Line, Scalar};
+use crate::{Circle, Line, Rotation, Scalar};
 
 use super::{Aabb, Point, Segment, Triangle, Vector};
 
@@ -40,6 +40,15 @@ impl Transform {
         ))
     }
 
+    /// Construct a transform from a rotation
+    ///
+    /// See [`Rotation`] for the ways to construct one.
+    pub fn from_rotation(rotation: Rotation) -> Self {
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            rotation.to_na().to_homogeneous(),
+        ))
+    }
+
     /// Construct a scaling
     pub fn scale(scaling_factor: f64) -> Self {
         Self(nalgebra::Transform::from_matrix_unchecked(
@@ -126,6 +135,35 @@ impl Transform {
         array.map(Scalar::from)
     }
 
+    /// Project transform according to an orthographic camera specification,
+    /// return data as an array. Used primarily for graphics code.
+    ///
+    /// The visible area is centered on the camera, and extends by
+    /// `half_width` and `half_height` to each side.
+    pub fn project_orthographic_to_array(
+        &self,
+        half_width: f64,
+        half_height: f64,
+        znear: f64,
+        zfar: f64,
+    ) -> [Scalar; 16] {
+        let projection = Orthographic3::new(
+            -half_width,
+            half_width,
+            -half_height,
+            half_height,
+            znear,
+            zfar,
+        );
+
+        let mut array = [0.; 16];
+        array.copy_from_slice(
+            (projection.to_projective() * self.0).matrix().as_slice(),
+        );
+
+        array.map(Scalar::from)
+    }
+
     /// Return a copy of the inner nalgebra transform
     pub fn get_inner(&self) -> nalgebra::Transform<f64, nalgebra::TAffine, 3> {
         self.0
@@ -224,4 +262,27 @@ mod tests {
             epsilon = 1e-8,
         );
     }
+
+    #[test]
+    fn project_orthographic_to_array() {
+        let transform = Transform::translation([0., 0., -1.]);
+        let matrix = transform
+            .project_orthographic_to_array(2., 1., 1., 3.)
+            .map(|scalar| scalar.into_f64());
+
+        // The point ends up halfway between the near and far planes, and
+        // halfway between the center and the edges of the visible area.
+        let point = [1., 0.5, -1., 1.];
+        let projected: [f64; 4] = [0, 1, 2, 3].map(|row| {
+            (0..4)
+                .map(|column| matrix[column * 4 + row] * point[column])
+                .sum()
+        });
+
+        assert_abs_diff_eq!(
+            &projected[..],
+            &[0.5, 0.5, 0., 1.][..],
+            epsilon = 1e-8,
+        );
+    }
 }
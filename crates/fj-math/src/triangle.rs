@@ -1,6 +1,6 @@
-use parry3d_f64::query::{Ray, RayCast as _};
+use parry3d_f64::query::{PointQuery as _, Ray, RayCast as _};
 
-use crate::Vector;
+use crate::{predicates, Sign, Vector};
 
 use super::{Point, Scalar};
 
@@ -23,16 +23,13 @@ impl<const D: usize> Triangle<D> {
     ) -> Result<Self, NotATriangle<D>> {
         let points = points.map(Into::into);
 
-        let area = {
-            let [a, b, c] = points.map(Point::to_xyz);
-            (b - a).cross(&(c - a)).magnitude()
-        };
-
-        // A triangle is not valid if it doesn't span any area
-        if area != Scalar::from(0.0) {
-            Ok(Self { points })
-        } else {
+        // A triangle is not valid if it doesn't span any area. This needs to
+        // be decided exactly, or `Triangle::winding` might later find points
+        // that were accepted here to be collinear.
+        if predicates::are_collinear(points) {
             Err(NotATriangle { points })
+        } else {
+            Ok(Self { points })
         }
     }
 
@@ -58,23 +55,16 @@ impl<const D: usize> Triangle<D> {
 impl Triangle<2> {
     /// Returns the direction of the line through the points of the triangle.
     pub fn winding(&self) -> Winding {
-        let [pa, pb, pc] = self.points.map(|point| robust::Coord {
-            x: point.u,
-            y: point.v,
-        });
-        let orient2d = robust::orient2d(pa, pb, pc);
-
-        if orient2d < 0. {
-            return Winding::Cw;
-        }
-        if orient2d > 0. {
-            return Winding::Ccw;
+        let [a, b, c] = self.points;
+
+        match predicates::orient2d(a, b, c) {
+            Sign::Negative => Winding::Cw,
+            Sign::Positive => Winding::Ccw,
+            Sign::Zero => unreachable!(
+                "Points don't form a triangle, but this was verified in the \
+                constructor."
+            ),
         }
-
-        unreachable!(
-            "Points don't form a triangle, but this was verified in the \
-            constructor."
-        )
     }
 }
 
@@ -102,6 +92,14 @@ impl Triangle<3> {
             .map(Into::into)
     }
 
+    /// Find the point on the triangle that is closest to the provided point
+    pub fn closest_point(&self, point: impl Into<Point<3>>) -> Point<3> {
+        let projection = self
+            .to_parry()
+            .project_local_point(&point.into().to_na(), true);
+        Point::from(projection.point)
+    }
+
     /// Compute the triangle's normal
     pub fn normal(&self) -> Vector<3> {
         self.to_parry()
@@ -195,4 +193,19 @@ mod tests {
             Triangle::from([[0.0, 0.0, 0.0], [2.0, 1.0, 0.0], [2.0, 0.0, 0.0]]);
         assert_eq!(triangle.normal(), Vector::from([0.0, 0.0, -1.0]));
     }
+
+    #[test]
+    fn closest_point() {
+        let triangle =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+
+        assert_eq!(
+            triangle.closest_point([0.5, 0.5, 1.0]),
+            Point::from([0.5, 0.5, 0.0])
+        );
+        assert_eq!(
+            triangle.closest_point([-1.0, -1.0, 0.0]),
+            Point::from([0.0, 0.0, 0.0])
+        );
+    }
 }
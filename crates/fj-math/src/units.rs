@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::{Point, Scalar};
+
+/// A unit of length
+///
+/// Coordinates in Fornjot are plain numbers. The unit they are measured in is
+/// a convention between the model and whatever consumes it. This type makes
+/// that convention explicit, so that values can be converted where models
+/// measured in different units meet, for example when exporting.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum LengthUnit {
+    /// Millimeters
+    ///
+    /// This is the unit that Fornjot assumes, if none is specified.
+    #[default]
+    Millimeter,
+
+    /// Centimeters
+    Centimeter,
+
+    /// Meters
+    Meter,
+
+    /// Inches
+    Inch,
+
+    /// Feet
+    Foot,
+}
+
+impl LengthUnit {
+    /// Access the length of one of this unit, in millimeters
+    pub fn in_millimeters(&self) -> Scalar {
+        let millimeters = match self {
+            Self::Millimeter => 1.,
+            Self::Centimeter => 10.,
+            Self::Meter => 1000.,
+            Self::Inch => 25.4,
+            Self::Foot => 304.8,
+        };
+
+        Scalar::from_f64(millimeters)
+    }
+
+    /// Access the abbreviation of this unit, like `mm` or `in`
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Millimeter => "mm",
+            Self::Centimeter => "cm",
+            Self::Meter => "m",
+            Self::Inch => "in",
+            Self::Foot => "ft",
+        }
+    }
+
+    /// Convert a point from this unit into another one
+    pub fn convert_point<const D: usize>(
+        &self,
+        point: impl Into<Point<D>>,
+        to: LengthUnit,
+    ) -> Point<D> {
+        let point = point.into();
+        Point::from(
+            point
+                .coords
+                .components
+                .map(|component| component.convert_length(*self, to)),
+        )
+    }
+}
+
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.abbreviation())
+    }
+}
+
+impl Scalar {
+    /// Convert a length from one unit into another
+    ///
+    /// Interprets this scalar as a length in the unit `from`, and returns the
+    /// same length, measured in the unit `to`.
+    pub fn convert_length(self, from: LengthUnit, to: LengthUnit) -> Self {
+        if from == to {
+            return self;
+        }
+
+        self * from.in_millimeters() / to.in_millimeters()
+    }
+
+    /// Construct a length in millimeters from a length in inches
+    pub fn from_inches(inches: impl Into<Scalar>) -> Self {
+        inches
+            .into()
+            .convert_length(LengthUnit::Inch, LengthUnit::Millimeter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Scalar};
+
+    use super::LengthUnit;
+
+    #[test]
+    fn convert_length() {
+        let length = Scalar::from(2.);
+
+        assert_eq!(
+            length.convert_length(LengthUnit::Inch, LengthUnit::Millimeter),
+            Scalar::from(50.8)
+        );
+        assert_eq!(
+            length.convert_length(LengthUnit::Meter, LengthUnit::Centimeter),
+            Scalar::from(200.)
+        );
+        assert_eq!(Scalar::from_inches(1.), Scalar::from(25.4));
+    }
+
+    #[test]
+    fn convert_point() {
+        let point = LengthUnit::Centimeter
+            .convert_point([1., 2., 3.], LengthUnit::Millimeter);
+        assert_eq!(point, Point::from([10., 20., 30.]));
+    }
+}
@@ -0,0 +1,325 @@
+//! # Fornjot Script
+//!
+//! [Fornjot] is an early-stage b-rep CAD kernel written in Rust. The kernel is
+//! split into multiple libraries that can be used semi-independently, and this
+//! is one of those.
+//!
+//! This library evaluates models that are written in [Rhai], a scripting
+//! language. Scripts can call a curated subset of the operations in
+//! [`fj-core`], and don't need to be compiled. This makes them a quick way to
+//! iterate on simple models.
+//!
+//! The following functions are available to scripts. Points and vectors are
+//! arrays of numbers:
+//!
+//! - `polygon(points)`: Create a sketch with a polygon in it
+//! - `circle(radius)`, `circle(center, radius)`: Create a sketch with a circle
+//!   in it
+//! - `cuboid(x, y, z)`: Create a cuboid of the provided size, centered on the
+//!   xy-plane
+//! - `sweep(sketch, path)`: Sweep a sketch along the provided vector, starting
+//!   from the xy-plane
+//! - `translate(solid, offset)`: Translate a solid
+//! - `rotate(solid, axis_angle)`: Rotate a solid around the origin; the length
+//!   of the axis is the angle, in radians
+//! - `merge(a, b)`: Merge two sketches or two solids; this does not compute
+//!   their union, so they should not overlap
+//!
+//! Functions can also be called like methods. The script must evaluate to a
+//! solid:
+//!
+//! ```rhai
+//! let base = cuboid(4, 4, 1);
+//! let post = circle(1.0).sweep([0, 0, 2]).translate([0, 0, 1]);
+//!
+//! base.merge(post)
+//! ```
+//!
+//! [Fornjot]: https://www.fornjot.app/
+//! [Rhai]: https://rhai.rs/
+//! [`fj-core`]: https://crates.io/crates/fj-core
+
+use std::{
+    mem,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use fj::core::{
+    operations::{
+        build::{BuildRegion, BuildSketch},
+        merge::Merge,
+        sweep::SweepSketch,
+        transform::TransformObject,
+        update::UpdateSketch,
+    },
+    topology::{Region, Sketch, Solid},
+    validation::ValidationErrors,
+    Core,
+};
+use rhai::{Array, Dynamic, Engine, EvalAltResult};
+
+/// Evaluate a script, building the model that it defines in the provided core
+///
+/// Returns the solid that the script evaluates to. Validation errors of the
+/// objects that the script created are returned as an error.
+pub fn evaluate(source: &str, core: &mut Core) -> Result<Solid, Error> {
+    // The functions that are registered with the engine need to access the
+    // core, while it is running the script. The core is moved back out, once
+    // that is over.
+    let shared = Arc::new(Mutex::new(mem::take(core)));
+
+    let engine = engine(&shared);
+    let result = engine.eval::<Solid>(source);
+    drop(engine);
+
+    *core = Arc::into_inner(shared)
+        .expect("Engine has been dropped; no other references to core")
+        .into_inner()
+        .expect("Functions don't panic while holding the lock");
+
+    let solid = result?;
+    core.layers.validation.take_errors()?;
+
+    Ok(solid)
+}
+
+fn engine(core: &Arc<Mutex<Core>>) -> Engine {
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<Sketch>("Sketch")
+        .register_type_with_name::<Solid>("Solid");
+
+    let c = core.clone();
+    engine.register_fn(
+        "polygon",
+        move |points: Array| -> ScriptResult<Sketch> {
+            let points = points
+                .into_iter()
+                .map(point::<2>)
+                .collect::<Result<Vec<_>, _>>()?;
+            if points.len() < 3 {
+                return Err(error("A polygon needs at least 3 points"));
+            }
+
+            let core = &mut *lock(&c);
+            let surface = core.layers.topology.surfaces.space_2d();
+            let region = Region::polygon(points, surface, core);
+            Ok(
+                Sketch::empty(&core.layers.topology)
+                    .add_regions([region], core),
+            )
+        },
+    );
+
+    let c = core.clone();
+    engine.register_fn("circle", move |radius: Dynamic| {
+        circle(&c, [0., 0.], radius)
+    });
+
+    let c = core.clone();
+    engine.register_fn("circle", move |center: Dynamic, radius: Dynamic| {
+        circle(&c, point::<2>(center)?, radius)
+    });
+
+    let c = core.clone();
+    engine.register_fn(
+        "cuboid",
+        move |x: Dynamic, y: Dynamic, z: Dynamic| -> ScriptResult<Solid> {
+            let [x, y, z] = [number(x)?, number(y)?, number(z)?];
+
+            let core = &mut *lock(&c);
+            let surface = core.layers.topology.surfaces.space_2d();
+            let bottom_surface = core.layers.topology.surfaces.xy_plane();
+
+            let region = Region::polygon(
+                [
+                    [-x / 2., -y / 2.],
+                    [x / 2., -y / 2.],
+                    [x / 2., y / 2.],
+                    [-x / 2., y / 2.],
+                ],
+                surface,
+                core,
+            );
+            Ok(Sketch::empty(&core.layers.topology)
+                .add_regions([region], core)
+                .sweep_sketch(bottom_surface, [0., 0., z], core))
+        },
+    );
+
+    let c = core.clone();
+    engine.register_fn(
+        "sweep",
+        move |sketch: Sketch, path: Dynamic| -> ScriptResult<Solid> {
+            let path = point::<3>(path)?;
+
+            let core = &mut *lock(&c);
+            let surface = core.layers.topology.surfaces.xy_plane();
+            Ok(sketch.sweep_sketch(surface, path, core))
+        },
+    );
+
+    let c = core.clone();
+    engine.register_fn(
+        "translate",
+        move |solid: Solid, offset: Dynamic| -> ScriptResult<Solid> {
+            let offset = point::<3>(offset)?;
+            Ok(solid.translate(offset, &mut lock(&c)))
+        },
+    );
+
+    let c = core.clone();
+    engine.register_fn(
+        "rotate",
+        move |solid: Solid, axis_angle: Dynamic| -> ScriptResult<Solid> {
+            let axis_angle = point::<3>(axis_angle)?;
+            Ok(solid.rotate(axis_angle, &mut lock(&c)))
+        },
+    );
+
+    let c = core.clone();
+    engine.register_fn("merge", move |a: Sketch, b: Sketch| {
+        a.add_regions(b.regions().iter().cloned(), &mut lock(&c))
+    });
+
+    let c = core.clone();
+    engine.register_fn("merge", move |a: Solid, b: Solid| {
+        a.merge(&b, &mut lock(&c))
+    });
+
+    engine
+}
+
+fn circle(
+    core: &Mutex<Core>,
+    center: [f64; 2],
+    radius: Dynamic,
+) -> ScriptResult<Sketch> {
+    let radius = number(radius)?;
+    if radius <= 0. {
+        return Err(error("The radius of a circle must be positive"));
+    }
+
+    let core = &mut *lock(core);
+    let surface = core.layers.topology.surfaces.space_2d();
+    let region = Region::circle(center, radius, surface, core);
+    Ok(Sketch::empty(&core.layers.topology).add_regions([region], core))
+}
+
+fn point<const D: usize>(value: Dynamic) -> ScriptResult<[f64; D]> {
+    let type_name = value.type_name();
+    let components = value
+        .into_array()
+        .map_err(|_| error(format!("Expected array, found `{type_name}`")))?;
+
+    let components = components
+        .into_iter()
+        .map(number)
+        .collect::<Result<Vec<_>, _>>()?;
+    let len = components.len();
+
+    components
+        .try_into()
+        .map_err(|_| error(format!("Expected {D} components, found {len}")))
+}
+
+fn number(value: Dynamic) -> ScriptResult<f64> {
+    if let Ok(value) = value.as_float() {
+        return Ok(value);
+    }
+    if let Ok(value) = value.as_int() {
+        return Ok(value as f64);
+    }
+
+    Err(error(format!(
+        "Expected number, found `{}`",
+        value.type_name()
+    )))
+}
+
+fn lock(core: &Mutex<Core>) -> MutexGuard<Core> {
+    core.lock()
+        .expect("Functions don't panic while holding the lock")
+}
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+fn error(message: impl Into<String>) -> Box<EvalAltResult> {
+    message.into().into()
+}
+
+/// Error returned by [`evaluate`]
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error evaluating the script
+    #[error("Error evaluating script")]
+    Script(#[from] Box<EvalAltResult>),
+
+    /// The model that the script created is not valid
+    #[error(transparent)]
+    Validation(#[from] ValidationErrors),
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::core::Core;
+
+    use super::{evaluate, Error};
+
+    #[test]
+    fn cuboid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = evaluate("cuboid(1, 2, 3.5)", &mut core)?;
+
+        let faces = solid.shells().first().faces();
+        assert_eq!(faces.len(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_and_merge() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = evaluate(
+            "
+                let base = polygon([[0, 0], [2, 0], [0, 2]]).sweep([0, 0, 1]);
+                let post = circle([5, 5], 0.5)
+                    .sweep([0, 0, 2])
+                    .rotate([0, 0, 1])
+                    .translate([0, 0, 1]);
+
+                base.merge(post)
+            ",
+            &mut core,
+        )?;
+
+        assert_eq!(solid.shells().len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn script_errors() {
+        let mut core = Core::new();
+
+        for source in [
+            "polygon([[0, 0], [1, 0]]).sweep([0, 0, 1])",
+            "circle(-1).sweep([0, 0, 1])",
+            "cuboid(1, 1, \"1\")",
+            "circle(1)",
+            "sphere(1)",
+        ] {
+            let result = evaluate(source, &mut core);
+            assert!(
+                matches!(result, Err(Error::Script(_))),
+                "Expected script error for `{source}`"
+            );
+        }
+
+        // The core is still usable afterwards.
+        evaluate("cuboid(1, 1, 1)", &mut core).unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+//! # Fornjot Script CLI
+//!
+//! Displays the model that a script defines, and updates it whenever the
+//! script is saved. Can also export the model instead. See the [`fj_script`]
+//! library for the functions that scripts can use.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+use fj::{
+    core::algorithms::approx::Tolerance, interop::Model, math::Scalar,
+    window::ModelSender, Instance,
+};
+
+/// Display or export the model that a script defines
+#[derive(clap::Parser)]
+struct Args {
+    /// The script that defines the model
+    script: PathBuf,
+
+    /// Export model to this path, instead of displaying it
+    #[arg(short, long, value_name = "PATH")]
+    export: Option<PathBuf>,
+
+    /// How much the triangulation can deviate from the original model
+    #[arg(short, long)]
+    tolerance: Option<f64>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = <Args as clap::Parser>::parse();
+
+    let tolerance = args
+        .tolerance
+        .map(|tolerance| Tolerance::from_scalar(Scalar::from_f64(tolerance)))
+        .transpose()?;
+
+    if let Some(path) = args.export {
+        let mut instance = Instance::new();
+        let source = read(&args.script)?;
+        let solid = fj_script::evaluate(&source, &mut instance.core)?;
+        instance.export_model(&solid, tolerance, &path)?;
+
+        return Ok(());
+    }
+
+    fj::window::display_live(false, move |sender| {
+        watch(&args.script, tolerance, sender)
+    })?;
+
+    Ok(())
+}
+
+/// Rebuild the model whenever the script changes, and send it to the window
+fn watch(script: &Path, tolerance: Option<Tolerance>, sender: ModelSender) {
+    // `None` means that the script hasn't been loaded yet. The inner option
+    // is `None`, if the script couldn't be accessed. The error is shown once,
+    // when building fails for that reason.
+    let mut last_modified = None;
+
+    loop {
+        let modified = Some(modified(script).ok());
+
+        if modified != last_modified {
+            last_modified = modified;
+
+            match build(script, tolerance) {
+                Ok(model) => {
+                    println!("Loaded `{}`", script.display());
+                    if sender.send(model).is_err() {
+                        // The window has been closed.
+                        return;
+                    }
+                }
+                Err(err) => {
+                    // The previous model stays on display, until the script
+                    // is fixed.
+                    eprintln!("{err:?}");
+                }
+            }
+        }
+
+        thread::sleep(Duration::from_millis(200));
+    }
+}
+
+fn build(script: &Path, tolerance: Option<Tolerance>) -> anyhow::Result<Model> {
+    // Every build gets a fresh instance, so objects of previous versions of
+    // the model don't pile up.
+    let mut instance = Instance::new();
+
+    let source = read(script)?;
+    let solid = fj_script::evaluate(&source, &mut instance.core)?;
+    let model = instance.displayed_model(&solid, tolerance)?;
+
+    Ok(model)
+}
+
+fn read(script: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(script)
+        .with_context(|| format!("Failed to read `{}`", script.display()))
+}
+
+fn modified(script: &Path) -> std::io::Result<SystemTime> {
+    fs::metadata(script)?.modified()
+}
@@ -1,7 +1,7 @@
 //! Viewer camera module
-use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
 
-use fj_interop::{Mesh, Model};
+use fj_interop::{Mesh, Model, Triangle};
 use fj_math::{Aabb, Point, Scalar, Transform, Vector};
 
 use crate::screen::NormalizedScreenPosition;
@@ -25,6 +25,16 @@ pub struct Camera {
 
     /// The locational part of the transform
     pub translation: Transform,
+
+    /// How the model is projected onto the screen
+    pub projection: Projection,
+
+    /// The distance from the camera to the center of the model
+    ///
+    /// Measured along the viewing direction. The orthographic projection
+    /// shows as much of the model as the perspective projection shows at
+    /// this distance, so switching between them doesn't change its size much.
+    focus_distance: f64,
 }
 
 impl Camera {
@@ -41,6 +51,9 @@ impl Camera {
 
             rotation: Transform::identity(),
             translation: Transform::identity(),
+
+            projection: Projection::Perspective,
+            focus_distance: 1.,
         }
     }
 
@@ -59,6 +72,15 @@ impl Camera {
         Self::INITIAL_FIELD_OF_VIEW_IN_X
     }
 
+    /// Returns half the width of the area that the camera shows.
+    ///
+    /// For the perspective projection, this is measured at the distance of
+    /// the model's center. For the orthographic projection, it is the same
+    /// everywhere.
+    pub fn visible_half_width(&self) -> f64 {
+        self.focus_distance * (self.field_of_view_in_x() / 2.).tan()
+    }
+
     /// Returns the position of the camera in world space.
     pub fn position(&self) -> Point<3> {
         self.camera_to_model()
@@ -71,13 +93,25 @@ impl Camera {
         cursor: NormalizedScreenPosition,
     ) -> Point<3> {
         // Cursor position in camera space.
-        let f = (self.field_of_view_in_x() / 2.).tan() * self.near_plane();
+        let f = match self.projection {
+            Projection::Perspective => {
+                (self.field_of_view_in_x() / 2.).tan() * self.near_plane()
+            }
+            Projection::Orthographic => self.visible_half_width(),
+        };
         let cursor = Point::origin()
             + Vector::from([cursor.x * f, cursor.y * f, -self.near_plane()]);
 
         self.camera_to_model().inverse_transform_point(&cursor)
     }
 
+    /// Returns the direction that the camera looks in, in model space.
+    pub fn view_direction(&self) -> Vector<3> {
+        self.camera_to_model()
+            .inverse()
+            .transform_vector(&Vector::from([0., 0., -1.]))
+    }
+
     /// Compute the point on the model, that the cursor currently points to.
     pub fn focus_point(
         &self,
@@ -93,12 +127,30 @@ impl Camera {
         cursor: Option<NormalizedScreenPosition>,
         mesh: &Mesh<Point<3>>,
     ) -> Option<FocusPoint> {
+        let (_, point) = self.pick_triangle(cursor?, mesh)?;
+        Some(FocusPoint(point))
+    }
+
+    /// Find the triangle that the cursor currently points to
+    ///
+    /// Returns the triangle closest to the camera, and the point on it that
+    /// the cursor points to.
+    pub fn pick_triangle(
+        &self,
+        cursor: NormalizedScreenPosition,
+        mesh: &Mesh<Point<3>>,
+    ) -> Option<(Triangle, Point<3>)> {
         // Transform camera and cursor positions to model space.
-        let origin = self.position();
-        let cursor = self.cursor_to_model_space(cursor?);
-        let dir = (cursor - origin).normalize();
+        let cursor = self.cursor_to_model_space(cursor);
+        let (origin, dir) = match self.projection {
+            Projection::Perspective => {
+                let origin = self.position();
+                (origin, (cursor - origin).normalize())
+            }
+            Projection::Orthographic => (cursor, self.view_direction()),
+        };
 
-        let mut min_t = None;
+        let mut closest = None;
 
         for triangle in mesh.triangles() {
             let t =
@@ -107,13 +159,14 @@ impl Camera {
                     .cast_local_ray(origin, dir, f64::INFINITY, true);
 
             if let Some(t) = t {
-                if t <= min_t.unwrap_or(t) {
-                    min_t = Some(t);
+                if closest.map_or(true, |(_, min_t)| t <= min_t) {
+                    closest = Some((triangle, t));
                 }
             }
         }
 
-        Some(FocusPoint(origin + dir * min_t?))
+        let (triangle, t) = closest?;
+        Some((triangle, origin + dir * t))
     }
 
     /// Access the transform from camera to model space.
@@ -176,9 +229,59 @@ impl Camera {
         self.translation = translation;
     }
 
+    /// Show the model from one of the standard views
+    ///
+    /// Also moves the camera, so the whole model is visible. See
+    /// [`Camera::fit`].
+    pub fn set_view(
+        &mut self,
+        view: StandardView,
+        aabb: &Aabb<3>,
+        aspect_ratio: f64,
+    ) {
+        self.rotation = view.rotation();
+        self.fit(aabb, aspect_ratio);
+    }
+
+    /// Move the camera, so the whole model is visible
+    ///
+    /// Keeps the direction that the model is viewed from, and centers the
+    /// model on the screen.
+    pub fn fit(&mut self, aabb: &Aabb<3>, aspect_ratio: f64) {
+        // The model fits on the screen, if its bounding sphere does. This is
+        // conservative, but independent of the direction it's viewed from.
+        let radius = aabb.size().magnitude().into_f64() / 2.;
+
+        let half_fov_x = self.field_of_view_in_x() / 2.;
+        let half_fov_y = (half_fov_x.tan() / aspect_ratio).atan();
+        let half_fov = half_fov_x.min(half_fov_y);
+
+        // In the perspective projection, the bounding sphere just touches the
+        // edges of the field of view at this distance. Since the orthographic
+        // projection shows as much as the perspective one at the focus
+        // distance, the same distance also works for that.
+        let distance = radius / half_fov.sin();
+
+        let center = self.rotation.transform_point(&aabb.center());
+        self.translation = Transform::translation(
+            Vector::from([0., 0., -distance]) - center.coords,
+        );
+        self.focus_distance = distance;
+    }
+
     /// Update the max and minimum rendering distance for this camera.
     pub fn update_planes(&mut self, aabb: &Aabb<3>) {
         let view_transform = self.camera_to_model();
+
+        let center_depth =
+            -view_transform.transform_point(&aabb.center()).z.into_f64();
+        self.focus_distance = center_depth.max(Self::DEFAULT_NEAR_PLANE);
+
+        if self.projection == Projection::Orthographic {
+            self.update_orthographic_planes(aabb);
+            return;
+        }
+
         let view_direction = Vector::from([0., 0., -1.]);
 
         let mut dist_min = f64::INFINITY;
@@ -220,6 +323,87 @@ impl Camera {
             Self::DEFAULT_FAR_PLANE
         };
     }
+
+    fn update_orthographic_planes(&mut self, aabb: &Aabb<3>) {
+        let view_transform = self.camera_to_model();
+
+        let (min_depth, max_depth) = aabb.vertices().into_iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min, max), vertex| {
+                let depth =
+                    -view_transform.transform_point(&vertex).z.into_f64();
+                (min.min(depth), max.max(depth))
+            },
+        );
+
+        // Unlike with the perspective projection, nothing gets distorted, if
+        // parts of the model are behind the camera. We can show them too.
+        //
+        // The projection maps the depth between the planes to the range from
+        // -1 to 1, but only the half from 0 to 1 is rendered. So the near
+        // plane needs to be placed far enough in front of the model, that the
+        // model starts beyond the middle between the planes.
+        let depth = (max_depth - min_depth).max(Self::DEFAULT_NEAR_PLANE);
+        self.near_plane = min_depth - depth * 1.2;
+        self.far_plane = max_depth + depth * 0.1;
+    }
+}
+
+/// How the camera projects the model onto the screen
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Projection {
+    /// Parts of the model that are further away appear smaller
+    #[default]
+    Perspective,
+
+    /// All parts of the model appear at the same scale
+    ///
+    /// This makes it easier to judge alignment and proportions.
+    Orthographic,
+}
+
+impl Projection {
+    /// Access the other projection
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Perspective => Self::Orthographic,
+            Self::Orthographic => Self::Perspective,
+        }
+    }
+}
+
+/// A standard direction to view the model from
+///
+/// The z-axis of the model points up in all views, except the top view.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StandardView {
+    /// Look down along the negative z-axis
+    Top,
+
+    /// Look along the positive y-axis
+    Front,
+
+    /// Look along the negative x-axis
+    Right,
+
+    /// Look at the front, right, and top of the model at the same angle
+    Isometric,
+}
+
+impl StandardView {
+    fn rotation(self) -> Transform {
+        // Azimuth measured from the front towards the right, elevation from
+        // the horizontal towards the top.
+        let (azimuth, elevation) = match self {
+            Self::Top => (0., FRAC_PI_2),
+            Self::Front => (0., 0.),
+            Self::Right => (FRAC_PI_2, 0.),
+            Self::Isometric => (FRAC_PI_4, (1. / 2f64.sqrt()).atan()),
+        };
+
+        Transform::rotation(Vector::unit_x() * (elevation - FRAC_PI_2))
+            * Transform::rotation(Vector::unit_z() * -azimuth)
+    }
 }
 
 impl Default for Camera {
@@ -0,0 +1,91 @@
+//! Saving captured images of the model
+//!
+//! Images are captured by [`Viewer::screenshot`] and [`Viewer::turntable`].
+//!
+//! [`Viewer::screenshot`]: crate::Viewer::screenshot
+//! [`Viewer::turntable`]: crate::Viewer::turntable
+
+use std::{fs, io, path::Path, time::Duration};
+
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, ImageError, RgbaImage,
+};
+
+/// Save a sequence of frames as numbered PNG files
+///
+/// The files are named `frame-0000.png`, `frame-0001.png`, and so on, and are
+/// placed into the provided directory, which is created, if it doesn't exist.
+/// That naming scheme is understood by tools that turn image sequences into
+/// videos, like `ffmpeg -i frame-%04d.png turntable.mp4`.
+pub fn save_frames(
+    frames: &[RgbaImage],
+    dir: impl AsRef<Path>,
+) -> Result<(), CaptureError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    for (i, frame) in frames.iter().enumerate() {
+        frame.save(dir.join(format!("frame-{i:04}.png")))?;
+    }
+
+    Ok(())
+}
+
+/// Save a sequence of frames as an animated GIF that loops forever
+pub fn save_gif(
+    frames: &[RgbaImage],
+    path: impl AsRef<Path>,
+    frame_delay: Duration,
+) -> Result<(), CaptureError> {
+    let file = io::BufWriter::new(fs::File::create(path)?);
+
+    let mut encoder = GifEncoder::new(file);
+    encoder.set_repeat(Repeat::Infinite)?;
+    encoder.encode_frames(frames.iter().map(|frame| {
+        Frame::from_parts(
+            frame.clone(),
+            0,
+            0,
+            Delay::from_saturating_duration(frame_delay),
+        )
+    }))?;
+
+    Ok(())
+}
+
+/// Error capturing or saving an image
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+    /// The requested image size is not supported
+    #[error(
+        "Can't capture image of size {width}x{height} (maximum size: \
+        {max_size}x{max_size})"
+    )]
+    InvalidSize {
+        /// The requested width
+        width: u32,
+
+        /// The requested height
+        height: u32,
+
+        /// The maximum width and height that the graphics device supports
+        max_size: u32,
+    },
+
+    /// The color format used for rendering can't be converted into an image
+    #[error("Can't capture image from color format {0:?}")]
+    UnsupportedFormat(wgpu::TextureFormat),
+
+    /// Failed to read the rendered image back from the graphics device
+    #[error("Failed to read back rendered image")]
+    ReadBack(#[from] wgpu::BufferAsyncError),
+
+    /// Failed to encode an image
+    #[error("Failed to encode image")]
+    Image(#[from] ImageError),
+
+    /// I/O error while saving an image
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+}
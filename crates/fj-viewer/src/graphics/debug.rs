@@ -0,0 +1,268 @@
+//! An overlay that displays the debug geometry of the model
+
+use std::collections::BTreeSet;
+
+use ab_glyph::{Font, FontRef, OutlineCurve};
+use fj_interop::{Color, DebugGeometry, DebugPrimitive};
+use fj_math::Point;
+
+use crate::camera::Camera;
+
+use super::{transform::Transform, vertices::Vertices};
+
+/// Create lines that display the debug geometry
+///
+/// Channels whose names are in `hidden_channels` are skipped. Each channel has
+/// its own color, which doesn't change when other channels are hidden.
+///
+/// Like the scale indicator, the vertices are in normalized device
+/// coordinates, so they are drawn on top of the model. Debug geometry that is
+/// hidden behind the model would not be of much help. Points, arrow heads, and
+/// text have the same size on screen, regardless of the camera.
+pub fn overlay(
+    debug: &DebugGeometry,
+    hidden_channels: &BTreeSet<String>,
+    camera: &Camera,
+    aspect_ratio: f64,
+) -> Vertices {
+    let projection = Projection {
+        transform: Transform::for_vertices(camera, aspect_ratio).0,
+        aspect_ratio: aspect_ratio as f32,
+        font: FontRef::try_from_slice(FONT).expect("Embedded font is valid"),
+    };
+
+    let mut lines = Vec::new();
+    for (i, (channel, primitives)) in debug.channels.iter().enumerate() {
+        if hidden_channels.contains(channel) {
+            continue;
+        }
+
+        let color = CHANNEL_COLORS[i % CHANNEL_COLORS.len()];
+        for primitive in primitives {
+            lines.extend(
+                projection
+                    .primitive(primitive)
+                    .into_iter()
+                    .map(|line| (line, color)),
+            );
+        }
+    }
+
+    // The lines have been built with the same scale in x and y, so they need
+    // to be stretched back to normalized device coordinates.
+    Vertices::from_lines(lines.into_iter().map(|(line, color)| {
+        let line = line.map(|[x, y]| {
+            Point::from([f64::from(x), f64::from(y) * aspect_ratio, 0.])
+        });
+        (line, color)
+    }))
+}
+
+/// Projects debug primitives into lines on the screen
+///
+/// The lines are in normalized device coordinates, except that y is scaled to
+/// match x. This way, shapes like arrow heads keep their proportions.
+struct Projection {
+    transform: [f32; 16],
+    aspect_ratio: f32,
+    font: FontRef<'static>,
+}
+
+impl Projection {
+    fn primitive(&self, primitive: &DebugPrimitive) -> Vec<[[f32; 2]; 2]> {
+        match primitive {
+            DebugPrimitive::Point(point) => {
+                let Some([x, y]) = self.point(point) else {
+                    return Vec::new();
+                };
+                let s = POINT_SIZE;
+
+                vec![
+                    [[x - s, y - s], [x + s, y + s]],
+                    [[x - s, y + s], [x + s, y - s]],
+                ]
+            }
+            DebugPrimitive::Segment(points) => {
+                self.segment(points).into_iter().collect()
+            }
+            DebugPrimitive::Arrow { from, to } => {
+                let Some([a, b]) = self.segment(&[*from, *to]) else {
+                    return Vec::new();
+                };
+                let mut lines = vec![[a, b]];
+
+                // Only add the head, if the tip is actually visible.
+                let [dx, dy] = [b[0] - a[0], b[1] - a[1]];
+                let length = (dx * dx + dy * dy).sqrt();
+                if self.point(to).is_some() && length > 0. {
+                    let [dx, dy] = [dx / length, dy / length];
+                    let [sin, cos] = [ARROW_ANGLE.sin(), ARROW_ANGLE.cos()];
+
+                    for sin in [sin, -sin] {
+                        let x = b[0] - (dx * cos - dy * sin) * ARROW_SIZE;
+                        let y = b[1] - (dx * sin + dy * cos) * ARROW_SIZE;
+                        lines.push([b, [x, y]]);
+                    }
+                }
+
+                lines
+            }
+            DebugPrimitive::Text { at, text } => {
+                let Some([x, y]) = self.point(at) else {
+                    return Vec::new();
+                };
+
+                self.text([x + TEXT_OFFSET, y + TEXT_OFFSET], text)
+            }
+        }
+    }
+
+    fn point(&self, point: &Point<3>) -> Option<[f32; 2]> {
+        let point = self.clip(point);
+
+        if point[3] < MIN_W {
+            return None;
+        }
+
+        Some(self.normalize(point))
+    }
+
+    /// Project a line segment, cutting off any part behind the camera
+    fn segment(&self, [a, b]: &[Point<3>; 2]) -> Option<[[f32; 2]; 2]> {
+        let [mut a, mut b] = [a, b].map(|point| self.clip(point));
+        let [wa, wb] = [a[3], b[3]];
+
+        if wa < MIN_W && wb < MIN_W {
+            return None;
+        }
+        if wa < MIN_W {
+            a = lerp(a, b, (MIN_W - wa) / (wb - wa));
+        }
+        if wb < MIN_W {
+            b = lerp(b, a, (MIN_W - wb) / (wa - wb));
+        }
+
+        Some([a, b].map(|point| self.normalize(point)))
+    }
+
+    /// Create the outlines of a text, with its baseline starting at `origin`
+    fn text(&self, origin: [f32; 2], text: &str) -> Vec<[[f32; 2]; 2]> {
+        let scale = TEXT_HEIGHT / self.font.units_per_em().unwrap_or(1.);
+
+        let mut lines = Vec::new();
+        let [mut x, mut y] = origin;
+
+        for character in text.chars() {
+            if character == '\n' {
+                x = origin[0];
+                y -= TEXT_HEIGHT * 1.5;
+                continue;
+            }
+
+            let glyph_id = self.font.glyph_id(character);
+
+            if let Some(outline) = self.font.outline(glyph_id) {
+                for curve in &outline.curves {
+                    let num_segments = match curve {
+                        OutlineCurve::Line(..) => 1,
+                        _ => CURVE_SEGMENTS,
+                    };
+
+                    let points = (0..=num_segments)
+                        .map(|i| {
+                            let t = i as f32 / num_segments as f32;
+                            let [gx, gy] = evaluate(curve, t);
+                            [x + gx * scale, y + gy * scale]
+                        })
+                        .collect::<Vec<_>>();
+
+                    lines.extend(points.windows(2).map(|w| [w[0], w[1]]));
+                }
+            }
+
+            x += self.font.h_advance_unscaled(glyph_id) * scale;
+        }
+
+        lines
+    }
+
+    /// Transform a point into clip space
+    fn clip(&self, point: &Point<3>) -> [f32; 4] {
+        let [px, py, pz] = point.coords.components.map(|s| s.into_f32());
+        let vector = [px, py, pz, 1.];
+
+        [0, 1, 2, 3].map(|row| {
+            (0..4)
+                .map(|column| self.transform[column * 4 + row] * vector[column])
+                .sum()
+        })
+    }
+
+    fn normalize(&self, [x, y, _, w]: [f32; 4]) -> [f32; 2] {
+        [x / w, y / w / self.aspect_ratio]
+    }
+}
+
+fn lerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+/// Evaluate a curve of a glyph outline, with `t` going from 0 to 1
+fn evaluate(curve: &OutlineCurve, t: f32) -> [f32; 2] {
+    let s = 1. - t;
+
+    let (points, weights) = match curve {
+        OutlineCurve::Line(a, b) => (vec![a, b], vec![s, t]),
+        OutlineCurve::Quad(a, b, c) => {
+            (vec![a, b, c], vec![s * s, 2. * s * t, t * t])
+        }
+        OutlineCurve::Cubic(a, b, c, d) => (
+            vec![a, b, c, d],
+            vec![s * s * s, 3. * s * s * t, 3. * s * t * t, t * t * t],
+        ),
+    };
+
+    points
+        .into_iter()
+        .zip(weights)
+        .fold([0., 0.], |[x, y], (point, weight)| {
+            [x + point.x * weight, y + point.y * weight]
+        })
+}
+
+const FONT: &[u8] = include_bytes!("fonts/B612Mono-Regular.ttf");
+
+/// The colors of the channels, which are reused if there are more channels
+const CHANNEL_COLORS: [Color; 6] = [
+    Color([220, 0, 220, 255]),
+    Color([240, 120, 0, 255]),
+    Color([0, 150, 150, 255]),
+    Color([120, 60, 200, 255]),
+    Color([150, 100, 50, 255]),
+    Color([200, 0, 80, 255]),
+];
+
+/// The minimum `w` of a point in clip space, for the point to be visible
+///
+/// Points closer to or behind the camera can't be projected onto the screen.
+const MIN_W: f32 = 1e-5;
+
+/// Half the size of the cross that marks a point
+///
+/// Specified in normalized device coordinates, like the other sizes here.
+const POINT_SIZE: f32 = 0.01;
+
+/// The length of the lines that make up an arrow head
+const ARROW_SIZE: f32 = 0.03;
+
+/// The angle between an arrow and the lines of its head, in radians
+const ARROW_ANGLE: f32 = 0.4;
+
+/// The size of text, which is the height of its em square
+const TEXT_HEIGHT: f32 = 0.035;
+
+/// The distance of text from the point it refers to, in x and y
+const TEXT_OFFSET: f32 = 0.01;
+
+/// The number of line segments that a curve of a glyph outline is split into
+const CURVE_SEGMENTS: usize = 8;
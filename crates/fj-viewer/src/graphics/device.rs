@@ -9,13 +9,13 @@ pub struct Device {
 impl Device {
     pub async fn from_preferred_adapter(
         instance: &wgpu::Instance,
-        surface: &wgpu::Surface<'_>,
+        surface: Option<&wgpu::Surface<'_>>,
     ) -> Result<(Self, wgpu::Adapter, wgpu::Features), DeviceError> {
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptions {
                 power_preference: wgpu::PowerPreference::None,
                 force_fallback_adapter: false,
-                compatible_surface: Some(surface),
+                compatible_surface: surface,
             })
             .await
             .ok_or(DeviceError::RequestAdapter)?;
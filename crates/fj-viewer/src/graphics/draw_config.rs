@@ -1,3 +1,7 @@
+use std::collections::BTreeSet;
+
+use fj_math::{Scalar, Vector};
+
 /// High level configuration for rendering the active model
 #[derive(Debug)]
 pub struct DrawConfig {
@@ -6,6 +10,27 @@ pub struct DrawConfig {
 
     /// Toggle for displaying the wireframe model
     pub draw_mesh: bool,
+
+    /// The mode that the model is rendered in
+    pub render_mode: RenderMode,
+
+    /// The plane that cuts the model, if any
+    pub section_plane: Option<SectionPlane>,
+
+    /// Toggle for displaying a grid in the xy-plane
+    pub draw_grid: bool,
+
+    /// Toggle for displaying the axes of the coordinate system
+    pub draw_axes: bool,
+
+    /// Toggle for displaying the scale indicator
+    pub draw_scale: bool,
+
+    /// Toggle for displaying the debug geometry of the model
+    pub draw_debug: bool,
+
+    /// The channels of debug geometry that are not displayed
+    pub hidden_debug_channels: BTreeSet<String>,
 }
 
 impl Default for DrawConfig {
@@ -13,6 +38,59 @@ impl Default for DrawConfig {
         Self {
             draw_model: true,
             draw_mesh: false,
+            render_mode: RenderMode::Shaded,
+            section_plane: None,
+            draw_grid: true,
+            draw_axes: true,
+            draw_scale: true,
+            draw_debug: true,
+            hidden_debug_channels: BTreeSet::new(),
+        }
+    }
+}
+
+/// The mode that the model is rendered in
+///
+/// All modes except [`RenderMode::Shaded`] are meant for debugging.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RenderMode {
+    /// Render the shaded model
+    #[default]
+    Shaded,
+
+    /// Render the shaded model, and the approximated edges of its faces
+    Edges,
+
+    /// Render the shaded model, and the normals of its triangles
+    Normals,
+
+    /// Render the shaded model, with each face in a different color
+    FaceColors,
+}
+
+/// A plane that cuts the model, to show its inside
+///
+/// Nothing that is located on the side of the plane that its normal points to
+/// is rendered.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SectionPlane {
+    /// The normal of the plane
+    pub normal: Vector<3>,
+
+    /// The distance of the plane from the origin, along its normal
+    pub distance: Scalar,
+}
+
+impl RenderMode {
+    /// Access the mode that comes after this one
+    ///
+    /// Can be used to cycle through all modes.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Shaded => Self::Edges,
+            Self::Edges => Self::Normals,
+            Self::Normals => Self::FaceColors,
+            Self::FaceColors => Self::Shaded,
         }
     }
 }
@@ -6,6 +6,13 @@ use super::{
 pub struct Drawables<'r> {
     pub model: Drawable<'r>,
     pub mesh: Option<Drawable<'r>>,
+    pub face_colors: Drawable<'r>,
+    pub edges: Drawable<'r>,
+    pub normals: Drawable<'r>,
+    pub measurement: Drawable<'r>,
+    pub failed_faces: Drawable<'r>,
+    pub grid: Drawable<'r>,
+    pub axes: Drawable<'r>,
 }
 
 impl<'r> Drawables<'r> {
@@ -15,8 +22,28 @@ impl<'r> Drawables<'r> {
             .mesh
             .as_ref()
             .map(|pipeline| Drawable::new(&geometries.mesh, pipeline));
+        let face_colors =
+            Drawable::new(&geometries.face_colors, &pipelines.model);
+        let edges = Drawable::new(&geometries.edges, &pipelines.lines);
+        let normals = Drawable::new(&geometries.normals, &pipelines.lines);
+        let measurement =
+            Drawable::new(&geometries.measurement, &pipelines.lines);
+        let failed_faces =
+            Drawable::new(&geometries.failed_faces, &pipelines.lines);
+        let grid = Drawable::new(&geometries.grid, &pipelines.lines);
+        let axes = Drawable::new(&geometries.axes, &pipelines.lines);
 
-        Self { model, mesh }
+        Self {
+            model,
+            mesh,
+            face_colors,
+            edges,
+            normals,
+            measurement,
+            failed_faces,
+            grid,
+            axes,
+        }
     }
 }
 
@@ -26,7 +53,7 @@ pub struct Drawable<'a> {
 }
 
 impl<'a> Drawable<'a> {
-    fn new(geometry: &'a Geometry, pipeline: &'a Pipeline) -> Self {
+    pub fn new(geometry: &'a Geometry, pipeline: &'a Pipeline) -> Self {
         Self { geometry, pipeline }
     }
 
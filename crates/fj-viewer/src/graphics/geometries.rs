@@ -2,18 +2,32 @@ use std::convert::TryInto;
 
 use wgpu::util::DeviceExt;
 
-use super::vertices::{Vertex, Vertices};
+use super::vertices::{ModelVertices, Vertex, Vertices};
 
 #[derive(Debug)]
 pub struct Geometries {
     pub mesh: Geometry,
+    pub face_colors: Geometry,
+    pub edges: Geometry,
+    pub normals: Geometry,
+    pub measurement: Geometry,
+    pub failed_faces: Geometry,
+    pub grid: Geometry,
+    pub axes: Geometry,
 }
 
 impl Geometries {
-    pub fn new(device: &wgpu::Device, mesh: &Vertices) -> Self {
-        let mesh = Geometry::new(device, mesh.vertices(), mesh.indices());
-
-        Self { mesh }
+    pub fn new(device: &wgpu::Device, vertices: &ModelVertices) -> Self {
+        Self {
+            mesh: Geometry::new(device, &vertices.mesh),
+            face_colors: Geometry::new(device, &vertices.face_colors),
+            edges: Geometry::new(device, &vertices.edges),
+            normals: Geometry::new(device, &vertices.normals),
+            measurement: Geometry::new(device, &vertices.measurement),
+            failed_faces: Geometry::new(device, &vertices.failed_faces),
+            grid: Geometry::new(device, &vertices.grid),
+            axes: Geometry::new(device, &vertices.axes),
+        }
     }
 }
 
@@ -25,11 +39,10 @@ pub struct Geometry {
 }
 
 impl Geometry {
-    fn new(
-        device: &wgpu::Device,
-        vertices: &[Vertex],
-        indices: &[u32],
-    ) -> Self {
+    pub fn new(device: &wgpu::Device, vertices: &Vertices) -> Self {
+        let indices = vertices.indices();
+        let vertices: &[Vertex] = vertices.vertices();
+
         Self {
             vertex_buffer: device.create_buffer_init(
                 &wgpu::util::BufferInitDescriptor {
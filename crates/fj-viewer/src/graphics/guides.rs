@@ -0,0 +1,201 @@
+//! Guides that help with judging the position, orientation, and size of the
+//! model
+
+use fj_interop::Color;
+use fj_math::{Aabb, Point};
+
+use crate::camera::Camera;
+
+use super::vertices::Vertices;
+
+/// Create a grid in the xy-plane, that covers the model and the origin
+///
+/// The spacing of the grid lines is a power of ten, chosen to fit the size of
+/// the covered area.
+pub fn grid(aabb: &Aabb<3>) -> Vertices {
+    let (min, max, extent) = covered_area(aabb);
+
+    let spacing = 10f64.powf((extent / 5.).log10().floor());
+
+    // Snap the grid to multiples of the spacing, so the origin is always on a
+    // grid line, and add a margin around the covered area.
+    let [min_x, min_y] = min.map(|coord| (coord / spacing).floor() - 1.);
+    let [max_x, max_y] = max.map(|coord| (coord / spacing).ceil() + 1.);
+
+    let mut lines = Vec::new();
+    for i in min_x as i64..=max_x as i64 {
+        let x = i as f64 * spacing;
+        lines.push((
+            [[x, min_y * spacing, 0.], [x, max_y * spacing, 0.]],
+            GRID_COLOR,
+        ));
+    }
+    for i in min_y as i64..=max_y as i64 {
+        let y = i as f64 * spacing;
+        lines.push((
+            [[min_x * spacing, y, 0.], [max_x * spacing, y, 0.]],
+            GRID_COLOR,
+        ));
+    }
+
+    Vertices::from_lines(
+        lines
+            .into_iter()
+            .map(|(points, color)| (points.map(Point::from), color)),
+    )
+}
+
+/// Create lines along the positive x, y, and z axes
+///
+/// Their length fits the area that the model and the origin cover.
+pub fn axes(aabb: &Aabb<3>) -> Vertices {
+    let (_, _, extent) = covered_area(aabb);
+    let length = extent / 2.;
+
+    Vertices::from_lines(
+        [
+            ([length, 0., 0.], X_AXIS_COLOR),
+            ([0., length, 0.], Y_AXIS_COLOR),
+            ([0., 0., length], Z_AXIS_COLOR),
+        ]
+        .map(|(end, color)| ([Point::origin(), Point::from(end)], color)),
+    )
+}
+
+/// Create a scale bar with a label, in the lower-left corner of the screen
+///
+/// The bar is as long as a round distance at the model's center. The
+/// vertices are in normalized device coordinates, so they must be rendered
+/// without any transformation.
+pub fn scale_indicator(camera: &Camera, aspect_ratio: f64) -> Vertices {
+    // Normalized device coordinates go from -1 to 1, across the screen.
+    let units_per_device = camera.visible_half_width();
+
+    let length = round_length(units_per_device * SCALE_TARGET_LENGTH);
+    let bar_length = length / units_per_device;
+
+    // Normalized device coordinates are stretched differently in x and y,
+    // unless the screen is square. Use this to scale heights, so everything
+    // has the same proportions on screen.
+    let y = |height: f64| height * aspect_ratio;
+
+    let [x0, y0] = SCALE_POSITION;
+    let x1 = x0 + bar_length;
+    let tick = y(SCALE_TICK_HEIGHT);
+
+    let mut lines = vec![
+        [[x0, y0], [x1, y0]],
+        [[x0, y0], [x0, y0 + tick]],
+        [[x1, y0], [x1, y0 + tick]],
+    ];
+
+    let label = format_length(length);
+    let char_width = SCALE_CHAR_WIDTH;
+    let char_height = y(SCALE_CHAR_WIDTH * 2.);
+    let label_width = label.len() as f64 * char_width * 1.5;
+    let mut x = x0 + (bar_length - label_width) / 2.;
+    let baseline = y0 + tick * 1.5;
+
+    for character in label.chars() {
+        for [a, b] in glyph(character) {
+            lines.push([a, b].map(|[gx, gy]| {
+                [x + gx * char_width, baseline + gy * char_height]
+            }));
+        }
+        x += char_width * 1.5;
+    }
+
+    Vertices::from_lines(
+        lines.into_iter().map(|line| {
+            (line.map(|[x, y]| Point::from([x, y, 0.])), SCALE_COLOR)
+        }),
+    )
+}
+
+/// Compute the area in the xy-plane that covers the model and the origin
+///
+/// Returns the minimum and maximum corners, and the size of the larger side.
+fn covered_area(aabb: &Aabb<3>) -> ([f64; 2], [f64; 2], f64) {
+    let aabb = aabb.merged(&Aabb {
+        min: Point::origin(),
+        max: Point::origin(),
+    });
+    let [min, max] = [aabb.min, aabb.max]
+        .map(|point| [point.x, point.y].map(|coord| coord.into_f64()));
+
+    let extent = (max[0] - min[0]).max(max[1] - min[1]);
+
+    // If there's no model, we still want to show something.
+    let extent = if extent > 0. { extent } else { 1. };
+
+    (min, max, extent)
+}
+
+/// Round a length down to 1, 2, or 5, times a power of ten
+fn round_length(length: f64) -> f64 {
+    let magnitude = 10f64.powf(length.log10().floor());
+
+    let mantissa = [5., 2., 1.]
+        .into_iter()
+        .find(|&mantissa| mantissa * magnitude <= length)
+        .unwrap_or(1.);
+
+    mantissa * magnitude
+}
+
+fn format_length(length: f64) -> String {
+    let decimals = (-length.log10().floor()).max(0.) as usize;
+    format!("{length:.decimals$}")
+}
+
+/// The lines that make up a character, within a box of size 1x2
+///
+/// Digits are drawn like on a seven-segment display. Characters that are not
+/// needed for the labels are not drawn.
+fn glyph(character: char) -> Vec<[[f64; 2]; 2]> {
+    let [a, b, c, d, e, f, g] = [
+        [[0., 2.], [1., 2.]],
+        [[1., 2.], [1., 1.]],
+        [[1., 1.], [1., 0.]],
+        [[0., 0.], [1., 0.]],
+        [[0., 0.], [0., 1.]],
+        [[0., 1.], [0., 2.]],
+        [[0., 1.], [1., 1.]],
+    ];
+
+    match character {
+        '0' => vec![a, b, c, d, e, f],
+        '1' => vec![b, c],
+        '2' => vec![a, b, g, e, d],
+        '3' => vec![a, b, g, c, d],
+        '4' => vec![f, g, b, c],
+        '5' => vec![a, f, g, c, d],
+        '6' => vec![a, f, g, e, c, d],
+        '7' => vec![a, b, c],
+        '8' => vec![a, b, c, d, e, f, g],
+        '9' => vec![a, b, c, d, f, g],
+        '.' => vec![[[0.4, 0.], [0.6, 0.]]],
+        _ => Vec::new(),
+    }
+}
+
+const GRID_COLOR: Color = Color([200, 200, 200, 255]);
+const X_AXIS_COLOR: Color = Color([220, 0, 0, 255]);
+const Y_AXIS_COLOR: Color = Color([0, 160, 0, 255]);
+const Z_AXIS_COLOR: Color = Color([0, 0, 220, 255]);
+const SCALE_COLOR: Color = Color([0, 0, 0, 255]);
+
+/// The length that the scale bar should roughly have
+///
+/// Specified in normalized device coordinates, so this is a fraction of the
+/// screen width. The bar will be shorter, to show a round distance.
+const SCALE_TARGET_LENGTH: f64 = 0.4;
+
+/// The position of the left end of the scale bar
+const SCALE_POSITION: [f64; 2] = [-0.95, -0.9];
+
+/// The height of the ticks at the ends of the scale bar
+const SCALE_TICK_HEIGHT: f64 = 0.015;
+
+/// The width of a character of the scale label
+const SCALE_CHAR_WIDTH: f64 = 0.02;
@@ -1,14 +1,17 @@
 //! Rendering primitives, routines, and structures.
 
+mod debug;
 mod device;
 mod draw_config;
 mod drawables;
 mod geometries;
+mod guides;
 mod model;
 mod navigation_cube;
 mod pipelines;
 mod renderer;
 mod shaders;
+mod software;
 mod texture;
 mod transform;
 mod uniforms;
@@ -16,8 +19,10 @@ mod vertices;
 
 pub use self::{
     device::DeviceError,
-    draw_config::DrawConfig,
-    renderer::{Renderer, RendererInitError},
+    draw_config::{DrawConfig, RenderMode, SectionPlane},
+    renderer::{DrawError, Renderer, RendererInitError},
+    software::SoftwareRenderer,
+    vertices::ModelVertices,
 };
 
 pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
@@ -10,6 +10,7 @@ use super::{
 pub struct Pipelines {
     pub model: Pipeline,
     pub mesh: Option<Pipeline>,
+    pub lines: Pipeline,
 }
 
 impl Pipelines {
@@ -53,7 +54,16 @@ impl Pipelines {
             None
         };
 
-        Self { model, mesh }
+        let lines = Pipeline::new(
+            device,
+            &pipeline_layout,
+            shaders.lines(),
+            wgpu::PrimitiveTopology::LineList,
+            wgpu::PolygonMode::Fill,
+            color_format,
+        );
+
+        Self { model, mesh, lines }
     }
 }
 
@@ -1,25 +1,35 @@
-use std::{io, mem::size_of, vec};
+use std::{io, mem::size_of, sync::mpsc, vec};
 
+use fj_interop::DebugGeometry;
+use image::RgbaImage;
 use thiserror::Error;
 use tracing::{debug, error, trace};
 use wgpu::util::DeviceExt as _;
 
 use crate::{
     camera::Camera,
+    capture::CaptureError,
     screen::{Screen, ScreenSize},
 };
 
 use super::{
-    device::Device, draw_config::DrawConfig, drawables::Drawables,
-    geometries::Geometries, navigation_cube::NavigationCubeRenderer,
-    pipelines::Pipelines, transform::Transform, uniforms::Uniforms,
-    vertices::Vertices, DeviceError, DEPTH_FORMAT, SAMPLE_COUNT,
+    debug,
+    device::Device,
+    draw_config::{DrawConfig, RenderMode},
+    drawables::{Drawable, Drawables},
+    geometries::{Geometries, Geometry},
+    guides,
+    navigation_cube::NavigationCubeRenderer,
+    pipelines::Pipelines,
+    uniforms::Uniforms,
+    vertices::ModelVertices,
+    DeviceError, DEPTH_FORMAT, SAMPLE_COUNT,
 };
 
 /// Graphics rendering state and target abstraction
 #[derive(Debug)]
 pub struct Renderer {
-    surface: wgpu::Surface<'static>,
+    surface: Option<wgpu::Surface<'static>>,
     device: Device,
 
     surface_config: wgpu::SurfaceConfiguration,
@@ -28,9 +38,11 @@ pub struct Renderer {
 
     uniform_buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
+    overlay_bind_group: wgpu::BindGroup,
 
     geometries: Geometries,
     pipelines: Pipelines,
+    debug: DebugGeometry,
 
     navigation_cube_renderer: NavigationCubeRenderer,
 }
@@ -38,25 +50,55 @@ pub struct Renderer {
 impl Renderer {
     /// Returns a new `Renderer`.
     pub async fn new(screen: &impl Screen) -> Result<Self, RendererInitError> {
-        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::all(),
-            ..Default::default()
-        });
+        let instance = Self::create_instance();
 
         // This is sound, as `window` is an object to create a surface upon.
         let surface = instance.create_surface(screen.window())?;
 
+        Self::init(&instance, Some(surface), screen.size()).await
+    }
+
+    /// Returns a new `Renderer` that doesn't render to a window
+    ///
+    /// Such a renderer can only produce images through [`Renderer::capture`].
+    /// [`Renderer::draw`] does nothing.
+    pub async fn new_headless() -> Result<Self, RendererInitError> {
+        let instance = Self::create_instance();
+
+        // The size doesn't matter, as there's no surface to configure. Every
+        // capture creates its own render targets, at the requested size.
+        let size = ScreenSize {
+            width: 1,
+            height: 1,
+        };
+
+        Self::init(&instance, None, size).await
+    }
+
+    fn create_instance() -> wgpu::Instance {
+        wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        })
+    }
+
+    async fn init(
+        instance: &wgpu::Instance,
+        surface: Option<wgpu::Surface<'static>>,
+        size: ScreenSize,
+    ) -> Result<Self, RendererInitError> {
         for adapter in instance.enumerate_adapters(wgpu::Backends::all()) {
             debug!("Available adapter: {:?}", adapter.get_info());
         }
 
-        let result = Device::from_preferred_adapter(&instance, &surface).await;
+        let result =
+            Device::from_preferred_adapter(instance, surface.as_ref()).await;
         let (device, adapter, features) = match result {
             Ok((device, adapter, features)) => (device, adapter, features),
             Err(_) => {
                 error!("Failed to acquire device from preferred adapter");
 
-                match Device::try_from_all_adapters(&instance).await {
+                match Device::try_from_all_adapters(instance).await {
                     Ok((device, adapter, features)) => {
                         (device, adapter, features)
                     }
@@ -74,6 +116,12 @@ impl Renderer {
         };
 
         let color_format = 'color_format: {
+            let Some(surface) = &surface else {
+                // Without a surface, we're free to choose. Let's pick the
+                // format that captured images are stored in.
+                break 'color_format wgpu::TextureFormat::Rgba8Unorm;
+            };
+
             let capabilities = surface.get_capabilities(&adapter);
             let supported_formats = capabilities.formats;
 
@@ -99,7 +147,7 @@ impl Renderer {
                 .expect("No color formats supported")
         };
 
-        let ScreenSize { width, height } = screen.size();
+        let ScreenSize { width, height } = size;
         let surface_config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format: color_format,
@@ -120,12 +168,13 @@ impl Renderer {
             alpha_mode: wgpu::CompositeAlphaMode::Auto,
             view_formats: vec![],
         };
-        surface.configure(&device.device, &surface_config);
+        if let Some(surface) = &surface {
+            surface.configure(&device.device, &surface_config);
+        }
 
         let frame_buffer =
-            Self::create_frame_buffer(&device.device, &surface_config);
-        let depth_view =
-            Self::create_depth_buffer(&device.device, &surface_config);
+            Self::create_frame_buffer(&device.device, color_format, size);
+        let depth_view = Self::create_depth_buffer(&device.device, size);
 
         let uniform_buffer = device.device.create_buffer_init(
             &wgpu::util::BufferInitDescriptor {
@@ -154,23 +203,37 @@ impl Renderer {
                 label: None,
             },
         );
-        let bind_group =
+        let create_bind_group = |buffer: &wgpu::Buffer| {
             device.device.create_bind_group(&wgpu::BindGroupDescriptor {
                 layout: &bind_group_layout,
                 entries: &[wgpu::BindGroupEntry {
                     binding: 0,
                     resource: wgpu::BindingResource::Buffer(
                         wgpu::BufferBinding {
-                            buffer: &uniform_buffer,
+                            buffer,
                             offset: 0,
                             size: None,
                         },
                     ),
                 }],
                 label: None,
-            });
-
-        let geometries = Geometries::new(&device.device, &Vertices::empty());
+            })
+        };
+        let bind_group = create_bind_group(&uniform_buffer);
+
+        // Overlays are specified in normalized device coordinates, so they are
+        // drawn with uniforms that leave them as they are.
+        let overlay_bind_group =
+            create_bind_group(&device.device.create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice(&[Uniforms::default()]),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                },
+            ));
+
+        let geometries =
+            Geometries::new(&device.device, &ModelVertices::empty());
         let pipelines = Pipelines::new(
             &device.device,
             &bind_group_layout,
@@ -194,17 +257,20 @@ impl Renderer {
 
             uniform_buffer,
             bind_group,
+            overlay_bind_group,
 
             geometries,
             pipelines,
+            debug: DebugGeometry::new(),
 
             navigation_cube_renderer,
         })
     }
 
     /// Updates the geometry of the model being rendered.
-    pub fn update_geometry(&mut self, mesh: Vertices) {
-        self.geometries = Geometries::new(&self.device.device, &mesh);
+    pub fn update_geometry(&mut self, vertices: ModelVertices) {
+        self.geometries = Geometries::new(&self.device.device, &vertices);
+        self.debug = vertices.debug;
     }
 
     /// Resizes the render surface.
@@ -215,17 +281,16 @@ impl Renderer {
         self.surface_config.width = size.width;
         self.surface_config.height = size.height;
 
-        self.surface
-            .configure(&self.device.device, &self.surface_config);
+        if let Some(surface) = &self.surface {
+            surface.configure(&self.device.device, &self.surface_config);
+        }
 
         self.frame_buffer = Self::create_frame_buffer(
             &self.device.device,
-            &self.surface_config,
-        );
-        self.depth_view = Self::create_depth_buffer(
-            &self.device.device,
-            &self.surface_config,
+            self.surface_config.format,
+            size,
         );
+        self.depth_view = Self::create_depth_buffer(&self.device.device, size);
     }
 
     /// Draws the renderer, camera, and config state to the window.
@@ -234,20 +299,16 @@ impl Renderer {
         camera: &Camera,
         config: &DrawConfig,
     ) -> Result<(), DrawError> {
-        let aspect_ratio = f64::from(self.surface_config.width)
-            / f64::from(self.surface_config.height);
-        let uniforms = Uniforms {
-            transform: Transform::for_vertices(camera, aspect_ratio),
-            transform_normals: Transform::for_normals(camera),
+        let Some(surface) = &self.surface else {
+            // A headless renderer has nothing to draw to.
+            return Ok(());
         };
 
-        self.device.queue.write_buffer(
-            &self.uniform_buffer,
-            0,
-            bytemuck::cast_slice(&[uniforms]),
-        );
+        let aspect_ratio = f64::from(self.surface_config.width)
+            / f64::from(self.surface_config.height);
+        self.update_uniforms(camera, config, aspect_ratio);
 
-        let surface_texture = match self.surface.get_current_texture() {
+        let surface_texture = match surface.get_current_texture() {
             Ok(surface_texture) => surface_texture,
             Err(wgpu::SurfaceError::Timeout) => {
                 // I'm seeing this all the time now (as in, multiple times per
@@ -269,48 +330,15 @@ impl Renderer {
             &wgpu::CommandEncoderDescriptor { label: None },
         );
 
-        // Need this block here, as a render pass only takes effect once it's
-        // dropped.
-        {
-            let mut render_pass =
-                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    color_attachments: &[Some(
-                        wgpu::RenderPassColorAttachment {
-                            view: &self.frame_buffer,
-                            resolve_target: Some(&color_view),
-                            ops: wgpu::Operations {
-                                load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
-                                // Not necessary, due to MSAA being enabled.
-                                store: wgpu::StoreOp::Discard,
-                            },
-                        },
-                    )],
-                    depth_stencil_attachment: Some(
-                        wgpu::RenderPassDepthStencilAttachment {
-                            view: &self.depth_view,
-                            depth_ops: Some(wgpu::Operations {
-                                load: wgpu::LoadOp::Clear(1.0),
-                                store: wgpu::StoreOp::Store,
-                            }),
-                            stencil_ops: None,
-                        },
-                    ),
-                    ..Default::default()
-                });
-            render_pass.set_bind_group(0, &self.bind_group, &[]);
-
-            let drawables = Drawables::new(&self.geometries, &self.pipelines);
-
-            if config.draw_model {
-                drawables.model.draw(&mut render_pass);
-            }
-
-            if let Some(drawable) = drawables.mesh {
-                if config.draw_mesh {
-                    drawable.draw(&mut render_pass);
-                }
-            }
-        }
+        let overlays = self.overlays(camera, config, aspect_ratio);
+        self.draw_model(
+            &mut encoder,
+            &self.frame_buffer,
+            &self.depth_view,
+            &color_view,
+            config,
+            &overlays,
+        );
 
         self.navigation_cube_renderer.draw(
             &color_view,
@@ -330,21 +358,287 @@ impl Renderer {
         Ok(())
     }
 
+    /// Renders the model into an image of the provided size
+    ///
+    /// The image is rendered offscreen, independently of the size of the
+    /// window, if there is one. Unlike [`Renderer::draw`], this doesn't draw
+    /// the navigation cube, as that is only useful within an interactive
+    /// viewer.
+    pub fn capture(
+        &mut self,
+        camera: &Camera,
+        config: &DrawConfig,
+        size: ScreenSize,
+    ) -> Result<RgbaImage, CaptureError> {
+        let ScreenSize { width, height } = size;
+
+        let max_size = self.device.device.limits().max_texture_dimension_2d;
+        if width == 0 || height == 0 || width > max_size || height > max_size {
+            return Err(CaptureError::InvalidSize {
+                width,
+                height,
+                max_size,
+            });
+        }
+
+        let format = self.surface_config.format;
+        let swap_red_and_blue = match format {
+            wgpu::TextureFormat::Rgba8Unorm
+            | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm
+            | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            format => return Err(CaptureError::UnsupportedFormat(format)),
+        };
+
+        let aspect_ratio = f64::from(width) / f64::from(height);
+        self.update_uniforms(camera, config, aspect_ratio);
+
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture =
+            self.device.device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+        let color_view =
+            texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let frame_buffer =
+            Self::create_frame_buffer(&self.device.device, format, size);
+        let depth_view = Self::create_depth_buffer(&self.device.device, size);
+
+        // Rows of the copied image need to be aligned. We get rid of the
+        // padding again, once the data has been read back.
+        let bytes_per_pixel = 4;
+        let unpadded_bytes_per_row = width * bytes_per_pixel;
+        let padded_bytes_per_row = unpadded_bytes_per_row
+            .next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let buffer =
+            self.device.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: u64::from(padded_bytes_per_row) * u64::from(height),
+                usage: wgpu::BufferUsages::COPY_DST
+                    | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+
+        let mut encoder = self.device.device.create_command_encoder(
+            &wgpu::CommandEncoderDescriptor { label: None },
+        );
+
+        let overlays = self.overlays(camera, config, aspect_ratio);
+        self.draw_model(
+            &mut encoder,
+            &frame_buffer,
+            &depth_view,
+            &color_view,
+            config,
+            &overlays,
+        );
+
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            extent,
+        );
+
+        let command_buffer = encoder.finish();
+        self.device.queue.submit(Some(command_buffer));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            // If this fails, the receiver has been dropped, and nobody is
+            // interested in the result anymore.
+            let _ = sender.send(result);
+        });
+        self.device.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .expect("Buffer mapping callback should have been called")?;
+
+        let mut pixels = Vec::with_capacity(
+            unpadded_bytes_per_row as usize * height as usize,
+        );
+        for row in slice
+            .get_mapped_range()
+            .chunks_exact(padded_bytes_per_row as usize)
+        {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+        buffer.unmap();
+
+        if swap_red_and_blue {
+            for pixel in pixels.chunks_exact_mut(bytes_per_pixel as usize) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        trace!("Finished capture.");
+        Ok(RgbaImage::from_raw(width, height, pixels)
+            .expect("Pixel data should match image size"))
+    }
+
+    fn update_uniforms(
+        &self,
+        camera: &Camera,
+        config: &DrawConfig,
+        aspect_ratio: f64,
+    ) {
+        let uniforms = Uniforms::new(camera, config, aspect_ratio);
+
+        self.device.queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::cast_slice(&[uniforms]),
+        );
+    }
+
+    /// Creates the geometry that is drawn on top of everything else
+    ///
+    /// This is the scale indicator and the debug geometry, if they should be
+    /// drawn. Both depend on the camera, so unlike the rest of the geometry,
+    /// they can't be prepared when the model changes.
+    fn overlays(
+        &self,
+        camera: &Camera,
+        config: &DrawConfig,
+        aspect_ratio: f64,
+    ) -> Vec<Geometry> {
+        let mut overlays = Vec::new();
+
+        if config.draw_scale {
+            overlays.push(Geometry::new(
+                &self.device.device,
+                &guides::scale_indicator(camera, aspect_ratio),
+            ));
+        }
+        if config.draw_debug && !self.debug.is_empty() {
+            overlays.push(Geometry::new(
+                &self.device.device,
+                &debug::overlay(
+                    &self.debug,
+                    &config.hidden_debug_channels,
+                    camera,
+                    aspect_ratio,
+                ),
+            ));
+        }
+
+        overlays
+    }
+
+    fn draw_model(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        frame_buffer: &wgpu::TextureView,
+        depth_view: &wgpu::TextureView,
+        color_view: &wgpu::TextureView,
+        config: &DrawConfig,
+        overlays: &[Geometry],
+    ) {
+        let mut render_pass =
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: frame_buffer,
+                    resolve_target: Some(color_view),
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                        // Not necessary, due to MSAA being enabled.
+                        store: wgpu::StoreOp::Discard,
+                    },
+                })],
+                depth_stencil_attachment: Some(
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view: depth_view,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(1.0),
+                            store: wgpu::StoreOp::Store,
+                        }),
+                        stencil_ops: None,
+                    },
+                ),
+                ..Default::default()
+            });
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+
+        let drawables = Drawables::new(&self.geometries, &self.pipelines);
+
+        if config.draw_model {
+            match config.render_mode {
+                RenderMode::FaceColors => {
+                    drawables.face_colors.draw(&mut render_pass);
+                }
+                _ => {
+                    drawables.model.draw(&mut render_pass);
+                }
+            }
+        }
+
+        if let Some(drawable) = drawables.mesh {
+            if config.draw_mesh {
+                drawable.draw(&mut render_pass);
+            }
+        }
+
+        match config.render_mode {
+            RenderMode::Edges => drawables.edges.draw(&mut render_pass),
+            RenderMode::Normals => {
+                drawables.normals.draw(&mut render_pass);
+            }
+            RenderMode::Shaded | RenderMode::FaceColors => {}
+        }
+
+        drawables.measurement.draw(&mut render_pass);
+        drawables.failed_faces.draw(&mut render_pass);
+
+        if config.draw_grid {
+            drawables.grid.draw(&mut render_pass);
+        }
+        if config.draw_axes {
+            drawables.axes.draw(&mut render_pass);
+        }
+
+        render_pass.set_bind_group(0, &self.overlay_bind_group, &[]);
+        for geometry in overlays {
+            Drawable::new(geometry, &self.pipelines.lines)
+                .draw(&mut render_pass);
+        }
+    }
+
     fn create_frame_buffer(
         device: &wgpu::Device,
-        surface_config: &wgpu::SurfaceConfiguration,
+        format: wgpu::TextureFormat,
+        size: ScreenSize,
     ) -> wgpu::TextureView {
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: None,
             size: wgpu::Extent3d {
-                width: surface_config.width,
-                height: surface_config.height,
+                width: size.width,
+                height: size.height,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
             sample_count: SAMPLE_COUNT,
             dimension: wgpu::TextureDimension::D2,
-            format: surface_config.format,
+            format,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             view_formats: &[],
         });
@@ -353,13 +647,13 @@ impl Renderer {
 
     fn create_depth_buffer(
         device: &wgpu::Device,
-        surface_config: &wgpu::SurfaceConfiguration,
+        size: ScreenSize,
     ) -> wgpu::TextureView {
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: None,
             size: wgpu::Extent3d {
-                width: surface_config.width,
-                height: surface_config.height,
+                width: size.width,
+                height: size.height,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
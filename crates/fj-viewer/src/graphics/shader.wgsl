@@ -1,6 +1,8 @@
 struct Uniforms {
     transform: mat4x4<f32>,
     transform_normals: mat4x4<f32>,
+    // Normal in `xyz`, distance from the origin in `w`
+    section_plane: vec4<f32>,
 };
 
 @group(0) @binding(0)
@@ -16,6 +18,7 @@ struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) normal: vec3<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) model_position: vec3<f32>,
 };
 
 struct FragmentOutput {
@@ -27,6 +30,7 @@ fn vertex(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     out.normal = (uniforms.transform_normals * vec4<f32>(in.normal, 0.0)).xyz;
     out.position = uniforms.transform * vec4<f32>(in.position, 1.0);
+    out.model_position = in.position;
     // We use premultiplied alpha blending.
     out.color = vec4<f32>(in.color.rgb * in.color.a, in.color.a);
 
@@ -35,8 +39,17 @@ fn vertex(in: VertexInput) -> VertexOutput {
 
 const pi: f32 = 3.14159265359;
 
+fn is_cut_away(model_position: vec3<f32>) -> bool {
+    let plane = uniforms.section_plane;
+    return dot(plane.xyz, model_position) > plane.w;
+}
+
 @fragment
 fn frag_model(in: VertexOutput) -> FragmentOutput {
+    if is_cut_away(in.model_position) {
+        discard;
+    }
+
     let light = vec3<f32>(0.0, 0.0, -1.0);
 
     let angle = acos(dot(light, -in.normal));
@@ -52,7 +65,22 @@ fn frag_model(in: VertexOutput) -> FragmentOutput {
 
 @fragment
 fn frag_mesh(in: VertexOutput) -> FragmentOutput {
+    if is_cut_away(in.model_position) {
+        discard;
+    }
+
     var out: FragmentOutput;
     out.color = vec4<f32>(1.0 - in.color.rgb, in.color.a);
     return out;
 }
+
+@fragment
+fn frag_lines(in: VertexOutput) -> FragmentOutput {
+    if is_cut_away(in.model_position) {
+        discard;
+    }
+
+    var out: FragmentOutput;
+    out.color = in.color;
+    return out;
+}
@@ -0,0 +1,574 @@
+use std::f32::consts::PI;
+
+use image::RgbaImage;
+
+use crate::{camera::Camera, capture::CaptureError, screen::ScreenSize};
+
+use super::{
+    debug,
+    draw_config::{DrawConfig, RenderMode},
+    guides,
+    uniforms::Uniforms,
+    vertices::{ModelVertices, Vertex, Vertices},
+};
+
+/// Renders the model on the CPU
+///
+/// This is a fallback for when no graphics device is available, which is
+/// common on CI machines. It is much slower than [`Renderer`], but does the
+/// same thing that its shaders do, so captured images look the same.
+///
+/// [`Renderer`]: super::Renderer
+#[derive(Debug)]
+pub struct SoftwareRenderer {
+    vertices: ModelVertices,
+}
+
+impl SoftwareRenderer {
+    /// Returns a new `SoftwareRenderer`.
+    pub fn new() -> Self {
+        Self {
+            vertices: ModelVertices::empty(),
+        }
+    }
+
+    /// Updates the geometry of the model being rendered.
+    pub fn update_geometry(&mut self, vertices: ModelVertices) {
+        self.vertices = vertices;
+    }
+
+    /// Renders the model into an image of the provided size
+    ///
+    /// See [`Renderer::capture`].
+    ///
+    /// [`Renderer::capture`]: super::Renderer::capture
+    pub fn capture(
+        &self,
+        camera: &Camera,
+        config: &DrawConfig,
+        size: ScreenSize,
+    ) -> Result<RgbaImage, CaptureError> {
+        let ScreenSize { width, height } = size;
+
+        if width == 0 || height == 0 || width > MAX_SIZE || height > MAX_SIZE {
+            return Err(CaptureError::InvalidSize {
+                width,
+                height,
+                max_size: MAX_SIZE,
+            });
+        }
+
+        let aspect_ratio = f64::from(width) / f64::from(height);
+        let uniforms = Uniforms::new(camera, config, aspect_ratio);
+        let mut target =
+            Target::new(width * SUPERSAMPLING, height * SUPERSAMPLING);
+
+        // Same order as in `Renderer::draw_model`, as that affects blending.
+        if config.draw_model {
+            let vertices = match config.render_mode {
+                RenderMode::FaceColors => &self.vertices.face_colors,
+                _ => &self.vertices.mesh,
+            };
+            target.draw_triangles(vertices, &uniforms);
+        }
+        if config.draw_mesh {
+            target.draw_wireframe(&self.vertices.mesh, &uniforms);
+        }
+        match config.render_mode {
+            RenderMode::Edges => {
+                target.draw_lines(&self.vertices.edges, &uniforms);
+            }
+            RenderMode::Normals => {
+                target.draw_lines(&self.vertices.normals, &uniforms);
+            }
+            RenderMode::Shaded | RenderMode::FaceColors => {}
+        }
+        target.draw_lines(&self.vertices.measurement, &uniforms);
+        target.draw_lines(&self.vertices.failed_faces, &uniforms);
+        if config.draw_grid {
+            target.draw_lines(&self.vertices.grid, &uniforms);
+        }
+        if config.draw_axes {
+            target.draw_lines(&self.vertices.axes, &uniforms);
+        }
+        if config.draw_scale {
+            target.draw_lines(
+                &guides::scale_indicator(camera, aspect_ratio),
+                &Uniforms::default(),
+            );
+        }
+        if config.draw_debug {
+            target.draw_lines(
+                &debug::overlay(
+                    &self.vertices.debug,
+                    &config.hidden_debug_channels,
+                    camera,
+                    aspect_ratio,
+                ),
+                &Uniforms::default(),
+            );
+        }
+
+        Ok(target.into_image(width, height))
+    }
+}
+
+/// The color and depth buffers that the software renderer draws into
+struct Target {
+    width: u32,
+    height: u32,
+
+    /// Colors with premultiplied alpha, as in the shaders
+    color: Vec<[f32; 4]>,
+    depth: Vec<f32>,
+}
+
+impl Target {
+    fn new(width: u32, height: u32) -> Self {
+        let num_pixels = width as usize * height as usize;
+
+        Self {
+            width,
+            height,
+            color: vec![[1.; 4]; num_pixels],
+            depth: vec![1.; num_pixels],
+        }
+    }
+
+    /// Draw triangles, shaded like `frag_model` in `shader.wgsl` does
+    fn draw_triangles(&mut self, vertices: &Vertices, uniforms: &Uniforms) {
+        for triangle in vertices.indices().chunks_exact(3) {
+            let triangle =
+                [0, 1, 2].map(|i| vertices.vertices()[triangle[i] as usize]);
+
+            // All vertices of a triangle share the same normal and color.
+            let [nx, ny, nz] = triangle[0].normal;
+            let [_, _, normal_z, _] =
+                multiply(uniforms.transform_normals.0, [nx, ny, nz, 0.]);
+
+            // The light shines along the negative z-axis, so the dot product
+            // of its direction and the inverted normal is just this.
+            let angle = normal_z.clamp(-1., 1.).acos();
+            let brightness = (1. - angle / (PI * 0.75)).max(0.);
+
+            let [r, g, b, a] = premultiplied(triangle[0].color);
+            let color = [r * brightness, g * brightness, b * brightness, a];
+
+            self.fill_triangle(triangle, color, uniforms);
+        }
+    }
+
+    /// Draw the edges of triangles, colored like `frag_mesh` does
+    fn draw_wireframe(&mut self, vertices: &Vertices, uniforms: &Uniforms) {
+        for triangle in vertices.indices().chunks_exact(3) {
+            let [a, b, c] =
+                [0, 1, 2].map(|i| vertices.vertices()[triangle[i] as usize]);
+
+            let [red, green, blue, alpha] = premultiplied(a.color);
+            let color = [1. - red, 1. - green, 1. - blue, alpha];
+
+            for line in [[a, b], [b, c], [c, a]] {
+                self.draw_line(line, color, uniforms);
+            }
+        }
+    }
+
+    /// Draw a list of lines, colored like `frag_lines` does
+    fn draw_lines(&mut self, vertices: &Vertices, uniforms: &Uniforms) {
+        for line in vertices.indices().chunks_exact(2) {
+            let [a, b] = [0, 1].map(|i| vertices.vertices()[line[i] as usize]);
+            self.draw_line([a, b], premultiplied(a.color), uniforms);
+        }
+    }
+
+    fn fill_triangle(
+        &mut self,
+        vertices: [Vertex; 3],
+        color: [f32; 4],
+        uniforms: &Uniforms,
+    ) {
+        let Some([a, b, c]) = self.project_all(vertices, uniforms) else {
+            return;
+        };
+
+        let area = edge_function(a.screen, b.screen, c.screen);
+        if area == 0. {
+            return;
+        }
+
+        let [min_x, max_x, min_y, max_y] = self.bounding_box(&[a, b, c]);
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let pixel = [x as f32 + 0.5, y as f32 + 0.5];
+
+                // Dividing by the signed area makes these positive inside the
+                // triangle, regardless of its winding. Like the pipelines,
+                // we don't cull any triangles.
+                let weights = [
+                    edge_function(b.screen, c.screen, pixel) / area,
+                    edge_function(c.screen, a.screen, pixel) / area,
+                    edge_function(a.screen, b.screen, pixel) / area,
+                ];
+                if weights.iter().any(|&weight| weight < 0.) {
+                    continue;
+                }
+
+                let fragment = Projected::interpolate([a, b, c], weights);
+                self.shade([x, y], fragment, color, uniforms, 0.);
+            }
+        }
+    }
+
+    fn draw_line(
+        &mut self,
+        vertices: [Vertex; 2],
+        color: [f32; 4],
+        uniforms: &Uniforms,
+    ) {
+        let Some([a, b]) = self.project_all(vertices, uniforms) else {
+            return;
+        };
+
+        let [dx, dy] = [0, 1].map(|i| b.screen[i] - a.screen[i]);
+        let num_steps = dx.abs().max(dy.abs()).ceil().max(1.) as u32;
+
+        for step in 0..=num_steps {
+            let t = step as f32 / num_steps as f32;
+            let fragment = Projected::interpolate([a, b], [1. - t, t]);
+
+            // Lines are one pixel wide in the final image, so they need to
+            // cover multiple pixels of the supersampled one.
+            let [x, y] = fragment.screen.map(|coord| coord.floor() as i64);
+            let offset = i64::from(SUPERSAMPLING / 2);
+            for y in y - offset..y - offset + i64::from(SUPERSAMPLING) {
+                for x in x - offset..x - offset + i64::from(SUPERSAMPLING) {
+                    let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y))
+                    else {
+                        continue;
+                    };
+                    if x >= self.width || y >= self.height {
+                        continue;
+                    }
+
+                    self.shade(
+                        [x, y],
+                        fragment,
+                        color,
+                        uniforms,
+                        LINE_DEPTH_BIAS,
+                    );
+                }
+            }
+        }
+    }
+
+    fn project_all<const N: usize>(
+        &self,
+        vertices: [Vertex; N],
+        uniforms: &Uniforms,
+    ) -> Option<[Projected; N]> {
+        let projected = vertices
+            .map(|vertex| Projected::new(vertex, uniforms, self.size()));
+
+        // There's no clipping against the near plane. The camera places that
+        // in front of the model anyway, so anything behind it is skipped.
+        if projected.iter().any(Option::is_none) {
+            return None;
+        }
+        Some(projected.map(|projected| {
+            projected.expect("Just checked that all vertices are projected")
+        }))
+    }
+
+    fn bounding_box(&self, vertices: &[Projected]) -> [u32; 4] {
+        let [mut min_x, mut min_y] = [f32::INFINITY; 2];
+        let [mut max_x, mut max_y] = [f32::NEG_INFINITY; 2];
+
+        for vertex in vertices {
+            let [x, y] = vertex.screen;
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+        }
+
+        let [width, height] = self.size();
+        [
+            min_x.floor().clamp(0., width),
+            max_x.ceil().clamp(0., width),
+            min_y.floor().clamp(0., height),
+            max_y.ceil().clamp(0., height),
+        ]
+        .map(|coord| coord as u32)
+    }
+
+    /// Depth-test, clip, and blend a fragment into the target
+    ///
+    /// Mirrors the pipeline configuration: Fragments that are cut away by the
+    /// section plane are discarded, the depth test passes for equal depths,
+    /// and colors use premultiplied alpha blending.
+    fn shade(
+        &mut self,
+        [x, y]: [u32; 2],
+        fragment: Projected,
+        color: [f32; 4],
+        uniforms: &Uniforms,
+        depth_bias: f32,
+    ) {
+        if !(0. ..=1.).contains(&fragment.depth) {
+            return;
+        }
+
+        let [nx, ny, nz, distance] = uniforms.section_plane;
+        let [px, py, pz] = fragment.model_position;
+        if nx * px + ny * py + nz * pz > distance {
+            return;
+        }
+
+        let index = y as usize * self.width as usize + x as usize;
+        let depth = fragment.depth - depth_bias;
+        if depth > self.depth[index] {
+            return;
+        }
+        self.depth[index] = depth;
+
+        let dst = self.color[index];
+        let alpha = color[3];
+        self.color[index] =
+            [0, 1, 2, 3].map(|i| color[i] + dst[i] * (1. - alpha));
+    }
+
+    fn size(&self) -> [f32; 2] {
+        [self.width, self.height].map(|size| size as f32)
+    }
+
+    /// Average each block of supersampled pixels into one pixel of the image
+    fn into_image(self, width: u32, height: u32) -> RgbaImage {
+        let num_samples = (SUPERSAMPLING * SUPERSAMPLING) as f32;
+
+        RgbaImage::from_fn(width, height, |x, y| {
+            let mut sum = [0.; 4];
+
+            for sy in 0..SUPERSAMPLING {
+                for sx in 0..SUPERSAMPLING {
+                    let index = (y * SUPERSAMPLING + sy) as usize
+                        * self.width as usize
+                        + (x * SUPERSAMPLING + sx) as usize;
+                    for (sum, value) in sum.iter_mut().zip(self.color[index]) {
+                        *sum += value;
+                    }
+                }
+            }
+
+            image::Rgba(sum.map(|value| {
+                (value / num_samples * 255.).round().clamp(0., 255.) as u8
+            }))
+        })
+    }
+}
+
+/// A vertex, transformed into screen space
+#[derive(Clone, Copy)]
+struct Projected {
+    /// The position in pixels, with the origin in the upper-left corner
+    screen: [f32; 2],
+
+    /// The depth, in the same range that the GPU uses
+    depth: f32,
+
+    /// The reciprocal of the homogeneous `w` coordinate
+    ///
+    /// Needed for perspective-correct interpolation.
+    w_inv: f32,
+
+    /// The position in model space, which the section plane refers to
+    model_position: [f32; 3],
+}
+
+impl Projected {
+    fn new(
+        vertex: Vertex,
+        uniforms: &Uniforms,
+        [width, height]: [f32; 2],
+    ) -> Option<Self> {
+        let [x, y, z] = vertex.position;
+        let clip = multiply(uniforms.transform.0, [x, y, z, 1.]);
+
+        let w = clip[3];
+        if w <= 0. {
+            return None;
+        }
+        let w_inv = 1. / w;
+
+        let [x, y, depth] = [0, 1, 2].map(|i| clip[i] * w_inv);
+
+        Some(Self {
+            screen: [(x + 1.) / 2. * width, (1. - y) / 2. * height],
+            depth,
+            w_inv,
+            model_position: vertex.position,
+        })
+    }
+
+    /// Interpolate between projected vertices, using the provided weights
+    ///
+    /// The weights refer to screen space. Depth is linear in screen space, but
+    /// the model position needs to be corrected for perspective.
+    fn interpolate<const N: usize>(
+        vertices: [Self; N],
+        weights: [f32; N],
+    ) -> Self {
+        let mut screen = [0.; 2];
+        let mut depth = 0.;
+        let mut w_inv = 0.;
+        let mut model_position = [0.; 3];
+
+        for (vertex, weight) in vertices.iter().zip(weights) {
+            for (screen, coord) in screen.iter_mut().zip(vertex.screen) {
+                *screen += coord * weight;
+            }
+            depth += vertex.depth * weight;
+            w_inv += vertex.w_inv * weight;
+            for (position, coord) in
+                model_position.iter_mut().zip(vertex.model_position)
+            {
+                *position += coord * vertex.w_inv * weight;
+            }
+        }
+
+        Self {
+            screen,
+            depth,
+            w_inv,
+            model_position: model_position.map(|coord| coord / w_inv),
+        }
+    }
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`
+fn edge_function(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Multiply a column-major 4x4 matrix with a vector
+fn multiply(matrix: [f32; 16], vector: [f32; 4]) -> [f32; 4] {
+    [0, 1, 2, 3].map(|row| {
+        (0..4)
+            .map(|column| matrix[column * 4 + row] * vector[column])
+            .sum()
+    })
+}
+
+/// Convert a vertex color to premultiplied alpha, like the vertex shader does
+fn premultiplied([r, g, b, a]: [f32; 4]) -> [f32; 4] {
+    [r * a, g * a, b * a, a]
+}
+
+/// The number of samples per pixel, in each direction
+///
+/// Smooths edges, like the multisampling of the GPU renderer does.
+const SUPERSAMPLING: u32 = 2;
+
+/// The maximum width and height of captured images
+///
+/// Keeps the memory used by the supersampled buffers within reason.
+const MAX_SIZE: u32 = 4096;
+
+/// Moves lines slightly towards the camera
+///
+/// Most lines lie exactly on the surface of the model. Without this, rounding
+/// errors would hide parts of them.
+const LINE_DEPTH_BIAS: f32 = 1e-5;
+
+#[cfg(test)]
+mod tests {
+    use crate::graphics::{uniforms::Uniforms, vertices::Vertex};
+
+    use super::Target;
+
+    const RED: [f32; 4] = [1., 0., 0., 1.];
+    const BLUE: [f32; 4] = [0., 0., 1., 1.];
+    const WHITE: [f32; 4] = [1.; 4];
+
+    #[test]
+    fn fill_triangle_covers_pixels() {
+        let mut target = Target::new(4, 4);
+
+        // With the identity transform, this covers the lower-left half of the
+        // target, including the pixels on the diagonal.
+        let triangle = [[-1., -1., 0.5], [1., -1., 0.5], [-1., 1., 0.5]];
+        target.fill_triangle(triangle.map(vertex), RED, &Uniforms::default());
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = if y >= x { RED } else { WHITE };
+                assert_eq!(pixel(&target, [x, y]), expected, "pixel {x}, {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn fill_triangle_keeps_nearest_fragment() {
+        let uniforms = Uniforms::default();
+        let triangle =
+            |z| [[-1., -1., z], [3., -1., z], [-1., 3., z]].map(vertex);
+
+        // The result must not depend on the order in which triangles are
+        // drawn.
+        for [first, second] in
+            [[(0.2, RED), (0.8, BLUE)], [(0.8, BLUE), (0.2, RED)]]
+        {
+            let mut target = Target::new(2, 2);
+            for (z, color) in [first, second] {
+                target.fill_triangle(triangle(z), color, &uniforms);
+            }
+
+            assert_eq!(pixel(&target, [1, 1]), RED);
+        }
+    }
+
+    #[test]
+    fn fill_triangle_is_clipped() {
+        // Most of this triangle is outside of the target.
+        let triangle =
+            [[-5., -5., 0.5], [15., -5., 0.5], [-5., 15., 0.5]].map(vertex);
+
+        let mut target = Target::new(4, 4);
+        target.fill_triangle(triangle, RED, &Uniforms::default());
+        assert!(target.color.iter().all(|&color| color == RED));
+
+        // Fragments that are in front of the section plane are discarded.
+        let uniforms = Uniforms {
+            section_plane: [1., 0., 0., 0.],
+            ..Uniforms::default()
+        };
+        let mut target = Target::new(4, 4);
+        target.fill_triangle(triangle, RED, &uniforms);
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = if x < 2 { RED } else { WHITE };
+                assert_eq!(pixel(&target, [x, y]), expected, "pixel {x}, {y}");
+            }
+        }
+
+        // Fragments outside of the depth range are discarded too.
+        let triangle =
+            [[-5., -5., 1.5], [15., -5., 1.5], [-5., 15., 1.5]].map(vertex);
+        let mut target = Target::new(4, 4);
+        target.fill_triangle(triangle, RED, &Uniforms::default());
+        assert!(target.color.iter().all(|&color| color == WHITE));
+    }
+
+    fn vertex(position: [f32; 3]) -> Vertex {
+        Vertex {
+            position,
+            normal: [0., 0., 1.],
+            color: [1.; 4],
+        }
+    }
+
+    fn pixel(target: &Target, [x, y]: [u32; 2]) -> [f32; 4] {
+        target.color[(y * target.width + x) as usize]
+    }
+}
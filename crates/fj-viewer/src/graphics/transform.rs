@@ -1,6 +1,6 @@
 use bytemuck::{Pod, Zeroable};
 
-use crate::camera::Camera;
+use crate::camera::{Camera, Projection};
 
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(transparent)]
@@ -15,15 +15,30 @@ impl Transform {
     ///
     /// The returned transform is used for transforming vertices on the GPU.
     pub fn for_vertices(camera: &Camera, aspect_ratio: f64) -> Self {
-        let field_of_view_in_y = 2.
-            * ((camera.field_of_view_in_x() / 2.).tan() / aspect_ratio).atan();
-
-        let transform = camera.camera_to_model().project_to_array(
-            aspect_ratio,
-            field_of_view_in_y,
-            camera.near_plane(),
-            camera.far_plane(),
-        );
+        let transform = match camera.projection {
+            Projection::Perspective => {
+                let field_of_view_in_y = 2.
+                    * ((camera.field_of_view_in_x() / 2.).tan() / aspect_ratio)
+                        .atan();
+
+                camera.camera_to_model().project_to_array(
+                    aspect_ratio,
+                    field_of_view_in_y,
+                    camera.near_plane(),
+                    camera.far_plane(),
+                )
+            }
+            Projection::Orthographic => {
+                let half_width = camera.visible_half_width();
+
+                camera.camera_to_model().project_orthographic_to_array(
+                    half_width,
+                    half_width / aspect_ratio,
+                    camera.near_plane(),
+                    camera.far_plane(),
+                )
+            }
+        };
 
         Self(transform.map(|scalar| scalar.into_f32()))
     }
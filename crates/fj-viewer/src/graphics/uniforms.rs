@@ -1,19 +1,53 @@
 use bytemuck::{Pod, Zeroable};
 
-use super::transform::Transform;
+use crate::camera::Camera;
+
+use super::{
+    draw_config::{DrawConfig, SectionPlane},
+    transform::Transform,
+};
 
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
 pub struct Uniforms {
     pub transform: Transform,
     pub transform_normals: Transform,
+
+    /// The section plane, as normal (`xyz`) and distance from the origin (`w`)
+    ///
+    /// If there is no section plane, this is set to a plane that nothing is
+    /// located in front of.
+    pub section_plane: [f32; 4],
+}
+
+impl Uniforms {
+    pub fn new(
+        camera: &Camera,
+        config: &DrawConfig,
+        aspect_ratio: f64,
+    ) -> Self {
+        Self {
+            transform: Transform::for_vertices(camera, aspect_ratio),
+            transform_normals: Transform::for_normals(camera),
+            section_plane: config.section_plane.map_or(
+                NO_SECTION_PLANE,
+                |SectionPlane { normal, distance }| {
+                    let [x, y, z] = normal.normalize().components;
+                    [x, y, z, distance].map(|scalar| scalar.into_f32())
+                },
+            ),
+        }
+    }
 }
 
+pub const NO_SECTION_PLANE: [f32; 4] = [0., 0., 0., 1.];
+
 impl Default for Uniforms {
     fn default() -> Self {
         Self {
             transform: Transform::identity(),
             transform_normals: Transform::identity(),
+            section_plane: NO_SECTION_PLANE,
         }
     }
 }
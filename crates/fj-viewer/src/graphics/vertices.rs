@@ -1,5 +1,123 @@
+use std::collections::BTreeMap;
+
 use bytemuck::{Pod, Zeroable};
-use fj_interop::{Index, Mesh};
+use fj_interop::{
+    Color, DebugGeometry, FaceId, FailedFace, Index, Mesh, Triangle,
+};
+use fj_math::{Aabb, Point, Scalar};
+
+use super::guides;
+
+/// The vertices for all the ways a model can be rendered
+#[derive(Debug)]
+pub struct ModelVertices {
+    /// The triangles of the model, in their own colors
+    pub mesh: Vertices,
+
+    /// The triangles of the model, colored according to their face
+    pub face_colors: Vertices,
+
+    /// Lines along the boundaries of the model's faces
+    pub edges: Vertices,
+
+    /// Lines that visualize the normals of the model's triangles
+    pub normals: Vertices,
+
+    /// A line between two measured points
+    pub measurement: Vertices,
+
+    /// Lines along the boundaries of faces that failed to triangulate
+    pub failed_faces: Vertices,
+
+    /// A grid in the xy-plane, around the model and the origin
+    pub grid: Vertices,
+
+    /// Lines along the axes of the coordinate system
+    pub axes: Vertices,
+
+    /// The debug geometry of the model
+    ///
+    /// Is projected onto the screen when drawing, so it can't be converted
+    /// into vertices up front.
+    pub debug: DebugGeometry,
+}
+
+impl ModelVertices {
+    pub fn empty() -> Self {
+        Self {
+            mesh: Vertices::empty(),
+            face_colors: Vertices::empty(),
+            edges: Vertices::empty(),
+            normals: Vertices::empty(),
+            measurement: Vertices::empty(),
+            failed_faces: Vertices::empty(),
+            grid: Vertices::empty(),
+            axes: Vertices::empty(),
+            debug: DebugGeometry::new(),
+        }
+    }
+
+    /// Create vertices from a mesh, highlighting some of its triangles
+    ///
+    /// The length of the normal glyphs is provided by the caller, as it needs
+    /// to fit the size of the model. The grid and axes are sized to fit the
+    /// provided bounding box of the model.
+    pub fn new(
+        mesh: &Mesh<Point<3>>,
+        aabb: &Aabb<3>,
+        is_highlighted: impl Fn(&Triangle) -> bool,
+        normal_length: Scalar,
+        measurement: Option<[Point<3>; 2]>,
+        failed_faces: &[FailedFace],
+        debug: DebugGeometry,
+    ) -> Self {
+        Self {
+            mesh: Vertices::from_triangles(mesh, |triangle| {
+                if is_highlighted(triangle) {
+                    HIGHLIGHT_COLOR
+                } else {
+                    triangle.color
+                }
+            }),
+            face_colors: Vertices::from_triangles(mesh, |triangle| {
+                triangle.face.map(color_of_face).unwrap_or_default()
+            }),
+            edges: Vertices::from_lines(
+                face_boundaries(mesh).map(|line| (line, EDGE_COLOR)),
+            ),
+            normals: Vertices::from_lines(
+                mesh.triangle_indices().zip(mesh.normals()).map(
+                    |(indices, normal)| {
+                        let [a, b, c] = mesh.triangle_vertices(indices);
+
+                        let center = Point {
+                            coords: (a.coords + b.coords + c.coords) / 3.,
+                        };
+
+                        (
+                            [center, center + normal * normal_length],
+                            NORMAL_COLOR,
+                        )
+                    },
+                ),
+            ),
+            measurement: Vertices::from_lines(
+                measurement.map(|line| (line, MEASUREMENT_COLOR)),
+            ),
+            failed_faces: Vertices::from_lines(failed_faces.iter().flat_map(
+                |failed| {
+                    failed
+                        .boundary
+                        .iter()
+                        .map(|&line| (line, FAILED_FACE_COLOR))
+                },
+            )),
+            grid: guides::grid(aabb),
+            axes: guides::axes(aabb),
+            debug,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Vertices {
@@ -22,17 +140,16 @@ impl Vertices {
     pub fn indices(&self) -> &[Index] {
         self.indices.as_slice()
     }
-}
 
-impl From<&Mesh<fj_math::Point<3>>> for Vertices {
-    fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
+    fn from_triangles(
+        mesh: &Mesh<Point<3>>,
+        color: impl Fn(&Triangle) -> Color,
+    ) -> Self {
         let mut m = Mesh::new();
 
-        for triangle in mesh.triangles() {
+        for (triangle, normal) in mesh.triangles().zip(mesh.normals()) {
             let [a, b, c] = triangle.inner.points();
-
-            let normal = (b - a).cross(&(c - a)).normalize();
-            let color = triangle.color;
+            let color = color(&triangle);
 
             m.push_vertex((a, normal, color));
             m.push_vertex((b, normal, color));
@@ -52,6 +169,33 @@ impl From<&Mesh<fj_math::Point<3>>> for Vertices {
 
         Self { vertices, indices }
     }
+
+    /// Create vertices for rendering a list of lines
+    ///
+    /// Each pair of consecutive indices makes up a line.
+    pub fn from_lines(
+        lines: impl IntoIterator<Item = ([Point<3>; 2], Color)>,
+    ) -> Self {
+        let mut vertices = Vec::new();
+
+        for (points, color) in lines {
+            for point in points {
+                vertices.push(Vertex {
+                    position: point.into(),
+                    normal: [0.; 3],
+                    color: color.0.map(|v| f32::from(v) / 255.0),
+                });
+            }
+        }
+
+        let indices = (0..vertices.len())
+            .map(|index| {
+                Index::try_from(index).expect("Too many vertices for index")
+            })
+            .collect();
+
+        Self { vertices, indices }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
@@ -61,3 +205,48 @@ pub struct Vertex {
     pub normal: [f32; 3],
     pub color: [f32; 4],
 }
+
+/// Find the lines in the mesh that are on the boundary of a face
+///
+/// These are the lines that the approximation of the model's edges consists
+/// of: Lines that are shared by triangles of different faces, or that only
+/// belong to a single triangle.
+fn face_boundaries(
+    mesh: &Mesh<Point<3>>,
+) -> impl Iterator<Item = [Point<3>; 2]> + '_ {
+    // The vertices of the mesh are unique, so the lines can be identified by
+    // their indices, which are cheaper to compare than points.
+    let mut faces_by_line: BTreeMap<[Index; 2], Vec<Option<FaceId>>> =
+        BTreeMap::new();
+
+    for ([a, b, c], face) in mesh.triangle_indices().zip(mesh.faces()) {
+        for mut line in [[a, b], [b, c], [c, a]] {
+            line.sort();
+            faces_by_line.entry(line).or_default().push(face);
+        }
+    }
+
+    faces_by_line.into_iter().filter_map(move |(line, faces)| {
+        let is_boundary = match faces.as_slice() {
+            [a, b] => a != b || a.is_none(),
+            _ => true,
+        };
+        is_boundary.then(|| line.map(|index| mesh.vertex(index)))
+    })
+}
+
+/// Compute a color that distinguishes a face from its neighbors
+fn color_of_face(face: FaceId) -> Color {
+    // Object IDs of neighboring faces are often similar. Scramble the bits, so
+    // their colors are not.
+    let hash = face.0.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    let [r, g, b, ..] = hash.to_be_bytes();
+
+    Color([r, g, b, 255])
+}
+
+const HIGHLIGHT_COLOR: Color = Color([255, 128, 0, 255]);
+const EDGE_COLOR: Color = Color([0, 0, 0, 255]);
+const NORMAL_COLOR: Color = Color([0, 0, 255, 255]);
+const MEASUREMENT_COLOR: Color = Color([255, 0, 0, 255]);
+const FAILED_FACE_COLOR: Color = Color([255, 0, 255, 255]);
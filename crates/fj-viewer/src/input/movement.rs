@@ -1,7 +1,7 @@
 use fj_math::{Point, Scalar, Transform, Vector};
 
 use crate::{
-    camera::{Camera, FocusPoint},
+    camera::{Camera, FocusPoint, Projection},
     screen::NormalizedScreenPosition,
 };
 
@@ -17,10 +17,18 @@ impl Movement {
         let previous = camera.cursor_to_model_space(previous);
         let cursor = camera.cursor_to_model_space(current);
 
-        let d1 = Point::distance_to(&camera.position(), &cursor);
-        let d2 = Point::distance_to(&camera.position(), &focus_point.0);
+        // With a perspective projection, the cursor moves further at the
+        // focus point than it does on the near plane.
+        let scale = match camera.projection {
+            Projection::Perspective => {
+                let d1 = Point::distance_to(&camera.position(), &cursor);
+                let d2 = Point::distance_to(&camera.position(), &focus_point.0);
+                d2 / d1
+            }
+            Projection::Orthographic => Scalar::ONE,
+        };
 
-        let diff = (cursor - previous) * d2 / d1;
+        let diff = (cursor - previous) * scale;
         let offset = camera.camera_to_model().transform_vector(&diff);
 
         camera.translation = camera.translation
@@ -10,14 +10,19 @@
 
 mod assets;
 mod camera;
+mod capture;
 mod graphics;
 mod input;
 mod screen;
 mod viewer;
 
+pub use image::RgbaImage;
+
 pub use self::{
-    graphics::{DeviceError, RendererInitError},
+    camera::{Projection, StandardView},
+    capture::{save_frames, save_gif, CaptureError},
+    graphics::{DeviceError, RenderMode, RendererInitError, SectionPlane},
     input::InputEvent,
     screen::{NormalizedScreenPosition, Screen, ScreenSize},
-    viewer::Viewer,
+    viewer::{Measurement, Pick, Viewer},
 };
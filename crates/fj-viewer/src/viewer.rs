@@ -1,22 +1,36 @@
-use fj_interop::Model;
-use tracing::warn;
+use std::f64::consts::TAU;
+
+use fj_interop::{FaceId, Model, Triangle};
+use fj_math::{Aabb, Point, Scalar, Transform, Vector};
+use image::RgbaImage;
+use tracing::{info, warn};
 
 use crate::{
-    camera::{Camera, FocusPoint},
-    graphics::{DrawConfig, Renderer},
+    camera::{Camera, FocusPoint, Projection, StandardView},
+    graphics::{
+        DrawConfig, DrawError, ModelVertices, RenderMode, Renderer,
+        SectionPlane, SoftwareRenderer,
+    },
     input::InputHandler,
-    InputEvent, NormalizedScreenPosition, RendererInitError, Screen,
-    ScreenSize,
+    CaptureError, InputEvent, NormalizedScreenPosition, RendererInitError,
+    Screen, ScreenSize,
 };
 
 /// The Fornjot model viewer
 pub struct Viewer {
+    aspect_ratio: f64,
     camera: Camera,
     cursor: Option<NormalizedScreenPosition>,
     draw_config: DrawConfig,
     focus_point: Option<FocusPoint>,
-    renderer: Renderer,
+    renderer: AnyRenderer,
     model: Option<Model>,
+    exploded_model: Option<Model>,
+    exploded_view_factor: f64,
+    level_of_detail: Option<usize>,
+    pick: Option<Pick>,
+    measurement_start: Option<Pick>,
+    measurement: Option<Measurement>,
 }
 
 impl Viewer {
@@ -24,14 +38,57 @@ impl Viewer {
     pub async fn new(screen: &impl Screen) -> Result<Self, RendererInitError> {
         let renderer = Renderer::new(screen).await?;
 
-        Ok(Self {
+        let mut viewer = Self::from_renderer(AnyRenderer::Gpu(renderer));
+        viewer.aspect_ratio = aspect_ratio(screen.size());
+
+        Ok(viewer)
+    }
+
+    /// Construct a new instance of `Viewer` that doesn't render to a window
+    ///
+    /// Such a viewer doesn't display anything, but can still capture images of
+    /// the model, using [`Viewer::screenshot`] and [`Viewer::turntable`]. It
+    /// doesn't require a display server.
+    ///
+    /// If no graphics device is available, this falls back to rendering on
+    /// the CPU, like [`Viewer::new_software`].
+    pub async fn new_headless() -> Self {
+        let renderer = match Renderer::new_headless().await {
+            Ok(renderer) => AnyRenderer::Gpu(renderer),
+            Err(err) => {
+                warn!("Falling back to software rendering: {err}");
+                AnyRenderer::Software(SoftwareRenderer::new())
+            }
+        };
+
+        Self::from_renderer(renderer)
+    }
+
+    /// Construct a new instance of `Viewer` that renders on the CPU
+    ///
+    /// Like [`Viewer::new_headless`], but never uses a graphics device. This is
+    /// much slower, but the captured images don't depend on the graphics
+    /// drivers of the machine.
+    pub fn new_software() -> Self {
+        Self::from_renderer(AnyRenderer::Software(SoftwareRenderer::new()))
+    }
+
+    fn from_renderer(renderer: AnyRenderer) -> Self {
+        Self {
+            aspect_ratio: 1.,
             camera: Camera::default(),
             cursor: None,
             draw_config: DrawConfig::default(),
             focus_point: None,
             renderer,
             model: None,
-        })
+            exploded_model: None,
+            exploded_view_factor: 0.,
+            level_of_detail: None,
+            pick: None,
+            measurement_start: None,
+            measurement: None,
+        }
     }
 
     /// Access the cursor
@@ -49,14 +106,220 @@ impl Viewer {
         self.draw_config.draw_mesh = !self.draw_config.draw_mesh;
     }
 
+    /// Toggle the "draw grid" setting
+    pub fn toggle_draw_grid(&mut self) {
+        self.draw_config.draw_grid = !self.draw_config.draw_grid;
+    }
+
+    /// Toggle the "draw axes" setting
+    pub fn toggle_draw_axes(&mut self) {
+        self.draw_config.draw_axes = !self.draw_config.draw_axes;
+    }
+
+    /// Toggle the "draw scale" setting
+    pub fn toggle_draw_scale(&mut self) {
+        self.draw_config.draw_scale = !self.draw_config.draw_scale;
+    }
+
+    /// Toggle the "draw debug" setting
+    pub fn toggle_draw_debug(&mut self) {
+        self.draw_config.draw_debug = !self.draw_config.draw_debug;
+    }
+
+    /// Access the names of the current model's debug channels
+    pub fn debug_channels(&self) -> Vec<String> {
+        self.model
+            .iter()
+            .flat_map(|model| model.debug.channel_names())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Show or hide a channel of the model's debug geometry
+    ///
+    /// This setting is kept when the model changes, so a channel stays hidden,
+    /// if it is emitted again after a rebuild.
+    pub fn set_debug_channel_visible(&mut self, channel: &str, visible: bool) {
+        let hidden = &mut self.draw_config.hidden_debug_channels;
+
+        if visible {
+            hidden.remove(channel);
+        } else {
+            hidden.insert(channel.to_string());
+        }
+    }
+
+    /// Show only the next channel of the model's debug geometry
+    ///
+    /// Cycles through showing each channel on its own, then all of them.
+    pub fn cycle_debug_channel(&mut self) {
+        let channels = self.debug_channels();
+        let hidden = &mut self.draw_config.hidden_debug_channels;
+
+        let visible = channels
+            .iter()
+            .enumerate()
+            .filter(|(_, channel)| !hidden.contains(*channel))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let next = match visible.as_slice() {
+            [i] => i + 1,
+            _ => 0,
+        };
+
+        hidden.clear();
+        if let Some(shown) = channels.get(next) {
+            hidden.extend(
+                channels.iter().filter(|&channel| channel != shown).cloned(),
+            );
+            info!("Showing debug channel `{shown}`");
+        } else {
+            info!("Showing all debug channels");
+        }
+    }
+
+    /// Access the current render mode
+    pub fn render_mode(&self) -> RenderMode {
+        self.draw_config.render_mode
+    }
+
+    /// Switch to the next render mode
+    pub fn cycle_render_mode(&mut self) {
+        self.draw_config.render_mode = self.draw_config.render_mode.next();
+    }
+
+    /// Access the current projection
+    pub fn projection(&self) -> Projection {
+        self.camera.projection
+    }
+
+    /// Set how the model is projected onto the screen
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.camera.projection = projection;
+    }
+
+    /// Switch between perspective and orthographic projection
+    pub fn toggle_projection(&mut self) {
+        self.camera.projection = self.camera.projection.toggle();
+    }
+
+    /// Show the model from one of the standard views
+    ///
+    /// The whole model is made visible, as with [`Viewer::fit_to_view`].
+    pub fn set_view(&mut self, view: StandardView) {
+        let aabb = self.aabb();
+        self.camera.set_view(view, &aabb, self.aspect_ratio);
+    }
+
+    /// Move the camera, so the whole model is visible
+    ///
+    /// The direction that the model is viewed from doesn't change.
+    pub fn fit_to_view(&mut self) {
+        let aabb = self.aabb();
+        self.camera.fit(&aabb, self.aspect_ratio);
+    }
+
+    /// Access the current section plane
+    pub fn section_plane(&self) -> Option<SectionPlane> {
+        self.draw_config.section_plane
+    }
+
+    /// Set the plane that cuts the model, or remove it
+    pub fn set_section_plane(&mut self, section_plane: Option<SectionPlane>) {
+        self.draw_config.section_plane = section_plane;
+    }
+
+    /// Switch to the next axis-aligned section plane
+    ///
+    /// Cycles through section planes that are perpendicular to the x, y, and z
+    /// axes, and go through the center of the model, then no section plane.
+    pub fn cycle_section_plane(&mut self) {
+        let Some(model) = self.exploded_model.as_ref().or(self.model.as_ref())
+        else {
+            return;
+        };
+
+        let normal = match self.draw_config.section_plane {
+            None => Some(Vector::unit_x()),
+            Some(plane) if plane.normal == Vector::unit_x() => {
+                Some(Vector::unit_y())
+            }
+            Some(plane) if plane.normal == Vector::unit_y() => {
+                Some(Vector::unit_z())
+            }
+            Some(_) => None,
+        };
+
+        self.draw_config.section_plane = normal.map(|normal| SectionPlane {
+            normal,
+            distance: normal.dot(&model.aabb.center().coords),
+        });
+    }
+
+    /// Move the section plane along its normal
+    ///
+    /// The distance is specified in steps, which are relative to the size of
+    /// the model. Does nothing, if there is no section plane.
+    pub fn move_section_plane(&mut self, steps: f64) {
+        let (Some(model), Some(plane)) = (
+            self.exploded_model.as_ref().or(self.model.as_ref()),
+            &mut self.draw_config.section_plane,
+        ) else {
+            return;
+        };
+
+        let step = model.aabb.size().magnitude() * 0.02;
+        plane.distance += step * steps;
+    }
+
     /// Handle the model being updated
     pub fn handle_model_update(&mut self, model: Model) {
-        self.renderer.update_geometry((&model.mesh).into());
+        self.pick = None;
+        self.measurement_start = None;
+        self.measurement = None;
+        self.level_of_detail = None;
 
         let aabb = model.aabb;
         if self.model.replace(model).is_none() {
             self.camera.init_planes(&aabb);
         }
+
+        self.update_exploded_model();
+        self.update_geometry();
+    }
+
+    /// Access the factor of the exploded view
+    ///
+    /// See [`Model::exploded_mesh`].
+    pub fn exploded_view_factor(&self) -> f64 {
+        self.exploded_view_factor
+    }
+
+    /// Set the factor of the exploded view
+    ///
+    /// A factor of zero disables the exploded view. See [`Model::exploded_mesh`]
+    /// for more information.
+    pub fn set_exploded_view_factor(&mut self, factor: f64) {
+        self.exploded_view_factor = factor;
+
+        self.pick = None;
+        self.measurement_start = None;
+        self.measurement = None;
+
+        self.update_exploded_model();
+        self.update_geometry();
+    }
+
+    /// Switch to the next exploded view factor
+    ///
+    /// Cycles through a few factors, starting with no exploded view.
+    pub fn cycle_exploded_view(&mut self) {
+        let factor = match self.exploded_view_factor {
+            factor if factor < 0.5 => 0.5,
+            factor if factor < 1. => 1.,
+            _ => 0.,
+        };
+        self.set_exploded_view_factor(factor);
     }
 
     /// Handle an input event
@@ -68,12 +331,15 @@ impl Viewer {
 
     /// Handle the screen being resized
     pub fn handle_screen_resize(&mut self, screen_size: ScreenSize) {
+        self.aspect_ratio = aspect_ratio(screen_size);
         self.renderer.handle_resize(screen_size);
     }
 
     /// Compute and store a focus point, unless one is already stored
     pub fn add_focus_point(&mut self) {
-        if let Some(model) = &self.model {
+        if let Some(model) =
+            self.exploded_model.as_ref().or(self.model.as_ref())
+        {
             if self.focus_point.is_none() {
                 self.focus_point =
                     Some(self.camera.focus_point(self.cursor, model));
@@ -86,18 +352,357 @@ impl Viewer {
         self.focus_point = None;
     }
 
-    /// Draw the graphics
-    pub fn draw(&mut self) {
-        let aabb = self
-            .model
+    /// Pick the part of the model that the cursor currently points to
+    ///
+    /// Highlights the picked face, or the picked triangle, if it is not known
+    /// which face it belongs to. Returns `None` and removes any highlight, if
+    /// the cursor doesn't point to the model.
+    ///
+    /// The returned [`Pick`] contains the [`FaceId`] of the picked face. The
+    /// code that created the model can use that to find the face itself.
+    pub fn pick(&mut self) -> Option<Pick> {
+        let model = self.exploded_model.as_ref().or(self.model.as_ref())?;
+
+        let pick = self.cursor.and_then(|cursor| {
+            let (triangle, point) =
+                self.camera.pick_triangle(cursor, &model.mesh)?;
+            Some(Pick { point, triangle })
+        });
+
+        if let Some(pick) = pick {
+            info!(
+                "Picked point {:?} on face {:?} (triangle normal: {:?})",
+                pick.point,
+                pick.face(),
+                pick.normal(),
+            );
+        }
+
+        self.pick = pick;
+        self.update_geometry();
+
+        pick
+    }
+
+    /// Measure between the point the cursor points to and a previous one
+    ///
+    /// The first call picks the starting point of the measurement, and returns
+    /// `None`. The second call picks the end point, and returns the resulting
+    /// [`Measurement`], which is also displayed.
+    ///
+    /// A call that doesn't pick a point, because the cursor doesn't point to
+    /// the model, starts a new measurement.
+    pub fn measure(&mut self) -> Option<Measurement> {
+        let Some(end) = self.pick() else {
+            self.measurement_start = None;
+            return None;
+        };
+
+        let Some(start) = self.measurement_start.take() else {
+            self.measurement_start = Some(end);
+            self.measurement = None;
+            self.update_geometry();
+            return None;
+        };
+
+        let measurement = Measurement::new(start, end);
+        match measurement.angle_rad {
+            Some(angle) => info!(
+                "Distance: {:?}; angle between faces: {:.2}°",
+                measurement.distance,
+                angle.into_f64().to_degrees(),
+            ),
+            None => info!("Distance: {:?}", measurement.distance),
+        }
+
+        self.measurement = Some(measurement);
+        self.update_geometry();
+
+        Some(measurement)
+    }
+
+    fn update_exploded_model(&mut self) {
+        self.exploded_model = self.model.as_ref().and_then(|model| {
+            if self.exploded_view_factor == 0. || model.parts.len() < 2 {
+                return None;
+            }
+
+            let mesh = model.exploded_mesh(self.exploded_view_factor);
+            let aabb = Aabb::<3>::from_points(mesh.vertices());
+
+            // The levels of detail don't have parts that could be exploded,
+            // so the exploded view always shows the full-detail mesh.
+            Some(Model {
+                mesh,
+                aabb,
+                parts: model.parts.clone(),
+                levels_of_detail: Vec::new(),
+                debug: model.debug.clone(),
+                failed_faces: model.failed_faces.clone(),
+            })
+        });
+    }
+
+    fn update_geometry(&mut self) {
+        let Some(model) = self.exploded_model.as_ref().or(self.model.as_ref())
+        else {
+            return;
+        };
+
+        // The normals are only there for visualization. They should be
+        // visible, but not clutter the view.
+        let normal_length = model.aabb.size().magnitude() * 0.02;
+
+        let pick = self.pick;
+        self.renderer.update_geometry(ModelVertices::new(
+            model.mesh_at(self.level_of_detail),
+            &model.aabb,
+            |triangle| pick.is_some_and(|pick| pick.contains(triangle)),
+            normal_length,
+            self.measurement.map(|measurement| measurement.points),
+            &model.failed_faces,
+            model.debug.clone(),
+        ));
+    }
+
+    /// Select the level of detail that matches the camera's distance
+    ///
+    /// Updates the geometry, if that results in a different level of detail
+    /// being selected.
+    fn update_level_of_detail(&mut self) {
+        let level_of_detail = self
+            .exploded_model
+            .as_ref()
+            .or(self.model.as_ref())
+            .and_then(|model| {
+                // The distance to the closest point of the model can't be
+                // smaller than this. Close enough, for this purpose.
+                let distance = (self.camera.position() - model.aabb.center())
+                    .magnitude()
+                    - model.aabb.size().magnitude() / 2.;
+                let max_error =
+                    distance.max(Scalar::ZERO) * MAX_ERROR_PER_DISTANCE;
+
+                model.level_of_detail(max_error)
+            });
+
+        if level_of_detail != self.level_of_detail {
+            self.level_of_detail = level_of_detail;
+            self.update_geometry();
+        }
+    }
+
+    fn aabb(&self) -> Aabb<3> {
+        self.exploded_model
             .as_ref()
+            .or(self.model.as_ref())
             .map(|shape| shape.aabb)
-            .unwrap_or_default();
+            .unwrap_or_default()
+    }
+
+    /// Prepare for rendering the model from the current camera position
+    fn update_camera(&mut self) {
+        let aabb = self.aabb();
 
         self.camera.update_planes(&aabb);
+        self.update_level_of_detail();
+    }
+
+    /// Draw the graphics
+    pub fn draw(&mut self) {
+        self.update_camera();
 
         if let Err(err) = self.renderer.draw(&self.camera, &self.draw_config) {
             warn!("Draw error: {}", err);
         }
     }
+
+    /// Capture an image of the model, as it is currently shown
+    ///
+    /// The image is rendered at the provided size, independently of the size
+    /// of the screen. The navigation cube is not included.
+    pub fn screenshot(
+        &mut self,
+        size: ScreenSize,
+    ) -> Result<RgbaImage, CaptureError> {
+        self.update_camera();
+        self.renderer.capture(&self.camera, &self.draw_config, size)
+    }
+
+    /// Capture the frames of a turntable animation of the model
+    ///
+    /// Starting from the current view, the model makes one full turn around
+    /// the vertical axis through its center, which is parallel to the z-axis.
+    /// Each of the frames advances the rotation by the same angle, and the
+    /// last frame stops one step short of a full turn, so the animation loops
+    /// seamlessly.
+    ///
+    /// The current view is restored afterwards.
+    pub fn turntable(
+        &mut self,
+        size: ScreenSize,
+        num_frames: usize,
+    ) -> Result<Vec<RgbaImage>, CaptureError> {
+        let center = self
+            .exploded_model
+            .as_ref()
+            .or(self.model.as_ref())
+            .map_or_else(Point::origin, |model| model.aabb.center());
+
+        let rotation = self.camera.rotation;
+        let translation = self.camera.translation;
+        let camera_to_model = self.camera.camera_to_model();
+        let rotate_around = Transform::translation(center.coords);
+
+        let frames = (0..num_frames)
+            .map(|i| {
+                let angle = TAU * i as f64 / num_frames as f64;
+                let transform = camera_to_model
+                    * rotate_around
+                    * Transform::rotation(Vector::unit_z() * angle)
+                    * rotate_around.inverse();
+
+                self.camera.rotation = transform.extract_rotation();
+                self.camera.translation = transform.extract_translation();
+
+                self.screenshot(size)
+            })
+            .collect();
+
+        self.camera.rotation = rotation;
+        self.camera.translation = translation;
+
+        frames
+    }
+}
+
+fn aspect_ratio(size: ScreenSize) -> f64 {
+    let [width, height] = size.as_f64();
+    width / height
+}
+
+/// The renderer that a [`Viewer`] uses
+// There's only ever one of these per viewer, so its size doesn't matter.
+#[allow(clippy::large_enum_variant)]
+enum AnyRenderer {
+    /// Renders on the GPU, to a window or offscreen
+    Gpu(Renderer),
+
+    /// Renders on the CPU, which only supports capturing images
+    Software(SoftwareRenderer),
+}
+
+impl AnyRenderer {
+    fn update_geometry(&mut self, vertices: ModelVertices) {
+        match self {
+            Self::Gpu(renderer) => renderer.update_geometry(vertices),
+            Self::Software(renderer) => renderer.update_geometry(vertices),
+        }
+    }
+
+    fn handle_resize(&mut self, size: ScreenSize) {
+        match self {
+            Self::Gpu(renderer) => renderer.handle_resize(size),
+            Self::Software(_) => {}
+        }
+    }
+
+    fn draw(
+        &mut self,
+        camera: &Camera,
+        config: &DrawConfig,
+    ) -> Result<(), DrawError> {
+        match self {
+            Self::Gpu(renderer) => renderer.draw(camera, config),
+            Self::Software(_) => Ok(()),
+        }
+    }
+
+    fn capture(
+        &mut self,
+        camera: &Camera,
+        config: &DrawConfig,
+        size: ScreenSize,
+    ) -> Result<RgbaImage, CaptureError> {
+        match self {
+            Self::Gpu(renderer) => renderer.capture(camera, config, size),
+            Self::Software(renderer) => renderer.capture(camera, config, size),
+        }
+    }
+}
+
+/// The acceptable approximation error, per unit of distance from the camera
+///
+/// This is roughly the size of a pixel on a typical screen, at the camera's
+/// field of view. Errors smaller than that are not visible.
+const MAX_ERROR_PER_DISTANCE: f64 = 1e-3;
+
+/// The result of picking a part of the model
+///
+/// See [`Viewer::pick`].
+#[derive(Clone, Copy, Debug)]
+pub struct Pick {
+    /// The picked point on the model
+    pub point: Point<3>,
+
+    /// The triangle that the picked point is on
+    pub triangle: Triangle,
+}
+
+impl Pick {
+    /// Access the ID of the picked face, if known
+    pub fn face(&self) -> Option<FaceId> {
+        self.triangle.face
+    }
+
+    /// Access the normal of the picked triangle
+    pub fn normal(&self) -> Vector<3> {
+        let [a, b, c] = self.triangle.inner.points();
+        (b - a).cross(&(c - a)).normalize()
+    }
+
+    fn contains(&self, triangle: &Triangle) -> bool {
+        match self.face() {
+            Some(face) => triangle.face == Some(face),
+            None => triangle == &self.triangle,
+        }
+    }
+}
+
+/// A measurement between two picked points
+///
+/// See [`Viewer::measure`].
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    /// The measured points
+    pub points: [Point<3>; 2],
+
+    /// The distance between the measured points
+    pub distance: Scalar,
+
+    /// The angle between the faces that the points are on, in radians
+    ///
+    /// `None`, if both points are on the same face. This is the angle between
+    /// the normals of the picked triangles, which is exact for planar faces.
+    pub angle_rad: Option<Scalar>,
+}
+
+impl Measurement {
+    fn new(start: Pick, end: Pick) -> Self {
+        let is_same_face = match (start.face(), end.face()) {
+            (Some(a), Some(b)) => a == b,
+            _ => start.triangle == end.triangle,
+        };
+
+        let angle_rad = (!is_same_face).then(|| {
+            let cos = start.normal().dot(&end.normal()).into_f64();
+            Scalar::from_f64(cos.clamp(-1., 1.).acos())
+        });
+
+        Self {
+            points: [start.point, end.point],
+            distance: start.point.distance_to(&end.point),
+            angle_rad,
+        }
+    }
 }
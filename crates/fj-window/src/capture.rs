@@ -0,0 +1,63 @@
+use std::{path::Path, time::Duration};
+
+use fj_interop::Model;
+use fj_viewer::{CaptureError, ScreenSize, StandardView, Viewer};
+use futures::executor::block_on;
+
+/// Save an image of the provided model as a PNG file, without opening a window
+///
+/// The model is shown in the isometric view. This works without a display
+/// server, and without a graphics device. See [`Viewer::new_headless`].
+pub fn screenshot(
+    model: Model,
+    size: ScreenSize,
+    path: impl AsRef<Path>,
+) -> Result<(), CaptureError> {
+    let mut viewer = headless_viewer(model, size);
+    viewer.screenshot(size)?.save(path)?;
+
+    Ok(())
+}
+
+/// Save a turntable animation of the provided model, without opening a window
+///
+/// The model is shown in the isometric view, while making a full turn around
+/// its vertical axis. See [`Viewer::turntable`].
+///
+/// If `path` has the extension `gif`, the animation is saved as an animated
+/// GIF. Otherwise, `path` is used as a directory, into which the frames are
+/// saved as a sequence of PNG files. See [`fj_viewer::save_frames`].
+pub fn turntable(
+    model: Model,
+    size: ScreenSize,
+    num_frames: usize,
+    path: impl AsRef<Path>,
+) -> Result<(), CaptureError> {
+    let path = path.as_ref();
+
+    let mut viewer = headless_viewer(model, size);
+    let frames = viewer.turntable(size, num_frames)?;
+
+    let is_gif = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"));
+    if is_gif {
+        fj_viewer::save_gif(&frames, path, TURNTABLE_FRAME_DELAY)?;
+    } else {
+        fj_viewer::save_frames(&frames, path)?;
+    }
+
+    Ok(())
+}
+
+fn headless_viewer(model: Model, size: ScreenSize) -> Viewer {
+    let mut viewer = block_on(Viewer::new_headless());
+    viewer.handle_screen_resize(size);
+    viewer.handle_model_update(model);
+    viewer.set_view(StandardView::Isometric);
+
+    viewer
+}
+
+/// The time each frame of an animated GIF is shown
+const TURNTABLE_FRAME_DELAY: Duration = Duration::from_millis(40);
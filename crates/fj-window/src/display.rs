@@ -1,9 +1,11 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::{path::PathBuf, thread};
+
 use fj_interop::Model;
 use fj_viewer::{
     InputEvent, NormalizedScreenPosition, RendererInitError, Screen,
-    ScreenSize, Viewer,
+    ScreenSize, StandardView, Viewer,
 };
-use futures::executor::block_on;
 use winit::{
     application::ApplicationHandler,
     dpi::PhysicalPosition,
@@ -11,7 +13,7 @@ use winit::{
     event::{
         ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent,
     },
-    event_loop::{ActiveEventLoop, EventLoop},
+    event_loop::{ActiveEventLoop, EventLoop, EventLoopClosed, EventLoopProxy},
     keyboard::{Key, NamedKey},
     window::WindowId,
 };
@@ -19,24 +21,117 @@ use winit::{
 use crate::window::{self, Window};
 
 /// Display the provided mesh in a window that processes input
+///
+/// Returns once the window is closed. On the web, the model is displayed in a
+/// canvas that is appended to the document, and this function returns right
+/// away, while the canvas stays interactive.
 pub fn display(model: Model, invert_zoom: bool) -> Result<(), Error> {
-    let event_loop = EventLoop::new()?;
-
-    let mut display_state = DisplayState {
-        model: Some(model),
-        invert_zoom,
-        window: None,
-        viewer: None,
-        held_mouse_button: None,
-        new_size: None,
-        stop_drawing: false,
+    let event_loop = EventLoop::<UserEvent>::with_user_event().build()?;
+    let display_state =
+        DisplayState::new(Some(model), invert_zoom, &event_loop);
+
+    run(event_loop, display_state)
+}
+
+/// Display multiple models in a window, and switch between them
+///
+/// Displays the first model. Pressing Tab switches to the next one, while the
+/// camera stays where it is. Returns once the window is closed, like
+/// [`display`].
+///
+/// # Panics
+///
+/// Panics, if `models` is empty.
+pub fn display_models(
+    models: Vec<Model>,
+    invert_zoom: bool,
+) -> Result<(), Error> {
+    let first = models.first().expect("Expected at least one model").clone();
+
+    let event_loop = EventLoop::<UserEvent>::with_user_event().build()?;
+    let mut display_state =
+        DisplayState::new(Some(first), invert_zoom, &event_loop);
+    display_state.models = models;
+
+    run(event_loop, display_state)
+}
+
+/// Display models in a window, as they are sent to it
+///
+/// Calls `f` on a separate thread, passing it a [`ModelSender`]. Every model
+/// that is sent replaces the one that is currently displayed, while the camera
+/// stays where it is. This is useful for showing a model that is rebuilt, for
+/// example whenever its source changes.
+///
+/// The window stays empty until the first model is sent. Returns once the
+/// window is closed.
+///
+/// Not available on the web, as it requires threads.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn display_live(
+    invert_zoom: bool,
+    f: impl FnOnce(ModelSender) + Send + 'static,
+) -> Result<(), Error> {
+    let event_loop = EventLoop::<UserEvent>::with_user_event().build()?;
+
+    let sender = ModelSender {
+        proxy: event_loop.create_proxy(),
     };
+    thread::spawn(move || f(sender));
+
+    let display_state = DisplayState::new(None, invert_zoom, &event_loop);
+
+    run(event_loop, display_state)
+}
 
+#[cfg(not(target_arch = "wasm32"))]
+fn run(
+    event_loop: EventLoop<UserEvent>,
+    mut display_state: DisplayState,
+) -> Result<(), Error> {
     event_loop.run_app(&mut display_state)?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn run(
+    event_loop: EventLoop<UserEvent>,
+    display_state: DisplayState,
+) -> Result<(), Error> {
+    use winit::platform::web::EventLoopExtWebSys;
 
+    // Blocking the browser's event loop is not an option. The event loop runs
+    // in the background instead.
+    event_loop.spawn_app(display_state);
     Ok(())
 }
 
+/// Sends models to a window that was opened by [`display_live`]
+#[derive(Clone)]
+pub struct ModelSender {
+    proxy: EventLoopProxy<UserEvent>,
+}
+
+impl ModelSender {
+    /// Send a model to the window, replacing the one it currently displays
+    ///
+    /// Returns the model in an error, if the window has been closed.
+    pub fn send(&self, model: Model) -> Result<(), WindowClosed> {
+        self.proxy.send_event(UserEvent::Model(model)).map_err(
+            |EventLoopClosed(event)| match event {
+                UserEvent::Model(model) => WindowClosed(model),
+                #[cfg(target_arch = "wasm32")]
+                UserEvent::Viewer(_) => unreachable!("Only sent models"),
+            },
+        )
+    }
+}
+
+/// Error returned by [`ModelSender::send`], if the window has been closed
+#[derive(Debug, thiserror::Error)]
+#[error("Window has been closed")]
+pub struct WindowClosed(pub Model);
+
 /// Main loop initialization error
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -53,29 +148,92 @@ pub enum Error {
     Graphics(#[from] RendererInitError),
 }
 
+/// An event that is sent to the event loop from outside of it
+enum UserEvent {
+    /// A model was sent through a [`ModelSender`]
+    Model(Model),
+
+    /// The viewer has been created
+    ///
+    /// On the web, the viewer is created asynchronously, as blocking until it
+    /// is ready is not possible there.
+    #[cfg(target_arch = "wasm32")]
+    Viewer(Viewer),
+}
+
 struct DisplayState {
     model: Option<Model>,
+    models: Vec<Model>,
+    current_model: usize,
     invert_zoom: bool,
     window: Option<Window>,
     viewer: Option<Viewer>,
     held_mouse_button: Option<MouseButton>,
     new_size: Option<ScreenSize>,
     stop_drawing: bool,
+
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    proxy: EventLoopProxy<UserEvent>,
 }
 
-impl ApplicationHandler for DisplayState {
-    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let window = self
-            .window
-            .get_or_insert_with(|| Window::new(event_loop).unwrap());
+impl DisplayState {
+    fn new(
+        model: Option<Model>,
+        invert_zoom: bool,
+        event_loop: &EventLoop<UserEvent>,
+    ) -> Self {
+        Self {
+            model,
+            models: Vec::new(),
+            current_model: 0,
+            invert_zoom,
+            window: None,
+            viewer: None,
+            held_mouse_button: None,
+            new_size: None,
+            stop_drawing: false,
+            proxy: event_loop.create_proxy(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn create_viewer(&mut self, window: &Window) {
+        let viewer = futures::executor::block_on(Viewer::new(window)).unwrap();
+        self.set_viewer(viewer);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn create_viewer(&mut self, window: &Window) {
+        let window = window.clone();
+        let proxy = self.proxy.clone();
 
-        let viewer = self
-            .viewer
-            .get_or_insert_with(|| block_on(Viewer::new(window)).unwrap());
+        wasm_bindgen_futures::spawn_local(async move {
+            let viewer = Viewer::new(&window).await.unwrap();
 
+            // If the event loop is gone, there's nothing left to display
+            // anything in.
+            let _ = proxy.send_event(UserEvent::Viewer(viewer));
+        });
+    }
+
+    fn set_viewer(&mut self, mut viewer: Viewer) {
         if let Some(model) = self.model.take() {
             viewer.handle_model_update(model);
         }
+
+        self.viewer = Some(viewer);
+    }
+}
+
+impl ApplicationHandler<UserEvent> for DisplayState {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return;
+        }
+
+        let window = Window::new(event_loop).unwrap();
+        self.create_viewer(&window);
+        self.window = Some(window);
     }
 
     fn window_event(
@@ -116,12 +274,74 @@ impl ApplicationHandler for DisplayState {
                 Key::Named(NamedKey::Escape) => {
                     event_loop.exit();
                 }
+                Key::Named(NamedKey::Tab) if !self.models.is_empty() => {
+                    self.current_model =
+                        (self.current_model + 1) % self.models.len();
+                    viewer.handle_model_update(
+                        self.models[self.current_model].clone(),
+                    );
+                }
                 Key::Character("1") => {
                     viewer.toggle_draw_model();
                 }
                 Key::Character("2") => {
                     viewer.toggle_draw_mesh();
                 }
+                Key::Character("3") => {
+                    viewer.cycle_render_mode();
+                }
+                Key::Character("4") => {
+                    viewer.cycle_section_plane();
+                }
+                Key::Character("5") => {
+                    viewer.cycle_exploded_view();
+                }
+                Key::Character("6") => {
+                    viewer.toggle_draw_grid();
+                }
+                Key::Character("7") => {
+                    viewer.toggle_draw_axes();
+                }
+                Key::Character("8") => {
+                    viewer.toggle_draw_scale();
+                }
+                Key::Character("9") => {
+                    viewer.toggle_draw_debug();
+                }
+                Key::Character("0") => {
+                    viewer.cycle_debug_channel();
+                }
+                Key::Character("[") => {
+                    viewer.move_section_plane(-1.);
+                }
+                Key::Character("]") => {
+                    viewer.move_section_plane(1.);
+                }
+                Key::Character("m") => {
+                    viewer.measure();
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                Key::Character("p") => {
+                    save_screenshot(viewer, window.size());
+                }
+                Key::Character("o") => {
+                    viewer.toggle_projection();
+                }
+                Key::Character("t") => {
+                    viewer.set_view(StandardView::Top);
+                }
+                Key::Character("f") => {
+                    viewer.set_view(StandardView::Front);
+                }
+                Key::Character("r") => {
+                    viewer.set_view(StandardView::Right);
+                }
+                Key::Character("i") => {
+                    viewer.set_view(StandardView::Isometric);
+                }
+                Key::Character("a") => {
+                    viewer.fit_to_view();
+                }
                 _ => {}
             },
             WindowEvent::Resized(size) => {
@@ -130,6 +350,18 @@ impl ApplicationHandler for DisplayState {
                     height: size.height,
                 });
             }
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Middle,
+                ..
+            } => {
+                // The middle button is used for picking only. Releasing it must
+                // not interfere with rotating or moving, which might still be
+                // going on.
+                if state == ElementState::Pressed {
+                    viewer.pick();
+                }
+            }
             WindowEvent::MouseInput { state, button, .. } => match state {
                 ElementState::Pressed => {
                     self.held_mouse_button = Some(button);
@@ -159,12 +391,42 @@ impl ApplicationHandler for DisplayState {
         }
     }
 
+    fn user_event(&mut self, _: &ActiveEventLoop, event: UserEvent) {
+        match event {
+            UserEvent::Model(model) => match &mut self.viewer {
+                Some(viewer) => viewer.handle_model_update(model),
+                // The viewer doesn't exist until the application is resumed.
+                // It picks up the model then.
+                None => self.model = Some(model),
+            },
+            #[cfg(target_arch = "wasm32")]
+            UserEvent::Viewer(viewer) => self.set_viewer(viewer),
+        }
+    }
+
     fn about_to_wait(&mut self, _: &ActiveEventLoop) {
         let Some(window) = &self.window else { return };
         window.window().request_redraw();
     }
 }
 
+/// Save an image of the current view, next to any previously saved ones
+#[cfg(not(target_arch = "wasm32"))]
+fn save_screenshot(viewer: &mut Viewer, size: ScreenSize) {
+    let path = (1..)
+        .map(|i| PathBuf::from(format!("fornjot-screenshot-{i}.png")))
+        .find(|path| !path.exists())
+        .expect("Should find a file name that is not taken");
+
+    let result = viewer.screenshot(size).and_then(|image| {
+        image.save(&path).map_err(fj_viewer::CaptureError::from)
+    });
+    match result {
+        Ok(()) => tracing::info!("Saved screenshot to `{}`", path.display()),
+        Err(err) => tracing::warn!("Failed to save screenshot: {err}"),
+    }
+}
+
 fn input_event(
     event: &WindowEvent,
     window: &Window,
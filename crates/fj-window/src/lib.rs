@@ -4,14 +4,24 @@
 //! split into multiple libraries that can be used semi-independently, and this
 //! is one of those.
 //!
-//! This library provides a window abstraction based on Winit.
+//! This library provides a window abstraction based on Winit. It also works on
+//! the web, when compiled to WebAssembly. The window is a canvas there, and
+//! functions that need threads or a file system are not available.
 //!
 //! [Fornjot]: https://www.fornjot.app/
 
+#[cfg(not(target_arch = "wasm32"))]
+mod capture;
 mod display;
 mod window;
 
 pub use self::{
-    display::{display, Error},
+    display::{display, display_models, Error, ModelSender, WindowClosed},
     window::WindowError,
 };
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::{
+    capture::{screenshot, turntable},
+    display::display_live,
+};
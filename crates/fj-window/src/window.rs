@@ -4,6 +4,7 @@ use fj_viewer::{Screen, ScreenSize};
 use winit::event_loop::ActiveEventLoop;
 
 /// A window that can be used with `fj-viewer`
+#[derive(Clone)]
 pub struct Window {
     inner: Arc<winit::window::Window>,
 }
@@ -11,8 +12,18 @@ pub struct Window {
 impl Window {
     /// Create an instance of `Window` from the given `EventLoop`
     pub fn new(event_loop: &ActiveEventLoop) -> Result<Self, WindowError> {
+        let attributes = winit::window::Window::default_attributes();
+
+        // On the web, the window is a canvas, which needs to be added to the
+        // document, to become visible.
+        #[cfg(target_arch = "wasm32")]
+        let attributes = {
+            use winit::platform::web::WindowAttributesExtWebSys;
+            attributes.with_append(true)
+        };
+
         let window = event_loop.create_window(
-            winit::window::Window::default_attributes()
+            attributes
                 .with_title("Fornjot")
                 .with_maximized(true)
                 // When the window decorations are enabled, I'm seeing the
@@ -17,6 +17,11 @@ use fj_math::Scalar;
 #[derive(clap::Parser)]
 pub struct Args {
     /// Export model to this path
+    ///
+    /// The model is exported without opening a window, which makes this
+    /// suitable for use in headless environments, like CI pipelines. Unless
+    /// validation errors are ignored, they cause the process to exit with a
+    /// non-zero exit code, and no file is written.
     #[arg(short, long, value_name = "PATH")]
     pub export: Option<PathBuf>,
 
@@ -27,6 +32,22 @@ pub struct Args {
     /// Ignore validation errors
     #[arg(short, long)]
     pub ignore_validation: bool,
+
+    /// Select the model with this name
+    ///
+    /// Only applies to model crates that define multiple models. If no model
+    /// is selected, all of them are displayed, and the first one is exported.
+    /// See [`Instance::process_named_models`].
+    ///
+    /// [`Instance::process_named_models`]: crate::Instance::process_named_models
+    #[arg(short, long, value_name = "NAME")]
+    pub model: Option<String>,
+
+    /// List the names of all models, instead of displaying or exporting one
+    ///
+    /// Only applies to model crates that define multiple models.
+    #[arg(short, long)]
+    pub list: bool,
 }
 
 impl Args {
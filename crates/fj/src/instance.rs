@@ -1,17 +1,23 @@
-use std::{error::Error as _, fmt};
+use std::{
+    error::Error as _,
+    fmt,
+    path::{Path, PathBuf},
+};
 
 use fj_core::{
     algorithms::{
         approx::{InvalidTolerance, Tolerance},
         bounding_volume::BoundingVolume,
-        triangulate::Triangulate,
+        triangulate::{Triangulate, TriangulateError},
     },
     validation::{ValidationConfig, ValidationErrors},
     Core,
 };
-use fj_interop::Model;
-use fj_math::{Aabb, Point, Scalar};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use fj_interop::{FailedFace, Model, ProgressSink};
+use fj_math::{Aabb, LengthUnit, Point, Scalar};
+use tracing_subscriber::{
+    fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt,
+};
 
 use crate::Args;
 
@@ -22,18 +28,55 @@ use crate::Args;
 pub struct Instance {
     /// The instance of the Fornjot core
     pub core: Core,
+
+    /// The unit that the model is measured in
+    ///
+    /// Defaults to millimeters. Exporters use this to write files in the unit
+    /// that their format expects.
+    pub unit: LengthUnit,
 }
 
 impl Instance {
     /// Construct an instance of `Instance`
+    ///
+    /// Also sets up logging, so anything the model logs while it is built is
+    /// shown. See [`Instance::process_model`].
     pub fn new() -> Self {
+        init_tracing();
         Self::default()
     }
 
     /// Construct an instance of `Instance`, using the provided configuration
+    ///
+    /// Also sets up logging, like [`Instance::new`].
     pub fn with_validation_config(config: ValidationConfig) -> Self {
+        init_tracing();
         let core = fj_core::Core::with_validation_config(config);
-        Self { core }
+        Self {
+            core,
+            unit: LengthUnit::default(),
+        }
+    }
+
+    /// Declare the unit that the model is measured in
+    pub fn with_unit(mut self, unit: LengthUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Report progress to the provided sink
+    ///
+    /// Long-running operations, like triangulation, report their progress to
+    /// it. So can the model itself, through `core.progress`. Use
+    /// [`TerminalProgress`] to show progress in the terminal.
+    ///
+    /// [`TerminalProgress`]: crate::TerminalProgress
+    pub fn with_progress(
+        mut self,
+        progress: impl ProgressSink + Send + Sync + 'static,
+    ) -> Self {
+        self.core.progress = Box::new(progress);
+        self
     }
 
     /// Export or display a model, according to CLI arguments
@@ -44,65 +87,328 @@ impl Instance {
     ///
     /// This function is used by Fornjot's own testing infrastructure, but is
     /// useful beyond that, when using Fornjot directly to define a model.
+    ///
+    /// Logging is configured through the `RUST_LOG` environment variable. To
+    /// find out why a model is slow to build, set it to `fj_core=debug`. This
+    /// logs how long each stage of the kernel's algorithms takes, and how many
+    /// objects it processed. `fj_core=trace` goes down to single faces and
+    /// validation checks. Events that the model itself logs through `tracing`
+    /// are shown too, if the filter includes them.
+    ///
+    /// If a model fails validation only for some parameter values, set the
+    /// `FJ_ARITHMETIC` environment variable to `exact`. This re-evaluates some
+    /// key computations using exact arithmetic, which tells rounding errors
+    /// apart from logic bugs. See [`Arithmetic`].
+    ///
+    /// [`Arithmetic`]: crate::math::Arithmetic
     pub fn process_model<M>(&mut self, model: &M) -> Result
     where
         for<'r> (&'r M, Tolerance): Triangulate,
         for<'r> &'r M: BoundingVolume<3>,
     {
-        tracing_subscriber::registry()
-            .with(tracing_subscriber::fmt::layer())
-            .with(tracing_subscriber::EnvFilter::from_default_env())
-            .init();
+        init_tracing();
+        let args = Args::parse();
+
+        self.process_model_with_args(model, args)
+    }
 
+    /// Export or display one of multiple named models, according to CLI
+    /// arguments
+    ///
+    /// Like [`Instance::process_model`], but for crates that define a family
+    /// of related models, like variants or sub-parts of a part. The model is
+    /// selected by name, using the `--model` argument. The `--list` argument
+    /// prints the names of all models instead.
+    ///
+    /// If no model is selected, the first one is exported. When displaying,
+    /// all models are shown in the same window, starting with the first one.
+    /// Pressing Tab switches to the next one, without restarting.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `models` is empty.
+    pub fn process_named_models<M>(&mut self, models: &[(&str, M)]) -> Result
+    where
+        for<'r> (&'r M, Tolerance): Triangulate,
+        for<'r> &'r M: BoundingVolume<3>,
+    {
+        init_tracing();
         let args = Args::parse();
 
+        if args.list {
+            for (name, _) in models {
+                println!("{name}");
+            }
+            return Ok(());
+        }
+
+        let model = match &args.model {
+            Some(selected) => models
+                .iter()
+                .find(|(name, _)| name == selected)
+                .map(|(_, model)| model)
+                .ok_or_else(|| Error::UnknownModel(selected.clone()))?,
+            None if args.export.is_none() && models.len() > 1 => {
+                return self.display_named_models(models, args);
+            }
+            None => {
+                let (_, model) =
+                    models.first().expect("Expected at least one model");
+                model
+            }
+        };
+
+        self.process_model_with_args(model, args)
+    }
+
+    fn display_named_models<M>(
+        &mut self,
+        models: &[(&str, M)],
+        args: Args,
+    ) -> Result
+    where
+        for<'r> (&'r M, Tolerance): Triangulate,
+        for<'r> &'r M: BoundingVolume<3>,
+    {
         if !args.ignore_validation {
             self.core.layers.validation.take_errors()?;
         }
 
-        let aabb = model.aabb(&self.core.layers.geometry).unwrap_or(Aabb {
-            min: Point::origin(),
-            max: Point::origin(),
-        });
+        let displayed = models
+            .iter()
+            .map(|(_, model)| self.displayed_model(model, args.tolerance))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
 
-        let tolerance = match args.tolerance {
-            None => {
-                // Compute a reasonable default for the tolerance value. To do
-                // this, we just look at the smallest non-zero extent of the
-                // bounding box and divide that by some value.
-
-                let mut min_extent = Scalar::MAX;
-                for extent in aabb.size().components {
-                    if extent > Scalar::ZERO && extent < min_extent {
-                        min_extent = extent;
-                    }
-                }
-
-                let tolerance = min_extent / Scalar::from_f64(1000.);
-                Tolerance::from_scalar(tolerance)?
-            }
+        let names = models
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        tracing::info!("Press Tab to switch between models: {names}");
+
+        crate::window::display_models(displayed, false)?;
+
+        Ok(())
+    }
+
+    fn process_model_with_args<M>(&mut self, model: &M, args: Args) -> Result
+    where
+        for<'r> (&'r M, Tolerance): Triangulate,
+        for<'r> &'r M: BoundingVolume<3>,
+    {
+        if !args.ignore_validation {
+            self.core.layers.validation.take_errors()?;
+        }
+
+        if let Some(path) = args.export {
+            return self.export_model(model, args.tolerance, &path);
+        }
+
+        let displayed = self.displayed_model(model, args.tolerance)?;
+        crate::window::display(displayed, false)?;
+
+        Ok(())
+    }
+
+    /// Export a model to the provided path
+    ///
+    /// This is what [`Instance::process_model`] does, if the `--export`
+    /// argument is passed. If no tolerance is provided, one is derived from the
+    /// size of the model. Validation errors are not checked.
+    pub fn export_model<M>(
+        &mut self,
+        model: &M,
+        tolerance: Option<Tolerance>,
+        path: &Path,
+    ) -> Result
+    where
+        for<'r> (&'r M, Tolerance): Triangulate,
+        for<'r> &'r M: BoundingVolume<3>,
+    {
+        let aabb = aabb_or_origin(model, &self.core);
+        let tolerance = match tolerance {
+            None => default_tolerance(&aabb)?,
             Some(user_defined_tolerance) => user_defined_tolerance,
         };
 
-        let mesh = (model, tolerance).triangulate(&mut self.core);
+        // The levels of detail are derived from this tolerance. Making it
+        // nested, makes sure they fit together with the full-detail mesh.
+        let tolerance = tolerance.nested();
 
-        if let Some(path) = args.export {
-            crate::export::export(&mesh, &path)?;
-            return Ok(());
+        let triangulation =
+            (model, tolerance).triangulate_partial(&mut self.core);
+        for failed in &triangulation.failed_faces {
+            tracing::warn!("Failed to triangulate face: {}", failed.error);
         }
 
-        let model = Model { mesh, aabb };
+        // An exported file with holes in it is worse than no file at all.
+        if let Some(failed) = triangulation.failed_faces.first() {
+            return Err(failed.error.clone().into());
+        }
 
-        crate::window::display(model, false)?;
+        crate::export::export_in_unit(&triangulation.mesh, self.unit, path)?;
+
+        Ok(())
+    }
+
+    /// Triangulate a model, for display in the viewer
+    ///
+    /// This is what [`Instance::process_model`] displays, if no other arguments
+    /// are passed. If no tolerance is provided, one is derived from the size of
+    /// the model. Validation errors are not checked.
+    pub fn displayed_model<M>(
+        &mut self,
+        model: &M,
+        tolerance: Option<Tolerance>,
+    ) -> std::result::Result<Model, Error>
+    where
+        for<'r> (&'r M, Tolerance): Triangulate,
+        for<'r> &'r M: BoundingVolume<3>,
+    {
+        let aabb = aabb_or_origin(model, &self.core);
+        let tolerance = match tolerance {
+            None => default_tolerance(&aabb)?,
+            Some(user_defined_tolerance) => user_defined_tolerance,
+        };
+
+        // The levels of detail are derived from this tolerance. Making it
+        // nested, makes sure they fit together with the full-detail mesh.
+        let tolerance = tolerance.nested();
+
+        let triangulation =
+            (model, tolerance).triangulate_partial(&mut self.core);
+        for failed in &triangulation.failed_faces {
+            tracing::warn!("Failed to triangulate face: {}", failed.error);
+        }
+
+        // Faces that failed to triangulate are missing from the mesh. The
+        // viewer highlights them, so the rest of the model can be inspected.
+        let failed_faces =
+            triangulation
+                .failed_faces
+                .into_iter()
+                .map(|failed| FailedFace {
+                    face: failed.face.id().into(),
+                    reason: failed.error.to_string(),
+                    boundary: failed
+                        .boundary
+                        .iter()
+                        .map(|segment| segment.points())
+                        .collect(),
+                });
+        let mut displayed = Model::new(triangulation.mesh, aabb)
+            .with_failed_faces(failed_faces);
+
+        // Coarser meshes keep big models responsive in the viewer, when they
+        // are viewed from a distance.
+        for level in 1..=NUM_LEVELS_OF_DETAIL {
+            let tolerance = tolerance.coarser(level);
+            let mesh =
+                (model, tolerance).triangulate_partial(&mut self.core).mesh;
+            displayed = displayed.with_level_of_detail(tolerance.inner(), mesh);
+        }
+
+        // Includes anything that was emitted while building the model, as
+        // well as during triangulation.
+        displayed = displayed.with_debug(self.core.layers.debug.take());
+
+        Ok(displayed)
+    }
+
+    /// Build and export multiple variants of a model
+    ///
+    /// Calls `build` once for each of the provided parameters, and exports the
+    /// resulting model to the path that `path` returns for those parameters.
+    /// This can be used to generate a range of sizes of a part, for example,
+    /// from a single invocation of the model.
+    ///
+    /// Each variant is triangulated with a tolerance that is derived from its
+    /// size, like [`Instance::process_model`] does by default. Validation
+    /// errors, or errors exporting a variant, abort the process.
+    pub fn export_variants<P, M>(
+        &mut self,
+        variants: impl IntoIterator<Item = P>,
+        mut build: impl FnMut(&P, &mut Core) -> M,
+        path: impl Fn(&P) -> PathBuf,
+    ) -> Result
+    where
+        for<'r> (&'r M, Tolerance): Triangulate,
+        for<'r> &'r M: BoundingVolume<3>,
+    {
+        for parameters in variants {
+            let model = build(&parameters, &mut self.core);
+            self.core.layers.validation.take_errors()?;
+
+            let aabb = aabb_or_origin(&model, &self.core);
+            let tolerance = default_tolerance(&aabb)?;
+
+            let mesh = (&model, tolerance).triangulate(&mut self.core);
+            crate::export::export_in_unit(
+                &mesh,
+                self.unit,
+                &path(&parameters),
+            )?;
+        }
 
         Ok(())
     }
 }
 
-/// Return value of [`Instance::process_model`]
+fn init_tracing() {
+    // The log output needs a clock and a terminal. Neither is available in the
+    // browser.
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+
+    // Logging has already been set up, if an instance has been constructed
+    // before, or if the model did it itself. Either way, there's nothing left
+    // to do.
+    let _ = tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_span_events(FmtSpan::CLOSE))
+        .with(tracing_subscriber::EnvFilter::from_default_env())
+        .try_init();
+}
+
+fn aabb_or_origin<M>(model: &M, core: &Core) -> Aabb<3>
+where
+    for<'r> &'r M: BoundingVolume<3>,
+{
+    model.aabb(&core.layers.geometry).unwrap_or(Aabb {
+        min: Point::origin(),
+        max: Point::origin(),
+    })
+}
+
+fn default_tolerance(
+    aabb: &Aabb<3>,
+) -> std::result::Result<Tolerance, InvalidTolerance> {
+    // Compute a reasonable default for the tolerance value. To do this, we
+    // just look at the smallest non-zero extent of the bounding box and divide
+    // that by some value.
+
+    let mut min_extent = Scalar::MAX;
+    for extent in aabb.size().components {
+        if extent > Scalar::ZERO && extent < min_extent {
+            min_extent = extent;
+        }
+    }
+
+    let tolerance = min_extent / Scalar::from_f64(1000.);
+    Tolerance::from_scalar(tolerance)
+}
+
+/// The number of coarser levels of detail, in addition to the full-detail mesh
+///
+/// See [`Tolerance::coarser`].
+const NUM_LEVELS_OF_DETAIL: u32 = 3;
+
+/// Return value of [`Instance::process_model`] and
+/// [`Instance::export_variants`]
 pub type Result = std::result::Result<(), Error>;
 
-/// Error returned by [`Instance::process_model`]
+/// Error returned by [`Instance::process_model`] and
+/// [`Instance::export_variants`]
 #[derive(thiserror::Error)]
 pub enum Error {
     /// Failed to set up logger
@@ -124,6 +430,14 @@ pub enum Error {
     /// Unhandled validation errors
     #[error(transparent)]
     Validation(#[from] ValidationErrors),
+
+    /// A face failed to triangulate
+    #[error("Failed to triangulate model")]
+    Triangulate(#[from] TriangulateError),
+
+    /// The selected model doesn't exist
+    #[error("No model named `{0}`; use `--list` to see all models")]
+    UnknownModel(String),
 }
 
 impl fmt::Debug for Error {
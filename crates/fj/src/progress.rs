@@ -0,0 +1,48 @@
+use std::io::{self, Write};
+
+use fj_interop::{Progress, ProgressSink};
+
+/// A [`ProgressSink`] that shows progress in the terminal
+///
+/// Prints the progress of the current task to stderr, updating it in place, so
+/// long-running models don't appear to hang. Model code can report its own
+/// progress through the same sink, using [`ProgressSink::report`] or
+/// [`ProgressSink::step`] on `core.progress`.
+///
+/// Install it with [`Instance::with_progress`].
+///
+/// [`Instance::with_progress`]: crate::Instance::with_progress
+#[derive(Debug, Default)]
+pub struct TerminalProgress {
+    current: Option<(&'static str, usize)>,
+}
+
+impl TerminalProgress {
+    /// Create an instance of `TerminalProgress`
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ProgressSink for TerminalProgress {
+    fn report(&mut self, progress: Progress) {
+        // Operations can report progress very often. Only print, if there is
+        // something new to show.
+        let percent = (progress.fraction() * 100.) as usize;
+        if self.current == Some((progress.task, percent)) {
+            return;
+        }
+        self.current = Some((progress.task, percent));
+
+        let Progress { task, done, total } = progress;
+        let mut stderr = io::stderr().lock();
+
+        // Nothing we can do, if writing to stderr fails. And it's not worth
+        // aborting the operation over.
+        let _ = write!(stderr, "\r{task}: {done}/{total} ({percent}%)");
+        if done >= total {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    }
+}
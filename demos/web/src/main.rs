@@ -0,0 +1,15 @@
+fn main() -> fj::Result {
+    // Without this, panics only show up as `unreachable` in the browser
+    // console, without any message.
+    #[cfg(target_arch = "wasm32")]
+    console_error_panic_hook::set_once();
+
+    let mut fj = fj::Instance::new();
+    let model = spacer::model(1., 0.5, 1., &mut fj.core);
+    fj.core.layers.validation.take_errors()?;
+
+    let model = fj.displayed_model(&model, None)?;
+    fj::window::display(model, false)?;
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 fn main() -> fj::Result {
-    let mut fj = fj::Instance::new();
+    let mut fj = fj::Instance::new().with_progress(fj::TerminalProgress::new());
     let model = all::model(&mut fj.core);
     fj.process_model(&model)?;
     Ok(())
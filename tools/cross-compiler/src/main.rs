@@ -13,20 +13,35 @@ fn main() -> anyhow::Result<()> {
     let targets = [
         Target {
             triple: "aarch64-apple-ios",
-            crates: &["fj-core", "fj-export", "fj-interop", "fj-math"],
+            crates: &[
+                "fj-core",
+                "fj-export",
+                "fj-import",
+                "fj-interop",
+                "fj-math",
+            ],
         },
         Target {
             triple: "aarch64-linux-android",
-            crates: &["fj-core", "fj-export", "fj-interop", "fj-math"],
+            crates: &[
+                "fj-core",
+                "fj-export",
+                "fj-import",
+                "fj-interop",
+                "fj-math",
+            ],
         },
         Target {
             triple: "wasm32-unknown-unknown",
             crates: &[
                 "fj-core",
                 "fj-export",
+                "fj-import",
                 "fj-interop",
                 "fj-math",
                 "fj-viewer",
+                "fj-window",
+                "web-demo",
             ],
         },
     ];